@@ -23,12 +23,13 @@ use crate::functions::{display_all_functions, Function};
 use crate::print_format::PrintFormat;
 use crate::print_options::PrintOptions;
 use clap::ValueEnum;
-use datafusion::arrow::array::{ArrayRef, StringArray};
+use datafusion::arrow::array::{ArrayRef, StringArray, UInt64Array};
 use datafusion::arrow::datatypes::{DataType, Field, Schema};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::exec_err;
 use datafusion::common::instant::Instant;
 use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::cache::byte_range_cache::ByteRangeCacheStats;
 use std::fs::File;
 use std::io::BufReader;
 use std::str::FromStr;
@@ -46,6 +47,7 @@ pub enum Command {
     SearchFunctions(String),
     QuietMode(Option<bool>),
     OutputFormat(Option<String>),
+    ObjectStoreCache,
 }
 
 pub enum OutputFormat {
@@ -115,6 +117,21 @@ impl Command {
             Self::OutputFormat(_) => exec_err!(
                 "Unexpected change output format, this should be handled outside"
             ),
+            Self::ObjectStoreCache => {
+                let now = Instant::now();
+                let Some(cache) = ctx
+                    .task_ctx()
+                    .runtime_env()
+                    .cache_manager
+                    .get_object_store_cache()
+                else {
+                    return exec_err!(
+                        "Object store cache is disabled, see --object-store-cache-memory-limit"
+                    );
+                };
+                let stats_batch = object_store_cache_stats(&cache.stats());
+                print_options.print_batches(stats_batch.schema(), &[stats_batch], now)
+            }
         }
     }
 
@@ -133,11 +150,12 @@ impl Command {
             Self::OutputFormat(_) => {
                 ("\\pset [NAME [VALUE]]", "set table output option\n(format)")
             }
+            Self::ObjectStoreCache => ("\\cache", "show object store cache statistics"),
         }
     }
 }
 
-const ALL_COMMANDS: [Command; 9] = [
+const ALL_COMMANDS: [Command; 10] = [
     Command::ListTables,
     Command::DescribeTableStmt(String::new()),
     Command::Quit,
@@ -147,6 +165,7 @@ const ALL_COMMANDS: [Command; 9] = [
     Command::SearchFunctions(String::new()),
     Command::QuietMode(None),
     Command::OutputFormat(None),
+    Command::ObjectStoreCache,
 ];
 
 fn all_commands_info() -> RecordBatch {
@@ -168,6 +187,34 @@ fn all_commands_info() -> RecordBatch {
     .expect("This should not fail")
 }
 
+fn object_store_cache_stats(stats: &ByteRangeCacheStats) -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("Statistic", DataType::Utf8, false),
+        Field::new("Value", DataType::UInt64, false),
+    ]));
+    let (names, values): (Vec<&str>, Vec<u64>) = [
+        ("memory_hits", stats.memory_hits),
+        ("disk_hits", stats.disk_hits),
+        ("misses", stats.misses),
+        ("memory_entries", stats.memory_entries),
+        ("memory_bytes", stats.memory_bytes),
+        ("disk_entries", stats.disk_entries),
+        ("disk_bytes", stats.disk_bytes),
+        ("evictions", stats.evictions),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name, value as u64))
+    .unzip();
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(StringArray::from(names)),
+            Arc::new(UInt64Array::from(values)),
+        ],
+    )
+    .expect("This should not fail")
+}
+
 impl FromStr for Command {
     type Err = ();
 
@@ -197,6 +244,7 @@ impl FromStr for Command {
                 Self::OutputFormat(Some(subcommand.to_string()))
             }
             ("pset", None) => Self::OutputFormat(None),
+            ("cache", None) => Self::ObjectStoreCache,
             _ => return Err(()),
         })
     }
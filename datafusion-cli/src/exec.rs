@@ -298,6 +298,7 @@ fn config_file_type_from_str(ext: &str) -> Option<ConfigFileType> {
         "csv" => Some(ConfigFileType::CSV),
         "json" => Some(ConfigFileType::JSON),
         "parquet" => Some(ConfigFileType::PARQUET),
+        "avro" => Some(ConfigFileType::AVRO),
         _ => None,
     }
 }
@@ -22,6 +22,7 @@ use std::process::ExitCode;
 use std::sync::{Arc, OnceLock};
 
 use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::cache::byte_range_cache::ByteRangeCacheConfig;
 use datafusion::execution::context::SessionConfig;
 use datafusion::execution::memory_pool::{FairSpillPool, GreedyMemoryPool};
 use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
@@ -123,6 +124,21 @@ struct Args {
 
     #[clap(long, help = "Enables console syntax highlighting")]
     color: bool,
+
+    #[clap(
+        long,
+        help = "Cache byte ranges read from object stores in memory, up to the given size (e.g. '1g'), default to None (no cache). Cached ranges of a file are dropped when listing it shows that it changed",
+        value_parser(extract_memory_pool_size)
+    )]
+    object_store_cache_memory_limit: Option<usize>,
+
+    #[clap(
+        long,
+        help = "Move the byte ranges evicted from the object store cache to local disk, up to the given size (e.g. '10g')",
+        value_parser(extract_memory_pool_size),
+        requires = "object_store_cache_memory_limit"
+    )]
+    object_store_cache_disk_limit: Option<usize>,
 }
 
 #[tokio::main]
@@ -171,6 +187,14 @@ async fn main_inner() -> Result<()> {
             rt_config
         };
 
+    let rt_config = match args.object_store_cache_memory_limit {
+        Some(memory_limit) => rt_config.with_object_store_cache(
+            ByteRangeCacheConfig::new(memory_limit)
+                .with_disk_limit(args.object_store_cache_disk_limit.unwrap_or(0)),
+        ),
+        None => rt_config,
+    };
+
     let runtime_env = create_runtime_env(rt_config.clone())?;
 
     // enable dynamic file query
@@ -24,8 +24,8 @@ use crate::session::Session;
 use arrow_schema::SchemaRef;
 use async_trait::async_trait;
 use datafusion_common::Result;
-use datafusion_common::{not_impl_err, Constraints, Statistics};
-use datafusion_expr::dml::InsertOp;
+use datafusion_common::{not_impl_err, Constraints, DFSchemaRef, Statistics};
+use datafusion_expr::dml::{InsertOp, MergeOp};
 use datafusion_expr::{
     CreateExternalTable, Expr, LogicalPlan, TableProviderFilterPushDown, TableType,
 };
@@ -279,6 +279,70 @@ pub trait TableProvider: Debug + Sync + Send {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         not_impl_err!("Insert into not implemented for this table")
     }
+
+    /// Return an [`ExecutionPlan`] to delete the rows of this table that
+    /// match all of the `filters`, if supported.
+    ///
+    /// The `filters` refer to the columns of the table without qualifiers.
+    /// A row matches a filter if the filter evaluates to `true` for it, and
+    /// all rows are deleted if there are no filters.
+    ///
+    /// The returned plan should return a single row in a UInt64 column
+    /// called "count" with the number of deleted rows, the same way as
+    /// [`Self::insert_into`].
+    async fn delete_from(
+        &self,
+        _state: &dyn Session,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Delete from not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to update the rows of this table that
+    /// match all of the `filters`, if supported.
+    ///
+    /// Each of the `assignments` is the name of a column and the expression
+    /// computing its new value from the current values of the row. Columns
+    /// without an assignment keep their value. The expressions have already
+    /// been cast to the type of their column, and like the `filters` refer to
+    /// the columns of the table without qualifiers. A row matches a filter if
+    /// the filter evaluates to `true` for it, and all rows are updated if
+    /// there are no filters.
+    ///
+    /// The returned plan should return a single row in a UInt64 column
+    /// called "count" with the number of updated rows, the same way as
+    /// [`Self::insert_into`].
+    async fn update(
+        &self,
+        _state: &dyn Session,
+        _assignments: Vec<(String, Expr)>,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Update not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to merge the rows of `source` into this
+    /// table, if supported.
+    ///
+    /// The expressions of `merge` are evaluated against `schema`, which has
+    /// the columns of the table followed by the columns of `source`. Each row
+    /// of the table and each row of `source` is changed by the first clause of
+    /// `merge` that applies to it, and rows no clause applies to are left
+    /// alone. Insert values and assignments have already been cast to the
+    /// type of their column.
+    ///
+    /// The returned plan should return a single row in a UInt64 column
+    /// called "count" with the number of inserted, updated and deleted rows,
+    /// the same way as [`Self::insert_into`].
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        _source: Arc<dyn ExecutionPlan>,
+        _schema: DFSchemaRef,
+        _merge: MergeOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Merge into not implemented for this table")
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
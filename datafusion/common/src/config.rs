@@ -298,6 +298,14 @@ config_namespace! {
         /// Number of files to read in parallel when inferring schema and statistics
         pub meta_fetch_concurrency: usize, default = 32
 
+        /// Maximum number of buckets of the histograms computed by `ANALYZE TABLE`
+        /// for each column. Setting it to 0 disables histograms
+        pub analyze_histogram_buckets: usize, default = 32
+
+        /// Maximum number of most common values, with their frequency, recorded by
+        /// `ANALYZE TABLE` for each column. Setting it to 0 disables them
+        pub analyze_most_common_values: usize, default = 10
+
         /// Guarantees a minimum level of output files running in parallel.
         /// RecordBatches will be distributed in round robin fashion to each
         /// parallel writer. Each writer is closed and a new file opened once
@@ -355,6 +363,27 @@ config_namespace! {
         /// in joins can reduce memory usage when joining large
         /// tables with a highly-selective join filter, but is also slightly slower.
         pub enforce_batch_size_in_joins: bool, default = false
+
+        /// Compression codec for the Arrow IPC files written by operators
+        /// spilling to disk (sorts, aggregations and joins). Valid values are:
+        /// uncompressed, lz4_frame and zstd. Compressing spill files trades CPU
+        /// for less disk I/O and space
+        pub spill_compression: SpillCompression, default = SpillCompression::Uncompressed
+
+        /// Maximum number of bytes the spill files of a single query may use on
+        /// disk at the same time. Spilling beyond this quota fails the query with
+        /// a resources exhausted error. If not set, spilling is only limited by
+        /// the `DiskManager` of the runtime environment
+        pub max_spill_disk_size: Option<usize>, default = None
+
+        /// Maximum number of bytes of memory a single query may reserve at the
+        /// same time, drawn from the memory pool of the runtime environment
+        /// shared by all queries. Reserving more memory makes the query spill to
+        /// disk or fail with a resources exhausted error. If a memory admission
+        /// queue is configured, queries also wait for this amount of memory to be
+        /// available before they start. If not set, a query may use the whole
+        /// memory pool
+        pub query_memory_limit: Option<usize>, default = None
     }
 }
 
@@ -624,6 +653,27 @@ config_namespace! {
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold_rows: usize, default = 1024 * 128
 
+        /// When set to true, the physical plan optimizer will make HashJoin publish
+        /// the bounds of its build side join keys as a filter on the probe side
+        /// ParquetExec, which uses it to prune row groups, pages and (if
+        /// `pushdown_filters` is enabled) rows while the query is executing
+        pub enable_dynamic_join_filter: bool, default = false
+
+        /// The maximum number of distinct build side join keys that a dynamic
+        /// join filter lists explicitly. Above that, only the bounds of the keys are used
+        pub dynamic_join_filter_max_distinct_values: usize, default = 20
+
+        /// When set to true, the physical plan optimizer will reorder trees of inner
+        /// HashJoins based on the estimated cardinality of their intermediate results,
+        /// which requires row counts and join key min/max statistics for the inputs
+        /// (see `datafusion.execution.collect_statistics`)
+        pub enable_join_reordering: bool, default = false
+
+        /// The maximum number of joined inputs for which join reordering searches all
+        /// possible join orders. Larger join trees are ordered greedily instead, as the
+        /// cost of the exhaustive search grows exponentially with the number of inputs
+        pub join_reordering_dp_max_relations: usize, default = 10
+
         /// The default filter selectivity used by Filter Statistics
         /// when an exact selectivity cannot be determined. Valid values are
         /// between 0 (no selectivity) and 100 (all rows are selected).
@@ -636,6 +686,12 @@ config_namespace! {
         /// then the output will be coerced to a non-view.
         /// Coerces `Utf8View` to `LargeUtf8`, and `BinaryView` to `LargeBinary`.
         pub expand_views_at_output: bool, default = false
+
+        /// When set to true, the logical plan optimizer will rewrite queries to read
+        /// from a materialized view whose definition computes a superset of the rows
+        /// they need. The contents of the view as of its last refresh are used, even
+        /// if its sources have changed since
+        pub enable_materialized_view_rewrite: bool, default = false
     }
 }
 
@@ -1039,6 +1095,56 @@ impl ConfigField for CompressionTypeVariant {
     }
 }
 
+/// Compression codec used for the Arrow IPC files written when spilling
+/// intermediate data to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpillCompression {
+    /// LZ4 frame compression, fast with a moderate compression ratio
+    Lz4Frame,
+    /// Zstandard compression, slower with a higher compression ratio
+    Zstd,
+    /// Spill files are not compressed
+    #[default]
+    Uncompressed,
+}
+
+impl FromStr for SpillCompression {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "lz4_frame" => Ok(Self::Lz4Frame),
+            "zstd" => Ok(Self::Zstd),
+            "" | "uncompressed" => Ok(Self::Uncompressed),
+            other => _config_err!(
+                "Invalid spill compression: {other}. Expected one of: uncompressed, lz4_frame, zstd"
+            ),
+        }
+    }
+}
+
+impl Display for SpillCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Lz4Frame => "lz4_frame",
+            Self::Zstd => "zstd",
+            Self::Uncompressed => "uncompressed",
+        };
+        write!(f, "{str}")
+    }
+}
+
+impl ConfigField for SpillCompression {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = SpillCompression::from_str(value)?;
+        Ok(())
+    }
+}
+
 /// An implementation trait used to recursively walk configuration
 pub trait Visit {
     fn some<V: Display>(&mut self, key: &str, value: V, description: &'static str);
@@ -1180,6 +1286,7 @@ pub enum ConfigFileType {
     #[cfg(feature = "parquet")]
     PARQUET,
     JSON,
+    AVRO,
 }
 
 /// Represents the configuration options available for handling different table formats within a data processing application.
@@ -1198,6 +1305,10 @@ pub struct TableOptions {
     /// Configuration options for JSON file handling.
     pub json: JsonOptions,
 
+    /// Configuration options for Avro file handling, such as the codec used
+    /// to compress written files.
+    pub avro: AvroOptions,
+
     /// The current file format that the table operations should assume. This option allows
     /// for dynamic switching between the supported file types (e.g., CSV, Parquet, JSON).
     pub current_format: Option<ConfigFileType>,
@@ -1221,11 +1332,13 @@ impl ConfigField for TableOptions {
                 ConfigFileType::PARQUET => self.parquet.visit(v, "format", ""),
                 ConfigFileType::CSV => self.csv.visit(v, "format", ""),
                 ConfigFileType::JSON => self.json.visit(v, "format", ""),
+                ConfigFileType::AVRO => self.avro.visit(v, "format", ""),
             }
         } else {
             self.csv.visit(v, "csv", "");
             self.parquet.visit(v, "parquet", "");
             self.json.visit(v, "json", "");
+            self.avro.visit(v, "avro", "");
         }
     }
 
@@ -1257,6 +1370,7 @@ impl ConfigField for TableOptions {
                     ConfigFileType::PARQUET => self.parquet.set(rem, value),
                     ConfigFileType::CSV => self.csv.set(rem, value),
                     ConfigFileType::JSON => self.json.set(rem, value),
+                    ConfigFileType::AVRO => self.avro.set(rem, value),
                 }
             }
             _ => _config_err!("Config value \"{key}\" not found on TableOptions"),
@@ -1777,6 +1891,16 @@ config_namespace! {
     }
 }
 
+config_namespace! {
+    /// Options controlling Avro format
+    pub struct AvroOptions {
+        /// Codec used to compress the data blocks of written Avro files.
+        /// Valid values are: null, deflate, snappy, bzip2, xz and zstandard.
+        /// These values are not case sensitive.
+        pub codec: String, default = "null".to_string()
+    }
+}
+
 pub trait FormatOptionsExt: Display {}
 
 #[derive(Debug, Clone, PartialEq)]
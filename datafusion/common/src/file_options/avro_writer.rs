@@ -17,5 +17,39 @@
 
 //! Options related to how avro files should be written
 
+use crate::{
+    config::AvroOptions,
+    error::{DataFusionError, Result},
+};
+
+/// The codecs that can be used to compress the data blocks of Avro files
+const AVRO_CODECS: [&str; 6] = ["null", "deflate", "snappy", "bzip2", "xz", "zstandard"];
+
+/// Options for writing Avro files
 #[derive(Clone, Debug)]
-pub struct AvroWriterOptions {}
+pub struct AvroWriterOptions {
+    /// Name of the codec used to compress data blocks, see [`AvroOptions::codec`]
+    pub codec: String,
+}
+
+impl AvroWriterOptions {
+    pub fn new(codec: String) -> Self {
+        Self { codec }
+    }
+}
+
+impl TryFrom<&AvroOptions> for AvroWriterOptions {
+    type Error = DataFusionError;
+
+    fn try_from(value: &AvroOptions) -> Result<Self> {
+        let codec = value.codec.to_lowercase();
+        if !AVRO_CODECS.contains(&codec.as_str()) {
+            return Err(DataFusionError::Configuration(format!(
+                "Unknown or unsupported avro codec {}. Valid values are: {}",
+                value.codec,
+                AVRO_CODECS.join(", ")
+            )));
+        }
+        Ok(AvroWriterOptions { codec })
+    }
+}
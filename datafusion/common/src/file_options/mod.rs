@@ -33,7 +33,10 @@ mod tests {
     use super::parquet_writer::ParquetWriterOptions;
     use crate::{
         config::{ConfigFileType, TableOptions},
-        file_options::{csv_writer::CsvWriterOptions, json_writer::JsonWriterOptions},
+        file_options::{
+            avro_writer::AvroWriterOptions, csv_writer::CsvWriterOptions,
+            json_writer::JsonWriterOptions,
+        },
         parsers::CompressionTypeVariant,
         Result,
     };
@@ -314,4 +317,23 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    // for StatementOptions
+    fn test_writeroptions_avro_from_statement_options() -> Result<()> {
+        let mut option_map: HashMap<String, String> = HashMap::new();
+        option_map.insert("format.codec".to_owned(), "Snappy".to_owned());
+
+        let mut table_config = TableOptions::new();
+        table_config.set_config_format(ConfigFileType::AVRO);
+        table_config.alter_with_string_hash_map(&option_map)?;
+
+        let avro_options = AvroWriterOptions::try_from(&table_config.avro)?;
+        assert_eq!(avro_options.codec, "snappy");
+
+        table_config.set("format.codec", "lzo")?;
+        assert!(AvroWriterOptions::try_from(&table_config.avro).is_err());
+
+        Ok(())
+    }
 }
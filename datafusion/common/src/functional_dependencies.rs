@@ -333,6 +333,16 @@ impl FunctionalDependencies {
                 left_func_dependencies.extend(right_func_dependencies);
                 left_func_dependencies
             }
+            JoinType::AsOf => {
+                // Each left row appears once, joined with a right row that may
+                // appear multiple times and may be NULL:
+                right_func_dependencies.add_offset(left_cols_len);
+                right_func_dependencies =
+                    right_func_dependencies.with_dependency(Dependency::Multi);
+                right_func_dependencies.downgrade_dependencies();
+                left_func_dependencies.extend(right_func_dependencies);
+                left_func_dependencies
+            }
             JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
                 // These joins preserve functional dependencies of the left side:
                 left_func_dependencies
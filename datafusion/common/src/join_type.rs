@@ -58,6 +58,15 @@ pub enum JoinType {
     ///
     /// [1]: http://btw2017.informatik.uni-stuttgart.de/slidesandpapers/F1-10-37/paper_web.pdf
     LeftMark,
+    /// As-of join
+    ///
+    /// Returns one record for each record from the left input, joined with the nearest record
+    /// from the right input that has the same join keys and satisfies the inequality of the join
+    /// filter, called the match condition. For example, with the match condition
+    /// `left.ts >= right.ts`, a record is joined with the right record with the greatest `ts`
+    /// that is not greater than its own. Records without such a match are joined with nulls,
+    /// like in a left join.
+    AsOf,
 }
 
 impl JoinType {
@@ -78,6 +87,7 @@ impl Display for JoinType {
             JoinType::LeftAnti => "LeftAnti",
             JoinType::RightAnti => "RightAnti",
             JoinType::LeftMark => "LeftMark",
+            JoinType::AsOf => "AsOf",
         };
         write!(f, "{join_type}")
     }
@@ -98,6 +108,7 @@ impl FromStr for JoinType {
             "LEFTANTI" => Ok(JoinType::LeftAnti),
             "RIGHTANTI" => Ok(JoinType::RightAnti),
             "LEFTMARK" => Ok(JoinType::LeftMark),
+            "ASOF" => Ok(JoinType::AsOf),
             _ => _not_impl_err!("The join type {s} does not exist or is not implemented"),
         }
     }
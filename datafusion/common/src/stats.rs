@@ -18,6 +18,7 @@
 //! This module provides data structures to represent statistics
 
 use std::fmt::{self, Debug, Display};
+use std::sync::Arc;
 
 use crate::{Result, ScalarValue};
 
@@ -389,6 +390,16 @@ impl Display for Statistics {
                 } else {
                     s
                 };
+                let s = match &cs.histogram {
+                    Some(histogram) => {
+                        format!("{} Buckets={}", s, histogram.buckets().len())
+                    }
+                    None => s,
+                };
+                let s = match &cs.most_common_values {
+                    Some(mcv) => format!("{} MCV={}", s, mcv.values().len()),
+                    None => s,
+                };
 
                 s + ")"
             })
@@ -416,6 +427,11 @@ pub struct ColumnStatistics {
     pub min_value: Precision<ScalarValue>,
     /// Number of distinct values
     pub distinct_count: Precision<usize>,
+    /// Histogram of the non null values of the column which are not among
+    /// its [`Self::most_common_values`]
+    pub histogram: Option<Arc<Histogram>>,
+    /// Most common non null values of the column
+    pub most_common_values: Option<Arc<MostCommonValues>>,
 }
 
 impl ColumnStatistics {
@@ -437,6 +453,8 @@ impl ColumnStatistics {
             max_value: Precision::Absent,
             min_value: Precision::Absent,
             distinct_count: Precision::Absent,
+            histogram: None,
+            most_common_values: None,
         }
     }
 
@@ -450,6 +468,342 @@ impl ColumnStatistics {
         self.distinct_count = self.distinct_count.to_inexact();
         self
     }
+
+    /// Returns true if the column has a [`Histogram`] or [`MostCommonValues`]
+    /// to estimate the selectivity of predicates from.
+    pub fn has_distribution(&self) -> bool {
+        self.distribution_num_rows().is_some()
+    }
+
+    /// Estimates the fraction of the non null values of the column that are
+    /// equal to `value`.
+    ///
+    /// Returns `None` if the column has no [`Histogram`] nor
+    /// [`MostCommonValues`], or if `value` can not be compared with them.
+    pub fn estimate_eq_selectivity(&self, value: &ScalarValue) -> Option<f64> {
+        let total = self.distribution_num_rows()?;
+        if value.is_null() {
+            return Some(0.0);
+        }
+        Some((self.estimate_eq_rows(value)? / total).clamp(0.0, 1.0))
+    }
+
+    /// Estimates the fraction of the non null values of the column that are
+    /// less than `value`, or equal to it if `inclusive` is true.
+    ///
+    /// Returns `None` if the column has no [`Histogram`] nor
+    /// [`MostCommonValues`], or if `value` can not be compared with them.
+    pub fn estimate_lt_selectivity(
+        &self,
+        value: &ScalarValue,
+        inclusive: bool,
+    ) -> Option<f64> {
+        let total = self.distribution_num_rows()?;
+        if value.is_null() {
+            return Some(0.0);
+        }
+        let mut rows = 0.0;
+        if let Some(mcv) = &self.most_common_values {
+            if !mcv.is_comparable(value) {
+                return None;
+            }
+            rows += mcv
+                .values()
+                .iter()
+                .filter(|(v, _)| v < value || (inclusive && v == value))
+                .map(|(_, count)| *count as f64)
+                .sum::<f64>();
+        }
+        if let Some(histogram) = &self.histogram {
+            rows += histogram.estimate_lt(value, inclusive)?;
+        }
+        Some((rows / total).clamp(0.0, 1.0))
+    }
+
+    /// Estimates the fraction of the pairs of non null values of this column
+    /// and of `other` which are equal, i.e. the selectivity of an equi-join
+    /// on the two columns.
+    ///
+    /// Returns `None` unless both columns have a [`Histogram`] or
+    /// [`MostCommonValues`], and their values can be compared.
+    pub fn estimate_join_selectivity(&self, other: &Self) -> Option<f64> {
+        let total = self.distribution_num_rows()? * other.distribution_num_rows()?;
+        let mut matches = 0.0;
+        if let Some(mcv) = &self.most_common_values {
+            for (value, count) in mcv.values() {
+                matches += *count as f64 * other.estimate_eq_rows(value)?;
+            }
+        }
+        if let Some(mcv) = &other.most_common_values {
+            for (value, count) in mcv.values() {
+                let in_mcv = self
+                    .most_common_values
+                    .as_ref()
+                    .is_some_and(|mcv| mcv.get(value).is_some());
+                if !in_mcv {
+                    if let Some(histogram) = &self.histogram {
+                        matches += *count as f64 * histogram.estimate_eq(value)?;
+                    }
+                }
+            }
+        }
+        if let (Some(lhs), Some(rhs)) = (&self.histogram, &other.histogram) {
+            matches += lhs.estimate_join_matches(rhs)?;
+        }
+        Some((matches / total).clamp(0.0, 1.0))
+    }
+
+    /// Estimated number of rows equal to `value`, from the most common values
+    /// if it is one of them or from the histogram otherwise.
+    fn estimate_eq_rows(&self, value: &ScalarValue) -> Option<f64> {
+        if let Some(mcv) = &self.most_common_values {
+            if !mcv.is_comparable(value) {
+                return None;
+            }
+            if let Some(count) = mcv.get(value) {
+                return Some(count as f64);
+            }
+        }
+        match &self.histogram {
+            Some(histogram) => histogram.estimate_eq(value),
+            None => Some(0.0),
+        }
+    }
+
+    /// Number of rows described by the histogram and the most common values,
+    /// if there are any.
+    fn distribution_num_rows(&self) -> Option<f64> {
+        let histogram_rows = self.histogram.as_ref().map(|h| h.num_rows());
+        let mcv_rows = self.most_common_values.as_ref().map(|m| m.num_rows());
+        match (histogram_rows, mcv_rows) {
+            (None, None) => None,
+            (h, m) => match h.unwrap_or(0) + m.unwrap_or(0) {
+                0 => None,
+                rows => Some(rows as f64),
+            },
+        }
+    }
+}
+
+/// An equi-depth histogram of the values of a column: the sorted values are
+/// split into buckets holding about the same number of rows.
+///
+/// Buckets may overlap, which is the case when the histograms of several
+/// files are combined with [`Histogram::merge`]. Estimates are the sum of the
+/// estimates of all buckets.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Histogram {
+    buckets: Vec<HistogramBucket>,
+}
+
+/// A bucket of a [`Histogram`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistogramBucket {
+    /// Smallest value of the bucket
+    pub lower: ScalarValue,
+    /// Largest value of the bucket
+    pub upper: ScalarValue,
+    /// Number of rows with a value in the bucket
+    pub num_rows: usize,
+    /// Number of distinct values in the bucket
+    pub distinct_count: usize,
+}
+
+impl Histogram {
+    /// Creates a histogram from its buckets
+    pub fn new(buckets: Vec<HistogramBucket>) -> Self {
+        Self { buckets }
+    }
+
+    /// The buckets of the histogram
+    pub fn buckets(&self) -> &[HistogramBucket] {
+        &self.buckets
+    }
+
+    /// Total number of rows in the histogram
+    pub fn num_rows(&self) -> usize {
+        self.buckets.iter().map(|b| b.num_rows).sum()
+    }
+
+    /// Combines the histograms of two disjoint sets of rows
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut buckets = self.buckets.clone();
+        buckets.extend(other.buckets.iter().cloned());
+        buckets.sort_by(|a, b| {
+            a.lower
+                .partial_cmp(&b.lower)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Self { buckets }
+    }
+
+    /// Estimates the number of rows equal to `value`, or returns `None` if
+    /// `value` can not be compared with the bucket bounds.
+    pub fn estimate_eq(&self, value: &ScalarValue) -> Option<f64> {
+        let mut rows = 0.0;
+        for bucket in &self.buckets {
+            if bucket.contains(value)? {
+                rows += bucket.rows_per_value();
+            }
+        }
+        Some(rows)
+    }
+
+    /// Estimates the number of rows less than `value`, or equal to it if
+    /// `inclusive` is true. Returns `None` if `value` can not be compared
+    /// with the bucket bounds.
+    pub fn estimate_lt(&self, value: &ScalarValue, inclusive: bool) -> Option<f64> {
+        let mut rows = 0.0;
+        for bucket in &self.buckets {
+            rows += bucket.estimate_lt(value, inclusive)?;
+        }
+        Some(rows)
+    }
+
+    /// Estimates the number of pairs of equal values between the rows of this
+    /// histogram and of `other`, assuming the values of the buckets are
+    /// uniformly distributed and that the values of the bucket with fewer
+    /// distinct values in an overlap all appear in the other bucket.
+    pub fn estimate_join_matches(&self, other: &Self) -> Option<f64> {
+        let mut matches = 0.0;
+        for lhs in &self.buckets {
+            for rhs in &other.buckets {
+                let lower = if lhs.lower.partial_cmp(&rhs.lower)?.is_ge() {
+                    &lhs.lower
+                } else {
+                    &rhs.lower
+                };
+                let upper = if lhs.upper.partial_cmp(&rhs.upper)?.is_le() {
+                    &lhs.upper
+                } else {
+                    &rhs.upper
+                };
+                if lower.partial_cmp(upper)?.is_gt() {
+                    continue;
+                }
+                let lhs_fraction = lhs.fraction_between(lower, upper);
+                let rhs_fraction = rhs.fraction_between(lower, upper);
+                let distinct_count = (lhs.distinct_count as f64 * lhs_fraction)
+                    .max(rhs.distinct_count as f64 * rhs_fraction)
+                    .max(1.0);
+                matches += lhs.num_rows as f64
+                    * lhs_fraction
+                    * rhs.num_rows as f64
+                    * rhs_fraction
+                    / distinct_count;
+            }
+        }
+        Some(matches)
+    }
+}
+
+impl HistogramBucket {
+    /// Estimated fraction of the rows of the bucket between `lower` and
+    /// `upper`, which are within the bounds of the bucket.
+    fn fraction_between(&self, lower: &ScalarValue, upper: &ScalarValue) -> f64 {
+        if lower == &self.lower && upper == &self.upper {
+            return 1.0;
+        }
+        let fraction = match (lower.distance(upper), self.lower.distance(&self.upper)) {
+            (Some(width), Some(total)) if total > 0 => width as f64 / total as f64,
+            _ => 0.5,
+        };
+        fraction.clamp(1.0 / self.distinct_count.max(1) as f64, 1.0)
+    }
+
+    /// Returns true if `value` is between the bounds of the bucket
+    fn contains(&self, value: &ScalarValue) -> Option<bool> {
+        Some(
+            self.lower.partial_cmp(value)?.is_le()
+                && self.upper.partial_cmp(value)?.is_ge(),
+        )
+    }
+
+    /// Average number of rows per distinct value
+    fn rows_per_value(&self) -> f64 {
+        self.num_rows as f64 / self.distinct_count.max(1) as f64
+    }
+
+    fn estimate_lt(&self, value: &ScalarValue, inclusive: bool) -> Option<f64> {
+        let lower = self.lower.partial_cmp(value)?;
+        let upper = self.upper.partial_cmp(value)?;
+        let equal = if inclusive {
+            self.rows_per_value()
+        } else {
+            0.0
+        };
+        let rows = if upper.is_lt() {
+            self.num_rows as f64
+        } else if lower.is_gt() {
+            0.0
+        } else if upper.is_eq() {
+            self.num_rows as f64 - self.rows_per_value() + equal
+        } else if lower.is_eq() {
+            equal
+        } else {
+            // Assume the values are uniformly distributed within the bucket
+            let fraction =
+                match (self.lower.distance(value), self.lower.distance(&self.upper)) {
+                    (Some(below), Some(width)) if width > 0 => {
+                        below as f64 / width as f64
+                    }
+                    _ => 0.5,
+                };
+            self.num_rows as f64 * fraction + equal
+        };
+        Some(rows.clamp(0.0, self.num_rows as f64))
+    }
+}
+
+/// The most common values of a column, with the number of rows holding each
+/// of them
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct MostCommonValues {
+    values: Vec<(ScalarValue, usize)>,
+}
+
+impl MostCommonValues {
+    /// Creates the most common values from pairs of values and row counts
+    pub fn new(values: Vec<(ScalarValue, usize)>) -> Self {
+        Self { values }
+    }
+
+    /// The values with their number of rows
+    pub fn values(&self) -> &[(ScalarValue, usize)] {
+        &self.values
+    }
+
+    /// Total number of rows holding one of the values
+    pub fn num_rows(&self) -> usize {
+        self.values.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Returns the number of rows holding `value`, if it is one of the values
+    pub fn get(&self, value: &ScalarValue) -> Option<usize> {
+        self.values
+            .iter()
+            .find(|(v, _)| v == value)
+            .map(|(_, count)| *count)
+    }
+
+    /// Combines the most common values of two disjoint sets of rows
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut values = self.values.clone();
+        for (value, count) in &other.values {
+            match values.iter_mut().find(|(v, _)| v == value) {
+                Some((_, existing)) => *existing += count,
+                None => values.push((value.clone(), *count)),
+            }
+        }
+        Self { values }
+    }
+
+    /// Returns true if `value` has the type of the values
+    fn is_comparable(&self, value: &ScalarValue) -> bool {
+        self.values
+            .first()
+            .map_or(true, |(v, _)| v.partial_cmp(value).is_some())
+    }
 }
 
 #[cfg(test)]
@@ -581,4 +935,121 @@ mod tests {
         let p2 = precision.clone();
         assert_eq!(precision, p2);
     }
+
+    fn bucket(
+        lower: i64,
+        upper: i64,
+        num_rows: usize,
+        distinct_count: usize,
+    ) -> HistogramBucket {
+        HistogramBucket {
+            lower: ScalarValue::from(lower),
+            upper: ScalarValue::from(upper),
+            num_rows,
+            distinct_count,
+        }
+    }
+
+    fn distribution_stats() -> ColumnStatistics {
+        ColumnStatistics {
+            histogram: Some(Arc::new(Histogram::new(vec![
+                bucket(0, 99, 100, 100),
+                bucket(100, 199, 100, 10),
+            ]))),
+            most_common_values: Some(Arc::new(MostCommonValues::new(vec![(
+                ScalarValue::from(500i64),
+                200,
+            )]))),
+            ..ColumnStatistics::new_unknown()
+        }
+    }
+
+    #[test]
+    fn test_estimate_eq_selectivity() {
+        let stats = distribution_stats();
+        let eq = |v: i64| stats.estimate_eq_selectivity(&ScalarValue::from(v));
+
+        assert_eq!(eq(500), Some(0.5));
+        assert_eq!(eq(50), Some(0.0025));
+        assert_eq!(eq(150), Some(0.025));
+        assert_eq!(eq(1000), Some(0.0));
+        assert_eq!(stats.estimate_eq_selectivity(&ScalarValue::from("a")), None);
+        assert_eq!(
+            ColumnStatistics::new_unknown()
+                .estimate_eq_selectivity(&ScalarValue::from(1i64)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_estimate_lt_selectivity() {
+        let stats = distribution_stats();
+        let lt = |v: i64, inclusive| {
+            stats.estimate_lt_selectivity(&ScalarValue::from(v), inclusive)
+        };
+
+        assert_eq!(lt(0, false), Some(0.0));
+        assert_eq!(lt(0, true), Some(0.0025));
+        assert_eq!(lt(100, false), Some(0.25));
+        assert_eq!(lt(100, true), Some(0.275));
+        assert_eq!(lt(500, false), Some(0.5));
+        assert_eq!(lt(500, true), Some(1.0));
+        assert_eq!(lt(1000, false), Some(1.0));
+        // Values are assumed to be uniformly distributed within the buckets
+        let half = lt(149, false).unwrap();
+        assert!((half - 0.375).abs() < 0.01, "{half}");
+    }
+
+    #[test]
+    fn test_estimate_join_selectivity() {
+        let lhs = ColumnStatistics {
+            histogram: Some(Arc::new(Histogram::new(vec![bucket(1, 10, 10, 10)]))),
+            ..ColumnStatistics::new_unknown()
+        };
+        assert_eq!(lhs.estimate_join_selectivity(&lhs), Some(0.1));
+
+        // Only half of the right values are in the range of the left values
+        let rhs = ColumnStatistics {
+            histogram: Some(Arc::new(Histogram::new(vec![bucket(6, 15, 10, 10)]))),
+            ..ColumnStatistics::new_unknown()
+        };
+        let selectivity = lhs.estimate_join_selectivity(&rhs).unwrap();
+        assert!((selectivity - 0.05).abs() < 0.01, "{selectivity}");
+
+        let mcv = ColumnStatistics {
+            most_common_values: Some(Arc::new(MostCommonValues::new(vec![(
+                ScalarValue::from(5i64),
+                10,
+            )]))),
+            ..ColumnStatistics::new_unknown()
+        };
+        assert_eq!(mcv.estimate_join_selectivity(&lhs), Some(0.1));
+        assert_eq!(mcv.estimate_join_selectivity(&mcv), Some(1.0));
+        assert_eq!(
+            lhs.estimate_join_selectivity(&ColumnStatistics::new_unknown()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_distributions() {
+        let lhs = Histogram::new(vec![bucket(10, 19, 10, 10)]);
+        let rhs = Histogram::new(vec![bucket(0, 9, 5, 5)]);
+        let merged = lhs.merge(&rhs);
+        assert_eq!(
+            merged.buckets(),
+            &[bucket(0, 9, 5, 5), bucket(10, 19, 10, 10)]
+        );
+        assert_eq!(merged.num_rows(), 15);
+
+        let lhs = MostCommonValues::new(vec![(ScalarValue::from(1i64), 3)]);
+        let rhs = MostCommonValues::new(vec![
+            (ScalarValue::from(2i64), 2),
+            (ScalarValue::from(1i64), 4),
+        ]);
+        let merged = lhs.merge(&rhs);
+        assert_eq!(merged.get(&ScalarValue::from(1i64)), Some(7));
+        assert_eq!(merged.get(&ScalarValue::from(2i64)), Some(2));
+        assert_eq!(merged.num_rows(), 9);
+    }
 }
@@ -66,6 +66,25 @@ use crate::Column;
 /// `recursions` instruct how a column should be unnested (e.g unnesting a column multiple
 /// time, with depth = 1 and depth = 2). Any unnested column not being mentioned inside this
 /// options is inferred to be unnested with depth = 1
+///
+/// If `ordinality` is true, every unnested list column is followed by an
+/// additional `Int64` column holding the 1-based position of each element
+/// within the list it was taken from. Rows produced by padding a shorter list
+/// or by preserving a null list have a null position.
+///
+/// ## `Unnest(c1)`, preserve_nulls: false, ordinality: true
+/// ```text
+///      ┌─────────┐ ┌─────┐                ┌─────────┐ ┌─────────┐ ┌─────┐
+///      │ {1, 2}  │ │  A  │   Unnest       │    1    │ │    1    │ │  A  │
+///      ├─────────┤ ├─────┤                ├─────────┤ ├─────────┤ ├─────┤
+///      │  null   │ │  B  │                │    2    │ │    2    │ │  A  │
+///      ├─────────┤ ├─────┤ ────────────▶  ├─────────┤ ├─────────┤ ├─────┤
+///      │   {}    │ │  D  │                │    3    │ │    1    │ │  E  │
+///      ├─────────┤ ├─────┤                └─────────┘ └─────────┘ └─────┘
+///      │   {3}   │ │  E  │                    c1     c1_ordinality   c2
+///      └─────────┘ └─────┘
+///        c1         c2
+/// ```
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq)]
 pub struct UnnestOptions {
     /// Should nulls in the input be preserved? Defaults to true
@@ -74,6 +93,9 @@ pub struct UnnestOptions {
     /// declare them here. Any unnested columns not being mentioned inside this option
     /// will be unnested with depth = 1
     pub recursions: Vec<RecursionUnnestOption>,
+    /// Should a 1-based element index column be emitted after each unnested
+    /// list column? Defaults to false
+    pub ordinality: bool,
 }
 
 /// Instruction on how to unnest a column (mostly with a list type)
@@ -91,6 +113,7 @@ impl Default for UnnestOptions {
             // default to true to maintain backwards compatible behavior
             preserve_nulls: true,
             recursions: vec![],
+            ordinality: false,
         }
     }
 }
@@ -113,4 +136,11 @@ impl UnnestOptions {
         self.recursions.push(recursion);
         self
     }
+
+    /// Set whether an element index column is emitted for each unnested
+    /// list column, as described on [`Self`]
+    pub fn with_ordinality(mut self, ordinality: bool) -> Self {
+        self.ordinality = ordinality;
+        self
+    }
 }
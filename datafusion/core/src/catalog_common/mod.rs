@@ -192,6 +192,8 @@ pub fn resolve_table_references(
                 }
             },
             DFStatement::Explain(explain) => visit_statement(&explain.statement, visitor),
+            // the view is resolved when the statement is executed
+            DFStatement::RefreshMaterializedView(_) => {}
         }
     }
 
@@ -36,7 +36,8 @@ use crate::execution::context::{SessionState, TaskContext};
 use crate::execution::FunctionRegistry;
 use crate::logical_expr::utils::find_window_exprs;
 use crate::logical_expr::{
-    col, Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Partitioning, TableType,
+    col, Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Partitioning, SampleMethod,
+    TableType,
 };
 use crate::physical_plan::{
     collect, collect_partitioned, execute_stream, execute_stream_partitioned,
@@ -412,7 +413,10 @@ impl DataFrame {
     /// behavior controlled by [`UnnestOptions`].
     ///
     /// Please see the documentation on [`UnnestOptions`] for more
-    /// details about the meaning of unnest.
+    /// details about the meaning of unnest. With
+    /// [`UnnestOptions::with_ordinality`], each unnested list column is
+    /// followed by a `<column>_ordinality` column holding the 1-based
+    /// position of every element in its list.
     pub fn unnest_columns_with_options(
         self,
         columns: &[&str],
@@ -538,6 +542,113 @@ impl DataFrame {
         })
     }
 
+    /// Return a new `DataFrame` that turns the values of `pivot_column` listed
+    /// in `pivot_values` into columns, holding the result of `aggr_expr`
+    /// computed over the rows with that value. The rows are grouped by all
+    /// other columns that are not used by `aggr_expr`.
+    ///
+    /// See [`LogicalPlanBuilder::pivot`] for how the columns are named.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::functions_aggregate::expr_fn::sum;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx
+    ///     .sql("SELECT * FROM (VALUES ('a', 'Q1', 1), ('a', 'Q2', 2), ('a', 'Q2', 3), ('b', 'Q1', 4)) AS t(region, quarter, amount)")
+    ///     .await?;
+    ///
+    /// // The equivalent of "SELECT * FROM t PIVOT (sum(amount) FOR quarter IN ('Q1', 'Q2'))"
+    /// let df = df.pivot(vec![sum(col("amount"))], "quarter", vec![lit("Q1"), lit("Q2")], None)?;
+    /// let expected = vec![
+    ///     "+--------+----+----+",
+    ///     "| region | Q1 | Q2 |",
+    ///     "+--------+----+----+",
+    ///     "| a      | 1  | 5  |",
+    ///     "| b      | 4  |    |",
+    ///     "+--------+----+----+",
+    /// ];
+    /// assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pivot(
+        self,
+        aggr_expr: Vec<Expr>,
+        pivot_column: &str,
+        pivot_values: Vec<Expr>,
+        default_on_null: Option<Expr>,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .pivot(
+                aggr_expr,
+                Column::from_qualified_name(pivot_column),
+                pivot_values,
+                default_on_null,
+            )?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
+    /// Return a new `DataFrame` that turns the given `columns` into rows, with
+    /// the column name in a new `name_column` and its value in a new
+    /// `value_column`. Rows with null values are skipped unless
+    /// `include_nulls` is true.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx
+    ///     .sql("SELECT * FROM (VALUES ('a', 1, 2), ('b', 3, NULL)) AS t(region, q1, q2)")
+    ///     .await?;
+    ///
+    /// // The equivalent of "SELECT * FROM t UNPIVOT (amount FOR quarter IN (q1, q2))"
+    /// let df = df.unpivot("amount", "quarter", &["q1", "q2"], false)?;
+    /// let expected = vec![
+    ///     "+--------+---------+--------+",
+    ///     "| region | quarter | amount |",
+    ///     "+--------+---------+--------+",
+    ///     "| a      | q1      | 1      |",
+    ///     "| a      | q2      | 2      |",
+    ///     "| b      | q1      | 3      |",
+    ///     "+--------+---------+--------+",
+    /// ];
+    /// assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unpivot(
+        self,
+        value_column: &str,
+        name_column: &str,
+        columns: &[&str],
+        include_nulls: bool,
+    ) -> Result<DataFrame> {
+        let columns = columns
+            .iter()
+            .map(|name| Column::from_qualified_name(*name))
+            .collect();
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .unpivot(value_column, name_column, columns, include_nulls)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Return a new DataFrame that adds the result of evaluating one or more
     /// window functions ([`Expr::WindowFunction`]) to the existing columns
     pub fn window(self, window_exprs: Vec<Expr>) -> Result<DataFrame> {
@@ -588,6 +699,44 @@ impl DataFrame {
         })
     }
 
+    /// Return a random sample of the rows of this DataFrame, each row (for
+    /// [`SampleMethod::Bernoulli`]) or block of rows (for
+    /// [`SampleMethod::System`]) being returned with probability `fraction`.
+    ///
+    /// With a `seed`, the same rows are returned every time the DataFrame is
+    /// executed. `SYSTEM` sampling is pushed, through filters and
+    /// projections, into scans that can skip whole blocks of rows, such as the
+    /// row groups of parquet files. Other inputs are sampled batch by batch.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_expr::SampleMethod;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.sample(SampleMethod::Bernoulli, 0.5, Some(42))?;
+    /// # assert!(df.count().await? <= 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .sample(method, fraction, seed)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Calculate the union of two [`DataFrame`]s, preserving duplicate rows.
     ///
     /// The two [`DataFrame`]s must have exactly the same schema
@@ -1051,6 +1200,78 @@ impl DataFrame {
         })
     }
 
+    /// Join this `DataFrame` with another `DataFrame` using an ASOF join.
+    ///
+    /// Every row of this `DataFrame` is joined with the single nearest row of
+    /// `right` that has the same `left_cols`/`right_cols` values and satisfies
+    /// `match_condition`, or with nulls if there is no such row.
+    ///
+    /// `match_condition` compares an expression of this `DataFrame` with an
+    /// expression of `right` using `>=`, `>`, `<=` or `<`. For `>=` and `>`
+    /// the nearest row is the one with the greatest value of the right
+    /// expression, for `<=` and `<` the one with the smallest value.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let trades = ctx
+    ///     .sql("SELECT * FROM (VALUES ('a', 5), ('a', 12), ('b', 7)) AS t(sym, ts)")
+    ///     .await?;
+    /// let quotes = ctx
+    ///     .sql("SELECT * FROM (VALUES ('a', 1, 100), ('a', 10, 101), ('b', 8, 200)) AS q(sym2, ts2, price)")
+    ///     .await?;
+    ///
+    /// // Find the latest quote of the same symbol at or before every trade
+    /// let join = trades.join_asof(quotes, &["sym"], &["sym2"], col("ts").gt_eq(col("ts2")))?;
+    /// let expected = vec![
+    ///     "+-----+----+------+-----+-------+",
+    ///     "| sym | ts | sym2 | ts2 | price |",
+    ///     "+-----+----+------+-----+-------+",
+    ///     "| a   | 5  | a    | 1   | 100   |",
+    ///     "| a   | 12 | a    | 10  | 101   |",
+    ///     "| b   | 7  |      |     |       |",
+    ///     "+-----+----+------+-----+-------+",
+    /// ];
+    /// assert_batches_sorted_eq!(expected, &join.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_asof(
+        self,
+        right: DataFrame,
+        left_cols: &[&str],
+        right_cols: &[&str],
+        match_condition: Expr,
+    ) -> Result<DataFrame> {
+        // Resolve the keys against their own side, as both sides may have
+        // columns with the same name
+        let keys = |plan: &LogicalPlan, cols: &[&str]| {
+            cols.iter()
+                .map(|name| {
+                    Column::from_qualified_name(*name)
+                        .normalize_with_schemas_and_ambiguity_check(
+                            &[&[plan.schema()]],
+                            &[],
+                        )
+                        .map(Expr::Column)
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let join_keys = (keys(&self.plan, left_cols)?, keys(&right.plan, right_cols)?);
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .join_asof(right.plan, join_keys, match_condition)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Join this `DataFrame` with another `DataFrame` using the specified
     /// expressions.
     ///
@@ -3913,6 +4134,8 @@ mod tests {
                         out_partitioning,
                     &Partitioning::UnknownPartitioning(partition_count) if partition_count == default_partition_count));
                 }
+                // Needs a match condition, tested separately
+                JoinType::AsOf => unreachable!(),
             }
         }
 
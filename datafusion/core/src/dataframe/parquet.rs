@@ -97,14 +97,16 @@ mod tests {
 
     use super::super::Result;
     use super::*;
+    use crate::arrow::array::{ArrayRef, Int32Array, RecordBatch};
     use crate::arrow::util::pretty;
     use crate::execution::context::SessionContext;
     use crate::execution::options::ParquetReadOptions;
+    use crate::physical_plan::displayable;
     use crate::test_util::{self, register_aggregate_csv};
 
     use datafusion_common::file_options::parquet_writer::parse_compression_string;
     use datafusion_execution::config::SessionConfig;
-    use datafusion_expr::{col, lit};
+    use datafusion_expr::{col, lit, SampleMethod};
 
     use object_store::local::LocalFileSystem;
     use parquet::file::reader::FileReader;
@@ -236,4 +238,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn sample_parquet_row_groups() -> Result<()> {
+        let ctx = SessionContext::new();
+        let batch = RecordBatch::try_from_iter(vec![(
+            "a",
+            Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef,
+        )])?;
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("test.parquet");
+        let mut options = TableParquetOptions::default();
+        options.global.max_row_group_size = 10;
+        ctx.read_batch(batch)?
+            .write_parquet(
+                path.to_str().unwrap(),
+                DataFrameWriteOptions::new().with_single_file_output(true),
+                Some(options),
+            )
+            .await?;
+        let df = ctx
+            .read_parquet(path.to_str().unwrap(), ParquetReadOptions::default())
+            .await?;
+
+        // SYSTEM sampling is pushed into the scan, reading whole row groups
+        let sampled = df.clone().sample(SampleMethod::System, 0.5, Some(42))?;
+        let plan = sampled.clone().create_physical_plan().await?;
+        let formatted = displayable(plan.as_ref()).indent(true).to_string();
+        assert!(formatted.contains("sample_fraction=0.5, sample_seed=42"));
+        assert!(!formatted.contains("SampleExec"));
+        let rows = sampled.count().await?;
+        assert!(
+            rows < 100 && rows % 10 == 0,
+            "unexpected sample size {rows}"
+        );
+
+        // It is also pushed through filters and projections above the scan
+        let sampled = df.clone().filter(col("a").gt(lit(5)))?.sample(
+            SampleMethod::System,
+            0.5,
+            Some(42),
+        )?;
+        let plan = sampled.create_physical_plan().await?;
+        let formatted = displayable(plan.as_ref()).indent(true).to_string();
+        assert!(formatted.contains("sample_fraction=0.5, sample_seed=42"));
+        assert!(!formatted.contains("SampleExec"));
+
+        // BERNOULLI sampling chooses rows
+        let sampled = df.sample(SampleMethod::Bernoulli, 0.5, Some(42))?;
+        let plan = sampled.create_physical_plan().await?;
+        let formatted = displayable(plan.as_ref()).indent(true).to_string();
+        assert!(formatted.contains("SampleExec: method=BERNOULLI, fraction=0.5, seed=42"));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,205 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use crate::error::Result;
+use apache_avro::Schema as AvroSchema;
+use datafusion_common::not_impl_err;
+use serde_json::{json, Value as JsonValue};
+
+/// Name of the top level record of written Avro files
+const ROOT_RECORD_NAME: &str = "record";
+
+/// Converts an arrow schema to an avro record schema
+pub fn to_avro_schema(schema: &Schema) -> Result<AvroSchema> {
+    Ok(AvroSchema::parse(&to_avro_schema_json(schema)?)?)
+}
+
+/// Converts an arrow schema to the JSON representation of an avro record
+/// schema.
+///
+/// Nullable fields become unions of `null` and the field type. Dates, times,
+/// timestamps and decimals use the matching avro logical types; seconds are
+/// widened to milliseconds and nanoseconds are truncated to microseconds.
+/// Structs become nested records, named after the path of their field.
+pub fn to_avro_schema_json(schema: &Schema) -> Result<JsonValue> {
+    fields_to_record(schema.fields(), ROOT_RECORD_NAME)
+}
+
+fn fields_to_record(fields: &Fields, name: &str) -> Result<JsonValue> {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let schema = field_to_avro(field, &format!("{name}_{}", field.name()))?;
+            Ok(json!({ "name": field.name(), "type": schema }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(json!({ "type": "record", "name": avro_name(name), "fields": fields }))
+}
+
+/// Returns the schema of the values of `field`, a union with `null` if the
+/// field is nullable. `name` is used to name the record and fixed types
+/// nested in the field.
+fn field_to_avro(field: &Field, name: &str) -> Result<JsonValue> {
+    let schema = data_type_to_avro(field.data_type(), name)?;
+    if field.is_nullable() && !field.data_type().is_null() {
+        Ok(json!(["null", schema]))
+    } else {
+        Ok(schema)
+    }
+}
+
+fn data_type_to_avro(data_type: &DataType, name: &str) -> Result<JsonValue> {
+    let schema = match data_type {
+        DataType::Null => json!("null"),
+        DataType::Boolean => json!("boolean"),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::UInt8
+        | DataType::UInt16 => json!("int"),
+        DataType::Int64 | DataType::UInt32 => json!("long"),
+        DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => json!("string"),
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            json!("bytes")
+        }
+        DataType::FixedSizeBinary(size) => {
+            json!({ "type": "fixed", "name": avro_name(name), "size": size })
+        }
+        DataType::Decimal128(precision, scale) if *scale >= 0 => json!({
+            "type": "bytes",
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale,
+        }),
+        DataType::Date32 | DataType::Date64 => {
+            json!({ "type": "int", "logicalType": "date" })
+        }
+        DataType::Time32(_) => json!({ "type": "int", "logicalType": "time-millis" }),
+        DataType::Time64(_) => json!({ "type": "long", "logicalType": "time-micros" }),
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, _) => {
+            json!({ "type": "long", "logicalType": "timestamp-millis" })
+        }
+        DataType::Timestamp(TimeUnit::Microsecond | TimeUnit::Nanosecond, _) => {
+            json!({ "type": "long", "logicalType": "timestamp-micros" })
+        }
+        DataType::List(field)
+        | DataType::LargeList(field)
+        | DataType::FixedSizeList(field, _) => json!({
+            "type": "array",
+            "items": field_to_avro(field, &format!("{name}_item"))?,
+        }),
+        DataType::Struct(fields) => fields_to_record(fields, name)?,
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(fields)
+                if fields.len() == 2
+                    && matches!(
+                        fields[0].data_type(),
+                        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                    ) =>
+            {
+                json!({
+                    "type": "map",
+                    "values": field_to_avro(&fields[1], &format!("{name}_value"))?,
+                })
+            }
+            _ => {
+                return not_impl_err!("Avro maps must have string keys, got {data_type}")
+            }
+        },
+        DataType::Dictionary(_, value_type) => data_type_to_avro(value_type, name)?,
+        _ => return not_impl_err!("Writing {data_type} values to Avro is not supported"),
+    };
+    Ok(schema)
+}
+
+/// Replaces the characters not allowed in avro type names
+fn avro_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::datasource::avro_to_arrow::to_arrow_schema;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_nested_schema_roundtrip() -> Result<()> {
+        let item = Field::new("item", DataType::Int64, true);
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("price", DataType::Decimal128(10, 2), true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+            Field::new("tags", DataType::List(Arc::new(item.clone())), true),
+            Field::new(
+                "point",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("x", DataType::Float64, false),
+                    Field::new("y", DataType::Float64, false),
+                ])),
+                true,
+            ),
+        ]);
+
+        let avro_schema = to_avro_schema(&schema)?;
+        let arrow_schema = to_arrow_schema(&avro_schema)?;
+        for (field, expected) in arrow_schema.fields().iter().zip(schema.fields()) {
+            assert_eq!(field.name(), expected.name());
+            assert_eq!(field.is_nullable(), expected.is_nullable());
+        }
+        let data_types = arrow_schema
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            data_types,
+            vec![
+                DataType::Int32,
+                DataType::Utf8,
+                DataType::Decimal128(10, 2),
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                DataType::List(Arc::new(item.with_name("element"))),
+                DataType::Struct(Fields::from(vec![
+                    Field::new("x", DataType::Float64, false),
+                    Field::new("y", DataType::Float64, false),
+                ])),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_types() {
+        let schema = Schema::new(vec![Field::new("u", DataType::UInt64, false)]);
+        let err = to_avro_schema(&schema).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "This feature is not implemented: Writing UInt64 values to Avro is not supported"
+        );
+    }
+}
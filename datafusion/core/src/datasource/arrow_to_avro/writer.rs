@@ -0,0 +1,412 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use super::schema::to_avro_schema_json;
+use crate::arrow::array::{
+    Array, ArrayRef, AsArray, FixedSizeListArray, GenericListArray, MapArray,
+    OffsetSizeTrait, RecordBatch,
+};
+use crate::arrow::compute::cast;
+use crate::arrow::datatypes::*;
+use crate::error::Result;
+use apache_avro::types::Value;
+use apache_avro::{to_avro_datum, Codec, Decimal, Schema as AvroSchema};
+use datafusion_common::{config_err, exec_err, not_impl_err};
+
+/// Magic bytes starting every Avro object container file
+const AVRO_MAGIC: &[u8] = b"Obj\x01";
+
+const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+
+/// Encodes [`RecordBatch`]es as the blocks of an Avro object container file.
+///
+/// Each batch becomes a single block, so batches can be encoded
+/// independently of each other as long as the blocks are written after
+/// [`Self::header`] and in any order.
+#[derive(Debug, Clone)]
+pub struct AvroEncoder {
+    /// Schema of the encoded batches
+    arrow_schema: SchemaRef,
+    /// Avro schema of the records of the file
+    schema: AvroSchema,
+    /// JSON representation of `schema`, written to the file header
+    schema_json: String,
+    codec: Codec,
+    codec_name: String,
+    /// The sync marker ending each block
+    sync_marker: [u8; 16],
+}
+
+impl AvroEncoder {
+    /// Creates an encoder for batches of `arrow_schema` compressing data
+    /// blocks with the codec called `codec`
+    pub fn try_new(arrow_schema: SchemaRef, codec: &str) -> Result<Self> {
+        let schema_json = to_avro_schema_json(&arrow_schema)?;
+        let schema = AvroSchema::parse(&schema_json)?;
+        let codec_name = codec.to_lowercase();
+        let codec = match codec_name.as_str() {
+            "null" => Codec::Null,
+            "deflate" => Codec::Deflate,
+            "snappy" => Codec::Snappy,
+            "bzip2" => Codec::Bzip2,
+            "xz" => Codec::Xz,
+            "zstandard" => Codec::Zstandard,
+            _ => return config_err!("Unknown or unsupported avro codec {codec}"),
+        };
+        Ok(Self {
+            arrow_schema,
+            schema,
+            schema_json: schema_json.to_string(),
+            codec,
+            codec_name,
+            sync_marker: rand::random(),
+        })
+    }
+
+    /// Returns a copy of this encoder with a new random sync marker, to
+    /// encode another file
+    pub fn with_new_sync_marker(&self) -> Self {
+        Self {
+            sync_marker: rand::random(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the header of the file, holding its schema, codec and sync marker
+    pub fn header(&self) -> Vec<u8> {
+        let mut header = AVRO_MAGIC.to_vec();
+        encode_long(2, &mut header);
+        for (key, value) in [
+            ("avro.schema", self.schema_json.as_bytes()),
+            ("avro.codec", self.codec_name.as_bytes()),
+        ] {
+            encode_bytes(key.as_bytes(), &mut header);
+            encode_bytes(value, &mut header);
+        }
+        encode_long(0, &mut header);
+        header.extend_from_slice(&self.sync_marker);
+        header
+    }
+
+    /// Encodes `batch` as a data block, returning no bytes for empty batches
+    pub fn encode_batch(&self, batch: &RecordBatch) -> Result<Vec<u8>> {
+        if batch.num_rows() == 0 {
+            return Ok(vec![]);
+        }
+        let records = records(
+            self.arrow_schema.fields(),
+            batch.columns(),
+            batch.num_rows(),
+        )?;
+        let mut data = vec![];
+        for record in records {
+            data.extend(to_avro_datum(&self.schema, record)?);
+        }
+        self.codec.compress(&mut data)?;
+
+        let mut block = Vec::with_capacity(data.len() + 36);
+        encode_long(batch.num_rows() as i64, &mut block);
+        encode_long(data.len() as i64, &mut block);
+        block.extend(data);
+        block.extend_from_slice(&self.sync_marker);
+        Ok(block)
+    }
+}
+
+/// Writes `n` as a zigzag encoded variable length integer
+fn encode_long(n: i64, buffer: &mut Vec<u8>) {
+    let mut z = ((n << 1) ^ (n >> 63)) as u64;
+    while z > 0x7F {
+        buffer.push((z & 0x7F) as u8 | 0x80);
+        z >>= 7;
+    }
+    buffer.push(z as u8);
+}
+
+fn encode_bytes(bytes: &[u8], buffer: &mut Vec<u8>) {
+    encode_long(bytes.len() as i64, buffer);
+    buffer.extend_from_slice(bytes);
+}
+
+/// Converts `num_rows` rows of `columns` to avro records of `fields`
+fn records(fields: &Fields, columns: &[ArrayRef], num_rows: usize) -> Result<Vec<Value>> {
+    let mut records = vec![Vec::with_capacity(fields.len()); num_rows];
+    for (field, column) in fields.iter().zip(columns) {
+        for (record, value) in records.iter_mut().zip(field_values(column, field)?) {
+            record.push((field.name().clone(), value));
+        }
+    }
+    Ok(records.into_iter().map(Value::Record).collect())
+}
+
+/// Converts the rows of `array` to avro values of `field`, wrapping them in
+/// a union with null if the field is nullable
+fn field_values(array: &ArrayRef, field: &Field) -> Result<Vec<Value>> {
+    let nullable = field.is_nullable() && !field.data_type().is_null();
+    array_values(array)?
+        .into_iter()
+        .map(|value| match (value, nullable) {
+            (Some(value), true) => Ok(Value::Union(1, Box::new(value))),
+            (None, true) => Ok(Value::Union(0, Box::new(Value::Null))),
+            (Some(value), false) => Ok(value),
+            (None, false) => exec_err!(
+                "Cannot write null to the non nullable avro field {}",
+                field.name()
+            ),
+        })
+        .collect()
+}
+
+/// Converts the rows of `array` to avro values, `None` for null rows
+fn array_values(array: &ArrayRef) -> Result<Vec<Option<Value>>> {
+    let values = match array.data_type() {
+        DataType::Null => vec![Some(Value::Null); array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(|v| v.map(Value::Boolean))
+            .collect(),
+        DataType::Int8 => primitive_values::<Int8Type>(array, |v| Value::Int(v as i32)),
+        DataType::Int16 => primitive_values::<Int16Type>(array, |v| Value::Int(v as i32)),
+        DataType::Int32 => primitive_values::<Int32Type>(array, Value::Int),
+        DataType::Int64 => primitive_values::<Int64Type>(array, Value::Long),
+        DataType::UInt8 => primitive_values::<UInt8Type>(array, |v| Value::Int(v as i32)),
+        DataType::UInt16 => {
+            primitive_values::<UInt16Type>(array, |v| Value::Int(v as i32))
+        }
+        DataType::UInt32 => {
+            primitive_values::<UInt32Type>(array, |v| Value::Long(v as i64))
+        }
+        DataType::Float32 => primitive_values::<Float32Type>(array, Value::Float),
+        DataType::Float64 => primitive_values::<Float64Type>(array, Value::Double),
+        DataType::Utf8 => string_values(array.as_string::<i32>().iter()),
+        DataType::LargeUtf8 => string_values(array.as_string::<i64>().iter()),
+        DataType::Utf8View => string_values(array.as_string_view().iter()),
+        DataType::Binary => binary_values(array.as_binary::<i32>().iter()),
+        DataType::LargeBinary => binary_values(array.as_binary::<i64>().iter()),
+        DataType::BinaryView => binary_values(array.as_binary_view().iter()),
+        DataType::FixedSizeBinary(size) => array
+            .as_fixed_size_binary()
+            .iter()
+            .map(|v| v.map(|v| Value::Fixed(*size as usize, v.to_vec())))
+            .collect(),
+        DataType::Decimal128(_, _) => primitive_values::<Decimal128Type>(array, |v| {
+            Value::Decimal(Decimal::from(v.to_be_bytes().to_vec()))
+        }),
+        DataType::Date32 => primitive_values::<Date32Type>(array, Value::Date),
+        DataType::Date64 => primitive_values::<Date64Type>(array, |v| {
+            Value::Date(v.div_euclid(MILLISECONDS_IN_DAY) as i32)
+        }),
+        DataType::Time32(TimeUnit::Second) => {
+            primitive_values::<Time32SecondType>(array, |v| Value::TimeMillis(v * 1000))
+        }
+        DataType::Time32(_) => {
+            primitive_values::<Time32MillisecondType>(array, Value::TimeMillis)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            primitive_values::<Time64NanosecondType>(array, |v| {
+                Value::TimeMicros(v / 1000)
+            })
+        }
+        DataType::Time64(_) => {
+            primitive_values::<Time64MicrosecondType>(array, Value::TimeMicros)
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            primitive_values::<TimestampSecondType>(array, |v| {
+                Value::TimestampMillis(v * 1000)
+            })
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            primitive_values::<TimestampMillisecondType>(array, Value::TimestampMillis)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            primitive_values::<TimestampMicrosecondType>(array, Value::TimestampMicros)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            primitive_values::<TimestampNanosecondType>(array, |v| {
+                Value::TimestampMicros(v.div_euclid(1000))
+            })
+        }
+        DataType::List(field) => list_values(array.as_list::<i32>(), field)?,
+        DataType::LargeList(field) => list_values(array.as_list::<i64>(), field)?,
+        DataType::FixedSizeList(field, _) => {
+            fixed_size_list_values(array.as_fixed_size_list(), field)?
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            records(fields, array.columns(), array.len())?
+                .into_iter()
+                .enumerate()
+                .map(|(row, record)| array.is_valid(row).then_some(record))
+                .collect()
+        }
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(fields) if fields.len() == 2 => {
+                map_values(array.as_map(), &fields[1])?
+            }
+            _ => return not_impl_err!("Unsupported map type {}", array.data_type()),
+        },
+        DataType::Dictionary(_, value_type) => array_values(&cast(array, value_type)?)?,
+        data_type => {
+            return not_impl_err!("Writing {data_type} values to Avro is not supported")
+        }
+    };
+    Ok(values)
+}
+
+fn primitive_values<T: ArrowPrimitiveType>(
+    array: &ArrayRef,
+    f: impl Fn(T::Native) -> Value,
+) -> Vec<Option<Value>> {
+    array
+        .as_primitive::<T>()
+        .iter()
+        .map(|v| v.map(&f))
+        .collect()
+}
+
+fn string_values<'a>(iter: impl Iterator<Item = Option<&'a str>>) -> Vec<Option<Value>> {
+    iter.map(|v| v.map(|v| Value::String(v.to_string())))
+        .collect()
+}
+
+fn binary_values<'a>(iter: impl Iterator<Item = Option<&'a [u8]>>) -> Vec<Option<Value>> {
+    iter.map(|v| v.map(|v| Value::Bytes(v.to_vec()))).collect()
+}
+
+fn list_values<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+    field: &Field,
+) -> Result<Vec<Option<Value>>> {
+    let offsets = array.value_offsets();
+    let mut items = field_values(array.values(), field)?
+        .into_iter()
+        .skip(offsets[0].as_usize());
+    Ok(offsets
+        .windows(2)
+        .enumerate()
+        .map(|(row, range)| {
+            let len = (range[1] - range[0]).as_usize();
+            let row_items = items.by_ref().take(len).collect();
+            array.is_valid(row).then_some(Value::Array(row_items))
+        })
+        .collect())
+}
+
+fn fixed_size_list_values(
+    array: &FixedSizeListArray,
+    field: &Field,
+) -> Result<Vec<Option<Value>>> {
+    let len = array.value_length() as usize;
+    let mut items = field_values(array.values(), field)?
+        .into_iter()
+        .skip(array.value_offset(0) as usize);
+    Ok((0..array.len())
+        .map(|row| {
+            let row_items = items.by_ref().take(len).collect();
+            array.is_valid(row).then_some(Value::Array(row_items))
+        })
+        .collect())
+}
+
+fn map_values(array: &MapArray, value_field: &Field) -> Result<Vec<Option<Value>>> {
+    let keys = cast(array.keys(), &DataType::Utf8)?;
+    let values = field_values(array.values(), value_field)?;
+    let offsets = array.value_offsets();
+    let mut entries = keys
+        .as_string::<i32>()
+        .iter()
+        .zip(values)
+        .skip(offsets[0] as usize);
+    Ok(offsets
+        .windows(2)
+        .enumerate()
+        .map(|(row, range)| {
+            let entries = entries
+                .by_ref()
+                .take((range[1] - range[0]) as usize)
+                .map(|(key, value)| (key.unwrap_or_default().to_string(), value))
+                .collect::<HashMap<_, _>>();
+            array.is_valid(row).then_some(Value::Map(entries))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arrow::array::{Int32Array, ListArray, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_encode_long() {
+        for (n, expected) in [
+            (0, vec![0x00]),
+            (-1, vec![0x01]),
+            (1, vec![0x02]),
+            (-64, vec![0x7F]),
+            (64, vec![0x80, 0x01]),
+        ] {
+            let mut buffer = vec![];
+            encode_long(n, &mut buffer);
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[test]
+    fn test_encode_roundtrip() -> Result<()> {
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef,
+            ),
+            (
+                "tags",
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                    Some(vec![Some(1), Some(2)]),
+                    None,
+                    Some(vec![]),
+                ])) as ArrayRef,
+            ),
+        ])?;
+
+        for codec in ["null", "deflate", "snappy"] {
+            let encoder = AvroEncoder::try_new(batch.schema(), codec)?;
+            let mut file = encoder.header();
+            file.extend(encoder.encode_batch(&batch)?);
+            file.extend(encoder.encode_batch(&batch.slice(1, 2))?);
+
+            let reader = apache_avro::Reader::new(file.as_slice())?;
+            let ids = reader
+                .map(|record| match record? {
+                    Value::Record(fields) => Ok(fields[0].1.clone()),
+                    other => panic!("unexpected value {other:?}"),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let int = |v| Value::Union(1, Box::new(Value::Int(v)));
+            let null = Value::Union(0, Box::new(Value::Null));
+            assert_eq!(ids, vec![int(1), null.clone(), int(3), null, int(3)]);
+        }
+        Ok(())
+    }
+}
@@ -20,50 +20,97 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
 use async_trait::async_trait;
+use datafusion_common::config::{AvroOptions, ConfigField, ConfigFileType};
+use datafusion_common::file_options::avro_writer::AvroWriterOptions;
+use datafusion_common::not_impl_err;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::DataFusionError;
 use datafusion_common::GetExt;
 use datafusion_common::DEFAULT_AVRO_EXTENSION;
+use datafusion_execution::TaskContext;
+use datafusion_expr::dml::InsertOp;
 use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
+use datafusion_physical_plan::metrics::MetricsSet;
 use object_store::{GetResultPayload, ObjectMeta, ObjectStore};
 
 use super::file_compression_type::FileCompressionType;
 use super::FileFormat;
 use super::FileFormatFactory;
 use crate::datasource::avro_to_arrow::read_avro_schema_from_reader;
-use crate::datasource::physical_plan::{AvroExec, FileScanConfig};
+use crate::datasource::physical_plan::{
+    AvroExec, FileGroupDisplay, FileScanConfig, FileSinkConfig,
+};
 use crate::error::Result;
 use crate::execution::context::SessionState;
+use crate::physical_plan::insert::{DataSink, DataSinkExec};
 use crate::physical_plan::ExecutionPlan;
 use crate::physical_plan::Statistics;
+use crate::physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+
+#[cfg(feature = "avro")]
+use super::write::{orchestration::stateless_multipart_put, BatchSerializer};
+#[cfg(feature = "avro")]
+use crate::datasource::arrow_to_avro::AvroEncoder;
+#[cfg(feature = "avro")]
+use arrow_array::RecordBatch;
+#[cfg(feature = "avro")]
+use bytes::Bytes;
 
 #[derive(Default)]
 /// Factory struct used to create [AvroFormat]
-pub struct AvroFormatFactory;
+pub struct AvroFormatFactory {
+    /// the options carried by format factory
+    pub options: Option<AvroOptions>,
+}
 
 impl AvroFormatFactory {
     /// Creates an instance of [AvroFormatFactory]
     pub fn new() -> Self {
-        Self {}
+        Self { options: None }
+    }
+
+    /// Creates an instance of [AvroFormatFactory] with customized default options
+    pub fn new_with_options(options: AvroOptions) -> Self {
+        Self {
+            options: Some(options),
+        }
     }
 }
 
 impl FileFormatFactory for AvroFormatFactory {
     fn create(
         &self,
-        _state: &SessionState,
-        _format_options: &HashMap<String, String>,
+        state: &SessionState,
+        format_options: &HashMap<String, String>,
     ) -> Result<Arc<dyn FileFormat>> {
-        Ok(Arc::new(AvroFormat))
+        let avro_options = match &self.options {
+            None => {
+                let mut table_options = state.default_table_options();
+                table_options.set_config_format(ConfigFileType::AVRO);
+                table_options.alter_with_string_hash_map(format_options)?;
+                table_options.avro
+            }
+            Some(avro_options) => {
+                let mut avro_options = avro_options.clone();
+                for (k, v) in format_options {
+                    avro_options.set(k, v)?;
+                }
+                avro_options
+            }
+        };
+
+        Ok(Arc::new(AvroFormat::default().with_options(avro_options)))
     }
 
     fn default(&self) -> Arc<dyn FileFormat> {
-        Arc::new(AvroFormat)
+        Arc::new(AvroFormat::default())
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -71,9 +118,11 @@ impl FileFormatFactory for AvroFormatFactory {
     }
 }
 
-impl fmt::Debug for AvroFormatFactory {
+impl Debug for AvroFormatFactory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("AvroFormatFactory").finish()
+        f.debug_struct("AvroFormatFactory")
+            .field("options", &self.options)
+            .finish()
     }
 }
 
@@ -86,7 +135,29 @@ impl GetExt for AvroFormatFactory {
 
 /// Avro `FileFormat` implementation.
 #[derive(Default, Debug)]
-pub struct AvroFormat;
+pub struct AvroFormat {
+    options: AvroOptions,
+}
+
+impl AvroFormat {
+    /// Set Avro options
+    pub fn with_options(mut self, options: AvroOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Retrieve Avro options
+    pub fn options(&self) -> &AvroOptions {
+        &self.options
+    }
+
+    /// Set the codec used to compress the data blocks of written files
+    /// - defaults to `null`, i.e. no compression
+    pub fn with_codec(mut self, codec: impl Into<String>) -> Self {
+        self.options.codec = codec.into();
+        self
+    }
+}
 
 #[async_trait]
 impl FileFormat for AvroFormat {
@@ -155,6 +226,186 @@ impl FileFormat for AvroFormat {
         let exec = AvroExec::new(conf);
         Ok(Arc::new(exec))
     }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &SessionState,
+        conf: FileSinkConfig,
+        order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if conf.insert_op != InsertOp::Append {
+            return not_impl_err!("Overwrites are not implemented yet for Avro");
+        }
+
+        let writer_options = AvroWriterOptions::try_from(&self.options)?;
+
+        let sink_schema = conf.output_schema().clone();
+        let sink = Arc::new(AvroSink::new(conf, writer_options));
+
+        Ok(Arc::new(DataSinkExec::new(
+            input,
+            sink,
+            sink_schema,
+            order_requirements,
+        )) as _)
+    }
+}
+
+/// Define a struct for serializing Avro records to a stream
+#[cfg(feature = "avro")]
+pub struct AvroSerializer {
+    encoder: AvroEncoder,
+}
+
+#[cfg(feature = "avro")]
+impl AvroSerializer {
+    /// Constructor for the AvroSerializer object writing a single file
+    /// with `encoder`
+    pub fn new(encoder: AvroEncoder) -> Self {
+        Self { encoder }
+    }
+}
+
+#[cfg(feature = "avro")]
+impl BatchSerializer for AvroSerializer {
+    fn serialize(&self, batch: RecordBatch, initial: bool) -> Result<Bytes> {
+        let block = self.encoder.encode_batch(&batch)?;
+        if initial {
+            let mut buffer = self.encoder.header();
+            buffer.extend(block);
+            Ok(Bytes::from(buffer))
+        } else {
+            Ok(Bytes::from(block))
+        }
+    }
+}
+
+/// Implements [`DataSink`] for writing to an Avro file.
+pub struct AvroSink {
+    /// Config options for writing data
+    config: FileSinkConfig,
+    /// Writer options for underlying Avro writer
+    writer_options: AvroWriterOptions,
+}
+
+impl Debug for AvroSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AvroSink").finish()
+    }
+}
+
+impl DisplayAs for AvroSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "AvroSink(file_groups=",)?;
+                FileGroupDisplay(&self.config.file_groups).fmt_as(t, f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl AvroSink {
+    /// Create from config.
+    pub fn new(config: FileSinkConfig, writer_options: AvroWriterOptions) -> Self {
+        Self {
+            config,
+            writer_options,
+        }
+    }
+
+    /// Retrieve the inner [`FileSinkConfig`].
+    pub fn config(&self) -> &FileSinkConfig {
+        &self.config
+    }
+
+    /// Retrieve the writer options
+    pub fn writer_options(&self) -> &AvroWriterOptions {
+        &self.writer_options
+    }
+
+    /// Converts table schema to writer schema, which may differ in the case
+    /// of hive style partitioning where some columns are removed from the
+    /// underlying files.
+    #[cfg(feature = "avro")]
+    fn get_writer_schema(&self) -> SchemaRef {
+        if !self.config.table_partition_cols.is_empty()
+            && !self.config.keep_partition_by_columns
+        {
+            let schema = self.config.output_schema();
+            let partition_names: Vec<_> = self
+                .config
+                .table_partition_cols
+                .iter()
+                .map(|(s, _)| s)
+                .collect();
+            Arc::new(Schema::new_with_metadata(
+                schema
+                    .fields()
+                    .iter()
+                    .filter(|f| !partition_names.contains(&f.name()))
+                    .map(|f| (**f).clone())
+                    .collect::<Vec<_>>(),
+                schema.metadata().clone(),
+            ))
+        } else {
+            self.config.output_schema().clone()
+        }
+    }
+
+    #[cfg(feature = "avro")]
+    async fn multipartput_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let encoder =
+            AvroEncoder::try_new(self.get_writer_schema(), &self.writer_options.codec)?;
+        // Each file gets its own sync marker
+        let get_serializer =
+            move || Arc::new(AvroSerializer::new(encoder.with_new_sync_marker())) as _;
+
+        stateless_multipart_put(
+            data,
+            context,
+            "avro".into(),
+            Box::new(get_serializer),
+            &self.config,
+            FileCompressionType::UNCOMPRESSED,
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "avro"))]
+    async fn multipartput_all(
+        &self,
+        _data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        not_impl_err!("cannot write avro files without the 'avro' feature enabled")
+    }
+}
+
+#[async_trait]
+impl DataSink for AvroSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let total_count = self.multipartput_all(data, context).await?;
+        Ok(total_count)
+    }
 }
 
 #[cfg(test)]
@@ -509,7 +760,7 @@ mod tests {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let testdata = crate::test_util::arrow_test_data();
         let store_root = format!("{testdata}/avro");
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
         scan_format(state, &format, &store_root, file_name, projection, limit).await
     }
 }
@@ -527,7 +778,7 @@ mod tests {
     async fn test() -> Result<()> {
         let session_ctx = SessionContext::new();
         let state = session_ctx.state();
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
         let testdata = crate::test_util::arrow_test_data();
         let filename = "avro/alltypes_plain.avro";
         let result = scan_format(&state, &format, &testdata, filename, None, None).await;
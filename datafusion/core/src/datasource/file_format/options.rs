@@ -21,6 +21,8 @@ use std::sync::Arc;
 
 use crate::datasource::file_format::arrow::ArrowFormat;
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
+#[cfg(feature = "orc")]
+use crate::datasource::file_format::orc::OrcFormat;
 #[cfg(feature = "parquet")]
 use crate::datasource::file_format::parquet::ParquetFormat;
 use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
@@ -36,7 +38,7 @@ use arrow::datatypes::{DataType, Schema, SchemaRef};
 use datafusion_common::config::TableOptions;
 use datafusion_common::{
     DEFAULT_ARROW_EXTENSION, DEFAULT_AVRO_EXTENSION, DEFAULT_CSV_EXTENSION,
-    DEFAULT_JSON_EXTENSION, DEFAULT_PARQUET_EXTENSION,
+    DEFAULT_JSON_EXTENSION, DEFAULT_ORC_EXTENSION, DEFAULT_PARQUET_EXTENSION,
 };
 
 use async_trait::async_trait;
@@ -389,6 +391,51 @@ impl<'a> AvroReadOptions<'a> {
     }
 }
 
+/// Options that control the reading of ORC files.
+///
+/// Note this structure is supplied when a datasource is created and
+/// can not not vary from statement to statement. For settings that
+/// can vary statement to statement see
+/// [`ConfigOptions`](crate::config::ConfigOptions).
+#[derive(Clone)]
+pub struct OrcReadOptions<'a> {
+    /// The data source schema.
+    pub schema: Option<&'a Schema>,
+
+    /// File extension; only files with this extension are selected for data input.
+    /// Defaults to `DEFAULT_ORC_EXTENSION`.
+    pub file_extension: &'a str,
+    /// Partition Columns
+    pub table_partition_cols: Vec<(String, DataType)>,
+}
+
+impl<'a> Default for OrcReadOptions<'a> {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            file_extension: DEFAULT_ORC_EXTENSION,
+            table_partition_cols: vec![],
+        }
+    }
+}
+
+impl<'a> OrcReadOptions<'a> {
+    /// Specify table_partition_cols for partition pruning
+    pub fn table_partition_cols(
+        mut self,
+        table_partition_cols: Vec<(String, DataType)>,
+    ) -> Self {
+        self.table_partition_cols = table_partition_cols;
+        self
+    }
+
+    /// Specify schema to use for ORC read
+    pub fn schema(mut self, schema: &'a Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+}
+
 /// Options that control the reading of Line-delimited JSON files (NDJson)
 ///
 /// Note this structure is supplied when a datasource is created and
@@ -615,12 +662,39 @@ impl ReadOptions<'_> for NdJsonReadOptions<'_> {
 
 #[async_trait]
 impl ReadOptions<'_> for AvroReadOptions<'_> {
+    fn to_listing_options(
+        &self,
+        config: &SessionConfig,
+        table_options: TableOptions,
+    ) -> ListingOptions {
+        let file_format = AvroFormat::default().with_options(table_options.avro);
+
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_target_partitions(config.target_partitions())
+            .with_table_partition_cols(self.table_partition_cols.clone())
+    }
+
+    async fn get_resolved_schema(
+        &self,
+        config: &SessionConfig,
+        state: SessionState,
+        table_path: ListingTableUrl,
+    ) -> Result<SchemaRef> {
+        self._get_resolved_schema(config, state, table_path, self.schema)
+            .await
+    }
+}
+
+#[cfg(feature = "orc")]
+#[async_trait]
+impl ReadOptions<'_> for OrcReadOptions<'_> {
     fn to_listing_options(
         &self,
         config: &SessionConfig,
         _table_options: TableOptions,
     ) -> ListingOptions {
-        let file_format = AvroFormat;
+        let file_format = OrcFormat;
 
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
@@ -0,0 +1,458 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`OrcFormat`]: Apache ORC [`FileFormat`] abstractions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::stats::Precision;
+use datafusion_common::{
+    internal_err, DataFusionError, GetExt, ScalarValue, DEFAULT_ORC_EXTENSION,
+};
+use datafusion_physical_expr::PhysicalExpr;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use object_store::{ObjectMeta, ObjectStore};
+use orc_rust::reader::metadata::{read_metadata_async, FileMetadata};
+use orc_rust::reader::AsyncChunkReader;
+use orc_rust::schema::RootDataType;
+use orc_rust::statistics::{ColumnStatistics as OrcColumnStatistics, TypeStatistics};
+
+use super::file_compression_type::FileCompressionType;
+use super::{FileFormat, FileFormatFactory};
+use crate::datasource::physical_plan::{FileScanConfig, OrcExec};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::physical_plan::{ExecutionPlan, Statistics};
+
+#[derive(Default)]
+/// Factory struct used to create [OrcFormat]
+pub struct OrcFormatFactory;
+
+impl OrcFormatFactory {
+    /// Creates an instance of [OrcFormatFactory]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FileFormatFactory for OrcFormatFactory {
+    fn create(
+        &self,
+        _state: &SessionState,
+        _format_options: &HashMap<String, String>,
+    ) -> Result<Arc<dyn FileFormat>> {
+        Ok(Arc::new(OrcFormat))
+    }
+
+    fn default(&self) -> Arc<dyn FileFormat> {
+        Arc::new(OrcFormat)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl fmt::Debug for OrcFormatFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrcFormatFactory").finish()
+    }
+}
+
+impl GetExt for OrcFormatFactory {
+    fn get_ext(&self) -> String {
+        // Removes the dot, i.e. ".orc" -> "orc"
+        DEFAULT_ORC_EXTENSION[1..].to_string()
+    }
+}
+
+/// Apache ORC `FileFormat` implementation.
+///
+/// The schema is read from the file footer, and the file and stripe
+/// statistics stored there are used for [`Statistics`] and to skip stripes
+/// that cannot match the scan predicate.
+#[derive(Default, Debug)]
+pub struct OrcFormat;
+
+#[async_trait]
+impl FileFormat for OrcFormat {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_ext(&self) -> String {
+        OrcFormatFactory::new().get_ext()
+    }
+
+    fn get_ext_with_compression(
+        &self,
+        file_compression_type: &FileCompressionType,
+    ) -> Result<String> {
+        let ext = self.get_ext();
+        match file_compression_type.get_variant() {
+            CompressionTypeVariant::UNCOMPRESSED => Ok(ext),
+            _ => internal_err!("ORC FileFormat does not support compression."),
+        }
+    }
+
+    async fn infer_schema(
+        &self,
+        _state: &SessionState,
+        store: &Arc<dyn ObjectStore>,
+        objects: &[ObjectMeta],
+    ) -> Result<SchemaRef> {
+        let mut schemas = vec![];
+        for object in objects {
+            let metadata = fetch_orc_metadata(store, object).await?;
+            schemas.push(orc_arrow_schema(&metadata));
+        }
+        let merged_schema = Schema::try_merge(schemas)?;
+        Ok(Arc::new(merged_schema))
+    }
+
+    async fn infer_stats(
+        &self,
+        _state: &SessionState,
+        store: &Arc<dyn ObjectStore>,
+        table_schema: SchemaRef,
+        object: &ObjectMeta,
+    ) -> Result<Statistics> {
+        let metadata = fetch_orc_metadata(store, object).await?;
+        Ok(statistics_from_orc_metadata(&metadata, &table_schema))
+    }
+
+    async fn create_physical_plan(
+        &self,
+        _state: &SessionState,
+        conf: FileScanConfig,
+        filters: Option<&Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut exec = OrcExec::new(conf);
+        if let Some(predicate) = filters {
+            exec = exec.with_predicate(Arc::clone(predicate));
+        }
+        Ok(Arc::new(exec))
+    }
+}
+
+/// An [`AsyncChunkReader`] reading an ORC file from an [`ObjectStore`]
+pub(crate) struct ObjectStoreOrcReader {
+    store: Arc<dyn ObjectStore>,
+    file: ObjectMeta,
+}
+
+impl ObjectStoreOrcReader {
+    pub(crate) fn new(store: Arc<dyn ObjectStore>, file: ObjectMeta) -> Self {
+        Self { store, file }
+    }
+}
+
+impl AsyncChunkReader for ObjectStoreOrcReader {
+    fn len(&mut self) -> BoxFuture<'_, std::io::Result<u64>> {
+        let len = self.file.size as u64;
+        async move { Ok(len) }.boxed()
+    }
+
+    fn get_bytes(
+        &mut self,
+        offset_from_start: u64,
+        length: u64,
+    ) -> BoxFuture<'_, std::io::Result<Bytes>> {
+        let range = offset_from_start as usize..(offset_from_start + length) as usize;
+        async move {
+            self.store
+                .get_range(&self.file.location, range)
+                .await
+                .map_err(std::io::Error::other)
+        }
+        .boxed()
+    }
+}
+
+/// Reads the footer [`FileMetadata`] of the ORC file `object`
+pub(crate) async fn fetch_orc_metadata(
+    store: &Arc<dyn ObjectStore>,
+    object: &ObjectMeta,
+) -> Result<FileMetadata> {
+    let mut reader = ObjectStoreOrcReader::new(Arc::clone(store), object.clone());
+    read_metadata_async(&mut reader)
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+/// Returns the arrow schema the ORC reader produces for the file described by `metadata`
+pub(crate) fn orc_arrow_schema(metadata: &FileMetadata) -> Schema {
+    let user_metadata = metadata
+        .user_custom_metadata()
+        .iter()
+        .map(|(key, value)| (key.clone(), String::from_utf8_lossy(value).to_string()))
+        .collect::<HashMap<_, _>>();
+    metadata
+        .root_data_type()
+        .create_arrow_schema(&user_metadata)
+}
+
+/// Returns the ORC column index of the top level column `name`, if the file has one
+pub(crate) fn orc_column_index(root: &RootDataType, name: &str) -> Option<usize> {
+    root.children()
+        .iter()
+        .find(|column| column.name() == name)
+        .map(|column| column.data_type().column_index())
+}
+
+/// Converts the minimum and maximum recorded in the ORC statistics of a
+/// column with `number_of_values` non null values to [`ScalarValue`]s of
+/// `data_type`.
+///
+/// Returns `None` if the statistics hold no usable bounds, for instance
+/// because the column only contains nulls or has a type whose bounds are
+/// not tracked precisely enough to be used for pruning.
+pub(crate) fn orc_min_max(
+    type_statistics: &TypeStatistics,
+    number_of_values: u64,
+    data_type: &DataType,
+) -> Option<(ScalarValue, ScalarValue)> {
+    if number_of_values == 0 {
+        return None;
+    }
+    let (min, max) = match type_statistics {
+        TypeStatistics::Integer { min, max, .. } => (
+            ScalarValue::Int64(Some(*min)),
+            ScalarValue::Int64(Some(*max)),
+        ),
+        TypeStatistics::Double { min, max, .. } => (
+            ScalarValue::Float64(Some(*min)),
+            ScalarValue::Float64(Some(*max)),
+        ),
+        // Writers store truncated bounds of long strings separately, which
+        // leaves the maximum empty. An empty maximum is thus not a valid
+        // upper bound.
+        TypeStatistics::String { min, max, .. } if !max.is_empty() => (
+            ScalarValue::Utf8(Some(min.clone())),
+            ScalarValue::Utf8(Some(max.clone())),
+        ),
+        TypeStatistics::Bucket { true_count } => (
+            ScalarValue::Boolean(Some(*true_count == number_of_values)),
+            ScalarValue::Boolean(Some(*true_count > 0)),
+        ),
+        TypeStatistics::Date { min, max } => (
+            ScalarValue::Date32(Some(*min)),
+            ScalarValue::Date32(Some(*max)),
+        ),
+        // Timestamp bounds are truncated to milliseconds and decimal bounds
+        // are strings, neither is used.
+        _ => return None,
+    };
+    Some((min.cast_to(data_type).ok()?, max.cast_to(data_type).ok()?))
+}
+
+/// Converts the bounds of ORC column statistics, see [`orc_min_max`]
+pub(crate) fn orc_column_min_max(
+    stats: &OrcColumnStatistics,
+    data_type: &DataType,
+) -> Option<(ScalarValue, ScalarValue)> {
+    orc_min_max(
+        stats.type_statistics()?,
+        stats.number_of_values(),
+        data_type,
+    )
+}
+
+/// Converts the file level statistics of an ORC file to [`Statistics`] for `table_schema`
+pub(crate) fn statistics_from_orc_metadata(
+    metadata: &FileMetadata,
+    table_schema: &SchemaRef,
+) -> Statistics {
+    let num_rows = metadata.number_of_rows() as usize;
+    let mut statistics = Statistics::new_unknown(table_schema);
+    statistics.num_rows = Precision::Exact(num_rows);
+
+    let file_statistics = metadata.column_file_statistics();
+    for (field, column_statistics) in table_schema
+        .fields()
+        .iter()
+        .zip(statistics.column_statistics.iter_mut())
+    {
+        let Some(stats) = orc_column_index(metadata.root_data_type(), field.name())
+            .and_then(|index| file_statistics.get(index))
+        else {
+            continue;
+        };
+        let null_count = num_rows.saturating_sub(stats.number_of_values() as usize);
+        column_statistics.null_count = Precision::Exact(null_count);
+        if let Some((min, max)) = orc_column_min_max(stats, field.data_type()) {
+            column_statistics.min_value = Precision::Exact(min);
+            column_statistics.max_value = Precision::Exact(max);
+        }
+    }
+    statistics
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::datasource::listing::PartitionedFile;
+    use crate::datasource::object_store::ObjectStoreUrl;
+    use crate::physical_plan::collect;
+    use crate::prelude::SessionContext;
+
+    use arrow::array::{Int32Array, RecordBatch, StringArray};
+    use arrow::datatypes::Field;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::PutPayload;
+    use orc_rust::ArrowWriterBuilder;
+
+    /// Writes `batches` as a single ORC file to `store` at `path`, each
+    /// batch in its own stripe
+    pub(crate) async fn write_orc_file(
+        store: &Arc<dyn ObjectStore>,
+        path: &str,
+        batches: &[RecordBatch],
+    ) -> Result<ObjectMeta> {
+        let mut buffer = vec![];
+        let mut writer = ArrowWriterBuilder::new(&mut buffer, batches[0].schema())
+            .try_build()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        for batch in batches {
+            writer
+                .write(batch)
+                .and_then(|_| writer.flush_stripe())
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+        writer
+            .close()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        let location = Path::from(path);
+        store.put(&location, PutPayload::from(buffer)).await?;
+        Ok(store.head(&location).await?)
+    }
+
+    pub(crate) fn test_batch() -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2, 3, 4])) as _),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    None,
+                    Some("c"),
+                    Some("d"),
+                ])) as _,
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn infer_schema_and_stats() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let meta = write_orc_file(&store, "test.orc", &[test_batch()]).await?;
+
+        let schema = OrcFormat
+            .infer_schema(&state, &store, &[meta.clone()])
+            .await?;
+        let expected = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        assert_eq!(schema.as_ref(), &expected);
+
+        let stats = OrcFormat
+            .infer_stats(&state, &store, Arc::clone(&schema), &meta)
+            .await?;
+        assert_eq!(stats.num_rows, Precision::Exact(4));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_with_projection() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        state
+            .runtime_env()
+            .register_object_store(url.as_ref(), Arc::clone(&store));
+        let meta = write_orc_file(&store, "test.orc", &[test_batch()]).await?;
+
+        let schema = OrcFormat
+            .infer_schema(&state, &store, &[meta.clone()])
+            .await?;
+        let conf = FileScanConfig::new(url, schema)
+            .with_file(PartitionedFile::from(meta))
+            .with_projection(Some(vec![1]));
+        let exec = OrcFormat.create_physical_plan(&state, conf, None).await?;
+        let batches = collect(exec, state.task_ctx()).await?;
+
+        let expected = [
+            "+------+", "| name |", "+------+", "| a    |", "|      |", "| c    |",
+            "| d    |", "+------+",
+        ];
+        crate::assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn min_max_from_orc_statistics() {
+        let stats = TypeStatistics::Integer {
+            min: -1,
+            max: 7,
+            sum: None,
+        };
+        assert_eq!(
+            orc_min_max(&stats, 3, &DataType::Int32),
+            Some((ScalarValue::Int32(Some(-1)), ScalarValue::Int32(Some(7))))
+        );
+
+        let stats = TypeStatistics::Bucket { true_count: 2 };
+        assert_eq!(
+            orc_min_max(&stats, 2, &DataType::Boolean),
+            Some((
+                ScalarValue::Boolean(Some(true)),
+                ScalarValue::Boolean(Some(true))
+            ))
+        );
+
+        // truncated string bounds are not used
+        let stats = TypeStatistics::String {
+            min: "a".to_string(),
+            max: String::new(),
+            sum: 10,
+        };
+        assert_eq!(orc_min_max(&stats, 2, &DataType::Utf8), None);
+
+        // all null columns have no bounds
+        let stats = TypeStatistics::Integer {
+            min: 0,
+            max: 0,
+            sum: None,
+        };
+        assert_eq!(orc_min_max(&stats, 0, &DataType::Int64), None);
+    }
+}
@@ -18,13 +18,14 @@
 //! The table implementation.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{any::Any, str::FromStr, sync::Arc};
 
 use super::helpers::{expr_applicable_for_cols, pruned_partition_list, split_files};
 use super::{ListingTableUrl, PartitionedFile};
 
 use crate::datasource::{
-    create_ordering,
+    analyze_column, create_ordering,
     file_format::{
         file_compression_type::FileCompressionType, FileFormat, FilePushdownSupport,
     },
@@ -37,10 +38,13 @@ use datafusion_common::{config_err, DataFusionError, Result};
 use datafusion_expr::dml::InsertOp;
 use datafusion_expr::{utils::conjunction, Expr, TableProviderFilterPushDown};
 use datafusion_expr::{SortExpr, TableType};
-use datafusion_physical_plan::{empty::EmptyExec, ExecutionPlan, Statistics};
+use datafusion_physical_plan::{collect, empty::EmptyExec, ExecutionPlan, Statistics};
 
+use arrow::array::new_empty_array;
+use arrow::compute::concat;
 use arrow::datatypes::{DataType, Field, SchemaBuilder, SchemaRef};
 use arrow_schema::Schema;
+use datafusion_common::stats::Precision;
 use datafusion_common::{
     config_datafusion_err, internal_err, plan_err, project_schema, Constraints,
     SchemaExt, ToDFSchema,
@@ -689,6 +693,8 @@ pub struct ListingTable {
     options: ListingOptions,
     definition: Option<String>,
     collected_statistics: FileStatisticsCache,
+    /// Whether the statistics of the files were computed by [`Self::analyze`]
+    analyzed: AtomicBool,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
 }
@@ -732,6 +738,7 @@ impl ListingTable {
             options,
             definition: None,
             collected_statistics: Arc::new(DefaultFileStatisticsCache::default()),
+            analyzed: AtomicBool::new(false),
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
         };
@@ -1082,11 +1089,14 @@ impl ListingTable {
         }))
         .await?;
         let file_list = stream::iter(file_list).flatten();
-        // collect the statistics if required by the config
+        // collect the statistics if required by the config, or if they were
+        // computed by `ANALYZE TABLE`
+        let collect_stat =
+            self.options.collect_stat || self.analyzed.load(Ordering::Relaxed);
         let files = file_list
             .map(|part_file| async {
                 let part_file = part_file?;
-                if self.options.collect_stat {
+                if collect_stat {
                     let statistics =
                         self.do_collect_statistics(ctx, &store, &part_file).await?;
                     Ok((part_file, statistics))
@@ -1100,13 +1110,8 @@ impl ListingTable {
             .boxed()
             .buffered(ctx.config_options().execution.meta_fetch_concurrency);
 
-        let (files, statistics) = get_statistics_with_limit(
-            files,
-            self.schema(),
-            limit,
-            self.options.collect_stat,
-        )
-        .await?;
+        let (files, statistics) =
+            get_statistics_with_limit(files, self.schema(), limit, collect_stat).await?;
 
         Ok((
             split_files(files, self.options.target_partitions),
@@ -1114,6 +1119,104 @@ impl ListingTable {
         ))
     }
 
+    /// Computes the exact statistics of every file of the table by reading
+    /// them, including the histograms and most common values of the given
+    /// `columns`, or of all the columns if `columns` is empty.
+    ///
+    /// The statistics are stored in the statistics cache of the table and are
+    /// used to plan the following scans, even if
+    /// [`ListingOptions::collect_stat`] is false.
+    pub async fn analyze(&self, state: &SessionState, columns: &[String]) -> Result<()> {
+        for column in columns {
+            if self.file_schema.field_with_name(column).is_err() {
+                return plan_err!(
+                    "Column '{column}' is not a column of the files of the table"
+                );
+            }
+        }
+        let Some(table_path) = self.table_paths.first() else {
+            return Ok(());
+        };
+        let store = state.runtime_env().object_store(table_path)?;
+        let execution = &state.config_options().execution;
+
+        for table_path in &self.table_paths {
+            let files = pruned_partition_list(
+                state,
+                store.as_ref(),
+                table_path,
+                &[],
+                &self.options.file_extension,
+                &self.options.table_partition_cols,
+            )
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+            for mut part_file in files {
+                part_file.partition_values.clear();
+                let object_meta = part_file.object_meta.clone();
+                let config = FileScanConfig::new(
+                    table_path.object_store(),
+                    Arc::clone(&self.file_schema),
+                )
+                .with_file(part_file);
+                let plan = self
+                    .options
+                    .format
+                    .create_physical_plan(state, config, None)
+                    .await?;
+                let batches = collect(plan, state.task_ctx()).await?;
+
+                let column_statistics = self
+                    .file_schema
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        let arrays = batches
+                            .iter()
+                            .map(|batch| batch.column(index).as_ref())
+                            .collect::<Vec<_>>();
+                        let values = if arrays.is_empty() {
+                            new_empty_array(field.data_type())
+                        } else {
+                            concat(&arrays)?
+                        };
+                        let (num_buckets, num_most_common_values) =
+                            if columns.is_empty() || columns.contains(field.name()) {
+                                (
+                                    execution.analyze_histogram_buckets,
+                                    execution.analyze_most_common_values,
+                                )
+                            } else {
+                                (0, 0)
+                            };
+                        analyze_column(&values, num_buckets, num_most_common_values)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let statistics = Statistics {
+                    num_rows: Precision::Exact(
+                        batches.iter().map(|batch| batch.num_rows()).sum(),
+                    ),
+                    total_byte_size: Precision::Inexact(
+                        batches
+                            .iter()
+                            .map(|batch| batch.get_array_memory_size())
+                            .sum(),
+                    ),
+                    column_statistics,
+                };
+                self.collected_statistics.put_with_extra(
+                    &object_meta.location,
+                    Arc::new(statistics),
+                    &object_meta,
+                );
+            }
+        }
+        self.analyzed.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
     /// Collects statistics for a given partitioned file.
     ///
     /// This method first checks if the statistics for the given file are already cached.
@@ -1350,8 +1453,8 @@ mod tests {
         let path = String::from("table/p1=v1/file.avro");
         register_test_store(&ctx, &[(&path, 100)]);
 
-        let opt = ListingOptions::new(Arc::new(AvroFormat {}))
-            .with_file_extension(AvroFormat.get_ext())
+        let opt = ListingOptions::new(Arc::new(AvroFormat::default()))
+            .with_file_extension(AvroFormat::default().get_ext())
             .with_table_partition_cols(vec![(String::from("p1"), DataType::Utf8)])
             .with_target_partitions(4);
 
@@ -1594,7 +1697,7 @@ mod tests {
         let ctx = SessionContext::new();
         register_test_store(&ctx, &files.iter().map(|f| (*f, 10)).collect::<Vec<_>>());
 
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
 
         let opt = ListingOptions::new(Arc::new(format))
             .with_file_extension_opt(file_ext)
@@ -1628,7 +1731,7 @@ mod tests {
         let ctx = SessionContext::new();
         register_test_store(&ctx, &files.iter().map(|f| (*f, 10)).collect::<Vec<_>>());
 
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
 
         let opt = ListingOptions::new(Arc::new(format))
             .with_file_extension_opt(file_ext)
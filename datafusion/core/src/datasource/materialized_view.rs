@@ -0,0 +1,521 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Materialized view data source, which stores the results of a LogicalPlan.
+
+use std::{any::Any, borrow::Cow, sync::Arc};
+
+use crate::{
+    datasource::{
+        listing::{ListingTable, ListingTableConfig, ListingTableUrl},
+        provider_as_source, source_as_provider, MemTable, TableProvider, TableType,
+    },
+    error::Result,
+    execution::context::SessionState,
+    logical_expr::{Expr, LogicalPlan},
+    physical_plan::{collect, ExecutionPlan},
+};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion_catalog::Session;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{plan_err, DFSchema, Statistics};
+use datafusion_expr::dml::InsertOp;
+use datafusion_expr::{TableProviderFilterPushDown, TableScan};
+use futures::TryStreamExt;
+use object_store::ObjectMeta;
+use tokio::sync::Mutex;
+
+/// An implementation of `TableProvider` that stores the results of a logical
+/// plan in another table, and recomputes them when refreshed.
+///
+/// The results are stored in a [`MemTable`], a [`ListingTable`], or any other
+/// table which supports `insert_into` and `delete_from`.
+#[derive(Debug)]
+pub struct MaterializedView {
+    /// LogicalPlan of the view
+    logical_plan: LogicalPlan,
+    /// SQL used to create the view, if available
+    definition: Option<String>,
+    /// Table storing the results of the view
+    storage: Arc<dyn TableProvider>,
+    /// What was read from each source of the view by the last refresh, if
+    /// the view can be refreshed incrementally
+    snapshot: Mutex<Option<Vec<SourceSnapshot>>>,
+}
+
+impl MaterializedView {
+    /// Create a new materialized view storing the results of `logical_plan`
+    /// in `storage`, which must have the same schema as the plan.
+    ///
+    /// The view is empty until it is first refreshed.
+    pub fn try_new(
+        logical_plan: LogicalPlan,
+        definition: Option<String>,
+        storage: Arc<dyn TableProvider>,
+    ) -> Result<Self> {
+        let storage_schema = storage.schema();
+        let plan_schema = logical_plan.schema().as_arrow();
+        let matches = storage_schema.fields().len() == plan_schema.fields().len()
+            && storage_schema
+                .fields()
+                .iter()
+                .zip(plan_schema.fields())
+                .all(|(storage_field, plan_field)| {
+                    storage_field.name() == plan_field.name()
+                        && DFSchema::datatype_is_logically_equal(
+                            storage_field.data_type(),
+                            plan_field.data_type(),
+                        )
+                });
+        if !matches {
+            return plan_err!(
+                "The schema of the materialized view storage {storage_schema} \
+                does not match the schema of its definition {plan_schema}"
+            );
+        }
+
+        Ok(Self {
+            logical_plan,
+            definition,
+            storage,
+            snapshot: Mutex::new(None),
+        })
+    }
+
+    /// Get definition ref
+    pub fn definition(&self) -> Option<&String> {
+        self.definition.as_ref()
+    }
+
+    /// Get logical_plan ref
+    pub fn logical_plan(&self) -> &LogicalPlan {
+        &self.logical_plan
+    }
+
+    /// Get the table storing the results of the view
+    pub fn storage(&self) -> &Arc<dyn TableProvider> {
+        &self.storage
+    }
+
+    /// Returns true if the view can be refreshed incrementally, which is the
+    /// case when its definition only projects, filters and unions the rows
+    /// of [`MemTable`]s and unpartitioned [`ListingTable`]s.
+    pub fn supports_incremental_refresh(&self) -> bool {
+        incremental_sources(&self.logical_plan).is_some()
+    }
+
+    /// Recompute the stored results of the view.
+    ///
+    /// If `incremental` is true, only the results for the rows appended to
+    /// the sources of the view since its last refresh are added to the
+    /// stored results, see [`Self::supports_incremental_refresh`]. All
+    /// results are recomputed if rows of a source were changed otherwise.
+    pub async fn refresh(&self, state: &SessionState, incremental: bool) -> Result<()> {
+        let mut snapshot = self.snapshot.lock().await;
+
+        let Some(sources) = incremental_sources(&self.logical_plan) else {
+            if incremental {
+                return plan_err!(
+                    "Materialized view can not be refreshed incrementally, \
+                    its definition may only project, filter and union the rows of \
+                    in-memory tables and unpartitioned listing tables"
+                );
+            }
+            let state = without_rewrite(state);
+            self.truncate(&state).await?;
+            return self.append(&state, self.logical_plan.clone()).await;
+        };
+
+        let state = without_rewrite(state);
+        let mut current = Vec::with_capacity(sources.len());
+        for source in &sources {
+            current.push(SourceSnapshot::try_new(&state, source).await?);
+        }
+
+        // Forget the previous snapshot until the refresh completes, so that
+        // the next refresh is a full one if this one fails
+        let previous = snapshot.take().filter(|_| incremental);
+        let deltas = match previous {
+            Some(previous) => previous
+                .iter()
+                .zip(&current)
+                .map(|(previous, current)| current.delta(previous))
+                .collect::<Result<Option<Vec<_>>>>()?,
+            None => None,
+        };
+        let inputs = match deltas {
+            Some(deltas) => deltas,
+            None => {
+                self.truncate(&state).await?;
+                current
+                    .iter()
+                    .map(SourceSnapshot::full)
+                    .collect::<Result<_>>()?
+            }
+        };
+
+        let plan = replace_sources(self.logical_plan.clone(), inputs)?;
+        self.append(&state, plan).await?;
+        *snapshot = Some(current);
+        Ok(())
+    }
+
+    /// Remove all stored results
+    async fn truncate(&self, state: &SessionState) -> Result<()> {
+        if let Some(table) = self.storage.as_any().downcast_ref::<MemTable>() {
+            for partition in &table.batches {
+                partition.write().await.clear();
+            }
+            return Ok(());
+        }
+
+        if let Some(table) = self.storage.as_any().downcast_ref::<ListingTable>() {
+            let extension = &table.options().file_extension;
+            for table_path in table.table_paths() {
+                let store = state.runtime_env().object_store(table_path)?;
+                let files: Vec<_> = table_path
+                    .list_all_files(state, store.as_ref(), extension)
+                    .await?
+                    .try_collect()
+                    .await?;
+                for file in files {
+                    store.delete(&file.location).await?;
+                }
+            }
+            return Ok(());
+        }
+
+        let plan = self.storage.delete_from(state, vec![]).await?;
+        collect(plan, state.task_ctx()).await?;
+        Ok(())
+    }
+
+    /// Append the results of `plan` to the stored results
+    async fn append(&self, state: &SessionState, plan: LogicalPlan) -> Result<()> {
+        let plan = state.create_physical_plan(&plan).await?;
+        let plan = self
+            .storage
+            .insert_into(state, plan, InsertOp::Append)
+            .await?;
+        collect(plan, state.task_ctx()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TableProvider for MaterializedView {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_logical_plan(&self) -> Option<Cow<LogicalPlan>> {
+        // The view is read from its storage, rather than inlined
+        None
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.storage.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.definition.as_deref()
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.storage.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.storage.statistics()
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.storage.scan(state, projection, filters, limit).await
+    }
+}
+
+/// Returns a copy of `state` which does not rewrite queries to read from
+/// materialized views, so that a view is never computed from its own results
+fn without_rewrite(state: &SessionState) -> SessionState {
+    let mut state = state.clone();
+    state
+        .config_mut()
+        .options_mut()
+        .optimizer
+        .enable_materialized_view_rewrite = false;
+    state
+}
+
+/// Returns the tables scanned by `plan`, in the order they are visited, if
+/// the results of `plan` for the rows appended to them can be computed from
+/// the appended rows alone.
+fn incremental_sources(plan: &LogicalPlan) -> Option<Vec<Arc<dyn TableProvider>>> {
+    let mut sources = vec![];
+    let mut supported = true;
+    plan.apply(|node| {
+        supported = match node {
+            LogicalPlan::Projection(_)
+            | LogicalPlan::Filter(_)
+            | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::Union(_) => {
+                let mut row_wise = true;
+                for expr in node.expressions() {
+                    row_wise &=
+                        !expr.is_volatile() && !expr.exists(|e| Ok(is_subquery(e)))?;
+                }
+                row_wise
+            }
+            LogicalPlan::TableScan(scan) if scan.fetch.is_none() => {
+                match source_as_provider(&scan.source) {
+                    Ok(provider) if is_incremental_source(provider.as_ref()) => {
+                        sources.push(provider);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+        Ok(if supported {
+            TreeNodeRecursion::Continue
+        } else {
+            TreeNodeRecursion::Stop
+        })
+    })
+    .ok()?;
+    supported.then_some(sources)
+}
+
+fn is_subquery(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::ScalarSubquery(_) | Expr::Exists(_) | Expr::InSubquery(_)
+    )
+}
+
+fn is_incremental_source(provider: &dyn TableProvider) -> bool {
+    if provider.as_any().is::<MemTable>() {
+        return true;
+    }
+    provider
+        .as_any()
+        .downcast_ref::<ListingTable>()
+        .is_some_and(|table| table.options().table_partition_cols.is_empty())
+}
+
+/// Replace the source of each table scan in `plan` with the next of `inputs`,
+/// in the order returned by [`incremental_sources`]
+fn replace_sources(
+    plan: LogicalPlan,
+    inputs: Vec<Arc<dyn TableProvider>>,
+) -> Result<LogicalPlan> {
+    let mut inputs = inputs.into_iter();
+    plan.transform_down(|node| match node {
+        LogicalPlan::TableScan(scan) => {
+            let Some(input) = inputs.next() else {
+                return plan_err!("Materialized view sources changed during refresh");
+            };
+            Ok(Transformed::yes(LogicalPlan::TableScan(TableScan {
+                source: provider_as_source(input),
+                ..scan
+            })))
+        }
+        _ => Ok(Transformed::no(node)),
+    })
+    .map(|transformed| transformed.data)
+}
+
+/// The rows read from a source of a materialized view by a refresh
+#[derive(Debug)]
+enum SourceSnapshot {
+    /// The batches of each partition of a [`MemTable`]
+    Memory {
+        schema: SchemaRef,
+        partitions: Vec<Vec<RecordBatch>>,
+    },
+    /// The files of a [`ListingTable`]
+    Listing {
+        table: Arc<dyn TableProvider>,
+        files: Vec<(ListingTableUrl, ObjectMeta)>,
+    },
+}
+
+impl SourceSnapshot {
+    async fn try_new(
+        state: &SessionState,
+        provider: &Arc<dyn TableProvider>,
+    ) -> Result<Self> {
+        if let Some(table) = provider.as_any().downcast_ref::<MemTable>() {
+            let mut partitions = Vec::with_capacity(table.batches.len());
+            for partition in &table.batches {
+                partitions.push(partition.read().await.clone());
+            }
+            return Ok(Self::Memory {
+                schema: table.schema(),
+                partitions,
+            });
+        }
+
+        let Some(table) = provider.as_any().downcast_ref::<ListingTable>() else {
+            return plan_err!(
+                "Materialized view source can not be refreshed incrementally"
+            );
+        };
+        let extension = &table.options().file_extension;
+        let mut files = vec![];
+        for table_path in table.table_paths() {
+            let store = state.runtime_env().object_store(table_path)?;
+            let metas: Vec<_> = table_path
+                .list_all_files(state, store.as_ref(), extension)
+                .await?
+                .try_collect()
+                .await?;
+            for meta in metas {
+                let url = ListingTableUrl::parse(format!(
+                    "{}{}",
+                    table_path.object_store().as_str(),
+                    meta.location
+                ))?;
+                files.push((url, meta));
+            }
+        }
+        Ok(Self::Listing {
+            table: Arc::clone(provider),
+            files,
+        })
+    }
+
+    /// Returns a table of all rows in this snapshot
+    fn full(&self) -> Result<Arc<dyn TableProvider>> {
+        match self {
+            Self::Memory { schema, partitions } => Ok(Arc::new(MemTable::try_new(
+                Arc::clone(schema),
+                partitions.clone(),
+            )?)),
+            Self::Listing { table, files } => listing_subset(table, files),
+        }
+    }
+
+    /// Returns a table of the rows appended since `previous`, or `None` if
+    /// rows of `previous` were changed or removed
+    fn delta(&self, previous: &Self) -> Result<Option<Arc<dyn TableProvider>>> {
+        match (previous, self) {
+            (
+                Self::Memory {
+                    partitions: previous,
+                    ..
+                },
+                Self::Memory { schema, partitions },
+            ) => {
+                if previous.len() != partitions.len() {
+                    return Ok(None);
+                }
+                let Some(appended) = previous
+                    .iter()
+                    .zip(partitions)
+                    .map(|(previous, current)| {
+                        let unchanged = previous.len() <= current.len()
+                            && previous
+                                .iter()
+                                .zip(current)
+                                .all(|(a, b)| same_batch(a, b));
+                        unchanged.then(|| current[previous.len()..].to_vec())
+                    })
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    return Ok(None);
+                };
+                Ok(Some(Arc::new(MemTable::try_new(
+                    Arc::clone(schema),
+                    appended,
+                )?)))
+            }
+            (
+                Self::Listing {
+                    files: previous, ..
+                },
+                Self::Listing { table, files },
+            ) => {
+                let unchanged = previous.iter().all(|(_, previous)| {
+                    files.iter().any(|(_, file)| {
+                        file.location == previous.location
+                            && file.size == previous.size
+                            && file.last_modified == previous.last_modified
+                    })
+                });
+                if !unchanged {
+                    return Ok(None);
+                }
+                let appended: Vec<_> = files
+                    .iter()
+                    .filter(|(_, file)| {
+                        !previous.iter().any(|(_, p)| p.location == file.location)
+                    })
+                    .cloned()
+                    .collect();
+                listing_subset(table, &appended).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Returns true if `a` and `b` share the same arrays, as is the case for
+/// batches left untouched by appends to a [`MemTable`]
+fn same_batch(a: &RecordBatch, b: &RecordBatch) -> bool {
+    a.num_rows() == b.num_rows()
+        && a.num_columns() == b.num_columns()
+        && a.columns()
+            .iter()
+            .zip(b.columns())
+            .all(|(a, b)| Arc::ptr_eq(a, b))
+}
+
+/// Returns a table reading only `files` of the listing table `table`
+fn listing_subset(
+    table: &Arc<dyn TableProvider>,
+    files: &[(ListingTableUrl, ObjectMeta)],
+) -> Result<Arc<dyn TableProvider>> {
+    let schema = table.schema();
+    let Some(listing) = table.as_any().downcast_ref::<ListingTable>() else {
+        return plan_err!("Expected a listing table");
+    };
+    if files.is_empty() {
+        return Ok(Arc::new(MemTable::try_new(schema, vec![vec![]])?));
+    }
+    let config = ListingTableConfig::new_with_multi_paths(
+        files.iter().map(|(url, _)| url.clone()).collect(),
+    )
+    .with_listing_options(listing.options().clone())
+    .with_schema(schema);
+    Ok(Arc::new(ListingTable::try_new(config)?))
+}
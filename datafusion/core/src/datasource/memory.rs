@@ -30,19 +30,37 @@ use crate::physical_plan::insert::{DataSink, DataSinkExec};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::repartition::RepartitionExec;
 use crate::physical_plan::{
-    common, DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
-    Partitioning, SendableRecordBatchStream,
+    collect, common, DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan,
+    ExecutionPlanProperties, Partitioning, PlanProperties, SendableRecordBatchStream,
 };
 use crate::physical_planner::create_physical_sort_exprs;
 
-use arrow::datatypes::SchemaRef;
+use arrow::array::{
+    new_null_array, Array, ArrayRef, BooleanArray, UInt32Array, UInt64Array,
+};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{
+    and, concat_batches, filter_record_batch, interleave, not, prep_null_mask_filter,
+    take_record_batch,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
 use datafusion_catalog::Session;
-use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
+use datafusion_common::cast::as_boolean_array;
+use datafusion_common::{
+    exec_err, internal_err, not_impl_err, plan_err, resources_err, Constraints, DFSchema,
+    DFSchemaRef, SchemaExt,
+};
 use datafusion_execution::TaskContext;
-use datafusion_expr::dml::InsertOp;
-use datafusion_expr::SortExpr;
+use datafusion_expr::dml::{InsertOp, MergeAction, MergeClauseKind, MergeOp};
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{Operator, SortExpr, WriteOp};
+use datafusion_physical_expr::expressions::BinaryExpr;
+use datafusion_physical_expr::utils::{collect_columns, split_conjunction};
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
 use datafusion_physical_plan::metrics::MetricsSet;
+use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
 
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -302,11 +320,128 @@ impl TableProvider for MemTable {
         )))
     }
 
+    /// Returns an ExecutionPlan that deletes the rows of this [`MemTable`]
+    /// matching all of the `filters`.
+    ///
+    /// The plan returns the number of rows deleted.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let predicate = self.create_predicate(state, filters)?;
+        Ok(Arc::new(MemDmlExec::new(
+            self.batches.clone(),
+            self.schema(),
+            WriteOp::Delete,
+            predicate,
+            vec![],
+        )))
+    }
+
+    /// Returns an ExecutionPlan that updates the rows of this [`MemTable`]
+    /// matching all of the `filters`.
+    ///
+    /// The plan returns the number of rows updated.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // If we are updating the table, any sort order may be messed up so reset it here
+        *self.sort_order.lock() = vec![];
+
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        let assignments = assignments
+            .into_iter()
+            .map(|(column, expr)| {
+                Ok((
+                    self.schema.index_of(&column)?,
+                    state.create_physical_expr(expr, &df_schema)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let predicate = self.create_predicate(state, filters)?;
+        Ok(Arc::new(MemDmlExec::new(
+            self.batches.clone(),
+            self.schema(),
+            WriteOp::Update,
+            predicate,
+            assignments,
+        )))
+    }
+
+    /// Returns an ExecutionPlan that merges the rows of `source` into this
+    /// [`MemTable`].
+    ///
+    /// The plan returns the number of rows inserted, updated and deleted.
+    async fn merge_into(
+        &self,
+        state: &dyn Session,
+        source: Arc<dyn ExecutionPlan>,
+        schema: DFSchemaRef,
+        merge: MergeOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // If we are changing the table, any sort order may be messed up so reset it here
+        *self.sort_order.lock() = vec![];
+
+        let create = |expr: Expr| state.create_physical_expr(expr, &schema);
+        let on = create(merge.on)?;
+        let clauses = merge
+            .clauses
+            .into_iter()
+            .map(|clause| {
+                let predicate = clause.predicate.map(create).transpose()?;
+                let action = match clause.action {
+                    MergeAction::Update(assignments) => MemMergeAction::Update(
+                        assignments
+                            .into_iter()
+                            .map(|(column, expr)| {
+                                Ok((self.schema.index_of(&column)?, create(expr)?))
+                            })
+                            .collect::<Result<_>>()?,
+                    ),
+                    MergeAction::Delete => MemMergeAction::Delete,
+                    MergeAction::Insert(values) => MemMergeAction::Insert(
+                        values.into_iter().map(create).collect::<Result<_>>()?,
+                    ),
+                };
+                Ok(MemMergeClause {
+                    kind: clause.kind,
+                    predicate,
+                    action,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Arc::new(MemMergeExec::new(
+            self.batches.clone(),
+            self.schema(),
+            source,
+            on,
+            clauses,
+        )))
+    }
+
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
 }
 
+impl MemTable {
+    /// Creates the predicate matching the rows of a `DELETE` or `UPDATE`
+    fn create_predicate(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        conjunction(filters)
+            .map(|predicate| state.create_physical_expr(predicate, &df_schema))
+            .transpose()
+    }
+}
+
 /// Implements for writing to a [`MemTable`]
 struct MemSink {
     /// Target locations for writing data
@@ -376,6 +511,828 @@ impl DataSink for MemSink {
     }
 }
 
+/// Properties of a plan returning a single row with the number of rows it
+/// changed in a UInt64 column called "count"
+fn count_plan_properties() -> PlanProperties {
+    let count_schema = Arc::new(Schema::new(vec![Field::new(
+        "count",
+        DataType::UInt64,
+        false,
+    )]));
+    PlanProperties::new(
+        EquivalenceProperties::new(count_schema),
+        Partitioning::UnknownPartitioning(1),
+        ExecutionMode::Bounded,
+    )
+}
+
+/// Evaluates `predicate` on `batch`. Rows for which the predicate is null
+/// don't match.
+fn evaluate_predicate(
+    predicate: &Arc<dyn PhysicalExpr>,
+    batch: &RecordBatch,
+) -> Result<BooleanArray> {
+    let array = predicate.evaluate(batch)?.into_array(batch.num_rows())?;
+    let matches = as_boolean_array(&array)?;
+    Ok(match matches.null_count() {
+        0 => matches.clone(),
+        _ => prep_null_mask_filter(matches),
+    })
+}
+
+/// Returns an error if `column` has nulls but `field` is not nullable
+fn check_not_null(field: &Field, column: &ArrayRef) -> Result<()> {
+    if !field.is_nullable() && column.null_count() > 0 {
+        return exec_err!(
+            "Invalid update of column '{}', which is not nullable, to null",
+            field.name()
+        );
+    }
+    Ok(())
+}
+
+/// Execution plan for deleting or updating the rows of a [`MemTable`]
+/// that match a predicate.
+///
+/// Returns a single row with the number of deleted or updated rows.
+#[derive(Clone, Debug)]
+struct MemDmlExec {
+    batches: Vec<PartitionData>,
+    /// Schema of the table
+    schema: SchemaRef,
+    /// Either [`WriteOp::Delete`] or [`WriteOp::Update`]
+    op: WriteOp,
+    /// Matches the rows to delete or update, all rows if `None`
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// The indices of the updated columns with their new values
+    assignments: Vec<(usize, Arc<dyn PhysicalExpr>)>,
+    cache: PlanProperties,
+}
+
+impl MemDmlExec {
+    fn new(
+        batches: Vec<PartitionData>,
+        schema: SchemaRef,
+        op: WriteOp,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        assignments: Vec<(usize, Arc<dyn PhysicalExpr>)>,
+    ) -> Self {
+        Self {
+            batches,
+            schema,
+            op,
+            predicate,
+            assignments,
+            cache: count_plan_properties(),
+        }
+    }
+
+    /// Applies the statement to `batch`, returning the resulting batch and
+    /// the number of rows it deleted or updated
+    fn apply(&self, batch: &RecordBatch) -> Result<(RecordBatch, usize)> {
+        let matches = match &self.predicate {
+            Some(predicate) => evaluate_predicate(predicate, batch)?,
+            None => BooleanArray::from(vec![true; batch.num_rows()]),
+        };
+        let count = matches.true_count();
+        if count == 0 {
+            return Ok((batch.clone(), 0));
+        }
+
+        if self.op == WriteOp::Delete {
+            return Ok((filter_record_batch(batch, &not(&matches)?)?, count));
+        }
+        let mut columns = batch.columns().to_vec();
+        for (index, expr) in &self.assignments {
+            let value = expr.evaluate(batch)?.into_array(batch.num_rows())?;
+            let column = zip(&matches, &value, &batch.column(*index))?;
+            check_not_null(self.schema.field(*index), &column)?;
+            columns[*index] = column;
+        }
+        Ok((RecordBatch::try_new(batch.schema(), columns)?, count))
+    }
+}
+
+impl DisplayAs for MemDmlExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "MemDmlExec: op={}, partitions={}",
+                    self.op,
+                    self.batches.len()
+                )?;
+                if !self.assignments.is_empty() {
+                    let assignments = self
+                        .assignments
+                        .iter()
+                        .map(|(index, expr)| {
+                            format!("{} = {expr}", self.schema.field(*index).name())
+                        })
+                        .collect::<Vec<_>>();
+                    write!(f, ", assignments=[{}]", assignments.join(", "))?;
+                }
+                if let Some(predicate) = &self.predicate {
+                    write!(f, ", predicate={predicate}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for MemDmlExec {
+    fn name(&self) -> &'static str {
+        "MemDmlExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!("MemDmlExec can only be called on partition 0!");
+        }
+        let exec = self.clone();
+        let count_schema = self.schema();
+        let schema = Arc::clone(&count_schema);
+
+        let stream = futures::stream::once(async move {
+            // Lock all partitions before changing any of them, so that the
+            // statement either changes all of them or none if it fails
+            let mut partitions = Vec::with_capacity(exec.batches.len());
+            for partition in &exec.batches {
+                partitions.push(partition.write().await);
+            }
+            let mut count = 0;
+            let mut new_partitions = Vec::with_capacity(partitions.len());
+            for partition in &partitions {
+                let mut new_batches = Vec::with_capacity(partition.len());
+                for batch in partition.iter() {
+                    let (batch, batch_count) = exec.apply(batch)?;
+                    count += batch_count;
+                    if batch.num_rows() > 0 {
+                        new_batches.push(batch);
+                    }
+                }
+                new_partitions.push(new_batches);
+            }
+            for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+                **partition = new_batches;
+            }
+
+            let count = Arc::new(UInt64Array::from(vec![count as u64])) as ArrayRef;
+            Ok(RecordBatch::try_new(schema, vec![count])?)
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            count_schema,
+            stream,
+        )))
+    }
+}
+
+/// A `WHEN` clause of a [`MemMergeExec`]
+#[derive(Clone, Debug)]
+struct MemMergeClause {
+    kind: MergeClauseKind,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    action: MemMergeAction,
+}
+
+#[derive(Clone, Debug)]
+enum MemMergeAction {
+    /// The indices of the updated columns with their new values
+    Update(Vec<(usize, Arc<dyn PhysicalExpr>)>),
+    Delete,
+    /// The values of all columns of the inserted row
+    Insert(Vec<Arc<dyn PhysicalExpr>>),
+}
+
+/// Execution plan for merging the rows of a source into a [`MemTable`].
+///
+/// The source is read into memory, and indexed by the equi-join keys of the
+/// `ON` condition in a hash table. Each row of the table is paired with the
+/// rows of the source with the same keys to evaluate `ON`, or with every row
+/// of the source if `ON` has no equi-join keys.
+///
+/// Returns a single row with the number of inserted, updated and deleted rows.
+#[derive(Clone, Debug)]
+struct MemMergeExec {
+    batches: Vec<PartitionData>,
+    /// Schema of the table
+    schema: SchemaRef,
+    source: Arc<dyn ExecutionPlan>,
+    /// Schema of the rows the expressions are evaluated on: the columns of the
+    /// table followed by the columns of the source, all of them nullable
+    joined_schema: SchemaRef,
+    on: Arc<dyn PhysicalExpr>,
+    /// The equi-join keys of `on`, see [`equi_join_keys`]
+    equi_keys: Vec<(Arc<dyn PhysicalExpr>, Arc<dyn PhysicalExpr>)>,
+    clauses: Vec<MemMergeClause>,
+    cache: PlanProperties,
+}
+
+/// The source of a [`MemMergeExec`], read into memory
+struct MemMergeSource {
+    batch: RecordBatch,
+    /// The converter of the equi-join keys to rows, with the indices of the
+    /// rows of the source for each of their values, if `ON` has equi-join keys
+    index: Option<(RowConverter, HashMap<Vec<u8>, Vec<u32>>)>,
+}
+
+impl MemMergeExec {
+    fn new(
+        batches: Vec<PartitionData>,
+        schema: SchemaRef,
+        source: Arc<dyn ExecutionPlan>,
+        on: Arc<dyn PhysicalExpr>,
+        clauses: Vec<MemMergeClause>,
+    ) -> Self {
+        let joined_schema = Arc::new(Schema::new(
+            schema
+                .fields()
+                .iter()
+                .chain(source.schema().fields())
+                .map(|field| field.as_ref().clone().with_nullable(true))
+                .collect::<Vec<_>>(),
+        ));
+        let equi_keys = equi_join_keys(&on, &joined_schema, schema.fields().len());
+        Self {
+            batches,
+            schema,
+            source,
+            joined_schema,
+            on,
+            equi_keys,
+            clauses,
+            cache: count_plan_properties(),
+        }
+    }
+
+    /// Indexes the rows of `source` by the equi-join keys of `ON`, if any
+    fn index_source(&self, source: RecordBatch) -> Result<MemMergeSource> {
+        if self.equi_keys.is_empty() {
+            return Ok(MemMergeSource {
+                batch: source,
+                index: None,
+            });
+        }
+
+        let fields = self
+            .equi_keys
+            .iter()
+            .map(|(_, key)| Ok(SortField::new(key.data_type(&self.joined_schema)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let converter = RowConverter::new(fields)?;
+        let table_nulls = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| new_null_array(field.data_type(), source.num_rows()))
+            .collect::<Vec<_>>();
+        let joined = self.join(&table_nulls, source.columns())?;
+        let (keys, valid) = evaluate_keys(
+            &converter,
+            self.equi_keys.iter().map(|(_, key)| key),
+            &joined,
+        )?;
+
+        let mut index: HashMap<Vec<u8>, Vec<u32>> = HashMap::new();
+        for (row, key) in keys.iter().enumerate() {
+            if valid[row] {
+                index
+                    .entry(key.as_ref().to_vec())
+                    .or_default()
+                    .push(row as u32);
+            }
+        }
+        Ok(MemMergeSource {
+            batch: source,
+            index: Some((converter, index)),
+        })
+    }
+
+    /// Creates a batch of the joined schema from the columns of the table and
+    /// the columns of the source
+    fn join(
+        &self,
+        table_columns: &[ArrayRef],
+        source_columns: &[ArrayRef],
+    ) -> Result<RecordBatch> {
+        let columns = table_columns
+            .iter()
+            .chain(source_columns)
+            .cloned()
+            .collect();
+        Ok(RecordBatch::try_new(
+            Arc::clone(&self.joined_schema),
+            columns,
+        )?)
+    }
+
+    /// Returns, for each row of `batch`, the index of the first clause of
+    /// `kind` that applies to it. Only the `candidates` are considered.
+    fn first_clauses(
+        &self,
+        kind: MergeClauseKind,
+        batch: &RecordBatch,
+        candidates: BooleanArray,
+    ) -> Result<Vec<Option<usize>>> {
+        let mut clauses = vec![None; batch.num_rows()];
+        let mut remaining = candidates;
+        for (index, clause) in self.clauses.iter().enumerate() {
+            if clause.kind != kind || remaining.true_count() == 0 {
+                continue;
+            }
+            let applies = match &clause.predicate {
+                Some(predicate) => {
+                    and(&remaining, &evaluate_predicate(predicate, batch)?)?
+                }
+                None => remaining.clone(),
+            };
+            for row in applies.values().set_indices() {
+                clauses[row] = Some(index);
+            }
+            remaining = and(&remaining, &not(&applies)?)?;
+        }
+        Ok(clauses)
+    }
+
+    /// Returns the pairs of rows of the table and of the source to evaluate
+    /// `ON` on, as the indices of their rows in `batch` and in `source`: the
+    /// rows with the same equi-join keys, or all of them if `ON` has none.
+    /// `table_rows` holds the rows of `batch` in the joined schema.
+    fn candidate_pairs(
+        &self,
+        batch: &RecordBatch,
+        table_rows: &RecordBatch,
+        source: &MemMergeSource,
+    ) -> Result<(UInt32Array, UInt32Array)> {
+        let num_rows = batch.num_rows();
+        let num_source_rows = source.batch.num_rows();
+        let Some((converter, index)) = &source.index else {
+            check_merge_pairs(num_rows.saturating_mul(num_source_rows))?;
+            let table_indices = UInt32Array::from_iter_values(
+                (0..num_rows as u32)
+                    .flat_map(|row| std::iter::repeat(row).take(num_source_rows)),
+            );
+            let source_indices = UInt32Array::from_iter_values(
+                (0..num_rows).flat_map(|_| 0..num_source_rows as u32),
+            );
+            return Ok((table_indices, source_indices));
+        };
+
+        let (keys, valid) = evaluate_keys(
+            converter,
+            self.equi_keys.iter().map(|(key, _)| key),
+            table_rows,
+        )?;
+        let mut table_indices = vec![];
+        let mut source_indices = vec![];
+        for (row, key) in keys.iter().enumerate() {
+            let Some(source_rows) = valid[row].then(|| index.get(key.as_ref())).flatten()
+            else {
+                continue;
+            };
+            check_merge_pairs(source_indices.len() + source_rows.len())?;
+            table_indices.extend(std::iter::repeat(row as u32).take(source_rows.len()));
+            source_indices.extend_from_slice(source_rows);
+        }
+        Ok((
+            UInt32Array::from(table_indices),
+            UInt32Array::from(source_indices),
+        ))
+    }
+
+    /// Applies the clauses to `batch` of the table, returning the resulting
+    /// batch and the number of rows it updated or deleted. Marks the rows of
+    /// `source` that match any row of the batch in `source_matched`.
+    fn merge_batch(
+        &self,
+        batch: &RecordBatch,
+        source: &MemMergeSource,
+        source_matched: &mut [bool],
+    ) -> Result<(RecordBatch, usize)> {
+        let num_rows = batch.num_rows();
+
+        // The rows of the batch, paired with nulls for the rows of the batch
+        // that no row of the source matches
+        let source_nulls = source
+            .batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| new_null_array(field.data_type(), num_rows))
+            .collect::<Vec<_>>();
+        let unmatched = self.join(batch.columns(), &source_nulls)?;
+
+        let (table_indices, source_indices) =
+            self.candidate_pairs(batch, &unmatched, source)?;
+        let pairs = self.join(
+            take_record_batch(batch, &table_indices)?.columns(),
+            take_record_batch(&source.batch, &source_indices)?.columns(),
+        )?;
+        let matches = evaluate_predicate(&self.on, &pairs)?;
+        let mut matched = vec![false; num_rows];
+        for pair in matches.values().set_indices() {
+            matched[table_indices.value(pair) as usize] = true;
+            source_matched[source_indices.value(pair) as usize] = true;
+        }
+        let pair_clauses =
+            self.first_clauses(MergeClauseKind::Matched, &pairs, matches)?;
+
+        let candidates = BooleanArray::from_iter(matched.iter().map(|m| Some(!m)));
+        let unmatched_clauses = self.first_clauses(
+            MergeClauseKind::NotMatchedBySource,
+            &unmatched,
+            candidates,
+        )?;
+
+        // For each row of the batch, the clause that applies to it along with
+        // the joined row to evaluate the clause on, either from the pairs or
+        // from the unmatched rows
+        let mut actions = vec![None; num_rows];
+        for (pair, clause) in pair_clauses.into_iter().enumerate() {
+            if let Some(clause) = clause {
+                let row = table_indices.value(pair) as usize;
+                if actions[row].is_some() {
+                    return exec_err!(
+                        "MERGE cannot change a row of the table that matches more than one source row"
+                    );
+                }
+                actions[row] = Some((clause, true, pair));
+            }
+        }
+        for (row, clause) in unmatched_clauses.into_iter().enumerate() {
+            if let Some(clause) = clause {
+                actions[row] = Some((clause, false, row));
+            }
+        }
+
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        for (index, field) in self.schema.fields().iter().enumerate() {
+            // The arrays to take the values of the column from, starting with
+            // the current values
+            let mut arrays = vec![Arc::clone(batch.column(index))];
+            let mut evaluated = HashMap::new();
+            let mut indices = Vec::with_capacity(num_rows);
+            for (row, action) in actions.iter().enumerate() {
+                let value = action.and_then(|(clause, paired, joined_row)| {
+                    let MemMergeAction::Update(assignments) =
+                        &self.clauses[clause].action
+                    else {
+                        return None;
+                    };
+                    let (_, expr) = assignments.iter().find(|(i, _)| *i == index)?;
+                    Some((clause, expr, paired, joined_row))
+                });
+                let Some((clause, expr, paired, joined_row)) = value else {
+                    indices.push((0, row));
+                    continue;
+                };
+                let joined = if paired { &pairs } else { &unmatched };
+                let key = (clause, paired);
+                let array = match evaluated.get(&key) {
+                    Some(array) => *array,
+                    None => {
+                        arrays
+                            .push(expr.evaluate(joined)?.into_array(joined.num_rows())?);
+                        evaluated.insert(key, arrays.len() - 1);
+                        arrays.len() - 1
+                    }
+                };
+                indices.push((array, joined_row));
+            }
+            let arrays = arrays
+                .iter()
+                .map(|array| array.as_ref())
+                .collect::<Vec<_>>();
+            let column = interleave(&arrays, &indices)?;
+            check_not_null(field, &column)?;
+            columns.push(column);
+        }
+
+        let count = actions.iter().flatten().count();
+        let keep = BooleanArray::from_iter(actions.iter().map(|action| {
+            let deleted = matches!(
+                action,
+                Some((clause, _, _)) if matches!(self.clauses[*clause].action, MemMergeAction::Delete)
+            );
+            Some(!deleted)
+        }));
+        let batch = RecordBatch::try_new(batch.schema(), columns)?;
+        Ok((filter_record_batch(&batch, &keep)?, count))
+    }
+
+    /// Returns the rows the clauses insert for the rows of `source` that don't
+    /// match any row of the table
+    fn insert_rows(
+        &self,
+        source: &RecordBatch,
+        source_matched: &[bool],
+    ) -> Result<Option<RecordBatch>> {
+        let num_rows = source.num_rows();
+        let table_nulls = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| new_null_array(field.data_type(), num_rows))
+            .collect::<Vec<_>>();
+        let unmatched = self.join(&table_nulls, source.columns())?;
+        let candidates = BooleanArray::from_iter(source_matched.iter().map(|m| Some(!m)));
+        let clauses = self.first_clauses(
+            MergeClauseKind::NotMatchedByTarget,
+            &unmatched,
+            candidates,
+        )?;
+
+        // The values of each applying clause, with the clause index
+        let mut values: Vec<(usize, Vec<ArrayRef>)> = vec![];
+        let mut indices = vec![];
+        for (row, clause) in clauses.into_iter().enumerate() {
+            let Some(clause) = clause else {
+                continue;
+            };
+            let MemMergeAction::Insert(exprs) = &self.clauses[clause].action else {
+                return internal_err!("Expected an INSERT when not matched by target");
+            };
+            let position = match values.iter().position(|(c, _)| *c == clause) {
+                Some(position) => position,
+                None => {
+                    let arrays = exprs
+                        .iter()
+                        .map(|expr| expr.evaluate(&unmatched)?.into_array(num_rows))
+                        .collect::<Result<_>>()?;
+                    values.push((clause, arrays));
+                    values.len() - 1
+                }
+            };
+            indices.push((position, row));
+        }
+        if indices.is_empty() {
+            return Ok(None);
+        }
+
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let arrays = values
+                    .iter()
+                    .map(|(_, arrays)| arrays[index].as_ref())
+                    .collect::<Vec<_>>();
+                let column = interleave(&arrays, &indices)?;
+                check_not_null(field, &column)?;
+                Ok(column)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Some(RecordBatch::try_new(
+            Arc::clone(&self.schema),
+            columns,
+        )?))
+    }
+
+    /// Formats `clause` for display, with the names of the updated columns
+    fn fmt_clause(&self, clause: &MemMergeClause) -> String {
+        let mut s = format!("WHEN {}", clause.kind);
+        if let Some(predicate) = &clause.predicate {
+            s += &format!(" AND {predicate}");
+        }
+        match &clause.action {
+            MemMergeAction::Update(assignments) => {
+                let assignments = assignments
+                    .iter()
+                    .map(|(index, expr)| {
+                        format!("{} = {expr}", self.schema.field(*index).name())
+                    })
+                    .collect::<Vec<_>>();
+                s += &format!(" THEN UPDATE SET {}", assignments.join(", "));
+            }
+            MemMergeAction::Delete => s += " THEN DELETE",
+            MemMergeAction::Insert(values) => {
+                let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                s += &format!(" THEN INSERT VALUES ({})", values.join(", "));
+            }
+        }
+        s
+    }
+}
+
+/// Returns the equi-join keys of `on`: the pairs of expressions on the table
+/// and on the source, the first `table_columns` columns of `schema` and the
+/// others, that a conjunct of `on` requires to be equal
+fn equi_join_keys(
+    on: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+    table_columns: usize,
+) -> Vec<(Arc<dyn PhysicalExpr>, Arc<dyn PhysicalExpr>)> {
+    // Whether `expr` only references columns of the table, or of the source
+    let is_table_side = |expr: &Arc<dyn PhysicalExpr>| {
+        let columns = collect_columns(expr);
+        if columns.is_empty() {
+            None
+        } else if columns.iter().all(|column| column.index() < table_columns) {
+            Some(true)
+        } else if columns.iter().all(|column| column.index() >= table_columns) {
+            Some(false)
+        } else {
+            None
+        }
+    };
+    split_conjunction(on)
+        .into_iter()
+        .filter_map(|expr| {
+            let binary = expr.as_any().downcast_ref::<BinaryExpr>()?;
+            if *binary.op() != Operator::Eq
+                || binary.left().data_type(schema).ok()?
+                    != binary.right().data_type(schema).ok()?
+            {
+                return None;
+            }
+            match (
+                is_table_side(binary.left())?,
+                is_table_side(binary.right())?,
+            ) {
+                (true, false) => {
+                    Some((Arc::clone(binary.left()), Arc::clone(binary.right())))
+                }
+                (false, true) => {
+                    Some((Arc::clone(binary.right()), Arc::clone(binary.left())))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Evaluates the equi-join `keys` of one side of a [`MemMergeExec`] on
+/// `batch`, returning them as rows of `converter`, along with whether each row
+/// has no null key: a null key matches no row.
+fn evaluate_keys<'a>(
+    converter: &RowConverter,
+    keys: impl Iterator<Item = &'a Arc<dyn PhysicalExpr>>,
+    batch: &RecordBatch,
+) -> Result<(Rows, Vec<bool>)> {
+    let columns = keys
+        .map(|key| key.evaluate(batch)?.into_array(batch.num_rows()))
+        .collect::<Result<Vec<_>>>()?;
+    let valid = (0..batch.num_rows())
+        .map(|row| columns.iter().all(|column| column.is_valid(row)))
+        .collect();
+    Ok((converter.convert_columns(&columns)?, valid))
+}
+
+/// Fails if a [`MemMergeExec`] would evaluate `ON` on more pairs of rows of the
+/// table and of the source than can be indexed
+fn check_merge_pairs(num_pairs: usize) -> Result<()> {
+    if num_pairs > u32::MAX as usize {
+        return resources_err!(
+            "MERGE would evaluate the ON condition on {num_pairs} pairs of rows, more than the maximum of {}",
+            u32::MAX
+        );
+    }
+    Ok(())
+}
+
+impl DisplayAs for MemMergeExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let clauses = self
+                    .clauses
+                    .iter()
+                    .map(|clause| self.fmt_clause(clause))
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "MemMergeExec: partitions={}, on={}, clauses=[{}]",
+                    self.batches.len(),
+                    self.on,
+                    clauses.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for MemMergeExec {
+    fn name(&self) -> &'static str {
+        "MemMergeExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.source]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            children.swap_remove(0),
+            Arc::clone(&self.on),
+            self.clauses.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!("MemMergeExec can only be called on partition 0!");
+        }
+        let exec = self.clone();
+        let count_schema = self.schema();
+        let schema = Arc::clone(&count_schema);
+
+        let stream = futures::stream::once(async move {
+            let source_batches = collect(Arc::clone(&exec.source), context).await?;
+            let source = exec
+                .index_source(concat_batches(&exec.source.schema(), &source_batches)?)?;
+
+            // Lock all partitions before changing any of them, so that the
+            // statement either changes all of them or none if it fails
+            let mut partitions = Vec::with_capacity(exec.batches.len());
+            for partition in &exec.batches {
+                partitions.push(partition.write().await);
+            }
+            let mut count = 0;
+            let mut source_matched = vec![false; source.batch.num_rows()];
+            let mut new_partitions = Vec::with_capacity(partitions.len());
+            for partition in &partitions {
+                let mut new_batches = Vec::with_capacity(partition.len());
+                for batch in partition.iter() {
+                    let (batch, batch_count) =
+                        exec.merge_batch(batch, &source, &mut source_matched)?;
+                    count += batch_count;
+                    if batch.num_rows() > 0 {
+                        new_batches.push(batch);
+                    }
+                }
+                new_partitions.push(new_batches);
+            }
+            if let Some(inserted) = exec.insert_rows(&source.batch, &source_matched)? {
+                count += inserted.num_rows();
+                match new_partitions.first_mut() {
+                    Some(new_batches) => new_batches.push(inserted),
+                    None => {
+                        return exec_err!("Cannot insert into a table without partitions")
+                    }
+                }
+            }
+            for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+                **partition = new_batches;
+            }
+
+            let count = Arc::new(UInt64Array::from(vec![count as u64])) as ArrayRef;
+            Ok(RecordBatch::try_new(schema, vec![count])?)
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            count_schema,
+            stream,
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -385,7 +1342,7 @@ mod tests {
     use crate::prelude::SessionContext;
 
     use arrow::array::{AsArray, Int32Array};
-    use arrow::datatypes::{DataType, Field, Schema, UInt64Type};
+    use arrow::datatypes::{DataType, Field, Int32Type, Schema, UInt64Type};
     use arrow::error::ArrowError;
     use datafusion_common::DataFusionError;
     use datafusion_expr::LogicalPlanBuilder;
@@ -779,4 +1736,49 @@ mod tests {
         assert_eq!(resulting_data_in_table[0].len(), 2);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_delete_and_update_multi_partition() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batch = |a: Vec<i32>, b: Vec<Option<i32>>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int32Array::from(a)), Arc::new(Int32Array::from(b))],
+            )
+        };
+        let table = Arc::new(MemTable::try_new(
+            schema.clone(),
+            vec![
+                vec![batch(vec![1, 2, 3], vec![Some(1), None, Some(3)])?],
+                vec![batch(vec![4, 5], vec![Some(4), Some(5)])?],
+            ],
+        )?);
+        let session_ctx = SessionContext::new();
+        session_ctx.register_table("t", table.clone())?;
+
+        let res = session_ctx.sql("DELETE FROM t WHERE a % 2 = 0").await?;
+        assert_eq!(extract_count(res.collect().await?), 2);
+        let res = session_ctx
+            .sql("UPDATE t SET a = a * 10 WHERE b > 1")
+            .await?;
+        assert_eq!(extract_count(res.collect().await?), 2);
+
+        let partitions = [
+            table.batches[0].read().await.clone(),
+            table.batches[1].read().await.clone(),
+        ];
+        let a = |batches: &[RecordBatch]| {
+            batches
+                .iter()
+                .flat_map(|batch| batch.column(0).as_primitive::<Int32Type>().values())
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(a(&partitions[0]), vec![1, 30]);
+        assert_eq!(a(&partitions[1]), vec![50]);
+        Ok(())
+    }
 }
@@ -19,6 +19,7 @@
 //!
 //! [`ListingTable`]: crate::datasource::listing::ListingTable
 
+pub mod arrow_to_avro;
 pub mod avro_to_arrow;
 pub mod cte_worktable;
 pub mod default_table_source;
@@ -28,6 +29,7 @@ pub mod file_format;
 pub mod function;
 pub mod listing;
 pub mod listing_table_factory;
+pub mod materialized_view;
 pub mod memory;
 pub mod physical_plan;
 pub mod provider;
@@ -43,10 +45,12 @@ pub use datafusion_execution::object_store;
 pub use self::default_table_source::{
     provider_as_source, source_as_provider, DefaultTableSource,
 };
+pub use self::materialized_view::MaterializedView;
 pub use self::memory::MemTable;
 pub use self::view::ViewTable;
 pub use crate::catalog::TableProvider;
 pub use crate::logical_expr::TableType;
+pub(crate) use statistics::analyze_column;
 pub use statistics::get_statistics_with_limit;
 
 use arrow_schema::{Schema, SortOptions};
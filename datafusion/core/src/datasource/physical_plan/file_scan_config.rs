@@ -27,6 +27,7 @@ use super::{get_projected_output_ordering, statistics::MinMaxStatistics};
 use crate::datasource::{listing::PartitionedFile, object_store::ObjectStoreUrl};
 use crate::{error::Result, scalar::ScalarValue};
 
+use ahash::RandomState;
 use arrow::array::{ArrayData, BufferBuilder};
 use arrow::buffer::Buffer;
 use arrow::datatypes::{ArrowNativeType, UInt16Type};
@@ -38,6 +39,9 @@ use datafusion_physical_expr::LexOrdering;
 use datafusion_physical_expr_common::sort_expr::LexOrderingRef;
 
 use log::warn;
+use object_store::path::Path;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 /// Convert type to a type suitable for use as a [`ListingTable`]
 /// partition column. Returns `Dictionary(UInt16, val_type)`, which is
@@ -128,6 +132,9 @@ pub struct FileScanConfig {
     pub table_partition_cols: Vec<Field>,
     /// All equivalent lexicographical orderings that describe the schema.
     pub output_ordering: Vec<LexOrdering>,
+    /// Optional random sample of the blocks to read. If `None`, all blocks
+    /// are read. Formats that can't skip blocks ignore it.
+    pub sample: Option<BlockSample>,
 }
 
 impl FileScanConfig {
@@ -152,6 +159,7 @@ impl FileScanConfig {
             limit: None,
             table_partition_cols: vec![],
             output_ordering: vec![],
+            sample: None,
         }
     }
 
@@ -173,6 +181,12 @@ impl FileScanConfig {
         self
     }
 
+    /// Set the random sample of the blocks to read
+    pub fn with_sample(mut self, sample: Option<BlockSample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
     /// Add a file as a single group
     ///
     /// See [Self::file_groups] for more information.
@@ -368,6 +382,46 @@ impl FileScanConfig {
     }
 }
 
+/// A random sample of the blocks (such as parquet row groups) of the files of
+/// a [`FileScanConfig`], each block being read with probability `fraction`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockSample {
+    /// The probability of a block to be read
+    pub fraction: f64,
+    /// Seed of the random number generator, if the same blocks must be read
+    /// every time
+    pub seed: Option<u64>,
+}
+
+impl BlockSample {
+    /// Create a new `BlockSample`
+    pub fn new(fraction: f64, seed: Option<u64>) -> Self {
+        Self { fraction, seed }
+    }
+
+    /// Returns the random number generator choosing the blocks of the file
+    /// at `location`.
+    ///
+    /// With a seed, the same blocks of a file are chosen regardless of how
+    /// the file is split into ranges.
+    pub fn file_rng(&self, location: &Path) -> StdRng {
+        match self.seed {
+            Some(seed) => {
+                // Fixed seeds rather than `DefaultHasher`, whose algorithm
+                // may change between Rust releases
+                let state = RandomState::with_seeds(seed, 0, 0, 0);
+                StdRng::seed_from_u64(state.hash_one(location.as_ref()))
+            }
+            None => StdRng::from_entropy(),
+        }
+    }
+
+    /// Returns true if the next block should be read
+    pub fn should_read(&self, rng: &mut StdRng) -> bool {
+        rng.gen_bool(self.fraction)
+    }
+}
+
 /// A helper that projects partition columns into the file record batches.
 ///
 /// One interesting trick is the usage of a cache for the key buffers of the partition column
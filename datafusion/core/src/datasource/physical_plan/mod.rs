@@ -24,6 +24,8 @@ mod file_groups;
 mod file_scan_config;
 mod file_stream;
 mod json;
+#[cfg(feature = "orc")]
+mod orc;
 #[cfg(feature = "parquet")]
 pub mod parquet;
 mod statistics;
@@ -39,10 +41,13 @@ pub use csv::{CsvConfig, CsvExec, CsvExecBuilder, CsvOpener};
 use datafusion_expr::dml::InsertOp;
 pub use file_groups::FileGroupPartitioner;
 pub use file_scan_config::{
-    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileScanConfig,
+    wrap_partition_type_in_dict, wrap_partition_value_in_dict, BlockSample,
+    FileScanConfig,
 };
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream, OnError};
 pub use json::{JsonOpener, NdJsonExec};
+#[cfg(feature = "orc")]
+pub use orc::OrcExec;
 
 use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
@@ -124,6 +129,13 @@ impl DisplayAs for FileScanConfig {
             write!(f, ", limit={limit}")?;
         }
 
+        if let Some(sample) = &self.sample {
+            write!(f, ", sample_fraction={}", sample.fraction)?;
+            if let Some(seed) = sample.seed {
+                write!(f, ", sample_seed={seed}")?;
+            }
+        }
+
         display_orderings(f, &orderings)?;
 
         Ok(())
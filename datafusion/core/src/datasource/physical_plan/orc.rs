@@ -0,0 +1,626 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for reading Apache ORC files
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+use super::{FileGroupPartitioner, FileMeta, FileOpenFuture, FileOpener, FileScanConfig};
+use crate::datasource::file_format::orc::{
+    orc_column_index, orc_column_min_max, ObjectStoreOrcReader,
+};
+use crate::datasource::listing::PartitionedFile;
+use crate::datasource::schema_adapter::{
+    DefaultSchemaAdapterFactory, SchemaAdapterFactory,
+};
+use crate::error::Result;
+use crate::physical_optimizer::pruning::{PruningPredicate, PruningStatistics};
+use crate::physical_plan::metrics::{
+    Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning,
+    PlanProperties, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::array::{ArrayRef, BooleanArray, UInt64Array};
+use arrow::datatypes::{Schema, SchemaRef};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{Column, DataFusionError, ScalarValue};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::{EquivalenceProperties, LexOrdering, PhysicalExpr};
+
+use futures::{StreamExt, TryStreamExt};
+use log::debug;
+use object_store::{ObjectMeta, ObjectStore};
+use orc_rust::projection::ProjectionMask;
+use orc_rust::reader::metadata::FileMetadata;
+use orc_rust::ArrowReaderBuilder;
+
+/// Execution plan for scanning one or more Apache ORC files.
+///
+/// If a predicate is supplied with [`Self::with_predicate`], the statistics
+/// of each stripe are used to skip stripes whose rows cannot match it. The
+/// predicate is not otherwise evaluated, so rows that do not match it may
+/// still be returned.
+#[derive(Debug, Clone)]
+pub struct OrcExec {
+    base_config: FileScanConfig,
+    projected_statistics: Statistics,
+    /// Optional predicate used to prune stripes
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// Optional pruning predicate derived from `predicate`
+    pruning_predicate: Option<Arc<PruningPredicate>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+impl OrcExec {
+    /// Create a new ORC reader execution plan provided base configurations
+    pub fn new(base_config: FileScanConfig) -> Self {
+        let (projected_schema, projected_statistics, projected_output_ordering) =
+            base_config.project();
+        let cache = Self::compute_properties(
+            projected_schema,
+            &projected_output_ordering,
+            &base_config,
+        );
+        Self {
+            base_config,
+            projected_statistics,
+            predicate: None,
+            pruning_predicate: None,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        }
+    }
+
+    /// Set the predicate used to skip stripes based on their statistics
+    pub fn with_predicate(mut self, predicate: Arc<dyn PhysicalExpr>) -> Self {
+        let file_schema = Arc::clone(&self.base_config.file_schema);
+        self.pruning_predicate =
+            match PruningPredicate::try_new(Arc::clone(&predicate), file_schema) {
+                Ok(pruning_predicate) if !pruning_predicate.always_true() => {
+                    Some(Arc::new(pruning_predicate))
+                }
+                Ok(_) => None,
+                Err(e) => {
+                    debug!("Could not create pruning predicate for: {e}");
+                    None
+                }
+            };
+        self.predicate = Some(predicate);
+        self
+    }
+
+    /// Ref to the base configs
+    pub fn base_config(&self) -> &FileScanConfig {
+        &self.base_config
+    }
+
+    /// Optional predicate used to prune stripes
+    pub fn predicate(&self) -> Option<&Arc<dyn PhysicalExpr>> {
+        self.predicate.as_ref()
+    }
+
+    /// Optional reference to this ORC scan's pruning predicate
+    pub fn pruning_predicate(&self) -> Option<&Arc<PruningPredicate>> {
+        self.pruning_predicate.as_ref()
+    }
+
+    fn output_partitioning_helper(file_scan_config: &FileScanConfig) -> Partitioning {
+        Partitioning::UnknownPartitioning(file_scan_config.file_groups.len())
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        schema: SchemaRef,
+        orderings: &[LexOrdering],
+        file_scan_config: &FileScanConfig,
+    ) -> PlanProperties {
+        // Equivalence Properties
+        let eq_properties = EquivalenceProperties::new_with_orderings(schema, orderings);
+
+        PlanProperties::new(
+            eq_properties,
+            Self::output_partitioning_helper(file_scan_config), // Output Partitioning
+            ExecutionMode::Bounded,                             // Execution Mode
+        )
+    }
+
+    fn with_file_groups(mut self, file_groups: Vec<Vec<PartitionedFile>>) -> Self {
+        self.base_config.file_groups = file_groups;
+        // Changing file groups may invalidate output partitioning. Update it also
+        let output_partitioning = Self::output_partitioning_helper(&self.base_config);
+        self.cache = self.cache.with_partitioning(output_partitioning);
+        self
+    }
+}
+
+impl DisplayAs for OrcExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        write!(f, "OrcExec: ")?;
+        self.base_config.fmt_as(t, f)?;
+        if let Some(predicate) = &self.predicate {
+            write!(f, ", predicate={predicate}")?;
+        }
+        if let Some(pruning_predicate) = &self.pruning_predicate {
+            write!(
+                f,
+                ", pruning_predicate={}",
+                pruning_predicate.predicate_expr()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionPlan for OrcExec {
+    fn name(&self) -> &'static str {
+        "OrcExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    /// Redistribute files across partitions according to their size
+    /// See comments on [`FileGroupPartitioner`] for more detail.
+    ///
+    /// A stripe is read by the partition whose byte range contains the start
+    /// of the stripe.
+    fn repartitioned(
+        &self,
+        target_partitions: usize,
+        config: &ConfigOptions,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        let repartition_file_min_size = config.optimizer.repartition_file_min_size;
+        let repartitioned_file_groups_option = FileGroupPartitioner::new()
+            .with_target_partitions(target_partitions)
+            .with_repartition_file_min_size(repartition_file_min_size)
+            .with_preserve_order_within_groups(
+                self.properties().output_ordering().is_some(),
+            )
+            .repartition_file_groups(&self.base_config.file_groups);
+
+        if let Some(repartitioned_file_groups) = repartitioned_file_groups_option {
+            let mut new_plan = self.clone();
+            new_plan = new_plan.with_file_groups(repartitioned_file_groups);
+            return Ok(Some(Arc::new(new_plan)));
+        }
+        Ok(None)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        use super::file_stream::FileStream;
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.base_config.object_store_url)?;
+
+        let projection = self
+            .base_config
+            .file_column_projection_indices()
+            .unwrap_or_else(|| {
+                (0..self.base_config.file_schema.fields().len()).collect()
+            });
+        let opener = OrcOpener {
+            object_store,
+            batch_size: context.session_config().batch_size(),
+            projection,
+            table_schema: Arc::clone(&self.base_config.file_schema),
+            pruning_predicate: self.pruning_predicate.clone(),
+            metrics: OrcStripeMetrics::new(partition, &self.metrics),
+        };
+        let stream =
+            FileStream::new(&self.base_config, partition, opener, &self.metrics)?;
+        Ok(Box::pin(stream))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(self.projected_statistics.clone())
+    }
+
+    fn fetch(&self) -> Option<usize> {
+        self.base_config.limit
+    }
+
+    fn with_fetch(&self, limit: Option<usize>) -> Option<Arc<dyn ExecutionPlan>> {
+        let new_config = self.base_config.clone().with_limit(limit);
+
+        Some(Arc::new(Self {
+            base_config: new_config,
+            ..self.clone()
+        }))
+    }
+}
+
+/// Stripe pruning metrics of an [`OrcExec`] partition
+#[derive(Debug, Clone)]
+struct OrcStripeMetrics {
+    /// Number of stripes skipped because of their statistics
+    stripes_pruned_statistics: Count,
+    /// Number of stripes whose statistics matched the predicate
+    stripes_matched_statistics: Count,
+}
+
+impl OrcStripeMetrics {
+    fn new(partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+        Self {
+            stripes_pruned_statistics: MetricBuilder::new(metrics)
+                .counter("stripes_pruned_statistics", partition),
+            stripes_matched_statistics: MetricBuilder::new(metrics)
+                .counter("stripes_matched_statistics", partition),
+        }
+    }
+}
+
+/// Opens ORC files for an [`OrcExec`]
+struct OrcOpener {
+    object_store: Arc<dyn ObjectStore>,
+    batch_size: usize,
+    /// Indices of the columns of `table_schema` to read
+    projection: Vec<usize>,
+    table_schema: SchemaRef,
+    pruning_predicate: Option<Arc<PruningPredicate>>,
+    metrics: OrcStripeMetrics,
+}
+
+impl FileOpener for OrcOpener {
+    fn open(&self, file_meta: FileMeta) -> Result<FileOpenFuture> {
+        let object_store = Arc::clone(&self.object_store);
+        let batch_size = self.batch_size;
+        let table_schema = Arc::clone(&self.table_schema);
+        let projected_schema = Arc::new(table_schema.project(&self.projection)?);
+        let schema_adapter = DefaultSchemaAdapterFactory
+            .create(projected_schema, Arc::clone(&table_schema));
+        let pruning_predicate = self.pruning_predicate.clone();
+        let metrics = self.metrics.clone();
+
+        Ok(Box::pin(async move {
+            let object_meta = file_meta.object_meta;
+            let builder = ArrowReaderBuilder::try_new_async(ObjectStoreOrcReader::new(
+                Arc::clone(&object_store),
+                object_meta.clone(),
+            ))
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            let metadata = builder.file_metadata();
+
+            let file_schema = builder.schema();
+            let (schema_mapping, adapted_projections) =
+                schema_adapter.map_schema(&file_schema)?;
+            let column_names = adapted_projections
+                .iter()
+                .map(|i| file_schema.field(*i).name().as_str())
+                .collect::<Vec<_>>();
+            let projection =
+                ProjectionMask::named_roots(metadata.root_data_type(), &column_names);
+
+            let file_range = file_meta
+                .range
+                .map(|range| range.start as usize..range.end as usize);
+            let ranges = stripe_ranges(
+                metadata,
+                file_range,
+                pruning_predicate.as_deref(),
+                &table_schema,
+                &metrics,
+            );
+
+            // The reader only selects stripes by byte range, so each run of
+            // consecutive selected stripes is read by its own reader
+            let mut first_builder = Some(builder);
+            let stream = futures::stream::iter(ranges)
+                .then(move |range| {
+                    let builder = first_builder.take();
+                    let object_store = Arc::clone(&object_store);
+                    let object_meta = object_meta.clone();
+                    let projection = projection.clone();
+                    async move {
+                        let builder = match builder {
+                            Some(builder) => builder,
+                            None => open_builder(object_store, object_meta).await?,
+                        };
+                        Ok::<_, DataFusionError>(
+                            builder
+                                .with_projection(projection)
+                                .with_batch_size(batch_size)
+                                .with_file_byte_range(range)
+                                .build_async()
+                                .map_err(DataFusionError::from),
+                        )
+                    }
+                })
+                .try_flatten()
+                .and_then(move |batch| {
+                    futures::future::ready(schema_mapping.map_batch(batch))
+                });
+
+            Ok(stream.map_err(Into::into).boxed())
+        }))
+    }
+}
+
+async fn open_builder(
+    object_store: Arc<dyn ObjectStore>,
+    object_meta: ObjectMeta,
+) -> Result<ArrowReaderBuilder<ObjectStoreOrcReader>> {
+    ArrowReaderBuilder::try_new_async(ObjectStoreOrcReader::new(
+        object_store,
+        object_meta,
+    ))
+    .await
+    .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+/// Returns the byte ranges covering the stripes of the file described by
+/// `metadata` that start within `file_range` and are not pruned by
+/// `pruning_predicate`. Consecutive selected stripes share a range.
+fn stripe_ranges(
+    metadata: &FileMetadata,
+    file_range: Option<Range<usize>>,
+    pruning_predicate: Option<&PruningPredicate>,
+    table_schema: &Schema,
+    metrics: &OrcStripeMetrics,
+) -> Vec<Range<usize>> {
+    let stripes = metadata.stripe_metadatas();
+    let mut selected = stripes
+        .iter()
+        .map(|stripe| {
+            file_range
+                .as_ref()
+                .map_or(true, |range| range.contains(&(stripe.offset() as usize)))
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(pruning_predicate) = pruning_predicate {
+        let statistics = StripePruningStatistics {
+            metadata,
+            table_schema,
+        };
+        match pruning_predicate.prune(&statistics) {
+            Ok(values) => {
+                for (selected, keep) in selected.iter_mut().zip(values) {
+                    if !*selected {
+                        continue;
+                    }
+                    if keep {
+                        metrics.stripes_matched_statistics.add(1);
+                    } else {
+                        metrics.stripes_pruned_statistics.add(1);
+                        *selected = false;
+                    }
+                }
+            }
+            // stripes can still be read without pruning
+            Err(e) => debug!("Error evaluating stripe predicate values {e}"),
+        }
+    }
+
+    let mut ranges: Vec<Range<usize>> = vec![];
+    let mut previous_selected = false;
+    for (stripe, selected) in stripes.iter().zip(selected) {
+        let offset = stripe.offset() as usize;
+        match ranges.last_mut() {
+            Some(range) if selected && previous_selected => range.end = offset + 1,
+            _ if selected => ranges.push(offset..offset + 1),
+            _ => {}
+        }
+        previous_selected = selected;
+    }
+    ranges
+}
+
+/// Implements [`PruningStatistics`] over the stripes of an ORC file, each
+/// stripe being a container
+struct StripePruningStatistics<'a> {
+    metadata: &'a FileMetadata,
+    table_schema: &'a Schema,
+}
+
+impl StripePruningStatistics<'_> {
+    /// Returns the ORC column index of `column`, if the file has it
+    fn column_index(&self, column: &Column) -> Option<usize> {
+        orc_column_index(self.metadata.root_data_type(), &column.name)
+    }
+
+    fn min_max_values(&self, column: &Column, min: bool) -> Option<ArrayRef> {
+        let index = self.column_index(column)?;
+        let data_type = self
+            .table_schema
+            .field_with_name(&column.name)
+            .ok()?
+            .data_type();
+        let null = ScalarValue::try_from(data_type).ok()?;
+        let values = self.metadata.stripe_metadatas().iter().map(|stripe| {
+            stripe
+                .column_statistics()
+                .get(index)
+                .and_then(|stats| orc_column_min_max(stats, data_type))
+                .map(|(min_value, max_value)| if min { min_value } else { max_value })
+                .unwrap_or_else(|| null.clone())
+        });
+        ScalarValue::iter_to_array(values).ok()
+    }
+}
+
+impl PruningStatistics for StripePruningStatistics<'_> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.min_max_values(column, true)
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.min_max_values(column, false)
+    }
+
+    fn num_containers(&self) -> usize {
+        self.metadata.stripe_metadatas().len()
+    }
+
+    fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
+        let index = self.column_index(column)?;
+        let null_counts = self.metadata.stripe_metadatas().iter().map(|stripe| {
+            stripe.column_statistics().get(index).map(|stats| {
+                stripe
+                    .number_of_rows()
+                    .saturating_sub(stats.number_of_values())
+            })
+        });
+        Some(Arc::new(UInt64Array::from_iter(null_counts)))
+    }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        let row_counts = self
+            .metadata
+            .stripe_metadatas()
+            .iter()
+            .map(|stripe| Some(stripe.number_of_rows()));
+        Some(Arc::new(UInt64Array::from_iter(row_counts)))
+    }
+
+    fn contained(
+        &self,
+        _column: &Column,
+        _values: &HashSet<ScalarValue>,
+    ) -> Option<BooleanArray> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::file_format::orc::tests::write_orc_file;
+    use crate::datasource::file_format::orc::OrcFormat;
+    use crate::datasource::file_format::FileFormat;
+    use crate::datasource::object_store::ObjectStoreUrl;
+    use crate::physical_plan::collect;
+    use crate::prelude::SessionContext;
+
+    use arrow::array::{Int32Array, RecordBatch};
+    use datafusion_expr::Operator;
+    use datafusion_physical_expr::expressions::{col, lit, BinaryExpr};
+    use object_store::memory::InMemory;
+
+    fn batch(ids: Vec<i32>) -> RecordBatch {
+        RecordBatch::try_from_iter(vec![("id", Arc::new(Int32Array::from(ids)) as _)])
+            .unwrap()
+    }
+
+    async fn orc_exec(ctx: &SessionContext) -> Result<OrcExec> {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        ctx.register_object_store(url.as_ref(), Arc::clone(&store));
+        let batches = [batch(vec![1, 2, 3]), batch(vec![4, 5]), batch(vec![6])];
+        let meta = write_orc_file(&store, "test.orc", &batches).await?;
+
+        let state = ctx.state();
+        let schema = OrcFormat
+            .infer_schema(&state, &store, &[meta.clone()])
+            .await?;
+        let conf = FileScanConfig::new(url, schema).with_file(meta.into());
+        Ok(OrcExec::new(conf))
+    }
+
+    fn sorted_ids(batches: &[RecordBatch]) -> Vec<i32> {
+        let mut ids = batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn read_file_ranges() -> Result<()> {
+        let ctx = SessionContext::new();
+        let exec = orc_exec(&ctx).await?;
+
+        let mut config = ConfigOptions::new();
+        config.optimizer.repartition_file_min_size = 0;
+        let exec = exec.repartitioned(3, &config)?.unwrap();
+        assert_eq!(exec.properties().output_partitioning().partition_count(), 3);
+
+        // every stripe is read by exactly one partition
+        let batches = collect(exec, ctx.task_ctx()).await?;
+        assert_eq!(sorted_ids(&batches), vec![1, 2, 3, 4, 5, 6]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_with_predicate() -> Result<()> {
+        let ctx = SessionContext::new();
+        let exec = orc_exec(&ctx).await?;
+        let schema = Arc::clone(&exec.base_config().file_schema);
+        let predicate =
+            Arc::new(BinaryExpr::new(col("id", &schema)?, Operator::Gt, lit(3)));
+        let exec = exec.with_predicate(predicate);
+        assert!(exec.pruning_predicate().is_some());
+
+        // stripes without statistics are never pruned
+        let exec = Arc::new(exec);
+        let batches = collect(Arc::clone(&exec) as _, ctx.task_ctx()).await?;
+        assert_eq!(sorted_ids(&batches), vec![1, 2, 3, 4, 5, 6]);
+        let metrics = exec.metrics().unwrap();
+        assert_eq!(
+            metrics
+                .sum_by_name("stripes_pruned_statistics")
+                .map(|v| v.as_usize()),
+            Some(0)
+        );
+        Ok(())
+    }
+}
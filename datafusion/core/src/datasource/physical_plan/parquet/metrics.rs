@@ -57,6 +57,10 @@ pub struct ParquetFileMetrics {
     pub page_index_eval_time: Time,
     /// Total time spent reading and parsing metadata from the footer
     pub metadata_load_time: Time,
+    /// Number of times the metadata was found in the file metadata cache
+    pub metadata_cache_hits: Count,
+    /// Number of times the metadata was not found in the file metadata cache
+    pub metadata_cache_misses: Count,
 }
 
 impl ParquetFileMetrics {
@@ -122,6 +126,13 @@ impl ParquetFileMetrics {
             .with_new_label("filename", filename.to_string())
             .subset_time("metadata_load_time", partition);
 
+        let metadata_cache_hits = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("metadata_cache_hits", partition);
+        let metadata_cache_misses = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("metadata_cache_misses", partition);
+
         Self {
             predicate_evaluation_errors,
             row_groups_matched_bloom_filter,
@@ -138,6 +149,8 @@ impl ParquetFileMetrics {
             bloom_filter_eval_time,
             page_index_eval_time,
             metadata_load_time,
+            metadata_cache_hits,
+            metadata_cache_misses,
         }
     }
 }
@@ -24,8 +24,8 @@ use std::sync::Arc;
 use crate::datasource::listing::PartitionedFile;
 use crate::datasource::physical_plan::file_stream::FileStream;
 use crate::datasource::physical_plan::{
-    parquet::page_filter::PagePruningAccessPlanFilter, DisplayAs, FileGroupPartitioner,
-    FileScanConfig,
+    parquet::page_filter::PagePruningAccessPlanFilter, BlockSample, DisplayAs,
+    FileGroupPartitioner, FileScanConfig,
 };
 use crate::{
     config::{ConfigOptions, TableParquetOptions},
@@ -34,6 +34,7 @@ use crate::{
     physical_optimizer::pruning::PruningPredicate,
     physical_plan::{
         metrics::{ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
+        sample::sample_statistics,
         DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning, PlanProperties,
         SendableRecordBatchStream, Statistics,
     },
@@ -60,7 +61,9 @@ use crate::datasource::schema_adapter::{
 pub use access_plan::{ParquetAccessPlan, RowGroupAccess};
 pub use metrics::ParquetFileMetrics;
 use opener::ParquetOpener;
-pub use reader::{DefaultParquetFileReaderFactory, ParquetFileReaderFactory};
+pub use reader::{
+    CachedParquetMetaData, DefaultParquetFileReaderFactory, ParquetFileReaderFactory,
+};
 pub use row_filter::can_expr_be_pushed_down_with_schemas;
 pub use writer::plan_to_parquet;
 
@@ -790,7 +793,12 @@ impl ExecutionPlan for ParquetExec {
                 ctx.runtime_env()
                     .object_store(&self.base_config.object_store_url)
                     .map(|store| {
-                        Arc::new(DefaultParquetFileReaderFactory::new(store)) as _
+                        let metadata_cache =
+                            ctx.runtime_env().cache_manager.get_file_metadata_cache();
+                        Arc::new(
+                            DefaultParquetFileReaderFactory::new(store)
+                                .with_metadata_cache(metadata_cache),
+                        ) as _
                     })
             })?;
 
@@ -804,6 +812,7 @@ impl ExecutionPlan for ParquetExec {
             projection: Arc::from(projection),
             batch_size: ctx.session_config().batch_size(),
             limit: self.base_config.limit,
+            sample: self.base_config.sample,
             predicate: self.predicate.clone(),
             pruning_predicate: self.pruning_predicate.clone(),
             page_pruning_predicate: self.page_pruning_predicate.clone(),
@@ -840,6 +849,10 @@ impl ExecutionPlan for ParquetExec {
         } else {
             self.projected_statistics.clone()
         };
+        let stats = match &self.base_config.sample {
+            Some(sample) => sample_statistics(stats, sample.fraction),
+            None => stats,
+        };
         Ok(stats)
     }
 
@@ -864,6 +877,31 @@ impl ExecutionPlan for ParquetExec {
             schema_adapter_factory: self.schema_adapter_factory.clone(),
         }))
     }
+
+    fn with_block_sample(
+        &self,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Option<Arc<dyn ExecutionPlan>> {
+        let new_config = self
+            .base_config
+            .clone()
+            .with_sample(Some(BlockSample::new(fraction, seed)));
+
+        Some(Arc::new(Self {
+            base_config: new_config,
+            projected_statistics: self.projected_statistics.clone(),
+            metrics: self.metrics.clone(),
+            predicate: self.predicate.clone(),
+            pruning_predicate: self.pruning_predicate.clone(),
+            page_pruning_predicate: self.page_pruning_predicate.clone(),
+            metadata_size_hint: self.metadata_size_hint,
+            parquet_file_reader_factory: self.parquet_file_reader_factory.clone(),
+            cache: self.cache.clone(),
+            table_parquet_options: self.table_parquet_options.clone(),
+            schema_adapter_factory: self.schema_adapter_factory.clone(),
+        }))
+    }
 }
 
 fn should_enable_page_index(
@@ -892,6 +930,7 @@ mod tests {
     use crate::datasource::listing::{FileRange, ListingOptions};
     use crate::datasource::object_store::ObjectStoreUrl;
     use crate::execution::context::SessionState;
+    use crate::execution::runtime_env::RuntimeEnvBuilder;
     use crate::physical_plan::displayable;
     use crate::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
     use crate::test::object_store::local_unpartitioned_file;
@@ -909,6 +948,9 @@ mod tests {
     use arrow::record_batch::RecordBatch;
     use arrow_schema::{DataType, Fields};
     use datafusion_common::{assert_contains, ScalarValue};
+    use datafusion_execution::cache::cache_manager::CacheManagerConfig;
+    use datafusion_execution::cache::cache_unit::DefaultFilesMetadataCache;
+    use datafusion_execution::cache::CacheAccessor;
     use datafusion_expr::{col, lit, when, Expr};
     use datafusion_physical_expr::planner::logical2physical;
     use datafusion_physical_plan::ExecutionPlanProperties;
@@ -1647,6 +1689,46 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn parquet_exec_with_metadata_cache() -> Result<()> {
+        let testdata = crate::test_util::parquet_test_data();
+        let metadata_cache = Arc::new(DefaultFilesMetadataCache::new(1024 * 1024));
+        let cache_config = CacheManagerConfig::default()
+            .with_file_metadata_cache(Some(Arc::clone(&metadata_cache) as _));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_cache_manager(cache_config)
+            .build_arc()?;
+        let session_ctx =
+            SessionContext::new_with_config_rt(SessionConfig::new(), runtime);
+        let state = session_ctx.state();
+        let parquet_exec = scan_format(
+            &state,
+            &ParquetFormat::default(),
+            &testdata,
+            "alltypes_plain.parquet",
+            None,
+            None,
+        )
+        .await?;
+
+        let batches = collect(Arc::clone(&parquet_exec), state.task_ctx()).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 8);
+        let metrics = parquet_exec.metrics().unwrap();
+        assert_eq!(get_value(&metrics, "metadata_cache_hits"), 0);
+        assert_eq!(get_value(&metrics, "metadata_cache_misses"), 1);
+        assert_eq!(metadata_cache.len(), 1);
+        assert!(metadata_cache.memory_used() > 0);
+
+        // the second scan reads the metadata from the cache
+        let batches = collect(Arc::clone(&parquet_exec), state.task_ctx()).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 8);
+        let metrics = parquet_exec.metrics().unwrap();
+        assert_eq!(get_value(&metrics, "metadata_cache_hits"), 1);
+        assert_eq!(get_value(&metrics, "metadata_cache_misses"), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_exec_with_range() -> Result<()> {
         fn file_range(meta: &ObjectMeta, start: i64, end: i64) -> PartitionedFile {
@@ -26,12 +26,15 @@ use crate::datasource::physical_plan::parquet::{
     row_filter, should_enable_page_index, ParquetAccessPlan,
 };
 use crate::datasource::physical_plan::{
-    FileMeta, FileOpenFuture, FileOpener, ParquetFileMetrics, ParquetFileReaderFactory,
+    BlockSample, FileMeta, FileOpenFuture, FileOpener, ParquetFileMetrics,
+    ParquetFileReaderFactory,
 };
 use crate::datasource::schema_adapter::SchemaAdapterFactory;
 use crate::physical_optimizer::pruning::PruningPredicate;
 use arrow_schema::{ArrowError, SchemaRef};
+use datafusion_common::tree_node::Transformed;
 use datafusion_common::{exec_err, Result};
+use datafusion_physical_expr::expressions::snapshot_dynamic_filters;
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use futures::{StreamExt, TryStreamExt};
@@ -51,6 +54,8 @@ pub(super) struct ParquetOpener {
     pub batch_size: usize,
     /// Optional limit on the number of rows to read
     pub limit: Option<usize>,
+    /// Optional random sample of the row groups to read
+    pub sample: Option<BlockSample>,
     /// Optional predicate to apply during the scan
     pub predicate: Option<Arc<dyn PhysicalExpr>>,
     /// Optional pruning predicate applied to row group statistics
@@ -81,11 +86,70 @@ pub(super) struct ParquetOpener {
     pub schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
 }
 
+impl ParquetOpener {
+    /// Returns the predicates to apply to the next file opened.
+    ///
+    /// If the predicate contains [`DynamicFilterExpr`]s whose values have
+    /// been published since the scan was planned, they are replaced with
+    /// their current predicate, and the pruning predicates are rebuilt so
+    /// that they can make use of it.
+    ///
+    /// [`DynamicFilterExpr`]: datafusion_physical_expr::expressions::DynamicFilterExpr
+    fn snapshot_predicates(&self) -> Result<ScanPredicates> {
+        let snapshot = self
+            .predicate
+            .clone()
+            .map(snapshot_dynamic_filters)
+            .transpose()?;
+        let Some(Transformed {
+            data: predicate,
+            transformed: true,
+            ..
+        }) = snapshot
+        else {
+            return Ok(ScanPredicates {
+                predicate: self.predicate.clone(),
+                pruning_predicate: self.pruning_predicate.clone(),
+                page_pruning_predicate: self.page_pruning_predicate.clone(),
+            });
+        };
+
+        let pruning_predicate = match PruningPredicate::try_new(
+            Arc::clone(&predicate),
+            self.table_schema.clone(),
+        ) {
+            Ok(pruning_predicate) => Some(Arc::new(pruning_predicate)),
+            Err(e) => {
+                debug!("Could not create pruning predicate for: {e}");
+                None
+            }
+        }
+        .filter(|p| !p.always_true());
+        let page_pruning_predicate = Arc::new(PagePruningAccessPlanFilter::new(
+            &predicate,
+            self.table_schema.clone(),
+        ));
+        Ok(ScanPredicates {
+            predicate: Some(predicate),
+            pruning_predicate,
+            page_pruning_predicate: Some(page_pruning_predicate),
+        })
+    }
+}
+
+/// The predicates a [`ParquetOpener`] applies to a file
+struct ScanPredicates {
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    pruning_predicate: Option<Arc<PruningPredicate>>,
+    page_pruning_predicate: Option<Arc<PagePruningAccessPlanFilter>>,
+}
+
 impl FileOpener for ParquetOpener {
     fn open(&self, file_meta: FileMeta) -> Result<FileOpenFuture> {
         let file_range = file_meta.range.clone();
         let extensions = file_meta.extensions.clone();
         let file_name = file_meta.location().to_string();
+        let location = file_meta.location().clone();
         let file_metrics =
             ParquetFileMetrics::new(self.partition_index, &file_name, &self.metrics);
 
@@ -104,18 +168,19 @@ impl FileOpener for ParquetOpener {
         let schema_adapter = self
             .schema_adapter_factory
             .create(projected_schema, self.table_schema.clone());
-        let predicate = self.predicate.clone();
-        let pruning_predicate = self.pruning_predicate.clone();
-        let page_pruning_predicate = self.page_pruning_predicate.clone();
+        let ScanPredicates {
+            predicate,
+            pruning_predicate,
+            page_pruning_predicate,
+        } = self.snapshot_predicates()?;
         let table_schema = self.table_schema.clone();
         let reorder_predicates = self.reorder_filters;
         let pushdown_filters = self.pushdown_filters;
-        let enable_page_index = should_enable_page_index(
-            self.enable_page_index,
-            &self.page_pruning_predicate,
-        );
+        let enable_page_index =
+            should_enable_page_index(self.enable_page_index, &page_pruning_predicate);
         let enable_bloom_filter = self.enable_bloom_filter;
         let limit = self.limit;
+        let sample = self.sample;
 
         Ok(Box::pin(async move {
             let options = ArrowReaderOptions::new().with_page_index(enable_page_index);
@@ -197,6 +262,10 @@ impl FileOpener for ParquetOpener {
             if let Some(range) = file_range.as_ref() {
                 row_groups.prune_by_range(rg_metadata, range);
             }
+            // if only a random sample of the row groups should be read
+            if let Some(sample) = sample.as_ref() {
+                row_groups.prune_by_sample(sample, &location);
+            }
             // If there is a predicate that can be evaluated against the metadata
             if let Some(predicate) = predicate.as_ref() {
                 row_groups.prune_by_statistics(
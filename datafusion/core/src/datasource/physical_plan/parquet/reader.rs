@@ -20,11 +20,14 @@
 
 use crate::datasource::physical_plan::{FileMeta, ParquetFileMetrics};
 use bytes::Bytes;
+use datafusion_execution::cache::cache_manager::{FileMetadata, FileMetadataCache};
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use futures::future::BoxFuture;
-use object_store::ObjectStore;
+use futures::FutureExt;
+use object_store::{ObjectMeta, ObjectStore};
 use parquet::arrow::async_reader::{AsyncFileReader, ParquetObjectReader};
 use parquet::file::metadata::ParquetMetaData;
+use std::any::Any;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
@@ -64,17 +67,59 @@ pub trait ParquetFileReaderFactory: Debug + Send + Sync + 'static {
 ///
 /// This implementation:
 /// 1. Reads parquet directly from an underlying [`ObjectStore`] instance.
-/// 2. Reads the footer and page metadata on demand.
-/// 3. Does not cache metadata or coalesce I/O operations.
+/// 2. Reads the footer and page metadata on demand, or from the
+///    [`FileMetadataCache`] if one is set with [`Self::with_metadata_cache`].
+/// 3. Does not coalesce I/O operations.
 #[derive(Debug)]
 pub struct DefaultParquetFileReaderFactory {
     store: Arc<dyn ObjectStore>,
+    metadata_cache: Option<FileMetadataCache>,
 }
 
 impl DefaultParquetFileReaderFactory {
     /// Create a new `DefaultParquetFileReaderFactory`.
     pub fn new(store: Arc<dyn ObjectStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            metadata_cache: None,
+        }
+    }
+
+    /// Caches the metadata of the read files in `metadata_cache`, if set.
+    ///
+    /// On a cache miss the page index is loaded together with the footer, so
+    /// that the cached metadata serves queries using the page index as well.
+    pub fn with_metadata_cache(
+        mut self,
+        metadata_cache: Option<FileMetadataCache>,
+    ) -> Self {
+        self.metadata_cache = metadata_cache;
+        self
+    }
+}
+
+/// [`ParquetMetaData`] stored in a [`FileMetadataCache`]
+pub struct CachedParquetMetaData(Arc<ParquetMetaData>);
+
+impl CachedParquetMetaData {
+    /// Wraps `metadata` to be stored in a [`FileMetadataCache`]
+    pub fn new(metadata: Arc<ParquetMetaData>) -> Self {
+        Self(metadata)
+    }
+
+    /// Returns the cached metadata
+    pub fn parquet_metadata(&self) -> &Arc<ParquetMetaData> {
+        &self.0
+    }
+}
+
+impl FileMetadata for CachedParquetMetaData {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn memory_size(&self) -> usize {
+        self.0.memory_size()
     }
 }
 
@@ -89,6 +134,8 @@ impl DefaultParquetFileReaderFactory {
 pub(crate) struct ParquetFileReader {
     pub file_metrics: ParquetFileMetrics,
     pub inner: ParquetObjectReader,
+    /// Cache of the metadata of the file, keyed by `object_meta`
+    pub metadata_cache: Option<(FileMetadataCache, ObjectMeta)>,
 }
 
 impl AsyncFileReader for ParquetFileReader {
@@ -115,7 +162,35 @@ impl AsyncFileReader for ParquetFileReader {
     fn get_metadata(
         &mut self,
     ) -> BoxFuture<'_, parquet::errors::Result<Arc<ParquetMetaData>>> {
-        self.inner.get_metadata()
+        let Some((cache, object_meta)) = &self.metadata_cache else {
+            return self.inner.get_metadata();
+        };
+
+        let cached = cache
+            .get_with_extra(&object_meta.location, object_meta)
+            .and_then(|metadata| {
+                let metadata =
+                    metadata.as_any().downcast_ref::<CachedParquetMetaData>()?;
+                Some(Arc::clone(metadata.parquet_metadata()))
+            });
+        if let Some(metadata) = cached {
+            self.file_metrics.metadata_cache_hits.add(1);
+            return futures::future::ready(Ok(metadata)).boxed();
+        }
+
+        self.file_metrics.metadata_cache_misses.add(1);
+        let cache = Arc::clone(cache);
+        let object_meta = object_meta.clone();
+        async move {
+            let metadata = self.inner.get_metadata().await?;
+            cache.put_with_extra(
+                &object_meta.location,
+                Arc::new(CachedParquetMetaData::new(Arc::clone(&metadata))),
+                &object_meta,
+            );
+            Ok(metadata)
+        }
+        .boxed()
     }
 }
 
@@ -133,15 +208,25 @@ impl ParquetFileReaderFactory for DefaultParquetFileReaderFactory {
             metrics,
         );
         let store = Arc::clone(&self.store);
+        let metadata_cache = self
+            .metadata_cache
+            .as_ref()
+            .map(|cache| (Arc::clone(cache), file_meta.object_meta.clone()));
         let mut inner = ParquetObjectReader::new(store, file_meta.object_meta);
 
         if let Some(hint) = metadata_size_hint {
             inner = inner.with_footer_size_hint(hint)
         };
+        if metadata_cache.is_some() {
+            inner = inner
+                .with_preload_column_index(true)
+                .with_preload_offset_index(true)
+        }
 
         Ok(Box::new(ParquetFileReader {
             inner,
             file_metrics,
+            metadata_cache,
         }))
     }
 }
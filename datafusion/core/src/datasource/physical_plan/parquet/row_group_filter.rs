@@ -16,10 +16,12 @@
 // under the License.
 
 use crate::datasource::listing::FileRange;
+use crate::datasource::physical_plan::BlockSample;
 use crate::physical_optimizer::pruning::{PruningPredicate, PruningStatistics};
 use arrow::{array::ArrayRef, datatypes::Schema};
 use arrow_array::BooleanArray;
 use datafusion_common::{Column, Result, ScalarValue};
+use object_store::path::Path;
 use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
 use parquet::arrow::parquet_column;
 use parquet::basic::Type;
@@ -91,6 +93,21 @@ impl RowGroupAccessPlanFilter {
             }
         }
     }
+
+    /// Prune remaining row groups to a random sample, keeping every row group
+    /// with probability `sample.fraction`.
+    ///
+    /// A random number is drawn for every row group of the file, so that the
+    /// same row groups are chosen for every range of the file.
+    pub fn prune_by_sample(&mut self, sample: &BlockSample, location: &Path) {
+        let mut rng = sample.file_rng(location);
+        for idx in 0..self.access_plan.len() {
+            if !sample.should_read(&mut rng) {
+                self.access_plan.skip(idx);
+            }
+        }
+    }
+
     /// Prune remaining row groups using min/max/null_count statistics and
     /// the [`PruningPredicate`] to determine if the predicate can not be true.
     ///
@@ -483,6 +500,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn row_group_pruning_by_sample() {
+        let location = Path::from("file.parquet");
+        let sample_row_groups = |fraction: f64| {
+            let mut row_groups =
+                RowGroupAccessPlanFilter::new(ParquetAccessPlan::new_all(100));
+            row_groups.prune_by_sample(&BlockSample::new(fraction, Some(42)), &location);
+            row_groups.build().row_group_indexes()
+        };
+
+        let sampled = sample_row_groups(0.5);
+        assert!(sampled.len() > 20 && sampled.len() < 80);
+        // The same row groups are chosen with the same seed
+        assert_eq!(sampled, sample_row_groups(0.5));
+
+        assert_eq!(sample_row_groups(1.0).len(), 100);
+        assert!(sample_row_groups(0.0).is_empty());
+    }
+
     #[test]
     fn row_group_pruning_predicate_simple_expr() {
         use datafusion_expr::{col, lit};
@@ -1501,7 +1537,7 @@ mod tests {
         use object_store::{ObjectMeta, ObjectStore};
 
         let object_meta = ObjectMeta {
-            location: object_store::path::Path::parse(file_name).expect("creating path"),
+            location: Path::parse(file_name).expect("creating path"),
             last_modified: chrono::DateTime::from(std::time::SystemTime::now()),
             size: data.len(),
             e_tag: None,
@@ -20,7 +20,9 @@ use std::sync::Arc;
 
 use futures::{Stream, StreamExt};
 
-use datafusion_common::stats::Precision;
+use arrow::array::ArrayRef;
+use arrow::compute::{filter, is_not_null, partition, sort};
+use datafusion_common::stats::{Histogram, HistogramBucket, MostCommonValues, Precision};
 use datafusion_common::ScalarValue;
 
 use crate::arrow::datatypes::SchemaRef;
@@ -76,6 +78,8 @@ pub async fn get_statistics_with_limit(
             col_stats_set[index].null_count = file_column.null_count;
             col_stats_set[index].max_value = file_column.max_value;
             col_stats_set[index].min_value = file_column.min_value;
+            col_stats_set[index].histogram = file_column.histogram;
+            col_stats_set[index].most_common_values = file_column.most_common_values;
         }
 
         // If the number of rows exceeds the limit, we can stop processing
@@ -114,11 +118,24 @@ pub async fn get_statistics_with_limit(
                         max_value: file_max,
                         min_value: file_min,
                         distinct_count: _,
+                        histogram: file_histogram,
+                        most_common_values: file_mcv,
                     } = file_col_stats;
 
                     col_stats.null_count = add_row_stats(*file_nc, col_stats.null_count);
                     set_max_if_greater(file_max, &mut col_stats.max_value);
-                    set_min_if_lesser(file_min, &mut col_stats.min_value)
+                    set_min_if_lesser(file_min, &mut col_stats.min_value);
+                    // The distribution of the values is only known if it is
+                    // known for every file
+                    col_stats.histogram = match (&col_stats.histogram, file_histogram) {
+                        (Some(lhs), Some(rhs)) => Some(Arc::new(lhs.merge(rhs))),
+                        _ => None,
+                    };
+                    col_stats.most_common_values =
+                        match (&col_stats.most_common_values, file_mcv) {
+                            (Some(lhs), Some(rhs)) => Some(Arc::new(lhs.merge(rhs))),
+                            _ => None,
+                        };
                 }
 
                 // If the number of rows exceeds the limit, we can stop processing
@@ -149,6 +166,102 @@ pub async fn get_statistics_with_limit(
     Ok((result_files, statistics))
 }
 
+/// Computes the exact statistics of a column from all its `values`.
+///
+/// If `num_buckets` is not zero, an equi-depth [`Histogram`] with at most
+/// `num_buckets` buckets is computed, and if `num_most_common_values` is not
+/// zero, the values more frequent than the average value are recorded as
+/// [`MostCommonValues`], up to `num_most_common_values` of them. The histogram
+/// describes the values which are not among the most common values.
+///
+/// Only the null count is computed for values which can not be sorted.
+pub(crate) fn analyze_column(
+    values: &ArrayRef,
+    num_buckets: usize,
+    num_most_common_values: usize,
+) -> Result<ColumnStatistics> {
+    let mut statistics = ColumnStatistics {
+        null_count: Precision::Exact(values.null_count()),
+        ..ColumnStatistics::new_unknown()
+    };
+    let values = filter(values, &is_not_null(values)?)?;
+    let Ok(sorted) = sort(&values, None) else {
+        return Ok(statistics);
+    };
+    // Ranges of equal values, in ascending order
+    let ranges = partition(&[Arc::clone(&sorted)])?.ranges();
+    statistics.distinct_count = Precision::Exact(ranges.len());
+    if sorted.is_empty() {
+        return Ok(statistics);
+    }
+    statistics.min_value = Precision::Exact(ScalarValue::try_from_array(&sorted, 0)?);
+    statistics.max_value =
+        Precision::Exact(ScalarValue::try_from_array(&sorted, sorted.len() - 1)?);
+
+    let mut most_common = vec![];
+    if num_most_common_values > 0 {
+        most_common = ranges
+            .iter()
+            .filter(|range| range.len() * ranges.len() > sorted.len())
+            .collect::<Vec<_>>();
+        most_common.sort_by_key(|range| std::cmp::Reverse(range.len()));
+        most_common.truncate(num_most_common_values);
+        let values = most_common
+            .iter()
+            .map(|range| {
+                Ok((
+                    ScalarValue::try_from_array(&sorted, range.start)?,
+                    range.len(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !values.is_empty() {
+            statistics.most_common_values = Some(Arc::new(MostCommonValues::new(values)));
+        }
+    }
+
+    if num_buckets > 0 {
+        let remaining = ranges
+            .iter()
+            .filter(|range| !most_common.iter().any(|mcv| mcv.start == range.start))
+            .collect::<Vec<_>>();
+        let remaining_rows = remaining.iter().map(|range| range.len()).sum::<usize>();
+        let bucket_rows = remaining_rows.div_ceil(num_buckets).max(1);
+        let mut buckets = vec![];
+        let mut bucket_ranges = vec![];
+        let mut num_rows = 0;
+        for range in remaining {
+            bucket_ranges.push(range);
+            num_rows += range.len();
+            if num_rows >= bucket_rows {
+                buckets.push(histogram_bucket(&sorted, &bucket_ranges, num_rows)?);
+                bucket_ranges.clear();
+                num_rows = 0;
+            }
+        }
+        if !bucket_ranges.is_empty() {
+            buckets.push(histogram_bucket(&sorted, &bucket_ranges, num_rows)?);
+        }
+        statistics.histogram = Some(Arc::new(Histogram::new(buckets)));
+    }
+    Ok(statistics)
+}
+
+/// Creates the histogram bucket holding the values of the given ranges of
+/// `sorted`
+fn histogram_bucket(
+    sorted: &ArrayRef,
+    ranges: &[&std::ops::Range<usize>],
+    num_rows: usize,
+) -> Result<HistogramBucket> {
+    Ok(HistogramBucket {
+        lower: ScalarValue::try_from_array(sorted, ranges[0].start)?,
+        upper: ScalarValue::try_from_array(sorted, ranges[ranges.len() - 1].start)?,
+        num_rows,
+        distinct_count: ranges.len(),
+    })
+}
+
 // only adding this cfg b/c this is the only feature it's used with currently
 #[cfg(feature = "parquet")]
 pub(crate) fn create_max_min_accs(
@@ -205,6 +318,8 @@ pub(crate) fn get_col_stats(
                 max_value: max_value.map(Precision::Exact).unwrap_or(Precision::Absent),
                 min_value: min_value.map(Precision::Exact).unwrap_or(Precision::Absent),
                 distinct_count: Precision::Absent,
+                histogram: None,
+                most_common_values: None,
             }
         })
         .collect()
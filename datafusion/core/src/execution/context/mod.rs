@@ -34,15 +34,17 @@ use crate::{
         function::{TableFunction, TableFunctionImpl},
         listing::{ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl},
     },
-    datasource::{provider_as_source, MemTable, ViewTable},
+    datasource::{provider_as_source, MaterializedView, MemTable, ViewTable},
     error::{DataFusionError, Result},
     execution::{options::ArrowReadOptions, runtime_env::RuntimeEnv, FunctionRegistry},
     logical_expr::AggregateUDF,
     logical_expr::ScalarUDF,
     logical_expr::{
-        CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-        CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, LogicalPlan, LogicalPlanBuilder, SetVariable, TableType, UNNAMED_TABLE,
+        AnalyzeTable, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+        CreateFunction, CreateMaterializedView, CreateMemoryTable, CreateView,
+        DropCatalogSchema, DropFunction, DropTable, DropView, LogicalPlan,
+        LogicalPlanBuilder, RefreshMaterializedView, SetVariable, TableType,
+        UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
     physical_plan::ExecutionPlan,
@@ -56,7 +58,7 @@ use datafusion_common::{
     config::{ConfigExtension, TableOptions},
     exec_err, not_impl_err, plan_datafusion_err, plan_err,
     tree_node::{TreeNodeRecursion, TreeNodeVisitor},
-    DFSchema, SchemaReference, TableReference,
+    Constraints, DFSchema, SchemaReference, TableReference,
 };
 use datafusion_execution::registry::SerializerRegistry;
 use datafusion_expr::{
@@ -68,6 +70,7 @@ use datafusion_expr::{
 
 // backwards compatibility
 pub use crate::execution::session_state::SessionState;
+use crate::execution::session_state::{TEMP_CATALOG, TEMP_SCHEMA};
 
 use crate::datasource::dynamic_file::DynamicListTableFactory;
 use crate::execution::session_state::SessionStateBuilder;
@@ -77,6 +80,7 @@ use datafusion_catalog::{DynamicFileCatalog, SessionStore, UrlTableFactory};
 pub use datafusion_execution::config::SessionConfig;
 pub use datafusion_execution::TaskContext;
 pub use datafusion_expr::execution_props::ExecutionProps;
+use datafusion_optimizer::materialized_view_rewrite::MaterializedViewDefinition;
 use datafusion_optimizer::{AnalyzerRule, OptimizerRule};
 use object_store::ObjectStore;
 use parking_lot::RwLock;
@@ -85,6 +89,8 @@ use url::Url;
 mod avro;
 mod csv;
 mod json;
+#[cfg(feature = "orc")]
+mod orc;
 #[cfg(feature = "parquet")]
 mod parquet;
 
@@ -663,6 +669,15 @@ impl SessionContext {
                     DdlStatement::CreateView(cmd) => {
                         Box::pin(self.create_view(cmd)).await
                     }
+                    DdlStatement::CreateMaterializedView(cmd) => {
+                        Box::pin(self.create_materialized_view(cmd)).await
+                    }
+                    DdlStatement::RefreshMaterializedView(cmd) => {
+                        Box::pin(self.refresh_materialized_view(cmd)).await
+                    }
+                    DdlStatement::AnalyzeTable(cmd) => {
+                        Box::pin(self.analyze_table(cmd)).await
+                    }
                     DdlStatement::CreateCatalogSchema(cmd) => {
                         Box::pin(self.create_catalog_schema(cmd)).await
                     }
@@ -737,11 +752,11 @@ impl SessionContext {
         &self,
         cmd: &CreateExternalTable,
     ) -> Result<DataFrame> {
-        let exist = self.table_exist(cmd.name.clone())?;
-
-        if cmd.temporary {
-            return not_impl_err!("Temporary tables not supported");
-        }
+        let name = match cmd.temporary {
+            true => temp_table_ref(&cmd.name)?,
+            false => cmd.name.clone(),
+        };
+        let exist = self.table_exist(name.clone())?;
 
         if exist {
             match cmd.if_not_exists {
@@ -754,7 +769,7 @@ impl SessionContext {
 
         let table_provider: Arc<dyn TableProvider> =
             self.create_custom_table(cmd).await?;
-        self.register_table(cmd.name.clone(), table_provider)?;
+        self.register_table(name, table_provider)?;
         self.return_empty_dataframe()
     }
 
@@ -772,9 +787,10 @@ impl SessionContext {
         let input = Arc::unwrap_or_clone(input);
         let input = self.state().optimize(&input)?;
 
-        if temporary {
-            return not_impl_err!("Temporary tables not supported");
-        }
+        let name = match temporary {
+            true => temp_table_ref(&name)?,
+            false => name,
+        };
 
         let table = self.table(name.clone()).await;
         match (if_not_exists, or_replace, table) {
@@ -850,6 +866,109 @@ impl SessionContext {
         }
     }
 
+    async fn create_materialized_view(
+        &self,
+        cmd: CreateMaterializedView,
+    ) -> Result<DataFrame> {
+        let CreateMaterializedView {
+            name,
+            input,
+            or_replace,
+            if_not_exists,
+            definition,
+            location,
+            file_type,
+        } = cmd;
+
+        let exists = self.table(name.clone()).await.is_ok();
+        match (if_not_exists, or_replace, exists) {
+            (true, true, _) => {
+                return exec_err!("'IF NOT EXISTS' cannot coexist with 'REPLACE'")
+            }
+            (true, false, true) => return self.return_empty_dataframe(),
+            (false, false, true) => {
+                return exec_err!("Table '{name}' already exists");
+            }
+            _ => {}
+        }
+
+        let state = self.state();
+        let logical_plan = state.analyzer().execute_and_check(
+            Arc::unwrap_or_clone(input),
+            state.config_options(),
+            |_, _| {},
+        )?;
+        let schema = Arc::clone(logical_plan.schema().inner());
+        let storage: Arc<dyn TableProvider> = match location {
+            Some(mut location) => {
+                if !location.ends_with('/') {
+                    location.push('/');
+                }
+                let cmd = CreateExternalTable {
+                    schema: Arc::new(DFSchema::try_from(schema.as_ref().clone())?),
+                    name: name.clone(),
+                    location,
+                    file_type,
+                    table_partition_cols: vec![],
+                    if_not_exists: false,
+                    temporary: false,
+                    definition: None,
+                    order_exprs: vec![],
+                    unbounded: false,
+                    options: Default::default(),
+                    constraints: Constraints::empty(),
+                    column_defaults: Default::default(),
+                };
+                self.create_custom_table(&cmd).await?
+            }
+            None => {
+                let partitions = vec![vec![]; state.config().target_partitions()];
+                Arc::new(MemTable::try_new(schema, partitions)?)
+            }
+        };
+
+        let view = Arc::new(MaterializedView::try_new(
+            logical_plan.clone(),
+            definition,
+            storage,
+        )?);
+        view.refresh(&state, false).await?;
+
+        if exists {
+            self.deregister_table(name.clone())?;
+        }
+        self.register_table(name.clone(), Arc::clone(&view) as _)?;
+        self.state.write().register_materialized_view(Arc::new(
+            MaterializedViewDefinition::new(name, logical_plan, provider_as_source(view)),
+        ));
+        self.return_empty_dataframe()
+    }
+
+    async fn refresh_materialized_view(
+        &self,
+        cmd: RefreshMaterializedView,
+    ) -> Result<DataFrame> {
+        let RefreshMaterializedView {
+            name, incremental, ..
+        } = cmd;
+        let table = self.table_provider(name.clone()).await?;
+        let Some(view) = table.as_any().downcast_ref::<MaterializedView>() else {
+            return exec_err!("'{name}' is not a materialized view");
+        };
+        view.refresh(&self.state(), incremental).await?;
+        self.return_empty_dataframe()
+    }
+
+    async fn analyze_table(&self, cmd: AnalyzeTable) -> Result<DataFrame> {
+        let AnalyzeTable { name, columns, .. } = cmd;
+        let table = self.table_provider(name).await?;
+        let Some(table) = table.as_any().downcast_ref::<ListingTable>() else {
+            return not_impl_err!("ANALYZE TABLE is only supported for listing tables");
+        };
+        table.analyze(&self.state(), &columns).await?;
+        self.return_empty_dataframe()
+    }
+
     async fn create_catalog_schema(&self, cmd: CreateCatalogSchema) -> Result<DataFrame> {
         let CreateCatalogSchema {
             schema_name,
@@ -899,6 +1018,11 @@ impl SessionContext {
             if_not_exists,
             ..
         } = cmd;
+        if catalog_name == TEMP_CATALOG {
+            return plan_err!(
+                "Catalog name '{catalog_name}' is reserved for temporary tables"
+            );
+        }
         let catalog = self.catalog(catalog_name.as_str());
 
         match (if_not_exists, catalog) {
@@ -1019,17 +1143,23 @@ impl SessionContext {
         let table = table_ref.table().to_owned();
         let maybe_schema = {
             let state = self.state.read();
-            let resolved = state.resolve_table_ref(table_ref);
-            state
-                .catalog_list()
-                .catalog(&resolved.catalog)
-                .and_then(|c| c.schema(&resolved.schema))
+            let resolved = state.resolve_table_ref(table_ref.clone());
+            if *resolved.catalog == *TEMP_CATALOG {
+                (*resolved.schema == *TEMP_SCHEMA)
+                    .then(|| Arc::clone(state.temp_schema()))
+            } else {
+                state
+                    .catalog_list()
+                    .catalog(&resolved.catalog)
+                    .and_then(|c| c.schema(&resolved.schema))
+            }
         };
 
         if let Some(schema) = maybe_schema {
             if let Some(table_provider) = schema.table(&table).await? {
                 if table_provider.table_type() == table_type {
                     schema.deregister_table(&table)?;
+                    self.state.write().deregister_materialized_view(table_ref);
                     return Ok(true);
                 }
             }
@@ -1327,6 +1457,9 @@ impl SessionContext {
     ///
     /// Returns the [`CatalogProvider`] previously registered for this
     /// name, if any
+    ///
+    /// The name [`TEMP_CATALOG`] is reserved for the temporary tables of the
+    /// session: queries referencing a catalog registered under it fail.
     pub fn register_catalog(
         &self,
         name: impl Into<String>,
@@ -1376,10 +1509,9 @@ impl SessionContext {
     ) -> Result<Option<Arc<dyn TableProvider>>> {
         let table_ref = table_ref.into();
         let table = table_ref.table().to_owned();
-        self.state
-            .read()
-            .schema_for_ref(table_ref)?
-            .deregister_table(&table)
+        let mut state = self.state.write();
+        state.deregister_materialized_view(table_ref.clone());
+        state.schema_for_ref(table_ref)?.deregister_table(&table)
     }
 
     /// Return `true` if the specified table exists in the schema provider.
@@ -1555,6 +1687,24 @@ impl From<SessionState> for SessionContext {
     }
 }
 
+/// Returns the reference of the temporary table `name`, which may only be
+/// qualified by the temporary catalog and schema
+fn temp_table_ref(name: &TableReference) -> Result<TableReference> {
+    match name {
+        TableReference::Bare { table } => Ok(TableReference::full(
+            TEMP_CATALOG,
+            TEMP_SCHEMA,
+            Arc::clone(table),
+        )),
+        TableReference::Full {
+            catalog, schema, ..
+        } if **catalog == *TEMP_CATALOG && **schema == *TEMP_SCHEMA => Ok(name.clone()),
+        _ => plan_err!(
+            "Temporary table '{name}' can not be created in another schema than {TEMP_CATALOG}.{TEMP_SCHEMA}"
+        ),
+    }
+}
+
 impl From<SessionContext> for SessionStateBuilder {
     fn from(session: SessionContext) -> Self {
         session.into_state_builder()
@@ -1722,6 +1872,7 @@ mod tests {
     use crate::test;
     use crate::test_util::{plan_and_collect, populate_csv_partitions};
 
+    use datafusion_common::assert_contains;
     use datafusion_common_runtime::SpawnedTask;
 
     use crate::catalog::SchemaProvider;
@@ -1822,6 +1973,100 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn temporary_tables_are_session_local() -> Result<()> {
+        let config = SessionConfig::new().with_information_schema(true);
+        let ctx1 = SessionContext::new_with_config(config.clone());
+        let ctx2: SessionContext = SessionStateBuilder::new()
+            .with_config(config)
+            .with_catalog_list(Arc::clone(ctx1.state().catalog_list()))
+            .with_default_features()
+            .build()
+            .into();
+
+        ctx1.sql("CREATE TABLE shared AS VALUES (1)").await?;
+        ctx1.sql("CREATE TEMPORARY TABLE scratch AS VALUES (2)")
+            .await?;
+        ctx1.sql("CREATE TEMPORARY TABLE shared AS VALUES (3)")
+            .await?;
+
+        let tables = "SELECT table_catalog, table_name FROM information_schema.tables \
+            WHERE table_schema = 'public' ORDER BY table_catalog, table_name";
+        let results = ctx1.sql(tables).await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+---------------+------------+",
+                "| table_catalog | table_name |",
+                "+---------------+------------+",
+                "| datafusion    | shared     |",
+                "| temp          | scratch    |",
+                "| temp          | shared     |",
+                "+---------------+------------+",
+            ],
+            &results
+        );
+
+        // Other sessions sharing the catalog do not see the temporary tables
+        let results = ctx2.sql(tables).await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+---------------+------------+",
+                "| table_catalog | table_name |",
+                "+---------------+------------+",
+                "| datafusion    | shared     |",
+                "+---------------+------------+",
+            ],
+            &results
+        );
+        let results = ctx2.sql("SELECT * FROM shared").await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+---------+",
+                "| column1 |",
+                "+---------+",
+                "| 1       |",
+                "+---------+",
+            ],
+            &results
+        );
+        let err = ctx2.sql("SELECT * FROM scratch").await.unwrap_err();
+        assert_contains!(
+            err.strip_backtrace(),
+            "table 'datafusion.public.scratch' not found"
+        );
+
+        // The temporary tables are dropped with the session
+        let scratch = Arc::downgrade(&ctx1.table_provider("scratch").await?);
+        drop(ctx1);
+        assert!(scratch.upgrade().is_none());
+        assert!(ctx2.table_exist("shared")?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn temp_catalog_name_is_reserved() -> Result<()> {
+        let ctx = SessionContext::new();
+        let err = ctx.sql("CREATE DATABASE temp").await.unwrap_err();
+        assert_contains!(
+            err.strip_backtrace(),
+            "Catalog name 'temp' is reserved for temporary tables"
+        );
+
+        let catalog = MemoryCatalogProvider::new();
+        let schema = MemorySchemaProvider::new();
+        schema.register_table("t".to_owned(), test::table_with_sequence(1, 1)?)?;
+        catalog.register_schema(TEMP_SCHEMA, Arc::new(schema))?;
+        ctx.register_catalog(TEMP_CATALOG, Arc::new(catalog));
+        let err = ctx.table_provider("temp.public.t").await.unwrap_err();
+        assert_contains!(
+            err.strip_backtrace(),
+            "catalog name temp is reserved for temporary tables"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn send_context_to_threads() -> Result<()> {
         // ensure SessionContexts can be used in a multi-threaded
@@ -0,0 +1,158 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::super::options::{OrcReadOptions, ReadOptions};
+use super::{DataFilePaths, DataFrame, Result, SessionContext};
+use datafusion_common::TableReference;
+use std::sync::Arc;
+
+impl SessionContext {
+    /// Creates a [`DataFrame`] for reading an ORC data source.
+    ///
+    /// For more control such as reading multiple files, you can use
+    /// [`read_table`](Self::read_table) with a [`super::ListingTable`].
+    ///
+    /// For an example, see [`read_csv`](Self::read_csv)
+    pub async fn read_orc<P: DataFilePaths>(
+        &self,
+        table_paths: P,
+        options: OrcReadOptions<'_>,
+    ) -> Result<DataFrame> {
+        self._read_type(table_paths, options).await
+    }
+
+    /// Registers an ORC file as a table that can be referenced from
+    /// SQL statements executed against this context.
+    pub async fn register_orc(
+        &self,
+        table_ref: impl Into<TableReference>,
+        table_path: impl AsRef<str>,
+        options: OrcReadOptions<'_>,
+    ) -> Result<()> {
+        let listing_options = options
+            .to_listing_options(&self.copied_config(), self.copied_table_options());
+
+        self.register_listing_table(
+            table_ref,
+            table_path,
+            listing_options,
+            options.schema.map(|s| Arc::new(s.to_owned())),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::array::{Int32Array, StringArray};
+    use crate::arrow::datatypes::{DataType, Field, Schema};
+    use crate::arrow::record_batch::RecordBatch;
+
+    use orc_rust::ArrowWriterBuilder;
+    use tempfile::tempdir;
+
+    fn write_orc_file(path: &std::path::Path, batch: &RecordBatch) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut writer = ArrowWriterBuilder::new(file, batch.schema())
+            .try_build()
+            .unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    fn test_batch(ids: Vec<i32>) -> RecordBatch {
+        let names = ids.iter().map(|id| format!("name{id}")).collect::<Vec<_>>();
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(ids)) as _),
+            ("name", Arc::new(StringArray::from(names)) as _),
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn register_orc_and_query() -> Result<()> {
+        let dir = tempdir()?;
+        write_orc_file(&dir.path().join("1.orc"), &test_batch(vec![1, 2, 3]));
+        write_orc_file(&dir.path().join("2.orc"), &test_batch(vec![4, 5]));
+
+        let ctx = SessionContext::new();
+        ctx.register_orc("t", dir.path().to_str().unwrap(), OrcReadOptions::default())
+            .await?;
+
+        let results = ctx
+            .sql("SELECT name FROM t WHERE id > 2 ORDER BY id")
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+-------+",
+            "| name  |",
+            "+-------+",
+            "| name3 |",
+            "| name4 |",
+            "| name5 |",
+            "+-------+",
+        ];
+        crate::assert_batches_eq!(expected, &results);
+
+        let results = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        let expected = [
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 5        |",
+            "+----------+",
+        ];
+        crate::assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_orc_with_schema() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("1.orc");
+        write_orc_file(&path, &test_batch(vec![1, 2]));
+
+        // a column missing from the file is read as nulls
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+        let ctx = SessionContext::new();
+        let results = ctx
+            .read_orc(
+                path.to_str().unwrap(),
+                OrcReadOptions::default().schema(&schema),
+            )
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+----+-------+",
+            "| id | score |",
+            "+----+-------+",
+            "| 1  |       |",
+            "| 2  |       |",
+            "+----+-------+",
+        ];
+        crate::assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+}
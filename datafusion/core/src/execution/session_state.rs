@@ -17,11 +17,15 @@
 
 //! [`SessionState`]: information required to run queries in a session
 
-use crate::catalog::{CatalogProviderList, SchemaProvider, TableProviderFactory};
+use crate::catalog::{
+    CatalogProvider, CatalogProviderList, SchemaProvider, TableProviderFactory,
+};
 use crate::catalog_common::information_schema::{
     InformationSchemaProvider, INFORMATION_SCHEMA,
 };
-use crate::catalog_common::MemoryCatalogProviderList;
+use crate::catalog_common::{
+    MemoryCatalogProvider, MemoryCatalogProviderList, MemorySchemaProvider,
+};
 use crate::datasource::cte_worktable::CteWorkTable;
 use crate::datasource::file_format::{format_as_file_type, FileFormatFactory};
 use crate::datasource::function::{TableFunction, TableFunctionImpl};
@@ -40,10 +44,11 @@ use datafusion_common::display::{PlanType, StringifiedPlan, ToStringifiedPlan};
 use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::tree_node::TreeNode;
 use datafusion_common::{
-    config_err, not_impl_err, plan_datafusion_err, DFSchema, DataFusionError,
+    config_err, not_impl_err, plan_datafusion_err, plan_err, DFSchema, DataFusionError,
     ResolvedTableReference, TableReference,
 };
 use datafusion_execution::config::SessionConfig;
+use datafusion_execution::memory_pool::QueryMemoryPool;
 use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
 use datafusion_expr::execution_props::ExecutionProps;
@@ -56,6 +61,7 @@ use datafusion_expr::{
     AggregateUDF, Explain, Expr, ExprSchemable, LogicalPlan, ScalarUDF, TableSource,
     WindowUDF,
 };
+use datafusion_optimizer::materialized_view_rewrite::MaterializedViewDefinition;
 use datafusion_optimizer::simplify_expressions::ExprSimplifier;
 use datafusion_optimizer::{
     Analyzer, AnalyzerRule, Optimizer, OptimizerConfig, OptimizerRule,
@@ -79,6 +85,15 @@ use std::sync::Arc;
 use url::Url;
 use uuid::Uuid;
 
+/// Name of the catalog holding the temporary tables of a session
+///
+/// This name is reserved: the tables of a catalog registered under it can't be
+/// queried
+pub const TEMP_CATALOG: &str = "temp";
+
+/// Name of the schema holding the temporary tables of a session
+pub const TEMP_SCHEMA: &str = "public";
+
 /// `SessionState` contains all the necessary state to plan and execute queries,
 /// such as configuration, functions, and runtime environment. Please see the
 /// documentation on [`SessionContext`] for more information.
@@ -171,6 +186,12 @@ pub struct SessionState {
     /// It will be invoked on `CREATE FUNCTION` statements.
     /// thus, changing dialect o PostgreSql is required
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    /// Materialized views which queries may be rewritten to read from
+    materialized_views: Vec<Arc<MaterializedViewDefinition>>,
+    /// Temporary tables, which are only visible to this session and are
+    /// dropped with it. Unqualified table references are resolved to them
+    /// ahead of the tables of the default schema.
+    temp_schema: Arc<dyn SchemaProvider>,
 }
 
 impl Debug for SessionState {
@@ -188,6 +209,8 @@ impl Debug for SessionState {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field("materialized_views", &self.materialized_views)
+            .field("temp_schema", &self.temp_schema)
             .field("expr_planners", &self.expr_planners)
             .field("query_planners", &self.query_planner)
             .field("analyzer", &self.analyzer)
@@ -283,10 +306,14 @@ impl SessionState {
         &self,
         table_ref: impl Into<TableReference>,
     ) -> ResolvedTableReference {
+        let table_ref = table_ref.into();
+        if let TableReference::Bare { table } = &table_ref {
+            if self.temp_schema.table_exist(table) {
+                return table_ref.resolve(TEMP_CATALOG, TEMP_SCHEMA);
+            }
+        }
         let catalog = &self.config_options().catalog;
-        table_ref
-            .into()
-            .resolve(&catalog.default_catalog, &catalog.default_schema)
+        table_ref.resolve(&catalog.default_catalog, &catalog.default_schema)
     }
 
     pub(crate) fn schema_for_ref(
@@ -296,9 +323,27 @@ impl SessionState {
         let resolved_ref = self.resolve_table_ref(table_ref);
         if self.config.information_schema() && *resolved_ref.schema == *INFORMATION_SCHEMA
         {
-            return Ok(Arc::new(InformationSchemaProvider::new(
-                self.catalog_list.clone(),
-            )));
+            return Ok(Arc::new(InformationSchemaProvider::new(Arc::new(
+                SessionCatalogList {
+                    catalog_list: Arc::clone(&self.catalog_list),
+                    temp_schema: Arc::clone(&self.temp_schema),
+                },
+            ))));
+        }
+
+        if *resolved_ref.catalog == *TEMP_CATALOG {
+            if self.catalog_list.catalog(TEMP_CATALOG).is_some() {
+                return plan_err!(
+                    "catalog name {TEMP_CATALOG} is reserved for temporary tables, register the catalog under another name"
+                );
+            }
+            if *resolved_ref.schema != *TEMP_SCHEMA {
+                return plan_err!(
+                    "failed to resolve schema: {}, temporary tables are in {TEMP_CATALOG}.{TEMP_SCHEMA}",
+                    resolved_ref.schema
+                );
+            }
+            return Ok(Arc::clone(&self.temp_schema));
         }
 
         self.catalog_list
@@ -444,6 +489,32 @@ impl SessionState {
         self.function_factory.as_ref()
     }
 
+    /// Registers a materialized view, which queries may be rewritten to read
+    /// from if `datafusion.optimizer.enable_materialized_view_rewrite` is set,
+    /// replacing any previously registered view with the same name
+    pub fn register_materialized_view(&mut self, view: Arc<MaterializedViewDefinition>) {
+        self.deregister_materialized_view(view.name().clone());
+        self.materialized_views.push(view);
+    }
+
+    /// Deregisters the materialized view with the given name, returning it
+    /// if it was registered
+    pub fn deregister_materialized_view(
+        &mut self,
+        name: impl Into<TableReference>,
+    ) -> Option<Arc<MaterializedViewDefinition>> {
+        let name = self.resolve_table_ref(name).to_string();
+        let index = self.materialized_views.iter().position(|view| {
+            self.resolve_table_ref(view.name().clone()).to_string() == name
+        })?;
+        Some(self.materialized_views.remove(index))
+    }
+
+    /// Get the schema holding the temporary tables of this session
+    pub fn temp_schema(&self) -> &Arc<dyn SchemaProvider> {
+        &self.temp_schema
+    }
+
     /// Get the table factories
     pub fn table_factories(&self) -> &HashMap<String, Arc<dyn TableProviderFactory>> {
         &self.table_factories
@@ -932,6 +1003,7 @@ pub struct SessionStateBuilder {
     table_factories: Option<HashMap<String, Arc<dyn TableProviderFactory>>>,
     runtime_env: Option<Arc<RuntimeEnv>>,
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    materialized_views: Option<Vec<Arc<MaterializedViewDefinition>>>,
     // fields to support convenience functions
     analyzer_rules: Option<Vec<Arc<dyn AnalyzerRule + Send + Sync>>>,
     optimizer_rules: Option<Vec<Arc<dyn OptimizerRule + Send + Sync>>>,
@@ -961,6 +1033,7 @@ impl SessionStateBuilder {
             table_factories: None,
             runtime_env: None,
             function_factory: None,
+            materialized_views: None,
             // fields to support convenience functions
             analyzer_rules: None,
             optimizer_rules: None,
@@ -969,7 +1042,8 @@ impl SessionStateBuilder {
     }
 
     /// Returns a new [SessionStateBuilder] based on an existing [SessionState]
-    /// The session id for the new builder will be unset; all other fields will
+    /// The session id for the new builder will be unset, and the new session
+    /// will not have the temporary tables of the existing one; all other fields will
     /// be cloned from what is set in the provided session state. If the default
     /// catalog exists in existing session state, the new session state will not
     /// create default catalog and schema.
@@ -1010,6 +1084,7 @@ impl SessionStateBuilder {
             table_factories: Some(existing.table_factories),
             runtime_env: Some(existing.runtime_env),
             function_factory: existing.function_factory,
+            materialized_views: Some(existing.materialized_views),
 
             // fields to support convenience functions
             analyzer_rules: None,
@@ -1234,6 +1309,15 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Set the materialized views which queries may be rewritten to read from
+    pub fn with_materialized_views(
+        mut self,
+        materialized_views: Vec<Arc<MaterializedViewDefinition>>,
+    ) -> Self {
+        self.materialized_views = Some(materialized_views);
+        self
+    }
+
     /// Register an `ObjectStore` to the [`RuntimeEnv`]. See [`RuntimeEnv::register_object_store`]
     /// for more details.
     ///
@@ -1295,6 +1379,7 @@ impl SessionStateBuilder {
             table_factories,
             runtime_env,
             function_factory,
+            materialized_views,
             analyzer_rules,
             optimizer_rules,
             physical_optimizer_rules,
@@ -1327,6 +1412,8 @@ impl SessionStateBuilder {
             table_factories: table_factories.unwrap_or_default(),
             runtime_env,
             function_factory,
+            materialized_views: materialized_views.unwrap_or_default(),
+            temp_schema: Arc::new(MemorySchemaProvider::new()),
         };
 
         if let Some(file_formats) = file_formats {
@@ -1500,6 +1587,13 @@ impl SessionStateBuilder {
         &mut self.function_factory
     }
 
+    /// Returns the current materialized_views value
+    pub fn materialized_views(
+        &mut self,
+    ) -> &mut Option<Vec<Arc<MaterializedViewDefinition>>> {
+        &mut self.materialized_views
+    }
+
     /// Returns the current analyzer_rules value
     pub fn analyzer_rules(
         &mut self,
@@ -1537,6 +1631,7 @@ impl Debug for SessionStateBuilder {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field("materialized_views", &self.materialized_views)
             .field("expr_planners", &self.expr_planners)
             .field("query_planners", &self.query_planner)
             .field("analyzer_rules", &self.analyzer_rules)
@@ -1565,6 +1660,47 @@ impl From<SessionState> for SessionStateBuilder {
     }
 }
 
+/// The catalogs visible to a session: the catalogs of its [`CatalogProviderList`],
+/// and the catalog of its temporary tables if it has any
+#[derive(Debug)]
+struct SessionCatalogList {
+    catalog_list: Arc<dyn CatalogProviderList>,
+    temp_schema: Arc<dyn SchemaProvider>,
+}
+
+impl CatalogProviderList for SessionCatalogList {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn register_catalog(
+        &self,
+        name: String,
+        catalog: Arc<dyn CatalogProvider>,
+    ) -> Option<Arc<dyn CatalogProvider>> {
+        self.catalog_list.register_catalog(name, catalog)
+    }
+
+    fn catalog_names(&self) -> Vec<String> {
+        let mut names = self.catalog_list.catalog_names();
+        if !self.temp_schema.table_names().is_empty() {
+            names.push(TEMP_CATALOG.to_string());
+        }
+        names
+    }
+
+    fn catalog(&self, name: &str) -> Option<Arc<dyn CatalogProvider>> {
+        if name != TEMP_CATALOG {
+            return self.catalog_list.catalog(name);
+        }
+        let catalog = MemoryCatalogProvider::new();
+        catalog
+            .register_schema(TEMP_SCHEMA, Arc::clone(&self.temp_schema))
+            .ok()?;
+        Some(Arc::new(catalog))
+    }
+}
+
 /// Adapter that implements the [`ContextProvider`] trait for a [`SessionState`]
 ///
 /// This is used so the SQL planner can access the state of the session without
@@ -1810,12 +1946,36 @@ impl OptimizerConfig for SessionState {
     fn function_registry(&self) -> Option<&dyn FunctionRegistry> {
         Some(self)
     }
+
+    fn materialized_views(&self) -> &[Arc<MaterializedViewDefinition>] {
+        &self.materialized_views
+    }
 }
 
 /// Create a new task context instance from SessionState
 impl From<&SessionState> for TaskContext {
     fn from(state: &SessionState) -> Self {
         let task_id = None;
+        // Every query gets its own task context, so give it its own spill quota
+        let runtime_env = match state.config.options().execution.max_spill_disk_size {
+            Some(quota) => {
+                let mut runtime_env = state.runtime_env.as_ref().clone();
+                runtime_env.disk_manager =
+                    runtime_env.disk_manager.with_disk_quota(quota as u64);
+                Arc::new(runtime_env)
+            }
+            None => Arc::clone(&state.runtime_env),
+        };
+        // ... and its own memory pool, drawing from the shared one, to limit
+        // its memory usage and track its peak
+        let mut query_memory_pool = QueryMemoryPool::new(
+            Arc::clone(&runtime_env.memory_pool),
+            state.config.options().execution.query_memory_limit,
+        );
+        if let Some(admission_queue) = &runtime_env.memory_admission_queue {
+            query_memory_pool =
+                query_memory_pool.with_admission_queue(Arc::clone(admission_queue));
+        }
         TaskContext::new(
             task_id,
             state.session_id.clone(),
@@ -1823,8 +1983,9 @@ impl From<&SessionState> for TaskContext {
             state.scalar_functions.clone(),
             state.aggregate_functions.clone(),
             state.window_functions.clone(),
-            state.runtime_env.clone(),
+            runtime_env,
         )
+        .with_query_memory_pool(Arc::new(query_memory_pool))
     }
 }
 
@@ -22,6 +22,8 @@ use crate::datasource::file_format::arrow::ArrowFormatFactory;
 use crate::datasource::file_format::avro::AvroFormatFactory;
 use crate::datasource::file_format::csv::CsvFormatFactory;
 use crate::datasource::file_format::json::JsonFormatFactory;
+#[cfg(feature = "orc")]
+use crate::datasource::file_format::orc::OrcFormatFactory;
 #[cfg(feature = "parquet")]
 use crate::datasource::file_format::parquet::ParquetFormatFactory;
 use crate::datasource::file_format::FileFormatFactory;
@@ -55,6 +57,8 @@ impl SessionStateDefaults {
         table_factories.insert("NDJSON".into(), Arc::new(DefaultTableFactory::new()));
         table_factories.insert("AVRO".into(), Arc::new(DefaultTableFactory::new()));
         table_factories.insert("ARROW".into(), Arc::new(DefaultTableFactory::new()));
+        #[cfg(feature = "orc")]
+        table_factories.insert("ORC".into(), Arc::new(DefaultTableFactory::new()));
 
         table_factories
     }
@@ -90,7 +94,8 @@ impl SessionStateDefaults {
             Arc::new(functions_nested::planner::FieldAccessPlanner),
             #[cfg(any(
                 feature = "datetime_expressions",
-                feature = "unicode_expressions"
+                feature = "unicode_expressions",
+                feature = "json_expressions"
             ))]
             Arc::new(functions::planner::UserDefinedFunctionPlanner),
         ];
@@ -128,6 +133,8 @@ impl SessionStateDefaults {
             Arc::new(CsvFormatFactory::new()),
             Arc::new(ArrowFormatFactory::new()),
             Arc::new(AvroFormatFactory::new()),
+            #[cfg(feature = "orc")]
+            Arc::new(OrcFormatFactory::new()),
         ];
 
         file_formats
@@ -0,0 +1,354 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`DynamicJoinFilter`] rule lets [`HashJoinExec`] filter the
+//! [`ParquetExec`] on its probe side with the join keys of its build side
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::datasource::physical_plan::ParquetExec;
+use crate::error::Result;
+use crate::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use crate::physical_plan::filter::FilterExec;
+use crate::physical_plan::joins::HashJoinExec;
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::ExecutionPlan;
+
+use arrow_schema::DataType;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column, DynamicFilterExpr};
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_optimizer::PhysicalOptimizerRule;
+
+/// Optimizer rule that gives a [`HashJoinExec`] a [`DynamicFilterExpr`] on its
+/// probe side join keys, and adds the same filter to the predicate of the
+/// [`ParquetExec`] the probe side reads from.
+///
+/// Once the build side has been collected, the join publishes the bounds of
+/// its join keys to the filter, which the scan then uses to prune row groups,
+/// pages and rows that can't match any build side row.
+///
+/// The filter is only pushed through operators that keep the join keys as
+/// plain columns and don't limit their output, since a limit below the join
+/// would keep different rows if some were filtered out.
+#[derive(Default, Debug)]
+pub struct DynamicJoinFilter {}
+
+impl DynamicJoinFilter {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicalOptimizerRule for DynamicJoinFilter {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_dynamic_join_filter {
+            return Ok(plan);
+        }
+
+        plan.transform_up(|plan| {
+            let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+                return Ok(Transformed::no(plan));
+            };
+            if !join.supports_dynamic_filter() || join.dynamic_filter().is_some() {
+                return Ok(Transformed::no(plan));
+            }
+
+            let left_schema = join.left().schema();
+            let right_schema = join.right().schema();
+            let mut keys = Vec::with_capacity(join.on().len());
+            for (left_key, right_key) in join.on() {
+                let Some(column) = right_key.as_any().downcast_ref::<Column>() else {
+                    return Ok(Transformed::no(plan));
+                };
+                let data_type = right_key.data_type(&right_schema)?;
+                if !supports_data_type(&data_type)
+                    || left_key.data_type(&left_schema)? != data_type
+                {
+                    return Ok(Transformed::no(plan));
+                }
+                keys.push(column.clone());
+            }
+
+            let filter = Arc::new(DynamicFilterExpr::new(
+                keys.iter()
+                    .map(|column| Arc::new(column.clone()) as _)
+                    .collect(),
+            ));
+            let Some(right) = push_down_filter(join.right(), keys, &filter)? else {
+                return Ok(Transformed::no(plan));
+            };
+            let join = Arc::new(join.with_dynamic_filter(filter)?);
+            let left = Arc::clone(join.left());
+            join.with_new_children(vec![left, right])
+                .map(Transformed::yes)
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "dynamic_join_filter"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns whether the bounds of join keys of this type can be compared
+/// with literals of the same type
+fn supports_data_type(data_type: &DataType) -> bool {
+    data_type.is_primitive()
+        || matches!(
+            data_type,
+            DataType::Boolean
+                | DataType::Utf8
+                | DataType::LargeUtf8
+                | DataType::Utf8View
+                | DataType::Binary
+                | DataType::LargeBinary
+                | DataType::BinaryView
+                | DataType::FixedSizeBinary(_)
+        )
+}
+
+/// Adds `filter` on the columns `keys` of the output of `plan` to the
+/// [`ParquetExec`] that `plan` reads them from, returning the rewritten plan
+/// or `None` if there is no such scan.
+fn push_down_filter(
+    plan: &Arc<dyn ExecutionPlan>,
+    keys: Vec<Column>,
+    filter: &Arc<DynamicFilterExpr>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    if plan.fetch().is_some() {
+        return Ok(None);
+    }
+
+    let plan_any = plan.as_any();
+    if let Some(parquet) = plan_any.downcast_ref::<ParquetExec>() {
+        // The predicate of the scan refers to the columns of the file schema
+        let file_schema = &parquet.base_config().file_schema;
+        let output_schema = parquet.schema();
+        let mut children = Vec::with_capacity(keys.len());
+        for key in keys {
+            let name = output_schema.field(key.index()).name();
+            // Partition columns can't be pruned with file statistics
+            let Ok(index) = file_schema.index_of(name) else {
+                return Ok(None);
+            };
+            children.push(Arc::new(Column::new(name, index)) as Arc<dyn PhysicalExpr>);
+        }
+        let filter = Arc::clone(filter).with_new_children(children)?;
+        let predicate = match parquet.predicate() {
+            Some(predicate) => Arc::new(BinaryExpr::new(
+                Arc::clone(predicate),
+                Operator::And,
+                filter,
+            )),
+            None => filter,
+        };
+        let parquet = parquet
+            .clone()
+            .into_builder()
+            .with_predicate(predicate)
+            .build_arc();
+        return Ok(Some(parquet));
+    }
+
+    let keys = if let Some(filter_exec) = plan_any.downcast_ref::<FilterExec>() {
+        match filter_exec.projection() {
+            Some(projection) => {
+                let input_schema = filter_exec.input().schema();
+                keys.iter()
+                    .map(|key| {
+                        let index = projection[key.index()];
+                        Column::new(input_schema.field(index).name(), index)
+                    })
+                    .collect()
+            }
+            None => keys,
+        }
+    } else if let Some(projection) = plan_any.downcast_ref::<ProjectionExec>() {
+        let mut input_keys = Vec::with_capacity(keys.len());
+        for key in keys {
+            let (expr, _) = &projection.expr()[key.index()];
+            let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+                return Ok(None);
+            };
+            input_keys.push(column.clone());
+        }
+        input_keys
+    } else if plan_any.is::<RepartitionExec>()
+        || plan_any.is::<CoalesceBatchesExec>()
+        || plan_any.is::<CoalescePartitionsExec>()
+    {
+        keys
+    } else {
+        return Ok(None);
+    };
+
+    let input = Arc::clone(plan.children()[0]);
+    let Some(input) = push_down_filter(&input, keys, filter)? else {
+        return Ok(None);
+    };
+    Arc::clone(plan).with_new_children(vec![input]).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_optimizer::test_utils::{
+        coalesce_batches_exec, filter_exec, global_limit_exec, memory_exec, parquet_exec,
+        repartition_exec,
+    };
+    use crate::physical_plan::get_plan_string;
+    use crate::physical_plan::joins::PartitionMode;
+
+    use arrow_schema::{Field, Schema, SchemaRef};
+    use datafusion_common::JoinType;
+    use datafusion_physical_expr::expressions::{col, lit};
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Int64, true),
+        ]))
+    }
+
+    fn hash_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+    ) -> Arc<dyn ExecutionPlan> {
+        let on = vec![
+            (
+                col("a", &left.schema()).unwrap(),
+                col("a", &right.schema()).unwrap(),
+            ),
+            (
+                col("b", &left.schema()).unwrap(),
+                col("b", &right.schema()).unwrap(),
+            ),
+        ];
+        Arc::new(
+            HashJoinExec::try_new(
+                left,
+                right,
+                on,
+                None,
+                &join_type,
+                None,
+                PartitionMode::CollectLeft,
+                false,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn optimize(plan: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_dynamic_join_filter = true;
+        DynamicJoinFilter::new().optimize(plan, &config).unwrap()
+    }
+
+    #[test]
+    fn pushes_filter_to_parquet_exec() {
+        let schema = schema();
+        let probe = coalesce_batches_exec(repartition_exec(filter_exec(
+            Arc::new(BinaryExpr::new(
+                col("c", &schema).unwrap(),
+                Operator::Gt,
+                lit(1i64),
+            )),
+            parquet_exec(&schema),
+        )));
+        let plan = optimize(hash_join(memory_exec(&schema), probe, JoinType::Inner));
+
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a@0, a@0), (b@1, b@1)]",
+            "  MemoryExec: partitions=1, partition_sizes=[0]",
+            "  CoalesceBatchesExec: target_batch_size=128",
+            "    RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1",
+            "      FilterExec: c@2 > 1",
+            "        ParquetExec: file_groups={1 group: [[x]]}, projection=[a, b, c], predicate=DynamicFilter [ a@0, b@1 ]",
+        ];
+        assert_eq!(get_plan_string(&plan), expected);
+        let join = plan.as_any().downcast_ref::<HashJoinExec>().unwrap();
+        assert!(join.dynamic_filter().is_some());
+    }
+
+    #[test]
+    fn remaps_projected_keys() {
+        let schema = schema();
+        let scan = parquet_exec(&schema);
+        let projection = Arc::new(
+            ProjectionExec::try_new(
+                vec![
+                    (col("c", &schema).unwrap(), "c".to_string()),
+                    (col("b", &schema).unwrap(), "b".to_string()),
+                    (col("a", &schema).unwrap(), "a".to_string()),
+                ],
+                scan,
+            )
+            .unwrap(),
+        );
+        let plan = optimize(hash_join(
+            memory_exec(&schema),
+            projection,
+            JoinType::RightSemi,
+        ));
+
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=RightSemi, on=[(a@0, a@2), (b@1, b@1)]",
+            "  MemoryExec: partitions=1, partition_sizes=[0]",
+            "  ProjectionExec: expr=[c@2 as c, b@1 as b, a@0 as a]",
+            "    ParquetExec: file_groups={1 group: [[x]]}, projection=[a, b, c], predicate=DynamicFilter [ a@0, b@1 ]",
+        ];
+        assert_eq!(get_plan_string(&plan), expected);
+    }
+
+    #[test]
+    fn unsupported_plans_are_unchanged() {
+        let schema = schema();
+        let plans = [
+            // probe side rows without a match are part of the output
+            hash_join(memory_exec(&schema), parquet_exec(&schema), JoinType::Right),
+            // a limit below the join must see all probe side rows
+            hash_join(
+                memory_exec(&schema),
+                global_limit_exec(parquet_exec(&schema)),
+                JoinType::Inner,
+            ),
+            // the probe side isn't read from a parquet file
+            hash_join(memory_exec(&schema), memory_exec(&schema), JoinType::Inner),
+        ];
+        for plan in plans {
+            let expected = get_plan_string(&plan);
+            assert_eq!(get_plan_string(&optimize(plan)), expected);
+        }
+    }
+}
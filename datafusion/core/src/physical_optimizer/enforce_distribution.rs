@@ -330,7 +330,8 @@ fn adjust_input_keys_ordering(
                     | JoinType::LeftSemi
                     | JoinType::LeftAnti
                     | JoinType::Full
-                    | JoinType::LeftMark => vec![],
+                    | JoinType::LeftMark
+                    | JoinType::AsOf => vec![],
                 };
             }
             PartitionMode::Auto => {
@@ -2033,7 +2034,7 @@ pub(crate) mod tests {
                     assert_optimized!(expected, top_join.clone(), true);
                     assert_optimized!(expected, top_join, false);
                 }
-                JoinType::RightSemi | JoinType::RightAnti => {}
+                JoinType::RightSemi | JoinType::RightAnti | JoinType::AsOf => {}
             }
 
             match join_type {
@@ -2098,7 +2099,10 @@ pub(crate) mod tests {
                     assert_optimized!(expected, top_join.clone(), true);
                     assert_optimized!(expected, top_join, false);
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {}
+                JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::LeftMark
+                | JoinType::AsOf => {}
             }
         }
 
@@ -0,0 +1,774 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`JoinReordering`] rule reorders trees of inner [`HashJoinExec`]s
+//! based on the estimated cardinality of their intermediate results
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_plan::joins::utils::{estimate_inner_join_statistics, JoinOn};
+use crate::physical_plan::joins::{HashJoinExec, PartitionMode};
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::{ExecutionPlan, Statistics};
+
+use arrow_schema::SchemaRef;
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_common::{internal_datafusion_err, internal_err, JoinType};
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_optimizer::PhysicalOptimizerRule;
+
+/// The largest join graph that is searched exhaustively, regardless of
+/// `join_reordering_dp_max_relations`, as the search keeps the best plan for
+/// every subset of the relations in memory
+const MAX_EXHAUSTIVE_RELATIONS: usize = 16;
+
+/// Optimizer rule that reorders trees of inner [`HashJoinExec`]s to minimize
+/// the estimated number of rows produced by their joins.
+///
+/// A join tree is made of inner equijoins without filters and of projections
+/// that only select columns. Its inputs (the "relations") and the equality
+/// conditions between their columns form a join graph, for which the rule
+/// looks for the cheapest order that doesn't need any cross joins. The
+/// cardinality of each join is estimated from the [`Statistics`] of its inputs
+/// the same way [`HashJoinExec`] estimates it, which means that the relations
+/// need row counts and min/max values for their join keys.
+///
+/// Join graphs with up to `join_reordering_dp_max_relations` relations are
+/// searched exhaustively with dynamic programming, larger ones are joined
+/// greedily, always picking the join with the smallest estimated output next.
+///
+/// The original order is kept unless the new one is estimated to be cheaper.
+/// Which input of each join becomes the build side is left to
+/// [`JoinSelection`], which runs afterwards.
+///
+/// [`JoinSelection`]: crate::physical_optimizer::join_selection::JoinSelection
+#[derive(Default, Debug)]
+pub struct JoinReordering {}
+
+impl JoinReordering {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicalOptimizerRule for JoinReordering {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_join_reordering {
+            return Ok(plan);
+        }
+        reorder_joins(plan, config.optimizer.join_reordering_dp_max_relations).data()
+    }
+
+    fn name(&self) -> &str {
+        "join_reordering"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Reorders every join tree in `plan`
+fn reorder_joins(
+    plan: Arc<dyn ExecutionPlan>,
+    dp_max_relations: usize,
+) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
+    plan.transform_down(|plan| {
+        let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+            return Ok(Transformed::no(plan));
+        };
+        if join_columns(join).is_none() {
+            return Ok(Transformed::no(plan));
+        }
+
+        let mut graph = JoinGraph::new(
+            *join.partition_mode(),
+            join.null_equals_null(),
+            dp_max_relations,
+        );
+        let flattened = graph.flatten(plan)?;
+        let schema = flattened.plan.data.schema();
+        Ok(
+            match graph.reorder(&flattened.tree, &flattened.columns, &schema)? {
+                Some(plan) => Transformed::new(plan, true, TreeNodeRecursion::Jump),
+                None => Transformed::new(
+                    flattened.plan.data,
+                    flattened.plan.transformed,
+                    TreeNodeRecursion::Jump,
+                ),
+            },
+        )
+    })
+}
+
+/// Returns the indices of the columns that `join` matches on, if it is an
+/// inner equijoin that can be reordered with others
+fn join_columns(join: &HashJoinExec) -> Option<Vec<(usize, usize)>> {
+    if join.join_type() != &JoinType::Inner
+        || join.filter().is_some()
+        || join.dynamic_filter().is_some()
+    {
+        return None;
+    }
+    join.on()
+        .iter()
+        .map(|(left, right)| {
+            Some((
+                left.as_any().downcast_ref::<Column>()?.index(),
+                right.as_any().downcast_ref::<Column>()?.index(),
+            ))
+        })
+        .collect()
+}
+
+/// The relations of a join tree and the equality conditions between them.
+///
+/// The columns of all relations are numbered consecutively, so that a column
+/// can be tracked through the joins and projections of the tree.
+struct JoinGraph {
+    partition_mode: PartitionMode,
+    null_equals_null: bool,
+    dp_max_relations: usize,
+    relations: Vec<Arc<dyn ExecutionPlan>>,
+    /// The columns of each relation
+    relation_columns: Vec<Range<usize>>,
+    /// The relation of each column
+    column_relations: Vec<usize>,
+    /// The name of each column in its relation
+    column_names: Vec<String>,
+    /// Pairs of columns that are joined on
+    edges: Vec<(usize, usize)>,
+}
+
+/// A join tree flattened into a [`JoinGraph`]
+struct Flattened {
+    /// The original plan, with the relations reordered
+    plan: Transformed<Arc<dyn ExecutionPlan>>,
+    /// The original join order
+    tree: Arc<JoinTree>,
+    /// The columns output by the plan
+    columns: Vec<usize>,
+}
+
+/// An order of joins between the relations of a [`JoinGraph`]
+enum JoinTree {
+    Relation(usize),
+    Join(Arc<JoinTree>, Arc<JoinTree>),
+}
+
+impl JoinTree {
+    /// Marks the relations joined by this tree in `relations`
+    fn collect_relations(&self, relations: &mut [bool]) {
+        match self {
+            JoinTree::Relation(relation) => relations[*relation] = true,
+            JoinTree::Join(left, right) => {
+                left.collect_relations(relations);
+                right.collect_relations(relations);
+            }
+        }
+    }
+}
+
+/// A join tree and its estimated cost
+#[derive(Clone)]
+struct JoinCandidate {
+    tree: Arc<JoinTree>,
+    /// The relations joined by the tree
+    relations: Vec<bool>,
+    /// The columns output by the tree, before any projection
+    columns: Vec<usize>,
+    statistics: Statistics,
+    num_rows: usize,
+    /// The total number of rows output by the joins of the tree
+    cost: usize,
+}
+
+impl JoinGraph {
+    fn new(
+        partition_mode: PartitionMode,
+        null_equals_null: bool,
+        dp_max_relations: usize,
+    ) -> Self {
+        Self {
+            partition_mode,
+            null_equals_null,
+            dp_max_relations,
+            relations: vec![],
+            relation_columns: vec![],
+            column_relations: vec![],
+            column_names: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Adds the relations of the join tree `plan` to the graph, reordering
+    /// the join trees within them
+    fn flatten(&mut self, plan: Arc<dyn ExecutionPlan>) -> Result<Flattened> {
+        if let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() {
+            if let Some(on) = join_columns(join).filter(|_| {
+                join.partition_mode() == &self.partition_mode
+                    && join.null_equals_null() == self.null_equals_null
+            }) {
+                let left = self.flatten(Arc::clone(join.left()))?;
+                let right = self.flatten(Arc::clone(join.right()))?;
+                self.edges.extend(
+                    on.into_iter()
+                        .map(|(l, r)| (left.columns[l], right.columns[r])),
+                );
+
+                let mut columns = left.columns;
+                columns.extend(right.columns);
+                if let Some(projection) = &join.projection {
+                    columns = projection.iter().map(|i| columns[*i]).collect();
+                }
+                let tree = Arc::new(JoinTree::Join(left.tree, right.tree));
+                let plan = if left.plan.transformed || right.plan.transformed {
+                    Transformed::yes(
+                        plan.with_new_children(vec![left.plan.data, right.plan.data])?,
+                    )
+                } else {
+                    Transformed::no(plan)
+                };
+                return Ok(Flattened {
+                    plan,
+                    tree,
+                    columns,
+                });
+            }
+        }
+
+        if let Some(projection) = plan.as_any().downcast_ref::<ProjectionExec>() {
+            if let Some(indices) = projection
+                .expr()
+                .iter()
+                .map(|(expr, _)| {
+                    expr.as_any().downcast_ref::<Column>().map(Column::index)
+                })
+                .collect::<Option<Vec<_>>>()
+            {
+                let input = self.flatten(Arc::clone(projection.input()))?;
+                let columns = indices.iter().map(|i| input.columns[*i]).collect();
+                let plan = if input.plan.transformed {
+                    Transformed::yes(plan.with_new_children(vec![input.plan.data])?)
+                } else {
+                    Transformed::no(plan)
+                };
+                return Ok(Flattened {
+                    plan,
+                    tree: input.tree,
+                    columns,
+                });
+            }
+        }
+
+        let plan = reorder_joins(plan, self.dp_max_relations)?;
+        let relation = self.relations.len();
+        let schema = plan.data.schema();
+        let columns =
+            self.column_names.len()..self.column_names.len() + schema.fields().len();
+        self.relations.push(Arc::clone(&plan.data));
+        self.relation_columns.push(columns.clone());
+        self.column_relations
+            .extend(columns.clone().map(|_| relation));
+        self.column_names
+            .extend(schema.fields().iter().map(|field| field.name().clone()));
+        Ok(Flattened {
+            plan,
+            tree: Arc::new(JoinTree::Relation(relation)),
+            columns: columns.collect(),
+        })
+    }
+
+    /// Returns a plan that joins the relations in a cheaper order than
+    /// `original`, outputting `columns` with the given `schema`, or `None`
+    /// if there is no such order
+    fn reorder(
+        &self,
+        original: &JoinTree,
+        columns: &[usize],
+        schema: &SchemaRef,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        if self.relations.len() < 3 {
+            return Ok(None);
+        }
+        let Some(relations) = self
+            .relations
+            .iter()
+            .enumerate()
+            .map(|(relation, plan)| self.relation_candidate(relation, plan))
+            .collect::<Result<Option<Vec<_>>>>()?
+        else {
+            return Ok(None);
+        };
+
+        let best =
+            if relations.len() <= self.dp_max_relations.min(MAX_EXHAUSTIVE_RELATIONS) {
+                self.join_exhaustively(relations.clone())
+            } else {
+                self.join_greedily(relations.clone())
+            };
+        let Some(best) = best else {
+            return Ok(None);
+        };
+        if let Some(original) = self.estimate(original, &relations) {
+            if original.cost <= best.cost {
+                return Ok(None);
+            }
+        }
+
+        let (plan, _) = self.build(&best.tree, columns, true)?;
+        if &plan.schema() == schema {
+            return Ok(Some(plan));
+        }
+        // Restore the names of columns that were aliased by projections
+        let exprs = schema
+            .fields()
+            .iter()
+            .zip(plan.schema().fields())
+            .enumerate()
+            .map(|(i, (field, input_field))| {
+                (
+                    Arc::new(Column::new(input_field.name(), i)) as _,
+                    field.name().clone(),
+                )
+            })
+            .collect();
+        let plan = Arc::new(ProjectionExec::try_new(exprs, plan)?);
+        Ok((&plan.schema() == schema).then_some(plan as _))
+    }
+
+    /// Returns the candidate for scanning `relation`, or `None` if its
+    /// number of rows is unknown
+    fn relation_candidate(
+        &self,
+        relation: usize,
+        plan: &Arc<dyn ExecutionPlan>,
+    ) -> Result<Option<JoinCandidate>> {
+        let statistics = plan.statistics()?;
+        let Some(num_rows) = statistics.num_rows.get_value().copied() else {
+            return Ok(None);
+        };
+        let mut relations = vec![false; self.relations.len()];
+        relations[relation] = true;
+        Ok(Some(JoinCandidate {
+            tree: Arc::new(JoinTree::Relation(relation)),
+            relations,
+            columns: self.relation_columns[relation].clone().collect(),
+            statistics,
+            num_rows,
+            cost: 0,
+        }))
+    }
+
+    /// Returns the pairs of columns of `left` and `right` that are joined on
+    fn join_keys(&self, left: &[bool], right: &[bool]) -> Vec<(usize, usize)> {
+        self.edges
+            .iter()
+            .filter_map(|&(a, b)| {
+                let (a_relation, b_relation) =
+                    (self.column_relations[a], self.column_relations[b]);
+                if left[a_relation] && right[b_relation] {
+                    Some((a, b))
+                } else if left[b_relation] && right[a_relation] {
+                    Some((b, a))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the join conditions for `keys` on inputs outputting the given
+    /// columns
+    fn join_on(
+        &self,
+        keys: &[(usize, usize)],
+        left_columns: &[usize],
+        right_columns: &[usize],
+    ) -> Result<JoinOn> {
+        let column = |id: usize, columns: &[usize]| -> Result<_> {
+            let Some(index) = columns.iter().position(|c| *c == id) else {
+                return internal_err!(
+                    "Join key {} is not in the join input",
+                    self.column_names[id]
+                );
+            };
+            Ok(Arc::new(Column::new(&self.column_names[id], index)) as _)
+        };
+        keys.iter()
+            .map(|(left, right)| {
+                Ok((column(*left, left_columns)?, column(*right, right_columns)?))
+            })
+            .collect()
+    }
+
+    /// Estimates the cost of joining `left` and `right`, returning `None` if
+    /// they aren't joined on any columns or there aren't enough statistics
+    fn join(&self, left: &JoinCandidate, right: &JoinCandidate) -> Option<JoinCandidate> {
+        let keys = self.join_keys(&left.relations, &right.relations);
+        if keys.is_empty() {
+            return None;
+        }
+        let on = self.join_on(&keys, &left.columns, &right.columns).ok()?;
+        let statistics = estimate_inner_join_statistics(
+            left.statistics.clone(),
+            right.statistics.clone(),
+            &on,
+        )?;
+        let num_rows = *statistics.num_rows.get_value()?;
+        Some(JoinCandidate {
+            tree: Arc::new(JoinTree::Join(
+                Arc::clone(&left.tree),
+                Arc::clone(&right.tree),
+            )),
+            relations: left
+                .relations
+                .iter()
+                .zip(&right.relations)
+                .map(|(l, r)| *l || *r)
+                .collect(),
+            columns: left.columns.iter().chain(&right.columns).copied().collect(),
+            statistics,
+            num_rows,
+            cost: left
+                .cost
+                .saturating_add(right.cost)
+                .saturating_add(num_rows),
+        })
+    }
+
+    /// Estimates the cost of the join order `tree`
+    fn estimate(
+        &self,
+        tree: &JoinTree,
+        relations: &[JoinCandidate],
+    ) -> Option<JoinCandidate> {
+        match tree {
+            JoinTree::Relation(relation) => Some(relations[*relation].clone()),
+            JoinTree::Join(left, right) => self.join(
+                &self.estimate(left, relations)?,
+                &self.estimate(right, relations)?,
+            ),
+        }
+    }
+
+    /// Finds the cheapest join order by finding the cheapest join order for
+    /// every connected subset of the relations, from the smallest up
+    fn join_exhaustively(&self, relations: Vec<JoinCandidate>) -> Option<JoinCandidate> {
+        let mut best: Vec<Option<JoinCandidate>> = vec![None; 1 << relations.len()];
+        for (relation, candidate) in relations.into_iter().enumerate() {
+            best[1 << relation] = Some(candidate);
+        }
+        for set in 1..best.len() {
+            if set.count_ones() < 2 {
+                continue;
+            }
+            // Only consider splits where the left side contains the lowest
+            // relation of the set, as swapping the sides has the same cost
+            let lowest = set & set.wrapping_neg();
+            let mut left = (set - 1) & set;
+            while left != 0 {
+                if left & lowest != 0 {
+                    if let (Some(l), Some(r)) = (&best[left], &best[set ^ left]) {
+                        if let Some(candidate) = self.join(l, r) {
+                            if best[set]
+                                .as_ref()
+                                .map_or(true, |b| candidate.cost < b.cost)
+                            {
+                                best[set] = Some(candidate);
+                            }
+                        }
+                    }
+                }
+                left = (left - 1) & set;
+            }
+        }
+        best.pop().flatten()
+    }
+
+    /// Finds a join order by repeatedly performing the join with the smallest
+    /// estimated output
+    fn join_greedily(&self, mut relations: Vec<JoinCandidate>) -> Option<JoinCandidate> {
+        while relations.len() > 1 {
+            let mut next: Option<(usize, usize, JoinCandidate)> = None;
+            for i in 0..relations.len() {
+                for j in i + 1..relations.len() {
+                    let Some(candidate) = self.join(&relations[i], &relations[j]) else {
+                        continue;
+                    };
+                    if next
+                        .as_ref()
+                        .map_or(true, |(_, _, n)| candidate.num_rows < n.num_rows)
+                    {
+                        next = Some((i, j, candidate));
+                    }
+                }
+            }
+            let (i, j, candidate) = next?;
+            relations.remove(j);
+            relations.remove(i);
+            relations.push(candidate);
+        }
+        relations.pop()
+    }
+
+    /// Creates the plan for `tree`, returning it with the columns it outputs.
+    ///
+    /// The root of the tree outputs exactly the columns in `required`, while
+    /// other joins only drop the columns that are neither required nor joined
+    /// on further up the tree.
+    fn build(
+        &self,
+        tree: &JoinTree,
+        required: &[usize],
+        is_root: bool,
+    ) -> Result<(Arc<dyn ExecutionPlan>, Vec<usize>)> {
+        let (left, right) = match tree {
+            JoinTree::Relation(relation) => {
+                return Ok((
+                    Arc::clone(&self.relations[*relation]),
+                    self.relation_columns[*relation].clone().collect(),
+                ));
+            }
+            JoinTree::Join(left, right) => (left, right),
+        };
+
+        let mut left_relations = vec![false; self.relations.len()];
+        left.collect_relations(&mut left_relations);
+        let mut right_relations = vec![false; self.relations.len()];
+        right.collect_relations(&mut right_relations);
+        let keys = self.join_keys(&left_relations, &right_relations);
+
+        let mut needed = required.to_vec();
+        needed.extend(keys.iter().flat_map(|(l, r)| [*l, *r]));
+        let (left, left_columns) = self.build(left, &needed, false)?;
+        let (right, right_columns) = self.build(right, &needed, false)?;
+        let on = self.join_on(&keys, &left_columns, &right_columns)?;
+
+        let columns: Vec<usize> = left_columns.into_iter().chain(right_columns).collect();
+        let projection: Vec<usize> = if is_root {
+            required
+                .iter()
+                .map(|id| {
+                    columns.iter().position(|c| c == id).ok_or_else(|| {
+                        internal_datafusion_err!(
+                            "Column {} is not in the join output",
+                            self.column_names[*id]
+                        )
+                    })
+                })
+                .collect::<Result<_>>()?
+        } else {
+            (0..columns.len())
+                .filter(|i| required.contains(&columns[*i]))
+                .collect()
+        };
+        let (projection, columns) = if projection.iter().copied().eq(0..columns.len()) {
+            (None, columns)
+        } else {
+            let columns = projection.iter().map(|i| columns[*i]).collect();
+            (Some(projection), columns)
+        };
+
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &JoinType::Inner,
+            projection,
+            self.partition_mode,
+            self.null_equals_null,
+        )?;
+        Ok((Arc::new(join), columns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::{get_plan_string, ColumnStatistics};
+    use crate::test::StatisticsExec;
+
+    use arrow_schema::{DataType, Field, Schema};
+    use datafusion_common::stats::Precision;
+    use datafusion_common::ScalarValue;
+
+    /// Returns a relation with `num_rows` rows and a column for each of the
+    /// given names, with values in `0..max` and `distinct` distinct values
+    fn relation(
+        num_rows: usize,
+        columns: &[(&str, i64, usize)],
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Schema::new(
+            columns
+                .iter()
+                .map(|(name, _, _)| Field::new(*name, DataType::Int64, false))
+                .collect::<Vec<_>>(),
+        );
+        let statistics = Statistics {
+            num_rows: Precision::Inexact(num_rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: columns
+                .iter()
+                .map(|(_, max, distinct)| ColumnStatistics {
+                    min_value: Precision::Inexact(ScalarValue::Int64(Some(0))),
+                    max_value: Precision::Inexact(ScalarValue::Int64(Some(*max))),
+                    distinct_count: Precision::Inexact(*distinct),
+                    ..Default::default()
+                })
+                .collect(),
+        };
+        Arc::new(StatisticsExec::new(statistics, schema))
+    }
+
+    /// Returns three relations where `a JOIN b ON a1 = b1` produces many more
+    /// rows than `b JOIN c ON b2 = c1`
+    fn relations() -> (
+        Arc<dyn ExecutionPlan>,
+        Arc<dyn ExecutionPlan>,
+        Arc<dyn ExecutionPlan>,
+    ) {
+        let a = relation(100_000, &[("a1", 1000, 1000), ("a2", 100_000, 100_000)]);
+        let b = relation(100_000, &[("b1", 1000, 1000), ("b2", 100_000, 100_000)]);
+        let c = relation(10, &[("c1", 100_000, 10)]);
+        (a, b, c)
+    }
+
+    fn hash_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: &[(&str, &str)],
+    ) -> Arc<dyn ExecutionPlan> {
+        let on = on
+            .iter()
+            .map(|(l, r)| {
+                (
+                    Arc::new(Column::new_with_schema(l, &left.schema()).unwrap()) as _,
+                    Arc::new(Column::new_with_schema(r, &right.schema()).unwrap()) as _,
+                )
+            })
+            .collect();
+        Arc::new(
+            HashJoinExec::try_new(
+                left,
+                right,
+                on,
+                None,
+                &JoinType::Inner,
+                None,
+                PartitionMode::CollectLeft,
+                false,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn optimize(
+        plan: Arc<dyn ExecutionPlan>,
+        dp_max_relations: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_join_reordering = true;
+        config.optimizer.join_reordering_dp_max_relations = dp_max_relations;
+        let optimized = JoinReordering::new()
+            .optimize(plan.clone(), &config)
+            .unwrap();
+        assert_eq!(optimized.schema(), plan.schema());
+        optimized
+    }
+
+    #[test]
+    fn reorder_joins() {
+        let (a, b, c) = relations();
+        let plan = hash_join(hash_join(a, b, &[("a1", "b1")]), c, &[("b2", "c1")]);
+
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a1@0, b1@0)]",
+            "  StatisticsExec: col_count=2, row_count=Inexact(100000)",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(b2@1, c1@0)]",
+            "    StatisticsExec: col_count=2, row_count=Inexact(100000)",
+            "    StatisticsExec: col_count=1, row_count=Inexact(10)",
+        ];
+        assert_eq!(get_plan_string(&optimize(Arc::clone(&plan), 10)), expected);
+        // Join greedily
+        assert_eq!(get_plan_string(&optimize(plan, 2)), expected);
+    }
+
+    #[test]
+    fn reorder_joins_through_projection() {
+        let (a, b, c) = relations();
+        let join = hash_join(a, b, &[("a1", "b1")]);
+        let projection = Arc::new(
+            ProjectionExec::try_new(
+                vec![
+                    (Arc::new(Column::new("b2", 3)) as _, "b2".to_string()),
+                    (Arc::new(Column::new("a1", 0)) as _, "x".to_string()),
+                ],
+                join,
+            )
+            .unwrap(),
+        );
+        let plan = hash_join(projection, c, &[("b2", "c1")]);
+
+        let expected = [
+            "ProjectionExec: expr=[b2@0 as b2, a1@1 as x, c1@2 as c1]",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a1@0, b1@0)], projection=[b2@3, a1@0, c1@4]",
+            "    StatisticsExec: col_count=2, row_count=Inexact(100000)",
+            "    HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(b2@1, c1@0)]",
+            "      StatisticsExec: col_count=2, row_count=Inexact(100000)",
+            "      StatisticsExec: col_count=1, row_count=Inexact(10)",
+        ];
+        assert_eq!(get_plan_string(&optimize(plan, 10)), expected);
+    }
+
+    #[test]
+    fn keep_cheaper_order() {
+        let (a, b, c) = relations();
+        let plan = hash_join(a, hash_join(b, c, &[("b2", "c1")]), &[("a1", "b1")]);
+
+        let optimized = optimize(Arc::clone(&plan), 10);
+        assert!(Arc::ptr_eq(&optimized, &plan));
+    }
+
+    #[test]
+    fn keep_order_without_statistics() {
+        let (a, b, _) = relations();
+        let c = Arc::new(StatisticsExec::new(
+            Statistics::new_unknown(&Schema::new(vec![Field::new(
+                "c1",
+                DataType::Int64,
+                false,
+            )])),
+            Schema::new(vec![Field::new("c1", DataType::Int64, false)]),
+        ));
+        let plan = hash_join(hash_join(a, b, &[("a1", "b1")]), c, &[("b2", "c1")]);
+
+        let optimized = optimize(Arc::clone(&plan), 10);
+        assert!(Arc::ptr_eq(&optimized, &plan));
+    }
+}
@@ -136,6 +136,9 @@ fn swap_join_type(join_type: JoinType) -> JoinType {
         JoinType::LeftMark => {
             unreachable!("LeftMark join type does not support swapping")
         }
+        JoinType::AsOf => {
+            unreachable!("AsOf join type does not support swapping")
+        }
     }
 }
 
@@ -22,8 +22,10 @@
 //!
 //! [`ExecutionPlan`]: crate::physical_plan::ExecutionPlan
 pub mod coalesce_batches;
+pub mod dynamic_join_filter;
 pub mod enforce_distribution;
 pub mod enforce_sorting;
+pub mod join_reordering;
 pub mod join_selection;
 pub mod optimizer;
 pub mod projection_pushdown;
@@ -44,13 +44,16 @@ use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::utils as join_utils;
 use crate::physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
+    AsofJoinExec, AsofMatchCondition, CrossJoinExec, DependentJoinExec, HashJoinExec,
+    NestedLoopJoinExec, OuterParameter, OuterParameters, PartitionMode,
+    SortMergeJoinExec,
 };
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sample::SampleExec;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::union::UnionExec;
 use crate::physical_plan::unnest::UnnestExec;
@@ -62,26 +65,28 @@ use crate::physical_plan::{
 };
 
 use arrow::compute::SortOptions;
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
 use arrow_array::builder::StringBuilder;
 use arrow_array::RecordBatch;
 use datafusion_common::display::ToStringifiedPlan;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{
     exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, DFSchema,
     ScalarValue,
 };
 use datafusion_expr::dml::{CopyTo, InsertOp};
 use datafusion_expr::expr::{
-    physical_name, AggregateFunction, Alias, GroupingSet, WindowFunction,
+    physical_name, AggregateFunction, Alias, BinaryExpr, GroupingSet, WindowFunction,
 };
-use datafusion_expr::expr_rewriter::unnormalize_cols;
+use datafusion_expr::expr_rewriter::{unnormalize_col, unnormalize_cols, NamePreserver};
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
 use datafusion_expr::{
-    DescribeTable, DmlStatement, Extension, FetchType, Filter, JoinType, RecursiveQuery,
-    SkipType, SortExpr, StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
+    lit, utils::split_conjunction, DescribeTable, DmlStatement, Extension, FetchType,
+    Filter, JoinType, RecursiveQuery, Sample, SampleMethod, ScalarUDF, SkipType,
+    SortExpr, StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
 };
 use datafusion_physical_expr::aggregate::{AggregateExprBuilder, AggregateFunctionExpr};
-use datafusion_physical_expr::expressions::Literal;
+use datafusion_physical_expr::expressions::{Column, Literal};
 use datafusion_physical_expr::LexOrdering;
 use datafusion_physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion_physical_plan::unnest::ListUnnest;
@@ -292,16 +297,16 @@ impl DefaultPhysicalPlanner {
             // Because of how we extend the visit stack here, we visit the children
             // in reverse order of how they appear, so later we need to reverse
             // the order of children when building the nodes.
-            dfs_visit_stack
-                .extend(node.inputs().iter().map(|&n| (Some(current_index), n)));
-            let state = match node.inputs().len() {
+            let inputs = physical_inputs(node);
+            dfs_visit_stack.extend(inputs.iter().map(|&n| (Some(current_index), n)));
+            let state = match inputs.len() {
                 0 => {
                     flat_tree_leaf_indices.push(current_index);
                     NodeState::ZeroOrOneChild
                 }
                 1 => NodeState::ZeroOrOneChild,
                 _ => {
-                    let ready_children = Vec::with_capacity(node.inputs().len());
+                    let ready_children = Vec::with_capacity(inputs.len());
                     let ready_children = Mutex::new(ready_children);
                     NodeState::TwoOrMoreChildren(ready_children)
                 }
@@ -397,7 +402,7 @@ impl DefaultPhysicalPlanner {
                             index: current_index,
                             plan,
                         });
-                        if guard.len() < node.node.inputs().len() {
+                        if guard.len() < physical_inputs(node.node).len() {
                             // This node is not ready yet, still pending more children.
                             // This task is finished forever.
                             return Ok(None);
@@ -508,7 +513,7 @@ impl DefaultPhysicalPlanner {
                 // the column name rather than column name + explicit data type.
                 let table_partition_cols = partition_by
                     .iter()
-                    .map(|s| (s.to_string(), arrow_schema::DataType::Null))
+                    .map(|s| (s.to_string(), DataType::Null))
                     .collect::<Vec<_>>();
 
                 let keep_partition_by_columns = match source_option_tuples
@@ -555,6 +560,56 @@ impl DefaultPhysicalPlanner {
                     return exec_err!("Table '{table_name}' does not exist");
                 }
             }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Delete,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let filters = extract_dml_filters(input)?;
+                    provider.delete_from(session_state, filters).await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Update,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let assignments = extract_update_assignments(input);
+                    let filters = extract_dml_filters(input)?;
+                    provider.update(session_state, assignments, filters).await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                table_schema,
+                op: WriteOp::Merge(merge),
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let source = children.one()?;
+                    let schema = Arc::new(table_schema.join(input.schema())?);
+                    provider
+                        .merge_into(session_state, source, schema, *merge.clone())
+                        .await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
             LogicalPlan::Window(Window {
                 input, window_expr, ..
             }) => {
@@ -795,7 +850,7 @@ impl DefaultPhysicalPlanner {
                     SortExec::new(sort_expr, physical_input).with_fetch(*fetch);
                 Arc::new(new_sort)
             }
-            LogicalPlan::Subquery(_) => todo!(),
+            LogicalPlan::Subquery(_) => children.one()?,
             LogicalPlan::SubqueryAlias(_) => children.one()?,
             LogicalPlan::Limit(limit) => {
                 let input = children.one()?;
@@ -827,6 +882,25 @@ impl DefaultPhysicalPlanner {
 
                 Arc::new(GlobalLimitExec::new(input, skip, fetch))
             }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                let input = children.one()?;
+                // SYSTEM sampling may be pushed into the scan, skipping whole blocks
+                let sampled_input = match method {
+                    SampleMethod::System => input.with_block_sample(*fraction, *seed),
+                    SampleMethod::Bernoulli => None,
+                };
+                match sampled_input {
+                    Some(sampled_input) => sampled_input,
+                    None => {
+                        Arc::new(SampleExec::try_new(input, *method, *fraction, *seed)?)
+                    }
+                }
+            }
             LogicalPlan::Unnest(Unnest {
                 list_type_columns,
                 struct_type_columns,
@@ -852,6 +926,60 @@ impl DefaultPhysicalPlanner {
                 ))
             }
 
+            // A lateral join whose right side refers to the left side, which
+            // is evaluated for every row of the left side
+            LogicalPlan::Join(join) if is_dependent_join(join) => {
+                let physical_left = children.one()?;
+                let left_schema = join.left.schema();
+                let outer_columns = join
+                    .right
+                    .all_out_ref_exprs()
+                    .into_iter()
+                    .map(|expr| match expr {
+                        Expr::OuterReferenceColumn(data_type, column) => {
+                            Ok((data_type, column))
+                        }
+                        expr => internal_err!(
+                            "Expected an outer reference column, got {expr}"
+                        ),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let physical_outer_columns = outer_columns
+                    .iter()
+                    .map(|(_, column)| {
+                        let index = left_schema.index_of_column(column)?;
+                        Ok(Column::new(&column.name, index))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let filter = join
+                    .on
+                    .iter()
+                    .map(|(l, r)| l.clone().eq(r.clone()))
+                    .chain(join.filter.clone())
+                    .reduce(Expr::and)
+                    .map(|expr| {
+                        self.create_physical_expr(&expr, &join.schema, session_state)
+                    })
+                    .transpose()?;
+
+                // The right side is planned once its references to the left
+                // side are replaced with parameters
+                let parameters = Arc::new(OuterParameters::new());
+                let right =
+                    replace_outer_references(&join.right, &outer_columns, &parameters)?;
+                let physical_right =
+                    self.create_physical_plan(&right, session_state).await?;
+                Arc::new(DependentJoinExec::try_new(
+                    physical_left,
+                    physical_right,
+                    physical_outer_columns,
+                    parameters,
+                    filter,
+                    join.join_type,
+                )?)
+            }
+
             // 2 Children
             LogicalPlan::Join(Join {
                 left,
@@ -1064,7 +1192,21 @@ impl DefaultPhysicalPlanner {
                 let prefer_hash_join =
                     session_state.config_options().optimizer.prefer_hash_join;
 
-                let join: Arc<dyn ExecutionPlan> = if join_on.is_empty() {
+                let join: Arc<dyn ExecutionPlan> = if *join_type == JoinType::AsOf {
+                    let match_condition = create_asof_match_condition(
+                        filter.as_ref(),
+                        left_df_schema,
+                        right_df_schema,
+                        execution_props,
+                    )?;
+                    Arc::new(AsofJoinExec::try_new(
+                        physical_left,
+                        physical_right,
+                        join_on,
+                        match_condition,
+                        null_equals_null,
+                    )?)
+                } else if join_on.is_empty() {
                     if join_filter.is_none() && matches!(join_type, JoinType::Inner) {
                         // cross join if there is no join conditions and no join filter set
                         Arc::new(CrossJoinExec::new(physical_left, physical_right))
@@ -1461,6 +1603,45 @@ fn get_physical_expr_pair(
     Ok((physical_expr, physical_name))
 }
 
+/// Creates the match condition of an ASOF join from its filter, which must be a
+/// single comparison between an expression of the left input and an expression
+/// of the right input.
+fn create_asof_match_condition(
+    filter: Option<&Expr>,
+    left_schema: &DFSchema,
+    right_schema: &DFSchema,
+    execution_props: &ExecutionProps,
+) -> Result<AsofMatchCondition> {
+    let Some(Expr::BinaryExpr(BinaryExpr { left, op, right })) = filter else {
+        return plan_err!(
+            "ASOF join requires a match condition comparing the left and right inputs"
+        );
+    };
+    let references = |expr: &Expr, schema: &DFSchema| {
+        expr.column_refs().iter().all(|c| schema.has_column(c))
+    };
+    let (left, op, right) =
+        if references(left, left_schema) && references(right, right_schema) {
+            (left, *op, right)
+        } else if references(right, left_schema) && references(left, right_schema) {
+            match op.swap() {
+                Some(op) => (right, op, left),
+                None => return plan_err!("Unsupported ASOF join match condition: {op}"),
+            }
+        } else {
+            return plan_err!(
+                "ASOF join match condition must compare an expression of the left input \
+                 with an expression of the right input, got {}",
+                filter.unwrap()
+            );
+        };
+    AsofMatchCondition::try_new(
+        create_physical_expr(left, left_schema, execution_props)?,
+        op,
+        create_physical_expr(right, right_schema, execution_props)?,
+    )
+}
+
 /// Check if window bounds are valid after schema information is available, and
 /// window_frame bounds are casted to the corresponding column type.
 /// queries like:
@@ -1968,6 +2149,62 @@ impl DefaultPhysicalPlanner {
     }
 }
 
+/// Whether `join` is a lateral join whose right side refers to columns of
+/// the left side, which is planned with a [`DependentJoinExec`]
+fn is_dependent_join(join: &Join) -> bool {
+    !join.right.all_out_ref_exprs().is_empty()
+}
+
+/// The inputs of `plan` that are planned as children of its physical plan.
+/// The right side of a dependent join is planned along with the join instead,
+/// as its references to the left side must be replaced first.
+fn physical_inputs(plan: &LogicalPlan) -> Vec<&LogicalPlan> {
+    match plan {
+        LogicalPlan::Join(join) if is_dependent_join(join) => vec![join.left.as_ref()],
+        _ => plan.inputs(),
+    }
+}
+
+/// Replaces the references to the columns of the left side of a dependent
+/// join in its right side `subquery` with [`OuterParameter`] placeholders, and
+/// removes the [`LogicalPlan::Subquery`] nodes that are no longer correlated
+fn replace_outer_references(
+    subquery: &LogicalPlan,
+    outer_columns: &[(DataType, datafusion_common::Column)],
+    parameters: &Arc<OuterParameters>,
+) -> Result<LogicalPlan> {
+    subquery
+        .clone()
+        .transform_up(|plan| {
+            if let LogicalPlan::Subquery(subquery) = plan {
+                return Ok(Transformed::yes(Arc::unwrap_or_clone(subquery.subquery)));
+            }
+            let name_preserver = NamePreserver::new(&plan);
+            plan.map_expressions(|expr| {
+                let original_name = name_preserver.save(&expr);
+                let transformed_expr = expr.transform_up(|expr| {
+                    if let Expr::OuterReferenceColumn(data_type, column) = &expr {
+                        if let Some(index) =
+                            outer_columns.iter().position(|(_, c)| c == column)
+                        {
+                            let parameter = OuterParameter::new(
+                                Arc::clone(parameters),
+                                index,
+                                data_type.clone(),
+                            );
+                            let parameter = ScalarUDF::new_from_impl(parameter)
+                                .call(vec![lit(column.flat_name())]);
+                            return Ok(Transformed::yes(parameter));
+                        }
+                    }
+                    Ok(Transformed::no(expr))
+                })?;
+                Ok(transformed_expr.update_data(|expr| original_name.restore(expr)))
+            })
+        })
+        .data()
+}
+
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     match value {
         (Ok(e), Ok(e1)) => Ok((e, e1)),
@@ -1977,6 +2214,75 @@ fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     }
 }
 
+/// Returns the filters on the target table of an `UPDATE` or `DELETE`
+/// statement from its input plan, as passed to [`TableProvider::delete_from`]
+/// and [`TableProvider::update`]
+///
+/// [`TableProvider::delete_from`]: crate::datasource::TableProvider::delete_from
+/// [`TableProvider::update`]: crate::datasource::TableProvider::update
+fn extract_dml_filters(input: &LogicalPlan) -> Result<Vec<Expr>> {
+    let mut filters = vec![];
+    let mut plan = input;
+    loop {
+        match plan {
+            LogicalPlan::Filter(filter) => {
+                filters.extend(split_conjunction(&filter.predicate).into_iter().cloned());
+                plan = &filter.input;
+            }
+            // The projection computing the new values of an `UPDATE` is on
+            // top of the filters, other projections must only select columns
+            LogicalPlan::Projection(projection)
+                if std::ptr::eq(plan, input)
+                    || projection
+                        .expr
+                        .iter()
+                        .all(|expr| matches!(expr, Expr::Column(_))) =>
+            {
+                plan = &projection.input;
+            }
+            LogicalPlan::SubqueryAlias(alias) => plan = &alias.input,
+            LogicalPlan::TableScan(scan) => {
+                filters.extend(scan.filters.iter().cloned());
+                break;
+            }
+            LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                ..
+            }) => {
+                filters.push(lit(false));
+                break;
+            }
+            _ => {
+                return not_impl_err!(
+                    "Unsupported input plan for UPDATE or DELETE: {}",
+                    plan.display()
+                )
+            }
+        }
+    }
+    Ok(unnormalize_cols(filters))
+}
+
+/// Returns the columns changed by an `UPDATE` statement from its input plan,
+/// as passed to [`TableProvider::update`]
+///
+/// [`TableProvider::update`]: crate::datasource::TableProvider::update
+fn extract_update_assignments(input: &LogicalPlan) -> Vec<(String, Expr)> {
+    // The projection is removed if it doesn't change any columns
+    let LogicalPlan::Projection(projection) = input else {
+        return vec![];
+    };
+    projection
+        .expr
+        .iter()
+        .zip(projection.schema.fields())
+        .filter_map(|(expr, field)| match expr.clone().unalias() {
+            Expr::Column(column) if column.name == *field.name() => None,
+            expr => Some((field.name().clone(), unnormalize_col(expr))),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::any::Any;
@@ -201,12 +201,16 @@ fn fully_defined() -> (Statistics, Schema) {
                     max_value: Precision::Exact(ScalarValue::Int32(Some(1023))),
                     min_value: Precision::Exact(ScalarValue::Int32(Some(-24))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(13),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(5486))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-6783))),
                     null_count: Precision::Exact(5),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         },
@@ -33,7 +33,7 @@ use arrow_array::{
     Array, BooleanArray, DictionaryArray, Float32Array, Float64Array, Int8Array,
     UnionArray,
 };
-use arrow_buffer::ScalarBuffer;
+use arrow_buffer::{OffsetBuffer, ScalarBuffer};
 use arrow_schema::{ArrowError, UnionFields, UnionMode};
 use datafusion_functions_aggregate::count::count_udaf;
 use object_store::local::LocalFileSystem;
@@ -51,7 +51,10 @@ use datafusion::prelude::JoinType;
 use datafusion::prelude::{CsvReadOptions, ParquetReadOptions};
 use datafusion::test_util::{parquet_test_data, populate_csv_partitions};
 use datafusion::{assert_batches_eq, assert_batches_sorted_eq};
-use datafusion_common::{assert_contains, DataFusionError, ScalarValue, UnnestOptions};
+use datafusion_common::{
+    assert_contains, Column, DataFusionError, RecursionUnnestOption, ScalarValue,
+    UnnestOptions,
+};
 use datafusion_execution::config::SessionConfig;
 use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_expr::expr::{GroupingSet, Sort};
@@ -1502,6 +1505,88 @@ async fn unnest_multiple_columns() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn unnest_multiple_columns_with_ordinality() -> Result<()> {
+    let df = table_with_mixed_lists().await?;
+    let results = df
+        .unnest_columns_with_options(
+            &["list", "large_list"],
+            UnnestOptions::new().with_ordinality(true),
+        )?
+        .collect()
+        .await?;
+    // list:        [1,2,3], null, [null], null,
+    // large_list:  [null, 1.1], [2.2, 3.3, 4.4], null, [],
+    // string:      a, b, c, d
+    let expected = [
+        "+------+-----------------+------------+-----------------------+------------+--------+",
+        "| list | list_ordinality | large_list | large_list_ordinality | fixed_list | string |",
+        "+------+-----------------+------------+-----------------------+------------+--------+",
+        "| 1    | 1               |            | 1                     |            | a      |",
+        "| 2    | 2               | 1.1        | 2                     |            | a      |",
+        "| 3    | 3               |            |                       |            | a      |",
+        "|      |                 | 2.2        | 1                     | [1, 2]     | b      |",
+        "|      |                 | 3.3        | 2                     | [1, 2]     | b      |",
+        "|      |                 | 4.4        | 3                     | [1, 2]     | b      |",
+        "|      | 1               |            |                       | [3, 4]     | c      |",
+        "|      |                 |            |                       |            | d      |",
+        "+------+-----------------+------------+-----------------------+------------+--------+",
+    ];
+    assert_batches_eq!(expected, &results);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn unnest_recursive_with_ordinality() -> Result<()> {
+    // c1: [[1, 2], [3]], [[4]]
+    let inner = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+        Some(vec![Some(1), Some(2)]),
+        Some(vec![Some(3)]),
+        Some(vec![Some(4)]),
+    ]);
+    let list_array = ListArray::new(
+        Arc::new(Field::new_list_field(inner.data_type().clone(), true)),
+        OffsetBuffer::from_lengths([2, 1]),
+        Arc::new(inner),
+        None,
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "c1",
+        list_array.data_type().clone(),
+        true,
+    )]));
+    let batch = RecordBatch::try_new(schema, vec![Arc::new(list_array)])?;
+    let ctx = SessionContext::new();
+    let options = UnnestOptions::new().with_ordinality(true).with_recursions(
+        RecursionUnnestOption {
+            input_column: Column::from_name("c1"),
+            output_column: Column::from_name("c1_depth_2"),
+            depth: 2,
+        },
+    );
+    let results = ctx
+        .read_batches(vec![batch])?
+        .unnest_columns_with_options(&["c1"], options)?
+        .collect()
+        .await?;
+
+    // The positions are taken from the innermost lists
+    let expected = [
+        "+------------+-----------------------+",
+        "| c1_depth_2 | c1_depth_2_ordinality |",
+        "+------------+-----------------------+",
+        "| 1          | 1                     |",
+        "| 2          | 2                     |",
+        "| 3          | 1                     |",
+        "| 4          | 1                     |",
+        "+------------+-----------------------+",
+    ];
+    assert_batches_eq!(expected, &results);
+
+    Ok(())
+}
+
 /// Test unnesting a non-nullable list.
 #[tokio::test]
 async fn unnest_non_nullable_list() -> Result<()> {
@@ -46,6 +46,7 @@ use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::physical_optimizer::join_selection::JoinSelection;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{ExecutionPlan, SendableRecordBatchStream};
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{assert_contains, Result};
 
 use datafusion::prelude::{SessionConfig, SessionContext};
@@ -203,6 +204,87 @@ async fn sort_merge_join_spill() {
         .await
 }
 
+#[tokio::test]
+async fn sort_merge_join_spill_compressed() {
+    let config = SessionConfig::new()
+        .with_target_partitions(2)
+        .set_bool("datafusion.optimizer.prefer_hash_join", false)
+        .with_spill_compression(SpillCompression::Zstd);
+
+    TestCase::new()
+        .with_query(
+            "select t1.* from t t1 JOIN t t2 ON t1.pod = t2.pod AND t1.time = t2.time",
+        )
+        .with_memory_limit(1_000)
+        .with_config(config)
+        .with_disk_manager_config(DiskManagerConfig::NewOs)
+        .with_scenario(Scenario::AccessLogStreaming)
+        .run()
+        .await
+}
+
+#[tokio::test]
+async fn sort_merge_join_spill_disk_quota() {
+    let config = SessionConfig::new()
+        .with_target_partitions(2)
+        .set_bool("datafusion.optimizer.prefer_hash_join", false)
+        .with_max_spill_disk_size(1024);
+
+    TestCase::new()
+        .with_query(
+            "select t1.* from t t1 JOIN t t2 ON t1.pod = t2.pod AND t1.time = t2.time",
+        )
+        .with_expected_errors(vec![
+            "Resources exhausted: Spill files use",
+            "exceeding the quota of 1024.0 B",
+        ])
+        .with_memory_limit(1_000)
+        .with_config(config)
+        .with_disk_manager_config(DiskManagerConfig::NewOs)
+        .with_scenario(Scenario::AccessLogStreaming)
+        .run()
+        .await
+}
+
+#[tokio::test]
+async fn oom_query_memory_limit() {
+    TestCase::new()
+        .with_query("select median(request_bytes) from t")
+        .with_expected_errors(vec![
+            "Resources exhausted: Failed to allocate additional",
+            "for AggregateStream",
+            "bytes remain available for the query",
+        ])
+        // the pool shared by all queries is large enough, but not the query limit
+        .with_memory_limit(10_000_000)
+        .with_config(SessionConfig::new().with_query_memory_limit(2_000))
+        .run()
+        .await
+}
+
+#[tokio::test]
+async fn query_memory_admission() {
+    let runtime = RuntimeEnvBuilder::new()
+        .with_memory_limit(100_000, 1.0)
+        .with_memory_admission(100_000)
+        .build_arc()
+        .unwrap();
+    let admission_queue = Arc::clone(runtime.memory_admission_queue.as_ref().unwrap());
+    // every query may use the whole pool, so they run one after the other
+    let config = SessionConfig::new().with_query_memory_limit(100_000);
+    let ctx = SessionContext::new_with_config_rt(config, runtime);
+    ctx.register_batch("t", access_log_batches()[0].clone())
+        .unwrap();
+
+    let query = "select count(*) from t group by host";
+    let (r1, r2) = tokio::join!(async { ctx.sql(query).await?.collect().await }, async {
+        ctx.sql(query).await?.collect().await
+    },);
+    assert_eq!(r1.unwrap(), r2.unwrap());
+    assert_eq!(admission_queue.admitted(), 0);
+    assert_eq!(admission_queue.num_waiting(), 0);
+}
+
 #[tokio::test]
 async fn symmetric_hash_join() {
     TestCase::new()
@@ -286,6 +286,35 @@ async fn prune_disabled() {
     );
 }
 
+#[tokio::test]
+async fn prune_with_dynamic_join_filter() {
+    // The build side of the join only has keys 6 and 7, which are all in the
+    // last of the four row groups
+    let query = "SELECT t.i64 FROM (VALUES (6), (7)) AS d(k) JOIN t ON t.i64 = d.k";
+    let mut config = SessionConfig::new();
+    config.options_mut().optimizer.enable_dynamic_join_filter = true;
+
+    let output = ContextWithParquet::with_config(Scenario::Int, RowGroup(5), config)
+        .await
+        .query(query)
+        .await;
+    println!("{}", output.description());
+
+    assert_eq!(output.predicate_evaluation_errors(), Some(0));
+    assert_eq!(output.row_groups_matched_statistics(), Some(1));
+    assert_eq!(output.row_groups_pruned_statistics(), Some(3));
+    assert_eq!(output.result_rows, 2, "{}", output.description());
+
+    // Without the dynamic filter, all row groups are read
+    let output =
+        ContextWithParquet::with_config(Scenario::Int, RowGroup(5), SessionConfig::new())
+            .await
+            .query(query)
+            .await;
+    assert_eq!(output.row_groups_pruned(), Some(0));
+    assert_eq!(output.result_rows, 2, "{}", output.description());
+}
+
 // $bits: number of bits of the integer to test (8, 16, 32, 64)
 // $correct_bloom_filters: if false, replicates the
 // https://github.com/apache/datafusion/issues/9779 bug so that tests pass
@@ -699,6 +699,9 @@ async fn csv_explain_analyze() {
 
     let verbose_needle = "Output Rows";
     assert_not_contains!(formatted, verbose_needle);
+
+    // the memory of every query is tracked separately
+    assert_contains!(&formatted, "Peak Memory Usage");
 }
 
 #[tokio::test]
@@ -0,0 +1,592 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ByteRangeCache`] caching byte ranges of objects read from object stores
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use datafusion_common::Result;
+use log::debug;
+use object_store::path::Path;
+use object_store::ObjectMeta;
+use parking_lot::Mutex;
+
+use crate::disk_manager::{DiskManager, RefCountedTempFile};
+
+/// Configuration of a [`ByteRangeCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRangeCacheConfig {
+    /// Maximum number of bytes cached in memory
+    pub memory_limit: usize,
+    /// Maximum number of bytes cached on local disk, in temporary files
+    /// created by the [`DiskManager`]. 0 disables the disk tier
+    pub disk_limit: usize,
+}
+
+impl ByteRangeCacheConfig {
+    /// Caches at most `memory_limit` bytes in memory, without disk tier
+    pub fn new(memory_limit: usize) -> Self {
+        Self {
+            memory_limit,
+            disk_limit: 0,
+        }
+    }
+
+    /// Caches the ranges evicted from memory in at most `disk_limit` bytes
+    /// of local disk
+    pub fn with_disk_limit(mut self, disk_limit: usize) -> Self {
+        self.disk_limit = disk_limit;
+        self
+    }
+}
+
+/// Statistics of a [`ByteRangeCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByteRangeCacheStats {
+    /// Number of reads served from memory
+    pub memory_hits: usize,
+    /// Number of reads served from local disk
+    pub disk_hits: usize,
+    /// Number of reads not found in the cache
+    pub misses: usize,
+    /// Number of ranges cached in memory
+    pub memory_entries: usize,
+    /// Number of bytes cached in memory
+    pub memory_bytes: usize,
+    /// Number of ranges cached on local disk
+    pub disk_entries: usize,
+    /// Number of bytes cached on local disk
+    pub disk_bytes: usize,
+    /// Number of ranges evicted from the cache
+    pub evictions: usize,
+}
+
+/// Identifies an object: the object store it is read from and its location
+type ObjectKey = (String, Path);
+
+/// Identifies a cached range: its object and start offset
+type EntryKey = (ObjectKey, usize);
+
+/// Caches byte ranges of objects read from (remote) object stores, so that
+/// repeated queries do not fetch the same data, e.g. Parquet column chunks,
+/// over and over.
+///
+/// Ranges are first cached in memory. Once the memory limit is reached, the
+/// least recently used ranges are moved to temporary files on local disk, if
+/// the disk tier is enabled, from which the least recently used ranges are
+/// evicted in turn.
+///
+/// A read is served from the cache if a cached range of the same object
+/// contains it. Only the ranges of objects whose version (e tag, version,
+/// modification time and size) has been recorded with
+/// [`ByteRangeCache::validate`], e.g. when listing them, are cached, and they
+/// are removed when another version is recorded or the object is written
+/// through the caching object store, see
+/// [`crate::object_store::CachingObjectStore`].
+pub struct ByteRangeCache {
+    config: ByteRangeCacheConfig,
+    /// Creates the files of the disk tier
+    disk_manager: Arc<DiskManager>,
+    state: Mutex<ByteRangeCacheState>,
+}
+
+#[derive(Default)]
+struct ByteRangeCacheState {
+    /// Cached ranges of every object, by start offset
+    objects: HashMap<ObjectKey, BTreeMap<usize, Entry>>,
+    /// Last known version of every object
+    versions: HashMap<ObjectKey, ObjectMeta>,
+    /// Keys of the ranges cached in memory by their last access, least
+    /// recent first
+    memory_lru: BTreeMap<u64, EntryKey>,
+    /// Keys of the ranges cached on disk by their last access, least
+    /// recent first
+    disk_lru: BTreeMap<u64, EntryKey>,
+    /// Incremented on every access
+    access_counter: u64,
+    stats: ByteRangeCacheStats,
+}
+
+struct Entry {
+    end: usize,
+    data: EntryData,
+    last_access: u64,
+}
+
+enum EntryData {
+    Memory(Bytes),
+    Disk(Arc<RefCountedTempFile>),
+}
+
+impl ByteRangeCacheState {
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+
+    /// Inserts `entry` for `key`, replacing the range starting at the same
+    /// offset, if any
+    fn insert(&mut self, key: EntryKey, entry: Entry) {
+        self.remove(&key);
+        let size = entry.end - key.1;
+        match &entry.data {
+            EntryData::Memory(_) => {
+                self.memory_lru.insert(entry.last_access, key.clone());
+                self.stats.memory_entries += 1;
+                self.stats.memory_bytes += size;
+            }
+            EntryData::Disk(_) => {
+                self.disk_lru.insert(entry.last_access, key.clone());
+                self.stats.disk_entries += 1;
+                self.stats.disk_bytes += size;
+            }
+        }
+        let ((store, location), start) = key;
+        self.objects
+            .entry((store, location))
+            .or_default()
+            .insert(start, entry);
+    }
+
+    fn remove(&mut self, key: &EntryKey) -> Option<Entry> {
+        let (object, start) = key;
+        let ranges = self.objects.get_mut(object)?;
+        let entry = ranges.remove(start)?;
+        if ranges.is_empty() {
+            self.objects.remove(object);
+        }
+        let size = entry.end - start;
+        match &entry.data {
+            EntryData::Memory(_) => {
+                self.memory_lru.remove(&entry.last_access);
+                self.stats.memory_entries -= 1;
+                self.stats.memory_bytes -= size;
+            }
+            EntryData::Disk(_) => {
+                self.disk_lru.remove(&entry.last_access);
+                self.stats.disk_entries -= 1;
+                self.stats.disk_bytes -= size;
+            }
+        }
+        Some(entry)
+    }
+
+    /// Removes all cached ranges of `object`
+    fn remove_object(&mut self, object: &ObjectKey) {
+        let starts = self
+            .objects
+            .get(object)
+            .map(|ranges| ranges.keys().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for start in starts {
+            self.remove(&(object.clone(), start));
+        }
+    }
+
+    /// Removes the least recently used ranges cached in memory until at most
+    /// `limit` bytes are used, returning them
+    fn evict_memory(&mut self, limit: usize) -> Vec<(EntryKey, Bytes)> {
+        let mut evicted = vec![];
+        while self.stats.memory_bytes > limit {
+            let Some((_, key)) = self.memory_lru.pop_first() else {
+                break;
+            };
+            if let Some(Entry {
+                data: EntryData::Memory(bytes),
+                ..
+            }) = self.remove_evicted(&key)
+            {
+                evicted.push((key, bytes));
+            }
+        }
+        evicted
+    }
+
+    /// Removes the least recently used ranges cached on disk until at most
+    /// `limit` bytes are used, deleting their files
+    fn evict_disk(&mut self, limit: usize) {
+        while self.stats.disk_bytes > limit {
+            let Some((_, key)) = self.disk_lru.pop_first() else {
+                break;
+            };
+            self.remove_evicted(&key);
+            self.stats.evictions += 1;
+        }
+    }
+
+    /// Removes the entry of `key`, whose LRU entry has already been removed
+    fn remove_evicted(&mut self, key: &EntryKey) -> Option<Entry> {
+        let entry = self.objects.get(&key.0)?.get(&key.1)?;
+        // `remove` expects the LRU entry to be present
+        let last_access = entry.last_access;
+        match entry.data {
+            EntryData::Memory(_) => self.memory_lru.insert(last_access, key.clone()),
+            EntryData::Disk(_) => self.disk_lru.insert(last_access, key.clone()),
+        };
+        self.remove(key)
+    }
+}
+
+impl ByteRangeCache {
+    /// Creates a cache creating the files of its disk tier with `disk_manager`
+    pub fn new(config: ByteRangeCacheConfig, disk_manager: Arc<DiskManager>) -> Self {
+        Self {
+            config,
+            disk_manager,
+            state: Mutex::new(ByteRangeCacheState::default()),
+        }
+    }
+
+    /// Returns the configuration of this cache
+    pub fn config(&self) -> &ByteRangeCacheConfig {
+        &self.config
+    }
+
+    /// Returns the current statistics of this cache
+    pub fn stats(&self) -> ByteRangeCacheStats {
+        self.state.lock().stats
+    }
+
+    /// Returns the bytes of `range` of the object at `location` of `store`,
+    /// if they are cached
+    pub fn get(
+        &self,
+        store: &str,
+        location: &Path,
+        range: &Range<usize>,
+    ) -> Option<Bytes> {
+        let object = (store.to_string(), location.clone());
+        let mut state = self.state.lock();
+        let found = state.objects.get(&object).and_then(|ranges| {
+            ranges
+                .range(..=range.start)
+                .rev()
+                .find(|(_, entry)| entry.end >= range.end)
+                .map(|(start, _)| *start)
+        });
+        let Some(start) = found else {
+            state.stats.misses += 1;
+            return None;
+        };
+
+        // mark the range as most recently used
+        let access = state.next_access();
+        let key = (object, start);
+        let entry = state.objects.get_mut(&key.0)?.get_mut(&start)?;
+        let previous_access = std::mem::replace(&mut entry.last_access, access);
+        let slice = (range.start - start)..(range.end - start);
+        match &entry.data {
+            EntryData::Memory(bytes) => {
+                let bytes = bytes.slice(slice);
+                state.memory_lru.remove(&previous_access);
+                state.memory_lru.insert(access, key);
+                state.stats.memory_hits += 1;
+                Some(bytes)
+            }
+            EntryData::Disk(file) => {
+                let file = Arc::clone(file);
+                state.disk_lru.remove(&previous_access);
+                state.disk_lru.insert(access, key);
+                state.stats.disk_hits += 1;
+                // don't hold the lock while reading the file
+                drop(state);
+                match std::fs::read(file.path()) {
+                    Ok(data) => Some(Bytes::from(data).slice(slice)),
+                    Err(e) => {
+                        debug!("Failed to read cached range from disk: {e}");
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Caches `bytes`, the contents of `range` of the object at `location`
+    /// of `store`, if the version of the object is known
+    pub fn put(&self, store: &str, location: &Path, range: Range<usize>, bytes: Bytes) {
+        if bytes.len() != range.len() || bytes.len() > self.config.memory_limit {
+            return;
+        }
+        let key = ((store.to_string(), location.clone()), range.start);
+        let evicted = {
+            let mut state = self.state.lock();
+            if !state.versions.contains_key(&key.0) {
+                return;
+            }
+            let last_access = state.next_access();
+            let entry = Entry {
+                end: range.end,
+                data: EntryData::Memory(bytes),
+                last_access,
+            };
+            state.insert(key, entry);
+            state
+                .evict_memory(self.config.memory_limit)
+                .into_iter()
+                .map(|(key, bytes)| {
+                    let version = state.versions.get(&key.0).cloned();
+                    (key, bytes, version)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (key, bytes, version) in evicted {
+            self.spill_to_disk(key, bytes, version);
+        }
+    }
+
+    /// Moves a range of the `version` of its object evicted from memory to
+    /// the disk tier, if enabled
+    fn spill_to_disk(&self, key: EntryKey, bytes: Bytes, version: Option<ObjectMeta>) {
+        let size = bytes.len();
+        if size > self.config.disk_limit || !self.disk_manager.tmp_files_enabled() {
+            self.state.lock().stats.evictions += 1;
+            return;
+        }
+
+        let file = match self.write_file(&bytes) {
+            Ok(file) => file,
+            Err(e) => {
+                debug!("Failed to cache range on disk: {e}");
+                self.state.lock().stats.evictions += 1;
+                return;
+            }
+        };
+
+        let mut state = self.state.lock();
+        // the range may have been cached again, or the object changed, in the
+        // meantime
+        let cached = state
+            .objects
+            .get(&key.0)
+            .is_some_and(|ranges| ranges.contains_key(&key.1));
+        let current = version.is_some() && state.versions.get(&key.0) == version.as_ref();
+        if !cached && current {
+            let last_access = state.next_access();
+            let entry = Entry {
+                end: key.1 + size,
+                data: EntryData::Disk(Arc::new(file)),
+                last_access,
+            };
+            state.insert(key, entry);
+            state.evict_disk(self.config.disk_limit);
+        }
+    }
+
+    fn write_file(&self, bytes: &Bytes) -> Result<RefCountedTempFile> {
+        let mut file = self.disk_manager.create_tmp_file("ByteRangeCache")?;
+        file.inner().as_file().write_all(bytes)?;
+        file.update_disk_usage()?;
+        Ok(file)
+    }
+
+    /// Records `meta` as the current version of the object at its location
+    /// of `store`, removing the cached ranges of any other version
+    pub fn validate(&self, store: &str, meta: &ObjectMeta) {
+        let object = (store.to_string(), meta.location.clone());
+        let mut state = self.state.lock();
+        if state.versions.get(&object) != Some(meta) {
+            state.remove_object(&object);
+            state.versions.insert(object, meta.clone());
+        }
+    }
+
+    /// Removes all cached ranges and the version of the object at `location`
+    /// of `store`
+    pub fn invalidate(&self, store: &str, location: &Path) {
+        let object = (store.to_string(), location.clone());
+        let mut state = self.state.lock();
+        state.remove_object(&object);
+        state.versions.remove(&object);
+    }
+
+    /// Removes all cached ranges
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        let stats = ByteRangeCacheStats {
+            memory_entries: 0,
+            memory_bytes: 0,
+            disk_entries: 0,
+            disk_bytes: 0,
+            ..state.stats
+        };
+        *state = ByteRangeCacheState {
+            stats,
+            ..Default::default()
+        };
+    }
+}
+
+impl std::fmt::Debug for ByteRangeCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ByteRangeCache")
+            .field("config", &self.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disk_manager::DiskManagerConfig;
+    use chrono::DateTime;
+
+    fn new_cache(memory_limit: usize, disk_limit: usize) -> ByteRangeCache {
+        let config = ByteRangeCacheConfig::new(memory_limit).with_disk_limit(disk_limit);
+        ByteRangeCache::new(
+            config,
+            DiskManager::try_new(DiskManagerConfig::new()).unwrap(),
+        )
+    }
+
+    fn data(range: Range<usize>) -> Bytes {
+        range.map(|i| i as u8).collect::<Vec<_>>().into()
+    }
+
+    fn object_meta(location: &Path, e_tag: &str) -> ObjectMeta {
+        ObjectMeta {
+            location: location.clone(),
+            last_modified: DateTime::parse_from_rfc3339("2022-09-27T22:36:00+02:00")
+                .unwrap()
+                .into(),
+            size: 1000,
+            e_tag: Some(e_tag.to_string()),
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_contained_ranges() {
+        let cache = new_cache(1000, 0);
+        let location = Path::from("file.parquet");
+        cache.validate("s3://bucket", &object_meta(&location, "1"));
+        assert_eq!(cache.get("s3://bucket", &location, &(0..10)), None);
+
+        cache.put("s3://bucket", &location, 10..100, data(10..100));
+        assert_eq!(
+            cache.get("s3://bucket", &location, &(10..100)),
+            Some(data(10..100))
+        );
+        assert_eq!(
+            cache.get("s3://bucket", &location, &(20..30)),
+            Some(data(20..30))
+        );
+        assert_eq!(cache.get("s3://bucket", &location, &(5..30)), None);
+        assert_eq!(cache.get("s3://bucket", &location, &(90..101)), None);
+        // other stores and objects are cached separately
+        assert_eq!(cache.get("s3://other", &location, &(20..30)), None);
+        assert_eq!(cache.get("s3://bucket", &Path::from("x"), &(20..30)), None);
+
+        let stats = cache.stats();
+        assert_eq!(stats.memory_hits, 2);
+        assert_eq!(stats.misses, 5);
+        assert_eq!(stats.memory_entries, 1);
+        assert_eq!(stats.memory_bytes, 90);
+
+        cache.invalidate("s3://bucket", &location);
+        assert_eq!(cache.get("s3://bucket", &location, &(20..30)), None);
+        assert_eq!(cache.stats().memory_bytes, 0);
+    }
+
+    #[test]
+    fn test_memory_eviction() {
+        let cache = new_cache(250, 0);
+        let location = Path::from("file.parquet");
+        cache.validate("s3://bucket", &object_meta(&location, "1"));
+        cache.put("s3://bucket", &location, 0..100, data(0..100));
+        cache.put("s3://bucket", &location, 100..200, data(100..200));
+        // `0..100` becomes the most recently used range
+        assert!(cache.get("s3://bucket", &location, &(0..100)).is_some());
+
+        cache.put("s3://bucket", &location, 200..300, data(200..300));
+        assert!(cache.get("s3://bucket", &location, &(100..200)).is_none());
+        assert!(cache.get("s3://bucket", &location, &(0..100)).is_some());
+        assert!(cache.get("s3://bucket", &location, &(200..300)).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.memory_bytes, 200);
+        assert_eq!(stats.evictions, 1);
+    }
+
+    #[test]
+    fn test_disk_tier() {
+        let cache = new_cache(150, 150);
+        let location = Path::from("file.parquet");
+        cache.validate("s3://bucket", &object_meta(&location, "1"));
+        cache.put("s3://bucket", &location, 0..100, data(0..100));
+        cache.put("s3://bucket", &location, 100..200, data(100..200));
+
+        // `0..100` has been moved to disk
+        let stats = cache.stats();
+        assert_eq!((stats.memory_entries, stats.memory_bytes), (1, 100));
+        assert_eq!((stats.disk_entries, stats.disk_bytes), (1, 100));
+        assert_eq!(
+            cache.get("s3://bucket", &location, &(50..60)),
+            Some(data(50..60))
+        );
+        assert_eq!(cache.stats().disk_hits, 1);
+
+        // `100..200` is moved to disk, evicting `0..100` from the disk tier
+        cache.put("s3://bucket", &location, 200..300, data(200..300));
+        assert!(cache.get("s3://bucket", &location, &(0..100)).is_none());
+        assert_eq!(
+            cache.get("s3://bucket", &location, &(100..200)),
+            Some(data(100..200))
+        );
+        let stats = cache.stats();
+        assert_eq!(stats.disk_bytes, 100);
+        assert_eq!(stats.evictions, 1);
+
+        cache.clear();
+        assert_eq!(cache.stats().disk_entries, 0);
+        assert!(cache.get("s3://bucket", &location, &(100..200)).is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let cache = new_cache(1000, 0);
+        let location = Path::from("file.parquet");
+
+        // the ranges of objects of unknown version are not cached
+        cache.put("s3://bucket", &location, 0..10, data(0..10));
+        assert_eq!(cache.get("s3://bucket", &location, &(0..10)), None);
+
+        cache.validate("s3://bucket", &object_meta(&location, "1"));
+        cache.put("s3://bucket", &location, 0..10, data(0..10));
+        cache.validate("s3://bucket", &object_meta(&location, "1"));
+        assert_eq!(
+            cache.get("s3://bucket", &location, &(0..10)),
+            Some(data(0..10))
+        );
+
+        // another version of the object drops its cached ranges
+        cache.validate("s3://bucket", &object_meta(&location, "2"));
+        assert_eq!(cache.get("s3://bucket", &location, &(0..10)), None);
+        assert_eq!(cache.stats().memory_bytes, 0);
+
+        // as does writing it
+        cache.put("s3://bucket", &location, 0..10, data(0..10));
+        cache.invalidate("s3://bucket", &location);
+        cache.put("s3://bucket", &location, 0..10, data(0..10));
+        assert_eq!(cache.get("s3://bucket", &location, &(0..10)), None);
+    }
+}
@@ -15,10 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::cache::byte_range_cache::ByteRangeCache;
 use crate::cache::CacheAccessor;
 use datafusion_common::{Result, Statistics};
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use std::any::Any;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
@@ -32,6 +34,26 @@ pub type FileStatisticsCache =
 pub type ListFilesCache =
     Arc<dyn CacheAccessor<Path, Arc<Vec<ObjectMeta>>, Extra = ObjectMeta>>;
 
+/// Metadata of a file, e.g. the decoded footer of a Parquet file, which can be
+/// stored in a [`FileMetadataCache`]
+pub trait FileMetadata: Any + Send + Sync {
+    /// Returns the metadata as [`Any`] so that it can be downcast to the
+    /// concrete type of the file format
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the memory used by the metadata in bytes, used to bound the
+    /// size of the cache
+    fn memory_size(&self) -> usize;
+}
+
+/// The cache of file metadata, e.g. Parquet footers and page indexes.
+/// if set [`CacheManagerConfig::with_file_metadata_cache`]
+/// Will avoid fetching and decoding the metadata of the same files repeatedly,
+/// entries are invalidated when the file changes, see
+/// [`crate::cache::cache_unit::DefaultFilesMetadataCache`].
+pub type FileMetadataCache =
+    Arc<dyn CacheAccessor<Path, Arc<dyn FileMetadata>, Extra = ObjectMeta>>;
+
 impl Debug for dyn CacheAccessor<Path, Arc<Statistics>, Extra = ObjectMeta> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Cache name: {} with length: {}", self.name(), self.len())
@@ -44,10 +66,18 @@ impl Debug for dyn CacheAccessor<Path, Arc<Vec<ObjectMeta>>, Extra = ObjectMeta>
     }
 }
 
+impl Debug for dyn CacheAccessor<Path, Arc<dyn FileMetadata>, Extra = ObjectMeta> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cache name: {} with length: {}", self.name(), self.len())
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct CacheManager {
     file_statistic_cache: Option<FileStatisticsCache>,
     list_files_cache: Option<ListFilesCache>,
+    file_metadata_cache: Option<FileMetadataCache>,
+    object_store_cache: Option<Arc<ByteRangeCache>>,
 }
 
 impl CacheManager {
@@ -59,6 +89,12 @@ impl CacheManager {
         if let Some(lc) = &config.list_files_cache {
             manager.list_files_cache = Some(Arc::clone(lc))
         }
+        if let Some(mc) = &config.file_metadata_cache {
+            manager.file_metadata_cache = Some(Arc::clone(mc))
+        }
+        if let Some(oc) = &config.object_store_cache {
+            manager.object_store_cache = Some(Arc::clone(oc))
+        }
         Ok(Arc::new(manager))
     }
 
@@ -71,6 +107,16 @@ impl CacheManager {
     pub fn get_list_files_cache(&self) -> Option<ListFilesCache> {
         self.list_files_cache.clone()
     }
+
+    /// Get the cache of file metadata.
+    pub fn get_file_metadata_cache(&self) -> Option<FileMetadataCache> {
+        self.file_metadata_cache.clone()
+    }
+
+    /// Get the cache of byte ranges read from object stores.
+    pub fn get_object_store_cache(&self) -> Option<Arc<ByteRangeCache>> {
+        self.object_store_cache.clone()
+    }
 }
 
 #[derive(Clone, Default)]
@@ -86,6 +132,17 @@ pub struct CacheManagerConfig {
     /// location.  
     /// Default is disable.
     pub list_files_cache: Option<ListFilesCache>,
+    /// Enable cache of file metadata, e.g. Parquet footers and page indexes.
+    /// Avoids fetching and decoding the metadata of the same files repeatedly
+    /// across queries, which may be expensive for remote object storage.
+    /// Default is disable.
+    pub file_metadata_cache: Option<FileMetadataCache>,
+    /// Enable cache of byte ranges read from object stores, e.g. Parquet
+    /// column chunks, in memory and on local disk. The object stores of the
+    /// [`crate::runtime_env::RuntimeEnv`] are wrapped to read through this cache,
+    /// see [`crate::object_store::CachingObjectStoreRegistry`].
+    /// Default is disable.
+    pub object_store_cache: Option<Arc<ByteRangeCache>>,
 }
 
 impl CacheManagerConfig {
@@ -101,4 +158,14 @@ impl CacheManagerConfig {
         self.list_files_cache = cache;
         self
     }
+
+    pub fn with_file_metadata_cache(mut self, cache: Option<FileMetadataCache>) -> Self {
+        self.file_metadata_cache = cache;
+        self
+    }
+
+    pub fn with_object_store_cache(mut self, cache: Option<Arc<ByteRangeCache>>) -> Self {
+        self.object_store_cache = cache;
+        self
+    }
 }
@@ -15,8 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+use crate::cache::cache_manager::FileMetadata;
 use crate::cache::CacheAccessor;
 
 use datafusion_common::Statistics;
@@ -24,6 +26,7 @@ use datafusion_common::Statistics;
 use dashmap::DashMap;
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use parking_lot::Mutex;
 
 /// Collected statistics for files
 /// Cache is invalided when file size or last modification has changed
@@ -157,15 +160,188 @@ impl CacheAccessor<Path, Arc<Vec<ObjectMeta>>> for DefaultListFilesCache {
     }
 }
 
+/// Collected file metadata, e.g. Parquet footers, bounded by the memory used
+/// by the metadata.
+///
+/// Entries are invalidated when the size, last modification or ETag of the
+/// file has changed. Once the cache is full, the least recently used entries
+/// are evicted.
+pub struct DefaultFilesMetadataCache {
+    /// Maximum memory in bytes used by the cached metadata
+    capacity: usize,
+    state: Mutex<FilesMetadataCacheState>,
+}
+
+#[derive(Default)]
+struct FilesMetadataCacheState {
+    entries: HashMap<Path, FilesMetadataCacheEntry>,
+    /// Keys of the entries by their last access, least recent first
+    lru: BTreeMap<u64, Path>,
+    /// Incremented on every access
+    access_counter: u64,
+    /// Memory in bytes used by the cached metadata
+    memory_used: usize,
+}
+
+struct FilesMetadataCacheEntry {
+    meta: ObjectMeta,
+    metadata: Arc<dyn FileMetadata>,
+    last_access: u64,
+}
+
+impl FilesMetadataCacheState {
+    /// Marks `key` as most recently used
+    fn touch(&mut self, key: &Path) {
+        self.access_counter += 1;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.lru.remove(&entry.last_access);
+            entry.last_access = self.access_counter;
+            self.lru.insert(self.access_counter, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &Path) -> Option<FilesMetadataCacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.lru.remove(&entry.last_access);
+        self.memory_used -= entry.metadata.memory_size();
+        Some(entry)
+    }
+
+    /// Evicts the least recently used entries until at most `capacity` bytes
+    /// are used
+    fn evict(&mut self, capacity: usize) {
+        while self.memory_used > capacity {
+            let Some((_, key)) = self.lru.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.memory_used -= entry.metadata.memory_size();
+            }
+        }
+    }
+}
+
+impl DefaultFilesMetadataCache {
+    /// Creates a cache whose metadata uses at most `capacity` bytes of memory
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(FilesMetadataCacheState::default()),
+        }
+    }
+
+    /// Returns the maximum memory in bytes used by the cached metadata
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the memory in bytes currently used by the cached metadata
+    pub fn memory_used(&self) -> usize {
+        self.state.lock().memory_used
+    }
+}
+
+impl CacheAccessor<Path, Arc<dyn FileMetadata>> for DefaultFilesMetadataCache {
+    type Extra = ObjectMeta;
+
+    fn get(&self, k: &Path) -> Option<Arc<dyn FileMetadata>> {
+        let mut state = self.state.lock();
+        let metadata = Arc::clone(&state.entries.get(k)?.metadata);
+        state.touch(k);
+        Some(metadata)
+    }
+
+    /// Get the metadata of a file. Returns None if file has changed or not found.
+    fn get_with_extra(&self, k: &Path, e: &Self::Extra) -> Option<Arc<dyn FileMetadata>> {
+        let mut state = self.state.lock();
+        let entry = state.entries.get(k)?;
+        if entry.meta.size != e.size
+            || entry.meta.last_modified != e.last_modified
+            || entry.meta.e_tag != e.e_tag
+        {
+            // file has changed
+            state.remove(k);
+            return None;
+        }
+        let metadata = Arc::clone(&entry.metadata);
+        state.touch(k);
+        Some(metadata)
+    }
+
+    fn put(
+        &self,
+        _key: &Path,
+        _value: Arc<dyn FileMetadata>,
+    ) -> Option<Arc<dyn FileMetadata>> {
+        panic!("Put cache in DefaultFilesMetadataCache without Extra not supported.")
+    }
+
+    fn put_with_extra(
+        &self,
+        key: &Path,
+        value: Arc<dyn FileMetadata>,
+        e: &Self::Extra,
+    ) -> Option<Arc<dyn FileMetadata>> {
+        let mut state = self.state.lock();
+        let old = state.remove(key).map(|entry| entry.metadata);
+
+        let memory_size = value.memory_size();
+        if memory_size > self.capacity {
+            // would evict everything else and itself
+            return old;
+        }
+
+        state.access_counter += 1;
+        let last_access = state.access_counter;
+        state.lru.insert(last_access, key.clone());
+        state.memory_used += memory_size;
+        state.entries.insert(
+            key.clone(),
+            FilesMetadataCacheEntry {
+                meta: e.clone(),
+                metadata: value,
+                last_access,
+            },
+        );
+        state.evict(self.capacity);
+        old
+    }
+
+    fn remove(&mut self, k: &Path) -> Option<Arc<dyn FileMetadata>> {
+        self.state.lock().remove(k).map(|entry| entry.metadata)
+    }
+
+    fn contains_key(&self, k: &Path) -> bool {
+        self.state.lock().entries.contains_key(k)
+    }
+
+    fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    fn clear(&self) {
+        *self.state.lock() = FilesMetadataCacheState::default();
+    }
+
+    fn name(&self) -> String {
+        "DefaultFilesMetadataCache".to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::cache::cache_unit::{DefaultFileStatisticsCache, DefaultListFilesCache};
+    use crate::cache::cache_manager::FileMetadata;
+    use crate::cache::cache_unit::{
+        DefaultFileStatisticsCache, DefaultFilesMetadataCache, DefaultListFilesCache,
+    };
     use crate::cache::CacheAccessor;
     use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
     use chrono::DateTime;
     use datafusion_common::Statistics;
     use object_store::path::Path;
     use object_store::ObjectMeta;
+    use std::any::Any;
+    use std::sync::Arc;
 
     #[test]
     fn test_statistics_cache() {
@@ -232,4 +408,85 @@ mod tests {
             meta.clone()
         );
     }
+
+    struct TestMetadata(usize);
+
+    impl FileMetadata for TestMetadata {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn memory_size(&self) -> usize {
+            self.0
+        }
+    }
+
+    fn object_meta(location: &str) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from(location),
+            last_modified: DateTime::parse_from_rfc3339("2022-09-27T22:36:00+02:00")
+                .unwrap()
+                .into(),
+            size: 1024,
+            e_tag: Some("etag".to_string()),
+            version: None,
+        }
+    }
+
+    fn cached_size(
+        cache: &DefaultFilesMetadataCache,
+        meta: &ObjectMeta,
+    ) -> Option<usize> {
+        cache
+            .get_with_extra(&meta.location, meta)
+            .map(|metadata| metadata.as_any().downcast_ref::<TestMetadata>().unwrap().0)
+    }
+
+    #[test]
+    fn test_files_metadata_cache_invalidation() {
+        let meta = object_meta("test");
+        let cache = DefaultFilesMetadataCache::new(1000);
+        assert!(cache.get_with_extra(&meta.location, &meta).is_none());
+
+        cache.put_with_extra(&meta.location, Arc::new(TestMetadata(100)), &meta);
+        assert_eq!(cached_size(&cache, &meta), Some(100));
+        assert_eq!(cache.memory_used(), 100);
+
+        // file ETag changed
+        let mut meta2 = meta.clone();
+        meta2.e_tag = Some("etag2".to_string());
+        assert_eq!(cached_size(&cache, &meta2), None);
+
+        // the stale entry has been removed
+        assert_eq!(cached_size(&cache, &meta), None);
+        assert_eq!(cache.memory_used(), 0);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_files_metadata_cache_lru_eviction() {
+        let (a, b, c) = (object_meta("a"), object_meta("b"), object_meta("c"));
+        let cache = DefaultFilesMetadataCache::new(250);
+
+        cache.put_with_extra(&a.location, Arc::new(TestMetadata(100)), &a);
+        cache.put_with_extra(&b.location, Arc::new(TestMetadata(100)), &b);
+        // `a` becomes the most recently used entry
+        assert!(cached_size(&cache, &a).is_some());
+
+        cache.put_with_extra(&c.location, Arc::new(TestMetadata(100)), &c);
+        assert_eq!(cached_size(&cache, &b), None);
+        assert_eq!(cached_size(&cache, &a), Some(100));
+        assert_eq!(cached_size(&cache, &c), Some(100));
+        assert_eq!(cache.memory_used(), 200);
+
+        // metadata larger than the cache is not cached
+        cache.put_with_extra(&b.location, Arc::new(TestMetadata(300)), &b);
+        assert_eq!(cached_size(&cache, &b), None);
+        assert_eq!(cache.len(), 2);
+
+        // replacing an entry accounts for its new size
+        cache.put_with_extra(&a.location, Arc::new(TestMetadata(150)), &a);
+        assert_eq!(cached_size(&cache, &a), Some(150));
+        assert_eq!(cache.memory_used(), 250);
+    }
 }
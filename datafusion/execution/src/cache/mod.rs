@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+pub mod byte_range_cache;
 pub mod cache_manager;
 pub mod cache_unit;
 
@@ -23,7 +23,7 @@ use std::{
 };
 
 use datafusion_common::{
-    config::{ConfigExtension, ConfigOptions},
+    config::{ConfigExtension, ConfigOptions, SpillCompression},
     Result, ScalarValue,
 };
 
@@ -446,6 +446,40 @@ impl SessionConfig {
         self.options.execution.enforce_batch_size_in_joins
     }
 
+    /// Sets the compression codec of the files written when spilling to disk
+    pub fn with_spill_compression(mut self, spill_compression: SpillCompression) -> Self {
+        self.options.execution.spill_compression = spill_compression;
+        self
+    }
+
+    /// Returns the compression codec of the files written when spilling to disk
+    pub fn spill_compression(&self) -> SpillCompression {
+        self.options.execution.spill_compression
+    }
+
+    /// Sets the maximum number of bytes the spill files of a query may use,
+    /// see [`max_spill_disk_size`]
+    ///
+    /// [`max_spill_disk_size`]: datafusion_common::config::ExecutionOptions::max_spill_disk_size
+    pub fn with_max_spill_disk_size(mut self, max_spill_disk_size: usize) -> Self {
+        self.options.execution.max_spill_disk_size = Some(max_spill_disk_size);
+        self
+    }
+
+    /// Sets the maximum number of bytes of memory a query may reserve, see
+    /// [`query_memory_limit`]
+    ///
+    /// [`query_memory_limit`]: datafusion_common::config::ExecutionOptions::query_memory_limit
+    pub fn with_query_memory_limit(mut self, query_memory_limit: usize) -> Self {
+        self.options.execution.query_memory_limit = Some(query_memory_limit);
+        self
+    }
+
+    /// Returns the maximum number of bytes of memory a query may reserve, if any
+    pub fn query_memory_limit(&self) -> Option<usize> {
+        self.options.execution.query_memory_limit
+    }
+
     /// Convert configuration options to name-value pairs with values
     /// converted to strings.
     ///
@@ -18,11 +18,15 @@
 //! Manages files generated during query execution, files are
 //! hashed among the directories listed in RuntimeConfig::local_dirs.
 
-use datafusion_common::{resources_datafusion_err, DataFusionError, Result};
+use crate::memory_pool::human_readable_size;
+use datafusion_common::{
+    resources_datafusion_err, resources_err, DataFusionError, Result,
+};
 use log::debug;
 use parking_lot::Mutex;
 use rand::{thread_rng, Rng};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tempfile::{Builder, NamedTempFile, TempDir};
 
@@ -75,7 +79,30 @@ pub struct DiskManager {
     ///
     /// If `Some(vec![])` a new OS specified temporary directory will be created
     /// If `None` an error will be returned (configured not to spill)
-    local_dirs: Mutex<Option<Vec<Arc<TempDir>>>>,
+    ///
+    /// Shared with the disk managers created by [`Self::with_disk_quota`]
+    local_dirs: Arc<Mutex<Option<Vec<Arc<TempDir>>>>>,
+    /// Disk space used by the temporary files created by this disk manager
+    disk_usage: Arc<DiskUsage>,
+}
+
+/// Disk space used by the temporary files of a [`DiskManager`], updated as
+/// files grow or are deleted
+#[derive(Debug)]
+struct DiskUsage {
+    /// Total size in bytes of the live temporary files
+    used: AtomicU64,
+    /// Maximum size in bytes the temporary files may use, if limited
+    quota: Option<u64>,
+}
+
+impl DiskUsage {
+    fn new(quota: Option<u64>) -> Arc<Self> {
+        Arc::new(Self {
+            used: AtomicU64::new(0),
+            quota,
+        })
+    }
 }
 
 impl DiskManager {
@@ -84,7 +111,8 @@ impl DiskManager {
         match config {
             DiskManagerConfig::Existing(manager) => Ok(manager),
             DiskManagerConfig::NewOs => Ok(Arc::new(Self {
-                local_dirs: Mutex::new(Some(vec![])),
+                local_dirs: Arc::new(Mutex::new(Some(vec![]))),
+                disk_usage: DiskUsage::new(None),
             })),
             DiskManagerConfig::NewSpecified(conf_dirs) => {
                 let local_dirs = create_local_dirs(conf_dirs)?;
@@ -93,15 +121,43 @@ impl DiskManager {
                     local_dirs
                 );
                 Ok(Arc::new(Self {
-                    local_dirs: Mutex::new(Some(local_dirs)),
+                    local_dirs: Arc::new(Mutex::new(Some(local_dirs))),
+                    disk_usage: DiskUsage::new(None),
                 }))
             }
             DiskManagerConfig::Disabled => Ok(Arc::new(Self {
-                local_dirs: Mutex::new(None),
+                local_dirs: Arc::new(Mutex::new(None)),
+                disk_usage: DiskUsage::new(None),
             })),
         }
     }
 
+    /// Returns a new disk manager that creates temporary files in the same
+    /// locations as this one, but whose files may use at most `quota` bytes
+    /// of disk space in total, e.g. to limit the spill files of a single query.
+    ///
+    /// Writing beyond the quota is reported by
+    /// [`RefCountedTempFile::update_disk_usage`]. The files of the returned
+    /// disk manager are not counted against the quota of this one.
+    pub fn with_disk_quota(&self, quota: u64) -> Arc<Self> {
+        Arc::new(Self {
+            local_dirs: Arc::clone(&self.local_dirs),
+            disk_usage: DiskUsage::new(Some(quota)),
+        })
+    }
+
+    /// Returns the maximum number of bytes the temporary files of this disk
+    /// manager may use, if limited
+    pub fn disk_quota(&self) -> Option<u64> {
+        self.disk_usage.quota
+    }
+
+    /// Returns the number of bytes currently used by the temporary files of
+    /// this disk manager, as of their last [`RefCountedTempFile::update_disk_usage`]
+    pub fn used_disk_space(&self) -> u64 {
+        self.disk_usage.used.load(Ordering::Relaxed)
+    }
+
     /// Return true if this disk manager supports creating temporary
     /// files. If this returns false, any call to `create_tmp_file`
     /// will error.
@@ -143,6 +199,8 @@ impl DiskManager {
             tempfile: Builder::new()
                 .tempfile_in(local_dirs[dir_index].as_ref())
                 .map_err(DataFusionError::IoError)?,
+            disk_usage: Arc::clone(&self.disk_usage),
+            current_file_disk_usage: 0,
         })
     }
 }
@@ -156,6 +214,10 @@ pub struct RefCountedTempFile {
     #[allow(dead_code)]
     parent_temp_dir: Arc<TempDir>,
     tempfile: NamedTempFile,
+    /// Disk usage of the [`DiskManager`] that created this file
+    disk_usage: Arc<DiskUsage>,
+    /// Size of the file as of the last call to [`Self::update_disk_usage`]
+    current_file_disk_usage: u64,
 }
 
 impl RefCountedTempFile {
@@ -166,6 +228,46 @@ impl RefCountedTempFile {
     pub fn inner(&self) -> &NamedTempFile {
         &self.tempfile
     }
+
+    /// Accounts the current size of this file in the disk usage of the
+    /// [`DiskManager`] that created it. Must be called after writing to the file.
+    ///
+    /// Returns a resources exhausted error if the temporary files of the disk
+    /// manager now exceed its quota, see [`DiskManager::with_disk_quota`].
+    pub fn update_disk_usage(&mut self) -> Result<()> {
+        let file_size = self.tempfile.as_file().metadata()?.len();
+        let previous = std::mem::replace(&mut self.current_file_disk_usage, file_size);
+        let used = if file_size >= previous {
+            let grown = file_size - previous;
+            self.disk_usage.used.fetch_add(grown, Ordering::Relaxed) + grown
+        } else {
+            let shrunk = previous - file_size;
+            self.disk_usage.used.fetch_sub(shrunk, Ordering::Relaxed) - shrunk
+        };
+
+        match self.disk_usage.quota {
+            Some(quota) if used > quota => resources_err!(
+                "Spill files use {} of disk space, exceeding the quota of {}",
+                human_readable_size(used as usize),
+                human_readable_size(quota as usize)
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the size of this file as of the last call to
+    /// [`Self::update_disk_usage`]
+    pub fn current_disk_usage(&self) -> u64 {
+        self.current_file_disk_usage
+    }
+}
+
+impl Drop for RefCountedTempFile {
+    fn drop(&mut self) {
+        self.disk_usage
+            .used
+            .fetch_sub(self.current_file_disk_usage, Ordering::Relaxed);
+    }
 }
 
 /// Setup local dirs by creating one new dir in each of the given dirs
@@ -188,6 +290,7 @@ fn create_local_dirs(local_dirs: Vec<PathBuf>) -> Result<Vec<Arc<TempDir>>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
 
     #[test]
     fn lazy_temp_dir_creation() -> Result<()> {
@@ -250,6 +353,37 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_disk_quota() -> Result<()> {
+        let dm = DiskManager::try_new(DiskManagerConfig::new())?;
+        let query_dm = dm.with_disk_quota(100);
+        assert_eq!(query_dm.disk_quota(), Some(100));
+
+        let mut file1 = query_dm.create_tmp_file("Testing")?;
+        file1.inner().as_file().write_all(&[0; 60])?;
+        file1.update_disk_usage()?;
+        assert_eq!(file1.current_disk_usage(), 60);
+        assert_eq!(query_dm.used_disk_space(), 60);
+        // the quota only applies to the files of the scoped disk manager
+        assert_eq!(dm.used_disk_space(), 0);
+
+        let mut file2 = query_dm.create_tmp_file("Testing")?;
+        file2.inner().as_file().write_all(&[0; 60])?;
+        assert_eq!(
+            file2.update_disk_usage().unwrap_err().strip_backtrace(),
+            "Resources exhausted: Spill files use 120.0 B of disk space, exceeding the quota of 100.0 B"
+        );
+
+        // deleting a file releases its disk space
+        drop(file1);
+        assert_eq!(query_dm.used_disk_space(), 60);
+        file2.update_disk_usage()?;
+        drop(file2);
+        assert_eq!(query_dm.used_disk_space(), 0);
+
+        Ok(())
+    }
+
     #[test]
     fn test_disk_manager_create_spill_folder() {
         let config = DiskManagerConfig::new_specified(vec!["DOESNT_EXIST".into()]);
@@ -0,0 +1,256 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use datafusion_common::{internal_datafusion_err, Result};
+use futures::channel::oneshot;
+use log::debug;
+use parking_lot::Mutex;
+
+/// Delays starting queries until the memory they may use is available.
+///
+/// Before it starts, every query requests its memory limit (see
+/// [`QueryMemoryPool`]) from the queue, and is admitted once the limits of
+/// the running queries plus its own limit do not exceed the capacity of the
+/// queue, typically the size of the memory pool shared by all queries.
+/// Queries are admitted in the order of their requests, and the memory is
+/// returned to the queue when the [`AdmissionPermit`] of the query is dropped.
+///
+/// A request larger than the capacity is reduced to the capacity, so that
+/// the query can run alone.
+///
+/// [`QueryMemoryPool`]: crate::memory_pool::QueryMemoryPool
+#[derive(Debug)]
+pub struct MemoryAdmissionQueue {
+    capacity: usize,
+    state: Mutex<AdmissionState>,
+}
+
+#[derive(Debug, Default)]
+struct AdmissionState {
+    /// The memory requested by the admitted queries
+    admitted: usize,
+    /// The id, the requested memory of the waiting queries, and where to send
+    /// their permits, in the order of their requests
+    waiting: VecDeque<(usize, usize, oneshot::Sender<AdmissionPermit>)>,
+    /// The id of the next waiting query
+    next_id: usize,
+}
+
+impl MemoryAdmissionQueue {
+    /// Admits queries requesting up to `capacity` bytes in total
+    pub fn new(capacity: usize) -> Self {
+        debug!("Created new MemoryAdmissionQueue(capacity={capacity})");
+        Self {
+            capacity,
+            state: Mutex::new(AdmissionState::default()),
+        }
+    }
+
+    /// Returns the capacity of this queue in bytes
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the memory requested by the admitted queries in bytes
+    pub fn admitted(&self) -> usize {
+        self.state.lock().admitted
+    }
+
+    /// Returns the number of queries waiting to be admitted
+    pub fn num_waiting(&self) -> usize {
+        self.state.lock().waiting.len()
+    }
+
+    /// Waits until `memory` bytes are available, returning a permit holding
+    /// them until it is dropped
+    pub async fn admit(self: &Arc<Self>, memory: usize) -> Result<AdmissionPermit> {
+        let memory = memory.min(self.capacity);
+        let (sender, receiver) = oneshot::channel();
+        let id = {
+            let mut state = self.state.lock();
+            let id = state.next_id;
+            state.next_id += 1;
+            state.waiting.push_back((id, memory, sender));
+            id
+        };
+        // removes the request if the query is cancelled while waiting
+        let _guard = WaitingGuard {
+            queue: Arc::clone(self),
+            id,
+        };
+        self.admit_waiting();
+        receiver
+            .await
+            .map_err(|_| internal_datafusion_err!("Memory admission request was dropped"))
+    }
+
+    /// Admits the waiting queries whose memory is available, in order
+    fn admit_waiting(self: &Arc<Self>) {
+        let admitted = {
+            let mut state = self.state.lock();
+            let mut admitted = vec![];
+            while let Some((_, memory, _)) = state.waiting.front() {
+                if state.admitted + memory > self.capacity {
+                    break;
+                }
+                let (_, memory, sender) = state.waiting.pop_front().unwrap();
+                state.admitted += memory;
+                admitted.push((memory, sender));
+            }
+            admitted
+        };
+
+        // send outside of the lock: if the query was cancelled in the
+        // meantime, the permit is dropped, releasing its memory again
+        for (memory, sender) in admitted {
+            let _ = sender.send(AdmissionPermit {
+                queue: Arc::clone(self),
+                memory,
+            });
+        }
+    }
+
+    fn release(self: &Arc<Self>, memory: usize) {
+        self.state.lock().admitted -= memory;
+        self.admit_waiting();
+    }
+}
+
+/// Removes the request of a query from the waiting queue when the query is
+/// cancelled before being admitted, admitting the queries behind it
+#[derive(Debug)]
+struct WaitingGuard {
+    queue: Arc<MemoryAdmissionQueue>,
+    id: usize,
+}
+
+impl Drop for WaitingGuard {
+    fn drop(&mut self) {
+        let removed = {
+            let mut state = self.queue.state.lock();
+            match state.waiting.iter().position(|(id, _, _)| *id == self.id) {
+                Some(index) => state.waiting.remove(index).is_some(),
+                // already admitted
+                None => false,
+            }
+        };
+        if removed {
+            self.queue.admit_waiting();
+        }
+    }
+}
+
+/// The admission of a query by a [`MemoryAdmissionQueue`], returning the
+/// memory requested by the query to the queue on drop
+#[derive(Debug)]
+pub struct AdmissionPermit {
+    queue: Arc<MemoryAdmissionQueue>,
+    memory: usize,
+}
+
+impl AdmissionPermit {
+    /// Returns the memory held by this permit in bytes
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.queue.release(self.memory);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::FutureExt;
+
+    #[test]
+    fn test_admission_order() {
+        let queue = Arc::new(MemoryAdmissionQueue::new(100));
+
+        let p1 = block_on(queue.admit(60)).unwrap();
+        assert_eq!(queue.admitted(), 60);
+
+        // does not fit until `p1` is dropped
+        let mut f2 = Box::pin(queue.admit(50));
+        assert!((&mut f2).now_or_never().is_none());
+        assert_eq!(queue.num_waiting(), 1);
+
+        // fits, but must wait for `f2`
+        let mut f3 = Box::pin(queue.admit(10));
+        assert!((&mut f3).now_or_never().is_none());
+        assert_eq!(queue.num_waiting(), 2);
+
+        drop(p1);
+        assert_eq!(queue.num_waiting(), 0);
+        assert_eq!(queue.admitted(), 60);
+        let p2 = f2.now_or_never().unwrap().unwrap();
+        let p3 = f3.now_or_never().unwrap().unwrap();
+        assert_eq!((p2.memory(), p3.memory()), (50, 10));
+
+        // larger requests are reduced to the capacity
+        let mut f4 = Box::pin(queue.admit(1000));
+        assert!((&mut f4).now_or_never().is_none());
+        drop(p2);
+        drop(p3);
+        let p4 = f4.now_or_never().unwrap().unwrap();
+        assert_eq!(p4.memory(), 100);
+        drop(p4);
+        assert_eq!(queue.admitted(), 0);
+    }
+
+    #[test]
+    fn test_cancelled_admission() {
+        let queue = Arc::new(MemoryAdmissionQueue::new(100));
+        let p1 = block_on(queue.admit(100)).unwrap();
+
+        let mut f2 = Box::pin(queue.admit(100));
+        assert!((&mut f2).now_or_never().is_none());
+        let mut f3 = Box::pin(queue.admit(50));
+        assert!((&mut f3).now_or_never().is_none());
+
+        // cancelling `f2` lets `f3` be admitted once `p1` is dropped
+        drop(f2);
+        assert_eq!(queue.num_waiting(), 1);
+        drop(p1);
+        let p3 = f3.now_or_never().unwrap().unwrap();
+        assert_eq!(queue.admitted(), 50);
+        assert_eq!(queue.num_waiting(), 0);
+
+        let mut f4 = Box::pin(queue.admit(60));
+        assert!((&mut f4).now_or_never().is_none());
+        let mut f5 = Box::pin(queue.admit(40));
+        assert!((&mut f5).now_or_never().is_none());
+        assert_eq!(queue.num_waiting(), 2);
+
+        // cancelling `f4` at the head of the queue admits `f5` immediately
+        drop(f4);
+        let p5 = f5.now_or_never().unwrap().unwrap();
+        assert_eq!(queue.admitted(), 90);
+        assert_eq!(queue.num_waiting(), 0);
+
+        drop(p3);
+        drop(p5);
+        assert_eq!(queue.admitted(), 0);
+    }
+}
@@ -21,11 +21,13 @@
 use datafusion_common::{internal_err, Result};
 use std::{cmp::Ordering, sync::Arc};
 
+mod admission;
 mod pool;
 pub mod proxy {
     pub use datafusion_common::utils::proxy::{RawTableAllocExt, VecAllocExt};
 }
 
+pub use admission::*;
 pub use pool::*;
 
 /// Tracks and potentially limits memory use across operators during execution.
@@ -106,6 +108,12 @@ pub use pool::*;
 ///
 /// * [`FairSpillPool`]: Limits memory usage to a fixed size, allocating memory
 ///   to all spilling operators fairly
+///
+/// * [`QueryMemoryPool`]: Limits the memory usage of a single query, drawing
+///   from a pool shared by all queries, see
+///   [`ExecutionOptions::query_memory_limit`]
+///
+/// [`ExecutionOptions::query_memory_limit`]: datafusion_common::config::ExecutionOptions::query_memory_limit
 pub trait MemoryPool: Send + Sync + std::fmt::Debug {
     /// Registers a new [`MemoryConsumer`]
     ///
@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
+use crate::memory_pool::{
+    AdmissionPermit, MemoryAdmissionQueue, MemoryConsumer, MemoryPool, MemoryReservation,
+};
 use datafusion_common::HashMap;
 use datafusion_common::{resources_datafusion_err, DataFusionError, Result};
 use log::debug;
@@ -23,6 +25,7 @@ use parking_lot::Mutex;
 use std::{
     num::NonZeroUsize,
     sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    sync::Arc,
 };
 
 /// A [`MemoryPool`] that enforces no limit
@@ -388,6 +391,139 @@ impl<I: MemoryPool> MemoryPool for TrackConsumersPool<I> {
     }
 }
 
+/// A [`MemoryPool`] for the reservations of a single query, which are also
+/// made in a parent pool shared by all queries.
+///
+/// This pool limits the memory used by the query, so that one heavy query
+/// cannot starve the other queries of the parent pool, and tracks the peak
+/// memory used by the query.
+///
+/// If a [`MemoryAdmissionQueue`] is provided, the query must be admitted by
+/// the queue before it starts, see [`Self::admit`].
+///
+/// ```text
+///    ┌──────────────────────────────────────────────────────────────┐
+///    │                    Parent (e.g. FairSpillPool)               │
+///    │  ┌──────────────────┐ ┌──────────────────┐                   │
+///    │  │ QueryMemoryPool  │ │ QueryMemoryPool  │        ...        │
+///    │  │    (query 1)     │ │    (query 2)     │                   │
+///    │  └──────────────────┘ └──────────────────┘                   │
+///    └──────────────────────────────────────────────────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct QueryMemoryPool {
+    parent: Arc<dyn MemoryPool>,
+    /// The memory limit of the query, if any
+    limit: Option<usize>,
+    used: AtomicUsize,
+    peak: AtomicUsize,
+    admission_queue: Option<Arc<MemoryAdmissionQueue>>,
+    /// The admission of the query, once admitted
+    permit: futures::lock::Mutex<Option<AdmissionPermit>>,
+}
+
+impl QueryMemoryPool {
+    /// Allocate up to `limit` bytes, if any, from `parent`
+    pub fn new(parent: Arc<dyn MemoryPool>, limit: Option<usize>) -> Self {
+        debug!("Created new QueryMemoryPool(limit={limit:?})");
+        Self {
+            parent,
+            limit,
+            used: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            admission_queue: None,
+            permit: futures::lock::Mutex::new(None),
+        }
+    }
+
+    /// Admit the query with `admission_queue` before it starts
+    pub fn with_admission_queue(
+        mut self,
+        admission_queue: Arc<MemoryAdmissionQueue>,
+    ) -> Self {
+        self.admission_queue = Some(admission_queue);
+        self
+    }
+
+    /// Returns the memory limit of the query, if any
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Returns the peak memory reserved by the query in bytes
+    pub fn peak_reserved(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Returns true if the query must be admitted before it starts
+    pub fn requires_admission(&self) -> bool {
+        self.admission_queue.is_some()
+    }
+
+    /// Waits until the query is admitted by the [`MemoryAdmissionQueue`], if
+    /// any. The query requests its memory limit from the queue, or the whole
+    /// capacity of the queue if it has no limit.
+    ///
+    /// This can be called by every partition of the query: the query is
+    /// only admitted once, and stays admitted until this pool is dropped.
+    pub async fn admit(&self) -> Result<()> {
+        let Some(admission_queue) = &self.admission_queue else {
+            return Ok(());
+        };
+        let mut permit = self.permit.lock().await;
+        if permit.is_none() {
+            let memory = self.limit.unwrap_or(usize::MAX);
+            *permit = Some(admission_queue.admit(memory).await?);
+        }
+        Ok(())
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.parent.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.parent.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.parent.grow(reservation, additional);
+        let used = self.used.fetch_add(additional, Ordering::Relaxed) + additional;
+        self.peak.fetch_max(used, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.parent.shrink(reservation, shrink);
+        self.used.fetch_sub(shrink, Ordering::Relaxed);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let used = self
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(additional)
+                    .filter(|new_used| *new_used <= limit)
+            })
+            .map_err(|used| {
+                resources_datafusion_err!("Failed to allocate additional {} bytes for {} with {} bytes already allocated for this reservation - {} bytes remain available for the query", additional, reservation.registration.consumer.name, reservation.size, limit.saturating_sub(used))
+            })?;
+
+        if let Err(e) = self.parent.try_grow(reservation, additional) {
+            self.used.fetch_sub(additional, Ordering::Relaxed);
+            return Err(e);
+        }
+        self.peak.fetch_max(used + additional, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+}
+
 fn provide_top_memory_consumers_to_error_msg(
     error_msg: String,
     top_consumers: String,
@@ -686,4 +822,74 @@ mod tests {
             res
         );
     }
+
+    #[test]
+    fn test_query_pool() {
+        let parent = Arc::new(GreedyMemoryPool::new(100)) as Arc<dyn MemoryPool>;
+        let q1 = Arc::new(QueryMemoryPool::new(Arc::clone(&parent), Some(60))) as _;
+        let q2 = Arc::new(QueryMemoryPool::new(Arc::clone(&parent), None)) as _;
+
+        let mut r1 = MemoryConsumer::new("r1").register(&q1);
+        r1.try_grow(50).unwrap();
+        // limited by the query
+        let err = r1.try_grow(20).unwrap_err().strip_backtrace();
+        assert_eq!(err, "Resources exhausted: Failed to allocate additional 20 bytes for r1 with 50 bytes already allocated for this reservation - 10 bytes remain available for the query");
+        assert_eq!(q1.reserved(), 50);
+
+        let mut r2 = MemoryConsumer::new("r2").register(&q2);
+        r2.try_grow(40).unwrap();
+        // limited by the parent
+        let err = r2.try_grow(20).unwrap_err().strip_backtrace();
+        assert_eq!(err, "Resources exhausted: Failed to allocate additional 20 bytes for r2 with 40 bytes already allocated for this reservation - 10 bytes remain available for the total pool");
+        assert_eq!(q2.reserved(), 40);
+        assert_eq!(parent.reserved(), 90);
+
+        r1.shrink(30);
+        r1.grow(100);
+        assert_eq!(q1.reserved(), 120);
+        drop(r1);
+        drop(r2);
+        assert_eq!(q1.reserved(), 0);
+        assert_eq!(parent.reserved(), 0);
+    }
+
+    #[test]
+    fn test_query_pool_peak() {
+        let parent = Arc::new(UnboundedMemoryPool::default()) as _;
+        let query = Arc::new(QueryMemoryPool::new(parent, Some(100)));
+        let pool = Arc::clone(&query) as Arc<dyn MemoryPool>;
+
+        let mut r1 = MemoryConsumer::new("r1").register(&pool);
+        let mut r2 = MemoryConsumer::new("r2").register(&pool);
+        r1.try_grow(40).unwrap();
+        r2.try_grow(30).unwrap();
+        r1.free();
+        r2.try_grow(20).unwrap();
+        assert_eq!(query.reserved(), 50);
+        assert_eq!(query.peak_reserved(), 70);
+    }
+
+    #[test]
+    fn test_query_pool_admission() {
+        use futures::executor::block_on;
+        use futures::FutureExt;
+
+        let parent = Arc::new(GreedyMemoryPool::new(100)) as Arc<dyn MemoryPool>;
+        let queue = Arc::new(MemoryAdmissionQueue::new(100));
+        let q1 = QueryMemoryPool::new(Arc::clone(&parent), Some(80))
+            .with_admission_queue(Arc::clone(&queue));
+        let q2 = QueryMemoryPool::new(Arc::clone(&parent), Some(40))
+            .with_admission_queue(Arc::clone(&queue));
+
+        block_on(q1.admit()).unwrap();
+        // admitting again is a no-op
+        block_on(q1.admit()).unwrap();
+        assert_eq!(queue.admitted(), 80);
+
+        let mut admit = Box::pin(q2.admit());
+        assert!((&mut admit).now_or_never().is_none());
+        drop(q1);
+        admit.now_or_never().unwrap().unwrap();
+        assert_eq!(queue.admitted(), 40);
+    }
 }
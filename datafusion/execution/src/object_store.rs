@@ -19,11 +19,21 @@
 //! This allows the user to extend DataFusion with different storage systems such as S3 or HDFS
 //! and query data inside these systems.
 
+use crate::cache::byte_range_cache::ByteRangeCache;
+use async_trait::async_trait;
+use bytes::Bytes;
 use dashmap::DashMap;
 use datafusion_common::{exec_err, DataFusionError, Result};
+use futures::stream::BoxStream;
+use futures::StreamExt;
 #[cfg(not(target_arch = "wasm32"))]
 use object_store::local::LocalFileSystem;
-use object_store::ObjectStore;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult,
+};
+use std::ops::Range;
 use std::sync::Arc;
 use url::Url;
 
@@ -243,6 +253,285 @@ impl ObjectStoreRegistry for DefaultObjectStoreRegistry {
     }
 }
 
+/// An [`ObjectStoreRegistry`] wrapping the stores of another registry in
+/// [`CachingObjectStore`]s sharing the same [`ByteRangeCache`]
+///
+/// The local file system (`file://`) is not cached, as reading from it is
+/// as fast as reading from the cache.
+pub struct CachingObjectStoreRegistry {
+    inner: Arc<dyn ObjectStoreRegistry>,
+    cache: Arc<ByteRangeCache>,
+}
+
+impl CachingObjectStoreRegistry {
+    /// Caches the byte ranges read from the stores of `inner` in `cache`
+    pub fn new(inner: Arc<dyn ObjectStoreRegistry>, cache: Arc<ByteRangeCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Returns the wrapped registry
+    pub fn inner(&self) -> &Arc<dyn ObjectStoreRegistry> {
+        &self.inner
+    }
+
+    /// Returns the cache of this registry
+    pub fn cache(&self) -> &Arc<ByteRangeCache> {
+        &self.cache
+    }
+}
+
+impl std::fmt::Debug for CachingObjectStoreRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CachingObjectStoreRegistry")
+            .field("inner", &self.inner)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+impl ObjectStoreRegistry for CachingObjectStoreRegistry {
+    fn register_store(
+        &self,
+        url: &Url,
+        store: Arc<dyn ObjectStore>,
+    ) -> Option<Arc<dyn ObjectStore>> {
+        self.inner.register_store(url, store)
+    }
+
+    fn get_store(&self, url: &Url) -> Result<Arc<dyn ObjectStore>> {
+        let store = self.inner.get_store(url)?;
+        if url.scheme() == "file" {
+            return Ok(store);
+        }
+        Ok(Arc::new(CachingObjectStore::new(
+            store,
+            Arc::clone(&self.cache),
+            get_url_key(url),
+        )))
+    }
+}
+
+/// An [`ObjectStore`] caching the byte ranges read from another store in a
+/// [`ByteRangeCache`]
+///
+/// Only [`ObjectStore::get_range`] and [`ObjectStore::get_ranges`], used to
+/// read e.g. Parquet column chunks, are served from the cache, and only for
+/// objects whose metadata has been returned by this store, e.g. when listing
+/// the files of a table. The cached ranges of an object are dropped when its
+/// metadata (e tag, version, modification time or size) changes, or when it
+/// is written, copied, renamed or deleted through this store.
+#[derive(Debug)]
+pub struct CachingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    cache: Arc<ByteRangeCache>,
+    /// Distinguishes the objects of this store from those of other stores
+    /// sharing the same cache
+    store_key: String,
+}
+
+impl CachingObjectStore {
+    /// Caches the byte ranges read from `inner` in `cache`, under `store_key`
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        cache: Arc<ByteRangeCache>,
+        store_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            inner,
+            cache,
+            store_key: store_key.into(),
+        }
+    }
+
+    /// Returns the wrapped store
+    pub fn inner(&self) -> &Arc<dyn ObjectStore> {
+        &self.inner
+    }
+
+    fn invalidate(&self, location: &Path) {
+        self.cache.invalidate(&self.store_key, location)
+    }
+
+    fn validate(&self, meta: &ObjectMeta) {
+        self.cache.validate(&self.store_key, meta)
+    }
+}
+
+impl std::fmt::Display for CachingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CachingObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CachingObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let result = self.inner.put_opts(location, payload, opts).await;
+        self.invalidate(location);
+        result
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.invalidate(location);
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let result = self.inner.get_opts(location, options).await?;
+        self.validate(&result.meta);
+        Ok(result)
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> object_store::Result<Bytes> {
+        if let Some(bytes) = self.cache.get(&self.store_key, location, &range) {
+            return Ok(bytes);
+        }
+        let bytes = self.inner.get_range(location, range.clone()).await?;
+        self.cache
+            .put(&self.store_key, location, range, bytes.clone());
+        Ok(bytes)
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let mut result = ranges
+            .iter()
+            .map(|range| self.cache.get(&self.store_key, location, range))
+            .collect::<Vec<_>>();
+        let missing = ranges
+            .iter()
+            .zip(&result)
+            .filter(|(_, bytes)| bytes.is_none())
+            .map(|(range, _)| range.clone())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let mut fetched =
+                self.inner.get_ranges(location, &missing).await?.into_iter();
+            for (range, bytes) in ranges.iter().zip(result.iter_mut()) {
+                if bytes.is_none() {
+                    let data =
+                        fetched.next().ok_or_else(|| object_store::Error::Generic {
+                            store: "CachingObjectStore",
+                            source: "missing range in response".into(),
+                        })?;
+                    self.cache.put(
+                        &self.store_key,
+                        location,
+                        range.clone(),
+                        data.clone(),
+                    );
+                    *bytes = Some(data);
+                }
+            }
+        }
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        let meta = self.inner.head(location).await?;
+        self.validate(&meta);
+        Ok(meta)
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        let result = self.inner.delete(location).await;
+        self.invalidate(location);
+        result
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+        self.inner
+            .list(prefix)
+            .inspect(|meta| {
+                if let Ok(meta) = meta {
+                    self.validate(meta)
+                }
+            })
+            .boxed()
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+        self.inner
+            .list_with_offset(prefix, offset)
+            .inspect(|meta| {
+                if let Ok(meta) = meta {
+                    self.validate(meta)
+                }
+            })
+            .boxed()
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        let result = self.inner.list_with_delimiter(prefix).await?;
+        result.objects.iter().for_each(|meta| self.validate(meta));
+        Ok(result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        let result = self.inner.copy(from, to).await;
+        self.invalidate(to);
+        result
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        let result = self.inner.rename(from, to).await;
+        self.invalidate(from);
+        self.invalidate(to);
+        result
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        let result = self.inner.copy_if_not_exists(from, to).await;
+        self.invalidate(to);
+        result
+    }
+
+    async fn rename_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        let result = self.inner.rename_if_not_exists(from, to).await;
+        self.invalidate(from);
+        self.invalidate(to);
+        result
+    }
+}
+
 /// Get the key of a url for object store registration.
 /// The credential info will be removed
 fn get_url_key(url: &Url) -> String {
@@ -256,6 +545,10 @@ fn get_url_key(url: &Url) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::byte_range_cache::ByteRangeCacheConfig;
+    use crate::disk_manager::{DiskManager, DiskManagerConfig};
+    use futures::executor::block_on;
+    use object_store::memory::InMemory;
 
     #[test]
     fn test_object_store_url() {
@@ -299,4 +592,60 @@ mod tests {
         let key = get_url_key(&url.url);
         assert_eq!(key.as_str(), "s3://host:123");
     }
+
+    #[test]
+    fn test_caching_object_store() {
+        let cache = Arc::new(ByteRangeCache::new(
+            ByteRangeCacheConfig::new(1024),
+            DiskManager::try_new(DiskManagerConfig::Disabled).unwrap(),
+        ));
+        let registry = CachingObjectStoreRegistry::new(
+            Arc::new(DefaultObjectStoreRegistry::new()),
+            Arc::clone(&cache),
+        );
+        let url = Url::parse("memory://bucket").unwrap();
+        let inner = Arc::new(InMemory::new());
+        registry.register_store(&url, Arc::clone(&inner) as _);
+        let store = registry.get_store(&url).unwrap();
+
+        let location = Path::from("file.parquet");
+        let data = Bytes::from_static(b"0123456789");
+        block_on(store.put(&location, data.clone().into())).unwrap();
+
+        // the version of the object is unknown until its metadata is read
+        block_on(store.get_range(&location, 2..8)).unwrap();
+        assert_eq!(cache.stats().memory_entries, 0);
+        block_on(store.head(&location)).unwrap();
+
+        let bytes = block_on(store.get_range(&location, 2..8)).unwrap();
+        assert_eq!(bytes, data.slice(2..8));
+        let bytes = block_on(store.get_ranges(&location, &[3..5, 0..2])).unwrap();
+        assert_eq!(bytes, vec![data.slice(3..5), data.slice(0..2)]);
+        let stats = cache.stats();
+        assert_eq!((stats.memory_hits, stats.misses), (1, 3));
+        assert_eq!(stats.memory_entries, 2);
+
+        // overwriting the object invalidates its cached ranges
+        let data = Bytes::from_static(b"abcdefghij");
+        block_on(store.put(&location, data.clone().into())).unwrap();
+        assert_eq!(cache.stats().memory_entries, 0);
+        let bytes = block_on(store.get_range(&location, 2..8)).unwrap();
+        assert_eq!(bytes, data.slice(2..8));
+
+        // as does listing it after it changed in the underlying store
+        block_on(store.list(None).collect::<Vec<_>>());
+        block_on(store.get_range(&location, 2..8)).unwrap();
+        assert_eq!(cache.stats().memory_entries, 1);
+        let data = Bytes::from_static(b"ABCDEFGHIJ");
+        block_on(inner.put(&location, data.clone().into())).unwrap();
+        block_on(store.list(None).collect::<Vec<_>>());
+        assert_eq!(cache.stats().memory_entries, 0);
+        let bytes = block_on(store.get_range(&location, 2..8)).unwrap();
+        assert_eq!(bytes, data.slice(2..8));
+
+        // the local file system is not cached
+        let file = Url::parse("file:///").unwrap();
+        let store = registry.get_store(&file).unwrap();
+        assert!(store.to_string().starts_with("LocalFileSystem"));
+    }
 }
@@ -19,11 +19,15 @@
 //! store, memory manager, disk manager.
 
 use crate::{
+    cache::byte_range_cache::{ByteRangeCache, ByteRangeCacheConfig},
     disk_manager::{DiskManager, DiskManagerConfig},
     memory_pool::{
-        GreedyMemoryPool, MemoryPool, TrackConsumersPool, UnboundedMemoryPool,
+        GreedyMemoryPool, MemoryAdmissionQueue, MemoryPool, TrackConsumersPool,
+        UnboundedMemoryPool,
+    },
+    object_store::{
+        CachingObjectStoreRegistry, DefaultObjectStoreRegistry, ObjectStoreRegistry,
     },
-    object_store::{DefaultObjectStoreRegistry, ObjectStoreRegistry},
 };
 
 use crate::cache::cache_manager::{CacheManager, CacheManagerConfig};
@@ -57,6 +61,8 @@ pub struct RuntimeEnv {
     pub cache_manager: Arc<CacheManager>,
     /// Object Store Registry
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// Delays starting queries until their memory is available, if set
+    pub memory_admission_queue: Option<Arc<MemoryAdmissionQueue>>,
 }
 
 impl Debug for RuntimeEnv {
@@ -72,22 +78,7 @@ impl RuntimeEnv {
     }
     /// Create env based on configuration
     pub fn try_new(config: RuntimeConfig) -> Result<Self> {
-        let RuntimeConfig {
-            memory_pool,
-            disk_manager,
-            cache_manager,
-            object_store_registry,
-        } = config;
-
-        let memory_pool =
-            memory_pool.unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
-
-        Ok(Self {
-            memory_pool,
-            disk_manager: DiskManager::try_new(disk_manager)?,
-            cache_manager: CacheManager::try_new(&cache_manager)?,
-            object_store_registry,
-        })
+        config.build()
     }
 
     /// Registers a custom `ObjectStore` to be used with a specific url.
@@ -172,6 +163,15 @@ pub struct RuntimeEnvBuilder {
     pub cache_manager: CacheManagerConfig,
     /// ObjectStoreRegistry to get object store based on url
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// Cache byte ranges read from object stores in memory and on local disk
+    ///
+    /// Defaults to no caching if `None`
+    pub object_store_cache: Option<ByteRangeCacheConfig>,
+    /// [`MemoryAdmissionQueue`] delaying the start of queries until their
+    /// memory is available
+    ///
+    /// Defaults to starting queries immediately if `None`
+    pub memory_admission_queue: Option<Arc<MemoryAdmissionQueue>>,
 }
 
 impl Default for RuntimeEnvBuilder {
@@ -188,6 +188,8 @@ impl RuntimeEnvBuilder {
             memory_pool: Default::default(),
             cache_manager: Default::default(),
             object_store_registry: Arc::new(DefaultObjectStoreRegistry::default()),
+            object_store_cache: None,
+            memory_admission_queue: None,
         }
     }
 
@@ -218,6 +220,30 @@ impl RuntimeEnvBuilder {
         self
     }
 
+    /// Cache the byte ranges read from object stores, e.g. Parquet column
+    /// chunks, so that repeated queries do not fetch them again.
+    ///
+    /// Up to `config.memory_limit` bytes are cached in memory, and the
+    /// ranges evicted from memory are moved to up to `config.disk_limit`
+    /// bytes of temporary files created by the [`DiskManager`].
+    ///
+    /// The statistics of the cache are available via
+    /// [`CacheManager::get_object_store_cache`].
+    pub fn with_object_store_cache(mut self, config: ByteRangeCacheConfig) -> Self {
+        self.object_store_cache = Some(config);
+        self
+    }
+
+    /// Delay starting queries while the memory limits of the running queries
+    /// (see [`query_memory_limit`]) sum up to more than `capacity` bytes,
+    /// typically the size of the memory pool.
+    ///
+    /// [`query_memory_limit`]: datafusion_common::config::ExecutionOptions::query_memory_limit
+    pub fn with_memory_admission(mut self, capacity: usize) -> Self {
+        self.memory_admission_queue = Some(Arc::new(MemoryAdmissionQueue::new(capacity)));
+        self
+    }
+
     /// Specify the total memory to use while running the DataFusion
     /// plan to `max_memory * memory_fraction` in bytes.
     ///
@@ -243,11 +269,28 @@ impl RuntimeEnvBuilder {
             .memory_pool
             .unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
 
+        let disk_manager = DiskManager::try_new(self.disk_manager)?;
+
+        let mut cache_manager = self.cache_manager;
+        if let Some(config) = self.object_store_cache {
+            let cache = ByteRangeCache::new(config, Arc::clone(&disk_manager));
+            cache_manager = cache_manager.with_object_store_cache(Some(Arc::new(cache)));
+        }
+        let object_store_registry: Arc<dyn ObjectStoreRegistry> =
+            match &cache_manager.object_store_cache {
+                Some(cache) => Arc::new(CachingObjectStoreRegistry::new(
+                    self.object_store_registry,
+                    Arc::clone(cache),
+                )),
+                None => self.object_store_registry,
+            };
+
         Ok(RuntimeEnv {
             memory_pool,
-            disk_manager: DiskManager::try_new(self.disk_manager)?,
-            cache_manager: CacheManager::try_new(&self.cache_manager)?,
-            object_store_registry: self.object_store_registry,
+            disk_manager,
+            cache_manager: CacheManager::try_new(&cache_manager)?,
+            object_store_registry,
+            memory_admission_queue: self.memory_admission_queue,
         })
     }
 
@@ -22,7 +22,7 @@ use std::{
 
 use crate::{
     config::SessionConfig,
-    memory_pool::MemoryPool,
+    memory_pool::{MemoryPool, QueryMemoryPool},
     registry::FunctionRegistry,
     runtime_env::{RuntimeEnv, RuntimeEnvBuilder},
 };
@@ -53,6 +53,8 @@ pub struct TaskContext {
     window_functions: HashMap<String, Arc<WindowUDF>>,
     /// Runtime environment associated with this task context
     runtime: Arc<RuntimeEnv>,
+    /// Memory pool of the query, if its memory is tracked separately
+    query_memory_pool: Option<Arc<QueryMemoryPool>>,
 }
 
 impl Default for TaskContext {
@@ -70,6 +72,7 @@ impl Default for TaskContext {
             aggregate_functions: HashMap::new(),
             window_functions: HashMap::new(),
             runtime,
+            query_memory_pool: None,
         }
     }
 }
@@ -97,6 +100,7 @@ impl TaskContext {
             aggregate_functions,
             window_functions,
             runtime,
+            query_memory_pool: None,
         }
     }
 
@@ -120,6 +124,11 @@ impl TaskContext {
         &self.runtime.memory_pool
     }
 
+    /// Return the [`QueryMemoryPool`] of the query of this [TaskContext], if any
+    pub fn query_memory_pool(&self) -> Option<&Arc<QueryMemoryPool>> {
+        self.query_memory_pool.as_ref()
+    }
+
     /// Return the [RuntimeEnv] associated with this [TaskContext]
     pub fn runtime_env(&self) -> Arc<RuntimeEnv> {
         Arc::clone(&self.runtime)
@@ -136,6 +145,19 @@ impl TaskContext {
         self.runtime = runtime;
         self
     }
+
+    /// Track the memory of the query separately in `query_memory_pool`,
+    /// which becomes the [`MemoryPool`] of this [TaskContext]
+    pub fn with_query_memory_pool(
+        mut self,
+        query_memory_pool: Arc<QueryMemoryPool>,
+    ) -> Self {
+        let mut runtime = self.runtime.as_ref().clone();
+        runtime.memory_pool = Arc::clone(&query_memory_pool) as _;
+        self.runtime = Arc::new(runtime);
+        self.query_memory_pool = Some(query_memory_pool);
+        self
+    }
 }
 
 impl FunctionRegistry for TaskContext {
@@ -33,7 +33,7 @@ use crate::{
     WindowUDF,
 };
 
-use arrow::datatypes::{DataType, FieldRef};
+use arrow::datatypes::{DataType, Field, FieldRef};
 use datafusion_common::cse::HashNode;
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
@@ -330,6 +330,9 @@ pub enum Expr {
     OuterReferenceColumn(DataType, Column),
     /// Unnest expression
     Unnest(Unnest),
+    /// A lambda function such as `x -> x + 1`, only valid as the last argument
+    /// of a higher-order function like `array_transform`.
+    Lambda(Lambda),
 }
 
 impl Default for Expr {
@@ -375,6 +378,62 @@ impl Unnest {
     }
 }
 
+/// Lambda function expression, e.g. `x -> x + 1` or `(acc, x) -> acc + x`.
+///
+/// The body refers to the parameters as unqualified columns and can't refer
+/// to any other column. Its parameter types are determined by the
+/// higher-order function it is passed to, see
+/// [`ScalarUDFImpl::lambda_parameters`](crate::ScalarUDFImpl::lambda_parameters).
+///
+/// Lambdas are leaves of the [`TreeNode`] traversal of their enclosing
+/// expression, as their body is evaluated against the lambda parameters
+/// rather than the input schema.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct Lambda {
+    /// The names of the parameters
+    pub params: Vec<String>,
+    /// The body of the lambda
+    pub body: Box<Expr>,
+}
+
+impl Lambda {
+    /// Create a new Lambda expression.
+    pub fn new(params: Vec<String>, body: Expr) -> Self {
+        Self {
+            params,
+            body: Box::new(body),
+        }
+    }
+
+    /// Returns the schema the body is evaluated against, with each parameter
+    /// bound to the matching type of `param_types`.
+    pub fn params_schema(&self, param_types: &[DataType]) -> Result<DFSchema> {
+        if self.params.len() != param_types.len() {
+            return plan_err!(
+                "Lambda {self} has {} parameter(s), but {} are expected",
+                self.params.len(),
+                param_types.len()
+            );
+        }
+        let fields = self
+            .params
+            .iter()
+            .zip(param_types)
+            .map(|(name, data_type)| Field::new(name, data_type.clone(), true))
+            .collect::<Vec<_>>();
+        DFSchema::from_unqualified_fields(fields.into(), Default::default())
+    }
+}
+
+impl Display for Lambda {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.params.as_slice() {
+            [param] => write!(f, "{param} -> {}", self.body),
+            params => write!(f, "({}) -> {}", params.join(", "), self.body),
+        }
+    }
+}
+
 /// Alias expression
 #[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
 pub struct Alias {
@@ -1160,6 +1219,7 @@ impl Expr {
             Expr::WindowFunction { .. } => "WindowFunction",
             Expr::Wildcard { .. } => "Wildcard",
             Expr::Unnest { .. } => "Unnest",
+            Expr::Lambda { .. } => "Lambda",
         }
     }
 
@@ -1651,7 +1711,8 @@ impl Expr {
             | Expr::Wildcard { .. }
             | Expr::WindowFunction(..)
             | Expr::Literal(..)
-            | Expr::Placeholder(..) => false,
+            | Expr::Placeholder(..)
+            | Expr::Lambda(..) => false,
         }
     }
 }
@@ -1807,6 +1868,10 @@ impl HashNode for Expr {
                 column.hash(state);
             }
             Expr::Unnest(Unnest { expr: _expr }) => {}
+            // The body is not a child of the lambda, so it's hashed here
+            Expr::Lambda(lambda) => {
+                lambda.hash(state);
+            }
         };
     }
 }
@@ -2020,6 +2085,12 @@ impl<'a> Display for SchemaDisplay<'a> {
             Expr::Unnest(Unnest { expr }) => {
                 write!(f, "UNNEST({})", SchemaDisplay(expr))
             }
+            Expr::Lambda(Lambda { params, body }) => match params.as_slice() {
+                [param] => write!(f, "{param} -> {}", SchemaDisplay(body)),
+                params => {
+                    write!(f, "({}) -> {}", params.join(", "), SchemaDisplay(body))
+                }
+            },
             Expr::ScalarFunction(ScalarFunction { func, args }) => {
                 match func.schema_name(args) {
                     Ok(name) => {
@@ -2342,6 +2413,7 @@ impl Display for Expr {
             Expr::Unnest(Unnest { expr }) => {
                 write!(f, "UNNEST({expr})")
             }
+            Expr::Lambda(lambda) => write!(f, "{lambda}"),
         }
     }
 }
@@ -18,7 +18,7 @@
 //! Functions for creating logical expressions
 
 use crate::expr::{
-    AggregateFunction, BinaryExpr, Cast, Exists, GroupingSet, InList, InSubquery,
+    AggregateFunction, BinaryExpr, Cast, Exists, GroupingSet, InList, InSubquery, Lambda,
     Placeholder, TryCast, Unnest, WildcardOptions, WindowFunction,
 };
 use crate::function::{
@@ -377,6 +377,18 @@ pub fn unnest(expr: Expr) -> Expr {
     })
 }
 
+/// Create a lambda expression with the given parameter names, for use as the
+/// last argument of a higher-order function.
+///
+/// The body refers to the parameters with unqualified columns, e.g.
+/// `lambda(["x"], col("x") + lit(1))` for `x -> x + 1`.
+pub fn lambda(params: impl IntoIterator<Item = impl Into<String>>, body: Expr) -> Expr {
+    Expr::Lambda(Lambda::new(
+        params.into_iter().map(Into::into).collect(),
+        body,
+    ))
+}
+
 /// Convenience method to create a new user defined scalar function (UDF) with a
 /// specific signature and specific return type.
 ///
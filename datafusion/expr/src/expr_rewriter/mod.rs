@@ -315,6 +315,7 @@ impl NamePreserver {
                     | LogicalPlan::TableScan(_)
                     | LogicalPlan::Limit(_)
                     | LogicalPlan::Execute(_)
+                    | LogicalPlan::Dml(_)
             ),
         }
     }
@@ -24,7 +24,9 @@ use crate::type_coercion::binary::get_result_type;
 use crate::type_coercion::functions::{
     data_types_with_aggregate_udf, data_types_with_scalar_udf, data_types_with_window_udf,
 };
-use crate::{utils, LogicalPlan, Projection, Subquery, WindowFunctionDefinition};
+use crate::{
+    utils, LogicalPlan, Projection, ScalarUDF, Subquery, WindowFunctionDefinition,
+};
 use arrow::compute::can_cast_types;
 use arrow::datatypes::{DataType, Field};
 use datafusion_common::{
@@ -145,10 +147,7 @@ impl ExprSchemable for Expr {
                 }
             }
             Expr::ScalarFunction(ScalarFunction { func, args }) => {
-                let arg_data_types = args
-                    .iter()
-                    .map(|e| e.get_type(schema))
-                    .collect::<Result<Vec<_>>>()?;
+                let arg_data_types = scalar_function_arg_types(func, args, schema)?;
 
                 // Verify that function is invoked with correct number and type of arguments as defined in `TypeSignature`
                 let new_data_types = data_types_with_scalar_udf(&arg_data_types, func)
@@ -222,6 +221,9 @@ impl ExprSchemable for Expr {
                 })
             }
             Expr::Wildcard { .. } => Ok(DataType::Null),
+            Expr::Lambda(lambda) => plan_err!(
+                "Lambda {lambda} can only be used as an argument of a higher-order function"
+            ),
             Expr::GroupingSet(_) => {
                 // Grouping sets do not really have a type and do not appear in projections
                 Ok(DataType::Null)
@@ -311,7 +313,8 @@ impl ExprSchemable for Expr {
             Expr::ScalarVariable(_, _)
             | Expr::TryCast { .. }
             | Expr::Unnest(_)
-            | Expr::Placeholder(_) => Ok(true),
+            | Expr::Placeholder(_)
+            | Expr::Lambda(_) => Ok(true),
             Expr::IsNull(_)
             | Expr::IsNotNull(_)
             | Expr::IsTrue(_)
@@ -565,6 +568,30 @@ pub fn cast_subquery(subquery: Subquery, cast_to_type: &DataType) -> Result<Subq
     })
 }
 
+/// Returns the types of the arguments of a call to the scalar function `func`.
+///
+/// The type of a [`Lambda`](crate::expr::Lambda) argument is the type of its body, with the lambda
+/// parameters bound to the types [`ScalarUDF::lambda_parameters`] returns for
+/// the preceding arguments.
+pub fn scalar_function_arg_types(
+    func: &ScalarUDF,
+    args: &[Expr],
+    schema: &dyn ExprSchema,
+) -> Result<Vec<DataType>> {
+    let mut arg_types = Vec::with_capacity(args.len());
+    for arg in args {
+        let arg_type = match arg {
+            Expr::Lambda(lambda) => {
+                let param_types = func.lambda_parameters(&arg_types)?;
+                lambda.body.get_type(&lambda.params_schema(&param_types)?)?
+            }
+            _ => arg.get_type(schema)?,
+        };
+        arg_types.push(arg_type);
+    }
+    Ok(arg_types)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,7 +24,7 @@ use std::iter::once;
 use std::sync::Arc;
 
 use crate::dml::CopyTo;
-use crate::expr::{Alias, Sort as SortExpr};
+use crate::expr::{AggregateFunction, Alias, BinaryExpr, Sort as SortExpr};
 use crate::expr_rewriter::{
     coerce_plan_expr_for_schema, normalize_col,
     normalize_col_with_schemas_and_ambiguity_check, normalize_cols, normalize_sorts,
@@ -33,16 +33,16 @@ use crate::expr_rewriter::{
 use crate::logical_plan::{
     Aggregate, Analyze, Distinct, DistinctOn, EmptyRelation, Explain, Filter, Join,
     JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Projection, Repartition, Sample, SampleMethod, Sort, SubqueryAlias, TableScan, Union,
+    Unnest, Values, Window,
 };
 use crate::utils::{
     can_hash, columnize_expr, compare_sort_expr, expr_to_columns,
-    find_valid_equijoin_key_pair, group_window_expr_by_sort_keys,
+    find_valid_equijoin_key_pair, group_window_expr_by_sort_keys, COUNT_STAR_EXPANSION,
 };
 use crate::{
-    and, binary_expr, lit, DmlStatement, Expr, ExprSchemable, Operator, RecursiveQuery,
-    TableProviderFilterPushDown, TableSource, WriteOp,
+    and, binary_expr, lit, when, DmlStatement, Expr, ExprSchemable, Operator,
+    RecursiveQuery, TableProviderFilterPushDown, TableSource, WriteOp,
 };
 
 use super::dml::InsertOp;
@@ -533,6 +533,24 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Return a random sample of the rows
+    ///
+    /// `fraction` - The probability of a row, or of a block of rows for
+    ///            [`SampleMethod::System`], to be returned, between 0 and 1.
+    ///
+    /// `seed` - Seed of the random number generator, to return the same rows
+    ///          every time the plan runs, if specified.
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        Sample::try_new(self.plan, method, fraction, seed)
+            .map(LogicalPlan::Sample)
+            .map(Self::new)
+    }
+
     /// Apply an alias
     pub fn alias(self, alias: impl Into<TableReference>) -> Result<Self> {
         subquery_alias(Arc::unwrap_or_clone(self.plan), alias).map(Self::new)
@@ -1096,6 +1114,188 @@ impl LogicalPlanBuilder {
             .map(Self::new)
     }
 
+    /// Apply a pivot, which turns the rows of the `pivot_column` values listed
+    /// in `pivot_values` into columns.
+    ///
+    /// The input is grouped by all of its columns except `pivot_column` and the
+    /// columns referenced by `aggr_expr`, and every aggregate function is
+    /// computed once for each pivot value, on the rows of the group that have
+    /// that value. For example, pivoting `sum(amount)` for `quarter` in
+    /// `('Q1', 'Q2')` is planned as
+    ///
+    /// ```text
+    /// Projection: other columns, Q1, Q2
+    ///   Aggregate: groupBy=[[other columns]], aggr=[[
+    ///     sum(CASE WHEN quarter = 'Q1' THEN amount END) AS Q1,
+    ///     sum(CASE WHEN quarter = 'Q2' THEN amount END) AS Q2
+    ///   ]]
+    /// ```
+    ///
+    /// The pivoted columns are named by the pivot value, or its alias if
+    /// `pivot_values` contains an [`Expr::Alias`]. An aliased aggregate
+    /// function adds its alias as a prefix, so `sum(amount) AS total` produces
+    /// the columns `total_Q1` and `total_Q2`. If `default_on_null` is set, it
+    /// replaces the null results of the aggregate functions.
+    pub fn pivot(
+        self,
+        aggr_expr: Vec<Expr>,
+        pivot_column: impl Into<Column>,
+        pivot_values: Vec<Expr>,
+        default_on_null: Option<Expr>,
+    ) -> Result<Self> {
+        if aggr_expr.is_empty() || pivot_values.is_empty() {
+            return plan_err!(
+                "PIVOT requires at least one aggregate function and one value"
+            );
+        }
+        let pivot_column = Self::normalize(&self.plan, pivot_column)?;
+        let aggr_expr = normalize_cols(aggr_expr, &self.plan)?
+            .into_iter()
+            .map(|expr| match expr {
+                Expr::Alias(Alias { expr, name, .. }) => Ok((Some(name), *expr)),
+                expr => Ok((None, expr)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if aggr_expr.len() > 1 && aggr_expr.iter().any(|(name, _)| name.is_none()) {
+            return plan_err!(
+                "PIVOT with multiple aggregate functions requires an alias for each of them"
+            );
+        }
+
+        // Group by every column that is neither pivoted nor aggregated
+        let mut used_columns = HashSet::from([pivot_column.clone()]);
+        for (_, expr) in &aggr_expr {
+            expr_to_columns(expr, &mut used_columns)?;
+        }
+        let group_expr = self
+            .plan
+            .schema()
+            .columns()
+            .into_iter()
+            .filter(|column| !used_columns.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut pivoted_expr = vec![];
+        for value in pivot_values {
+            let (value_name, value) = match value {
+                Expr::Alias(Alias { expr, name, .. }) => (name, *expr),
+                Expr::Literal(scalar) => (scalar.to_string(), Expr::Literal(scalar)),
+                value => {
+                    let name = value.schema_name().to_string();
+                    (name, value)
+                }
+            };
+            let condition = Expr::Column(pivot_column.clone()).eq(value);
+            for (aggr_name, expr) in &aggr_expr {
+                let Expr::AggregateFunction(aggr) = expr else {
+                    return plan_err!("PIVOT expects aggregate functions, got {expr}");
+                };
+                let args = aggr
+                    .args
+                    .iter()
+                    .map(|arg| {
+                        let arg = match arg {
+                            Expr::Wildcard { .. } => lit(COUNT_STAR_EXPANSION),
+                            arg => arg.clone(),
+                        };
+                        when(condition.clone(), arg).end()
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let name = match aggr_name {
+                    Some(aggr_name) => format!("{aggr_name}_{value_name}"),
+                    None => value_name.clone(),
+                };
+                let expr = Expr::AggregateFunction(AggregateFunction {
+                    args,
+                    ..aggr.clone()
+                });
+                pivoted_expr.push((expr, name));
+            }
+        }
+
+        let names = pivoted_expr
+            .iter()
+            .map(|(_, name)| name.clone())
+            .collect::<Vec<_>>();
+        let plan = self.aggregate(
+            group_expr.clone(),
+            pivoted_expr
+                .into_iter()
+                .map(|(expr, name)| expr.alias(name)),
+        )?;
+        // Project the pivoted columns, replacing their nulls if requested. The
+        // projection also keeps a wildcard over the pivot from being expanded
+        // against the input of the aggregate.
+        let pivoted = names
+            .into_iter()
+            .map(|name| {
+                let column = Expr::Column(Column::from_name(&name));
+                match &default_on_null {
+                    Some(default) => when(column.clone().is_null(), default.clone())
+                        .otherwise(column)
+                        .map(|expr| expr.alias(name)),
+                    None => Ok(column),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        plan.project(group_expr.into_iter().chain(pivoted))
+    }
+
+    /// Apply an unpivot, which turns the `columns` of the input into rows.
+    ///
+    /// For every input row and every column in `columns`, a row is produced
+    /// with the other columns of the input, the name of the column in a new
+    /// `name_column` and its value in a new `value_column`. Rows with null
+    /// values are skipped, unless `include_nulls` is true. The unpivot is
+    /// planned as a union of one projection per unpivoted column.
+    pub fn unpivot(
+        self,
+        value_column: impl Into<String>,
+        name_column: impl Into<String>,
+        columns: Vec<impl Into<Column>>,
+        include_nulls: bool,
+    ) -> Result<Self> {
+        let value_column = value_column.into();
+        let name_column = name_column.into();
+        let columns = columns
+            .into_iter()
+            .map(|column| Self::normalize(&self.plan, column))
+            .collect::<Result<Vec<_>>>()?;
+        if columns.is_empty() {
+            return plan_err!("UNPIVOT requires at least one column");
+        }
+
+        let kept = self
+            .plan
+            .schema()
+            .columns()
+            .into_iter()
+            .filter(|column| !columns.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        let inputs = columns
+            .into_iter()
+            .map(|column| {
+                let mut plan = Self::new_from_arc(Arc::clone(&self.plan));
+                if !include_nulls {
+                    plan = plan.filter(Expr::Column(column.clone()).is_not_null())?;
+                }
+                let plan = plan.project(kept.iter().cloned().chain([
+                    lit(column.name.clone()).alias(&name_column),
+                    Expr::Column(column).alias(&value_column),
+                ]))?;
+                Ok(plan.plan)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if inputs.len() == 1 {
+            return Ok(Self::new_from_arc(inputs.into_iter().next().unwrap()));
+        }
+        Union::try_new_with_loose_types(inputs)
+            .map(LogicalPlan::Union)
+            .map(Self::new)
+    }
+
     /// Create an expression to represent the explanation of the plan
     ///
     /// if `analyze` is true, runs the actual plan and produces
@@ -1268,6 +1468,39 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply an ASOF join, which joins every row of the existing input with the
+    /// nearest row of the right input that has the same `equi_exprs` keys and
+    /// satisfies `match_condition`, or with nulls if there is no such row.
+    ///
+    /// `match_condition` must compare an expression of the existing input with
+    /// an expression of the right input using `>=`, `>`, `<=` or `<`. For
+    /// example `trades.ts >= quotes.ts` matches every trade with the latest
+    /// quote at or before it.
+    pub fn join_asof(
+        self,
+        right: LogicalPlan,
+        equi_exprs: (Vec<impl Into<Expr>>, Vec<impl Into<Expr>>),
+        match_condition: Expr,
+    ) -> Result<Self> {
+        if !matches!(
+            &match_condition,
+            Expr::BinaryExpr(BinaryExpr {
+                op: Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq,
+                ..
+            })
+        ) {
+            return plan_err!(
+                "ASOF join match condition must be a comparison using >=, >, <= or <, got {match_condition}"
+            );
+        }
+        let match_condition = normalize_col_with_schemas_and_ambiguity_check(
+            match_condition,
+            &[&[self.schema(), right.schema()]],
+            &[],
+        )?;
+        self.join_with_expr_keys(right, JoinType::AsOf, equi_exprs, Some(match_condition))
+    }
+
     /// Unnest the given column.
     pub fn unnest_column(self, column: impl Into<Column>) -> Result<Self> {
         unnest(Arc::unwrap_or_clone(self.plan), vec![column.into()]).map(Self::new)
@@ -1378,7 +1611,7 @@ pub fn build_join_schema(
                 .collect::<Vec<_>>();
             left_fields.into_iter().chain(right_fields).collect()
         }
-        JoinType::Left => {
+        JoinType::Left | JoinType::AsOf => {
             // left then right, right set to nullable in case of not matched scenario
             let left_fields = left_fields
                 .map(|(q, f)| (q.cloned(), Arc::clone(f)))
@@ -1774,6 +2007,16 @@ pub fn get_unnested_columns(
     Ok(qualified_columns)
 }
 
+/// Return the element index column that follows the unnested list column
+/// `col_name` when [`UnnestOptions::ordinality`] is set.
+/// For example, given a list column named "a" returns "a_ordinality"
+pub fn get_unnested_ordinality_column(col_name: &str) -> (Column, Arc<Field>) {
+    let name = format!("{col_name}_ordinality");
+    // Padded elements and preserved null lists have no position
+    let field = Arc::new(Field::new(&name, DataType::Int64, true));
+    (Column::from_name(name), field)
+}
+
 /// Create a [`LogicalPlan::Unnest`] plan with options
 /// This function receive a list of columns to be unnested
 /// because multiple unnest can be performed on the same column (e.g unnest with different depth)
@@ -1854,16 +2097,24 @@ pub fn unnest_with_options(
                                     depth: r.depth,
                                 },
                             ));
-                            Ok(get_unnested_columns(
+                            let mut columns = get_unnested_columns(
                                 &r.output_column.name,
                                 original_field.data_type(),
                                 r.depth,
-                            )?
-                            .into_iter()
-                            .next()
-                            .unwrap()) // because unnesting a list column always result into one result
+                            )?;
+                            // because unnesting a list column always result into one result
+                            columns.truncate(1);
+                            if options.ordinality {
+                                columns.push(get_unnested_ordinality_column(
+                                    &r.output_column.name,
+                                ));
+                            }
+                            Ok(columns)
                         })
-                        .collect::<Result<Vec<(Column, Arc<Field>)>>>()?;
+                        .collect::<Result<Vec<_>>>()?
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<(Column, Arc<Field>)>>();
                     if transformed_columns.is_empty() {
                         transformed_columns = get_unnested_columns(
                             &column_to_unnest.name,
@@ -1886,6 +2137,13 @@ pub fn unnest_with_options(
                                         depth: 1,
                                     },
                                 ));
+                                if options.ordinality {
+                                    transformed_columns.push(
+                                        get_unnested_ordinality_column(
+                                            &column_to_unnest.name,
+                                        ),
+                                    );
+                                }
                             }
                             _ => {}
                         };
@@ -38,6 +38,8 @@ pub enum DdlStatement {
     CreateMemoryTable(CreateMemoryTable),
     /// Creates a new view.
     CreateView(CreateView),
+    /// Creates a new materialized view.
+    CreateMaterializedView(CreateMaterializedView),
     /// Creates a new catalog schema.
     CreateCatalogSchema(CreateCatalogSchema),
     /// Creates a new catalog (aka "Database").
@@ -54,6 +56,10 @@ pub enum DdlStatement {
     CreateFunction(CreateFunction),
     /// Drop function statement
     DropFunction(DropFunction),
+    /// Recomputes the contents of a materialized view.
+    RefreshMaterializedView(RefreshMaterializedView),
+    /// Computes and caches the statistics of a table.
+    AnalyzeTable(AnalyzeTable),
 }
 
 impl DdlStatement {
@@ -64,7 +70,11 @@ impl DdlStatement {
                 schema
             }
             DdlStatement::CreateMemoryTable(CreateMemoryTable { input, .. })
-            | DdlStatement::CreateView(CreateView { input, .. }) => input.schema(),
+            | DdlStatement::CreateView(CreateView { input, .. })
+            | DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                input,
+                ..
+            }) => input.schema(),
             DdlStatement::CreateCatalogSchema(CreateCatalogSchema { schema, .. }) => {
                 schema
             }
@@ -75,6 +85,11 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(DropCatalogSchema { schema, .. }) => schema,
             DdlStatement::CreateFunction(CreateFunction { schema, .. }) => schema,
             DdlStatement::DropFunction(DropFunction { schema, .. }) => schema,
+            DdlStatement::RefreshMaterializedView(RefreshMaterializedView {
+                schema,
+                ..
+            }) => schema,
+            DdlStatement::AnalyzeTable(AnalyzeTable { schema, .. }) => schema,
         }
     }
 
@@ -85,6 +100,7 @@ impl DdlStatement {
             DdlStatement::CreateExternalTable(_) => "CreateExternalTable",
             DdlStatement::CreateMemoryTable(_) => "CreateMemoryTable",
             DdlStatement::CreateView(_) => "CreateView",
+            DdlStatement::CreateMaterializedView(_) => "CreateMaterializedView",
             DdlStatement::CreateCatalogSchema(_) => "CreateCatalogSchema",
             DdlStatement::CreateCatalog(_) => "CreateCatalog",
             DdlStatement::CreateIndex(_) => "CreateIndex",
@@ -93,6 +109,8 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(_) => "DropCatalogSchema",
             DdlStatement::CreateFunction(_) => "CreateFunction",
             DdlStatement::DropFunction(_) => "DropFunction",
+            DdlStatement::RefreshMaterializedView(_) => "RefreshMaterializedView",
+            DdlStatement::AnalyzeTable(_) => "AnalyzeTable",
         }
     }
 
@@ -106,12 +124,18 @@ impl DdlStatement {
                 vec![input]
             }
             DdlStatement::CreateView(CreateView { input, .. }) => vec![input],
+            DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                input,
+                ..
+            }) => vec![input],
             DdlStatement::CreateIndex(_) => vec![],
             DdlStatement::DropTable(_) => vec![],
             DdlStatement::DropView(_) => vec![],
             DdlStatement::DropCatalogSchema(_) => vec![],
             DdlStatement::CreateFunction(_) => vec![],
             DdlStatement::DropFunction(_) => vec![],
+            DdlStatement::RefreshMaterializedView(_) => vec![],
+            DdlStatement::AnalyzeTable(_) => vec![],
         }
     }
 
@@ -142,6 +166,12 @@ impl DdlStatement {
                     DdlStatement::CreateView(CreateView { name, .. }) => {
                         write!(f, "CreateView: {name:?}")
                     }
+                    DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                        name,
+                        ..
+                    }) => {
+                        write!(f, "CreateMaterializedView: {name:?}")
+                    }
                     DdlStatement::CreateCatalogSchema(CreateCatalogSchema {
                         schema_name,
                         ..
@@ -180,6 +210,21 @@ impl DdlStatement {
                     DdlStatement::DropFunction(DropFunction { name, .. }) => {
                         write!(f, "CreateFunction: name {name:?}")
                     }
+                    DdlStatement::RefreshMaterializedView(RefreshMaterializedView {
+                        name,
+                        incremental,
+                        ..
+                    }) => {
+                        write!(
+                            f,
+                            "RefreshMaterializedView: {name:?} incremental:={incremental}"
+                        )
+                    }
+                    DdlStatement::AnalyzeTable(AnalyzeTable {
+                        name, columns, ..
+                    }) => {
+                        write!(f, "AnalyzeTable: {name:?} columns:={columns:?}")
+                    }
                 }
             }
         }
@@ -319,6 +364,26 @@ pub struct CreateView {
     pub temporary: bool,
 }
 
+/// Creates a materialized view, whose results are computed when it is
+/// created and stored until it is refreshed.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+pub struct CreateMaterializedView {
+    /// The view name
+    pub name: TableReference,
+    /// The logical plan
+    pub input: Arc<LogicalPlan>,
+    /// Option to replace the view if it already exists
+    pub or_replace: bool,
+    /// Option to not error if the view already exists
+    pub if_not_exists: bool,
+    /// SQL used to create the view, if available
+    pub definition: Option<String>,
+    /// The location to store the results at, or `None` to keep them in memory
+    pub location: Option<String>,
+    /// The file type of the stored results, used if `location` is set
+    pub file_type: String,
+}
+
 /// Creates a catalog (aka "Database").
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateCatalog {
@@ -403,6 +468,51 @@ impl PartialOrd for DropView {
     }
 }
 
+/// Recomputes the stored results of a materialized view.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefreshMaterializedView {
+    /// The view name
+    pub name: TableReference,
+    /// Whether to only add the rows computed from the rows appended to the
+    /// sources of the view since its last refresh
+    pub incremental: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for RefreshMaterializedView {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.name.partial_cmp(&other.name) {
+            Some(Ordering::Equal) => self.incremental.partial_cmp(&other.incremental),
+            cmp => cmp,
+        }
+    }
+}
+
+/// Computes the statistics of a table, including the histograms and most
+/// common values of its columns, and caches them for planning.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnalyzeTable {
+    /// The table name
+    pub name: TableReference,
+    /// The columns to compute histograms and most common values for, all
+    /// columns if empty
+    pub columns: Vec<String>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for AnalyzeTable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.name.partial_cmp(&other.name) {
+            Some(Ordering::Equal) => self.columns.partial_cmp(&other.columns),
+            cmp => cmp,
+        }
+    }
+}
+
 /// Drops a schema
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DropCatalogSchema {
@@ -22,7 +22,7 @@ use std::fmt;
 use crate::{
     expr_vec_fmt, Aggregate, DescribeTable, Distinct, DistinctOn, DmlStatement, Execute,
     Expr, Filter, Join, Limit, LogicalPlan, Partitioning, Prepare, Projection,
-    RecursiveQuery, Repartition, Sort, Subquery, SubqueryAlias,
+    RecursiveQuery, Repartition, Sample, Sort, Subquery, SubqueryAlias,
     TableProviderFilterPushDown, TableScan, Unnest, Values, Window,
 };
 
@@ -554,6 +554,22 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 };
                 object
             }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                let mut object = json!({
+                    "Node Type": "Sample",
+                    "Method": method.to_string(),
+                    "Fraction": fraction,
+                });
+                if let Some(seed) = seed {
+                    object["Seed"] = (*seed).into()
+                };
+                object
+            }
             LogicalPlan::Subquery(Subquery { .. }) => {
                 json!({
                     "Node Type": "Subquery"
@@ -23,9 +23,13 @@ use std::sync::Arc;
 
 use arrow::datatypes::{DataType, Field, Schema};
 use datafusion_common::file_options::file_type::FileType;
-use datafusion_common::{DFSchemaRef, TableReference};
+use datafusion_common::tree_node::{Transformed, TreeNodeIterator, TreeNodeRecursion};
+use datafusion_common::{
+    internal_datafusion_err, internal_err, map_until_stop_and_collect, DFSchemaRef,
+    DataFusionError, Result, TableReference,
+};
 
-use crate::LogicalPlan;
+use crate::{Expr, LogicalPlan};
 
 /// Operator that copies the contents of a database to file(s)
 #[derive(Clone)]
@@ -150,6 +154,7 @@ pub enum WriteOp {
     Delete,
     Update,
     Ctas,
+    Merge(Box<MergeOp>),
 }
 
 impl WriteOp {
@@ -160,6 +165,7 @@ impl WriteOp {
             WriteOp::Delete => "Delete",
             WriteOp::Update => "Update",
             WriteOp::Ctas => "Ctas",
+            WriteOp::Merge(_) => "Merge",
         }
     }
 }
@@ -170,6 +176,215 @@ impl Display for WriteOp {
     }
 }
 
+/// The join condition and the clauses of a `MERGE` statement.
+///
+/// The expressions refer to the columns of the target table, qualified by
+/// its alias if it has one, and to the columns of the source relation, which
+/// is the input of the [`DmlStatement`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct MergeOp {
+    /// Matches the rows of the target table with the rows of the source
+    pub on: Expr,
+    /// The `WHEN` clauses in the order they were written. Each row is
+    /// changed by the first clause that applies to it
+    pub clauses: Vec<MergeClause>,
+}
+
+impl MergeOp {
+    /// Return the expressions of this [`MergeOp`]: the join condition
+    /// followed by the predicate and the values of each clause, in order
+    pub fn expressions(&self) -> Vec<&Expr> {
+        let mut exprs = vec![&self.on];
+        for clause in &self.clauses {
+            exprs.extend(clause.predicate.iter());
+            match &clause.action {
+                MergeAction::Update(assignments) => {
+                    exprs.extend(assignments.iter().map(|(_, value)| value))
+                }
+                MergeAction::Delete => {}
+                MergeAction::Insert(values) => exprs.extend(values.iter()),
+            }
+        }
+        exprs
+    }
+
+    /// Rewrites the expressions of this [`MergeOp`] using `f`, in the order
+    /// returned by [`Self::expressions`]
+    pub fn map_expressions<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        mut f: F,
+    ) -> Result<Transformed<Self>> {
+        let MergeOp { on, clauses } = self;
+        Ok(map_until_stop_and_collect!(
+            f(on),
+            clauses,
+            clauses
+                .into_iter()
+                .map_until_stop_and_collect(|clause| clause.map_expressions(&mut f))
+        )?
+        .update_data(|(on, clauses)| MergeOp { on, clauses }))
+    }
+
+    /// Create a new [`MergeOp`] with its expressions replaced by `exprs`,
+    /// which must be in the order returned by [`Self::expressions`]
+    pub fn with_new_exprs(&self, exprs: Vec<Expr>) -> Result<Self> {
+        let mut exprs = exprs.into_iter();
+        let merge = self
+            .clone()
+            .map_expressions(|_| {
+                exprs.next().map(Transformed::yes).ok_or_else(|| {
+                    internal_datafusion_err!("Not enough expressions for MERGE")
+                })
+            })?
+            .data;
+        if exprs.next().is_some() {
+            return internal_err!("Too many expressions for MERGE");
+        }
+        Ok(merge)
+    }
+}
+
+impl Display for MergeOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "on=[{}] clauses=[", self.on)?;
+        for (i, clause) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{clause}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// A `WHEN ... THEN ...` clause of a `MERGE` statement
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct MergeClause {
+    /// The rows the clause applies to
+    pub kind: MergeClauseKind,
+    /// An additional condition the rows must satisfy, if any
+    pub predicate: Option<Expr>,
+    /// What to do with the rows
+    pub action: MergeAction,
+}
+
+impl MergeClause {
+    fn map_expressions<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        mut f: F,
+    ) -> Result<Transformed<Self>> {
+        let MergeClause {
+            kind,
+            predicate,
+            action,
+        } = self;
+        Ok(map_until_stop_and_collect!(
+            predicate.map_or(Ok::<_, DataFusionError>(Transformed::no(None)), |e| {
+                Ok(f(e)?.update_data(Some))
+            }),
+            action,
+            action.map_expressions(&mut f)
+        )?
+        .update_data(|(predicate, action)| MergeClause {
+            kind,
+            predicate,
+            action,
+        }))
+    }
+}
+
+impl Display for MergeClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "WHEN {}", self.kind)?;
+        if let Some(predicate) = &self.predicate {
+            write!(f, " AND {predicate}")?;
+        }
+        write!(f, " THEN {}", self.action)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum MergeClauseKind {
+    /// Target rows matched by at least one source row. This corresponds to
+    /// `WHEN MATCHED`.
+    Matched,
+    /// Source rows that don't match any target row. This corresponds to
+    /// `WHEN NOT MATCHED [BY TARGET]`.
+    NotMatchedByTarget,
+    /// Target rows that aren't matched by any source row. This corresponds
+    /// to `WHEN NOT MATCHED BY SOURCE`.
+    NotMatchedBySource,
+}
+
+impl Display for MergeClauseKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeClauseKind::Matched => write!(f, "MATCHED"),
+            MergeClauseKind::NotMatchedByTarget => write!(f, "NOT MATCHED BY TARGET"),
+            MergeClauseKind::NotMatchedBySource => write!(f, "NOT MATCHED BY SOURCE"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum MergeAction {
+    /// Sets the named columns of the target row to new values
+    Update(Vec<(String, Expr)>),
+    /// Deletes the target row
+    Delete,
+    /// Inserts a new row, with one value for each column of the target table
+    Insert(Vec<Expr>),
+}
+
+impl MergeAction {
+    fn map_expressions<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        mut f: F,
+    ) -> Result<Transformed<Self>> {
+        Ok(match self {
+            MergeAction::Update(assignments) => assignments
+                .into_iter()
+                .map_until_stop_and_collect(|(column, value)| {
+                    Ok(f(value)?.update_data(|value| (column, value)))
+                })?
+                .update_data(MergeAction::Update),
+            MergeAction::Delete => Transformed::no(MergeAction::Delete),
+            MergeAction::Insert(values) => values
+                .into_iter()
+                .map_until_stop_and_collect(f)?
+                .update_data(MergeAction::Insert),
+        })
+    }
+}
+
+impl Display for MergeAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeAction::Update(assignments) => {
+                write!(f, "UPDATE SET ")?;
+                for (i, (column, value)) in assignments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{column} = {value}")?;
+                }
+                Ok(())
+            }
+            MergeAction::Delete => write!(f, "DELETE"),
+            MergeAction::Insert(values) => {
+                write!(f, "INSERT VALUES (")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub enum InsertOp {
     /// Appends new rows to the existing table without modifying any
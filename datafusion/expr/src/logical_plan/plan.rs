@@ -32,16 +32,16 @@ use crate::expr_rewriter::{
 };
 use crate::logical_plan::display::{GraphvizVisitor, IndentVisitor};
 use crate::logical_plan::extension::UserDefinedLogicalNode;
-use crate::logical_plan::{DmlStatement, Statement};
+use crate::logical_plan::{DmlStatement, Statement, WriteOp};
 use crate::utils::{
     enumerate_grouping_sets, exprlist_len, exprlist_to_fields, find_base_plan,
     find_out_reference_exprs, grouping_set_expr_count, grouping_set_to_exprlist,
     split_conjunction,
 };
 use crate::{
-    build_join_schema, expr_vec_fmt, BinaryExpr, CreateMemoryTable, CreateView, Expr,
-    ExprSchemable, LogicalPlanBuilder, Operator, TableProviderFilterPushDown,
-    TableSource, WindowFunctionDefinition,
+    build_join_schema, expr_vec_fmt, BinaryExpr, CreateMaterializedView,
+    CreateMemoryTable, CreateView, Expr, ExprSchemable, LogicalPlanBuilder, Operator,
+    TableProviderFilterPushDown, TableSource, WindowFunctionDefinition,
 };
 
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
@@ -243,6 +243,9 @@ pub enum LogicalPlan {
     SubqueryAlias(SubqueryAlias),
     /// Skip some number of rows, and then fetch some number of rows.
     Limit(Limit),
+    /// Returns a random sample of the rows of its input. This is used to
+    /// implement SQL `TABLESAMPLE`.
+    Sample(Sample),
     /// A DataFusion [`Statement`] such as `SET VARIABLE` or `START TRANSACTION`
     Statement(Statement),
     /// Values expression. See
@@ -312,6 +315,7 @@ impl LogicalPlan {
             LogicalPlan::Join(Join { schema, .. }) => schema,
             LogicalPlan::Repartition(Repartition { input, .. }) => input.schema(),
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
+            LogicalPlan::Sample(Sample { input, .. }) => input.schema(),
             LogicalPlan::Statement(statement) => statement.schema(),
             LogicalPlan::Subquery(Subquery { subquery, .. }) => subquery.schema(),
             LogicalPlan::SubqueryAlias(SubqueryAlias { schema, .. }) => schema,
@@ -434,6 +438,7 @@ impl LogicalPlan {
             LogicalPlan::Sort(Sort { input, .. }) => vec![input],
             LogicalPlan::Join(Join { left, right, .. }) => vec![left, right],
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
+            LogicalPlan::Sample(Sample { input, .. }) => vec![input],
             LogicalPlan::Subquery(Subquery { subquery, .. }) => vec![subquery],
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => vec![input],
             LogicalPlan::Extension(extension) => extension.node.inputs(),
@@ -521,6 +526,7 @@ impl LogicalPlan {
             | LogicalPlan::Distinct(Distinct::All(input))
             | LogicalPlan::Sort(Sort { input, .. })
             | LogicalPlan::Limit(Limit { input, .. })
+            | LogicalPlan::Sample(Sample { input, .. })
             | LogicalPlan::Repartition(Repartition { input, .. })
             | LogicalPlan::Window(Window { input, .. }) => input.head_output_expr(),
             LogicalPlan::Join(Join {
@@ -529,7 +535,11 @@ impl LogicalPlan {
                 join_type,
                 ..
             }) => match join_type {
-                JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
+                JoinType::Inner
+                | JoinType::Left
+                | JoinType::Right
+                | JoinType::Full
+                | JoinType::AsOf => {
                     if left.schema().fields().is_empty() {
                         right.head_output_expr()
                     } else {
@@ -674,6 +684,7 @@ impl LogicalPlan {
                 schema: _,
             }) => SubqueryAlias::try_new(input, alias).map(LogicalPlan::SubqueryAlias),
             LogicalPlan::Limit(_) => Ok(self),
+            LogicalPlan::Sample(_) => Ok(self),
             LogicalPlan::Ddl(_) => Ok(self),
             LogicalPlan::Extension(Extension { node }) => {
                 // todo make an API that does not require cloning
@@ -775,12 +786,20 @@ impl LogicalPlan {
                 op,
                 ..
             }) => {
-                self.assert_no_expressions(expr)?;
+                let op = match op {
+                    WriteOp::Merge(merge) => {
+                        WriteOp::Merge(Box::new(merge.with_new_exprs(expr)?))
+                    }
+                    _ => {
+                        self.assert_no_expressions(expr)?;
+                        op.clone()
+                    }
+                };
                 let input = self.only_input(inputs)?;
                 Ok(LogicalPlan::Dml(DmlStatement::new(
                     table_name.clone(),
                     Arc::clone(table_schema),
-                    op.clone(),
+                    op,
                     Arc::new(input),
                 )))
             }
@@ -951,6 +970,17 @@ impl LogicalPlan {
                     input: Arc::new(input),
                 }))
             }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Sample::try_new(Arc::new(input), *method, *fraction, *seed)
+                    .map(LogicalPlan::Sample)
+            }
             LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(CreateMemoryTable {
                 name,
                 if_not_exists,
@@ -990,6 +1020,31 @@ impl LogicalPlan {
                     definition: definition.clone(),
                 })))
             }
+            LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                CreateMaterializedView {
+                    name,
+                    or_replace,
+                    if_not_exists,
+                    definition,
+                    location,
+                    file_type,
+                    ..
+                },
+            )) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Ok(LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                    CreateMaterializedView {
+                        input: Arc::new(input),
+                        name: name.clone(),
+                        or_replace: *or_replace,
+                        if_not_exists: *if_not_exists,
+                        definition: definition.clone(),
+                        location: location.clone(),
+                        file_type: file_type.clone(),
+                    },
+                )))
+            }
             LogicalPlan::Extension(e) => Ok(LogicalPlan::Extension(Extension {
                 node: e.node.with_exprs_and_inputs(expr, inputs)?,
             })),
@@ -1304,9 +1359,10 @@ impl LogicalPlan {
                         _ => None,
                     }
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
-                    left.max_rows()
-                }
+                JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::LeftMark
+                | JoinType::AsOf => left.max_rows(),
                 JoinType::RightSemi | JoinType::RightAnti => right.max_rows(),
             },
             LogicalPlan::Repartition(Repartition { input, .. }) => input.max_rows(),
@@ -1330,6 +1386,7 @@ impl LogicalPlan {
                 Ok(FetchType::Literal(s)) => s,
                 _ => None,
             },
+            LogicalPlan::Sample(Sample { input, .. }) => input.max_rows(),
             LogicalPlan::Distinct(
                 Distinct::All(input) | Distinct::On(DistinctOn { input, .. }),
             ) => input.max_rows(),
@@ -1773,6 +1830,13 @@ impl LogicalPlan {
                         }
                         Ok(())
                     }
+                    LogicalPlan::Dml(DmlStatement {
+                        table_name,
+                        op: WriteOp::Merge(merge),
+                        ..
+                    }) => {
+                        write!(f, "Dml: op=[Merge] table=[{table_name}] {merge}")
+                    }
                     LogicalPlan::Dml(DmlStatement { table_name, op, .. }) => {
                         write!(f, "Dml: op=[{op}] table=[{table_name}]")
                     }
@@ -1913,6 +1977,7 @@ impl LogicalPlan {
                             "Limit: skip={}, fetch={}", skip_str,fetch_str,
                         )
                     }
+                    LogicalPlan::Sample(sample) => write!(f, "{sample}"),
                     LogicalPlan::Subquery(Subquery { .. }) => {
                         write!(f, "Subquery:")
                     }
@@ -1955,7 +2020,8 @@ impl LogicalPlan {
                     LogicalPlan::Unnest(Unnest {
                         input: plan,
                         list_type_columns: list_col_indices,
-                        struct_type_columns: struct_col_indices, .. }) => {
+                        struct_type_columns: struct_col_indices,
+                        options, .. }) => {
                         let input_columns = plan.schema().columns();
                         let list_type_columns = list_col_indices
                             .iter()
@@ -1970,7 +2036,11 @@ impl LogicalPlan {
                         // get items from input_columns indexed by list_col_indices
                         write!(f, "Unnest: lists[{}] structs[{}]",
                         expr_vec_fmt!(list_type_columns),
-                        expr_vec_fmt!(struct_type_columns))
+                        expr_vec_fmt!(struct_type_columns))?;
+                        if options.ordinality {
+                            write!(f, " ordinality")?;
+                        }
+                        Ok(())
                     }
                 }
             }
@@ -2881,6 +2951,120 @@ pub struct Limit {
     pub input: Arc<LogicalPlan>,
 }
 
+/// Returns a random sample of the rows of its input.
+///
+/// `fraction` is the probability of every row, or block of rows depending on
+/// the [`SampleMethod`], to be part of the sample.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// How the rows are chosen
+    pub method: SampleMethod,
+    /// The probability of a row or block of rows to be chosen, between 0 and 1
+    pub fraction: f64,
+    /// Seed of the random number generator, so that the same rows are chosen
+    /// every time the plan runs. The sample is random if `None`
+    pub seed: Option<u64>,
+    /// The logical plan
+    pub input: Arc<LogicalPlan>,
+}
+
+impl Sample {
+    /// Create a new sample of `input`, returning an error if `fraction` isn't
+    /// between 0 and 1
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!("Sample fraction must be between 0 and 1, got {fraction}");
+        }
+        Ok(Self {
+            method,
+            fraction,
+            seed,
+            input,
+        })
+    }
+}
+
+impl Display for Sample {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Sample: method={}, fraction={}",
+            self.method, self.fraction
+        )?;
+        if let Some(seed) = self.seed {
+            write!(f, ", seed={seed}")?;
+        }
+        Ok(())
+    }
+}
+
+// Manual implementations as f64 is neither `Eq` nor `Hash`, comparing the bit
+// patterns of the fractions
+impl PartialEq for Sample {
+    fn eq(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.fraction.to_bits() == other.fraction.to_bits()
+            && self.seed == other.seed
+            && self.input == other.input
+    }
+}
+
+impl Eq for Sample {}
+
+impl Hash for Sample {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.method.hash(state);
+        self.fraction.to_bits().hash(state);
+        self.seed.hash(state);
+        self.input.hash(state);
+    }
+}
+
+impl PartialOrd for Sample {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.method.partial_cmp(&other.method) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        match self.fraction.partial_cmp(&other.fraction) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        match self.seed.partial_cmp(&other.seed) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        self.input.partial_cmp(&other.input)
+    }
+}
+
+/// How a [`Sample`] chooses rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum SampleMethod {
+    /// Every row is chosen independently of the others, like SQL
+    /// `TABLESAMPLE BERNOULLI`
+    Bernoulli,
+    /// Blocks of rows, such as batches or the row groups of a Parquet file,
+    /// are chosen independently of the others, like SQL `TABLESAMPLE SYSTEM`.
+    /// Blocks that aren't chosen may not be read at all, which makes it
+    /// faster but less random than [`SampleMethod::Bernoulli`]
+    System,
+}
+
+impl Display for SampleMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleMethod::Bernoulli => write!(f, "BERNOULLI"),
+            SampleMethod::System => write!(f, "SYSTEM"),
+        }
+    }
+}
+
 /// Different types of skip expression in Limit plan.
 pub enum SkipType {
     /// The skip expression is a literal value.
@@ -37,11 +37,11 @@
 //! * [`LogicalPlan::with_new_exprs`]: Create a new plan with different expressions
 //! * [`LogicalPlan::expressions`]: Return a copy of the plan's expressions
 use crate::{
-    dml::CopyTo, Aggregate, Analyze, CreateMemoryTable, CreateView, DdlStatement,
-    Distinct, DistinctOn, DmlStatement, Execute, Explain, Expr, Extension, Filter, Join,
-    Limit, LogicalPlan, Partitioning, Prepare, Projection, RecursiveQuery, Repartition,
-    Sort, Subquery, SubqueryAlias, TableScan, Union, Unnest, UserDefinedLogicalNode,
-    Values, Window,
+    dml::CopyTo, Aggregate, Analyze, CreateMaterializedView, CreateMemoryTable,
+    CreateView, DdlStatement, Distinct, DistinctOn, DmlStatement, Execute, Explain, Expr,
+    Extension, Filter, Join, Limit, LogicalPlan, Partitioning, Prepare, Projection,
+    RecursiveQuery, Repartition, Sample, Sort, Subquery, SubqueryAlias, TableScan, Union,
+    Unnest, UserDefinedLogicalNode, Values, Window, WriteOp,
 };
 use std::ops::Deref;
 use std::sync::Arc;
@@ -162,6 +162,19 @@ impl TreeNode for LogicalPlan {
             }),
             LogicalPlan::Limit(Limit { skip, fetch, input }) => rewrite_arc(input, f)?
                 .update_data(|input| LogicalPlan::Limit(Limit { skip, fetch, input })),
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                input,
+            }) => rewrite_arc(input, f)?.update_data(|input| {
+                LogicalPlan::Sample(Sample {
+                    method,
+                    fraction,
+                    seed,
+                    input,
+                })
+            }),
             LogicalPlan::Subquery(Subquery {
                 subquery,
                 outer_ref_columns,
@@ -297,6 +310,25 @@ impl TreeNode for LogicalPlan {
                             temporary,
                         })
                     }),
+                    DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                        name,
+                        input,
+                        or_replace,
+                        if_not_exists,
+                        definition,
+                        location,
+                        file_type,
+                    }) => rewrite_arc(input, f)?.update_data(|input| {
+                        DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                            name,
+                            input,
+                            or_replace,
+                            if_not_exists,
+                            definition,
+                            location,
+                            file_type,
+                        })
+                    }),
                     // no inputs in these statements
                     DdlStatement::CreateExternalTable(_)
                     | DdlStatement::CreateCatalogSchema(_)
@@ -306,7 +338,9 @@ impl TreeNode for LogicalPlan {
                     | DdlStatement::DropView(_)
                     | DdlStatement::DropCatalogSchema(_)
                     | DdlStatement::CreateFunction(_)
-                    | DdlStatement::DropFunction(_) => Transformed::no(ddl),
+                    | DdlStatement::DropFunction(_)
+                    | DdlStatement::RefreshMaterializedView(_)
+                    | DdlStatement::AnalyzeTable(_) => Transformed::no(ddl),
                 }
                 .update_data(LogicalPlan::Ddl)
             }
@@ -509,6 +543,10 @@ impl LogicalPlan {
             LogicalPlan::Execute(Execute { parameters, .. }) => {
                 parameters.iter().apply_until_stop(f)
             }
+            LogicalPlan::Dml(DmlStatement {
+                op: WriteOp::Merge(merge),
+                ..
+            }) => merge.expressions().into_iter().apply_until_stop(f),
             // plans without expressions
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::RecursiveQuery(_)
@@ -519,6 +557,7 @@ impl LogicalPlan {
             | LogicalPlan::Explain(_)
             | LogicalPlan::Union(_)
             | LogicalPlan::Distinct(Distinct::All(_))
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
@@ -752,6 +791,21 @@ impl LogicalPlan {
                         schema,
                     })
                 }),
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                table_schema,
+                op: WriteOp::Merge(merge),
+                input,
+                output_schema,
+            }) => merge.map_expressions(f)?.update_data(|merge| {
+                LogicalPlan::Dml(DmlStatement {
+                    table_name,
+                    table_schema,
+                    op: WriteOp::Merge(Box::new(merge)),
+                    input,
+                    output_schema,
+                })
+            }),
             // plans without expressions
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Unnest(_)
@@ -763,6 +817,7 @@ impl LogicalPlan {
             | LogicalPlan::Explain(_)
             | LogicalPlan::Union(_)
             | LogicalPlan::Distinct(Distinct::All(_))
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
@@ -74,7 +74,9 @@ impl TreeNode for Expr {
             | Expr::Exists {..}
             | Expr::ScalarSubquery(_)
             | Expr::Wildcard {..}
-            | Expr::Placeholder (_) => vec![],
+            | Expr::Placeholder (_)
+            // The body of a lambda refers to its parameters, not to the input
+            | Expr::Lambda(_) => vec![],
             Expr::BinaryExpr(BinaryExpr { left, right, .. }) => {
                 vec![left.as_ref(), right.as_ref()]
             }
@@ -147,7 +149,8 @@ impl TreeNode for Expr {
             | Expr::Exists { .. }
             | Expr::ScalarSubquery(_)
             | Expr::ScalarVariable(_, _)
-            | Expr::Literal(_) => Transformed::no(self),
+            | Expr::Literal(_)
+            | Expr::Lambda(_) => Transformed::no(self),
             Expr::Unnest(Unnest { expr, .. }) => transform_box(expr, &mut f)?
                 .update_data(|be| Expr::Unnest(Unnest::new_boxed(be))),
             Expr::Alias(Alias {
@@ -239,6 +239,14 @@ impl AggregateUDF {
         self.inner.order_sensitivity()
     }
 
+    /// Returns true if this is an ordered-set aggregate function, called with a
+    /// `WITHIN GROUP` clause.
+    ///
+    /// See [`AggregateUDFImpl::is_ordered_set_aggregate`] for more details.
+    pub fn is_ordered_set_aggregate(&self) -> bool {
+        self.inner.is_ordered_set_aggregate()
+    }
+
     /// Reserves the `AggregateUDF` (e.g. returns the `AggregateUDF` that will
     /// generate same result with this `AggregateUDF` when iterated in reverse
     /// order, and `None` if there is no such `AggregateUDF`).
@@ -522,6 +530,20 @@ pub trait AggregateUDFImpl: Debug + Send + Sync {
         AggregateOrderSensitivity::HardRequirement
     }
 
+    /// Returns true if this is an ordered-set aggregate function, such as
+    /// `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`.
+    ///
+    /// The expressions of the `WITHIN GROUP` clause are passed to the function
+    /// as its first arguments, followed by the direct arguments, and are also
+    /// its ordering requirement (see [`AccumulatorArgs::ordering_req`]). So the
+    /// example above is called with the arguments `x, 0.5`, ordered by `x`.
+    ///
+    /// Ordered-set aggregates usually sort their values themselves and are
+    /// [`AggregateOrderSensitivity::Insensitive`]. The default is false.
+    fn is_ordered_set_aggregate(&self) -> bool {
+        false
+    }
+
     /// Optionally apply per-UDaF simplification / rewrite rules.
     ///
     /// This can be used to apply function specific simplification rules during
@@ -755,6 +777,10 @@ impl AggregateUDFImpl for AliasedAggregateUDFImpl {
         self.inner.order_sensitivity()
     }
 
+    fn is_ordered_set_aggregate(&self) -> bool {
+        self.inner.is_ordered_set_aggregate()
+    }
+
     fn simplify(&self) -> Option<AggregateFunctionSimplification> {
         self.inner.simplify()
     }
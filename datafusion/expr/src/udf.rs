@@ -23,8 +23,9 @@ use crate::sort_properties::{ExprProperties, SortProperties};
 use crate::{
     ColumnarValue, Documentation, Expr, ScalarFunctionImplementation, Signature,
 };
+use arrow::array::ArrayRef;
 use arrow::datatypes::DataType;
-use datafusion_common::{not_impl_err, ExprSchema, Result};
+use datafusion_common::{not_impl_err, plan_err, ExprSchema, Result};
 use datafusion_expr_common::interval_arithmetic::Interval;
 use std::any::Any;
 use std::cmp::Ordering;
@@ -214,6 +215,23 @@ impl ScalarUDF {
         self.inner.invoke_batch(args, number_rows)
     }
 
+    /// Invoke the higher-order function with `args` and its `lambda` argument.
+    ///
+    /// See [`ScalarUDFImpl::invoke_with_lambda`] for more details.
+    pub fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        self.inner.invoke_with_lambda(args, lambda, number_rows)
+    }
+
+    /// See [`ScalarUDFImpl::lambda_parameters`] for more details.
+    pub fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        self.inner.lambda_parameters(arg_types)
+    }
+
     /// Invoke the function without `args` but number of rows, returning the appropriate result.
     ///
     /// See [`ScalarUDFImpl::invoke_no_args`] for more details.
@@ -524,6 +542,45 @@ pub trait ScalarUDFImpl: Debug + Send + Sync {
         }
     }
 
+    /// Invoke a higher-order function, whose last argument is a [`Lambda`],
+    /// with its other evaluated `args` and the compiled `lambda`.
+    ///
+    /// The function applies the lambda to arrays of values bound to the
+    /// parameters returned by [`Self::lambda_parameters`], typically the
+    /// flattened elements of a list argument, so that the lambda is evaluated
+    /// once per batch rather than once per element.
+    ///
+    /// Defaults to an error for functions that don't accept a lambda.
+    ///
+    /// [`Lambda`]: crate::expr::Lambda
+    fn invoke_with_lambda(
+        &self,
+        _args: &[ColumnarValue],
+        _lambda: &dyn ScalarLambda,
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        not_impl_err!(
+            "Function {} does not implement invoke_with_lambda but called",
+            self.name()
+        )
+    }
+
+    /// Returns the types bound to the parameters of the [`Lambda`] argument of
+    /// a higher-order function, given the types of the arguments preceding
+    /// it. The lambda must be the last argument.
+    ///
+    /// For example `array_transform(list, x -> x + 1)` binds `x` to the
+    /// element type of `list`. The body of the lambda is type checked with
+    /// these types, and its type is passed as the type of the lambda argument
+    /// to [`Self::return_type`] and [`Self::coerce_types`].
+    ///
+    /// Defaults to an error, meaning the function doesn't accept a lambda.
+    ///
+    /// [`Lambda`]: crate::expr::Lambda
+    fn lambda_parameters(&self, _arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        plan_err!("Function {} does not accept a lambda argument", self.name())
+    }
+
     /// Invoke the function without `args`, instead the number of rows are provided,
     /// returning the appropriate result.
     #[deprecated(since = "42.1.0", note = "Use `invoke_batch` instead")]
@@ -692,6 +749,18 @@ pub trait ScalarUDFImpl: Debug + Send + Sync {
     }
 }
 
+/// A compiled [`Lambda`] argument of a higher-order function, see
+/// [`ScalarUDFImpl::invoke_with_lambda`].
+///
+/// [`Lambda`]: crate::expr::Lambda
+pub trait ScalarLambda: Debug + Send + Sync {
+    /// Evaluate the body of the lambda with `args` bound to its parameters.
+    ///
+    /// All `args` must have the same length, which is also the length of the
+    /// returned array.
+    fn evaluate(&self, args: &[ArrayRef]) -> Result<ArrayRef>;
+}
+
 /// ScalarUDF that adds an alias to the underlying function. It is better to
 /// implement [`ScalarUDFImpl`], which supports aliases, directly if possible.
 #[derive(Debug)]
@@ -754,6 +823,19 @@ impl ScalarUDFImpl for AliasedScalarUDFImpl {
         self.inner.invoke(args)
     }
 
+    fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        self.inner.invoke_with_lambda(args, lambda, number_rows)
+    }
+
+    fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        self.inner.lambda_parameters(arg_types)
+    }
+
     fn invoke_no_args(&self, number_rows: usize) -> Result<ColumnarValue> {
         #[allow(deprecated)]
         self.inner.invoke_no_args(number_rows)
@@ -826,6 +908,7 @@ pub mod scalar_doc_sections {
             DOC_SECTION_ARRAY,
             DOC_SECTION_STRUCT,
             DOC_SECTION_MAP,
+            DOC_SECTION_JSON,
             DOC_SECTION_HASHING,
             DOC_SECTION_OTHER,
         ]
@@ -890,6 +973,12 @@ The following regular expression functions are supported:"#,
         description: None,
     };
 
+    pub const DOC_SECTION_JSON: DocSection = DocSection {
+        include: true,
+        label: "JSON Functions",
+        description: None,
+    };
+
     pub const DOC_SECTION_HASHING: DocSection = DocSection {
         include: true,
         label: "Hashing Functions",
@@ -313,7 +313,9 @@ pub fn expr_to_columns(expr: &Expr, accum: &mut HashSet<Column>) -> Result<()> {
             | Expr::ScalarSubquery(_)
             | Expr::Wildcard { .. }
             | Expr::Placeholder(_)
-            | Expr::OuterReferenceColumn { .. } => {}
+            | Expr::OuterReferenceColumn { .. }
+            // The columns of a lambda body are its parameters
+            | Expr::Lambda(_) => {}
         }
         Ok(TreeNodeRecursion::Continue)
     })
@@ -66,6 +66,7 @@ pub mod first_last;
 pub mod hyperloglog;
 pub mod median;
 pub mod min_max;
+pub mod mode;
 pub mod regr;
 pub mod stddev;
 pub mod sum;
@@ -79,6 +80,7 @@ pub mod bit_and_or_xor;
 pub mod bool_and_or;
 pub mod grouping;
 pub mod nth_value;
+pub mod percentile;
 pub mod string_agg;
 
 use crate::approx_percentile_cont::approx_percentile_cont_udaf;
@@ -113,7 +115,10 @@ pub mod expr_fn {
     pub use super::median::median;
     pub use super::min_max::max;
     pub use super::min_max::min;
+    pub use super::mode::mode;
     pub use super::nth_value::nth_value;
+    pub use super::percentile::percentile_cont;
+    pub use super::percentile::percentile_disc;
     pub use super::regr::regr_avgx;
     pub use super::regr::regr_avgy;
     pub use super::regr::regr_count;
@@ -143,6 +148,9 @@ pub fn all_default_aggregate_functions() -> Vec<Arc<AggregateUDF>> {
         min_max::max_udaf(),
         min_max::min_udaf(),
         median::median_udaf(),
+        percentile::percentile_cont_udaf(),
+        percentile::percentile_disc_udaf(),
+        mode::mode_udaf(),
         count::count_udaf(),
         regr::regr_slope_udaf(),
         regr::regr_intercept_udaf(),
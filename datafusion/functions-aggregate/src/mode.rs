@@ -0,0 +1,155 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `mode` ordered-set aggregate function
+
+use std::fmt::{Debug, Formatter};
+use std::sync::OnceLock;
+
+use arrow::datatypes::{DataType, Field};
+
+use datafusion_common::Result;
+use datafusion_expr::aggregate_doc_sections::DOC_SECTION_GENERAL;
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Expr, GroupsAccumulator, Signature,
+    Volatility,
+};
+use datafusion_functions_aggregate_common::order::AggregateOrderSensitivity;
+
+use crate::percentile::{
+    sorted_values_accumulator, sorted_values_groups_accumulator,
+    sorted_values_groups_accumulator_supported, values_state_field, within_group,
+    OrderedSetFunction,
+};
+
+create_func!(Mode, mode_udaf);
+
+/// Computes the most frequent value of `expression`
+pub fn mode(expression: Expr) -> Expr {
+    within_group(mode_udaf().call(vec![expression.clone()]), expression)
+}
+
+/// MODE aggregate expression, returning the most frequent value like
+/// PostgreSQL: if several values are equally frequent, the first one in the
+/// WITHIN GROUP ordering is returned. All the values are stored in memory
+/// until the result is computed.
+pub struct Mode {
+    signature: Signature,
+}
+
+impl Debug for Mode {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Mode")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mode {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for Mode {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "mode"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(vec![values_state_field(
+            args.name,
+            self.name(),
+            &args.input_types[0],
+        )])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        sorted_values_accumulator(OrderedSetFunction::Mode, &acc_args)
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        sorted_values_groups_accumulator_supported(&args)
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        sorted_values_groups_accumulator(OrderedSetFunction::Mode, &args)
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn is_ordered_set_aggregate(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_mode_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_mode_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_GENERAL)
+            .with_description(
+                "Returns the most frequent input value. If several values are equally frequent, returns the first one in the ordering.",
+            )
+            .with_syntax_example("mode() WITHIN GROUP (ORDER BY expression)")
+            .with_sql_example(
+                r#"```sql
+> SELECT mode() WITHIN GROUP (ORDER BY column_name) FROM table_name;
++--------------------------------------------+
+| mode() WITHIN GROUP (ORDER BY column_name) |
++--------------------------------------------+
+| 12                                         |
++--------------------------------------------+
+```"#,
+            )
+            .with_standard_argument("expression", None)
+            .build()
+            .unwrap()
+    })
+}
@@ -0,0 +1,1006 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the exact `percentile_cont` and `percentile_disc` ordered-set
+//! aggregate functions, and the accumulators shared with `mode`
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::mem::{size_of, size_of_val};
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{
+    downcast_primitive, Array, ArrayRef, ArrowNativeTypeOp, ArrowPrimitiveType, AsArray,
+    BooleanArray, ListArray, PrimitiveArray,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer, ScalarBuffer};
+use arrow::datatypes::{DataType, Field, Float64Type};
+
+use datafusion_common::{
+    internal_err, not_impl_datafusion_err, not_impl_err, plan_err, Result, ScalarValue,
+};
+use datafusion_expr::aggregate_doc_sections::DOC_SECTION_GENERAL;
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, EmitTo, Expr, GroupsAccumulator,
+    Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::aggregate::groups_accumulator::accumulate::accumulate;
+use datafusion_functions_aggregate_common::order::AggregateOrderSensitivity;
+
+use crate::approx_percentile_cont::get_scalar_value;
+
+create_func!(PercentileCont, percentile_cont_udaf);
+create_func!(PercentileDisc, percentile_disc_udaf);
+
+/// Computes the exact continuous percentile of `expression`, interpolating
+/// between its values. `percentile` is a fraction or an array of fractions.
+pub fn percentile_cont(expression: Expr, percentile: Expr) -> Expr {
+    within_group(
+        percentile_cont_udaf().call(vec![expression.clone(), percentile]),
+        expression,
+    )
+}
+
+/// Computes the exact discrete percentile of `expression`, the first value
+/// whose position in the ordering is at least `percentile`. `percentile` is
+/// a fraction or an array of fractions.
+pub fn percentile_disc(expression: Expr, percentile: Expr) -> Expr {
+    within_group(
+        percentile_disc_udaf().call(vec![expression.clone(), percentile]),
+        expression,
+    )
+}
+
+/// Adds the `WITHIN GROUP (ORDER BY expression)` clause to an ordered-set
+/// aggregate function
+pub(crate) fn within_group(aggregate: Expr, expression: Expr) -> Expr {
+    match aggregate {
+        Expr::AggregateFunction(mut aggregate) => {
+            aggregate.order_by = Some(vec![expression.sort(true, false)]);
+            Expr::AggregateFunction(aggregate)
+        }
+        expr => expr,
+    }
+}
+
+/// PERCENTILE_CONT aggregate expression, computing the exact continuous
+/// percentile of a set of numbers like PostgreSQL: all the values are
+/// converted to `Float64` and stored in memory until the result is computed.
+pub struct PercentileCont {
+    signature: Signature,
+}
+
+impl Debug for PercentileCont {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PercentileCont")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for PercentileCont {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileCont {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileCont {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_cont"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [expression, percentile] = take_percentile_args(self.name(), arg_types)?;
+        if !expression.is_numeric() {
+            return plan_err!(
+                "{} requires a numeric expression, got {expression}",
+                self.name()
+            );
+        }
+        Ok(vec![
+            DataType::Float64,
+            coerce_percentile(self.name(), percentile)?,
+        ])
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(percentile_return_type(&DataType::Float64, &arg_types[1]))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(vec![values_state_field(
+            args.name,
+            self.name(),
+            &args.input_types[0],
+        )])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let function = OrderedSetFunction::PercentileCont(Percentiles::try_new(
+            self.name(),
+            &acc_args,
+        )?);
+        Ok(Box::new(
+            SortedValuesAccumulator::<Float64Type>::try_new(function, &acc_args)?
+                .with_interpolation(interpolate_f64),
+        ))
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        !args.is_distinct
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        let function =
+            OrderedSetFunction::PercentileCont(Percentiles::try_new(self.name(), &args)?);
+        Ok(Box::new(
+            SortedValuesGroupsAccumulator::<Float64Type>::try_new(function, &args)?
+                .with_interpolation(interpolate_f64),
+        ))
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn is_ordered_set_aggregate(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_percentile_cont_doc())
+    }
+}
+
+static PERCENTILE_CONT_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_percentile_cont_doc() -> &'static Documentation {
+    PERCENTILE_CONT_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_GENERAL)
+            .with_description(
+                "Returns the exact percentile of the input values, interpolating between the two nearest values if needed. \
+                With an array of percentiles, returns an array of the corresponding percentiles.",
+            )
+            .with_syntax_example(
+                "percentile_cont(percentile) WITHIN GROUP (ORDER BY expression)",
+            )
+            .with_sql_example(
+                r#"```sql
+> SELECT percentile_cont(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++-----------------------------------------------------------+
+| percentile_cont(0.75) WITHIN GROUP (ORDER BY column_name) |
++-----------------------------------------------------------+
+| 65.0                                                      |
++-----------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "percentile",
+                "Percentile to compute, a float value between 0 and 1 (inclusive), or an array of such values.",
+            )
+            .with_standard_argument("expression", Some("The numeric"))
+            .build()
+            .unwrap()
+    })
+}
+
+/// PERCENTILE_DISC aggregate expression, computing the exact discrete
+/// percentile of a set of values like PostgreSQL: all the values are stored in
+/// memory until the result is computed.
+pub struct PercentileDisc {
+    signature: Signature,
+}
+
+impl Debug for PercentileDisc {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PercentileDisc")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for PercentileDisc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileDisc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileDisc {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_disc"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [expression, percentile] = take_percentile_args(self.name(), arg_types)?;
+        Ok(vec![
+            expression.clone(),
+            coerce_percentile(self.name(), percentile)?,
+        ])
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(percentile_return_type(&arg_types[0], &arg_types[1]))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(vec![values_state_field(
+            args.name,
+            self.name(),
+            &args.input_types[0],
+        )])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let function = OrderedSetFunction::PercentileDisc(Percentiles::try_new(
+            self.name(),
+            &acc_args,
+        )?);
+        sorted_values_accumulator(function, &acc_args)
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        sorted_values_groups_accumulator_supported(&args)
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        let function =
+            OrderedSetFunction::PercentileDisc(Percentiles::try_new(self.name(), &args)?);
+        sorted_values_groups_accumulator(function, &args)
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn is_ordered_set_aggregate(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_percentile_disc_doc())
+    }
+}
+
+static PERCENTILE_DISC_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_percentile_disc_doc() -> &'static Documentation {
+    PERCENTILE_DISC_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_GENERAL)
+            .with_description(
+                "Returns the first input value whose position in the ordering equals or exceeds the specified percentile. \
+                With an array of percentiles, returns an array of the corresponding values.",
+            )
+            .with_syntax_example(
+                "percentile_disc(percentile) WITHIN GROUP (ORDER BY expression)",
+            )
+            .with_sql_example(
+                r#"```sql
+> SELECT percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++-----------------------------------------------------------+
+| percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) |
++-----------------------------------------------------------+
+| 65                                                        |
++-----------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "percentile",
+                "Percentile to compute, a float value between 0 and 1 (inclusive), or an array of such values.",
+            )
+            .with_standard_argument("expression", None)
+            .build()
+            .unwrap()
+    })
+}
+
+/// Returns the two arguments of a percentile function
+fn take_percentile_args<'a>(
+    name: &str,
+    arg_types: &'a [DataType],
+) -> Result<[&'a DataType; 2]> {
+    match arg_types {
+        [expression, percentile] => Ok([expression, percentile]),
+        _ => plan_err!(
+            "{name} requires a percentile and a WITHIN GROUP (ORDER BY expression) clause, got {} arguments",
+            arg_types.len()
+        ),
+    }
+}
+
+/// Coerces a percentile, or an array of percentiles, to `Float64`
+fn coerce_percentile(name: &str, percentile: &DataType) -> Result<DataType> {
+    match percentile {
+        percentile if percentile.is_numeric() => Ok(DataType::Float64),
+        DataType::List(field)
+        | DataType::LargeList(field)
+        | DataType::FixedSizeList(field, _)
+            if field.data_type().is_numeric() =>
+        {
+            Ok(DataType::new_list(DataType::Float64, true))
+        }
+        _ => plan_err!(
+            "{name} requires a numeric percentile or an array of numeric percentiles, got {percentile}"
+        ),
+    }
+}
+
+/// The return type of a percentile function: the type of the values, or a
+/// list of it if several percentiles are computed
+fn percentile_return_type(value_type: &DataType, percentile: &DataType) -> DataType {
+    match percentile {
+        DataType::List(_) => DataType::new_list(value_type.clone(), true),
+        _ => value_type.clone(),
+    }
+}
+
+/// The state of the accumulators of ordered-set aggregates: the list of all
+/// the non-null values of the group
+pub(crate) fn values_state_field(
+    name: &str,
+    function: &str,
+    value_type: &DataType,
+) -> Field {
+    Field::new(
+        format_state_name(name, function),
+        DataType::new_list(value_type.clone(), true),
+        true,
+    )
+}
+
+/// The percentiles to compute
+#[derive(Debug, Clone)]
+pub(crate) struct Percentiles {
+    percentiles: Vec<f64>,
+    /// Are the percentiles an array, in which case an array is returned
+    is_array: bool,
+}
+
+impl Percentiles {
+    /// Reads the percentiles from the second argument of the function, which
+    /// must be a literal
+    fn try_new(name: &str, args: &AccumulatorArgs) -> Result<Self> {
+        let Some(expr) = args.exprs.get(1) else {
+            return internal_err!("{name} requires a percentile argument");
+        };
+        let percentile = get_scalar_value(expr).map_err(|_| {
+            not_impl_datafusion_err!(
+                "Percentile value for '{name}' must be a literal, got: {expr}"
+            )
+        })?;
+        let (percentiles, is_array) = match percentile {
+            ScalarValue::Float64(Some(percentile)) => (vec![Some(percentile)], false),
+            ScalarValue::List(list) if list.is_valid(0) => (
+                list.value(0).as_primitive::<Float64Type>().iter().collect(),
+                true,
+            ),
+            percentile => {
+                return not_impl_err!(
+                    "Percentile value for '{name}' must be a Float64 literal or an array of Float64 literals, got {percentile}"
+                )
+            }
+        };
+        let percentiles = percentiles
+            .into_iter()
+            .map(|percentile| match percentile {
+                Some(percentile) if (0.0..=1.0).contains(&percentile) => Ok(percentile),
+                Some(percentile) => plan_err!(
+                    "Percentile value must be between 0.0 and 1.0 inclusive, {percentile} is invalid"
+                ),
+                None => plan_err!("Percentile value for '{name}' must not be null"),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            percentiles,
+            is_array,
+        })
+    }
+}
+
+/// How an ordered-set aggregate computes its result from the sorted values
+/// of a group
+#[derive(Debug, Clone)]
+pub(crate) enum OrderedSetFunction {
+    /// Interpolates between the values nearest to the percentiles
+    PercentileCont(Percentiles),
+    /// The first values whose positions are at least the percentiles
+    PercentileDisc(Percentiles),
+    /// The most frequent value, the first one in the ordering in case of ties
+    Mode,
+}
+
+impl OrderedSetFunction {
+    /// Does this function return an array of values
+    fn is_array(&self) -> bool {
+        match self {
+            Self::PercentileCont(percentiles) | Self::PercentileDisc(percentiles) => {
+                percentiles.is_array
+            }
+            Self::Mode => false,
+        }
+    }
+
+    /// Computes the results of this function from the values of a group,
+    /// returning `None` if the group has no values. The values are sorted in
+    /// place.
+    fn evaluate<N: Clone>(
+        &self,
+        values: &mut [N],
+        descending: bool,
+        cmp: impl Fn(&N, &N) -> Ordering,
+        interpolate: Option<Interpolate<N>>,
+    ) -> Result<Option<Vec<N>>> {
+        if values.is_empty() {
+            return Ok(None);
+        }
+        if descending {
+            values.sort_unstable_by(|a, b| cmp(b, a));
+        } else {
+            values.sort_unstable_by(&cmp);
+        }
+
+        let len = values.len();
+        let results = match self {
+            Self::PercentileCont(percentiles) => {
+                let Some(interpolate) = interpolate else {
+                    return internal_err!("percentile_cont requires interpolation");
+                };
+                percentiles
+                    .percentiles
+                    .iter()
+                    .map(|percentile| {
+                        let position = percentile * (len - 1) as f64;
+                        let (lower, upper) = (position.floor(), position.ceil());
+                        interpolate(
+                            &values[lower as usize],
+                            &values[upper as usize],
+                            position - lower,
+                        )
+                    })
+                    .collect()
+            }
+            Self::PercentileDisc(percentiles) => percentiles
+                .percentiles
+                .iter()
+                .map(|percentile| {
+                    let position = (percentile * len as f64).ceil() as usize;
+                    values[position.clamp(1, len) - 1].clone()
+                })
+                .collect(),
+            Self::Mode => {
+                let mut mode = 0;
+                let mut mode_count = 0;
+                let mut start = 0;
+                for end in 1..=len {
+                    if end == len || cmp(&values[start], &values[end]).is_ne() {
+                        if end - start > mode_count {
+                            mode = start;
+                            mode_count = end - start;
+                        }
+                        start = end;
+                    }
+                }
+                vec![values[mode].clone()]
+            }
+        };
+        Ok(Some(results))
+    }
+}
+
+/// Interpolates between two values, `fraction` of the way from the first
+type Interpolate<N> = fn(&N, &N, f64) -> N;
+
+fn interpolate_f64(lower: &f64, upper: &f64, fraction: f64) -> f64 {
+    lower + (upper - lower) * fraction
+}
+
+/// Is the ordering of the WITHIN GROUP clause descending
+fn is_descending(args: &AccumulatorArgs) -> bool {
+    args.ordering_req
+        .first()
+        .is_some_and(|sort| sort.options.descending)
+}
+
+/// Creates the [`Accumulator`] of an ordered-set aggregate computing its
+/// result from any type of values
+pub(crate) fn sorted_values_accumulator(
+    function: OrderedSetFunction,
+    args: &AccumulatorArgs,
+) -> Result<Box<dyn Accumulator>> {
+    if args.is_distinct {
+        return not_impl_err!("DISTINCT is not supported for {}", args.name);
+    }
+    macro_rules! helper {
+        ($t:ty, $function:expr, $args:expr) => {
+            Ok(Box::new(SortedValuesAccumulator::<$t>::try_new(
+                $function, $args,
+            )?))
+        };
+    }
+    let data_type = args.exprs[0].data_type(args.schema)?;
+    downcast_primitive! {
+        data_type => (helper, function, args),
+        _ => Ok(Box::new(SortedScalarValuesAccumulator::try_new(function, args)?)),
+    }
+}
+
+/// Returns true if [`sorted_values_groups_accumulator`] supports the arguments
+pub(crate) fn sorted_values_groups_accumulator_supported(args: &AccumulatorArgs) -> bool {
+    !args.is_distinct
+        && args.exprs[0]
+            .data_type(args.schema)
+            .is_ok_and(|data_type| data_type.is_primitive())
+}
+
+/// Creates the [`GroupsAccumulator`] of an ordered-set aggregate computing its
+/// result from primitive values
+pub(crate) fn sorted_values_groups_accumulator(
+    function: OrderedSetFunction,
+    args: &AccumulatorArgs,
+) -> Result<Box<dyn GroupsAccumulator>> {
+    macro_rules! helper {
+        ($t:ty, $function:expr, $args:expr) => {
+            Ok(Box::new(SortedValuesGroupsAccumulator::<$t>::try_new(
+                $function, $args,
+            )?))
+        };
+    }
+    let data_type = args.exprs[0].data_type(args.schema)?;
+    downcast_primitive! {
+        data_type => (helper, function, args),
+        _ => not_impl_err!(
+            "GroupsAccumulator not supported for {} with {data_type}",
+            args.name
+        ),
+    }
+}
+
+/// Accumulates all the non-null primitive values of a group, and computes the
+/// result of an [`OrderedSetFunction`] from them
+struct SortedValuesAccumulator<T: ArrowPrimitiveType> {
+    function: OrderedSetFunction,
+    data_type: DataType,
+    descending: bool,
+    interpolate: Option<Interpolate<T::Native>>,
+    values: Vec<T::Native>,
+}
+
+impl<T: ArrowPrimitiveType> Debug for SortedValuesAccumulator<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SortedValuesAccumulator({})", self.data_type)
+    }
+}
+
+impl<T: ArrowPrimitiveType> SortedValuesAccumulator<T> {
+    fn try_new(function: OrderedSetFunction, args: &AccumulatorArgs) -> Result<Self> {
+        Ok(Self {
+            function,
+            data_type: args.exprs[0].data_type(args.schema)?,
+            descending: is_descending(args),
+            interpolate: None,
+            values: vec![],
+        })
+    }
+
+    fn with_interpolation(mut self, interpolate: Interpolate<T::Native>) -> Self {
+        self.interpolate = Some(interpolate);
+        self
+    }
+}
+
+impl<T: ArrowPrimitiveType> Accumulator for SortedValuesAccumulator<T> {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let values = PrimitiveArray::<T>::from_iter_values(self.values.iter().copied())
+            .with_data_type(self.data_type.clone());
+        let list = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            OffsetBuffer::from_lengths([values.len()]),
+            Arc::new(values),
+            None,
+        );
+        Ok(vec![ScalarValue::List(Arc::new(list))])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        self.values.reserve(values.len() - values.null_count());
+        self.values.extend(values.iter().flatten());
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = states[0].as_list::<i32>();
+        for values in array.iter().flatten() {
+            self.update_batch(&[values])?
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let results = self.function.evaluate(
+            &mut self.values,
+            self.descending,
+            |a, b| a.compare(*b),
+            self.interpolate,
+        )?;
+        let scalar = |value| ScalarValue::new_primitive::<T>(value, &self.data_type);
+        match (results, self.function.is_array()) {
+            (None, false) => scalar(None),
+            (Some(results), false) => scalar(Some(results[0])),
+            (None, true) => {
+                ScalarValue::try_from(DataType::new_list(self.data_type.clone(), true))
+            }
+            (Some(results), true) => {
+                let results = results
+                    .into_iter()
+                    .map(|value| scalar(Some(value)))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(ScalarValue::List(ScalarValue::new_list_nullable(
+                    &results,
+                    &self.data_type,
+                )))
+            }
+        }
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) + self.values.capacity() * size_of::<T::Native>()
+    }
+}
+
+/// Accumulates all the non-null values of a group as `ScalarValue`s, for the
+/// types that are not supported by [`SortedValuesAccumulator`]
+#[derive(Debug)]
+struct SortedScalarValuesAccumulator {
+    function: OrderedSetFunction,
+    data_type: DataType,
+    descending: bool,
+    values: Vec<ScalarValue>,
+}
+
+impl SortedScalarValuesAccumulator {
+    fn try_new(function: OrderedSetFunction, args: &AccumulatorArgs) -> Result<Self> {
+        if matches!(function, OrderedSetFunction::PercentileCont(_)) {
+            return internal_err!("percentile_cont requires Float64 values");
+        }
+        Ok(Self {
+            function,
+            data_type: args.exprs[0].data_type(args.schema)?,
+            descending: is_descending(args),
+            values: vec![],
+        })
+    }
+}
+
+impl Accumulator for SortedScalarValuesAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![ScalarValue::List(ScalarValue::new_list_nullable(
+            &self.values,
+            &self.data_type,
+        ))])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = &values[0];
+        self.values.reserve(values.len() - values.null_count());
+        for index in 0..values.len() {
+            if values.is_valid(index) {
+                self.values
+                    .push(ScalarValue::try_from_array(values, index)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = states[0].as_list::<i32>();
+        for values in array.iter().flatten() {
+            self.update_batch(&[values])?
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let results = self.function.evaluate(
+            &mut self.values,
+            self.descending,
+            |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal),
+            None,
+        )?;
+        match (results, self.function.is_array()) {
+            (None, false) => ScalarValue::try_from(&self.data_type),
+            (Some(mut results), false) => Ok(results.swap_remove(0)),
+            (None, true) => {
+                ScalarValue::try_from(DataType::new_list(self.data_type.clone(), true))
+            }
+            (Some(results), true) => Ok(ScalarValue::List(
+                ScalarValue::new_list_nullable(&results, &self.data_type),
+            )),
+        }
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) + ScalarValue::size_of_vec(&self.values)
+            - size_of_val(&self.values)
+    }
+}
+
+/// Accumulates all the non-null primitive values of every group, and
+/// computes the result of an [`OrderedSetFunction`] from them
+struct SortedValuesGroupsAccumulator<T: ArrowPrimitiveType + Send> {
+    function: OrderedSetFunction,
+    data_type: DataType,
+    descending: bool,
+    interpolate: Option<Interpolate<T::Native>>,
+    /// The values of every group
+    groups: Vec<Vec<T::Native>>,
+}
+
+impl<T: ArrowPrimitiveType + Send> SortedValuesGroupsAccumulator<T> {
+    fn try_new(function: OrderedSetFunction, args: &AccumulatorArgs) -> Result<Self> {
+        Ok(Self {
+            function,
+            data_type: args.exprs[0].data_type(args.schema)?,
+            descending: is_descending(args),
+            interpolate: None,
+            groups: vec![],
+        })
+    }
+
+    fn with_interpolation(mut self, interpolate: Interpolate<T::Native>) -> Self {
+        self.interpolate = Some(interpolate);
+        self
+    }
+
+    /// Returns a list array of `lists`, null where `lists` is `None`
+    fn list_array(
+        &self,
+        lists: Vec<Option<Vec<T::Native>>>,
+        with_nulls: bool,
+    ) -> ListArray {
+        let nulls =
+            with_nulls.then(|| NullBuffer::from_iter(lists.iter().map(Option::is_some)));
+        let offsets = OffsetBuffer::from_lengths(
+            lists.iter().map(|list| list.as_ref().map_or(0, Vec::len)),
+        );
+        let values = lists.into_iter().flatten().flatten().collect::<Vec<_>>();
+        let values = PrimitiveArray::<T>::new(ScalarBuffer::from(values), None)
+            .with_data_type(self.data_type.clone());
+        ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            offsets,
+            Arc::new(values),
+            nulls,
+        )
+    }
+}
+
+impl<T: ArrowPrimitiveType + Send> GroupsAccumulator
+    for SortedValuesGroupsAccumulator<T>
+{
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        self.groups.resize_with(total_num_groups, Vec::new);
+        accumulate(group_indices, values, opt_filter, |group_index, value| {
+            self.groups[group_index].push(value);
+        });
+        Ok(())
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        let groups = emit_to.take_needed(&mut self.groups);
+        let results = groups
+            .into_iter()
+            .map(|mut values| {
+                self.function.evaluate(
+                    &mut values,
+                    self.descending,
+                    |a, b| a.compare(*b),
+                    self.interpolate,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if self.function.is_array() {
+            return Ok(Arc::new(self.list_array(results, true)));
+        }
+        let values = results
+            .into_iter()
+            .map(|results| results.map(|results| results[0]))
+            .collect::<PrimitiveArray<T>>()
+            .with_data_type(self.data_type.clone());
+        Ok(Arc::new(values))
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        let groups = emit_to.take_needed(&mut self.groups);
+        let lists = groups.into_iter().map(Some).collect();
+        Ok(vec![Arc::new(self.list_array(lists, false))])
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let lists = values[0].as_list::<i32>();
+        self.groups.resize_with(total_num_groups, Vec::new);
+        for (row, &group_index) in group_indices.iter().enumerate() {
+            if lists.is_null(row) || opt_filter.is_some_and(|filter| !filter.value(row)) {
+                continue;
+            }
+            let values = lists.value(row);
+            self.groups[group_index].extend(values.as_primitive::<T>().iter().flatten());
+        }
+        Ok(())
+    }
+
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        let values = values[0].as_primitive::<T>();
+        let lists = (0..values.len())
+            .map(|row| {
+                let is_included = values.is_valid(row)
+                    && opt_filter.map_or(true, |filter| filter.value(row));
+                Some(is_included.then(|| values.value(row)).into_iter().collect())
+            })
+            .collect();
+        Ok(vec![Arc::new(self.list_array(lists, false))])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self.groups.capacity() * size_of::<Vec<T::Native>>()
+            + self
+                .groups
+                .iter()
+                .map(|values| values.capacity() * size_of::<T::Native>())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn percentiles(percentiles: &[f64]) -> Percentiles {
+        Percentiles {
+            percentiles: percentiles.to_vec(),
+            is_array: true,
+        }
+    }
+
+    fn evaluate_f64(
+        function: OrderedSetFunction,
+        mut values: Vec<f64>,
+        descending: bool,
+    ) -> Option<Vec<f64>> {
+        function
+            .evaluate(
+                &mut values,
+                descending,
+                |a, b| a.total_cmp(b),
+                Some(interpolate_f64),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn percentile_cont() {
+        let function =
+            OrderedSetFunction::PercentileCont(percentiles(&[0.0, 0.25, 0.5, 0.75, 1.0]));
+        let values = vec![4.0, 1.0, 3.0, 2.0];
+        assert_eq!(
+            evaluate_f64(function.clone(), values.clone(), false),
+            Some(vec![1.0, 1.75, 2.5, 3.25, 4.0])
+        );
+        assert_eq!(
+            evaluate_f64(function.clone(), values, true),
+            Some(vec![4.0, 3.25, 2.5, 1.75, 1.0])
+        );
+        assert_eq!(evaluate_f64(function, vec![], false), None);
+    }
+
+    #[test]
+    fn percentile_disc() {
+        let function =
+            OrderedSetFunction::PercentileDisc(percentiles(&[0.0, 0.25, 0.5, 0.51, 1.0]));
+        let values = vec![4.0, 1.0, 3.0, 2.0];
+        assert_eq!(
+            evaluate_f64(function.clone(), values.clone(), false),
+            Some(vec![1.0, 1.0, 2.0, 3.0, 4.0])
+        );
+        assert_eq!(
+            evaluate_f64(function, values, true),
+            Some(vec![4.0, 4.0, 3.0, 2.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn mode() {
+        let values = vec![3.0, 1.0, 3.0, 2.0, 1.0];
+        assert_eq!(
+            evaluate_f64(OrderedSetFunction::Mode, values.clone(), false),
+            Some(vec![1.0])
+        );
+        assert_eq!(
+            evaluate_f64(OrderedSetFunction::Mode, values, true),
+            Some(vec![3.0])
+        );
+        assert_eq!(
+            evaluate_f64(OrderedSetFunction::Mode, vec![1.0, 2.0, 2.0], false),
+            Some(vec![2.0])
+        );
+    }
+}
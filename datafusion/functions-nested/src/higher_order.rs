@@ -0,0 +1,914 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for higher-order array functions, which take
+//! a lambda such as `x -> x + 1` as their last argument.
+
+use crate::utils::make_scalar_function;
+use arrow::compute::{cast, filter, interleave, is_null, nullif, take};
+use arrow_array::builder::BooleanBuilder;
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, GenericListArray, OffsetSizeTrait, UInt32Array,
+};
+use arrow_buffer::{OffsetBuffer, ScalarBuffer};
+use arrow_schema::DataType::{FixedSizeList, LargeList, List, Null};
+use arrow_schema::{DataType, Field};
+use datafusion_common::cast::{as_boolean_array, as_large_list_array, as_list_array};
+use datafusion_common::utils::coerced_fixed_size_list_to_list;
+use datafusion_common::{exec_err, plan_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_ARRAY;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarLambda, ScalarUDFImpl, Signature, Volatility,
+};
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+make_udf_expr_and_func!(
+    ArrayTransform,
+    array_transform,
+    array lambda,
+    "applies a lambda to each element of the array.",
+    array_transform_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayFilter,
+    array_filter,
+    array lambda,
+    "returns the elements of the array for which a lambda returns true.",
+    array_filter_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayReduce,
+    array_reduce,
+    array initial_value lambda,
+    "reduces the elements of the array to a single value with a lambda.",
+    array_reduce_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayAny,
+    array_any,
+    array lambda,
+    "returns true if a lambda returns true for any element of the array.",
+    array_any_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayAll,
+    array_all,
+    array lambda,
+    "returns true if a lambda returns true for all elements of the array.",
+    array_all_udf
+);
+
+#[derive(Debug)]
+pub(super) struct ArrayTransform {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayTransform {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec!["list_transform".to_string()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayTransform {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_transform"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types.len(), 2)?;
+        let field = Arc::new(Field::new_list_field(arg_types[1].clone(), true));
+        match &arg_types[0] {
+            LargeList(_) => Ok(LargeList(field)),
+            _ => Ok(List(field)),
+        }
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        check_arg_count(self.name(), arg_types.len() + 1, 2)?;
+        Ok(vec![list_element_type(self.name(), &arg_types[0])?])
+    }
+
+    fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        make_scalar_function(|args| array_transform_inner(args, lambda))(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_array_transform_doc())
+    }
+}
+
+static TRANSFORM_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_array_transform_doc() -> &'static Documentation {
+    TRANSFORM_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_ARRAY)
+            .with_description(
+                "Returns an array with the result of applying a lambda to each element of the array.",
+            )
+            .with_syntax_example("array_transform(array, x -> expression)")
+            .with_sql_example(
+                r#"```sql
+> select array_transform([1, 2, 3], x -> x * 10);
++----------------------------------------------------------------------------+
+| array_transform(make_array(Int64(1),Int64(2),Int64(3)),x -> x * Int64(10)) |
++----------------------------------------------------------------------------+
+| [10, 20, 30]                                                               |
++----------------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "array",
+                "Array expression. Can be a constant, column, or function, and any combination of array operators.",
+            )
+            .with_argument(
+                "x -> expression",
+                "Lambda applied to each element `x` of the array. The expression may only refer to its parameter.",
+            )
+            .build()
+            .unwrap()
+    })
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayFilter {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayFilter {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec!["list_filter".to_string()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayFilter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_filter"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types.len(), 2)?;
+        check_predicate_type(self.name(), &arg_types[1])?;
+        Ok(arg_types[0].clone())
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        check_arg_count(self.name(), arg_types.len() + 1, 2)?;
+        Ok(vec![list_element_type(self.name(), &arg_types[0])?])
+    }
+
+    fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        make_scalar_function(|args| array_filter_inner(args, lambda))(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_array_filter_doc())
+    }
+}
+
+static FILTER_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_array_filter_doc() -> &'static Documentation {
+    FILTER_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_ARRAY)
+            .with_description(
+                "Returns an array with the elements of the array for which a lambda returns true.",
+            )
+            .with_syntax_example("array_filter(array, x -> predicate)")
+            .with_sql_example(
+                r#"```sql
+> select array_filter([1, 2, 3, 4], x -> x % 2 = 0);
++--------------------------------------------------------------------------------------------+
+| array_filter(make_array(Int64(1),Int64(2),Int64(3),Int64(4)),x -> x % Int64(2) = Int64(0)) |
++--------------------------------------------------------------------------------------------+
+| [2, 4]                                                                                     |
++--------------------------------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "array",
+                "Array expression. Can be a constant, column, or function, and any combination of array operators.",
+            )
+            .with_argument(
+                "x -> predicate",
+                "Lambda returning a boolean for each element `x` of the array. Elements for which it returns false or null are removed.",
+            )
+            .build()
+            .unwrap()
+    })
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayReduce {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayReduce {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec!["list_reduce".to_string()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayReduce {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_reduce"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types.len(), 3)?;
+        Ok(arg_types[1].clone())
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        check_arg_count(self.name(), arg_types.len() + 1, 3)?;
+        Ok(vec![
+            arg_types[1].clone(),
+            list_element_type(self.name(), &arg_types[0])?,
+        ])
+    }
+
+    fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        make_scalar_function(|args| array_reduce_inner(args, lambda))(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_array_reduce_doc())
+    }
+}
+
+static REDUCE_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_array_reduce_doc() -> &'static Documentation {
+    REDUCE_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_ARRAY)
+            .with_description(
+                "Reduces the elements of the array to a single value, by applying a lambda to an accumulator and each element in turn. Returns the initial value for an empty array.",
+            )
+            .with_syntax_example("array_reduce(array, initial_value, (acc, x) -> expression)")
+            .with_sql_example(
+                r#"```sql
+> select array_reduce([1, 2, 3], 0, (acc, x) -> acc + x);
++-----------------------------------------------------------------------------------+
+| array_reduce(make_array(Int64(1),Int64(2),Int64(3)),Int64(0),(acc, x) -> acc + x) |
++-----------------------------------------------------------------------------------+
+| 6                                                                                 |
++-----------------------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "array",
+                "Array expression. Can be a constant, column, or function, and any combination of array operators.",
+            )
+            .with_argument(
+                "initial_value",
+                "Initial value of the accumulator, which also determines the type of the result.",
+            )
+            .with_argument(
+                "(acc, x) -> expression",
+                "Lambda returning the next value of the accumulator `acc` from its current value and an element `x` of the array.",
+            )
+            .build()
+            .unwrap()
+    })
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayAny {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayAny {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec!["list_any".to_string()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayAny {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_any"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types.len(), 2)?;
+        check_predicate_type(self.name(), &arg_types[1])?;
+        Ok(DataType::Boolean)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        check_arg_count(self.name(), arg_types.len() + 1, 2)?;
+        Ok(vec![list_element_type(self.name(), &arg_types[0])?])
+    }
+
+    fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        make_scalar_function(|args| array_quantifier_inner(args, lambda, true))(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_array_any_doc())
+    }
+}
+
+static ANY_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_array_any_doc() -> &'static Documentation {
+    ANY_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_ARRAY)
+            .with_description(
+                "Returns true if a lambda returns true for any element of the array. Otherwise returns null if it returns null for any element, and false if not, including for an empty array.",
+            )
+            .with_syntax_example("array_any(array, x -> predicate)")
+            .with_sql_example(
+                r#"```sql
+> select array_any([1, 2, 3], x -> x > 2);
++---------------------------------------------------------------------+
+| array_any(make_array(Int64(1),Int64(2),Int64(3)),x -> x > Int64(2)) |
++---------------------------------------------------------------------+
+| true                                                                |
++---------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "array",
+                "Array expression. Can be a constant, column, or function, and any combination of array operators.",
+            )
+            .with_argument(
+                "x -> predicate",
+                "Lambda returning a boolean for each element `x` of the array.",
+            )
+            .build()
+            .unwrap()
+    })
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayAll {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayAll {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec!["list_all".to_string()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayAll {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_all"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types.len(), 2)?;
+        check_predicate_type(self.name(), &arg_types[1])?;
+        Ok(DataType::Boolean)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameters(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        check_arg_count(self.name(), arg_types.len() + 1, 2)?;
+        Ok(vec![list_element_type(self.name(), &arg_types[0])?])
+    }
+
+    fn invoke_with_lambda(
+        &self,
+        args: &[ColumnarValue],
+        lambda: &dyn ScalarLambda,
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        make_scalar_function(|args| array_quantifier_inner(args, lambda, false))(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_array_all_doc())
+    }
+}
+
+static ALL_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_array_all_doc() -> &'static Documentation {
+    ALL_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_ARRAY)
+            .with_description(
+                "Returns false if a lambda returns false for any element of the array. Otherwise returns null if it returns null for any element, and true if not, including for an empty array.",
+            )
+            .with_syntax_example("array_all(array, x -> predicate)")
+            .with_sql_example(
+                r#"```sql
+> select array_all([1, 2, 3], x -> x > 2);
++---------------------------------------------------------------------+
+| array_all(make_array(Int64(1),Int64(2),Int64(3)),x -> x > Int64(2)) |
++---------------------------------------------------------------------+
+| false                                                               |
++---------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "array",
+                "Array expression. Can be a constant, column, or function, and any combination of array operators.",
+            )
+            .with_argument(
+                "x -> predicate",
+                "Lambda returning a boolean for each element `x` of the array.",
+            )
+            .build()
+            .unwrap()
+    })
+}
+
+/// Checks the number of arguments of a higher-order function, including its lambda
+fn check_arg_count(name: &str, num_args: usize, expected: usize) -> Result<()> {
+    if num_args != expected {
+        return plan_err!(
+            "{name} expects {expected} arguments including the lambda, got {num_args}"
+        );
+    }
+    Ok(())
+}
+
+fn check_predicate_type(name: &str, body_type: &DataType) -> Result<()> {
+    match body_type {
+        DataType::Boolean | Null => Ok(()),
+        _ => plan_err!("The lambda of {name} must return a boolean, got {body_type}"),
+    }
+}
+
+/// Coerces the array argument to a `List` or `LargeList`, leaving the other
+/// arguments, including the type of the lambda, unchanged
+fn coerce_list_arg(name: &str, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+    let Some((array_type, other_types)) = arg_types.split_first() else {
+        return plan_err!("{name} expects an array argument");
+    };
+    let array_type = match array_type {
+        List(_) | LargeList(_) | FixedSizeList(_, _) => {
+            coerced_fixed_size_list_to_list(array_type)
+        }
+        Null => List(Arc::new(Field::new_list_field(Null, true))),
+        _ => return plan_err!("{name} does not support type '{array_type:?}'"),
+    };
+    Ok(std::iter::once(array_type)
+        .chain(other_types.iter().cloned())
+        .collect())
+}
+
+/// Returns the type of the elements of the array argument
+fn list_element_type(name: &str, array_type: &DataType) -> Result<DataType> {
+    match array_type {
+        List(field) | LargeList(field) | FixedSizeList(field, _) => {
+            Ok(field.data_type().clone())
+        }
+        Null => Ok(Null),
+        _ => plan_err!("{name} does not support type '{array_type:?}'"),
+    }
+}
+
+/// Returns the elements of `array` that are within its offsets, along with
+/// offsets that start at zero
+fn list_values<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+) -> (ArrayRef, OffsetBuffer<O>) {
+    let offsets = array.offsets();
+    let first = offsets[0];
+    let last = offsets[offsets.len() - 1];
+    let values = array
+        .values()
+        .slice(first.as_usize(), (last - first).as_usize());
+    let offsets = offsets
+        .iter()
+        .map(|offset| *offset - first)
+        .collect::<Vec<_>>();
+    (values, OffsetBuffer::new(ScalarBuffer::from(offsets)))
+}
+
+/// Evaluates a predicate lambda over `values`
+fn evaluate_predicate(
+    name: &str,
+    values: &ArrayRef,
+    lambda: &dyn ScalarLambda,
+) -> Result<BooleanArray> {
+    let mask = lambda.evaluate(&[Arc::clone(values)])?;
+    if mask.len() != values.len() {
+        return exec_err!("The lambda of {name} returned the wrong number of values");
+    }
+    Ok(as_boolean_array(&cast(&mask, &DataType::Boolean)?)?.clone())
+}
+
+/// array_transform SQL function
+pub fn array_transform_inner(
+    args: &[ArrayRef],
+    lambda: &dyn ScalarLambda,
+) -> Result<ArrayRef> {
+    if args.len() != 1 {
+        return exec_err!("array_transform needs one argument and a lambda");
+    }
+
+    match args[0].data_type() {
+        List(_) => general_array_transform(as_list_array(&args[0])?, lambda),
+        LargeList(_) => general_array_transform(as_large_list_array(&args[0])?, lambda),
+        array_type => {
+            exec_err!("array_transform does not support type '{array_type:?}'.")
+        }
+    }
+}
+
+fn general_array_transform<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+    lambda: &dyn ScalarLambda,
+) -> Result<ArrayRef> {
+    let (values, offsets) = list_values(array);
+    let transformed = lambda.evaluate(&[Arc::clone(&values)])?;
+    if transformed.len() != values.len() {
+        return exec_err!(
+            "The lambda of array_transform returned the wrong number of values"
+        );
+    }
+    let field = Arc::new(Field::new_list_field(transformed.data_type().clone(), true));
+    Ok(Arc::new(GenericListArray::<O>::try_new(
+        field,
+        offsets,
+        transformed,
+        array.nulls().cloned(),
+    )?))
+}
+
+/// array_filter SQL function
+pub fn array_filter_inner(
+    args: &[ArrayRef],
+    lambda: &dyn ScalarLambda,
+) -> Result<ArrayRef> {
+    if args.len() != 1 {
+        return exec_err!("array_filter needs one argument and a lambda");
+    }
+
+    match args[0].data_type() {
+        List(_) => general_array_filter(as_list_array(&args[0])?, lambda),
+        LargeList(_) => general_array_filter(as_large_list_array(&args[0])?, lambda),
+        array_type => exec_err!("array_filter does not support type '{array_type:?}'."),
+    }
+}
+
+fn general_array_filter<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+    lambda: &dyn ScalarLambda,
+) -> Result<ArrayRef> {
+    let (values, offsets) = list_values(array);
+    let mask = evaluate_predicate("array_filter", &values, lambda)?;
+
+    let mut new_offsets = Vec::with_capacity(offsets.len());
+    new_offsets.push(O::usize_as(0));
+    let mut kept = 0;
+    for window in offsets.windows(2) {
+        let start = window[0].as_usize();
+        let end = window[1].as_usize();
+        kept += mask.slice(start, end - start).true_count();
+        new_offsets.push(O::usize_as(kept));
+    }
+
+    let (List(field) | LargeList(field)) = array.data_type() else {
+        return exec_err!("array_filter expects a list, got {}", array.data_type());
+    };
+    Ok(Arc::new(GenericListArray::<O>::try_new(
+        Arc::clone(field),
+        OffsetBuffer::new(ScalarBuffer::from(new_offsets)),
+        filter(&values, &mask)?,
+        array.nulls().cloned(),
+    )?))
+}
+
+/// array_reduce SQL function
+pub fn array_reduce_inner(
+    args: &[ArrayRef],
+    lambda: &dyn ScalarLambda,
+) -> Result<ArrayRef> {
+    if args.len() != 2 {
+        return exec_err!("array_reduce needs two arguments and a lambda");
+    }
+
+    match args[0].data_type() {
+        List(_) => general_array_reduce(as_list_array(&args[0])?, &args[1], lambda),
+        LargeList(_) => {
+            general_array_reduce(as_large_list_array(&args[0])?, &args[1], lambda)
+        }
+        array_type => exec_err!("array_reduce does not support type '{array_type:?}'."),
+    }
+}
+
+/// Reduces the lists of `array` one position at a time, evaluating the lambda
+/// once for all the lists that have an element at that position
+fn general_array_reduce<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+    initial_value: &ArrayRef,
+    lambda: &dyn ScalarLambda,
+) -> Result<ArrayRef> {
+    let (values, offsets) = list_values(array);
+    let lengths = offsets
+        .windows(2)
+        .map(|window| (window[1] - window[0]).as_usize())
+        .collect::<Vec<_>>();
+    let max_length = lengths.iter().copied().max().unwrap_or(0);
+
+    let mut acc = Arc::clone(initial_value);
+    for position in 0..max_length {
+        let (rows, value_indices): (Vec<u32>, Vec<u32>) = lengths
+            .iter()
+            .enumerate()
+            .filter(|(_, length)| **length > position)
+            .map(|(row, _)| {
+                let index = offsets[row].as_usize() + position;
+                (row as u32, index as u32)
+            })
+            .unzip();
+        let rows = UInt32Array::from(rows);
+        let acc_values = take(&acc, &rows, None)?;
+        let element_values = take(&values, &UInt32Array::from(value_indices), None)?;
+
+        let reduced = lambda.evaluate(&[acc_values, element_values])?;
+        if reduced.len() != rows.len() {
+            return exec_err!(
+                "The lambda of array_reduce returned the wrong number of values"
+            );
+        }
+        let reduced = cast(&reduced, initial_value.data_type())?;
+
+        // Replace the accumulator of the lists that were reduced
+        let mut next = rows.values().iter().enumerate().peekable();
+        let indices = (0..acc.len())
+            .map(|row| match next.next_if(|(_, r)| **r as usize == row) {
+                Some((i, _)) => (1, i),
+                None => (0, row),
+            })
+            .collect::<Vec<_>>();
+        acc = interleave(&[acc.as_ref(), reduced.as_ref()], &indices)?;
+    }
+
+    match array.nulls() {
+        Some(_) => Ok(nullif(&acc, &is_null(array)?)?),
+        None => Ok(acc),
+    }
+}
+
+/// array_any and array_all SQL functions, following the SQL semantics of `OR`
+/// and `AND` respectively for null values of the predicate
+fn array_quantifier_inner(
+    args: &[ArrayRef],
+    lambda: &dyn ScalarLambda,
+    any: bool,
+) -> Result<ArrayRef> {
+    let name = if any { "array_any" } else { "array_all" };
+    if args.len() != 1 {
+        return exec_err!("{name} needs one argument and a lambda");
+    }
+
+    match args[0].data_type() {
+        List(_) => general_array_quantifier(as_list_array(&args[0])?, lambda, any),
+        LargeList(_) => {
+            general_array_quantifier(as_large_list_array(&args[0])?, lambda, any)
+        }
+        array_type => exec_err!("{name} does not support type '{array_type:?}'."),
+    }
+}
+
+fn general_array_quantifier<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+    lambda: &dyn ScalarLambda,
+    any: bool,
+) -> Result<ArrayRef> {
+    let name = if any { "array_any" } else { "array_all" };
+    let (values, offsets) = list_values(array);
+    let mask = evaluate_predicate(name, &values, lambda)?;
+
+    let mut builder = BooleanBuilder::with_capacity(array.len());
+    for (row, window) in offsets.windows(2).enumerate() {
+        if array.is_null(row) {
+            builder.append_null();
+            continue;
+        }
+        let start = window[0].as_usize();
+        let end = window[1].as_usize();
+        let mut has_null = false;
+        let mut decided = false;
+        for i in start..end {
+            if mask.is_null(i) {
+                has_null = true;
+            } else if mask.value(i) == any {
+                decided = true;
+                break;
+            }
+        }
+        if decided {
+            builder.append_value(any);
+        } else if has_null {
+            builder.append_null();
+        } else {
+            builder.append_value(!any);
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::Int32Type;
+    use arrow_array::{Int32Array, ListArray};
+    use datafusion_common::cast::as_int32_array;
+
+    /// A lambda adding its parameters, or multiplying its single parameter by 10
+    #[derive(Debug)]
+    struct TestLambda;
+
+    impl ScalarLambda for TestLambda {
+        fn evaluate(&self, args: &[ArrayRef]) -> Result<ArrayRef> {
+            match args {
+                [x] => Ok(Arc::new(arrow::compute::kernels::numeric::mul(
+                    x,
+                    &Int32Array::new_scalar(10),
+                )?)),
+                [acc, x] => Ok(arrow::compute::kernels::numeric::add(acc, x)?),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    fn test_list() -> ArrayRef {
+        Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2), Some(3)]),
+            None,
+            Some(vec![]),
+            Some(vec![Some(4), None]),
+            Some(vec![Some(5)]),
+        ]))
+    }
+
+    #[test]
+    fn test_array_transform() -> Result<()> {
+        let list = test_list().slice(1, 4);
+        let result = array_transform_inner(&[list], &TestLambda)?;
+        let expected = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            None,
+            Some(vec![]),
+            Some(vec![Some(40), None]),
+            Some(vec![Some(50)]),
+        ]);
+        assert_eq!(result.as_ref(), &expected as &dyn Array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_reduce() -> Result<()> {
+        let initial_value: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(100),
+            Some(0),
+            Some(7),
+            Some(0),
+            None,
+        ]));
+        let result = array_reduce_inner(&[test_list(), initial_value], &TestLambda)?;
+        let result = as_int32_array(&result)?;
+        assert_eq!(
+            result,
+            &Int32Array::from(vec![Some(106), None, Some(7), None, None])
+        );
+        Ok(())
+    }
+}
@@ -40,6 +40,7 @@ pub mod except;
 pub mod expr_ext;
 pub mod extract;
 pub mod flatten;
+pub mod higher_order;
 pub mod length;
 pub mod make_array;
 pub mod map;
@@ -85,6 +86,11 @@ pub mod expr_fn {
     pub use super::extract::array_pop_front;
     pub use super::extract::array_slice;
     pub use super::flatten::flatten;
+    pub use super::higher_order::array_all;
+    pub use super::higher_order::array_any;
+    pub use super::higher_order::array_filter;
+    pub use super::higher_order::array_reduce;
+    pub use super::higher_order::array_transform;
     pub use super::length::array_length;
     pub use super::make_array::make_array;
     pub use super::map_extract::map_extract;
@@ -138,6 +144,11 @@ pub fn all_default_nested_functions() -> Vec<Arc<ScalarUDF>> {
         length::array_length_udf(),
         distance::array_distance_udf(),
         flatten::flatten_udf(),
+        higher_order::array_transform_udf(),
+        higher_order::array_filter_udf(),
+        higher_order::array_reduce_udf(),
+        higher_order::array_any_udf(),
+        higher_order::array_all_udf(),
         sort::array_sort_udf(),
         repeat::array_repeat_udf(),
         resize::array_resize_udf(),
@@ -0,0 +1,402 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Parsing of JSON documents and evaluation of JSON paths shared by the JSON
+//! functions
+//!
+//! The documents are not fully deserialized: only the objects and arrays on
+//! the path are split into their members, which are kept as raw JSON text.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, Int64Array, LargeStringArray, StringArray, StringViewArray,
+};
+use arrow::datatypes::DataType;
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_json::value::RawValue;
+
+/// An element of a JSON path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PathElement<'a> {
+    /// The member of an object with this key. If the value is an array, the
+    /// key is parsed as an index.
+    Key(&'a str),
+    /// The element of an array at this index, counted from the end if negative
+    Index(i64),
+}
+
+/// Coerces the arguments of a JSON function: a JSON document followed by the
+/// elements of a path, each a string or an integer
+pub(crate) fn coerce_json_args(
+    name: &str,
+    arg_types: &[DataType],
+    min_path_len: usize,
+) -> Result<Vec<DataType>> {
+    let Some((json, path)) = arg_types.split_first() else {
+        return plan_err!("{name} requires a JSON string argument");
+    };
+    if path.len() < min_path_len {
+        return plan_err!(
+            "{name} requires at least {min_path_len} path elements, got {}",
+            path.len()
+        );
+    }
+    let json = match json {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => json.clone(),
+        DataType::Null => DataType::Utf8,
+        _ => return plan_err!("{name} requires a JSON string argument, got {json}"),
+    };
+    let path = path.iter().map(|element| match element {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => Ok(element.clone()),
+        DataType::Null => Ok(DataType::Utf8),
+        element if element.is_integer() => Ok(DataType::Int64),
+        _ => plan_err!("{name} path elements must be strings or integers, got {element}"),
+    });
+    std::iter::once(Ok(json)).chain(path).collect()
+}
+
+/// Evaluates a JSON function: for every row, looks up the path in the JSON
+/// document and converts the value found with `extract`, which is called with
+/// `None` if there is no value at the path. The result is null if the
+/// document or a path element is null, or if the document isn't valid JSON.
+///
+/// `to_array` builds the result array from the results of all the rows.
+pub(crate) fn invoke_json<T>(
+    args: &[ColumnarValue],
+    extract: impl Fn(Option<&RawValue>) -> Option<T>,
+    to_array: impl FnOnce(Vec<Option<T>>) -> ArrayRef,
+) -> Result<ColumnarValue> {
+    let num_rows = args.iter().find_map(|arg| match arg {
+        ColumnarValue::Array(array) => Some(array.len()),
+        ColumnarValue::Scalar(_) => None,
+    });
+    let is_scalar = num_rows.is_none();
+    let num_rows = num_rows.unwrap_or(1);
+
+    let (json_is_scalar, json) = match &args[0] {
+        ColumnarValue::Scalar(scalar) => (true, scalar.to_array()?),
+        ColumnarValue::Array(array) => (false, Arc::clone(array)),
+    };
+    let json = StringColumn::try_new(&json)?;
+    let path_args = args[1..]
+        .iter()
+        .map(PathArg::try_new)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut path = Vec::with_capacity(path_args.len());
+    let values = (0..num_rows)
+        .map(|row| {
+            path.clear();
+            for arg in &path_args {
+                path.push(arg.element(row)?);
+            }
+            let json = json.value(if json_is_scalar { 0 } else { row })?;
+            extract(lookup(json, &path)?)
+        })
+        .collect();
+
+    let array = to_array(values);
+    if is_scalar {
+        ScalarValue::try_from_array(&array, 0).map(ColumnarValue::Scalar)
+    } else {
+        Ok(ColumnarValue::Array(array))
+    }
+}
+
+/// Looks up a path in a JSON document, returning `None` if the document isn't
+/// valid JSON, or `Some(None)` if there is no value at the path
+pub(crate) fn lookup<'a>(
+    json: &'a str,
+    path: &[PathElement],
+) -> Option<Option<&'a RawValue>> {
+    let mut value = serde_json::from_str::<&RawValue>(json).ok()?;
+    for element in path {
+        match get(value, element) {
+            Some(member) => value = member,
+            None => return Some(None),
+        }
+    }
+    Some(Some(value))
+}
+
+/// Returns the member of an object or the element of an array
+fn get<'a>(value: &'a RawValue, element: &PathElement) -> Option<&'a RawValue> {
+    match (value.get().as_bytes().first()?, element) {
+        (b'{', PathElement::Key(key)) => {
+            // The last member wins if a key is repeated
+            object_members(value)?
+                .into_iter()
+                .rev()
+                .find_map(|(name, member)| (name == *key).then_some(member))
+        }
+        (b'[', element) => {
+            let index = match element {
+                PathElement::Index(index) => *index,
+                PathElement::Key(key) => key.parse().ok()?,
+            };
+            let elements = array_elements(value)?;
+            let index = if index < 0 {
+                elements.len() as i64 + index
+            } else {
+                index
+            };
+            elements.get(usize::try_from(index).ok()?).copied()
+        }
+        _ => None,
+    }
+}
+
+/// Returns the members of a JSON object in order, or `None` if the value
+/// isn't an object
+pub(crate) fn object_members(value: &RawValue) -> Option<Vec<(Cow<str>, &RawValue)>> {
+    if !value.get().starts_with('{') {
+        return None;
+    }
+    serde_json::from_str::<JsonObject>(value.get())
+        .ok()
+        .map(|object| object.0)
+}
+
+/// Returns the elements of a JSON array, or `None` if the value isn't an
+/// array
+pub(crate) fn array_elements(value: &RawValue) -> Option<Vec<&RawValue>> {
+    if !value.get().starts_with('[') {
+        return None;
+    }
+    serde_json::from_str(value.get()).ok()
+}
+
+/// Returns the text of a JSON value: strings are unescaped, null is `None`
+/// and other values are returned as JSON
+pub(crate) fn value_as_text(value: &RawValue) -> Option<Cow<str>> {
+    match value.get() {
+        "null" => None,
+        text if text.starts_with('"') => value_as_str(value),
+        text => Some(Cow::Borrowed(text)),
+    }
+}
+
+/// Returns the unescaped value of a JSON string, or `None` if the value isn't
+/// a string
+pub(crate) fn value_as_str(value: &RawValue) -> Option<Cow<str>> {
+    if !value.get().starts_with('"') {
+        return None;
+    }
+    serde_json::from_str::<JsonStr>(value.get())
+        .ok()
+        .map(|text| text.0)
+}
+
+/// The JSON document argument of a JSON function
+enum StringColumn<'a> {
+    Utf8(&'a StringArray),
+    LargeUtf8(&'a LargeStringArray),
+    Utf8View(&'a StringViewArray),
+}
+
+impl<'a> StringColumn<'a> {
+    fn try_new(array: &'a ArrayRef) -> Result<Self> {
+        match array.data_type() {
+            DataType::Utf8 => Ok(Self::Utf8(array.as_string())),
+            DataType::LargeUtf8 => Ok(Self::LargeUtf8(array.as_string())),
+            DataType::Utf8View => Ok(Self::Utf8View(array.as_string_view())),
+            other => exec_err!("JSON functions require string arguments, got {other}"),
+        }
+    }
+
+    fn value(&self, row: usize) -> Option<&'a str> {
+        match self {
+            Self::Utf8(array) => array.is_valid(row).then(|| array.value(row)),
+            Self::LargeUtf8(array) => array.is_valid(row).then(|| array.value(row)),
+            Self::Utf8View(array) => array.is_valid(row).then(|| array.value(row)),
+        }
+    }
+}
+
+/// A path element argument of a JSON function
+enum PathArg<'a> {
+    Scalar(Option<PathElement<'a>>),
+    Keys(StringColumn<'a>),
+    Indexes(&'a Int64Array),
+}
+
+impl<'a> PathArg<'a> {
+    fn try_new(arg: &'a ColumnarValue) -> Result<Self> {
+        match arg {
+            ColumnarValue::Scalar(scalar) => match scalar {
+                ScalarValue::Utf8(key)
+                | ScalarValue::LargeUtf8(key)
+                | ScalarValue::Utf8View(key) => {
+                    Ok(Self::Scalar(key.as_deref().map(PathElement::Key)))
+                }
+                ScalarValue::Int64(index) => {
+                    Ok(Self::Scalar(index.map(PathElement::Index)))
+                }
+                ScalarValue::Null => Ok(Self::Scalar(None)),
+                other => exec_err!(
+                    "JSON path elements must be strings or integers, got {}",
+                    other.data_type()
+                ),
+            },
+            ColumnarValue::Array(array) => match array.data_type() {
+                DataType::Int64 => Ok(Self::Indexes(array.as_primitive())),
+                _ => StringColumn::try_new(array).map(Self::Keys),
+            },
+        }
+    }
+
+    fn element(&self, row: usize) -> Option<PathElement<'a>> {
+        match self {
+            Self::Scalar(element) => *element,
+            Self::Keys(keys) => keys.value(row).map(PathElement::Key),
+            Self::Indexes(indexes) => indexes
+                .is_valid(row)
+                .then(|| PathElement::Index(indexes.value(row))),
+        }
+    }
+}
+
+/// The members of a JSON object, in order
+struct JsonObject<'a>(Vec<(Cow<'a, str>, &'a RawValue)>);
+
+impl<'de> Deserialize<'de> for JsonObject<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ObjectVisitor;
+
+        impl<'de> Visitor<'de> for ObjectVisitor {
+            type Value = JsonObject<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut members = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((JsonStr(key), value)) = map.next_entry()? {
+                    members.push((key, value));
+                }
+                Ok(JsonObject(members))
+            }
+        }
+
+        deserializer.deserialize_map(ObjectVisitor)
+    }
+}
+
+/// A JSON string, borrowed from the document unless it contains escapes
+struct JsonStr<'a>(Cow<'a, str>);
+
+impl<'de> Deserialize<'de> for JsonStr<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StrVisitor;
+
+        impl<'de> Visitor<'de> for StrVisitor {
+            type Value = JsonStr<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON string")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(JsonStr(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(JsonStr(Cow::Owned(v.to_string())))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+                Ok(JsonStr(Cow::Owned(v)))
+            }
+        }
+
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_text(json: &str, path: &[PathElement]) -> Option<Option<String>> {
+        lookup(json, path).map(|value| value.map(|value| value.get().to_string()))
+    }
+
+    #[test]
+    fn lookup_path() {
+        let json = r#" {"a": {"b": [1, "x", {"c": null}]}, "a\"q": 2, "d": 3, "d": 4} "#;
+        assert_eq!(get_text(json, &[]), Some(Some(json.trim().to_string())));
+        assert_eq!(
+            get_text(json, &[PathElement::Key("a"), PathElement::Key("b")]),
+            Some(Some(r#"[1, "x", {"c": null}]"#.to_string()))
+        );
+        assert_eq!(
+            get_text(
+                json,
+                &[
+                    PathElement::Key("a"),
+                    PathElement::Key("b"),
+                    PathElement::Index(-1),
+                    PathElement::Key("c")
+                ]
+            ),
+            Some(Some("null".to_string()))
+        );
+        assert_eq!(
+            get_text(
+                json,
+                &[
+                    PathElement::Key("a"),
+                    PathElement::Key("b"),
+                    PathElement::Key("1")
+                ]
+            ),
+            Some(Some(r#""x""#.to_string()))
+        );
+        assert_eq!(
+            get_text(json, &[PathElement::Key("a\"q")]),
+            Some(Some("2".to_string()))
+        );
+        assert_eq!(
+            get_text(json, &[PathElement::Key("d")]),
+            Some(Some("4".to_string()))
+        );
+        assert_eq!(get_text(json, &[PathElement::Key("x")]), Some(None));
+        assert_eq!(get_text(json, &[PathElement::Index(0)]), Some(None));
+        assert_eq!(get_text("{\"a\": ", &[PathElement::Key("a")]), None);
+    }
+
+    #[test]
+    fn text_values() {
+        let text = |json: &str| {
+            let value = serde_json::from_str::<&RawValue>(json).unwrap();
+            value_as_text(value).map(|text| text.into_owned())
+        };
+        assert_eq!(text(r#""a\nb""#), Some("a\nb".to_string()));
+        assert_eq!(text("1.50"), Some("1.50".to_string()));
+        assert_eq!(text("[1, 2]"), Some("[1, 2]".to_string()));
+        assert_eq!(text("null"), None);
+    }
+}
@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `json_as_text` function, returning the value at a path of a JSON document as text
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::StringArray;
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_JSON;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use super::common::{coerce_json_args, invoke_json, value_as_text};
+
+#[derive(Debug)]
+pub struct JsonAsTextFunc {
+    signature: Signature,
+}
+
+impl Default for JsonAsTextFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonAsTextFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonAsTextFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_as_text"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_json_args(self.name(), arg_types, 0)
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        invoke_json(
+            args,
+            |value| value.and_then(value_as_text).map(Cow::into_owned),
+            |values| Arc::new(StringArray::from_iter(values)),
+        )
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_json_as_text_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_json_as_text_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_JSON)
+            .with_description(
+                "Returns the value at a path of a JSON document as text: strings are unescaped, JSON null is returned as null and other values as JSON text. The `->>` operator is equivalent to `json_as_text` with a single path element.",
+            )
+            .with_syntax_example("json_as_text(json[, path[, ...]])")
+            .with_sql_example(
+                r#"```sql
+> select '{"a": {"b": "x"}}' -> 'a' ->> 'b';
++-------------------------------------------------------------+
+| json_as_text(Utf8("{"a": {"b": "x"}}"),Utf8("a"),Utf8("b")) |
++-------------------------------------------------------------+
+| x                                                           |
++-------------------------------------------------------------+
+```"#,
+            )
+            .with_argument("json", "JSON document: string expression to operate on. Can be a constant, column, or function, and any combination of operators.")
+            .with_argument("path", "Object keys (strings) and array indexes (integers, negative to count from the end) leading to the value. Strings that are integers also index arrays.")
+            .build()
+            .unwrap()
+    })
+}
@@ -0,0 +1,111 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `json_contains` function, checking whether a JSON document has a value at a path
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::BooleanArray;
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_JSON;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use super::common::{coerce_json_args, invoke_json};
+
+#[derive(Debug)]
+pub struct JsonContainsFunc {
+    signature: Signature,
+}
+
+impl Default for JsonContainsFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonContainsFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonContainsFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_contains"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_json_args(self.name(), arg_types, 1)
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        invoke_json(
+            args,
+            |value| Some(value.is_some()),
+            |values| Arc::new(BooleanArray::from(values)),
+        )
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_json_contains_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_json_contains_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_JSON)
+            .with_description(
+                "Returns true if a JSON document has a value at a path, false otherwise, or null if the document is not valid JSON.",
+            )
+            .with_syntax_example("json_contains(json, path[, ...])")
+            .with_sql_example(
+                r#"```sql
+> select json_contains('{"a": [1, 2]}', 'a', 2);
++---------------------------------------------------------+
+| json_contains(Utf8("{"a": [1, 2]}"),Utf8("a"),Int64(2)) |
++---------------------------------------------------------+
+| false                                                   |
++---------------------------------------------------------+
+```"#,
+            )
+            .with_argument("json", "JSON document: string expression to operate on. Can be a constant, column, or function, and any combination of operators.")
+            .with_argument("path", "Object keys (strings) and array indexes (integers, negative to count from the end) leading to the value. Strings that are integers also index arrays.")
+            .build()
+            .unwrap()
+    })
+}
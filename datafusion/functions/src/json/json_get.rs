@@ -0,0 +1,123 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `json_get` function, returning the value at a path of a JSON document as JSON
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::StringArray;
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_JSON;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use super::common::{coerce_json_args, invoke_json};
+
+#[derive(Debug)]
+pub struct JsonGetFunc {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl Default for JsonGetFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonGetFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec![String::from("json_extract_path")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonGetFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_get"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_json_args(self.name(), arg_types, 0)
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        invoke_json(
+            args,
+            |value| value.map(|value| value.get().to_string()),
+            |values| Arc::new(StringArray::from_iter(values)),
+        )
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_json_get_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_json_get_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_JSON)
+            .with_description(
+                "Returns the value at a path of a JSON document as JSON text, or null if there is no such value or the document is not valid JSON. The `->` operator is equivalent to `json_get` with a single path element.",
+            )
+            .with_syntax_example("json_get(json[, path[, ...]])")
+            .with_sql_example(
+                r#"```sql
+> select json_get('{"a": {"b": [1, 2]}}', 'a', 'b');
++------------------------------------------------------------+
+| json_get(Utf8("{"a": {"b": [1, 2]}}"),Utf8("a"),Utf8("b")) |
++------------------------------------------------------------+
+| [1, 2]                                                     |
++------------------------------------------------------------+
+> select '{"a": {"b": [1, 2]}}' -> 'a' -> 'b' -> -1;
++----------------------------------------------------------------------+
+| json_get(Utf8("{"a": {"b": [1, 2]}}"),Utf8("a"),Utf8("b"),Int64(-1)) |
++----------------------------------------------------------------------+
+| 2                                                                    |
++----------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument("json", "JSON document: string expression to operate on. Can be a constant, column, or function, and any combination of operators.")
+            .with_argument("path", "Object keys (strings) and array indexes (integers, negative to count from the end) leading to the value. Strings that are integers also index arrays.")
+            .build()
+            .unwrap()
+    })
+}
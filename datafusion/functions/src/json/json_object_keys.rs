@@ -0,0 +1,123 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `json_object_keys` function, returning the keys of the object at a path of a JSON document
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ListBuilder, StringBuilder};
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_JSON;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use super::common::{coerce_json_args, invoke_json, object_members};
+
+#[derive(Debug)]
+pub struct JsonObjectKeysFunc {
+    signature: Signature,
+}
+
+impl Default for JsonObjectKeysFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonObjectKeysFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonObjectKeysFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_object_keys"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_json_args(self.name(), arg_types, 0)
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::new_list(DataType::Utf8, true))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        invoke_json(
+            args,
+            |value| {
+                let members = object_members(value?)?;
+                Some(
+                    members
+                        .into_iter()
+                        .map(|(key, _)| Some(key.into_owned()))
+                        .collect::<Vec<_>>(),
+                )
+            },
+            |values| {
+                let mut builder = ListBuilder::new(StringBuilder::new());
+                builder.extend(values);
+                Arc::new(builder.finish())
+            },
+        )
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_json_object_keys_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_json_object_keys_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_JSON)
+            .with_description(
+                "Returns the keys of the object at a path of a JSON document in order, or null if the value is not an object.",
+            )
+            .with_syntax_example("json_object_keys(json[, path[, ...]])")
+            .with_sql_example(
+                r#"```sql
+> select json_object_keys('{"a": {"x": 1, "b": 2}}', 'a');
++-------------------------------------------------------------+
+| json_object_keys(Utf8("{"a": {"x": 1, "b": 2}}"),Utf8("a")) |
++-------------------------------------------------------------+
+| [x, b]                                                      |
++-------------------------------------------------------------+
+```"#,
+            )
+            .with_argument("json", "JSON document: string expression to operate on. Can be a constant, column, or function, and any combination of operators.")
+            .with_argument("path", "Object keys (strings) and array indexes (integers, negative to count from the end) leading to the value. Strings that are integers also index arrays.")
+            .build()
+            .unwrap()
+    })
+}
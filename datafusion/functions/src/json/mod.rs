@@ -0,0 +1,123 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! "json" DataFusion functions, operating on JSON documents stored as strings
+
+use datafusion_expr::ScalarUDF;
+use std::sync::Arc;
+
+pub mod common;
+pub mod json_as_text;
+pub mod json_contains;
+pub mod json_get;
+pub mod json_get_bool;
+pub mod json_get_float;
+pub mod json_get_int;
+pub mod json_get_str;
+pub mod json_length;
+pub mod json_object_keys;
+
+// create UDFs
+make_udf_function!(json_get::JsonGetFunc, JSON_GET, json_get);
+make_udf_function!(json_as_text::JsonAsTextFunc, JSON_AS_TEXT, json_as_text);
+make_udf_function!(json_get_str::JsonGetStrFunc, JSON_GET_STR, json_get_str);
+make_udf_function!(json_get_int::JsonGetIntFunc, JSON_GET_INT, json_get_int);
+make_udf_function!(
+    json_get_float::JsonGetFloatFunc,
+    JSON_GET_FLOAT,
+    json_get_float
+);
+make_udf_function!(json_get_bool::JsonGetBoolFunc, JSON_GET_BOOL, json_get_bool);
+make_udf_function!(
+    json_contains::JsonContainsFunc,
+    JSON_CONTAINS,
+    json_contains
+);
+make_udf_function!(json_length::JsonLengthFunc, JSON_LENGTH, json_length);
+make_udf_function!(
+    json_object_keys::JsonObjectKeysFunc,
+    JSON_OBJECT_KEYS,
+    json_object_keys
+);
+
+pub mod expr_fn {
+    use datafusion_expr::Expr;
+
+    /// Returns the value at `path` of the `json` document as JSON text.
+    pub fn json_get(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get().call(args(json, path))
+    }
+
+    /// Returns the value at `path` of the `json` document as text.
+    pub fn json_as_text(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_as_text().call(args(json, path))
+    }
+
+    /// Returns the string at `path` of the `json` document.
+    pub fn json_get_str(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get_str().call(args(json, path))
+    }
+
+    /// Returns the integer at `path` of the `json` document.
+    pub fn json_get_int(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get_int().call(args(json, path))
+    }
+
+    /// Returns the number at `path` of the `json` document as a float.
+    pub fn json_get_float(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get_float().call(args(json, path))
+    }
+
+    /// Returns the boolean at `path` of the `json` document.
+    pub fn json_get_bool(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get_bool().call(args(json, path))
+    }
+
+    /// Returns true if the `json` document has a value at `path`.
+    pub fn json_contains(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_contains().call(args(json, path))
+    }
+
+    /// Returns the length of the array or object at `path` of the `json` document.
+    pub fn json_length(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_length().call(args(json, path))
+    }
+
+    /// Returns the keys of the object at `path` of the `json` document.
+    pub fn json_object_keys(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_object_keys().call(args(json, path))
+    }
+
+    fn args(json: Expr, path: Vec<Expr>) -> Vec<Expr> {
+        std::iter::once(json).chain(path).collect()
+    }
+}
+
+/// Returns all DataFusion functions defined in this package
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![
+        json_get(),
+        json_as_text(),
+        json_get_str(),
+        json_get_int(),
+        json_get_float(),
+        json_get_bool(),
+        json_contains(),
+        json_length(),
+        json_object_keys(),
+    ]
+}
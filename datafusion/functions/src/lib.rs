@@ -132,7 +132,15 @@ make_stub_package!(crypto, "crypto_expressions");
 pub mod unicode;
 make_stub_package!(unicode, "unicode_expressions");
 
-#[cfg(any(feature = "datetime_expressions", feature = "unicode_expressions"))]
+#[cfg(feature = "json_expressions")]
+pub mod json;
+make_stub_package!(json, "json_expressions");
+
+#[cfg(any(
+    feature = "datetime_expressions",
+    feature = "unicode_expressions",
+    feature = "json_expressions"
+))]
 pub mod planner;
 
 pub mod strings;
@@ -149,6 +157,8 @@ pub mod expr_fn {
     pub use super::datetime::expr_fn::*;
     #[cfg(feature = "encoding_expressions")]
     pub use super::encoding::expr_fn::*;
+    #[cfg(feature = "json_expressions")]
+    pub use super::json::expr_fn::*;
     #[cfg(feature = "math_expressions")]
     pub use super::math::expr_fn::*;
     #[cfg(feature = "regex_expressions")]
@@ -170,6 +180,7 @@ pub fn all_default_functions() -> Vec<Arc<ScalarUDF>> {
         .chain(crypto::functions())
         .chain(unicode::functions())
         .chain(string::functions())
+        .chain(json::functions())
         .collect::<Vec<_>>()
 }
 
@@ -17,7 +17,17 @@
 
 //! SQL planning extensions like [`UserDefinedFunctionPlanner`]
 
+#[cfg(feature = "json_expressions")]
+use arrow::datatypes::DataType;
+#[cfg(feature = "json_expressions")]
+use datafusion_common::DFSchema;
 use datafusion_common::Result;
+#[cfg(feature = "json_expressions")]
+use datafusion_expr::planner::RawBinaryExpr;
+#[cfg(feature = "json_expressions")]
+use datafusion_expr::sqlparser::ast::BinaryOperator;
+#[cfg(feature = "json_expressions")]
+use datafusion_expr::ExprSchemable;
 use datafusion_expr::{
     expr::ScalarFunction,
     planner::{ExprPlanner, PlannerResult},
@@ -28,6 +38,47 @@ use datafusion_expr::{
 pub struct UserDefinedFunctionPlanner;
 
 impl ExprPlanner for UserDefinedFunctionPlanner {
+    /// Plans the `->` and `->>` operators on JSON strings as [`json_get`] and
+    /// [`json_as_text`], merging chains of operators into a single call.
+    ///
+    /// [`json_get`]: crate::json::json_get
+    /// [`json_as_text`]: crate::json::json_as_text
+    #[cfg(feature = "json_expressions")]
+    fn plan_binary_op(
+        &self,
+        expr: RawBinaryExpr,
+        schema: &DFSchema,
+    ) -> Result<PlannerResult<RawBinaryExpr>> {
+        let func = match expr.op {
+            BinaryOperator::Arrow => crate::json::json_get(),
+            BinaryOperator::LongArrow => crate::json::json_as_text(),
+            _ => return Ok(PlannerResult::Original(expr)),
+        };
+        let is_string = expr.left.get_type(schema).is_ok_and(|data_type| {
+            matches!(
+                data_type,
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            )
+        });
+        if !is_string {
+            return Ok(PlannerResult::Original(expr));
+        }
+
+        let args = match expr.left {
+            Expr::ScalarFunction(ScalarFunction {
+                func: left,
+                mut args,
+            }) if left.name() == crate::json::json_get().name() => {
+                args.push(expr.right);
+                args
+            }
+            left => vec![left, expr.right],
+        };
+        Ok(PlannerResult::Planned(Expr::ScalarFunction(
+            ScalarFunction::new_udf(func, args),
+        )))
+    }
+
     #[cfg(feature = "datetime_expressions")]
     fn plan_extract(&self, args: Vec<Expr>) -> Result<PlannerResult<Vec<Expr>>> {
         Ok(PlannerResult::Planned(Expr::ScalarFunction(
@@ -22,7 +22,7 @@ use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{DFSchema, Result};
 
-use crate::utils::NamePreserver;
+use crate::utils::{merge_target_table_schema, NamePreserver};
 use datafusion_expr::expr_rewriter::FunctionRewrite;
 use datafusion_expr::utils::merge_schema;
 use datafusion_expr::LogicalPlan;
@@ -58,6 +58,8 @@ impl ApplyFunctionRewrites {
             schema.merge(&source_schema);
         }
 
+        merge_target_table_schema(&plan, &mut schema);
+
         let name_preserver = NamePreserver::new(&plan);
 
         plan.map_expressions(|expr| {
@@ -24,20 +24,22 @@ use itertools::izip;
 use arrow::datatypes::{DataType, Field, IntervalUnit, Schema};
 
 use crate::analyzer::AnalyzerRule;
-use crate::utils::NamePreserver;
+use crate::utils::{merge_target_table_schema, NamePreserver};
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRewriter};
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRewriter,
+};
 use datafusion_common::{
     exec_err, internal_err, not_impl_err, plan_datafusion_err, plan_err, Column,
     DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue, TableReference,
 };
 use datafusion_expr::expr::{
-    self, Alias, Between, BinaryExpr, Case, Exists, InList, InSubquery, Like,
+    self, Alias, Between, BinaryExpr, Case, Exists, InList, InSubquery, Lambda, Like,
     ScalarFunction, Sort, WindowFunction,
 };
 use datafusion_expr::expr_rewriter::coerce_plan_expr_for_schema;
-use datafusion_expr::expr_schema::cast_subquery;
-use datafusion_expr::logical_plan::Subquery;
+use datafusion_expr::expr_schema::{cast_subquery, scalar_function_arg_types};
+use datafusion_expr::logical_plan::{Subquery, WriteOp};
 use datafusion_expr::type_coercion::binary::{
     comparison_coercion, get_input_types, like_coercion,
 };
@@ -122,6 +124,8 @@ fn analyze_internal(
         schema.merge(&source_schema);
     }
 
+    merge_target_table_schema(&plan, &mut schema);
+
     // merge the outer schema for correlated subqueries
     // like case:
     // select t2.c2 from t1 where t1.c1 in (select t2.c1 from t2 where t2.c2=t1.c3)
@@ -135,6 +139,24 @@ fn analyze_internal(
         plan
     };
 
+    // Coerce the join condition and clause predicates of a MERGE to boolean
+    let plan = if let LogicalPlan::Dml(mut dml) = plan {
+        if let WriteOp::Merge(merge) = &mut dml.op {
+            merge.on =
+                std::mem::take(&mut merge.on).cast_to(&DataType::Boolean, &schema)?;
+            for clause in &mut merge.clauses {
+                clause.predicate = clause
+                    .predicate
+                    .take()
+                    .map(|predicate| predicate.cast_to(&DataType::Boolean, &schema))
+                    .transpose()?;
+            }
+        }
+        LogicalPlan::Dml(dml)
+    } else {
+        plan
+    };
+
     let mut expr_rewrite = TypeCoercionRewriter::new(&schema);
 
     let name_preserver = NamePreserver::new(&plan);
@@ -561,7 +583,9 @@ impl<'a> TreeNodeRewriter for TypeCoercionRewriter<'a> {
             | Expr::Wildcard { .. }
             | Expr::GroupingSet(_)
             | Expr::Placeholder(_)
-            | Expr::OuterReferenceColumn(_, _) => Ok(Transformed::no(expr)),
+            | Expr::OuterReferenceColumn(_, _)
+            // Lambdas are coerced along with the arguments of their function
+            | Expr::Lambda(_) => Ok(Transformed::no(expr)),
         }
     }
 }
@@ -752,17 +776,27 @@ fn coerce_arguments_for_signature_with_scalar_udf(
         return Ok(expressions);
     }
 
-    let current_types = expressions
-        .iter()
-        .map(|e| e.get_type(schema))
-        .collect::<Result<Vec<_>>>()?;
+    let current_types = scalar_function_arg_types(func, &expressions, schema)?;
 
     let new_types = data_types_with_scalar_udf(&current_types, func)?;
 
     expressions
         .into_iter()
         .enumerate()
-        .map(|(i, expr)| expr.cast_to(&new_types[i], schema))
+        .map(|(i, expr)| match expr {
+            // The body of a lambda is coerced against its parameters, whose
+            // types follow from the coerced arguments before it
+            Expr::Lambda(lambda) => {
+                let params_schema =
+                    lambda.params_schema(&func.lambda_parameters(&new_types[..i])?)?;
+                let body = lambda
+                    .body
+                    .rewrite(&mut TypeCoercionRewriter::new(&params_schema))
+                    .data()?;
+                Ok(Expr::Lambda(Lambda::new(lambda.params, body)))
+            }
+            expr => expr.cast_to(&new_types[i], schema),
+        })
         .collect()
 }
 
@@ -542,6 +542,7 @@ impl OptimizerRule for CommonSubexprEliminate {
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
@@ -587,6 +588,7 @@ enum ExprMask {
     /// - [`ScalarVariable`](Expr::ScalarVariable)
     /// - [`Alias`](Expr::Alias)
     /// - [`Wildcard`](Expr::Wildcard)
+    /// - [`Lambda`](Expr::Lambda)
     /// - [`AggregateFunction`](Expr::AggregateFunction)
     Normal,
 
@@ -675,6 +677,7 @@ impl CSEController for ExprCSEController<'_> {
                 | Expr::ScalarVariable(..)
                 | Expr::Alias(..)
                 | Expr::Wildcard { .. }
+                | Expr::Lambda(..)
         );
 
         let is_aggr = matches!(node, Expr::AggregateFunction(..));
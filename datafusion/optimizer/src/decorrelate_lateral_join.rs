@@ -0,0 +1,532 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DecorrelateLateralJoin`] rewrites lateral joins to joins without correlation
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::decorrelate::PullUpCorrelatedExpr;
+use crate::optimizer::ApplyOrder;
+use crate::push_down_filter::replace_cols_by_name;
+use crate::utils::replace_qualified_name;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_common::{Column, Result, TableReference};
+use datafusion_expr::expr::WindowFunction;
+use datafusion_expr::logical_plan::builder::{build_join_schema, subquery_alias};
+use datafusion_expr::logical_plan::{Join, JoinType, Limit, Projection, Subquery};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{
+    col, lit, BinaryExpr, Expr, ExprFunctionExt, FetchType, LogicalPlan,
+    LogicalPlanBuilder, Operator, SkipType, SortExpr, WindowFunctionDefinition,
+};
+
+/// Name of the `row_number()` column used to compute the top-N rows of a lateral subquery
+const ROW_NUMBER_COLUMN: &str = "__lateral_row_number";
+
+/// Optimizer rule for rewriting lateral joins, whose right side is a subquery
+/// that refers to columns of the left side, to regular joins.
+///
+/// The correlated predicates of the subquery are pulled up into the join
+/// filter, so that
+///
+/// ```text
+/// SELECT * FROM t, LATERAL (SELECT * FROM u WHERE u.k = t.k) AS s
+/// ```
+///
+/// is rewritten to
+///
+/// ```text
+/// SELECT * FROM t JOIN (SELECT * FROM u) AS s ON s.k = t.k
+/// ```
+///
+/// A limit of the correlated rows, like `ORDER BY x LIMIT 3`, is rewritten to
+/// a filter on `row_number()` partitioned by the columns compared with the
+/// left side, which computes the top-N rows for every row of the left side
+/// at once.
+///
+/// Lateral joins that can't be decorrelated are left unchanged, and executed
+/// by evaluating the subquery for every row of the left side.
+#[derive(Default, Debug)]
+pub struct DecorrelateLateralJoin {}
+
+impl DecorrelateLateralJoin {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OptimizerRule for DecorrelateLateralJoin {
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Join(join) = &plan else {
+            return Ok(Transformed::no(plan));
+        };
+        let Some((subquery, alias)) = lateral_subquery(&join.right) else {
+            return Ok(Transformed::no(plan));
+        };
+
+        // A subquery that isn't correlated can be joined as it is
+        if subquery.subquery.all_out_ref_exprs().is_empty() {
+            let right = unwrap_subquery(subquery, alias)?;
+            let join = rebuild_join(join, right, None)?;
+            return Ok(Transformed::yes(LogicalPlan::Join(join)));
+        }
+
+        if !matches!(join.join_type, JoinType::Inner | JoinType::Left) {
+            return Ok(Transformed::no(plan));
+        }
+        let Some((right, join_filter)) = decorrelate(subquery, alias, config)? else {
+            return Ok(Transformed::no(plan));
+        };
+        let new_join = rebuild_join(join, right, join_filter)?;
+
+        // The decorrelated subquery may output additional columns
+        let expr = join
+            .schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        let projection =
+            Projection::try_new(expr, Arc::new(LogicalPlan::Join(new_join)))?;
+        Ok(Transformed::yes(LogicalPlan::Projection(projection)))
+    }
+
+    fn name(&self) -> &str {
+        "decorrelate_lateral_join"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// Returns the subquery of a lateral join and its alias
+fn lateral_subquery(plan: &LogicalPlan) -> Option<(&Subquery, Option<&TableReference>)> {
+    match plan {
+        LogicalPlan::Subquery(subquery) => Some((subquery, None)),
+        LogicalPlan::SubqueryAlias(alias) => match alias.input.as_ref() {
+            LogicalPlan::Subquery(subquery) => Some((subquery, Some(&alias.alias))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn unwrap_subquery(
+    subquery: &Subquery,
+    alias: Option<&TableReference>,
+) -> Result<LogicalPlan> {
+    let plan = subquery.subquery.as_ref().clone();
+    match alias {
+        Some(alias) => subquery_alias(plan, alias.clone()),
+        None => Ok(plan),
+    }
+}
+
+/// Returns `join` with a new right side, and `filter` added to its filter
+fn rebuild_join(join: &Join, right: LogicalPlan, filter: Option<Expr>) -> Result<Join> {
+    let filter = conjunction(join.filter.clone().into_iter().chain(filter));
+    let schema = build_join_schema(join.left.schema(), right.schema(), &join.join_type)?;
+    Ok(Join {
+        left: Arc::clone(&join.left),
+        right: Arc::new(right),
+        on: join.on.clone(),
+        filter,
+        join_type: join.join_type,
+        join_constraint: join.join_constraint,
+        schema: Arc::new(schema),
+        null_equals_null: join.null_equals_null,
+    })
+}
+
+/// Pulls the correlated predicates out of the subquery, returning the new right
+/// side of the join and the predicates to add to the join filter, or `None` if
+/// the subquery can't be decorrelated.
+fn decorrelate(
+    subquery: &Subquery,
+    alias: Option<&TableReference>,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<(LogicalPlan, Option<Expr>)>> {
+    let plan = subquery.subquery.as_ref().clone();
+
+    // An aggregate without grouping returns a row even for no input rows, which
+    // would be lost by joining it with the correlated predicates
+    let has_scalar_aggregate = plan.exists(|plan| {
+        Ok(matches!(
+            plan,
+            LogicalPlan::Aggregate(aggregate)
+                if aggregate.group_expr.is_empty()
+                    && !aggregate.input.all_out_ref_exprs().is_empty()
+        ))
+    })?;
+    if has_scalar_aggregate {
+        return Ok(None);
+    }
+
+    let Some(plan) = rewrite_correlated_limits(plan, config)? else {
+        return Ok(None);
+    };
+    let mut pull_up = PullUpCorrelatedExpr::new();
+    let plan = plan.rewrite(&mut pull_up).data()?;
+    if !pull_up.can_pull_up || !plan.all_out_ref_exprs().is_empty() {
+        return Ok(None);
+    }
+    // The pull up adds the correlated columns to the projections and
+    // aggregations of the subquery, which the nodes above must expose too
+    let plan = plan
+        .transform_up(|plan| plan.recompute_schema().map(Transformed::yes))
+        .data()?;
+
+    let mut correlated_cols = BTreeSet::new();
+    pull_up
+        .correlated_subquery_cols_map
+        .values()
+        .for_each(|cols| correlated_cols.extend(cols.clone()));
+    let join_filter = conjunction(pull_up.join_filters);
+    match alias {
+        Some(alias) => {
+            let join_filter = join_filter
+                .map(|filter| {
+                    replace_qualified_name(filter, &correlated_cols, &alias.to_string())
+                })
+                .transpose()?;
+            Ok(Some((subquery_alias(plan, alias.clone())?, join_filter)))
+        }
+        None => Ok(Some((plan, join_filter))),
+    }
+}
+
+/// Rewrites the limits of correlated rows in `plan` to filters on
+/// `row_number()`, returning `None` if one of them can't be rewritten.
+fn rewrite_correlated_limits(
+    plan: LogicalPlan,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    let mut rewritten = true;
+    let plan = plan
+        .transform_down(|plan| {
+            let LogicalPlan::Limit(limit) = &plan else {
+                return Ok(Transformed::no(plan));
+            };
+            if limit.input.all_out_ref_exprs().is_empty() {
+                return Ok(Transformed::new(plan, false, TreeNodeRecursion::Jump));
+            }
+            match limit_to_row_number(limit, config)? {
+                Some(new_plan) => Ok(Transformed::yes(new_plan)),
+                None => {
+                    rewritten = false;
+                    Ok(Transformed::new(plan, false, TreeNodeRecursion::Stop))
+                }
+            }
+        })
+        .data()?;
+    Ok(rewritten.then_some(plan))
+}
+
+/// Rewrites a limit, and the sort below it, of rows that are correlated by
+/// equality predicates to a filter on `row_number()` partitioned by the
+/// expressions compared with the outer columns.
+fn limit_to_row_number(
+    limit: &Limit,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    let (SkipType::Literal(skip), FetchType::Literal(fetch)) =
+        (limit.get_skip_type()?, limit.get_fetch_type()?)
+    else {
+        return Ok(None);
+    };
+    let (mut sort_exprs, mut input) = match limit.input.as_ref() {
+        LogicalPlan::Sort(sort) => (sort.expr.clone(), Arc::clone(&sort.input)),
+        _ => (vec![], Arc::clone(&limit.input)),
+    };
+
+    // Every correlated predicate must compare an expression of the subquery
+    // with outer columns
+    let mut keys = vec![];
+    let mut supported = true;
+    input.apply(|plan| {
+        if let LogicalPlan::Filter(filter) = plan {
+            for predicate in split_conjunction(&filter.predicate) {
+                if predicate.contains_outer() {
+                    match correlated_key(predicate) {
+                        Some(key) if !keys.contains(key) => keys.push(key.clone()),
+                        Some(_) => {}
+                        None => supported = false,
+                    }
+                }
+            }
+        } else if plan.contains_outer_reference() {
+            supported = false;
+        }
+        Ok(if supported {
+            TreeNodeRecursion::Continue
+        } else {
+            TreeNodeRecursion::Stop
+        })
+    })?;
+    if !supported || keys.is_empty() {
+        return Ok(None);
+    }
+
+    // Compute the row numbers below the projections that don't output the keys
+    let mut projections = vec![];
+    while !keys.iter().all(|key| {
+        key.column_refs()
+            .iter()
+            .all(|c| input.schema().has_column(c))
+    }) {
+        let LogicalPlan::Projection(projection) = input.as_ref() else {
+            return Ok(None);
+        };
+        let replace_map = projection
+            .schema
+            .iter()
+            .zip(&projection.expr)
+            .map(|((qualifier, field), expr)| {
+                let column = Column::new(qualifier.cloned(), field.name());
+                (column.flat_name(), expr.clone().unalias())
+            })
+            .collect::<HashMap<_, _>>();
+        sort_exprs = sort_exprs
+            .into_iter()
+            .map(|sort| {
+                let expr = replace_cols_by_name(sort.expr.clone(), &replace_map)?;
+                Ok(SortExpr { expr, ..sort })
+            })
+            .collect::<Result<_>>()?;
+        projections.push(projection.expr.clone());
+        input = Arc::clone(&projection.input);
+    }
+
+    let Some(row_number) = config
+        .function_registry()
+        .and_then(|registry| registry.udwf("row_number").ok())
+    else {
+        return Ok(None);
+    };
+    let row_number = Expr::WindowFunction(WindowFunction::new(
+        WindowFunctionDefinition::WindowUDF(row_number),
+        vec![],
+    ))
+    .partition_by(keys)
+    .order_by(sort_exprs)
+    .build()?
+    .alias(ROW_NUMBER_COLUMN);
+    let mut predicate = col(ROW_NUMBER_COLUMN).gt(lit(skip as u64));
+    if let Some(fetch) = fetch {
+        predicate =
+            predicate.and(col(ROW_NUMBER_COLUMN).lt_eq(lit((skip + fetch) as u64)));
+    }
+    let columns = input.schema().columns().into_iter().map(Expr::Column);
+    let mut plan = LogicalPlanBuilder::from(Arc::unwrap_or_clone(input))
+        .window(vec![row_number])?
+        .filter(predicate)?
+        .project(columns)?;
+    for expr in projections.into_iter().rev() {
+        plan = plan.project(expr)?;
+    }
+    plan.build().map(Some)
+}
+
+/// Returns the expression of the subquery compared with outer columns by
+/// `predicate`, if it's such an equality
+fn correlated_key(predicate: &Expr) -> Option<&Expr> {
+    let Expr::BinaryExpr(BinaryExpr {
+        left,
+        op: Operator::Eq,
+        right,
+    }) = predicate
+    else {
+        return None;
+    };
+    let is_outer = |expr: &Expr| expr.contains_outer() && expr.column_refs().is_empty();
+    match (left.as_ref(), right.as_ref()) {
+        (key, outer) | (outer, key) if is_outer(outer) && !key.contains_outer() => {
+            Some(key)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use arrow::datatypes::DataType;
+    use datafusion_expr::out_ref_col;
+    use datafusion_expr::test::function_stub::sum;
+
+    /// Plans `customer, LATERAL (subquery) AS alias`
+    fn lateral_join(subquery: LogicalPlan, join_type: JoinType) -> Result<LogicalPlan> {
+        let outer_ref_columns = subquery.all_out_ref_exprs();
+        let right = subquery_alias(
+            LogicalPlan::Subquery(Subquery {
+                subquery: Arc::new(subquery),
+                outer_ref_columns,
+            }),
+            "o",
+        )?;
+        LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .join_on(right, join_type, None)?
+            .build()
+    }
+
+    #[test]
+    fn lateral_correlated_filter() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let plan = lateral_join(subquery, JoinType::Inner)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64]\
+        \n  Inner Join:  Filter: o.o_custkey = customer.c_custkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64, o_custkey:Int64]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    SubqueryAlias: o [o_orderkey:Int64, o_custkey:Int64]\
+        \n      Projection: orders.o_orderkey, orders.o_custkey [o_orderkey:Int64, o_custkey:Int64]\
+        \n        TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lateral_left_join_correlated_filter() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey"))
+                    .and(col("orders.o_totalprice").gt(lit(100.0))),
+            )?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let plan = lateral_join(subquery, JoinType::Left)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64;N]\
+        \n  Left Join:  Filter: o.o_custkey = customer.c_custkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64;N, o_custkey:Int64;N]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    SubqueryAlias: o [o_orderkey:Int64, o_custkey:Int64]\
+        \n      Projection: orders.o_orderkey, orders.o_custkey [o_orderkey:Int64, o_custkey:Int64]\
+        \n        Filter: orders.o_totalprice > Float64(100) [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lateral_uncorrelated() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let plan = lateral_join(subquery, JoinType::Inner)?;
+
+        let expected = "Cross Join:  [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64]\
+        \n  TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n  SubqueryAlias: o [o_orderkey:Int64]\
+        \n    Projection: orders.o_orderkey [o_orderkey:Int64]\
+        \n      TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    /// An aggregate without grouping produces a row for every row of the
+    /// left side, even without matching rows, so it isn't decorrelated
+    #[test]
+    fn lateral_scalar_aggregate_unchanged() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .aggregate(Vec::<Expr>::new(), vec![sum(col("orders.o_totalprice"))])?
+            .build()?;
+        let plan = lateral_join(subquery, JoinType::Inner)?;
+
+        let expected = "Cross Join:  [c_custkey:Int64, c_name:Utf8, sum(orders.o_totalprice):Float64;N]\
+        \n  TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n  SubqueryAlias: o [sum(orders.o_totalprice):Float64;N]\
+        \n    Subquery: [sum(orders.o_totalprice):Float64;N]\
+        \n      Aggregate: groupBy=[[]], aggr=[[sum(orders.o_totalprice)]] [sum(orders.o_totalprice):Float64;N]\
+        \n        Filter: orders.o_custkey = outer_ref(customer.c_custkey) [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    /// Limits are rewritten with `row_number()`, which isn't available
+    /// without a function registry
+    #[test]
+    fn lateral_limit_without_registry_unchanged() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .sort(vec![col("orders.o_totalprice").sort(false, true)])?
+            .limit(0, Some(3))?
+            .build()?;
+        let plan = lateral_join(subquery, JoinType::Inner)?;
+
+        let expected = "Cross Join:  [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n  TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n  SubqueryAlias: o [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n    Subquery: [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n      Limit: skip=0, fetch=3 [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n        Sort: orders.o_totalprice DESC NULLS FIRST [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n          Filter: orders.o_custkey = outer_ref(customer.c_custkey) [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n            TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+}
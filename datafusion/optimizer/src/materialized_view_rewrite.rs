@@ -0,0 +1,485 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`MaterializedViewRewrite`] rewrites queries to read from materialized views
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use crate::plan_signature::LogicalPlanSignature;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, DFSchema, Result, TableReference};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{
+    Expr, Filter, LogicalPlan, LogicalPlanBuilder, Projection, TableSource,
+};
+
+/// A materialized view that [`MaterializedViewRewrite`] may rewrite queries
+/// to read from.
+#[derive(Clone)]
+pub struct MaterializedViewDefinition {
+    name: TableReference,
+    plan: LogicalPlan,
+    source: Arc<dyn TableSource>,
+}
+
+impl MaterializedViewDefinition {
+    /// Create the definition of the materialized view `name`, whose stored
+    /// results are the results of the analyzed `plan` and are read from
+    /// `source`
+    pub fn new(
+        name: impl Into<TableReference>,
+        plan: LogicalPlan,
+        source: Arc<dyn TableSource>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            plan,
+            source,
+        }
+    }
+
+    /// The name of the view
+    pub fn name(&self) -> &TableReference {
+        &self.name
+    }
+
+    /// The analyzed plan that computes the results of the view
+    pub fn plan(&self) -> &LogicalPlan {
+        &self.plan
+    }
+
+    /// The source to read the stored results of the view from
+    pub fn source(&self) -> &Arc<dyn TableSource> {
+        &self.source
+    }
+}
+
+impl Debug for MaterializedViewDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaterializedViewDefinition")
+            .field("name", &self.name)
+            .field("plan", &self.plan)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Optimizer rule that rewrites the parts of a plan computed by a
+/// materialized view to read the stored results of the view instead.
+///
+/// A view matches a part of the plan if both apply a projection and filters
+/// to the same input, the filters of the view are a subset of the filters of
+/// the plan and the remaining filters and the projection of the plan only
+/// use expressions that the view returns. For example, given the view
+///
+/// ```sql
+/// CREATE MATERIALIZED VIEW v AS SELECT a, b FROM t WHERE a > 1
+/// ```
+///
+/// the query `SELECT b + 1 FROM t WHERE a > 1 AND b < 5` is rewritten to
+/// read from `v` as `SELECT b + 1 FROM v WHERE b < 5`.
+///
+/// The views are registered with [`OptimizerConfig::materialized_views`] and
+/// are only used if `datafusion.optimizer.enable_materialized_view_rewrite`
+/// is set. They are compared to the plan before other rules change it, so
+/// this rule must run first.
+#[derive(Default, Debug)]
+pub struct MaterializedViewRewrite {}
+
+impl MaterializedViewRewrite {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for MaterializedViewRewrite {
+    fn name(&self) -> &str {
+        "materialized_view_rewrite"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let views = config.materialized_views();
+        if !config.options().optimizer.enable_materialized_view_rewrite
+            || views.is_empty()
+        {
+            return Ok(Transformed::no(plan));
+        }
+
+        // Index the views by the signature of their input to only compare
+        // the parts of the plan with the views that may match them
+        let mut candidates: HashMap<LogicalPlanSignature, Vec<ViewCandidate>> =
+            HashMap::new();
+        for view in views {
+            if let Some(candidate) = ViewCandidate::try_new(view) {
+                candidates
+                    .entry(LogicalPlanSignature::new(candidate.parts.input))
+                    .or_default()
+                    .push(candidate);
+            }
+        }
+
+        plan.transform_down_with_subqueries(|plan| {
+            let parts = PlanParts::new(&plan);
+            let Some(views) = candidates.get(&LogicalPlanSignature::new(parts.input))
+            else {
+                return Ok(Transformed::no(plan));
+            };
+            for view in views {
+                if let Some(rewritten) = view.try_rewrite(&plan, &parts)? {
+                    // the rewritten plan reads the view, do not visit it again
+                    return Ok(Transformed::new(
+                        rewritten,
+                        true,
+                        TreeNodeRecursion::Jump,
+                    ));
+                }
+            }
+            Ok(Transformed::no(plan))
+        })
+    }
+}
+
+/// A plan split into a projection of the filters of an input
+struct PlanParts<'a> {
+    /// The projected expressions, or `None` to return the columns of `input`
+    exprs: Option<&'a [Expr]>,
+    /// The conjuncts of the filters
+    predicates: Vec<&'a Expr>,
+    input: &'a LogicalPlan,
+}
+
+impl<'a> PlanParts<'a> {
+    fn new(plan: &'a LogicalPlan) -> Self {
+        let (exprs, plan) = match plan {
+            LogicalPlan::Projection(projection) => {
+                (Some(projection.expr.as_slice()), projection.input.as_ref())
+            }
+            _ => (None, plan),
+        };
+        let (predicates, input) = match plan {
+            LogicalPlan::Filter(filter) => {
+                (split_conjunction(&filter.predicate), filter.input.as_ref())
+            }
+            _ => (vec![], plan),
+        };
+        Self {
+            exprs,
+            predicates,
+            input,
+        }
+    }
+
+    /// The expressions that compute the output of the plan from `input`
+    fn output_exprs(&self) -> Vec<Expr> {
+        match self.exprs {
+            Some(exprs) => exprs.to_vec(),
+            None => self
+                .input
+                .schema()
+                .columns()
+                .into_iter()
+                .map(Expr::Column)
+                .collect(),
+        }
+    }
+}
+
+/// A materialized view split into its parts to match the plan against
+struct ViewCandidate<'a> {
+    view: &'a MaterializedViewDefinition,
+    parts: PlanParts<'a>,
+}
+
+impl<'a> ViewCandidate<'a> {
+    /// Returns `None` if the view can not be used to rewrite queries
+    fn try_new(view: &'a MaterializedViewDefinition) -> Option<Self> {
+        // the results of volatile expressions differ each time they are computed
+        let volatile = view
+            .plan
+            .exists(|plan| Ok(plan.expressions().iter().any(Expr::is_volatile)))
+            .unwrap_or(true);
+        if volatile {
+            return None;
+        }
+        Some(Self {
+            view,
+            parts: PlanParts::new(&view.plan),
+        })
+    }
+
+    /// Rewrite `plan`, split into `parts`, to read from the view, returning
+    /// `None` if the view does not match the plan
+    fn try_rewrite(
+        &self,
+        plan: &LogicalPlan,
+        parts: &PlanParts,
+    ) -> Result<Option<LogicalPlan>> {
+        if parts.input != self.parts.input {
+            return Ok(None);
+        }
+
+        // the plan must keep the rows that the view keeps, and may only
+        // filter out more rows
+        let mut residual = parts.predicates.clone();
+        for predicate in &self.parts.predicates {
+            match residual.iter().position(|p| p == predicate) {
+                Some(index) => residual.remove(index),
+                None => return Ok(None),
+            };
+        }
+
+        let scan = LogicalPlanBuilder::scan(
+            self.view.name.clone(),
+            Arc::clone(&self.view.source),
+            None,
+        )?
+        .build()?;
+        let scan_schema = Arc::clone(scan.schema());
+        let replacements = self
+            .parts
+            .output_exprs()
+            .into_iter()
+            .map(Expr::unalias)
+            .zip(scan_schema.columns().into_iter().map(Expr::Column))
+            .collect::<Vec<_>>();
+
+        let residual = residual
+            .into_iter()
+            .map(|predicate| {
+                replace_exprs(predicate.clone(), &replacements, &scan_schema)
+            })
+            .collect::<Result<Option<Vec<_>>>>()?;
+        let Some(residual) = residual else {
+            return Ok(None);
+        };
+
+        let mut exprs = Vec::with_capacity(plan.schema().fields().len());
+        for (expr, (qualifier, field)) in
+            parts.output_exprs().into_iter().zip(plan.schema().iter())
+        {
+            let Some(expr) = replace_exprs(expr.unalias(), &replacements, &scan_schema)?
+            else {
+                return Ok(None);
+            };
+            exprs.push(expr.alias_qualified(qualifier.cloned(), field.name()));
+        }
+
+        let input = match conjunction(residual) {
+            Some(predicate) => {
+                LogicalPlan::Filter(Filter::try_new(predicate, Arc::new(scan))?)
+            }
+            None => scan,
+        };
+        Projection::try_new(exprs, Arc::new(input))
+            .map(|projection| Some(LogicalPlan::Projection(projection)))
+    }
+}
+
+/// Replace the expressions in `expr` that the view returns with the columns
+/// of the view, returning `None` if the result still references columns that
+/// are not in the view
+fn replace_exprs(
+    expr: Expr,
+    replacements: &[(Expr, Expr)],
+    view_schema: &DFSchema,
+) -> Result<Option<Expr>> {
+    // the columns that subqueries reference can not be replaced
+    if expr.exists(|e| {
+        Ok(matches!(
+            e,
+            Expr::ScalarSubquery(_) | Expr::InSubquery(_) | Expr::Exists(_)
+        ))
+    })? {
+        return Ok(None);
+    }
+
+    let expr = expr
+        .transform_down(|e| match replacements.iter().find(|(from, _)| *from == e) {
+            Some((_, to)) => {
+                Ok(Transformed::new(to.clone(), true, TreeNodeRecursion::Jump))
+            }
+            None => Ok(Transformed::no(e)),
+        })?
+        .data;
+
+    let unresolved = expr.exists(|e| {
+        Ok(matches!(e, Expr::Column(column) if !has_column(view_schema, column)))
+    })?;
+    Ok((!unresolved).then_some(expr))
+}
+
+fn has_column(schema: &DFSchema, column: &Column) -> bool {
+    column.relation.is_some() && schema.has_column(column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use crate::OptimizerContext;
+
+    use arrow::datatypes::{Field, Schema};
+    use datafusion_expr::logical_plan::builder::LogicalTableSource;
+    use datafusion_expr::test::function_stub::sum;
+    use datafusion_expr::{col, in_subquery, lit};
+
+    fn view(name: &str, plan: LogicalPlan) -> Arc<MaterializedViewDefinition> {
+        let schema = Schema::new(
+            plan.schema()
+                .fields()
+                .iter()
+                .map(|field| Field::new(field.name(), field.data_type().clone(), true))
+                .collect::<Vec<_>>(),
+        );
+        Arc::new(MaterializedViewDefinition::new(
+            name,
+            plan,
+            Arc::new(LogicalTableSource::new(Arc::new(schema))),
+        ))
+    }
+
+    fn optimize(
+        plan: LogicalPlan,
+        views: Vec<Arc<MaterializedViewDefinition>>,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let config = OptimizerContext::new()
+            .with_materialized_views(views)
+            .with_materialized_view_rewrite(true);
+        MaterializedViewRewrite::new().rewrite(plan, &config)
+    }
+
+    fn assert_rewritten(
+        plan: LogicalPlan,
+        views: Vec<Arc<MaterializedViewDefinition>>,
+        expected: &str,
+    ) -> Result<()> {
+        let schema = Arc::clone(plan.schema());
+        let optimized = optimize(plan, views)?;
+        assert!(optimized.transformed);
+        assert_eq!(format!("{}", optimized.data.display_indent()), expected);
+        assert!(optimized.data.schema().equivalent_names_and_types(&schema));
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_same_plan() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .project(vec![col("a"), col("b")])?
+            .build()?;
+
+        let expected = "Projection: v.a AS a, v.b AS b\
+        \n  TableScan: v";
+        assert_rewritten(definition.clone(), vec![view("v", definition)], expected)
+    }
+
+    #[test]
+    fn rewrite_subsumed_filter() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .project(vec![col("a"), col("b").alias("x")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)).and(col("b").lt(lit(5u32))))?
+            .project(vec![(col("b") + lit(1u32)).alias("y")])?
+            .build()?;
+
+        let expected = "Projection: v.x + UInt32(1) AS y\
+        \n  Filter: v.x < UInt32(5)\
+        \n    TableScan: v";
+        assert_rewritten(plan, vec![view("v", definition)], expected)
+    }
+
+    #[test]
+    fn rewrite_input_of_aggregate() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("a"), col("b"), col("c")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[test.a]], aggr=[[sum(test.b)]]\
+        \n  Projection: v.a AS a, v.b AS b, v.c AS c\
+        \n    TableScan: v";
+        assert_rewritten(plan, vec![view("v", definition)], expected)
+    }
+
+    #[test]
+    fn no_rewrite() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .project(vec![col("a")])?
+            .build()?;
+
+        // the view filters out rows the query needs
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(0u32)))?
+            .project(vec![col("a")])?
+            .build()?;
+        assert!(!optimize(plan, vec![view("v", definition.clone())])?.transformed);
+
+        // the view does not return the columns the query needs
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .project(vec![col("b")])?
+            .build()?;
+        assert!(!optimize(plan, vec![view("v", definition.clone())])?.transformed);
+
+        // the rewrite is disabled by default
+        let config = OptimizerContext::new()
+            .with_materialized_views(vec![view("v", definition.clone())]);
+        assert!(
+            !MaterializedViewRewrite::new()
+                .rewrite(definition, &config)?
+                .transformed
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_in_subquery() -> Result<()> {
+        let table_scan = test_table_scan_with_name("sq")?;
+        let definition = LogicalPlanBuilder::from(table_scan.clone())
+            .project(vec![col("sq.a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(in_subquery(col("test.a"), Arc::new(definition.clone())))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b\
+        \n  Filter: test.a IN (<subquery>)\
+        \n    Subquery:\
+        \n      Projection: v.a AS a\
+        \n        TableScan: v\
+        \n    TableScan: test";
+        assert_rewritten(plan, vec![view("v", definition)], expected)
+    }
+}
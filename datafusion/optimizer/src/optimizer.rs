@@ -32,6 +32,7 @@ use datafusion_common::{internal_err, DFSchema, DataFusionError, HashSet, Result
 use datafusion_expr::logical_plan::LogicalPlan;
 
 use crate::common_subexpr_eliminate::CommonSubexprEliminate;
+use crate::decorrelate_lateral_join::DecorrelateLateralJoin;
 use crate::decorrelate_predicate_subquery::DecorrelatePredicateSubquery;
 use crate::eliminate_cross_join::EliminateCrossJoin;
 use crate::eliminate_duplicated_expr::EliminateDuplicatedExpr;
@@ -44,6 +45,9 @@ use crate::eliminate_one_union::EliminateOneUnion;
 use crate::eliminate_outer_join::EliminateOuterJoin;
 use crate::extract_equijoin_predicate::ExtractEquijoinPredicate;
 use crate::filter_null_join_keys::FilterNullJoinKeys;
+use crate::materialized_view_rewrite::{
+    MaterializedViewDefinition, MaterializedViewRewrite,
+};
 use crate::optimize_projections::OptimizeProjections;
 use crate::plan_signature::LogicalPlanSignature;
 use crate::propagate_empty_relation::PropagateEmptyRelation;
@@ -132,6 +136,12 @@ pub trait OptimizerConfig {
     fn function_registry(&self) -> Option<&dyn FunctionRegistry> {
         None
     }
+
+    /// Return the materialized views that [`MaterializedViewRewrite`] may
+    /// rewrite queries to read from
+    fn materialized_views(&self) -> &[Arc<MaterializedViewDefinition>] {
+        &[]
+    }
 }
 
 /// A standalone [`OptimizerConfig`] that can be used independently
@@ -146,6 +156,9 @@ pub struct OptimizerContext {
     alias_generator: Arc<AliasGenerator>,
 
     options: ConfigOptions,
+
+    /// Materialized views that queries may be rewritten to read from
+    materialized_views: Vec<Arc<MaterializedViewDefinition>>,
 }
 
 impl OptimizerContext {
@@ -158,6 +171,7 @@ impl OptimizerContext {
             query_execution_start_time: Utc::now(),
             alias_generator: Arc::new(AliasGenerator::new()),
             options,
+            materialized_views: vec![],
         }
     }
 
@@ -189,6 +203,21 @@ impl OptimizerContext {
         self.options.optimizer.max_passes = v as usize;
         self
     }
+
+    /// Specify whether queries may be rewritten to read from materialized views
+    pub fn with_materialized_view_rewrite(mut self, enabled: bool) -> Self {
+        self.options.optimizer.enable_materialized_view_rewrite = enabled;
+        self
+    }
+
+    /// Specify the materialized views that queries may be rewritten to read from
+    pub fn with_materialized_views(
+        mut self,
+        materialized_views: Vec<Arc<MaterializedViewDefinition>>,
+    ) -> Self {
+        self.materialized_views = materialized_views;
+        self
+    }
 }
 
 impl Default for OptimizerContext {
@@ -210,6 +239,10 @@ impl OptimizerConfig for OptimizerContext {
     fn options(&self) -> &ConfigOptions {
         &self.options
     }
+
+    fn materialized_views(&self) -> &[Arc<MaterializedViewDefinition>] {
+        &self.materialized_views
+    }
 }
 
 /// A rule-based optimizer.
@@ -241,6 +274,8 @@ impl Optimizer {
     /// Create a new optimizer using the recommended list of rules
     pub fn new() -> Self {
         let rules: Vec<Arc<dyn OptimizerRule + Sync + Send>> = vec![
+            // Must be first, to compare the views to the plan as it was analyzed
+            Arc::new(MaterializedViewRewrite::new()),
             Arc::new(EliminateNestedUnion::new()),
             Arc::new(SimplifyExpressions::new()),
             Arc::new(UnwrapCastInComparison::new()),
@@ -248,6 +283,7 @@ impl Optimizer {
             Arc::new(EliminateJoin::new()),
             Arc::new(DecorrelatePredicateSubquery::new()),
             Arc::new(ScalarSubqueryToJoin::new()),
+            Arc::new(DecorrelateLateralJoin::new()),
             Arc::new(ExtractEquijoinPredicate::new()),
             Arc::new(EliminateDuplicatedExpr::new()),
             Arc::new(EliminateFilter::new()),
@@ -26,6 +26,7 @@ use datafusion_common::tree_node::{
 use datafusion_common::{
     internal_err, plan_err, qualified_name, Column, DFSchema, Result,
 };
+use datafusion_expr::builder::get_unnested_ordinality_column;
 use datafusion_expr::expr_rewriter::replace_col;
 use datafusion_expr::logical_plan::{Join, JoinType, LogicalPlan, TableScan, Union};
 use datafusion_expr::utils::{
@@ -165,6 +166,7 @@ pub(crate) fn lr_is_preserved(join_type: JoinType) -> (bool, bool) {
         // No columns from the left side of the join can be referenced in output
         // predicates for semi/anti joins, so whether we specify t/f doesn't matter.
         JoinType::RightSemi | JoinType::RightAnti => (false, true),
+        JoinType::AsOf => (true, false),
     }
 }
 
@@ -187,6 +189,9 @@ pub(crate) fn on_lr_is_preserved(join_type: JoinType) -> (bool, bool) {
         JoinType::LeftAnti => (false, true),
         JoinType::RightAnti => (true, false),
         JoinType::LeftMark => (false, true),
+        // The filter of an as-of join is its match condition, which must stay
+        // in the join
+        JoinType::AsOf => (false, false),
     }
 }
 
@@ -281,6 +286,8 @@ fn can_evaluate_as_join_condition(predicate: &Expr) -> Result<bool> {
         | Expr::TryCast(_)
         | Expr::InList { .. }
         | Expr::ScalarFunction(_) => Ok(TreeNodeRecursion::Continue),
+        // The body of a lambda only refers to its parameters
+        Expr::Lambda(_) => Ok(TreeNodeRecursion::Jump),
         Expr::AggregateFunction(_)
         | Expr::WindowFunction(_)
         | Expr::Wildcard { .. }
@@ -672,7 +679,9 @@ fn infer_join_predicates_from_on_filters(
     inferred_predicates: &mut InferredPredicates,
 ) -> Result<()> {
     match join_type {
-        JoinType::Full | JoinType::LeftAnti | JoinType::RightAnti => Ok(()),
+        JoinType::Full | JoinType::LeftAnti | JoinType::RightAnti | JoinType::AsOf => {
+            Ok(())
+        }
         JoinType::Inner => infer_join_predicates_impl::<true, true>(
             join_col_keys,
             on_filters,
@@ -861,6 +870,13 @@ impl OptimizerRule for PushDownFilter {
 
                     if unnest.list_type_columns.iter().any(|(_, unnest_list)| {
                         accum.contains(&unnest_list.output_column)
+                            || (unnest.options.ordinality
+                                && accum.contains(
+                                    &get_unnested_ordinality_column(
+                                        &unnest_list.output_column.name,
+                                    )
+                                    .0,
+                                ))
                     }) {
                         unnest_predicates.push(predicate);
                     } else {
@@ -27,7 +27,7 @@ use datafusion_expr::simplify::SimplifyContext;
 use datafusion_expr::utils::merge_schema;
 
 use crate::optimizer::ApplyOrder;
-use crate::utils::NamePreserver;
+use crate::utils::{merge_target_table_schema, NamePreserver};
 use crate::{OptimizerConfig, OptimizerRule};
 
 use super::ExprSimplifier;
@@ -80,7 +80,9 @@ impl SimplifyExpressions {
         execution_props: &ExecutionProps,
     ) -> Result<Transformed<LogicalPlan>> {
         let schema = if !plan.inputs().is_empty() {
-            DFSchemaRef::new(merge_schema(&plan.inputs()))
+            let mut schema = merge_schema(&plan.inputs());
+            merge_target_table_schema(&plan, &mut schema);
+            DFSchemaRef::new(schema)
         } else if let LogicalPlan::TableScan(scan) = &plan {
             // When predicates are pushed into a table scan, there is no input
             // schema to resolve predicates against, so it must be handled specially
@@ -24,7 +24,7 @@ use std::sync::Arc;
 use crate::optimizer::ApplyOrder;
 use crate::{OptimizerConfig, OptimizerRule};
 
-use crate::utils::NamePreserver;
+use crate::utils::{merge_target_table_schema, NamePreserver};
 use arrow::datatypes::{
     DataType, TimeUnit, MAX_DECIMAL_FOR_EACH_PRECISION, MIN_DECIMAL_FOR_EACH_PRECISION,
 };
@@ -109,6 +109,7 @@ impl OptimizerRule for UnwrapCastInComparison {
             schema.merge(&source_schema);
         }
 
+        merge_target_table_schema(&plan, &mut schema);
         schema.merge(plan.schema());
 
         let mut expr_rewriter = UnwrapCastExprRewriter {
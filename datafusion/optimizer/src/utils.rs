@@ -29,7 +29,8 @@ use datafusion_common::tree_node::{TransformedResult, TreeNode};
 use datafusion_common::{Column, DFSchema, Result, ScalarValue};
 use datafusion_expr::execution_props::ExecutionProps;
 use datafusion_expr::expr_rewriter::replace_col;
-use datafusion_expr::{logical_plan::LogicalPlan, ColumnarValue, Expr};
+use datafusion_expr::logical_plan::{DmlStatement, LogicalPlan, WriteOp};
+use datafusion_expr::{ColumnarValue, Expr};
 use datafusion_physical_expr::create_physical_expr;
 use log::{debug, trace};
 use std::sync::Arc;
@@ -87,6 +88,22 @@ pub(crate) fn has_all_column_refs(expr: &Expr, schema_cols: &HashSet<Column>) ->
         == column_refs.len()
 }
 
+/// Merges the schema of the target table of a `MERGE` statement into `schema`.
+///
+/// The expressions of a `MERGE` refer to the target table as well as to its
+/// input, so rules that resolve the expressions of a plan against the schemas
+/// of its inputs need the target table too.
+pub(crate) fn merge_target_table_schema(plan: &LogicalPlan, schema: &mut DFSchema) {
+    if let LogicalPlan::Dml(DmlStatement {
+        op: WriteOp::Merge(_),
+        table_schema,
+        ..
+    }) = plan
+    {
+        schema.merge(table_schema);
+    }
+}
+
 pub(crate) fn collect_subquery_cols(
     exprs: &[Expr],
     subquery_schema: &DFSchema,
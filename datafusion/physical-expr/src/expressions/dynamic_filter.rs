@@ -0,0 +1,480 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Filter expressions whose predicate is only known at runtime
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::expressions::in_list::try_cast_static_filter_to_set;
+use crate::expressions::{lit, BinaryExpr, InListExpr, Literal};
+use crate::physical_expr::down_cast_any_ref;
+use crate::PhysicalExpr;
+
+use arrow::array::{make_comparator, Array};
+use arrow::compute::SortOptions;
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::{ColumnarValue, Operator};
+
+/// Bounds of the non-null values of one column, as observed at runtime
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnBounds {
+    /// The smallest value
+    pub min: ScalarValue,
+    /// The largest value
+    pub max: ScalarValue,
+    /// The distinct values, if there are few enough of them to list
+    pub values: Option<Vec<ScalarValue>>,
+}
+
+impl ColumnBounds {
+    /// Computes the bounds of the non-null values in `array`, listing the
+    /// distinct values if there are at most `max_distinct_values` of them.
+    ///
+    /// Returns `None` if `array` contains no non-null values.
+    pub fn try_new(
+        array: &dyn Array,
+        max_distinct_values: usize,
+    ) -> Result<Option<Self>> {
+        let mut non_null = (0..array.len()).filter(|i| array.is_valid(*i));
+        let Some(first) = non_null.next() else {
+            return Ok(None);
+        };
+
+        let cmp = make_comparator(array, array, SortOptions::default())?;
+        let (mut min, mut max) = (first, first);
+        for i in non_null {
+            if cmp(i, min) == Ordering::Less {
+                min = i;
+            } else if cmp(i, max) == Ordering::Greater {
+                max = i;
+            }
+        }
+
+        let mut distinct = HashSet::new();
+        for i in (0..array.len()).filter(|i| array.is_valid(*i)) {
+            distinct.insert(ScalarValue::try_from_array(array, i)?);
+            if distinct.len() > max_distinct_values {
+                break;
+            }
+        }
+        let values = (distinct.len() <= max_distinct_values)
+            .then(|| sorted_values(distinct.into_iter().collect()));
+
+        Ok(Some(Self {
+            min: ScalarValue::try_from_array(array, min)?,
+            max: ScalarValue::try_from_array(array, max)?,
+            values,
+        }))
+    }
+
+    /// Returns the bounds covering the values of both `self` and `other`
+    pub fn merge(self, other: Self, max_distinct_values: usize) -> Self {
+        let min = match self.min.partial_cmp(&other.min) {
+            Some(Ordering::Greater) => other.min,
+            _ => self.min,
+        };
+        let max = match self.max.partial_cmp(&other.max) {
+            Some(Ordering::Less) => other.max,
+            _ => self.max,
+        };
+        let values = match (self.values, other.values) {
+            (Some(mut values), Some(other)) => {
+                values.extend(other);
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                values.dedup();
+                (values.len() <= max_distinct_values).then_some(values)
+            }
+            _ => None,
+        };
+        Self { min, max, values }
+    }
+}
+
+fn sorted_values(mut values: Vec<ScalarValue>) -> Vec<ScalarValue> {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    values
+}
+
+/// The values published to a [`DynamicFilterExpr`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicFilterValues {
+    /// No value can pass the filter
+    Empty,
+    /// Only values within these bounds, one per child of the filter, can pass
+    Bounds(Vec<ColumnBounds>),
+}
+
+/// A filter on its children whose predicate is published while the query
+/// is executing, for example by a join once it has seen its build side.
+///
+/// Until values are published the filter lets every row pass. Once they
+/// are, a row passes if each child lies within the corresponding
+/// [`ColumnBounds`] and, where the distinct values are known, is one of
+/// them. The values are shared between all copies of the expression made
+/// with [`PhysicalExpr::with_new_children`], so a scan can hold a remapped
+/// copy of the filter that the join publishes to. [`Self::reset`] withdraws
+/// the values from all copies, so that a plan that is executed again
+/// doesn't filter with the values of its previous execution.
+///
+/// The filter never rejects a row that could have matched, so consumers
+/// may apply it (or not) wherever convenient. Pruning code that inspects
+/// the expression tree should first call [`snapshot_dynamic_filters`].
+#[derive(Debug)]
+pub struct DynamicFilterExpr {
+    children: Vec<Arc<dyn PhysicalExpr>>,
+    /// The published values, shared with all copies of this filter
+    values: Arc<RwLock<Option<Arc<DynamicFilterValues>>>>,
+    /// The predicate on `children` built from the published values, and the
+    /// values it was built from
+    predicate: Mutex<Option<(Arc<DynamicFilterValues>, Arc<dyn PhysicalExpr>)>>,
+}
+
+impl DynamicFilterExpr {
+    /// Create a new filter on `children` with no values published yet
+    pub fn new(children: Vec<Arc<dyn PhysicalExpr>>) -> Self {
+        Self {
+            children,
+            values: Arc::new(RwLock::new(None)),
+            predicate: Mutex::new(None),
+        }
+    }
+
+    /// Publish the values of this filter.
+    ///
+    /// Values can only be published once, later calls are ignored until the
+    /// filter is [reset](Self::reset).
+    pub fn update(&self, values: DynamicFilterValues) {
+        let mut current = self.values.write().unwrap();
+        if current.is_none() {
+            *current = Some(Arc::new(values));
+        }
+    }
+
+    /// Withdraws the published values from this filter and all its copies,
+    /// which let every row pass again until new values are published
+    pub fn reset(&self) {
+        *self.values.write().unwrap() = None;
+    }
+
+    /// Returns true if values have been published to this filter
+    pub fn is_published(&self) -> bool {
+        self.values.read().unwrap().is_some()
+    }
+
+    /// Returns the predicate this filter currently evaluates, which is
+    /// `true` until values are published
+    pub fn current(&self) -> Result<Arc<dyn PhysicalExpr>> {
+        let Some(values) = self.values.read().unwrap().clone() else {
+            return Ok(lit(true));
+        };
+        let mut cached = self.predicate.lock().unwrap();
+        if let Some((cached_values, predicate)) = cached.as_ref() {
+            if Arc::ptr_eq(cached_values, &values) {
+                return Ok(Arc::clone(predicate));
+            }
+        }
+        let predicate = self.build_predicate(&values)?;
+        *cached = Some((values, Arc::clone(&predicate)));
+        Ok(predicate)
+    }
+
+    fn build_predicate(
+        &self,
+        values: &DynamicFilterValues,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        let bounds = match values {
+            DynamicFilterValues::Empty => return Ok(lit(false)),
+            DynamicFilterValues::Bounds(bounds) => bounds,
+        };
+
+        let mut predicate: Option<Arc<dyn PhysicalExpr>> = None;
+        let mut and = |expr: Arc<dyn PhysicalExpr>| {
+            predicate = Some(match predicate.take() {
+                Some(p) => Arc::new(BinaryExpr::new(p, Operator::And, expr)),
+                None => expr,
+            });
+        };
+        for (child, bounds) in self.children.iter().zip(bounds) {
+            let compare = |op, value: &ScalarValue| {
+                Arc::new(BinaryExpr::new(
+                    Arc::clone(child),
+                    op,
+                    Arc::new(Literal::new(value.clone())),
+                )) as Arc<dyn PhysicalExpr>
+            };
+            if bounds.min == bounds.max {
+                and(compare(Operator::Eq, &bounds.min));
+                continue;
+            }
+            and(compare(Operator::GtEq, &bounds.min));
+            and(compare(Operator::LtEq, &bounds.max));
+            if let Some(values) = &bounds.values {
+                let list: Vec<Arc<dyn PhysicalExpr>> = values
+                    .iter()
+                    .map(|v| Arc::new(Literal::new(v.clone())) as _)
+                    .collect();
+                let static_filter =
+                    try_cast_static_filter_to_set(&list, &Schema::empty()).ok();
+                and(Arc::new(InListExpr::new(
+                    Arc::clone(child),
+                    list,
+                    false,
+                    static_filter,
+                )));
+            }
+        }
+        Ok(predicate.unwrap_or_else(|| lit(true)))
+    }
+}
+
+/// Replaces every [`DynamicFilterExpr`] in `expr` whose values have been
+/// published with the predicate it currently evaluates.
+///
+/// Filters still waiting for their values are left in place.
+pub fn snapshot_dynamic_filters(
+    expr: Arc<dyn PhysicalExpr>,
+) -> Result<Transformed<Arc<dyn PhysicalExpr>>> {
+    expr.transform_up(
+        |expr| match expr.as_any().downcast_ref::<DynamicFilterExpr>() {
+            Some(filter) if filter.is_published() => {
+                Ok(Transformed::yes(filter.current()?))
+            }
+            _ => Ok(Transformed::no(expr)),
+        },
+    )
+}
+
+impl std::fmt::Display for DynamicFilterExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.current() {
+            Ok(predicate) if self.is_published() => {
+                write!(f, "DynamicFilter [ {predicate} ]")
+            }
+            _ => {
+                let children = self
+                    .children
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "DynamicFilter [ {children} ]")
+            }
+        }
+    }
+}
+
+impl PhysicalExpr for DynamicFilterExpr {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> Result<bool> {
+        for child in &self.children {
+            if child.nullable(input_schema)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        self.current()?.evaluate(batch)
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn PhysicalExpr>> {
+        self.children.iter().collect()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(Self {
+            children,
+            values: Arc::clone(&self.values),
+            predicate: Mutex::new(None),
+        }))
+    }
+
+    fn dyn_hash(&self, state: &mut dyn Hasher) {
+        let mut s = state;
+        self.children.hash(&mut s);
+        Arc::as_ptr(&self.values).hash(&mut s);
+    }
+}
+
+impl PartialEq<dyn Any> for DynamicFilterExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                Arc::ptr_eq(&self.values, &x.values)
+                    && self.children.len() == x.children.len()
+                    && self
+                        .children
+                        .iter()
+                        .zip(x.children.iter())
+                        .all(|(a, b)| a.eq(b))
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::Field;
+    use datafusion_common::cast::as_boolean_array;
+
+    fn bounds(array: &dyn Array, max_distinct_values: usize) -> ColumnBounds {
+        ColumnBounds::try_new(array, max_distinct_values)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn column_bounds() -> Result<()> {
+        let array = Int32Array::from(vec![Some(4), None, Some(-2), Some(9), Some(4)]);
+        assert_eq!(
+            bounds(&array, 3),
+            ColumnBounds {
+                min: ScalarValue::Int32(Some(-2)),
+                max: ScalarValue::Int32(Some(9)),
+                values: Some(vec![
+                    ScalarValue::Int32(Some(-2)),
+                    ScalarValue::Int32(Some(4)),
+                    ScalarValue::Int32(Some(9)),
+                ]),
+            }
+        );
+        assert_eq!(bounds(&array, 2).values, None);
+
+        let array = StringArray::from(vec![Some("b"), Some("a"), Some("c")]);
+        let merged =
+            bounds(&array, 3).merge(bounds(&StringArray::from(vec!["d", "b"]), 3), 4);
+        assert_eq!(merged.min, ScalarValue::from("a"));
+        assert_eq!(merged.max, ScalarValue::from("d"));
+        assert_eq!(merged.values.map(|v| v.len()), Some(4));
+
+        let array = Int32Array::from(vec![None, None]);
+        assert_eq!(ColumnBounds::try_new(&array, 3)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_filter_evaluate() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int32Array::from(vec![
+                Some(1),
+                Some(3),
+                None,
+                Some(5),
+                Some(8),
+            ]))],
+        )?;
+        let evaluate = |filter: &DynamicFilterExpr| -> Result<Vec<Option<bool>>> {
+            let result = filter.evaluate(&batch)?.into_array(batch.num_rows())?;
+            Ok(as_boolean_array(&result)?.iter().collect())
+        };
+
+        let filter = DynamicFilterExpr::new(vec![col("a", &schema)?]);
+        assert_eq!(filter.to_string(), "DynamicFilter [ a@0 ]");
+        assert_eq!(evaluate(&filter)?, vec![Some(true); 5]);
+
+        let array = Int32Array::from(vec![3, 8, 6]);
+        filter.update(DynamicFilterValues::Bounds(vec![bounds(&array, 3)]));
+        assert_eq!(
+            filter.to_string(),
+            "DynamicFilter [ a@0 >= 3 AND a@0 <= 8 AND Use a@0 IN (SET) ([Literal { value: Int32(3) }, Literal { value: Int32(6) }, Literal { value: Int32(8) }]) ]"
+        );
+        assert_eq!(
+            evaluate(&filter)?,
+            vec![Some(false), Some(true), None, Some(false), Some(true)]
+        );
+
+        // values can only be published once
+        filter.update(DynamicFilterValues::Empty);
+        assert_eq!(evaluate(&filter)?[1], Some(true));
+
+        let filter = DynamicFilterExpr::new(vec![col("a", &schema)?]);
+        filter.update(DynamicFilterValues::Empty);
+        assert_eq!(evaluate(&filter)?, vec![Some(false); 5]);
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_filter_shared_values() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Boolean, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let filter = Arc::new(DynamicFilterExpr::new(vec![col("a", &schema)?]));
+        let remapped = Arc::clone(&filter).with_new_children(vec![col("b", &schema)?])?;
+        let predicate = Arc::new(BinaryExpr::new(
+            col("a", &schema)?,
+            Operator::And,
+            Arc::clone(&remapped),
+        )) as Arc<dyn PhysicalExpr>;
+
+        let snapshot = snapshot_dynamic_filters(Arc::clone(&predicate))?;
+        assert!(!snapshot.transformed);
+
+        let array = Int32Array::from(vec![2, 7]);
+        filter.update(DynamicFilterValues::Bounds(vec![bounds(&array, 0)]));
+        assert_eq!(
+            remapped.to_string(),
+            "DynamicFilter [ b@1 >= 2 AND b@1 <= 7 ]"
+        );
+        let snapshot = snapshot_dynamic_filters(predicate)?;
+        assert!(snapshot.transformed);
+        assert_eq!(snapshot.data.to_string(), "a@0 AND b@1 >= 2 AND b@1 <= 7");
+
+        // resetting any copy withdraws the values from all of them
+        remapped
+            .as_any()
+            .downcast_ref::<DynamicFilterExpr>()
+            .unwrap()
+            .reset();
+        assert!(!filter.is_published());
+        assert_eq!(remapped.to_string(), "DynamicFilter [ b@1 ]");
+
+        let array = Int32Array::from(vec![4, 5]);
+        filter.update(DynamicFilterValues::Bounds(vec![bounds(&array, 0)]));
+        assert_eq!(
+            remapped.to_string(),
+            "DynamicFilter [ b@1 >= 4 AND b@1 <= 5 ]"
+        );
+        Ok(())
+    }
+}
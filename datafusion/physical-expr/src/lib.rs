@@ -33,6 +33,7 @@ pub mod planner;
 mod scalar_function;
 pub mod udf {
     pub use crate::scalar_function::create_physical_expr;
+    pub use crate::scalar_function::create_physical_expr_with_lambda;
 }
 pub mod utils;
 pub mod window;
@@ -59,7 +60,7 @@ pub use datafusion_physical_expr_common::sort_expr::{
 };
 
 pub use planner::{create_physical_expr, create_physical_exprs};
-pub use scalar_function::ScalarFunctionExpr;
+pub use scalar_function::{PhysicalLambda, ScalarFunctionExpr};
 
 pub use datafusion_physical_expr_common::utils::reverse_order_bys;
 pub use utils::split_conjunction;
@@ -296,6 +296,19 @@ pub fn create_physical_expr(
             execution_props,
         )?),
         Expr::ScalarFunction(ScalarFunction { func, args }) => {
+            if let Some((Expr::Lambda(lambda), other_args)) = args.split_last() {
+                let physical_args =
+                    create_physical_exprs(other_args, input_dfschema, execution_props)?;
+                return scalar_function::create_physical_expr_with_lambda(
+                    Arc::clone(func).as_ref(),
+                    &physical_args,
+                    lambda,
+                    input_schema,
+                    args,
+                    input_dfschema,
+                    execution_props,
+                );
+            }
             let physical_args =
                 create_physical_exprs(args, input_dfschema, execution_props)?;
 
@@ -35,16 +35,19 @@ use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use crate::physical_expr::{down_cast_any_ref, physical_exprs_equal};
+use crate::planner;
 use crate::PhysicalExpr;
 
-use arrow::datatypes::{DataType, Schema};
-use arrow::record_batch::RecordBatch;
-use arrow_array::Array;
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use arrow_array::{Array, ArrayRef};
 use datafusion_common::{internal_err, DFSchema, Result, ScalarValue};
+use datafusion_expr::execution_props::ExecutionProps;
+use datafusion_expr::expr::Lambda;
 use datafusion_expr::interval_arithmetic::Interval;
 use datafusion_expr::sort_properties::ExprProperties;
 use datafusion_expr::type_coercion::functions::data_types_with_scalar_udf;
-use datafusion_expr::{expr_vec_fmt, ColumnarValue, Expr, ScalarUDF};
+use datafusion_expr::{expr_vec_fmt, ColumnarValue, Expr, ScalarLambda, ScalarUDF};
 
 /// Physical expression of a scalar function
 pub struct ScalarFunctionExpr {
@@ -53,6 +56,9 @@ pub struct ScalarFunctionExpr {
     args: Vec<Arc<dyn PhysicalExpr>>,
     return_type: DataType,
     nullable: bool,
+    /// The lambda argument of a higher-order function, which is not one of
+    /// `args` as it is evaluated against its own parameters
+    lambda: Option<Arc<PhysicalLambda>>,
 }
 
 impl Debug for ScalarFunctionExpr {
@@ -62,6 +68,7 @@ impl Debug for ScalarFunctionExpr {
             .field("name", &self.name)
             .field("args", &self.args)
             .field("return_type", &self.return_type)
+            .field("lambda", &self.lambda)
             .finish()
     }
 }
@@ -80,6 +87,7 @@ impl ScalarFunctionExpr {
             args,
             return_type,
             nullable: true,
+            lambda: None,
         }
     }
 
@@ -111,11 +119,30 @@ impl ScalarFunctionExpr {
     pub fn nullable(&self) -> bool {
         self.nullable
     }
+
+    /// Set the lambda argument of a higher-order function, which follows `args`
+    pub fn with_lambda(mut self, lambda: Arc<PhysicalLambda>) -> Self {
+        self.lambda = Some(lambda);
+        self
+    }
+
+    /// The lambda argument of a higher-order function, if any
+    pub fn lambda(&self) -> Option<&Arc<PhysicalLambda>> {
+        self.lambda.as_ref()
+    }
 }
 
 impl fmt::Display for ScalarFunctionExpr {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}({})", self.name, expr_vec_fmt!(self.args))
+        match &self.lambda {
+            Some(lambda) if self.args.is_empty() => {
+                write!(f, "{}({lambda})", self.name)
+            }
+            Some(lambda) => {
+                write!(f, "{}({}, {lambda})", self.name, expr_vec_fmt!(self.args))
+            }
+            None => write!(f, "{}({})", self.name, expr_vec_fmt!(self.args)),
+        }
     }
 }
 
@@ -141,7 +168,13 @@ impl PhysicalExpr for ScalarFunctionExpr {
             .collect::<Result<Vec<_>>>()?;
 
         // evaluate the function
-        let output = self.fun.invoke_batch(&inputs, batch.num_rows())?;
+        let output = match &self.lambda {
+            Some(lambda) => {
+                self.fun
+                    .invoke_with_lambda(&inputs, lambda.as_ref(), batch.num_rows())?
+            }
+            None => self.fun.invoke_batch(&inputs, batch.num_rows())?,
+        };
 
         if let ColumnarValue::Array(array) = &output {
             if array.len() != batch.num_rows() {
@@ -171,15 +204,14 @@ impl PhysicalExpr for ScalarFunctionExpr {
         self: Arc<Self>,
         children: Vec<Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn PhysicalExpr>> {
-        Ok(Arc::new(
-            ScalarFunctionExpr::new(
-                &self.name,
-                Arc::clone(&self.fun),
-                children,
-                self.return_type().clone(),
-            )
-            .with_nullable(self.nullable),
-        ))
+        Ok(Arc::new(ScalarFunctionExpr {
+            fun: Arc::clone(&self.fun),
+            name: self.name.clone(),
+            args: children,
+            return_type: self.return_type.clone(),
+            nullable: self.nullable,
+            lambda: self.lambda.clone(),
+        }))
     }
 
     fn evaluate_bounds(&self, children: &[&Interval]) -> Result<Interval> {
@@ -199,6 +231,10 @@ impl PhysicalExpr for ScalarFunctionExpr {
         self.name.hash(&mut s);
         self.args.hash(&mut s);
         self.return_type.hash(&mut s);
+        if let Some(lambda) = &self.lambda {
+            lambda.params.hash(&mut s);
+            lambda.body.dyn_hash(&mut s);
+        }
         // Add `self.fun` when hash is available
     }
 
@@ -226,6 +262,13 @@ impl PartialEq<dyn Any> for ScalarFunctionExpr {
                 self.name == x.name
                     && physical_exprs_equal(&self.args, &x.args)
                     && self.return_type == x.return_type
+                    && match (&self.lambda, &x.lambda) {
+                        (Some(lambda), Some(other)) => {
+                            lambda.params == other.params && lambda.body.eq(&other.body)
+                        }
+                        (None, None) => true,
+                        _ => false,
+                    }
             })
             .unwrap_or(false)
     }
@@ -261,3 +304,112 @@ pub fn create_physical_expr(
         .with_nullable(fun.is_nullable(args, input_dfschema)),
     ))
 }
+
+/// Create a physical expression for a higher-order UDF, whose last argument
+/// `lambda` follows the arguments `input_phy_exprs`.
+pub fn create_physical_expr_with_lambda(
+    fun: &ScalarUDF,
+    input_phy_exprs: &[Arc<dyn PhysicalExpr>],
+    lambda: &Lambda,
+    input_schema: &Schema,
+    args: &[Expr],
+    input_dfschema: &DFSchema,
+    execution_props: &ExecutionProps,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let mut input_expr_types = input_phy_exprs
+        .iter()
+        .map(|e| e.data_type(input_schema))
+        .collect::<Result<Vec<_>>>()?;
+
+    // The lambda body is planned against its parameters, and its type is
+    // the type of the lambda argument
+    let params_schema =
+        lambda.params_schema(&fun.lambda_parameters(&input_expr_types)?)?;
+    let body =
+        planner::create_physical_expr(&lambda.body, &params_schema, execution_props)?;
+    input_expr_types.push(body.data_type(params_schema.as_arrow())?);
+
+    // verify that input data types is consistent with function's `TypeSignature`
+    data_types_with_scalar_udf(&input_expr_types, fun)?;
+
+    let return_type =
+        fun.return_type_from_exprs(args, input_dfschema, &input_expr_types)?;
+    let lambda = PhysicalLambda::new(
+        lambda.params.clone(),
+        Arc::clone(params_schema.inner()),
+        body,
+    );
+
+    Ok(Arc::new(
+        ScalarFunctionExpr::new(
+            fun.name(),
+            Arc::new(fun.clone()),
+            input_phy_exprs.to_vec(),
+            return_type,
+        )
+        .with_nullable(fun.is_nullable(args, input_dfschema))
+        .with_lambda(Arc::new(lambda)),
+    ))
+}
+
+/// The compiled lambda argument of a higher-order [`ScalarFunctionExpr`], such
+/// as `x -> x + 1` in `array_transform(list, x -> x + 1)`
+#[derive(Debug)]
+pub struct PhysicalLambda {
+    params: Vec<String>,
+    /// The schema of the parameters, which the body is evaluated against
+    schema: SchemaRef,
+    body: Arc<dyn PhysicalExpr>,
+}
+
+impl PhysicalLambda {
+    /// Create a new lambda, whose `body` refers to the fields of `schema`
+    pub fn new(
+        params: Vec<String>,
+        schema: SchemaRef,
+        body: Arc<dyn PhysicalExpr>,
+    ) -> Self {
+        Self {
+            params,
+            schema,
+            body,
+        }
+    }
+
+    /// The names of the parameters
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    /// The schema of the parameters
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// The body of the lambda
+    pub fn body(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.body
+    }
+}
+
+impl fmt::Display for PhysicalLambda {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.params.as_slice() {
+            [param] => write!(f, "{param} -> {}", self.body),
+            params => write!(f, "({}) -> {}", params.join(", "), self.body),
+        }
+    }
+}
+
+impl ScalarLambda for PhysicalLambda {
+    fn evaluate(&self, args: &[ArrayRef]) -> Result<ArrayRef> {
+        let num_rows = args.first().map_or(0, |arg| arg.len());
+        let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+        let batch = RecordBatch::try_new_with_options(
+            Arc::clone(&self.schema),
+            args.to_vec(),
+            &options,
+        )?;
+        self.body.evaluate(&batch)?.into_array(num_rows)
+    }
+}
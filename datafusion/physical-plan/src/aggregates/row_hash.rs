@@ -38,6 +38,7 @@ use crate::{RecordBatchStream, SendableRecordBatchStream};
 use arrow::array::*;
 use arrow::datatypes::SchemaRef;
 use arrow_schema::SortOptions;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{internal_err, DataFusionError, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::proxy::VecAllocExt;
@@ -91,6 +92,9 @@ struct SpillState {
     /// GROUP BY expressions for merging spilled data
     merging_group_by: PhysicalGroupBy,
 
+    /// Compression codec of the spill files
+    spill_compression: SpillCompression,
+
     // ========================================================================
     // STATES:
     // Fields changes during execution. Can be buffer, or state flags that
@@ -113,6 +117,8 @@ struct SpillState {
     spill_count: metrics::Count,
     /// total spilled bytes during the execution of the operator
     spilled_bytes: metrics::Count,
+    /// total size of the spill files written by the operator
+    spill_file_bytes: metrics::Count,
     /// total spilled rows during the execution of the operator
     spilled_rows: metrics::Count,
 }
@@ -526,10 +532,13 @@ impl GroupedHashAggregateStream {
             is_stream_merging: false,
             merging_aggregate_arguments,
             merging_group_by: PhysicalGroupBy::new_single(agg_group_by.expr.clone()),
+            spill_compression: context.session_config().spill_compression(),
             peak_mem_used: MetricBuilder::new(&agg.metrics)
                 .gauge("peak_mem_used", partition),
             spill_count: MetricBuilder::new(&agg.metrics).spill_count(partition),
             spilled_bytes: MetricBuilder::new(&agg.metrics).spilled_bytes(partition),
+            spill_file_bytes: MetricBuilder::new(&agg.metrics)
+                .spill_file_bytes(partition),
             spilled_rows: MetricBuilder::new(&agg.metrics).spilled_rows(partition),
         };
 
@@ -966,14 +975,16 @@ impl GroupedHashAggregateStream {
     fn spill(&mut self) -> Result<()> {
         let emit = self.emit(EmitTo::All, true)?;
         let sorted = sort_batch(&emit, self.spill_state.spill_expr.as_ref(), None)?;
-        let spillfile = self.runtime.disk_manager.create_tmp_file("HashAggSpill")?;
+        let mut spillfile = self.runtime.disk_manager.create_tmp_file("HashAggSpill")?;
         // TODO: slice large `sorted` and write to multiple files in parallel
         spill_record_batch_by_size(
             &sorted,
-            spillfile.path().into(),
+            &mut spillfile,
             sorted.schema(),
             self.batch_size,
+            self.spill_state.spill_compression,
         )?;
+        let spill_file_bytes = spillfile.current_disk_usage() as usize;
         self.spill_state.spills.push(spillfile);
 
         // Update metrics
@@ -981,6 +992,7 @@ impl GroupedHashAggregateStream {
         self.spill_state
             .spilled_bytes
             .add(sorted.get_array_memory_size());
+        self.spill_state.spill_file_bytes.add(spill_file_bytes);
         self.spill_state.spilled_rows.add(sorted.num_rows());
 
         Ok(())
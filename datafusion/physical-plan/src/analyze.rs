@@ -31,6 +31,7 @@ use crate::{DisplayFormatType, ExecutionPlan, Partitioning};
 use arrow::{array::StringBuilder, datatypes::SchemaRef, record_batch::RecordBatch};
 use datafusion_common::instant::Instant;
 use datafusion_common::{internal_err, DataFusionError, Result};
+use datafusion_execution::memory_pool::human_readable_size;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::EquivalenceProperties;
 
@@ -177,6 +178,7 @@ impl ExecutionPlan for AnalyzeExec {
         let captured_schema = Arc::clone(&self.schema);
         let verbose = self.verbose;
         let show_statistics = self.show_statistics;
+        let query_memory_pool = context.query_memory_pool().map(Arc::clone);
 
         // future that gathers the results from all the tasks in the
         // JoinSet that computes the overall row count and final
@@ -189,11 +191,13 @@ impl ExecutionPlan for AnalyzeExec {
             }
 
             let duration = Instant::now() - start;
+            let peak_memory = query_memory_pool.map(|pool| pool.peak_reserved());
             create_output_batch(
                 verbose,
                 show_statistics,
                 total_rows,
                 duration,
+                peak_memory,
                 captured_input,
                 captured_schema,
             )
@@ -212,6 +216,7 @@ fn create_output_batch(
     show_statistics: bool,
     total_rows: usize,
     duration: std::time::Duration,
+    peak_memory: Option<usize>,
     input: Arc<dyn ExecutionPlan>,
     schema: SchemaRef,
 ) -> Result<RecordBatch> {
@@ -245,6 +250,12 @@ fn create_output_batch(
         plan_builder.append_value(format!("{duration:?}"));
     }
 
+    // Memory reserved by the query, if tracked separately
+    if let Some(peak_memory) = peak_memory {
+        type_builder.append_value("Peak Memory Usage");
+        plan_builder.append_value(human_readable_size(peak_memory));
+    }
+
     RecordBatch::try_new(
         schema,
         vec![
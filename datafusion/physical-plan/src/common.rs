@@ -334,12 +334,16 @@ mod tests {
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -372,6 +376,8 @@ mod tests {
                 max_value: Precision::Absent,
                 min_value: Precision::Absent,
                 null_count: Precision::Exact(3),
+                histogram: None,
+                most_common_values: None,
             }],
         };
 
@@ -418,6 +418,21 @@ pub trait ExecutionPlan: Debug + DisplayAs + Send + Sync {
         None
     }
 
+    /// Returns a variant of this `ExecutionPlan` node returning a random
+    /// sample of blocks (such as files or row groups) of its output, each
+    /// block being returned with probability `fraction`. Returns `None` if
+    /// this node can't sample its blocks.
+    ///
+    /// This is used to implement `SYSTEM` sampling without reading the
+    /// skipped blocks.
+    fn with_block_sample(
+        &self,
+        _fraction: f64,
+        _seed: Option<u64>,
+    ) -> Option<Arc<dyn ExecutionPlan>> {
+        None
+    }
+
     /// Gets the effect on cardinality, if known
     fn cardinality_effect(&self) -> CardinalityEffect {
         CardinalityEffect::Unknown
@@ -736,17 +751,56 @@ pub fn execute_stream(
 ) -> Result<SendableRecordBatchStream> {
     match plan.output_partitioning().partition_count() {
         0 => Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
-        1 => plan.execute(0, context),
+        1 => execute_when_admitted(plan.schema(), context, move |context| {
+            plan.execute(0, context)
+        }),
         2.. => {
             // merge into a single partition
             let plan = CoalescePartitionsExec::new(Arc::clone(&plan));
             // CoalescePartitionsExec must produce a single partition
             assert_eq!(1, plan.properties().output_partitioning().partition_count());
-            plan.execute(0, context)
+            execute_when_admitted(plan.schema(), context, move |context| {
+                plan.execute(0, context)
+            })
         }
     }
 }
 
+/// Calls `execute` once the query of `context` is admitted by the
+/// [`MemoryAdmissionQueue`] of its [`QueryMemoryPool`], if any, so that the
+/// query does not start, nor reserve memory, before.
+///
+/// [`MemoryAdmissionQueue`]: datafusion_execution::memory_pool::MemoryAdmissionQueue
+/// [`QueryMemoryPool`]: datafusion_execution::memory_pool::QueryMemoryPool
+fn execute_when_admitted(
+    schema: SchemaRef,
+    context: Arc<TaskContext>,
+    execute: impl FnOnce(Arc<TaskContext>) -> Result<SendableRecordBatchStream>
+        + Send
+        + 'static,
+) -> Result<SendableRecordBatchStream> {
+    let Some(pool) = context
+        .query_memory_pool()
+        .filter(|pool| pool.requires_admission())
+        .map(Arc::clone)
+    else {
+        return execute(context);
+    };
+
+    let admitted_pool = Arc::clone(&pool);
+    let stream = futures::stream::once(async move {
+        admitted_pool.admit().await?;
+        execute(context)
+    })
+    .try_flatten()
+    .map(move |batch| {
+        // the query stays admitted as long as its pool is alive
+        let _pool = &pool;
+        batch
+    });
+    Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+}
+
 /// Execute the [ExecutionPlan] and collect the results in memory
 pub async fn collect_partitioned(
     plan: Arc<dyn ExecutionPlan>,
@@ -801,7 +855,12 @@ pub fn execute_stream_partitioned(
     let num_partitions = plan.output_partitioning().partition_count();
     let mut streams = Vec::with_capacity(num_partitions);
     for i in 0..num_partitions {
-        streams.push(plan.execute(i, Arc::clone(&context))?);
+        let plan = Arc::clone(&plan);
+        streams.push(execute_when_admitted(
+            plan.schema(),
+            Arc::clone(&context),
+            move |context| plan.execute(i, context),
+        )?);
     }
     Ok(streams)
 }
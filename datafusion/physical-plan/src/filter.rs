@@ -41,7 +41,7 @@ use datafusion_common::{
 use datafusion_execution::TaskContext;
 use datafusion_expr::Operator;
 use datafusion_physical_expr::equivalence::ProjectionMapping;
-use datafusion_physical_expr::expressions::BinaryExpr;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column, InListExpr, Literal};
 use datafusion_physical_expr::intervals::utils::check_support;
 use datafusion_physical_expr::utils::collect_columns;
 use datafusion_physical_expr::{
@@ -170,9 +170,22 @@ impl FilterExec {
     ) -> Result<Statistics> {
         let input_stats = input.statistics()?;
         let schema = input.schema();
+        if let Some((selectivity, remaining)) =
+            distribution_selectivity(predicate, &input_stats)
+        {
+            return Self::distribution_statistics_helper(
+                input_stats,
+                &schema,
+                predicate,
+                selectivity,
+                remaining,
+                default_selectivity,
+            );
+        }
         if !check_support(predicate, &schema) {
             let selectivity = default_selectivity as f64 / 100.0;
             let mut stats = input_stats.to_inexact();
+            stats.column_statistics = without_distributions(stats.column_statistics);
             stats.num_rows = stats.num_rows.with_estimated_selectivity(selectivity);
             stats.total_byte_size = stats
                 .total_byte_size
@@ -205,6 +218,57 @@ impl FilterExec {
         })
     }
 
+    /// Estimates the statistics of the filter when some of the conjuncts of
+    /// the predicate were estimated from the histograms or most common values
+    /// of the input columns, with a combined `selectivity`. The `remaining`
+    /// conjuncts are estimated from the bounds of the columns if possible.
+    fn distribution_statistics_helper(
+        input_stats: Statistics,
+        schema: &SchemaRef,
+        predicate: &Arc<dyn PhysicalExpr>,
+        selectivity: f64,
+        remaining: Option<Arc<dyn PhysicalExpr>>,
+        default_selectivity: u8,
+    ) -> Result<Statistics> {
+        let remaining_selectivity = match remaining {
+            None => 1.0,
+            Some(remaining) if check_support(&remaining, schema) => {
+                let input_analysis_ctx = AnalysisContext::try_from_statistics(
+                    schema,
+                    &input_stats.column_statistics,
+                )?;
+                analyze(&remaining, input_analysis_ctx, schema)?
+                    .selectivity
+                    .unwrap_or(1.0)
+            }
+            Some(_) => default_selectivity as f64 / 100.0,
+        };
+        let selectivity = selectivity * remaining_selectivity;
+        let num_rows = input_stats.num_rows.with_estimated_selectivity(selectivity);
+        let total_byte_size = input_stats
+            .total_byte_size
+            .with_estimated_selectivity(selectivity);
+
+        let column_statistics = if check_support(predicate, schema) {
+            let input_analysis_ctx = AnalysisContext::try_from_statistics(
+                schema,
+                &input_stats.column_statistics,
+            )?;
+            let analysis_ctx = analyze(predicate, input_analysis_ctx, schema)?;
+            collect_new_statistics(
+                &input_stats.column_statistics,
+                analysis_ctx.boundaries,
+            )
+        } else {
+            without_distributions(input_stats.to_inexact().column_statistics)
+        };
+        Ok(Statistics {
+            num_rows,
+            total_byte_size,
+            column_statistics,
+        })
+    }
+
     fn extend_constants(
         input: &Arc<dyn ExecutionPlan>,
         predicate: &Arc<dyn PhysicalExpr>,
@@ -382,6 +446,16 @@ impl ExecutionPlan for FilterExec {
     fn cardinality_effect(&self) -> CardinalityEffect {
         CardinalityEffect::LowerEqual
     }
+
+    fn with_block_sample(
+        &self,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Option<Arc<dyn ExecutionPlan>> {
+        // Filtered rows stay in the blocks they were read from
+        let input = self.input.with_block_sample(fraction, seed)?;
+        Arc::new(self.clone()).with_new_children(vec![input]).ok()
+    }
 }
 
 /// This function ensures that all bounds in the `ExprBoundaries` vector are
@@ -415,12 +489,132 @@ fn collect_new_statistics(
                     max_value,
                     min_value,
                     distinct_count: distinct_count.to_inexact(),
+                    histogram: None,
+                    most_common_values: None,
                 }
             },
         )
         .collect()
 }
 
+/// Removes the histograms and most common values of the columns, which no
+/// longer describe the values once they are filtered.
+fn without_distributions(
+    column_statistics: Vec<ColumnStatistics>,
+) -> Vec<ColumnStatistics> {
+    column_statistics
+        .into_iter()
+        .map(|stats| ColumnStatistics {
+            histogram: None,
+            most_common_values: None,
+            ..stats
+        })
+        .collect()
+}
+
+/// Estimates the selectivity of the conjuncts of `predicate` which compare a
+/// column having a histogram or most common values with literals, assuming
+/// the conjuncts are independent.
+///
+/// Returns `None` if no conjunct can be estimated this way, otherwise the
+/// combined selectivity of the estimated conjuncts and the conjunction of the
+/// other ones, if any.
+fn distribution_selectivity(
+    predicate: &Arc<dyn PhysicalExpr>,
+    input_stats: &Statistics,
+) -> Option<(f64, Option<Arc<dyn PhysicalExpr>>)> {
+    let mut selectivity = None;
+    let mut remaining = vec![];
+    for conjunct in split_conjunction(predicate) {
+        match conjunct_distribution_selectivity(conjunct, input_stats) {
+            Some(conjunct_selectivity) => {
+                selectivity = Some(selectivity.unwrap_or(1.0) * conjunct_selectivity)
+            }
+            None => remaining.push(Arc::clone(conjunct)),
+        }
+    }
+    let remaining = remaining.into_iter().reduce(|lhs, rhs| {
+        Arc::new(BinaryExpr::new(lhs, Operator::And, rhs)) as Arc<dyn PhysicalExpr>
+    });
+    selectivity.map(|selectivity| (selectivity, remaining))
+}
+
+/// Estimates the selectivity of `col op literal`, `literal op col` and
+/// `col [NOT] IN (literals)` expressions, and of disjunctions of them, from
+/// the histogram or most common values of the columns.
+fn conjunct_distribution_selectivity(
+    expr: &Arc<dyn PhysicalExpr>,
+    input_stats: &Statistics,
+) -> Option<f64> {
+    let column_stats = |expr: &Arc<dyn PhysicalExpr>| {
+        let column = expr.as_any().downcast_ref::<Column>()?;
+        input_stats
+            .column_statistics
+            .get(column.index())
+            .filter(|stats| stats.has_distribution())
+    };
+
+    if let Some(binary) = expr.as_any().downcast_ref::<BinaryExpr>() {
+        if binary.op() == &Operator::Or {
+            let lhs = conjunct_distribution_selectivity(binary.left(), input_stats)?;
+            let rhs = conjunct_distribution_selectivity(binary.right(), input_stats)?;
+            return Some(lhs + rhs - lhs * rhs);
+        }
+        let (stats, op, value) =
+            match (column_stats(binary.left()), as_literal(binary.right())) {
+                (Some(stats), Some(value)) => (stats, *binary.op(), value),
+                _ => (
+                    column_stats(binary.right())?,
+                    binary.op().swap()?,
+                    as_literal(binary.left())?,
+                ),
+            };
+        let value = value.value();
+        let fraction = match op {
+            Operator::Eq => stats.estimate_eq_selectivity(value)?,
+            Operator::NotEq => 1.0 - stats.estimate_eq_selectivity(value)?,
+            Operator::Lt => stats.estimate_lt_selectivity(value, false)?,
+            Operator::LtEq => stats.estimate_lt_selectivity(value, true)?,
+            Operator::Gt => 1.0 - stats.estimate_lt_selectivity(value, true)?,
+            Operator::GtEq => 1.0 - stats.estimate_lt_selectivity(value, false)?,
+            _ => return None,
+        };
+        Some(fraction * non_null_fraction(input_stats, stats))
+    } else if let Some(in_list) = expr.as_any().downcast_ref::<InListExpr>() {
+        let stats = column_stats(in_list.expr())?;
+        let mut fraction = 0.0;
+        for item in in_list.list() {
+            fraction += stats.estimate_eq_selectivity(as_literal(item)?.value())?;
+        }
+        let fraction = fraction.min(1.0);
+        let fraction = if in_list.negated() {
+            1.0 - fraction
+        } else {
+            fraction
+        };
+        Some(fraction * non_null_fraction(input_stats, stats))
+    } else {
+        None
+    }
+}
+
+fn as_literal(expr: &Arc<dyn PhysicalExpr>) -> Option<&Literal> {
+    expr.as_any().downcast_ref::<Literal>()
+}
+
+/// Fraction of the rows of the input for which the column is not null
+fn non_null_fraction(input_stats: &Statistics, column_stats: &ColumnStatistics) -> f64 {
+    match (
+        input_stats.num_rows.get_value(),
+        column_stats.null_count.get_value(),
+    ) {
+        (Some(&num_rows), Some(&null_count)) if num_rows > 0 => {
+            1.0 - null_count.min(num_rows) as f64 / num_rows as f64
+        }
+        _ => 1.0,
+    }
+}
+
 /// The FilterExec streams wraps the input iterator and applies the predicate expression to
 /// determine which rows to include in its output batches
 struct FilterExecStream {
@@ -563,6 +757,7 @@ mod tests {
 
     use arrow::datatypes::{Field, Schema};
     use arrow_schema::{UnionFields, UnionMode};
+    use datafusion_common::stats::{Histogram, HistogramBucket, MostCommonValues};
     use datafusion_common::ScalarValue;
 
     #[tokio::test]
@@ -655,6 +850,93 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_filter_statistics_histogram() -> Result<()> {
+        // Table:
+        //      a: min=1, max=100, 10 nulls, 50 rows equal to 1 and 40 rows
+        //         uniformly distributed between 2 and 100
+        //      b: min=1, max=100
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Precision::Exact(100),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![
+                    ColumnStatistics {
+                        null_count: Precision::Exact(10),
+                        min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                        max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
+                        histogram: Some(Arc::new(Histogram::new(vec![
+                            HistogramBucket {
+                                lower: ScalarValue::Int32(Some(2)),
+                                upper: ScalarValue::Int32(Some(51)),
+                                num_rows: 20,
+                                distinct_count: 20,
+                            },
+                            HistogramBucket {
+                                lower: ScalarValue::Int32(Some(52)),
+                                upper: ScalarValue::Int32(Some(100)),
+                                num_rows: 20,
+                                distinct_count: 20,
+                            },
+                        ]))),
+                        most_common_values: Some(Arc::new(MostCommonValues::new(vec![
+                            (ScalarValue::Int32(Some(1)), 50),
+                        ]))),
+                        ..Default::default()
+                    },
+                    ColumnStatistics {
+                        min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                        max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
+                        ..Default::default()
+                    },
+                ],
+            },
+            schema.clone(),
+        ));
+        let num_rows = |predicate: Arc<dyn PhysicalExpr>| -> Result<_> {
+            let filter = FilterExec::try_new(predicate, Arc::clone(&input) as _)?;
+            Ok(filter.statistics()?.num_rows)
+        };
+
+        // The most common value is estimated from its frequency
+        let predicate = binary(col("a", &schema)?, Operator::Eq, lit(1i32), &schema)?;
+        assert_eq!(num_rows(predicate)?, Precision::Inexact(50));
+
+        // The other ones from the histogram, excluding nulls
+        let predicate = binary(lit(51i32), Operator::Lt, col("a", &schema)?, &schema)?;
+        assert_eq!(num_rows(predicate)?, Precision::Inexact(20));
+
+        let predicate = in_list(
+            col("a", &schema)?,
+            vec![lit(1i32), lit(2i32)],
+            &true,
+            &schema,
+        )?;
+        assert_eq!(num_rows(predicate)?, Precision::Inexact(39));
+
+        // Conjuncts on columns without histogram are estimated from their bounds
+        let predicate = binary(
+            binary(col("a", &schema)?, Operator::Eq, lit(1i32), &schema)?,
+            Operator::And,
+            binary(col("b", &schema)?, Operator::LtEq, lit(50i32), &schema)?,
+            &schema,
+        )?;
+        assert_eq!(num_rows(predicate)?, Precision::Inexact(25));
+
+        // The histograms no longer describe the filtered values
+        let predicate = binary(col("a", &schema)?, Operator::Eq, lit(1i32), &schema)?;
+        let filter = FilterExec::try_new(predicate, Arc::clone(&input) as _)?;
+        let statistics = filter.statistics()?;
+        assert!(statistics.column_statistics[0].histogram.is_none());
+        assert!(statistics.column_statistics[0].most_common_values.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_filter_statistics_column_level_nested() -> Result<()> {
         // Table:
@@ -1133,6 +1415,8 @@ mod tests {
                 min_value: Precision::Inexact(ScalarValue::Int32(Some(5))),
                 max_value: Precision::Inexact(ScalarValue::Int32(Some(10))),
                 distinct_count: Precision::Absent,
+                histogram: None,
+                most_common_values: None,
             }],
         };
 
@@ -0,0 +1,944 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ASOF join execution plan.
+//!
+//! An ASOF join matches every row of the left input with the single nearest
+//! row of the right input that has the same equality keys and satisfies an
+//! inequality (the "match condition") on an ordered column, such as a
+//! timestamp. Left rows without such a row are joined with nulls.
+//!
+//! Both inputs are required to be sorted on the equality keys followed by the
+//! match condition expression, so the join is performed in a single pass over
+//! both inputs, similar to [`SortMergeJoinExec`](super::SortMergeJoinExec).
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use arrow::array::{new_null_array, Array, ArrayRef};
+use arrow::compute::{interleave, SortOptions};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::{internal_err, plan_err, JoinSide, JoinType, Result};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::{PhysicalExprRef, PhysicalSortRequirement};
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
+use futures::StreamExt;
+
+use crate::joins::utils::{
+    build_join_schema, check_join_is_valid, estimate_join_statistics,
+    symmetric_join_output_partitioning, JoinOn, JoinOnRef,
+};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    execution_mode_from_children, DisplayAs, DisplayFormatType, Distribution,
+    ExecutionPlan, ExecutionPlanProperties, PlanProperties, SendableRecordBatchStream,
+    Statistics,
+};
+
+/// The match condition of an ASOF join: `left <op> right`, where `left` is
+/// evaluated against the left input, `right` against the right input and `op`
+/// is one of `>=`, `>`, `<=` or `<`.
+///
+/// For example, `trades.ts >= quotes.ts` matches every trade with the latest
+/// quote at or before the time of the trade.
+#[derive(Debug, Clone)]
+pub struct AsofMatchCondition {
+    left: PhysicalExprRef,
+    op: Operator,
+    right: PhysicalExprRef,
+}
+
+impl AsofMatchCondition {
+    /// Creates a new match condition, returning an error if `op` is not an
+    /// inequality
+    pub fn try_new(
+        left: PhysicalExprRef,
+        op: Operator,
+        right: PhysicalExprRef,
+    ) -> Result<Self> {
+        match op {
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq => {
+                Ok(Self { left, op, right })
+            }
+            _ => plan_err!(
+                "ASOF join match condition must be one of >=, >, <= or <, got {op}"
+            ),
+        }
+    }
+
+    /// The expression evaluated against the left input
+    pub fn left(&self) -> &PhysicalExprRef {
+        &self.left
+    }
+
+    /// The comparison operator
+    pub fn op(&self) -> Operator {
+        self.op
+    }
+
+    /// The expression evaluated against the right input
+    pub fn right(&self) -> &PhysicalExprRef {
+        &self.right
+    }
+
+    /// The order both inputs must be sorted on the match condition expressions,
+    /// so that the nearest matching right row is the last one that precedes
+    /// (or ties with, for non strict conditions) the left row
+    fn sort_options(&self) -> SortOptions {
+        SortOptions {
+            descending: matches!(self.op, Operator::Lt | Operator::LtEq),
+            nulls_first: false,
+        }
+    }
+
+    /// Whether rows with equal values don't match
+    fn is_strict(&self) -> bool {
+        matches!(self.op, Operator::Lt | Operator::Gt)
+    }
+}
+
+impl fmt::Display for AsofMatchCondition {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.op, self.right)
+    }
+}
+
+/// Sort options of the equality keys used in the ordering required from the
+/// inputs
+const KEY_SORT_OPTIONS: SortOptions = SortOptions {
+    descending: false,
+    nulls_first: false,
+};
+
+/// Execution plan for an ASOF join, see the [module level documentation](self)
+#[derive(Debug, Clone)]
+pub struct AsofJoinExec {
+    /// Left sorted joining execution plan
+    left: Arc<dyn ExecutionPlan>,
+    /// Right sorted joining execution plan
+    right: Arc<dyn ExecutionPlan>,
+    /// Set of common columns used to join on, may be empty
+    on: JoinOn,
+    /// The inequality used to find the nearest right row
+    match_condition: AsofMatchCondition,
+    /// If null_equals_null is true, null == null else null != null
+    null_equals_null: bool,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl AsofJoinExec {
+    /// Tries to create a new [AsofJoinExec].
+    /// # Error
+    /// This function errors when the equality keys or the two sides of the
+    /// match condition don't have the same types.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        match_condition: AsofMatchCondition,
+        null_equals_null: bool,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &on)?;
+
+        for (l, r) in &on {
+            let left_type = l.data_type(&left_schema)?;
+            let right_type = r.data_type(&right_schema)?;
+            if left_type != right_type {
+                return plan_err!(
+                    "ASOF join keys must have the same type, got {left_type} and {right_type}"
+                );
+            }
+        }
+        let left_type = match_condition.left.data_type(&left_schema)?;
+        let right_type = match_condition.right.data_type(&right_schema)?;
+        if left_type != right_type {
+            return plan_err!(
+                "ASOF join match condition must compare values of the same type, got {left_type} and {right_type}"
+            );
+        }
+
+        let schema =
+            Arc::new(build_join_schema(&left_schema, &right_schema, &JoinType::AsOf).0);
+        let cache = Self::compute_properties(&left, &right, Arc::clone(&schema), &on);
+        Ok(Self {
+            left,
+            right,
+            on,
+            match_condition,
+            null_equals_null,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// left (probe) side which gets joined with the nearest right rows
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side which is searched for the nearest rows
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Set of common columns used to join on
+    pub fn on(&self) -> &[(PhysicalExprRef, PhysicalExprRef)] {
+        &self.on
+    }
+
+    /// The inequality used to find the nearest right row
+    pub fn match_condition(&self) -> &AsofMatchCondition {
+        &self.match_condition
+    }
+
+    /// Get null_equals_null
+    pub fn null_equals_null(&self) -> bool {
+        self.null_equals_null
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        join_on: JoinOnRef,
+    ) -> PlanProperties {
+        // Calculate equivalence properties:
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &JoinType::AsOf,
+            schema,
+            &[true, false],
+            Some(JoinSide::Left),
+            join_on,
+        );
+
+        let output_partitioning =
+            symmetric_join_output_partitioning(left, right, &JoinType::AsOf);
+
+        // Determine execution mode:
+        let mode = execution_mode_from_children([left, right]);
+
+        PlanProperties::new(eq_properties, output_partitioning, mode)
+    }
+
+    /// The ordering required from one side: the equality keys followed by the
+    /// match condition expression
+    fn input_requirement(
+        &self,
+        keys: impl Iterator<Item = PhysicalExprRef>,
+        match_expr: &PhysicalExprRef,
+    ) -> LexRequirement {
+        keys.map(|expr| PhysicalSortRequirement::new(expr, Some(KEY_SORT_OPTIONS)))
+            .chain(std::iter::once(PhysicalSortRequirement::new(
+                Arc::clone(match_expr),
+                Some(self.match_condition.sort_options()),
+            )))
+            .collect()
+    }
+}
+
+impl DisplayAs for AsofJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| format!("({}, {})", c1, c2))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AsofJoin: on=[{}], match_condition={}",
+                    on, self.match_condition
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsofJoinExec {
+    fn name(&self) -> &'static str {
+        "AsofJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        if self.on.is_empty() {
+            return vec![Distribution::SinglePartition, Distribution::SinglePartition];
+        }
+        let (left_expr, right_expr) = self
+            .on
+            .iter()
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .unzip();
+        vec![
+            Distribution::HashPartitioned(left_expr),
+            Distribution::HashPartitioned(right_expr),
+        ]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<LexRequirement>> {
+        vec![
+            Some(self.input_requirement(
+                self.on.iter().map(|(l, _)| Arc::clone(l)),
+                &self.match_condition.left,
+            )),
+            Some(self.input_requirement(
+                self.on.iter().map(|(_, r)| Arc::clone(r)),
+                &self.match_condition.right,
+            )),
+        ]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(AsofJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                self.on.clone(),
+                self.match_condition.clone(),
+                self.null_equals_null,
+            )?)),
+            _ => internal_err!("AsofJoinExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left_partitions = self.left.output_partitioning().partition_count();
+        let right_partitions = self.right.output_partitioning().partition_count();
+        if left_partitions != right_partitions {
+            return internal_err!(
+                "Invalid AsofJoinExec, partition count mismatch {left_partitions}!={right_partitions},\
+                 consider using RepartitionExec"
+            );
+        }
+
+        let left_schema = self.left.schema();
+        let right_schema = self.right.schema();
+        let key_converter = if self.on.is_empty() {
+            None
+        } else {
+            let fields = self
+                .on
+                .iter()
+                .map(|(l, _)| {
+                    Ok(SortField::new_with_options(
+                        l.data_type(&left_schema)?,
+                        KEY_SORT_OPTIONS,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Some(RowConverter::new(fields)?)
+        };
+        let value_converter = RowConverter::new(vec![SortField::new_with_options(
+            self.match_condition.left.data_type(&left_schema)?,
+            self.match_condition.sort_options(),
+        )])?;
+        let null_row = right_schema
+            .fields()
+            .iter()
+            .map(|field| new_null_array(field.data_type(), 1))
+            .collect();
+
+        let reservation = MemoryConsumer::new(format!("AsofJoinStream[{partition}]"))
+            .register(context.memory_pool());
+
+        let (on_left, on_right) = self.on.iter().cloned().unzip();
+        let state = AsofJoinStreamState {
+            schema: Arc::clone(&self.schema),
+            left: self.left.execute(partition, Arc::clone(&context))?,
+            right: self.right.execute(partition, context)?,
+            on_left,
+            on_right,
+            match_condition: self.match_condition.clone(),
+            null_equals_null: self.null_equals_null,
+            key_converter,
+            value_converter,
+            null_row,
+            right_batches: vec![],
+            cursor: (0, 0),
+            candidate: None,
+            right_finished: false,
+            finished: false,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+            reservation,
+        };
+        let stream = futures::stream::unfold(state, |mut this| async move {
+            if this.finished {
+                return None;
+            }
+            let result = match this.left.next().await {
+                Some(Ok(batch)) => this.join_left_batch(batch).await,
+                Some(Err(e)) => Err(e),
+                None => {
+                    this.finished = true;
+                    return None;
+                }
+            };
+            this.finished = result.is_err();
+            Some((result, this))
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        estimate_join_statistics(
+            Arc::clone(&self.left),
+            Arc::clone(&self.right),
+            self.on.clone(),
+            &JoinType::AsOf,
+            &self.schema,
+        )
+    }
+}
+
+/// A buffered batch of the right input along with its converted rows
+struct RightBatch {
+    batch: RecordBatch,
+    keys: Option<Rows>,
+    values: Rows,
+    /// Whether each row can match, i.e. it has no null value or key
+    matchable: Vec<bool>,
+}
+
+impl RightBatch {
+    fn size(&self) -> usize {
+        self.batch.get_array_memory_size()
+            + self.keys.as_ref().map_or(0, |keys| keys.size())
+            + self.values.size()
+            + self.matchable.len()
+    }
+}
+
+/// State of the stream returned by [`AsofJoinExec::execute`]
+struct AsofJoinStreamState {
+    schema: SchemaRef,
+    left: SendableRecordBatchStream,
+    right: SendableRecordBatchStream,
+    on_left: Vec<PhysicalExprRef>,
+    on_right: Vec<PhysicalExprRef>,
+    match_condition: AsofMatchCondition,
+    null_equals_null: bool,
+    /// Converts the equality keys of both sides, `None` without keys
+    key_converter: Option<RowConverter>,
+    /// Converts the match condition values of both sides
+    value_converter: RowConverter,
+    /// A single row of nulls for every column of the right input
+    null_row: Vec<ArrayRef>,
+    /// Right batches that may still be referenced by the output
+    right_batches: Vec<RightBatch>,
+    /// The next right row (batch index, row index) that hasn't been passed
+    /// by any left row yet
+    cursor: (usize, usize),
+    /// The last right row passed by the left rows, which is the nearest
+    /// row for the current left row if it has the same keys
+    candidate: Option<(usize, usize)>,
+    right_finished: bool,
+    finished: bool,
+    baseline_metrics: BaselineMetrics,
+    reservation: MemoryReservation,
+}
+
+impl AsofJoinStreamState {
+    /// Converts the keys and match condition values of `batch`, and finds
+    /// which of its rows can match
+    fn convert_batch(
+        &self,
+        batch: &RecordBatch,
+        on: &[PhysicalExprRef],
+        match_expr: &PhysicalExprRef,
+    ) -> Result<(Option<Rows>, Rows, Vec<bool>)> {
+        let num_rows = batch.num_rows();
+        let key_arrays = on
+            .iter()
+            .map(|expr| expr.evaluate(batch)?.into_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+        let value_array = match_expr.evaluate(batch)?.into_array(num_rows)?;
+
+        let keys = self
+            .key_converter
+            .as_ref()
+            .map(|converter| converter.convert_columns(&key_arrays))
+            .transpose()?;
+        let values = self
+            .value_converter
+            .convert_columns(&[Arc::clone(&value_array)])?;
+        let matchable = (0..num_rows)
+            .map(|row| {
+                value_array.is_valid(row)
+                    && (self.null_equals_null
+                        || key_arrays.iter().all(|keys| keys.is_valid(row)))
+            })
+            .collect();
+        Ok((keys, values, matchable))
+    }
+
+    /// Returns the position of the next right row not passed yet, reading
+    /// more of the right input if needed, or `None` once it is exhausted
+    async fn next_right_row(&mut self) -> Result<Option<(usize, usize)>> {
+        loop {
+            let (batch_idx, row_idx) = self.cursor;
+            if let Some(right) = self.right_batches.get(batch_idx) {
+                if row_idx < right.batch.num_rows() {
+                    return Ok(Some(self.cursor));
+                }
+                if batch_idx + 1 < self.right_batches.len() {
+                    self.cursor = (batch_idx + 1, 0);
+                    continue;
+                }
+            }
+            if self.right_finished {
+                return Ok(None);
+            }
+            match self.right.next().await {
+                Some(Ok(batch)) if batch.num_rows() == 0 => {}
+                Some(Ok(batch)) => {
+                    let (keys, values, matchable) = self.convert_batch(
+                        &batch,
+                        &self.on_right,
+                        &self.match_condition.right,
+                    )?;
+                    let right = RightBatch {
+                        batch,
+                        keys,
+                        values,
+                        matchable,
+                    };
+                    self.reservation.try_grow(right.size())?;
+                    self.right_batches.push(right);
+                }
+                Some(Err(e)) => return Err(e),
+                None => self.right_finished = true,
+            }
+        }
+    }
+
+    /// Joins a batch of the left input with the nearest rows of the right input
+    async fn join_left_batch(&mut self, left_batch: RecordBatch) -> Result<RecordBatch> {
+        let (left_keys, left_values, left_matchable) =
+            self.convert_batch(&left_batch, &self.on_left, &self.match_condition.left)?;
+        let strict = self.match_condition.is_strict();
+
+        // (source, row) pairs to interleave, source 0 is the null row and
+        // source `i + 1` is `self.right_batches[i]`
+        let mut indices = Vec::with_capacity(left_batch.num_rows());
+        for (row, left_matchable) in left_matchable.into_iter().enumerate() {
+            let left_key = left_keys.as_ref().map(|keys| keys.row(row));
+            let left_value = left_values.row(row);
+
+            // Advance past every right row ordered before the left row
+            while let Some((batch_idx, row_idx)) = self.next_right_row().await? {
+                let right = &self.right_batches[batch_idx];
+                let ordering = match (&right.keys, left_key) {
+                    (Some(keys), Some(left_key)) => keys.row(row_idx).cmp(&left_key),
+                    _ => Ordering::Equal,
+                }
+                .then_with(|| right.values.row(row_idx).cmp(&left_value));
+                let passed = match ordering {
+                    Ordering::Less => true,
+                    Ordering::Equal => !strict,
+                    Ordering::Greater => false,
+                };
+                if !passed {
+                    break;
+                }
+                self.candidate = Some((batch_idx, row_idx));
+                self.cursor = (batch_idx, row_idx + 1);
+            }
+
+            let matched = self.candidate.filter(|&(batch_idx, row_idx)| {
+                let right = &self.right_batches[batch_idx];
+                left_matchable
+                    && right.matchable[row_idx]
+                    && match (&right.keys, left_key) {
+                        (Some(keys), Some(left_key)) => keys.row(row_idx) == left_key,
+                        _ => true,
+                    }
+            });
+            indices.push(
+                matched.map_or((0, 0), |(batch_idx, row_idx)| (batch_idx + 1, row_idx)),
+            );
+        }
+
+        let timer = self.baseline_metrics.elapsed_compute().timer();
+        let mut columns = left_batch.columns().to_vec();
+        for (column_idx, null_column) in self.null_row.iter().enumerate() {
+            let sources = std::iter::once(null_column.as_ref())
+                .chain(
+                    self.right_batches
+                        .iter()
+                        .map(|right| right.batch.column(column_idx).as_ref()),
+                )
+                .collect::<Vec<_>>();
+            columns.push(interleave(&sources, &indices)?);
+        }
+        let output = RecordBatch::try_new(Arc::clone(&self.schema), columns)?;
+        timer.done();
+
+        // Release the right batches that can't be referenced anymore
+        let first_used = self
+            .candidate
+            .map_or(self.cursor.0, |(batch_idx, _)| batch_idx.min(self.cursor.0));
+        if first_used > 0 {
+            let released = self
+                .right_batches
+                .drain(..first_used)
+                .map(|right| right.size())
+                .sum();
+            self.reservation.shrink(released);
+            self.cursor.0 -= first_used;
+            if let Some((batch_idx, _)) = self.candidate.as_mut() {
+                *batch_idx -= first_used;
+            }
+        }
+
+        self.baseline_metrics.record_output(output.num_rows());
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::assert_batches_eq;
+    use datafusion_execution::config::SessionConfig;
+
+    use crate::common;
+    use crate::expressions::Column;
+    use crate::memory::MemoryExec;
+    use crate::test::build_table_i32;
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    fn build_nullable_table(
+        a: (&str, &Vec<Option<i32>>),
+        b: (&str, &Vec<Option<i32>>),
+        c: (&str, &Vec<Option<i32>>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(a.0, DataType::Int32, true),
+            Field::new(b.0, DataType::Int32, true),
+            Field::new(c.0, DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int32Array::from(a.1.clone())),
+                Arc::new(Int32Array::from(b.1.clone())),
+                Arc::new(Int32Array::from(c.1.clone())),
+            ],
+        )
+        .unwrap();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    /// Joins `left` and `right` on `(a1, a2)` with `b1 <op> b2`
+    fn join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        op: Operator,
+    ) -> Result<AsofJoinExec> {
+        let on = vec![(
+            Arc::new(Column::new_with_schema("a1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("a2", &right.schema())?) as _,
+        )];
+        let match_condition = AsofMatchCondition::try_new(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?),
+            op,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?),
+        )?;
+        AsofJoinExec::try_new(left, right, on, match_condition, false)
+    }
+
+    async fn join_collect(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        op: Operator,
+        batch_size: usize,
+    ) -> Result<Vec<RecordBatch>> {
+        let task_ctx = TaskContext::default()
+            .with_session_config(SessionConfig::new().with_batch_size(batch_size));
+        let join = join(left, right, op)?;
+        let stream = join.execute(0, Arc::new(task_ctx))?;
+        common::collect(stream).await
+    }
+
+    fn trades_batch() -> RecordBatch {
+        build_table_i32(
+            ("a1", &vec![1, 1, 1, 2, 3]),
+            ("b1", &vec![5, 10, 20, 10, 10]),
+            ("c1", &vec![100, 101, 102, 103, 104]),
+        )
+    }
+
+    fn quotes_batch() -> RecordBatch {
+        build_table_i32(
+            ("a2", &vec![1, 1, 1, 2, 2]),
+            ("b2", &vec![4, 10, 15, 11, 12]),
+            ("c2", &vec![1, 2, 3, 4, 5]),
+        )
+    }
+
+    fn build_table_from_batches(batches: Vec<RecordBatch>) -> Arc<dyn ExecutionPlan> {
+        let schema = batches.first().unwrap().schema();
+        Arc::new(MemoryExec::try_new(&[batches], schema, None).unwrap())
+    }
+
+    fn trades() -> Arc<dyn ExecutionPlan> {
+        build_table_from_batches(vec![trades_batch()])
+    }
+
+    fn quotes() -> Arc<dyn ExecutionPlan> {
+        build_table_from_batches(vec![quotes_batch()])
+    }
+
+    #[tokio::test]
+    async fn join_greater_equal() -> Result<()> {
+        let batches = join_collect(trades(), quotes(), Operator::GtEq, 8192).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 5  | 100 | 1  | 4  | 1  |",
+            "| 1  | 10 | 101 | 1  | 10 | 2  |",
+            "| 1  | 20 | 102 | 1  | 15 | 3  |",
+            "| 2  | 10 | 103 |    |    |    |",
+            "| 3  | 10 | 104 |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_greater() -> Result<()> {
+        let batches = join_collect(trades(), quotes(), Operator::Gt, 8192).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 5  | 100 | 1  | 4  | 1  |",
+            "| 1  | 10 | 101 | 1  | 4  | 1  |",
+            "| 1  | 20 | 102 | 1  | 15 | 3  |",
+            "| 2  | 10 | 103 |    |    |    |",
+            "| 3  | 10 | 104 |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_less_equal() -> Result<()> {
+        // For `<=` and `<` both inputs are sorted descending on the match
+        // condition
+        let left = build_table(
+            ("a1", &vec![1, 1, 1, 2, 3]),
+            ("b1", &vec![20, 10, 5, 10, 10]),
+            ("c1", &vec![102, 101, 100, 103, 104]),
+        );
+        let right = build_table(
+            ("a2", &vec![1, 1, 1, 2, 2]),
+            ("b2", &vec![15, 10, 4, 12, 11]),
+            ("c2", &vec![3, 2, 1, 5, 4]),
+        );
+        let batches =
+            join_collect(Arc::clone(&left), Arc::clone(&right), Operator::LtEq, 8192)
+                .await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 20 | 102 |    |    |    |",
+            "| 1  | 10 | 101 | 1  | 10 | 2  |",
+            "| 1  | 5  | 100 | 1  | 10 | 2  |",
+            "| 2  | 10 | 103 | 2  | 11 | 4  |",
+            "| 3  | 10 | 104 |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        let batches = join_collect(left, right, Operator::Lt, 8192).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 20 | 102 |    |    |    |",
+            "| 1  | 10 | 101 | 1  | 15 | 3  |",
+            "| 1  | 5  | 100 | 1  | 10 | 2  |",
+            "| 2  | 10 | 103 | 2  | 11 | 4  |",
+            "| 3  | 10 | 104 |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_nulls() -> Result<()> {
+        let left = build_nullable_table(
+            ("a1", &vec![Some(1), Some(1), None]),
+            ("b1", &vec![Some(10), None, Some(10)]),
+            ("c1", &vec![Some(100), Some(101), Some(102)]),
+        );
+        let right = build_nullable_table(
+            ("a2", &vec![Some(1), Some(1), None]),
+            ("b2", &vec![Some(5), None, Some(5)]),
+            ("c2", &vec![Some(1), Some(2), Some(3)]),
+        );
+        let batches = join_collect(left, right, Operator::GtEq, 8192).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 10 | 100 | 1  | 5  | 1  |",
+            "| 1  |    | 101 |    |    |    |",
+            "|    | 10 | 102 |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_multiple_batches() -> Result<()> {
+        // Split the inputs in batches of one row, the nearest right row is
+        // often in an earlier batch than the current one
+        let split = |batch: RecordBatch| {
+            build_table_from_batches(
+                (0..batch.num_rows())
+                    .map(|row| batch.slice(row, 1))
+                    .collect(),
+            )
+        };
+        let batches = join_collect(
+            split(trades_batch()),
+            split(quotes_batch()),
+            Operator::GtEq,
+            1,
+        )
+        .await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 5  | 100 | 1  | 4  | 1  |",
+            "| 1  | 10 | 101 | 1  | 10 | 2  |",
+            "| 1  | 20 | 102 | 1  | 15 | 3  |",
+            "| 2  | 10 | 103 |    |    |    |",
+            "| 3  | 10 | 104 |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn required_input_ordering() -> Result<()> {
+        let join = join(trades(), quotes(), Operator::Lt)?;
+        let [left, right] = join.required_input_ordering().try_into().unwrap();
+        let display = |req: Option<LexRequirement>| {
+            req.unwrap()
+                .iter()
+                .map(|req| format!("{}: {:?}", req.expr, req.options.unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            display(left),
+            [
+                "a1@0: SortOptions { descending: false, nulls_first: false }",
+                "b1@1: SortOptions { descending: true, nulls_first: false }",
+            ]
+        );
+        assert_eq!(
+            display(right),
+            [
+                "a2@0: SortOptions { descending: false, nulls_first: false }",
+                "b2@1: SortOptions { descending: true, nulls_first: false }",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_match_condition() {
+        let err = join(trades(), quotes(), Operator::Eq).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: ASOF join match condition must be one of >=, >, <= or <, got ="
+        );
+    }
+}
@@ -0,0 +1,369 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Block nested loop mode of [`NestedLoopJoinExec`] and [`CrossJoinExec`],
+//! used when their build side does not fit in memory.
+//!
+//! The build side is then spilled to disk in chunks (blocks) small enough
+//! for each probe-side stream to hold one of them in memory. Each stream joins
+//! its probe side with one chunk at a time: the probe side is spilled to disk
+//! while it is joined with the first chunk, and read back from disk for each
+//! of the next chunks. The chunks and the probe side are read back on
+//! blocking threads, see [`read_spill_as_stream`].
+//!
+//! [`NestedLoopJoinExec`]: super::NestedLoopJoinExec
+//! [`CrossJoinExec`]: super::CrossJoinExec
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::compute::concat_batches;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::config::SpillCompression;
+use datafusion_common::{internal_err, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::MemoryReservation;
+use datafusion_execution::DiskManager;
+use futures::{ready, StreamExt};
+
+use crate::common::IPCWriter;
+use crate::joins::utils::BuildProbeJoinMetrics;
+use crate::spill::{get_record_batch_memory_size, read_spill_as_stream, spill_writer};
+use crate::SendableRecordBatchStream;
+
+/// Where and how to spill the build and probe sides of a join
+#[derive(Debug, Clone)]
+pub(crate) struct JoinSpillConfig {
+    pub(crate) disk_manager: Arc<DiskManager>,
+    pub(crate) compression: SpillCompression,
+}
+
+/// The build side of a join, as collected by [`collect_build_side`]
+pub(crate) enum BuildSide {
+    /// The batches of the build side, which fit in memory
+    InMemory(Vec<RecordBatch>),
+    /// The build side did not fit in memory, and was spilled to disk
+    Spilled(SpilledBuildSide),
+}
+
+/// Collects the build side `stream` in memory, tracking its size in
+/// `reservation`.
+///
+/// If the reservation can not grow and `spill` is set, the build side is
+/// spilled to disk instead, in chunks of at most the reserved memory divided
+/// by `probe_threads_count`, so that each probe-side stream can hold one of
+/// them in memory.
+pub(crate) async fn collect_build_side(
+    mut stream: SendableRecordBatchStream,
+    metrics: &BuildProbeJoinMetrics,
+    reservation: &mut MemoryReservation,
+    spill: Option<JoinSpillConfig>,
+    probe_threads_count: usize,
+) -> Result<BuildSide> {
+    let schema = stream.schema();
+    let mut batches = vec![];
+    let mut chunk_writer: Option<BuildChunkWriter> = None;
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        metrics.build_input_batches.add(1);
+        metrics.build_input_rows.add(batch.num_rows());
+        if let Some(chunk_writer) = &mut chunk_writer {
+            chunk_writer.write(&batch)?;
+            continue;
+        }
+
+        let batch_size = batch.get_array_memory_size();
+        match (reservation.try_grow(batch_size), &spill) {
+            (Ok(_), _) => {
+                metrics.build_mem_used.add(batch_size);
+                batches.push(batch);
+            }
+            (Err(_), Some(spill)) => {
+                let chunk_size = reservation.size() / probe_threads_count.max(1);
+                let mut writer = BuildChunkWriter::new(
+                    Arc::clone(&schema),
+                    spill.clone(),
+                    chunk_size,
+                    metrics.clone(),
+                );
+                for batch in batches.drain(..).chain([batch]) {
+                    writer.write(&batch)?;
+                }
+                metrics.build_mem_used.sub(reservation.free());
+                chunk_writer = Some(writer);
+            }
+            (Err(e), None) => return Err(e),
+        }
+    }
+
+    match chunk_writer {
+        Some(chunk_writer) => Ok(BuildSide::Spilled(chunk_writer.finish()?)),
+        None => Ok(BuildSide::InMemory(batches)),
+    }
+}
+
+/// A chunk of a [`SpilledBuildSide`]
+#[derive(Debug)]
+struct BuildChunk {
+    /// The spill file of the chunk, shared by the probe-side streams reading it
+    file: Arc<RefCountedTempFile>,
+    /// Index of the first row of the chunk in the build side
+    offset: usize,
+}
+
+/// A build side spilled to disk in chunks, see [`collect_build_side`]
+#[derive(Debug)]
+pub(crate) struct SpilledBuildSide {
+    schema: SchemaRef,
+    chunks: Vec<BuildChunk>,
+    num_rows: usize,
+}
+
+impl SpilledBuildSide {
+    /// Returns the number of chunks of the build side
+    pub(crate) fn num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the number of rows of the build side
+    pub(crate) fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the index of the first row of chunk `idx` in the build side
+    pub(crate) fn chunk_offset(&self, idx: usize) -> usize {
+        self.chunks[idx].offset
+    }
+
+    /// Starts reading chunk `idx` back from disk, replacing the memory
+    /// tracked by `reservation`, typically the previously loaded chunk, see
+    /// [`BuildChunkLoader::poll_load`]
+    pub(crate) fn load_chunk(
+        &self,
+        idx: usize,
+        reservation: &mut MemoryReservation,
+    ) -> Result<BuildChunkLoader> {
+        reservation.free();
+        let stream = read_spill_as_stream(
+            Arc::clone(&self.chunks[idx].file),
+            Arc::clone(&self.schema),
+            2,
+        )?;
+        Ok(BuildChunkLoader {
+            stream,
+            batches: vec![],
+        })
+    }
+}
+
+/// Reads a chunk of a [`SpilledBuildSide`] back from disk, see
+/// [`SpilledBuildSide::load_chunk`]
+pub(crate) struct BuildChunkLoader {
+    stream: SendableRecordBatchStream,
+    /// The batches of the chunk read so far
+    batches: Vec<RecordBatch>,
+}
+
+impl BuildChunkLoader {
+    /// Polls the reading of the chunk, tracking its memory in `reservation`,
+    /// and returns it as a single batch once it is entirely read
+    pub(crate) fn poll_load(
+        &mut self,
+        cx: &mut Context<'_>,
+        reservation: &mut MemoryReservation,
+    ) -> Poll<Result<RecordBatch>> {
+        while let Some(batch) = ready!(self.stream.poll_next_unpin(cx)).transpose()? {
+            reservation.try_grow(get_record_batch_memory_size(&batch))?;
+            self.batches.push(batch);
+        }
+        let batches = std::mem::take(&mut self.batches);
+        Poll::Ready(Ok(concat_batches(&self.stream.schema(), &batches)?))
+    }
+}
+
+/// Writes a build side to disk in chunks of bounded size
+struct BuildChunkWriter {
+    schema: SchemaRef,
+    spill: JoinSpillConfig,
+    /// The maximum size of the batches of a chunk in memory, unless the
+    /// chunk holds a single batch
+    chunk_size: usize,
+    metrics: BuildProbeJoinMetrics,
+    chunks: Vec<BuildChunk>,
+    /// The chunk being written, and the size of its batches in memory
+    current: Option<(RefCountedTempFile, IPCWriter, usize)>,
+    num_rows: usize,
+}
+
+impl BuildChunkWriter {
+    fn new(
+        schema: SchemaRef,
+        spill: JoinSpillConfig,
+        chunk_size: usize,
+        metrics: BuildProbeJoinMetrics,
+    ) -> Self {
+        Self {
+            schema,
+            spill,
+            chunk_size,
+            metrics,
+            chunks: vec![],
+            current: None,
+            num_rows: 0,
+        }
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let batch_size = batch.get_array_memory_size();
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|(_, _, size)| size + batch_size > self.chunk_size)
+        {
+            self.finish_chunk()?;
+        }
+
+        let (file, writer, size) = match &mut self.current {
+            Some(current) => current,
+            current @ None => {
+                let file = self
+                    .spill
+                    .disk_manager
+                    .create_tmp_file("BlockNestedLoopBuildSpill")?;
+                let writer = spill_writer(&file, &self.schema, self.spill.compression)?;
+                current.insert((file, writer, 0))
+            }
+        };
+        writer.write(batch)?;
+        file.update_disk_usage()?;
+        *size += batch_size;
+
+        self.metrics.spilled_bytes.add(batch_size);
+        self.metrics.spilled_rows.add(batch.num_rows());
+        Ok(())
+    }
+
+    fn finish_chunk(&mut self) -> Result<()> {
+        if let Some((mut file, mut writer, _)) = self.current.take() {
+            writer.finish()?;
+            file.update_disk_usage()?;
+            self.metrics.spill_count.add(1);
+            self.metrics
+                .spill_file_bytes
+                .add(file.current_disk_usage() as usize);
+
+            self.chunks.push(BuildChunk {
+                file: Arc::new(file),
+                offset: self.num_rows,
+            });
+            self.num_rows += writer.num_rows;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<SpilledBuildSide> {
+        self.finish_chunk()?;
+        Ok(SpilledBuildSide {
+            schema: self.schema,
+            chunks: self.chunks,
+            num_rows: self.num_rows,
+        })
+    }
+}
+
+/// The probe side of a stream joining a [`SpilledBuildSide`], written to
+/// disk while it is joined with the first chunk, and read back from disk for
+/// each of the next chunks
+pub(crate) struct SpilledProbeSide {
+    schema: SchemaRef,
+    /// The spill file being written, until the probe side is entirely written
+    writer: Option<(RefCountedTempFile, IPCWriter)>,
+    /// The spill file, once the probe side is entirely written
+    file: Option<Arc<RefCountedTempFile>>,
+    /// The batches of the probe side read back from disk
+    reader: Option<SendableRecordBatchStream>,
+    metrics: BuildProbeJoinMetrics,
+}
+
+impl SpilledProbeSide {
+    pub(crate) fn try_new(
+        schema: &SchemaRef,
+        spill: &JoinSpillConfig,
+        metrics: BuildProbeJoinMetrics,
+    ) -> Result<Self> {
+        let file = spill
+            .disk_manager
+            .create_tmp_file("BlockNestedLoopProbeSpill")?;
+        let writer = spill_writer(&file, schema, spill.compression)?;
+        Ok(Self {
+            schema: Arc::clone(schema),
+            writer: Some((file, writer)),
+            file: None,
+            reader: None,
+            metrics,
+        })
+    }
+
+    /// Writes a batch of the probe side, as it is read from the input
+    pub(crate) fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let Some((file, writer)) = &mut self.writer else {
+            return internal_err!("The spilled probe side is already written");
+        };
+        writer.write(batch)?;
+        file.update_disk_usage()?;
+        self.metrics
+            .spilled_bytes
+            .add(batch.get_array_memory_size());
+        self.metrics.spilled_rows.add(batch.num_rows());
+        Ok(())
+    }
+
+    /// Starts reading the probe side from the beginning, after it has been
+    /// entirely written
+    pub(crate) fn rewind(&mut self) -> Result<()> {
+        if let Some((mut file, mut writer)) = self.writer.take() {
+            writer.finish()?;
+            file.update_disk_usage()?;
+            self.metrics.spill_count.add(1);
+            self.metrics
+                .spill_file_bytes
+                .add(file.current_disk_usage() as usize);
+            self.file = Some(Arc::new(file));
+        }
+        let Some(file) = &self.file else {
+            return internal_err!("The spilled probe side is not written");
+        };
+        self.reader = Some(read_spill_as_stream(
+            Arc::clone(file),
+            Arc::clone(&self.schema),
+            2,
+        )?);
+        Ok(())
+    }
+
+    /// Polls the next batch of the probe side read back from disk
+    pub(crate) fn poll_next_batch(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        match &mut self.reader {
+            Some(reader) => reader.poll_next_unpin(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
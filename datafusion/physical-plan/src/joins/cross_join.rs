@@ -18,6 +18,10 @@
 //! Defines the cross join plan for loading the left side of the cross join
 //! and producing batches in parallel for the right partitions
 
+use super::block_nested_loop::{
+    collect_build_side, BuildChunkLoader, BuildSide, JoinSpillConfig, SpilledBuildSide,
+    SpilledProbeSide,
+};
 use super::utils::{
     adjust_right_output_partitioning, BatchSplitter, BatchTransformer,
     BuildProbeJoinMetrics, NoopBatchTransformer, OnceAsync, OnceFut,
@@ -44,16 +48,18 @@ use datafusion_execution::TaskContext;
 use datafusion_physical_expr::equivalence::join_equivalence_properties;
 
 use async_trait::async_trait;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
 
 /// Data of the left side
 #[derive(Debug)]
 struct JoinLeftData {
-    /// Single RecordBatch with all rows from the left side
+    /// Single RecordBatch with all rows from the left side, empty if the left
+    /// side has been spilled to disk
     merged_batch: RecordBatch,
+    /// The left side spilled to disk in chunks, if it did not fit in memory
+    spilled: Option<SpilledBuildSide>,
     /// Track memory reservation for merged_batch. Relies on drop
     /// semantics to release reservation when JoinLeftData is dropped.
-    #[allow(dead_code)]
     reservation: MemoryReservation,
 }
 
@@ -61,6 +67,12 @@ struct JoinLeftData {
 /// executes partitions in parallel and combines them into a set of
 /// partitions by combining all values from the left with all values on the right
 ///
+/// If the left side does not fit in memory and the disk manager allows
+/// temporary files, it is spilled to disk in chunks, and each partition is
+/// combined with one chunk at a time (block nested loop join): the right side
+/// is spilled to disk while combined with the first chunk, and re-scanned from
+/// disk for the next ones.
+///
 /// Note that the `Clone` trait is not implemented for this struct due to the
 /// `left_fut` [`OnceAsync`], which is used to coordinate the loading of the
 /// left side with the processing in each output stream.
@@ -160,11 +172,16 @@ impl CrossJoinExec {
 }
 
 /// Asynchronously collect the result of the left child
+///
+/// If the left side does not fit in memory and `spill` is set, it is spilled
+/// to disk in chunks instead, see [`collect_build_side`].
 async fn load_left_input(
     left: Arc<dyn ExecutionPlan>,
     context: Arc<TaskContext>,
     metrics: BuildProbeJoinMetrics,
-    reservation: MemoryReservation,
+    mut reservation: MemoryReservation,
+    spill: Option<JoinSpillConfig>,
+    probe_threads_count: usize,
 ) -> Result<JoinLeftData> {
     // merge all left parts into a single stream
     let left_schema = left.schema();
@@ -176,25 +193,25 @@ async fn load_left_input(
     let stream = merge.execute(0, context)?;
 
     // Load all batches and count the rows
-    let (batches, _metrics, reservation) = stream
-        .try_fold((Vec::new(), metrics, reservation), |mut acc, batch| async {
-            let batch_size = batch.get_array_memory_size();
-            // Reserve memory for incoming batch
-            acc.2.try_grow(batch_size)?;
-            // Update metrics
-            acc.1.build_mem_used.add(batch_size);
-            acc.1.build_input_batches.add(1);
-            acc.1.build_input_rows.add(batch.num_rows());
-            // Push batch to output
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let build_side = collect_build_side(
+        stream,
+        &metrics,
+        &mut reservation,
+        spill,
+        probe_threads_count,
+    )
+    .await?;
 
-    let merged_batch = concat_batches(&left_schema, &batches)?;
+    let (merged_batch, spilled) = match build_side {
+        BuildSide::InMemory(batches) => (concat_batches(&left_schema, &batches)?, None),
+        BuildSide::Spilled(spilled) => {
+            (RecordBatch::new_empty(left_schema), Some(spilled))
+        }
+    };
 
     Ok(JoinLeftData {
         merged_batch,
+        spilled,
         reservation,
     })
 }
@@ -260,9 +277,16 @@ impl ExecutionPlan for CrossJoinExec {
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
 
+        let disk_manager = Arc::clone(&context.runtime_env().disk_manager);
+        let spill = disk_manager.tmp_files_enabled().then(|| JoinSpillConfig {
+            disk_manager,
+            compression: context.session_config().spill_compression(),
+        });
+
         // Initialization of operator-level reservation
-        let reservation =
-            MemoryConsumer::new("CrossJoinExec").register(context.memory_pool());
+        let reservation = MemoryConsumer::new("CrossJoinExec")
+            .with_can_spill(spill.is_some())
+            .register(context.memory_pool());
 
         let batch_size = context.session_config().batch_size();
         let enforce_batch_size_in_joins =
@@ -274,6 +298,8 @@ impl ExecutionPlan for CrossJoinExec {
                 context,
                 join_metrics.clone(),
                 reservation,
+                spill.clone(),
+                self.right.output_partitioning().partition_count(),
             )
         });
 
@@ -287,6 +313,8 @@ impl ExecutionPlan for CrossJoinExec {
                 state: CrossJoinStreamState::WaitBuildSide,
                 left_data: RecordBatch::new_empty(self.left().schema()),
                 batch_transformer: BatchSplitter::new(batch_size),
+                chunked: None,
+                spill,
             }))
         } else {
             Ok(Box::pin(CrossJoinStream {
@@ -298,6 +326,8 @@ impl ExecutionPlan for CrossJoinExec {
                 state: CrossJoinStreamState::WaitBuildSide,
                 left_data: RecordBatch::new_empty(self.left().schema()),
                 batch_transformer: NoopBatchTransformer::new(),
+                chunked: None,
+                spill,
             }))
         }
     }
@@ -338,12 +368,16 @@ fn stats_cartesian_product(
             distinct_count: s.distinct_count,
             min_value: s.min_value,
             max_value: s.max_value,
+            histogram: None,
+            most_common_values: None,
         })
         .chain(right_col_stats.into_iter().map(|s| ColumnStatistics {
             null_count: s.null_count.multiply(&left_row_count),
             distinct_count: s.distinct_count,
             min_value: s.min_value,
             max_value: s.max_value,
+            histogram: None,
+            most_common_values: None,
         }))
         .collect();
 
@@ -368,10 +402,31 @@ struct CrossJoinStream<T> {
     join_metrics: BuildProbeJoinMetrics,
     /// State of the stream
     state: CrossJoinStreamState,
-    /// Left data (copy of the entire buffered left side)
+    /// Left data (copy of the entire buffered left side, or the loaded chunk
+    /// of a spilled left side)
     left_data: RecordBatch,
     /// Batch transformer
     batch_transformer: T,
+    /// State of the join with the chunks of a spilled left side
+    chunked: Option<ChunkedCrossJoin>,
+    /// Where and how to spill the right side, if the left side is spilled
+    spill: Option<JoinSpillConfig>,
+}
+
+/// State of a [`CrossJoinStream`] combining its right side with the chunks of
+/// a spilled left side, one at a time
+struct ChunkedCrossJoin {
+    /// The left side data, holding the spilled chunks
+    left: Arc<JoinLeftData>,
+    /// Index of the loaded chunk
+    chunk_idx: usize,
+    /// The chunk being read back from disk, replacing the left data of the
+    /// stream once it is read
+    loading: Option<BuildChunkLoader>,
+    /// Memory reservation for the loaded chunk
+    reservation: MemoryReservation,
+    /// The right side, spilled while combined with the first chunk
+    probe: SpilledProbeSide,
 }
 
 impl<T: BatchTransformer + Unpin + Send> RecordBatchStream for CrossJoinStream<T> {
@@ -468,12 +523,36 @@ impl<T: BatchTransformer> CrossJoinStream<T> {
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
         let build_timer = self.join_metrics.build_time.timer();
-        let left_data = match ready!(self.left_fut.get(cx)) {
+        let left_data = match ready!(self.left_fut.get_shared(cx)) {
             Ok(left_data) => left_data,
             Err(e) => return Poll::Ready(Err(e)),
         };
         build_timer.done();
 
+        if let Some(spilled) = &left_data.spilled {
+            let Some(spill) = &self.spill else {
+                return Poll::Ready(internal_err!(
+                    "Expected spill config for a spilled build side"
+                ));
+            };
+            let mut reservation = left_data.reservation.new_empty();
+            let loading = spilled.load_chunk(0, &mut reservation)?;
+            let probe = SpilledProbeSide::try_new(
+                &self.right.schema(),
+                spill,
+                self.join_metrics.clone(),
+            )?;
+            self.chunked = Some(ChunkedCrossJoin {
+                left: Arc::clone(&left_data),
+                chunk_idx: 0,
+                loading: Some(loading),
+                reservation,
+                probe,
+            });
+            self.state = CrossJoinStreamState::FetchProbeBatch;
+            return Poll::Ready(Ok(StatefulStreamResult::Continue));
+        }
+
         let left_data = left_data.merged_batch.clone();
         let result = if left_data.num_rows() == 0 {
             StatefulStreamResult::Ready(None)
@@ -487,23 +566,74 @@ impl<T: BatchTransformer> CrossJoinStream<T> {
 
     /// Fetches the probe (right) batch, updates the metrics, and save the batch in the state.
     /// Then, the state is updated to build result batches.
+    ///
+    /// With a spilled left side, the chunk to combine the right side with is
+    /// first read back from disk if it is not loaded yet.
     fn fetch_probe_batch(
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        if let Some(chunked) = &mut self.chunked {
+            if let Some(loading) = &mut chunked.loading {
+                self.left_data = ready!(loading.poll_load(cx, &mut chunked.reservation))?;
+                chunked.loading = None;
+            }
+        }
+
         self.left_index = 0;
-        let right_data = match ready!(self.right.poll_next_unpin(cx)) {
+        // The right side is re-scanned from disk for the chunks of a spilled
+        // left side following the first one
+        let rescan = matches!(&self.chunked, Some(chunked) if chunked.chunk_idx > 0);
+        let right_data = match &mut self.chunked {
+            Some(chunked) if rescan => ready!(chunked.probe.poll_next_batch(cx)),
+            _ => ready!(self.right.poll_next_unpin(cx)),
+        };
+        let right_data = match right_data {
             Some(Ok(right_data)) => right_data,
             Some(Err(e)) => return Poll::Ready(Err(e)),
+            None if self.chunked.is_some() => {
+                return Poll::Ready(self.next_build_chunk())
+            }
             None => return Poll::Ready(Ok(StatefulStreamResult::Ready(None))),
         };
-        self.join_metrics.input_batches.add(1);
-        self.join_metrics.input_rows.add(right_data.num_rows());
+        if !rescan {
+            if let Some(chunked) = &mut self.chunked {
+                chunked.probe.write(&right_data)?;
+            }
+            self.join_metrics.input_batches.add(1);
+            self.join_metrics.input_rows.add(right_data.num_rows());
+        }
 
         self.state = CrossJoinStreamState::BuildBatches(right_data);
         Poll::Ready(Ok(StatefulStreamResult::Continue))
     }
 
+    /// Loads the next chunk of a spilled left side once the right side has been
+    /// combined with the current one, and rewinds the spilled right side. The
+    /// execution terminates after the last chunk.
+    fn next_build_chunk(&mut self) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
+        let Some(chunked) = self.chunked.as_mut() else {
+            return internal_err!("Expected chunked join state in FetchProbeBatch state");
+        };
+        let Some(spilled) = &chunked.left.spilled else {
+            return internal_err!(
+                "Expected a spilled build side in FetchProbeBatch state"
+            );
+        };
+
+        chunked.chunk_idx += 1;
+        if chunked.chunk_idx >= spilled.num_chunks() {
+            self.left_data = RecordBatch::new_empty(self.left_data.schema());
+            chunked.reservation.free();
+            return Ok(StatefulStreamResult::Ready(None));
+        }
+
+        chunked.probe.rewind()?;
+        chunked.loading =
+            Some(spilled.load_chunk(chunked.chunk_idx, &mut chunked.reservation)?);
+        Ok(StatefulStreamResult::Continue)
+    }
+
     /// Joins the the indexed row of left data with the current probe batch.
     /// If all the results are produced, the state is set to fetch new probe batch.
     fn build_batches(&mut self) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
@@ -543,9 +673,13 @@ impl<T: BatchTransformer> CrossJoinStream<T> {
 mod tests {
     use super::*;
     use crate::common;
+    use crate::joins::test_utils::{
+        collect_partitioned_sorted_lines, collect_sorted_lines, spilling_join_inputs,
+    };
     use crate::test::build_table_scan_i32;
 
     use datafusion_common::{assert_batches_sorted_eq, assert_contains};
+    use datafusion_execution::disk_manager::DiskManagerConfig;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
 
     async fn join_collect(
@@ -578,12 +712,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -596,6 +734,8 @@ mod tests {
                 max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                 min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                 null_count: Precision::Exact(2),
+                histogram: None,
+                most_common_values: None,
             }],
         };
 
@@ -610,18 +750,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3 * right_row_count),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(3),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                     null_count: Precision::Exact(2 * left_row_count),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -642,12 +788,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -660,6 +810,8 @@ mod tests {
                 max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                 min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                 null_count: Precision::Exact(2),
+                histogram: None,
+                most_common_values: None,
             }],
         };
 
@@ -674,18 +826,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Absent, // we don't know the row count on the right
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Absent, // we don't know the row count on the right
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(3),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                     null_count: Precision::Exact(2 * left_row_count),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -733,6 +891,7 @@ mod tests {
     async fn test_overallocation() -> Result<()> {
         let runtime = RuntimeEnvBuilder::new()
             .with_memory_limit(100, 1.0)
+            .with_disk_manager(DiskManagerConfig::Disabled)
             .build_arc()?;
         let task_ctx = TaskContext::default().with_runtime(runtime);
         let task_ctx = Arc::new(task_ctx);
@@ -758,6 +917,59 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_spill_left_side() -> Result<()> {
+        let (left, right, left_batch_size) = spilling_join_inputs()?;
+
+        let join = Arc::new(CrossJoinExec::new(Arc::clone(&left), Arc::clone(&right)))
+            as Arc<dyn ExecutionPlan>;
+        let expected =
+            collect_sorted_lines(&join, Arc::new(TaskContext::default())).await?;
+        assert_eq!(expected.len(), 20 * 12 + 4);
+
+        // the left side only fits in memory in chunks
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(6 * left_batch_size + left_batch_size / 2, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let join = Arc::new(CrossJoinExec::new(left, right)) as Arc<dyn ExecutionPlan>;
+        let actual = collect_sorted_lines(&join, task_ctx).await?;
+
+        assert_eq!(actual, expected);
+        assert!(join.metrics().unwrap().spill_count().unwrap() > 0);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_spill_left_side_concurrent_right_partitions() -> Result<()> {
+        let (left, right, left_batch_size) = spilling_join_inputs()?;
+
+        let join = Arc::new(CrossJoinExec::new(Arc::clone(&left), Arc::clone(&right)))
+            as Arc<dyn ExecutionPlan>;
+        let expected =
+            collect_sorted_lines(&join, Arc::new(TaskContext::default())).await?;
+
+        // the left side only fits in memory in chunks, which the partitions of
+        // the right side read back from disk at the same time
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(6 * left_batch_size + left_batch_size / 2, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let join = Arc::new(CrossJoinExec::new(left, right)) as Arc<dyn ExecutionPlan>;
+        let actual = collect_partitioned_sorted_lines(&join, task_ctx).await?;
+
+        assert_eq!(actual, expected);
+        // at least two chunks of the left side, and the right side of each of
+        // the 4 partitions
+        let spill_count = join.metrics().unwrap().spill_count().unwrap();
+        assert!(spill_count >= 2 + 4, "spill count {spill_count}");
+
+        Ok(())
+    }
+
     /// Returns the column names on the schema
     fn columns(schema: &Schema) -> Vec<String> {
         schema.fields().iter().map(|f| f.name().clone()).collect()
@@ -0,0 +1,639 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the dependent join execution plan.
+//!
+//! A dependent join evaluates its right side once for every distinct
+//! combination of values of the left columns the right side refers to, such as
+//! a `LATERAL` subquery that can't be decorrelated into a regular join. The
+//! references to the left columns are planned as [`OuterParameter`]
+//! placeholders, which are bound to the values of each combination before the
+//! right side is executed.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{self, Formatter};
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+use arrow::array::{new_null_array, ArrayRef, UInt32Array};
+use arrow::compute::{concat_batches, filter_record_batch, take};
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion_common::cast::as_boolean_array;
+use datafusion_common::{
+    internal_datafusion_err, internal_err, not_impl_err, JoinSide, JoinType, Result,
+    ScalarValue,
+};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr, PhysicalExprRef};
+use futures::StreamExt;
+use parking_lot::RwLock;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::joins::utils::build_join_schema;
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::recursive_query::reset_plan_states;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    collect, DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
+    PlanProperties, SendableRecordBatchStream, Statistics,
+};
+
+/// The values of the outer columns the right side of a [`DependentJoinExec`]
+/// is evaluated for, read by the [`OuterParameter`] placeholders in the right
+/// side
+#[derive(Debug, Default)]
+pub struct OuterParameters {
+    values: RwLock<Vec<ScalarValue>>,
+    /// Held while the right side is evaluated, as the partitions of the join
+    /// share the right side and its parameters
+    evaluation: Mutex<()>,
+}
+
+impl OuterParameters {
+    /// Creates parameters that are not bound to any values yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of the outer column at `index`
+    pub fn value(&self, index: usize) -> Result<ScalarValue> {
+        self.values.read().get(index).cloned().ok_or_else(|| {
+            internal_datafusion_err!("Outer parameter {index} is not bound")
+        })
+    }
+
+    /// Binds the parameters to `values`, which stay bound until the returned
+    /// guard is dropped
+    async fn bind(&self, values: Vec<ScalarValue>) -> MutexGuard<'_, ()> {
+        let guard = self.evaluation.lock().await;
+        *self.values.write() = values;
+        guard
+    }
+}
+
+/// A placeholder for the value of one of the outer columns of a
+/// [`DependentJoinExec`], which replaces the references to that column in
+/// the right side.
+///
+/// Its only argument is the name of the outer column, for display.
+#[derive(Debug)]
+pub struct OuterParameter {
+    parameters: Arc<OuterParameters>,
+    index: usize,
+    data_type: DataType,
+    signature: Signature,
+}
+
+impl OuterParameter {
+    /// Creates a placeholder for the outer column at `index` of `parameters`,
+    /// whose values are cast to `data_type`
+    pub fn new(
+        parameters: Arc<OuterParameters>,
+        index: usize,
+        data_type: DataType,
+    ) -> Self {
+        Self {
+            parameters,
+            index,
+            data_type,
+            signature: Signature::exact(vec![DataType::Utf8], Volatility::Volatile),
+        }
+    }
+}
+
+impl ScalarUDFImpl for OuterParameter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "outer_parameter"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.data_type.clone())
+    }
+
+    fn invoke_batch(
+        &self,
+        _args: &[ColumnarValue],
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        let value = self.parameters.value(self.index)?;
+        Ok(ColumnarValue::Scalar(value.cast_to(&self.data_type)?))
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|other| {
+            Arc::ptr_eq(&self.parameters, &other.parameters) && self.index == other.index
+        })
+    }
+
+    fn hash_value(&self) -> u64 {
+        let hasher = &mut DefaultHasher::new();
+        self.name().hash(hasher);
+        self.index.hash(hasher);
+        hasher.finish()
+    }
+}
+
+/// Execution plan for a dependent join, see the [module level documentation](self)
+#[derive(Debug, Clone)]
+pub struct DependentJoinExec {
+    /// The side whose rows the right side depends on
+    left: Arc<dyn ExecutionPlan>,
+    /// The side evaluated for every combination of values of `outer_columns`
+    right: Arc<dyn ExecutionPlan>,
+    /// The columns of the left side referred to by the right side
+    outer_columns: Vec<Column>,
+    /// The parameters of the right side, bound to the values of `outer_columns`
+    parameters: Arc<OuterParameters>,
+    /// Filter applied to the joined rows, evaluated against the join schema
+    filter: Option<PhysicalExprRef>,
+    /// How the join is performed, either `Inner` or `Left`
+    join_type: JoinType,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl DependentJoinExec {
+    /// Tries to create a new [DependentJoinExec].
+    /// # Error
+    /// This function errors when the join type is neither `Inner` nor `Left`.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        outer_columns: Vec<Column>,
+        parameters: Arc<OuterParameters>,
+        filter: Option<PhysicalExprRef>,
+        join_type: JoinType,
+    ) -> Result<Self> {
+        if !matches!(join_type, JoinType::Inner | JoinType::Left) {
+            return not_impl_err!("DependentJoinExec does not support {join_type} joins");
+        }
+        let schema =
+            Arc::new(build_join_schema(&left.schema(), &right.schema(), &join_type).0);
+        let cache = Self::compute_properties(&left, &right, Arc::clone(&schema));
+        Ok(Self {
+            left,
+            right,
+            outer_columns,
+            parameters,
+            filter,
+            join_type,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// The side whose rows the right side depends on
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// The side evaluated for every combination of values of the outer columns
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// The columns of the left side referred to by the right side
+    pub fn outer_columns(&self) -> &[Column] {
+        &self.outer_columns
+    }
+
+    /// The parameters of the right side
+    pub fn parameters(&self) -> &Arc<OuterParameters> {
+        &self.parameters
+    }
+
+    /// Filter applied to the joined rows
+    pub fn filter(&self) -> Option<&PhysicalExprRef> {
+        self.filter.as_ref()
+    }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> &JoinType {
+        &self.join_type
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+    ) -> PlanProperties {
+        // The rows of the left side are joined in order, and keep the
+        // positions of their columns. The properties of the right side only
+        // hold for a single evaluation, such as the columns that are constant
+        // for the values of its parameters, so they are not kept.
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            EquivalenceProperties::new(right.schema()),
+            &JoinType::Inner,
+            schema,
+            &[true, false],
+            Some(JoinSide::Left),
+            &[],
+        );
+
+        PlanProperties::new(
+            eq_properties,
+            left.output_partitioning().clone(),
+            left.execution_mode(),
+        )
+    }
+}
+
+impl DisplayAs for DependentJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let outer_columns = self
+                    .outer_columns
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "DependentJoin: join_type={}, outer_columns=[{}]",
+                    self.join_type, outer_columns
+                )?;
+                if let Some(filter) = &self.filter {
+                    write!(f, ", filter={filter}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for DependentJoinExec {
+    fn name(&self) -> &'static str {
+        "DependentJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(DependentJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                self.outer_columns.clone(),
+                Arc::clone(&self.parameters),
+                self.filter.clone(),
+                self.join_type,
+            )?)),
+            _ => internal_err!("DependentJoinExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let reservation =
+            MemoryConsumer::new(format!("DependentJoinStream[{partition}]"))
+                .register(context.memory_pool());
+        let state = DependentJoinStreamState {
+            schema: Arc::clone(&self.schema),
+            left: self.left.execute(partition, Arc::clone(&context))?,
+            right: Arc::clone(&self.right),
+            outer_columns: self.outer_columns.clone(),
+            parameters: Arc::clone(&self.parameters),
+            filter: self.filter.clone(),
+            join_type: self.join_type,
+            context,
+            reservation,
+            finished: false,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        };
+        let stream = futures::stream::unfold(state, |mut this| async move {
+            if this.finished {
+                return None;
+            }
+            let result = match this.left.next().await {
+                Some(Ok(batch)) => this.join_left_batch(batch).await,
+                Some(Err(e)) => Err(e),
+                None => {
+                    this.finished = true;
+                    return None;
+                }
+            };
+            this.finished = result.is_err();
+            Some((result, this))
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&self.schema))
+    }
+}
+
+/// State of the stream returned by [`DependentJoinExec::execute`]
+struct DependentJoinStreamState {
+    schema: SchemaRef,
+    left: SendableRecordBatchStream,
+    right: Arc<dyn ExecutionPlan>,
+    outer_columns: Vec<Column>,
+    parameters: Arc<OuterParameters>,
+    filter: Option<PhysicalExprRef>,
+    join_type: JoinType,
+    context: Arc<TaskContext>,
+    /// Accounts for the rows of the right side buffered for a left batch
+    reservation: MemoryReservation,
+    finished: bool,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl DependentJoinStreamState {
+    /// Joins every row of `batch` with the right side evaluated for its
+    /// outer values. The right side is evaluated once for every distinct
+    /// combination of outer values in the batch.
+    async fn join_left_batch(&mut self, batch: RecordBatch) -> Result<RecordBatch> {
+        let num_rows = batch.num_rows();
+        let outer_arrays = self
+            .outer_columns
+            .iter()
+            .map(|column| column.evaluate(&batch)?.into_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut right_batches: HashMap<Vec<ScalarValue>, RecordBatch> = HashMap::new();
+        let mut output = Vec::with_capacity(num_rows);
+        for row in 0..num_rows {
+            let outer_values = outer_arrays
+                .iter()
+                .map(|array| ScalarValue::try_from_array(array, row))
+                .collect::<Result<Vec<_>>>()?;
+            let right = match right_batches.get(&outer_values) {
+                Some(right) => right.clone(),
+                None => {
+                    let right = self.evaluate_right(outer_values.clone()).await?;
+                    self.reservation.try_grow(right.get_array_memory_size())?;
+                    right_batches.insert(outer_values, right.clone());
+                    right
+                }
+            };
+            if let Some(joined) = self.join_row(&batch, row, &right)? {
+                output.push(joined);
+            }
+        }
+        // The right side is evaluated again for the next batch
+        drop(right_batches);
+        self.reservation.free();
+
+        let result = concat_batches(&self.schema, &output)?;
+        self.baseline_metrics.record_output(result.num_rows());
+        Ok(result)
+    }
+
+    /// Runs the right side with its parameters bound to `outer_values`,
+    /// returning its rows as a single batch
+    fn evaluate_right(
+        &self,
+        outer_values: Vec<ScalarValue>,
+    ) -> impl Future<Output = Result<RecordBatch>> + Send + 'static {
+        let right = Arc::clone(&self.right);
+        let parameters = Arc::clone(&self.parameters);
+        let context = Arc::clone(&self.context);
+        async move {
+            let _bound = parameters.bind(outer_values).await;
+            let schema = right.schema();
+            // The right side may keep state from its previous execution, such
+            // as the build side of a hash join
+            let right = reset_plan_states(right)?;
+            let batches = collect(right, context).await?;
+            Ok(concat_batches(&schema, &batches)?)
+        }
+    }
+
+    /// Joins the row `row` of `left` with the rows of `right` that pass the
+    /// filter, or with nulls for left joins if there are none
+    fn join_row(
+        &self,
+        left: &RecordBatch,
+        row: usize,
+        right: &RecordBatch,
+    ) -> Result<Option<RecordBatch>> {
+        let joined =
+            self.build_batch(left, row, right.columns().to_vec(), right.num_rows())?;
+        let joined = match &self.filter {
+            Some(filter) => {
+                let mask = filter.evaluate(&joined)?.into_array(joined.num_rows())?;
+                filter_record_batch(&joined, as_boolean_array(&mask)?)?
+            }
+            None => joined,
+        };
+
+        if joined.num_rows() > 0 {
+            Ok(Some(joined))
+        } else if self.join_type == JoinType::Left {
+            let null_row = self
+                .right
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| new_null_array(field.data_type(), 1))
+                .collect();
+            Ok(Some(self.build_batch(left, row, null_row, 1)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Repeats the row `row` of `left` next to the `num_rows` rows of
+    /// `right_columns`
+    fn build_batch(
+        &self,
+        left: &RecordBatch,
+        row: usize,
+        right_columns: Vec<ArrayRef>,
+        num_rows: usize,
+    ) -> Result<RecordBatch> {
+        let indices = UInt32Array::from(vec![row as u32; num_rows]);
+        let mut columns = left
+            .columns()
+            .iter()
+            .map(|array| take(array, &indices, None))
+            .collect::<Result<Vec<_>, _>>()?;
+        columns.extend(right_columns);
+        Ok(RecordBatch::try_new_with_options(
+            Arc::clone(&self.schema),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(num_rows)),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::filter::FilterExec;
+    use crate::test::build_table_scan_i32;
+
+    use datafusion_common::{assert_batches_eq, assert_contains};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_expr::{Operator, ScalarUDF};
+    use datafusion_physical_expr::expressions::{binary, lit};
+    use datafusion_physical_expr::ScalarFunctionExpr;
+
+    /// Joins every row of the left side with the rows of the right side whose
+    /// `a2` is equal to its `b1`
+    fn build_dependent_join(join_type: JoinType) -> Result<DependentJoinExec> {
+        let left = build_table_scan_i32(
+            ("a1", &vec![1, 2, 3, 4]),
+            ("b1", &vec![1, 2, 1, 5]),
+            ("c1", &vec![7, 8, 9, 10]),
+        );
+        let right = build_table_scan_i32(
+            ("a2", &vec![1, 1, 2, 3]),
+            ("b2", &vec![10, 11, 12, 13]),
+            ("c2", &vec![14, 15, 16, 17]),
+        );
+
+        let parameters = Arc::new(OuterParameters::new());
+        let parameter = OuterParameter::new(Arc::clone(&parameters), 0, DataType::Int32);
+        let parameter = Arc::new(ScalarFunctionExpr::new(
+            "outer_parameter",
+            Arc::new(ScalarUDF::new_from_impl(parameter)),
+            vec![lit("b1")],
+            DataType::Int32,
+        ));
+        let predicate = binary(
+            Arc::new(Column::new("a2", 0)),
+            Operator::Eq,
+            parameter,
+            &right.schema(),
+        )?;
+        let right = Arc::new(FilterExec::try_new(predicate, right)?);
+
+        DependentJoinExec::try_new(
+            left,
+            right,
+            vec![Column::new("b1", 1)],
+            parameters,
+            None,
+            join_type,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_inner_join() -> Result<()> {
+        let join = build_dependent_join(JoinType::Inner)?;
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        let batches = common::collect(stream).await?;
+
+        let expected = [
+            "+----+----+----+----+----+----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2 |",
+            "+----+----+----+----+----+----+",
+            "| 1  | 1  | 7  | 1  | 10 | 14 |",
+            "| 1  | 1  | 7  | 1  | 11 | 15 |",
+            "| 2  | 2  | 8  | 2  | 12 | 16 |",
+            "| 3  | 1  | 9  | 1  | 10 | 14 |",
+            "| 3  | 1  | 9  | 1  | 11 | 15 |",
+            "+----+----+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_left_join() -> Result<()> {
+        let join = build_dependent_join(JoinType::Left)?;
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        let batches = common::collect(stream).await?;
+
+        let expected = [
+            "+----+----+----+----+----+----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2 |",
+            "+----+----+----+----+----+----+",
+            "| 1  | 1  | 7  | 1  | 10 | 14 |",
+            "| 1  | 1  | 7  | 1  | 11 | 15 |",
+            "| 2  | 2  | 8  | 2  | 12 | 16 |",
+            "| 3  | 1  | 9  | 1  | 10 | 14 |",
+            "| 3  | 1  | 9  | 1  | 11 | 15 |",
+            "| 4  | 5  | 10 |    |    |    |",
+            "+----+----+----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_overallocation() -> Result<()> {
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(100, 1.0)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        let join = build_dependent_join(JoinType::Inner)?;
+        let stream = join.execute(0, task_ctx)?;
+        let err = common::collect(stream).await.unwrap_err();
+
+        assert_contains!(err.to_string(), "Resources exhausted");
+        assert_contains!(err.to_string(), "DependentJoinStream[0]");
+
+        Ok(())
+    }
+}
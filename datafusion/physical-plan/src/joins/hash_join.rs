@@ -32,7 +32,7 @@ use super::{
 use crate::ExecutionPlanProperties;
 use crate::{
     coalesce_partitions::CoalescePartitionsExec,
-    common::can_project,
+    common::{can_project, IPCWriter},
     execution_mode_from_children, handle_state,
     hash_utils::create_hashes,
     joins::utils::{
@@ -44,37 +44,43 @@ use crate::{
         JoinHashMapType, JoinOn, JoinOnRef, StatefulStreamResult,
     },
     metrics::{ExecutionPlanMetricsSet, MetricsSet},
-    DisplayAs, DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
-    Partitioning, PlanProperties, RecordBatchStream, SendableRecordBatchStream,
-    Statistics,
+    spill::{read_spill_as_stream, spill_file_reader, spill_writer},
+    DisplayAs, DisplayFormatType, Distribution, EmptyRecordBatchStream, ExecutionMode,
+    ExecutionPlan, Partitioning, PlanProperties, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
 };
 
 use arrow::array::{
     Array, ArrayRef, BooleanArray, BooleanBufferBuilder, UInt32Array, UInt64Array,
 };
 use arrow::compute::kernels::cmp::{eq, not_distinct};
-use arrow::compute::{and, concat_batches, take, FilterBuilder};
+use arrow::compute::{and, concat_batches, take, take_record_batch, FilterBuilder};
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use arrow::util::bit_util;
 use arrow_array::cast::downcast_array;
 use arrow_schema::ArrowError;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::utils::memory::estimate_memory_size;
 use datafusion_common::{
-    internal_datafusion_err, internal_err, plan_err, project_schema, DataFusionError,
-    JoinSide, JoinType, Result,
+    internal_datafusion_err, internal_err, not_impl_err, plan_err, project_schema,
+    DataFusionError, JoinSide, JoinType, Result,
 };
+use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
-use datafusion_execution::TaskContext;
+use datafusion_execution::{DiskManager, TaskContext};
 use datafusion_physical_expr::equivalence::{
     join_equivalence_properties, ProjectionMapping,
 };
-use datafusion_physical_expr::PhysicalExprRef;
+use datafusion_physical_expr::expressions::{
+    ColumnBounds, DynamicFilterExpr, DynamicFilterValues,
+};
+use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
 
 use ahash::RandomState;
 use datafusion_expr::Operator;
 use datafusion_physical_expr_common::datum::compare_op_for_nested;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
 use parking_lot::Mutex;
 
 type SharedBitmapBuilder = Mutex<BooleanBufferBuilder>;
@@ -136,6 +142,269 @@ impl JoinLeftData {
     }
 }
 
+/// Build side of a join, as collected by [`collect_left_input`]
+enum BuildSideInput {
+    /// The build side fits in memory and its hash table has been created
+    InMemory(Arc<JoinLeftData>),
+    /// The build side exceeded the memory limit and has been hash-partitioned
+    /// to disk by its join keys
+    Spilled(SpilledBuildSide),
+}
+
+/// Build side data which has been hash-partitioned into spill files by
+/// [`SpillPartitioner`]
+struct SpilledBuildSide {
+    /// Schema of the build side
+    schema: SchemaRef,
+    /// Spill file for every partition, if any rows were routed to it. The files
+    /// are taken by the (single) probe stream consuming this build side.
+    partitions: Mutex<Vec<Option<RefCountedTempFile>>>,
+    /// Disk manager used to create spill files
+    disk_manager: Arc<DiskManager>,
+    /// Compression codec of the spill files
+    spill_compression: SpillCompression,
+    /// Empty reservation, used to account for the memory of partitions loaded
+    /// back from disk
+    reservation: MemoryReservation,
+}
+
+/// Collects the join key bounds of the build side partitions of a
+/// [`HashJoinExec`] and publishes them to its [`DynamicFilterExpr`] once all
+/// partitions have been collected
+#[derive(Debug)]
+struct BuildSideBounds {
+    filter: Arc<DynamicFilterExpr>,
+    state: Mutex<BuildSideBoundsState>,
+}
+
+#[derive(Debug)]
+struct BuildSideBoundsState {
+    /// Number of build side partitions reported so far
+    reported: usize,
+    /// Merged bounds of the reported partitions, `None` if the bounds of
+    /// any of them are unknown
+    values: Option<DynamicFilterValues>,
+}
+
+impl BuildSideBounds {
+    fn new(filter: Arc<DynamicFilterExpr>) -> Self {
+        Self {
+            filter,
+            state: Mutex::new(BuildSideBoundsState {
+                reported: 0,
+                values: Some(DynamicFilterValues::Empty),
+            }),
+        }
+    }
+
+    /// Returns new bounds publishing to the same filter, for a join that has
+    /// been rebuilt and may be executed again with a different build side.
+    ///
+    /// The values published by previous executions are withdrawn from the
+    /// filter, including the copies of it held by probe side scans.
+    fn reset(&self) -> Self {
+        self.filter.reset();
+        Self::new(Arc::clone(&self.filter))
+    }
+
+    /// Reports the bounds of one of `partition_count` build side partitions,
+    /// publishing the filter if this was the last one
+    fn report(
+        &self,
+        values: Option<DynamicFilterValues>,
+        partition_count: usize,
+        max_distinct_values: usize,
+    ) {
+        let mut state = self.state.lock();
+        state.reported += 1;
+        state.values = match (state.values.take(), values) {
+            (Some(DynamicFilterValues::Empty), values)
+            | (values, Some(DynamicFilterValues::Empty)) => values,
+            (
+                Some(DynamicFilterValues::Bounds(bounds)),
+                Some(DynamicFilterValues::Bounds(other)),
+            ) => Some(DynamicFilterValues::Bounds(
+                bounds
+                    .into_iter()
+                    .zip(other)
+                    .map(|(bounds, other)| bounds.merge(other, max_distinct_values))
+                    .collect(),
+            )),
+            _ => None,
+        };
+        if state.reported == partition_count {
+            if let Some(values) = state.values.take() {
+                self.filter.update(values);
+            }
+        }
+    }
+}
+
+/// Reports the bounds of a build side partition to the [`BuildSideBounds`]
+/// of a join
+struct BuildSideBoundsReporter {
+    bounds: Arc<BuildSideBounds>,
+    /// Number of build side partitions reporting to `bounds`
+    partition_count: usize,
+    /// Maximum number of distinct join keys listed in the filter
+    max_distinct_values: usize,
+}
+
+impl BuildSideBoundsReporter {
+    fn report(&self, input: &BuildSideInput, on_left: &[PhysicalExprRef]) {
+        let values = build_side_key_bounds(input, on_left, self.max_distinct_values);
+        self.bounds
+            .report(values, self.partition_count, self.max_distinct_values);
+    }
+}
+
+/// Computes the bounds of the join keys of a build side partition, or `None`
+/// if they can't be determined (e.g. because the partition was spilled)
+fn build_side_key_bounds(
+    input: &BuildSideInput,
+    on_left: &[PhysicalExprRef],
+    max_distinct_values: usize,
+) -> Option<DynamicFilterValues> {
+    let BuildSideInput::InMemory(data) = input else {
+        return None;
+    };
+    let batch = data.batch();
+    let mut bounds = Vec::with_capacity(on_left.len());
+    for expr in on_left {
+        let array = expr
+            .evaluate(batch)
+            .and_then(|v| v.into_array(batch.num_rows()))
+            .ok()?;
+        match ColumnBounds::try_new(&array, max_distinct_values).ok()? {
+            Some(column_bounds) => bounds.push(column_bounds),
+            // A key without non-null values can't match any probe side row
+            None => return Some(DynamicFilterValues::Empty),
+        }
+    }
+    Some(DynamicFilterValues::Bounds(bounds))
+}
+
+/// Number of partitions spilled build and probe side data is split into
+const SPILL_PARTITION_FANOUT: usize = 16;
+
+/// Maximum number of times a spilled partition which doesn't fit in memory is
+/// split again. Once exceeded, the join fails with the original memory error,
+/// e.g. if a single join key has more rows than the memory limit allows for.
+const MAX_SPILL_DEPTH: usize = 3;
+
+/// Hash-partitions record batches by their join keys into
+/// [`SPILL_PARTITION_FANOUT`] spill files.
+///
+/// Every level of partitioning uses its own hash seed, so that rows which
+/// ended up in the same partition on a previous level (or in the same input
+/// partition of the join, after hash repartitioning) are spread again.
+struct SpillPartitioner {
+    /// Join key expressions used to route rows
+    on: Vec<PhysicalExprRef>,
+    /// Random state used to route rows, depending on the partitioning depth
+    random_state: RandomState,
+    /// Schema of the partitioned batches
+    schema: SchemaRef,
+    /// Lazily created spill file and writer for every partition
+    writers: Vec<Option<(RefCountedTempFile, IPCWriter)>>,
+    /// Disk manager used to create spill files
+    disk_manager: Arc<DiskManager>,
+    /// Compression codec of the spill files
+    compression: SpillCompression,
+    /// Scratch space for computing hashes
+    hashes_buffer: Vec<u64>,
+}
+
+impl SpillPartitioner {
+    fn new(
+        on: Vec<PhysicalExprRef>,
+        schema: SchemaRef,
+        depth: usize,
+        disk_manager: Arc<DiskManager>,
+        compression: SpillCompression,
+    ) -> Self {
+        Self {
+            on,
+            random_state: RandomState::with_seeds(depth as u64 + 1, 0, 0, 0),
+            schema,
+            writers: (0..SPILL_PARTITION_FANOUT).map(|_| None).collect(),
+            disk_manager,
+            compression,
+            hashes_buffer: vec![],
+        }
+    }
+
+    /// Routes the rows of `batch` into the spill files of their partitions
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let keys_values = self
+            .on
+            .iter()
+            .map(|c| c.evaluate(batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
+
+        let mut indices = vec![vec![]; SPILL_PARTITION_FANOUT];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % SPILL_PARTITION_FANOUT as u64) as usize].push(row as u32);
+        }
+
+        for (partition, indices) in indices.into_iter().enumerate() {
+            if indices.is_empty() {
+                continue;
+            }
+            let partition_batch = take_record_batch(batch, &UInt32Array::from(indices))?;
+            let (file, writer) = match &mut self.writers[partition] {
+                Some(writer) => writer,
+                writer @ None => {
+                    let file = self.disk_manager.create_tmp_file("HashJoinSpill")?;
+                    let ipc_writer = spill_writer(&file, &self.schema, self.compression)?;
+                    writer.insert((file, ipc_writer))
+                }
+            };
+            writer.write(&partition_batch)?;
+            file.update_disk_usage()?;
+        }
+
+        Ok(())
+    }
+
+    /// Routes all rows of a previously written spill `file`
+    fn write_spill_file(&mut self, file: &RefCountedTempFile) -> Result<()> {
+        for batch in spill_file_reader(file)? {
+            self.write(&batch?)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes all spill files, returning them in partition order
+    fn finish(
+        self,
+        metrics: &BuildProbeJoinMetrics,
+    ) -> Result<Vec<Option<RefCountedTempFile>>> {
+        self.writers
+            .into_iter()
+            .map(|writer| {
+                writer
+                    .map(|(mut file, mut writer)| {
+                        writer.finish()?;
+                        file.update_disk_usage()?;
+                        metrics.spill_count.add(1);
+                        metrics.spilled_bytes.add(writer.num_bytes);
+                        metrics
+                            .spill_file_bytes
+                            .add(file.current_disk_usage() as usize);
+                        metrics.spilled_rows.add(writer.num_rows);
+                        Ok(file)
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+}
+
 #[allow(rustdoc::private_intra_doc_links)]
 /// Join execution plan: Evaluates eqijoin predicates in parallel on multiple
 /// partitions using a hash table and an optional filter list to apply post
@@ -295,6 +564,31 @@ impl JoinLeftData {
 ///                       └───────────────┘     └───────────────┘
 /// ```
 ///
+/// # Spilling
+///
+/// In [`PartitionMode::Partitioned`] mode, if the build side of a partition
+/// doesn't fit in memory and the [`DiskManager`] allows temporary files, the
+/// join falls back to a "Grace" hash join: the build side is hash-partitioned
+/// by its join keys into spill files, the probe side is partitioned the same
+/// way, and each pair of partitions is then joined in memory. Partitions which
+/// still don't fit in memory are split again, up to a fixed depth.
+///
+/// Spilling is disabled if the probe side has an output ordering, as joining
+/// partitions one after another doesn't maintain it.
+///
+/// # Dynamic Filters
+///
+/// A join can be given a [`DynamicFilterExpr`] on the join keys of its probe
+/// side (see [`Self::with_dynamic_filter`]). Once the build side has been
+/// collected, the join publishes the bounds of the build side join keys, and
+/// the distinct keys if there are only a few, to that filter. Scans on the
+/// probe side that hold a copy of the filter can then skip data which can't
+/// match any build side row.
+///
+/// Rebuilding the join with [`ExecutionPlan::with_new_children`] withdraws the
+/// published values, so that the rebuilt join can be executed again, e.g. by
+/// a recursive query, with a different build side.
+///
 /// Note that the `Clone` trait is not implemented for this struct due to the
 /// `left_fut` [`OnceAsync`], which is used to coordinate the loading of the
 /// left side with the processing in each output stream.
@@ -314,7 +608,7 @@ pub struct HashJoinExec {
     /// if there is a projection, the schema isn't the same as the output schema.
     join_schema: SchemaRef,
     /// Future that consumes left input and builds the hash table
-    left_fut: OnceAsync<JoinLeftData>,
+    left_fut: OnceAsync<BuildSideInput>,
     /// Shared the `RandomState` for the hashing algorithm
     random_state: RandomState,
     /// Partitioning mode to use
@@ -332,6 +626,8 @@ pub struct HashJoinExec {
     pub null_equals_null: bool,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
+    /// Filter on the probe side join keys, published once the build side is collected
+    dynamic_filter: Option<Arc<BuildSideBounds>>,
 }
 
 impl HashJoinExec {
@@ -355,6 +651,11 @@ impl HashJoinExec {
         if on.is_empty() {
             return plan_err!("On constraints in HashJoinExec should be non-empty");
         }
+        if join_type == &JoinType::AsOf {
+            return not_impl_err!(
+                "HashJoinExec does not support JoinType::AsOf, use AsofJoinExec"
+            );
+        }
 
         check_join_is_valid(&left_schema, &right_schema, &on)?;
 
@@ -393,6 +694,7 @@ impl HashJoinExec {
             column_indices,
             null_equals_null,
             cache,
+            dynamic_filter: None,
         })
     }
 
@@ -437,6 +739,56 @@ impl HashJoinExec {
         self.null_equals_null
     }
 
+    /// Returns the filter on the probe side join keys this join publishes to
+    pub fn dynamic_filter(&self) -> Option<&Arc<DynamicFilterExpr>> {
+        self.dynamic_filter.as_ref().map(|bounds| &bounds.filter)
+    }
+
+    /// Returns whether a dynamic filter on the probe side join keys can be
+    /// used with this join, i.e. whether probe side rows without a matching
+    /// build side row are never part of the output
+    pub fn supports_dynamic_filter(&self) -> bool {
+        !self.null_equals_null
+            && matches!(
+                self.join_type,
+                JoinType::Inner
+                    | JoinType::Left
+                    | JoinType::LeftSemi
+                    | JoinType::LeftAnti
+                    | JoinType::LeftMark
+                    | JoinType::RightSemi
+            )
+    }
+
+    /// Return new instance of [HashJoinExec] that publishes the bounds of its
+    /// build side join keys to `filter`, whose children must be the probe
+    /// side join keys (possibly remapped to the schema of a probe side input).
+    pub fn with_dynamic_filter(&self, filter: Arc<DynamicFilterExpr>) -> Result<Self> {
+        if !self.supports_dynamic_filter() {
+            return plan_err!(
+                "Dynamic filters are not supported for {} HashJoinExec",
+                self.join_type
+            );
+        }
+        if filter.children().len() != self.on.len() {
+            return plan_err!(
+                "Dynamic filter of HashJoinExec must have one child per join key"
+            );
+        }
+        let mut join = Self::try_new(
+            Arc::clone(&self.left),
+            Arc::clone(&self.right),
+            self.on.clone(),
+            self.filter.clone(),
+            &self.join_type,
+            self.projection.clone(),
+            self.mode,
+            self.null_equals_null,
+        )?;
+        join.dynamic_filter = Some(Arc::new(BuildSideBounds::new(filter)));
+        Ok(join)
+    }
+
     /// Calculate order preservation flags for this hash join.
     fn maintains_input_order(join_type: JoinType) -> Vec<bool> {
         vec![
@@ -473,7 +825,7 @@ impl HashJoinExec {
             },
             None => None,
         };
-        Self::try_new(
+        let mut join = Self::try_new(
             Arc::clone(&self.left),
             Arc::clone(&self.right),
             self.on.clone(),
@@ -482,7 +834,12 @@ impl HashJoinExec {
             projection,
             self.mode,
             self.null_equals_null,
-        )
+        )?;
+        join.dynamic_filter = self
+            .dynamic_filter
+            .as_ref()
+            .map(|bounds| Arc::new(bounds.reset()));
+        Ok(join)
     }
 
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
@@ -663,7 +1020,7 @@ impl ExecutionPlan for HashJoinExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(HashJoinExec::try_new(
+        let mut join = HashJoinExec::try_new(
             Arc::clone(&children[0]),
             Arc::clone(&children[1]),
             self.on.clone(),
@@ -672,7 +1029,12 @@ impl ExecutionPlan for HashJoinExec {
             self.projection.clone(),
             self.mode,
             self.null_equals_null,
-        )?))
+        )?;
+        join.dynamic_filter = self
+            .dynamic_filter
+            .as_ref()
+            .map(|bounds| Arc::new(bounds.reset()));
+        Ok(Arc::new(join))
     }
 
     fn execute(
@@ -702,6 +1064,22 @@ impl ExecutionPlan for HashJoinExec {
         }
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+        let right_side_ordered = self.right.output_ordering().is_some();
+        let dynamic_filter = self.dynamic_filter.as_ref().map(|bounds| {
+            let partition_count = match self.mode {
+                PartitionMode::Partitioned => left_partitions,
+                _ => 1,
+            };
+            BuildSideBoundsReporter {
+                bounds: Arc::clone(bounds),
+                partition_count,
+                max_distinct_values: context
+                    .session_config()
+                    .options()
+                    .optimizer
+                    .dynamic_join_filter_max_distinct_values,
+            }
+        });
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.once(|| {
                 let reservation =
@@ -716,11 +1094,18 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     self.right().output_partitioning().partition_count(),
+                    false,
+                    dynamic_filter,
                 )
             }),
             PartitionMode::Partitioned => {
+                // Spilled partitions are joined one after another, which doesn't
+                // retain the order of the probe side
+                let can_spill = context.runtime_env().disk_manager.tmp_files_enabled()
+                    && !right_side_ordered;
                 let reservation =
                     MemoryConsumer::new(format!("HashJoinInput[{partition}]"))
+                        .with_can_spill(can_spill)
                         .register(context.memory_pool());
 
                 OnceFut::new(collect_left_input(
@@ -733,6 +1118,8 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     1,
+                    can_spill,
+                    dynamic_filter,
                 ))
             }
             PartitionMode::Auto => {
@@ -773,7 +1160,8 @@ impl ExecutionPlan for HashJoinExec {
             build_side: BuildSide::Initial(BuildSideInitialState { left_fut }),
             batch_size,
             hashes_buffer: vec![],
-            right_side_ordered: self.right.output_ordering().is_some(),
+            right_side_ordered,
+            spill_state: None,
         }))
     }
 
@@ -798,7 +1186,13 @@ impl ExecutionPlan for HashJoinExec {
 }
 
 /// Reads the left (build) side of the input, buffering it in memory, to build a
-/// hash table (`LeftJoinData`)
+/// hash table (`LeftJoinData`).
+///
+/// If `can_spill` is set and the build side doesn't fit in memory, it is
+/// hash-partitioned to disk instead, see [`SpillPartitioner`].
+///
+/// If `dynamic_filter` is set, the bounds of the build side join keys are
+/// reported to it.
 #[allow(clippy::too_many_arguments)]
 async fn collect_left_input(
     partition: Option<usize>,
@@ -810,7 +1204,42 @@ async fn collect_left_input(
     reservation: MemoryReservation,
     with_visited_indices_bitmap: bool,
     probe_threads_count: usize,
-) -> Result<JoinLeftData> {
+    can_spill: bool,
+    dynamic_filter: Option<BuildSideBoundsReporter>,
+) -> Result<BuildSideInput> {
+    let input = collect_build_side(
+        partition,
+        random_state,
+        left,
+        &on_left,
+        context,
+        metrics,
+        reservation,
+        with_visited_indices_bitmap,
+        probe_threads_count,
+        can_spill,
+    )
+    .await?;
+    if let Some(dynamic_filter) = dynamic_filter {
+        dynamic_filter.report(&input, &on_left);
+    }
+    Ok(input)
+}
+
+/// Buffers the build side in memory or spills it, see [`collect_left_input`]
+#[allow(clippy::too_many_arguments)]
+async fn collect_build_side(
+    partition: Option<usize>,
+    random_state: RandomState,
+    left: Arc<dyn ExecutionPlan>,
+    on_left: &[PhysicalExprRef],
+    context: Arc<TaskContext>,
+    metrics: BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    with_visited_indices_bitmap: bool,
+    probe_threads_count: usize,
+    can_spill: bool,
+) -> Result<BuildSideInput> {
     let schema = left.schema();
 
     let (left_input, left_input_partition) = if let Some(partition) = partition {
@@ -822,38 +1251,146 @@ async fn collect_left_input(
     };
 
     // Depending on partition argument load single partition or whole left side in memory
-    let stream = left_input.execute(left_input_partition, Arc::clone(&context))?;
-
-    // This operation performs 2 steps at once:
-    // 1. creates a [JoinHashMap] of all batches from the stream
-    // 2. stores the batches in a vector.
-    let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = stream
-        .try_fold(initial, |mut acc, batch| async {
-            let batch_size = batch.get_array_memory_size();
-            // Reserve memory for incoming batch
-            acc.3.try_grow(batch_size)?;
-            // Update metrics
-            acc.2.build_mem_used.add(batch_size);
-            acc.2.build_input_batches.add(1);
-            acc.2.build_input_rows.add(batch.num_rows());
-            // Update rowcount
-            acc.1 += batch.num_rows();
-            // Push batch to output
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let mut stream = left_input.execute(left_input_partition, Arc::clone(&context))?;
+    let disk_manager = Arc::clone(&context.runtime_env().disk_manager);
+    let spill_compression = context.session_config().spill_compression();
+
+    // Buffer all batches in memory, or route them to spill files once
+    // the memory limit has been reached
+    let mut batches = Vec::new();
+    let mut num_rows = 0;
+    let mut spill_partitioner: Option<SpillPartitioner> = None;
+    while let Some(batch) = stream.next().await.transpose()? {
+        // Update metrics
+        metrics.build_input_batches.add(1);
+        metrics.build_input_rows.add(batch.num_rows());
+
+        if let Some(partitioner) = spill_partitioner.as_mut() {
+            partitioner.write(&batch)?;
+            continue;
+        }
+
+        let batch_size = batch.get_array_memory_size();
+        num_rows += batch.num_rows();
+        batches.push(batch);
+        // Reserve memory for incoming batch
+        match reservation.try_grow(batch_size) {
+            Ok(()) => metrics.build_mem_used.add(batch_size),
+            Err(e) if !can_spill => return Err(e),
+            Err(_) => {
+                spill_partitioner = Some(spill_build_side(
+                    &mut batches,
+                    on_left,
+                    &schema,
+                    &disk_manager,
+                    spill_compression,
+                    &mut reservation,
+                    &metrics,
+                )?)
+            }
+        }
+    }
+
+    let spill_partitioner = match spill_partitioner {
+        Some(partitioner) => partitioner,
+        None => {
+            // Estimation of memory size, required for hashtable and visited
+            // indices bitmap, prior to allocation.
+            let hash_table_size =
+                estimate_hash_table_size(num_rows, with_visited_indices_bitmap)?;
+            match reservation.try_grow(hash_table_size) {
+                Ok(()) => {
+                    metrics.build_mem_used.add(hash_table_size);
+                    let data = build_join_left_data(
+                        &schema,
+                        batches,
+                        num_rows,
+                        on_left,
+                        &random_state,
+                        reservation,
+                        with_visited_indices_bitmap,
+                        probe_threads_count,
+                    )?;
+                    return Ok(BuildSideInput::InMemory(Arc::new(data)));
+                }
+                Err(e) if !can_spill => return Err(e),
+                Err(_) => spill_build_side(
+                    &mut batches,
+                    on_left,
+                    &schema,
+                    &disk_manager,
+                    spill_compression,
+                    &mut reservation,
+                    &metrics,
+                )?,
+            }
+        }
+    };
 
-    // Estimation of memory size, required for hashtable, prior to allocation.
+    Ok(BuildSideInput::Spilled(SpilledBuildSide {
+        schema,
+        partitions: Mutex::new(spill_partitioner.finish(&metrics)?),
+        disk_manager,
+        spill_compression,
+        reservation,
+    }))
+}
+
+/// Hash-partitions the buffered build side `batches` to disk and releases
+/// their memory. Returns the partitioner for the remaining build side input.
+fn spill_build_side(
+    batches: &mut Vec<RecordBatch>,
+    on_left: &[PhysicalExprRef],
+    schema: &SchemaRef,
+    disk_manager: &Arc<DiskManager>,
+    spill_compression: SpillCompression,
+    reservation: &mut MemoryReservation,
+    metrics: &BuildProbeJoinMetrics,
+) -> Result<SpillPartitioner> {
+    let mut partitioner = SpillPartitioner::new(
+        on_left.to_vec(),
+        Arc::clone(schema),
+        0,
+        Arc::clone(disk_manager),
+        spill_compression,
+    );
+    for batch in batches.drain(..) {
+        partitioner.write(&batch)?;
+    }
+    metrics.build_mem_used.sub(reservation.free());
+    Ok(partitioner)
+}
+
+/// Estimates the memory required for the hash table (and optionally the
+/// visited indices bitmap) of `num_rows` build side rows
+fn estimate_hash_table_size(
+    num_rows: usize,
+    with_visited_indices_bitmap: bool,
+) -> Result<usize> {
     // Final result can be verified using `RawTable.allocation_info()`
     let fixed_size = size_of::<JoinHashMap>();
-    let estimated_hashtable_size =
-        estimate_memory_size::<(u64, u64)>(num_rows, fixed_size)?;
-
-    reservation.try_grow(estimated_hashtable_size)?;
-    metrics.build_mem_used.add(estimated_hashtable_size);
+    let hash_table_size = estimate_memory_size::<(u64, u64)>(num_rows, fixed_size)?;
+    let bitmap_size = if with_visited_indices_bitmap {
+        bit_util::ceil(num_rows, 8)
+    } else {
+        0
+    };
+    Ok(hash_table_size + bitmap_size)
+}
 
+/// Creates the hash table for the build side `batches`, whose memory (including
+/// the hash table itself) is already accounted for in `reservation`
+#[allow(clippy::too_many_arguments)]
+fn build_join_left_data(
+    schema: &SchemaRef,
+    batches: Vec<RecordBatch>,
+    num_rows: usize,
+    on_left: &[PhysicalExprRef],
+    random_state: &RandomState,
+    reservation: MemoryReservation,
+    with_visited_indices_bitmap: bool,
+    probe_threads_count: usize,
+) -> Result<JoinLeftData> {
     let mut hashmap = JoinHashMap::with_capacity(num_rows);
     let mut hashes_buffer = Vec::new();
     let mut offset = 0;
@@ -864,11 +1401,11 @@ async fn collect_left_input(
         hashes_buffer.clear();
         hashes_buffer.resize(batch.num_rows(), 0);
         update_hash(
-            &on_left,
+            on_left,
             batch,
             &mut hashmap,
             offset,
-            &random_state,
+            random_state,
             &mut hashes_buffer,
             0,
             true,
@@ -876,14 +1413,10 @@ async fn collect_left_input(
         offset += batch.num_rows();
     }
     // Merge all batches into a single batch, so we can directly index into the arrays
-    let single_batch = concat_batches(&schema, batches_iter)?;
+    let single_batch = concat_batches(schema, batches_iter)?;
 
-    // Reserve additional memory for visited indices bitmap and create shared builder
+    // Create shared builder for visited indices bitmap, if required
     let visited_indices_bitmap = if with_visited_indices_bitmap {
-        let bitmap_size = bit_util::ceil(single_batch.num_rows(), 8);
-        reservation.try_grow(bitmap_size)?;
-        metrics.build_mem_used.add(bitmap_size);
-
         let mut bitmap_buffer = BooleanBufferBuilder::new(single_batch.num_rows());
         bitmap_buffer.append_n(num_rows, false);
         bitmap_buffer
@@ -891,15 +1424,13 @@ async fn collect_left_input(
         BooleanBufferBuilder::new(0)
     };
 
-    let data = JoinLeftData::new(
+    Ok(JoinLeftData::new(
         hashmap,
         single_batch,
         Mutex::new(visited_indices_bitmap),
         AtomicUsize::new(probe_threads_count),
         reservation,
-    );
-
-    Ok(data)
+    ))
 }
 
 /// Updates `hash_map` with new entries from `batch` evaluated against the expressions `on`
@@ -960,7 +1491,7 @@ enum BuildSide {
 /// Container for BuildSide::Initial related data
 struct BuildSideInitialState {
     /// Future for building hash table from build-side input
-    left_fut: OnceFut<JoinLeftData>,
+    left_fut: OnceFut<BuildSideInput>,
 }
 
 /// Container for BuildSide::Ready related data
@@ -1004,18 +1535,28 @@ impl BuildSide {
 ///
 /// ```text
 ///
-///       WaitBuildSide
-///             │
-///             ▼
-///  ┌─► FetchProbeBatch ───► ExhaustedProbeSide ───► Completed
-///  │          │
-///  │          ▼
-///  └─ ProcessProbeBatch
+///       WaitBuildSide ───────────► PartitionProbeSide
+///             │                            │
+///             ▼                            ▼
+///  ┌─► FetchProbeBatch ◄──────── LoadSpilledPartition ───► Completed
+///  │          │                            ▲
+///  │          ▼                            │
+///  │  ProcessProbeBatch                    │
+///  │          │                            │
+///  └──────────┴─► ExhaustedProbeSide ──────┴─────────────► Completed
 ///
 /// ```
+///
+/// The `PartitionProbeSide` and `LoadSpilledPartition` states are only
+/// used if the build side has been spilled to disk.
 enum HashJoinStreamState {
     /// Initial state for HashJoinStream indicating that build-side data not collected yet
     WaitBuildSide,
+    /// Indicates that build-side has been spilled to disk, and probe-side is being
+    /// hash-partitioned to disk the same way
+    PartitionProbeSide(PartitionProbeSideState),
+    /// Indicates that the next spilled partition is ready to be loaded into memory
+    LoadSpilledPartition,
     /// Indicates that build-side has been collected, and stream is ready for fetching probe-side
     FetchProbeBatch,
     /// Indicates that non-empty batch has been fetched from probe-side, and is ready to be processed
@@ -1035,6 +1576,75 @@ impl HashJoinStreamState {
             _ => internal_err!("Expected hash join stream in ProcessProbeBatch state"),
         }
     }
+
+    /// Tries to extract PartitionProbeSideState from HashJoinStreamState enum.
+    /// Returns an error if state is not PartitionProbeSideState.
+    fn try_as_partition_probe_side_mut(
+        &mut self,
+    ) -> Result<&mut PartitionProbeSideState> {
+        match self {
+            HashJoinStreamState::PartitionProbeSide(state) => Ok(state),
+            _ => internal_err!("Expected hash join stream in PartitionProbeSide state"),
+        }
+    }
+}
+
+/// Container for HashJoinStreamState::PartitionProbeSide related data
+struct PartitionProbeSideState {
+    /// Spill files of the build side partitions
+    build_partitions: Vec<Option<RefCountedTempFile>>,
+    /// Partitioner writing the probe side to disk
+    partitioner: SpillPartitioner,
+}
+
+/// Spill state of a [`HashJoinStream`] whose build side didn't fit in memory
+struct SpillState {
+    /// Schema of the build side
+    build_schema: SchemaRef,
+    /// Disk manager used to create spill files
+    disk_manager: Arc<DiskManager>,
+    /// Compression codec of the spill files
+    spill_compression: SpillCompression,
+    /// Empty reservation, used to account for the memory of partitions loaded
+    /// back from disk
+    reservation: MemoryReservation,
+    /// Spilled partitions which haven't been joined yet
+    pending: Vec<SpilledPartition>,
+}
+
+/// Build and probe side spill files of a single partition, either of which
+/// is `None` if no rows were routed to the partition
+struct SpilledPartition {
+    build: Option<RefCountedTempFile>,
+    probe: Option<RefCountedTempFile>,
+    /// Number of times the data of this partition has been partitioned
+    depth: usize,
+}
+
+impl SpillState {
+    /// Tries to extract SpillState from the spill state of a stream.
+    /// Returns an error if the build side hasn't been spilled.
+    fn try_as_mut(spill_state: &mut Option<SpillState>) -> Result<&mut SpillState> {
+        spill_state
+            .as_mut()
+            .ok_or_else(|| internal_datafusion_err!("Expected spilled hash join stream"))
+    }
+}
+
+impl SpilledPartition {
+    /// Returns false if joining this partition can't produce any output rows
+    /// for `join_type`, so it can be skipped
+    fn has_output(&self, join_type: JoinType) -> bool {
+        match (&self.build, &self.probe) {
+            (Some(_), Some(_)) => true,
+            (Some(_), None) => need_produce_result_in_final(join_type),
+            (None, Some(_)) => matches!(
+                join_type,
+                JoinType::Right | JoinType::Full | JoinType::RightAnti
+            ),
+            (None, None) => false,
+        }
+    }
 }
 
 /// Container for HashJoinStreamState::ProcessProbeBatch related data
@@ -1064,6 +1674,10 @@ impl ProcessProbeBatchState {
 ///
 /// 2. Streams [RecordBatch]es as they arrive from the right input (probe) and joins
 ///    them with the contents of the hash table
+///
+/// If the left input has been spilled to disk, the right input is
+/// hash-partitioned to disk as well, and the resulting pairs of partitions
+/// are joined one at a time.
 struct HashJoinStream {
     /// Input schema
     schema: Arc<Schema>,
@@ -1095,6 +1709,8 @@ struct HashJoinStream {
     hashes_buffer: Vec<u64>,
     /// Specifies whether the right side has an ordering to potentially preserve
     right_side_ordered: bool,
+    /// Spilled partitions, if the build side didn't fit in memory
+    spill_state: Option<SpillState>,
 }
 
 impl RecordBatchStream for HashJoinStream {
@@ -1257,6 +1873,31 @@ pub fn equal_rows_arr(
     ))
 }
 
+/// Reads a spilled build side partition back into memory, reserving memory for
+/// its batches and hash table. Fails with [`DataFusionError::ResourcesExhausted`]
+/// if the partition doesn't fit in memory.
+fn read_spilled_build_side(
+    file: Option<&RefCountedTempFile>,
+    reservation: &mut MemoryReservation,
+    with_visited_indices_bitmap: bool,
+) -> Result<(Vec<RecordBatch>, usize)> {
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    if let Some(file) = file {
+        for batch in spill_file_reader(file)? {
+            let batch = batch?;
+            reservation.try_grow(batch.get_array_memory_size())?;
+            num_rows += batch.num_rows();
+            batches.push(batch);
+        }
+    }
+    reservation.try_grow(estimate_hash_table_size(
+        num_rows,
+        with_visited_indices_bitmap,
+    )?)?;
+    Ok((batches, num_rows))
+}
+
 fn get_final_indices_from_shared_bitmap(
     shared_bitmap: &SharedBitmapBuilder,
     join_type: JoinType,
@@ -1277,6 +1918,12 @@ impl HashJoinStream {
                 HashJoinStreamState::WaitBuildSide => {
                     handle_state!(ready!(self.collect_build_side(cx)))
                 }
+                HashJoinStreamState::PartitionProbeSide(_) => {
+                    handle_state!(ready!(self.partition_probe_side(cx)))
+                }
+                HashJoinStreamState::LoadSpilledPartition => {
+                    handle_state!(self.load_spilled_partition())
+                }
                 HashJoinStreamState::FetchProbeBatch => {
                     handle_state!(ready!(self.fetch_probe_batch(cx)))
                 }
@@ -1300,19 +1947,210 @@ impl HashJoinStream {
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
         let build_timer = self.join_metrics.build_time.timer();
         // build hash table from left (build) side, if not yet done
-        let left_data = ready!(self
+        let left_input = ready!(self
             .build_side
             .try_as_initial_mut()?
             .left_fut
             .get_shared(cx))?;
         build_timer.done();
 
-        self.state = HashJoinStreamState::FetchProbeBatch;
-        self.build_side = BuildSide::Ready(BuildSideReadyState { left_data });
+        match left_input.as_ref() {
+            BuildSideInput::InMemory(left_data) => {
+                self.state = HashJoinStreamState::FetchProbeBatch;
+                self.build_side = BuildSide::Ready(BuildSideReadyState {
+                    left_data: Arc::clone(left_data),
+                });
+            }
+            BuildSideInput::Spilled(spilled) => {
+                let build_partitions = std::mem::take(&mut *spilled.partitions.lock());
+                let partitioner = SpillPartitioner::new(
+                    self.on_right.clone(),
+                    self.right.schema(),
+                    0,
+                    Arc::clone(&spilled.disk_manager),
+                    spilled.spill_compression,
+                );
+                self.spill_state = Some(SpillState {
+                    build_schema: Arc::clone(&spilled.schema),
+                    disk_manager: Arc::clone(&spilled.disk_manager),
+                    spill_compression: spilled.spill_compression,
+                    reservation: spilled.reservation.new_empty(),
+                    pending: vec![],
+                });
+                self.state =
+                    HashJoinStreamState::PartitionProbeSide(PartitionProbeSideState {
+                        build_partitions,
+                        partitioner,
+                    });
+            }
+        }
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
     }
 
+    /// Hash-partitions the probe-side to disk, after the build-side has been spilled
+    ///
+    /// Once the probe-side is exhausted, updates state to `LoadSpilledPartition`
+    fn partition_probe_side(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        match ready!(self.right.poll_next_unpin(cx)) {
+            Some(Ok(batch)) => {
+                self.state
+                    .try_as_partition_probe_side_mut()?
+                    .partitioner
+                    .write(&batch)?;
+            }
+            Some(Err(err)) => return Poll::Ready(Err(err)),
+            None => {
+                let HashJoinStreamState::PartitionProbeSide(state) = std::mem::replace(
+                    &mut self.state,
+                    HashJoinStreamState::LoadSpilledPartition,
+                ) else {
+                    return Poll::Ready(internal_err!(
+                        "Expected hash join stream in PartitionProbeSide state"
+                    ));
+                };
+                let probe_partitions = state.partitioner.finish(&self.join_metrics)?;
+                let spill_state = SpillState::try_as_mut(&mut self.spill_state)?;
+                spill_state.pending.extend(
+                    state
+                        .build_partitions
+                        .into_iter()
+                        .zip(probe_partitions)
+                        .map(|(build, probe)| SpilledPartition {
+                            build,
+                            probe,
+                            depth: 0,
+                        }),
+                );
+            }
+        }
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Loads the build-side of the next spilled partition into memory and creates
+    /// its hash table. Partitions which don't fit in memory are split further.
+    ///
+    /// Updates state to `FetchProbeBatch` for the probe-side of the loaded
+    /// partition, or to `Completed` if there are no partitions left
+    fn load_spilled_partition(
+        &mut self,
+    ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
+        let join_type = self.join_type;
+        let spill_state = SpillState::try_as_mut(&mut self.spill_state)?;
+        let Some(partition) = spill_state.pending.pop() else {
+            self.state = HashJoinStreamState::Completed;
+            return Ok(StatefulStreamResult::Continue);
+        };
+        if !partition.has_output(join_type) {
+            return Ok(StatefulStreamResult::Continue);
+        }
+
+        let build_time = self.join_metrics.build_time.clone();
+        let build_timer = build_time.timer();
+        let with_visited_indices_bitmap = need_produce_result_in_final(join_type);
+        let mut reservation = spill_state.reservation.new_empty();
+        let (batches, num_rows) = match read_spilled_build_side(
+            partition.build.as_ref(),
+            &mut reservation,
+            with_visited_indices_bitmap,
+        ) {
+            Ok(result) => result,
+            Err(DataFusionError::ResourcesExhausted(_))
+                if partition.depth < MAX_SPILL_DEPTH =>
+            {
+                drop(reservation);
+                self.split_spilled_partition(partition)?;
+                build_timer.done();
+                return Ok(StatefulStreamResult::Continue);
+            }
+            Err(e) => return Err(e),
+        };
+        self.join_metrics.build_mem_used.set(reservation.size());
+
+        let left_data = build_join_left_data(
+            &spill_state.build_schema,
+            batches,
+            num_rows,
+            &self.on_left,
+            &self.random_state,
+            reservation,
+            with_visited_indices_bitmap,
+            1,
+        )?;
+        build_timer.done();
+
+        self.build_side = BuildSide::Ready(BuildSideReadyState {
+            left_data: Arc::new(left_data),
+        });
+        self.right = match partition.probe {
+            Some(file) => read_spill_as_stream(file, self.right.schema(), 2)?,
+            None => Box::pin(EmptyRecordBatchStream::new(self.right.schema())),
+        };
+        self.state = HashJoinStreamState::FetchProbeBatch;
+
+        Ok(StatefulStreamResult::Continue)
+    }
+
+    /// Splits a spilled partition which doesn't fit in memory into
+    /// [`SPILL_PARTITION_FANOUT`] smaller partitions
+    fn split_spilled_partition(&mut self, partition: SpilledPartition) -> Result<()> {
+        let depth = partition.depth + 1;
+        let probe_schema = self.right.schema();
+        let spill_state = SpillState::try_as_mut(&mut self.spill_state)?;
+
+        let mut build_partitioner = SpillPartitioner::new(
+            self.on_left.clone(),
+            Arc::clone(&spill_state.build_schema),
+            depth,
+            Arc::clone(&spill_state.disk_manager),
+            spill_state.spill_compression,
+        );
+        if let Some(file) = &partition.build {
+            build_partitioner.write_spill_file(file)?;
+        }
+
+        let mut probe_partitioner = SpillPartitioner::new(
+            self.on_right.clone(),
+            probe_schema,
+            depth,
+            Arc::clone(&spill_state.disk_manager),
+            spill_state.spill_compression,
+        );
+        if let Some(file) = &partition.probe {
+            probe_partitioner.write_spill_file(file)?;
+        }
+
+        let build_partitions = build_partitioner.finish(&self.join_metrics)?;
+        let probe_partitions = probe_partitioner.finish(&self.join_metrics)?;
+        spill_state.pending.extend(
+            build_partitions
+                .into_iter()
+                .zip(probe_partitions)
+                .map(|(build, probe)| SpilledPartition {
+                    build,
+                    probe,
+                    depth,
+                }),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the state after all output for the current build-side has been
+    /// produced: the next spilled partition is loaded, if the build-side has
+    /// been spilled, otherwise the stream is completed
+    fn state_after_build_side_completed(&self) -> HashJoinStreamState {
+        if self.spill_state.is_some() {
+            HashJoinStreamState::LoadSpilledPartition
+        } else {
+            HashJoinStreamState::Completed
+        }
+    }
+
     /// Fetches next batch from probe-side
     ///
     /// If non-empty batch has been fetched, updates state to `ProcessProbeBatchState`,
@@ -1474,13 +2312,13 @@ impl HashJoinStream {
         let timer = self.join_metrics.join_time.timer();
 
         if !need_produce_result_in_final(self.join_type) {
-            self.state = HashJoinStreamState::Completed;
+            self.state = self.state_after_build_side_completed();
             return Ok(StatefulStreamResult::Continue);
         }
 
         let build_side = self.build_side.try_as_ready()?;
         if !build_side.left_data.report_probe_completed() {
-            self.state = HashJoinStreamState::Completed;
+            self.state = self.state_after_build_side_completed();
             return Ok(StatefulStreamResult::Continue);
         }
 
@@ -1510,7 +2348,7 @@ impl HashJoinStream {
         }
         timer.done();
 
-        self.state = HashJoinStreamState::Completed;
+        self.state = self.state_after_build_side_completed();
 
         Ok(StatefulStreamResult::Ready(Some(result?)))
     }
@@ -1530,6 +2368,8 @@ impl Stream for HashJoinStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::work_table::{ReservedBatches, WorkTable, WorkTableExec};
+    use crate::{collect, filter::FilterExec, recursive_query::reset_plan_states};
     use crate::{
         common, expressions::Column, memory::MemoryExec, repartition::RepartitionExec,
         test::build_table_i32, test::exec::MockExec,
@@ -1537,6 +2377,7 @@ mod tests {
 
     use arrow::array::{Date32Array, Int32Array};
     use arrow::datatypes::{DataType, Field};
+    use arrow::util::pretty::pretty_format_batches;
     use arrow_array::StructArray;
     use arrow_buffer::NullBuffer;
     use datafusion_common::{
@@ -1544,7 +2385,8 @@ mod tests {
         ScalarValue,
     };
     use datafusion_execution::config::SessionConfig;
-    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_execution::disk_manager::DiskManagerConfig;
+    use datafusion_execution::runtime_env::{RuntimeEnv, RuntimeEnvBuilder};
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
     use datafusion_physical_expr::PhysicalExpr;
@@ -3941,8 +4783,10 @@ mod tests {
         ];
 
         for join_type in join_types {
+            // Disable DiskManager to prevent spilling
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled)
                 .build_arc()?;
             let session_config = SessionConfig::default().with_batch_size(50);
             let task_ctx = TaskContext::default()
@@ -3975,6 +4819,235 @@ mod tests {
         Ok(())
     }
 
+    /// Builds a single partition input of `num_batches` batches with 200 rows each,
+    /// with join keys in column `b` repeating every `num_keys` rows
+    fn build_spill_table(
+        names: (&str, &str, &str),
+        num_batches: i32,
+        num_keys: i32,
+    ) -> Arc<dyn ExecutionPlan> {
+        let batches = (0..num_batches)
+            .map(|i| {
+                let a = (i * 200..(i + 1) * 200).collect::<Vec<_>>();
+                let b = a.iter().map(|v| v % num_keys).collect::<Vec<_>>();
+                build_table_i32((names.0, &a), (names.1, &b), (names.2, &a))
+            })
+            .collect::<Vec<_>>();
+        let schema = batches[0].schema();
+        Arc::new(MemoryExec::try_new(&[batches], schema, None).unwrap())
+    }
+
+    async fn partitioned_join_with_runtime(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+        runtime: Arc<RuntimeEnv>,
+    ) -> Result<(Vec<RecordBatch>, MetricsSet)> {
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+        let join = HashJoinExec::try_new(
+            Arc::clone(left),
+            Arc::clone(right),
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::Partitioned,
+            false,
+        )?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let batches = common::collect(join.execute(0, task_ctx)?).await?;
+        Ok((batches, join.metrics().unwrap()))
+    }
+
+    #[tokio::test]
+    async fn partitioned_join_spill() -> Result<()> {
+        let left = build_spill_table(("a1", "b1", "c1"), 10, 500);
+        let right = build_spill_table(("a2", "b2", "c2"), 5, 700);
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+            JoinType::LeftMark,
+        ];
+
+        for join_type in join_types {
+            let (expected, metrics) = partitioned_join_with_runtime(
+                &left,
+                &right,
+                join_type,
+                RuntimeEnvBuilder::new().build_arc()?,
+            )
+            .await?;
+            assert_eq!(metrics.spill_count(), Some(0));
+
+            // The build side doesn't fit in memory, but each of its spilled
+            // partitions does
+            let runtime = RuntimeEnvBuilder::new()
+                .with_memory_limit(40_000, 1.0)
+                .with_disk_manager(DiskManagerConfig::NewOs)
+                .build_arc()?;
+            let (spilled, metrics) =
+                partitioned_join_with_runtime(&left, &right, join_type, runtime).await?;
+            assert!(metrics.spill_count().unwrap() > 0);
+            assert!(metrics.spilled_bytes().unwrap() > 0);
+            assert!(metrics.spilled_rows().unwrap() > 0);
+
+            let expected = pretty_format_batches(&expected)?.to_string();
+            let spilled = pretty_format_batches(&spilled)?.to_string();
+            let mut expected_lines = expected.trim().lines().collect::<Vec<_>>();
+            let mut spilled_lines = spilled.trim().lines().collect::<Vec<_>>();
+            expected_lines.sort_unstable();
+            spilled_lines.sort_unstable();
+            assert_eq!(expected_lines, spilled_lines, "join type {join_type}");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partitioned_join_spill_overallocation() -> Result<()> {
+        // All rows share the same join key, so partitioning can't split them
+        let left = build_spill_table(("a1", "b1", "c1"), 10, 1);
+        let right = build_spill_table(("a2", "b2", "c2"), 1, 1);
+
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(40_000, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let err = partitioned_join_with_runtime(&left, &right, JoinType::Inner, runtime)
+            .await
+            .unwrap_err();
+
+        assert_contains!(
+            err.to_string(),
+            "Resources exhausted: Additional allocation failed with top memory consumers (across reservations) as: HashJoinInput[0]"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_publishes_dynamic_filter() -> Result<()> {
+        let left = build_table(
+            ("a1", &vec![1, 2, 3]),
+            ("b1", &vec![4, 5, 7]),
+            ("c1", &vec![7, 8, 9]),
+        );
+        let right_batch = build_table_i32(
+            ("a2", &vec![10, 20, 30]),
+            ("b1", &vec![4, 6, 8]),
+            ("c2", &vec![70, 80, 90]),
+        );
+        let right = Arc::new(MemoryExec::try_new(
+            &[vec![right_batch.clone()]],
+            right_batch.schema(),
+            None,
+        )?) as Arc<dyn ExecutionPlan>;
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        )];
+        let filter = Arc::new(DynamicFilterExpr::new(vec![Arc::clone(&on[0].1)]));
+
+        let right_join = join(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on.clone(),
+            &JoinType::Right,
+            false,
+        )?;
+        assert!(right_join.with_dynamic_filter(Arc::clone(&filter)).is_err());
+
+        let join = join(left, right, on, &JoinType::Inner, false)?
+            .with_dynamic_filter(Arc::clone(&filter))?;
+        assert!(!filter.is_published());
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        let batches = common::collect(stream).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+
+        // b1 is within [4, 7] and one of (4, 5, 7)
+        let result = filter
+            .evaluate(&right_batch)?
+            .into_array(right_batch.num_rows())?;
+        assert_eq!(
+            result.as_ref(),
+            &BooleanArray::from(vec![true, false, false])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reset_join_republishes_dynamic_filter() -> Result<()> {
+        let left_batch = |b1: &Vec<i32>| {
+            build_table_i32(("a1", &vec![1, 2]), ("b1", b1), ("c1", &vec![7, 8]))
+        };
+        let schema = left_batch(&vec![0, 0]).schema();
+        // The build side is read from a work table, which keeps its contents
+        // when the plan is reset, like the recursive term of a CTE
+        let work_table = Arc::new(WorkTable::new());
+        let left = Arc::new(
+            WorkTableExec::new("t".to_string(), Arc::clone(&schema))
+                .with_work_table(Arc::clone(&work_table)),
+        ) as Arc<dyn ExecutionPlan>;
+
+        let right_batch = build_table_i32(
+            ("a2", &vec![10, 20, 30]),
+            ("b1", &vec![4, 6, 8]),
+            ("c2", &vec![70, 80, 90]),
+        );
+        let right_schema = right_batch.schema();
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &schema)?) as _,
+            Arc::new(Column::new_with_schema("b1", &right_schema)?) as _,
+        )];
+        // The probe side scan holds a copy of the filter the join publishes to
+        let filter = Arc::new(DynamicFilterExpr::new(vec![Arc::clone(&on[0].1)]));
+        let right = Arc::new(FilterExec::try_new(
+            Arc::clone(&filter).with_new_children(vec![Arc::clone(&on[0].1)])?,
+            Arc::new(MemoryExec::try_new(
+                &[vec![right_batch]],
+                right_schema,
+                None,
+            )?),
+        )?) as Arc<dyn ExecutionPlan>;
+        let plan = Arc::new(
+            join(left, right, on, &JoinType::Inner, false)?
+                .with_dynamic_filter(filter)?,
+        ) as Arc<dyn ExecutionPlan>;
+
+        let context = Arc::new(TaskContext::default());
+        let run = |plan: Arc<dyn ExecutionPlan>, b1: Vec<i32>| {
+            let reservation = MemoryConsumer::new("test").register(context.memory_pool());
+            work_table.update(ReservedBatches::new(vec![left_batch(&b1)], reservation));
+            let context = Arc::clone(&context);
+            async move {
+                let batches = collect(plan, context).await?;
+                Ok::<_, DataFusionError>(
+                    batches.iter().map(|b| b.num_rows()).sum::<usize>(),
+                )
+            }
+        };
+
+        assert_eq!(run(Arc::clone(&plan), vec![4, 5]).await?, 1);
+        // The filter published by the first execution only lets b1 = 4 pass
+        let plan = reset_plan_states(plan)?;
+        assert_eq!(run(Arc::clone(&plan), vec![6, 8]).await?, 2);
+        let plan = reset_plan_states(plan)?;
+        assert_eq!(run(plan, vec![4, 8]).await?, 2);
+
+        Ok(())
+    }
+
     fn build_table_struct(
         struct_name: &str,
         field_name_and_values: (&str, &Vec<Option<i32>>),
@@ -17,13 +17,18 @@
 
 //! DataFusion Join implementations
 
+pub use asof_join::{AsofJoinExec, AsofMatchCondition};
 pub use cross_join::CrossJoinExec;
+pub use dependent_join::{DependentJoinExec, OuterParameter, OuterParameters};
 pub use hash_join::HashJoinExec;
 pub use nested_loop_join::NestedLoopJoinExec;
 // Note: SortMergeJoin is not used in plans yet
 pub use sort_merge_join::SortMergeJoinExec;
 pub use symmetric_hash_join::SymmetricHashJoinExec;
+mod asof_join;
+mod block_nested_loop;
 mod cross_join;
+mod dependent_join;
 mod hash_join;
 mod nested_loop_join;
 mod sort_merge_join;
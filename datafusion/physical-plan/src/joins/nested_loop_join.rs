@@ -15,7 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Defines the nested loop join plan, it supports all [`JoinType`].
+//! Defines the nested loop join plan, it supports all [`JoinType`] except
+//! [`JoinType::AsOf`], see [`AsofJoinExec`](super::AsofJoinExec).
 //! The nested loop join can execute in parallel by partitions and it is
 //! determined by the [`JoinType`].
 
@@ -25,6 +26,10 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::task::Poll;
 
+use super::block_nested_loop::{
+    collect_build_side, BuildChunkLoader, BuildSide, JoinSpillConfig, SpilledBuildSide,
+    SpilledProbeSide,
+};
 use super::utils::{
     asymmetric_join_output_partitioning, need_produce_result_in_final, BatchSplitter,
     BatchTransformer, NoopBatchTransformer, StatefulStreamResult,
@@ -49,41 +54,45 @@ use arrow::datatypes::{Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use arrow::util::bit_util;
 use datafusion_common::{
-    exec_datafusion_err, internal_err, JoinSide, Result, Statistics,
+    exec_datafusion_err, internal_err, not_impl_err, JoinSide, Result, Statistics,
 };
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use datafusion_execution::TaskContext;
 use datafusion_expr::JoinType;
 use datafusion_physical_expr::equivalence::join_equivalence_properties;
 
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
 use parking_lot::Mutex;
 
 /// Shared bitmap for visited left-side indices
 type SharedBitmapBuilder = Mutex<BooleanBufferBuilder>;
 /// Left (build-side) data
 struct JoinLeftData {
-    /// Build-side data collected to single batch
+    /// Build-side data collected to single batch, empty if the build side has
+    /// been spilled to disk
     batch: RecordBatch,
+    /// Build-side data spilled to disk in chunks, if it did not fit in memory
+    spilled: Option<SpilledBuildSide>,
     /// Shared bitmap builder for visited left indices
     bitmap: SharedBitmapBuilder,
     /// Counter of running probe-threads, potentially able to update `bitmap`
     probe_threads_counter: AtomicUsize,
     /// Memory reservation for tracking batch and bitmap
     /// Cleared on `JoinLeftData` drop
-    #[allow(dead_code)]
     reservation: MemoryReservation,
 }
 
 impl JoinLeftData {
     fn new(
         batch: RecordBatch,
+        spilled: Option<SpilledBuildSide>,
         bitmap: SharedBitmapBuilder,
         probe_threads_counter: AtomicUsize,
         reservation: MemoryReservation,
     ) -> Self {
         Self {
             batch,
+            spilled,
             bitmap,
             probe_threads_counter,
             reservation,
@@ -141,6 +150,15 @@ impl JoinLeftData {
 /// "reports" about probe phase completion (which means that "visited" bitmap won't be
 /// updated anymore), and only the last thread, reporting about completion, will return output.
 ///
+/// #### Spilling
+/// If the build side does not fit in memory and the disk manager allows temporary files, the
+/// build side is spilled to disk in chunks, each small enough to be held in memory by every
+/// probe thread (block nested loop join). Each thread then joins its probe side with one chunk
+/// at a time, spilling the probe side to disk while joining it with the first chunk, and
+/// re-scanning it from disk for the next ones. Unmatched probe-side rows are produced after the
+/// last chunk, from a per-thread bitmap of visited probe-side rows. This mode does not preserve
+/// the order of the probe side, so it is not used when the order is required.
+///
 /// Note that the `Clone` trait is not implemented for this struct due to the
 /// `left_fut` [`OnceAsync`], which is used to coordinate the loading of the
 /// left side with the processing in each output stream.
@@ -176,6 +194,11 @@ impl NestedLoopJoinExec {
     ) -> Result<Self> {
         let left_schema = left.schema();
         let right_schema = right.schema();
+        if join_type == &JoinType::AsOf {
+            return not_impl_err!(
+                "NestedLoopJoinExec does not support JoinType::AsOf, use AsofJoinExec"
+            );
+        }
         check_join_is_valid(&left_schema, &right_schema, &[])?;
         let (schema, column_indices) =
             build_join_schema(&left_schema, &right_schema, join_type);
@@ -339,9 +362,25 @@ impl ExecutionPlan for NestedLoopJoinExec {
     ) -> Result<SendableRecordBatchStream> {
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
 
+        // Right side has an order and it is maintained during operation.
+        let right_side_ordered =
+            self.maintains_input_order()[1] && self.right.output_ordering().is_some();
+
+        // Joining the right side with chunks of a spilled left side does not
+        // maintain the order of the right side
+        let disk_manager = Arc::clone(&context.runtime_env().disk_manager);
+        let spill =
+            (disk_manager.tmp_files_enabled() && !right_side_ordered).then(|| {
+                JoinSpillConfig {
+                    disk_manager,
+                    compression: context.session_config().spill_compression(),
+                }
+            });
+
         // Initialization reservation for load of inner table
         let load_reservation =
             MemoryConsumer::new(format!("NestedLoopJoinLoad[{partition}]"))
+                .with_can_spill(spill.is_some())
                 .register(context.memory_pool());
 
         let inner_table = self.inner_table.once(|| {
@@ -352,6 +391,7 @@ impl ExecutionPlan for NestedLoopJoinExec {
                 load_reservation,
                 need_produce_result_in_final(self.join_type),
                 self.right().output_partitioning().partition_count(),
+                spill.clone(),
             )
         });
 
@@ -363,10 +403,6 @@ impl ExecutionPlan for NestedLoopJoinExec {
 
         let indices_cache = (UInt64Array::new_null(0), UInt32Array::new_null(0));
 
-        // Right side has an order and it is maintained during operation.
-        let right_side_ordered =
-            self.maintains_input_order()[1] && self.right.output_ordering().is_some();
-
         if enforce_batch_size_in_joins {
             Ok(Box::pin(NestedLoopJoinStream {
                 schema: Arc::clone(&self.schema),
@@ -381,6 +417,8 @@ impl ExecutionPlan for NestedLoopJoinExec {
                 state: NestedLoopJoinStreamState::WaitBuildSide,
                 batch_transformer: BatchSplitter::new(batch_size),
                 left_data: None,
+                chunked: None,
+                spill,
             }))
         } else {
             Ok(Box::pin(NestedLoopJoinStream {
@@ -396,6 +434,8 @@ impl ExecutionPlan for NestedLoopJoinExec {
                 state: NestedLoopJoinStreamState::WaitBuildSide,
                 batch_transformer: NoopBatchTransformer::new(),
                 left_data: None,
+                chunked: None,
+                spill,
             }))
         }
     }
@@ -415,14 +455,18 @@ impl ExecutionPlan for NestedLoopJoinExec {
     }
 }
 
-/// Asynchronously collect input into a single batch, and creates `JoinLeftData` from it
+/// Asynchronously collect input into a single batch, and creates `JoinLeftData` from it.
+///
+/// If the input does not fit in memory and `spill` is set, it is spilled to
+/// disk in chunks instead, see [`collect_build_side`].
 async fn collect_left_input(
     input: Arc<dyn ExecutionPlan>,
     context: Arc<TaskContext>,
     join_metrics: BuildProbeJoinMetrics,
-    reservation: MemoryReservation,
+    mut reservation: MemoryReservation,
     with_visited_left_side: bool,
     probe_threads_count: usize,
+    spill: Option<JoinSpillConfig>,
 ) -> Result<JoinLeftData> {
     let schema = input.schema();
     let merge = if input.output_partitioning().partition_count() != 1 {
@@ -433,36 +477,33 @@ async fn collect_left_input(
     let stream = merge.execute(0, context)?;
 
     // Load all batches and count the rows
-    let (batches, metrics, mut reservation) = stream
-        .try_fold(
-            (Vec::new(), join_metrics, reservation),
-            |mut acc, batch| async {
-                let batch_size = batch.get_array_memory_size();
-                // Reserve memory for incoming batch
-                acc.2.try_grow(batch_size)?;
-                // Update metrics
-                acc.1.build_mem_used.add(batch_size);
-                acc.1.build_input_batches.add(1);
-                acc.1.build_input_rows.add(batch.num_rows());
-                // Push batch to output
-                acc.0.push(batch);
-                Ok(acc)
-            },
-        )
-        .await?;
+    let build_side = collect_build_side(
+        stream,
+        &join_metrics,
+        &mut reservation,
+        spill,
+        probe_threads_count,
+    )
+    .await?;
 
-    let merged_batch = concat_batches(&schema, &batches)?;
+    let (merged_batch, spilled) = match build_side {
+        BuildSide::InMemory(batches) => (concat_batches(&schema, &batches)?, None),
+        BuildSide::Spilled(spilled) => (RecordBatch::new_empty(schema), Some(spilled)),
+    };
+    let num_rows = spilled
+        .as_ref()
+        .map_or(merged_batch.num_rows(), |spilled| spilled.num_rows());
 
     // Reserve memory for visited_left_side bitmap if required by join type
     let visited_left_side = if with_visited_left_side {
         // TODO: Replace `ceil` wrapper with stable `div_cell` after
         // https://github.com/rust-lang/rust/issues/88581
-        let buffer_size = bit_util::ceil(merged_batch.num_rows(), 8);
+        let buffer_size = bit_util::ceil(num_rows, 8);
         reservation.try_grow(buffer_size)?;
-        metrics.build_mem_used.add(buffer_size);
+        join_metrics.build_mem_used.add(buffer_size);
 
-        let mut buffer = BooleanBufferBuilder::new(merged_batch.num_rows());
-        buffer.append_n(merged_batch.num_rows(), false);
+        let mut buffer = BooleanBufferBuilder::new(num_rows);
+        buffer.append_n(num_rows, false);
         buffer
     } else {
         BooleanBufferBuilder::new(0)
@@ -470,6 +511,7 @@ async fn collect_left_input(
 
     Ok(JoinLeftData::new(
         merged_batch,
+        spilled,
         Mutex::new(visited_left_side),
         AtomicUsize::new(probe_threads_count),
         reservation,
@@ -487,8 +529,14 @@ enum NestedLoopJoinStreamState {
     /// Indicates that a non-empty batch has been fetched from probe-side, and
     /// is ready to be processed
     ProcessProbeBatch(RecordBatch),
+    /// Indicates that the probe side has been joined with all the chunks of a
+    /// spilled build side, and its unmatched rows are ready to be produced
+    ProcessUnmatchedProbeBatch,
     /// Indicates that probe-side has been fully processed
     ExhaustedProbeSide,
+    /// Indicates that the unmatched rows of the chunk of a spilled build side
+    /// with the given index are ready to be produced
+    ProcessUnmatchedBuildChunk(usize),
     /// Indicates that NestedLoopJoinStream execution is completed
     Completed,
 }
@@ -533,6 +581,54 @@ struct NestedLoopJoinStream<T> {
     batch_transformer: T,
     /// Result of the left data future
     left_data: Option<Arc<JoinLeftData>>,
+    /// State of the join with the chunks of a spilled left side
+    chunked: Option<ChunkedJoinState>,
+    /// Where and how to spill the right side, if the left side is spilled
+    spill: Option<JoinSpillConfig>,
+}
+
+/// State of a [`NestedLoopJoinStream`] joining its probe side with the chunks
+/// of a spilled build side, one at a time
+struct ChunkedJoinState {
+    /// The loaded chunk of the build side
+    chunk: RecordBatch,
+    /// The chunk being read back from disk, replacing `chunk` once it is read
+    loading: Option<BuildChunkLoader>,
+    /// Index of the loaded chunk
+    chunk_idx: usize,
+    /// Index of the first row of the loaded chunk in the build side
+    chunk_offset: usize,
+    /// Memory reservation for the loaded chunk
+    reservation: MemoryReservation,
+    /// The probe side, spilled while joined with the first chunk
+    probe: SpilledProbeSide,
+    /// Index of the first row of the current probe batch in the probe side
+    probe_offset: usize,
+    /// Number of rows of the probe side read since the last rewind
+    probe_rows: usize,
+    /// Bitmap of the probe-side rows matched with any chunk, for the join
+    /// types producing unmatched probe-side rows
+    visited_right_side: Option<BooleanBufferBuilder>,
+}
+
+impl ChunkedJoinState {
+    /// Returns the loaded chunk with the index of its first row in the build
+    /// side, and the bitmap of visited probe-side rows, if any, with the index
+    /// of the first row of the current probe batch in the probe side
+    fn join_input(
+        &mut self,
+    ) -> (
+        &RecordBatch,
+        usize,
+        Option<(&mut BooleanBufferBuilder, usize)>,
+    ) {
+        let probe_offset = self.probe_offset;
+        let visited_right_side = self
+            .visited_right_side
+            .as_mut()
+            .map(|visited_right_side| (visited_right_side, probe_offset));
+        (&self.chunk, self.chunk_offset, visited_right_side)
+    }
 }
 
 /// Creates a Cartesian product of two input batches, preserving the order of the right batch,
@@ -627,9 +723,17 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
                 NestedLoopJoinStreamState::ProcessProbeBatch(_) => {
                     handle_state!(self.process_probe_batch())
                 }
+                NestedLoopJoinStreamState::ProcessUnmatchedProbeBatch => {
+                    handle_state!(ready!(self.process_unmatched_probe_batch(cx)))
+                }
                 NestedLoopJoinStreamState::ExhaustedProbeSide => {
                     handle_state!(self.process_unmatched_build_batch())
                 }
+                NestedLoopJoinStreamState::ProcessUnmatchedBuildChunk(chunk_idx) => {
+                    handle_state!(ready!(
+                        self.process_unmatched_build_chunk(cx, chunk_idx)
+                    ))
+                }
                 NestedLoopJoinStreamState::Completed => Poll::Ready(None),
             };
         }
@@ -641,9 +745,44 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
         let build_timer = self.join_metrics.build_time.timer();
         // build hash table from left (build) side, if not yet done
-        self.left_data = Some(ready!(self.inner_table.get_shared(cx))?);
+        let left_data = ready!(self.inner_table.get_shared(cx))?;
         build_timer.done();
 
+        if let Some(spilled) = &left_data.spilled {
+            let Some(spill) = &self.spill else {
+                return Poll::Ready(internal_err!(
+                    "Expected spill config for a spilled build side"
+                ));
+            };
+            let mut reservation = left_data.reservation.new_empty();
+            let loading = spilled.load_chunk(0, &mut reservation)?;
+            let probe = SpilledProbeSide::try_new(
+                &self.outer_table.schema(),
+                spill,
+                self.join_metrics.clone(),
+            )?;
+            let visited_right_side = matches!(
+                self.join_type,
+                JoinType::Right
+                    | JoinType::Full
+                    | JoinType::RightSemi
+                    | JoinType::RightAnti
+            )
+            .then(|| BooleanBufferBuilder::new(0));
+            self.chunked = Some(ChunkedJoinState {
+                chunk: left_data.batch().clone(),
+                loading: Some(loading),
+                chunk_idx: 0,
+                chunk_offset: 0,
+                reservation,
+                probe,
+                probe_offset: 0,
+                probe_rows: 0,
+                visited_right_side,
+            });
+        }
+        self.left_data = Some(left_data);
+
         self.state = NestedLoopJoinStreamState::FetchProbeBatch;
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
@@ -657,6 +796,10 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        if self.chunked.is_some() {
+            return self.fetch_chunked_probe_batch(cx);
+        }
+
         match ready!(self.outer_table.poll_next_unpin(cx)) {
             None => {
                 self.state = NestedLoopJoinStreamState::ExhaustedProbeSide;
@@ -670,6 +813,97 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
         Poll::Ready(Ok(StatefulStreamResult::Continue))
     }
 
+    /// Fetches next batch from probe-side, when joining it with the chunks of
+    /// a spilled build side
+    ///
+    /// The chunk is first read back from disk if it is not loaded yet. The
+    /// probe side is read from its input and spilled while joined with the
+    /// first chunk, and re-scanned from disk for the next chunks. Once it is
+    /// exhausted, moves on to the next chunk, see [`Self::next_build_chunk`].
+    fn fetch_chunked_probe_batch(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let Some(chunked) = self.chunked.as_mut() else {
+            return Poll::Ready(internal_err!(
+                "Expected chunked join state in FetchProbeBatch state"
+            ));
+        };
+        if let Some(loading) = &mut chunked.loading {
+            chunked.chunk = ready!(loading.poll_load(cx, &mut chunked.reservation))?;
+            chunked.loading = None;
+        }
+
+        let right_batch = if chunked.chunk_idx == 0 {
+            match ready!(self.outer_table.poll_next_unpin(cx)) {
+                Some(Ok(right_batch)) => {
+                    chunked.probe.write(&right_batch)?;
+                    if let Some(visited_right_side) = &mut chunked.visited_right_side {
+                        visited_right_side.append_n(right_batch.num_rows(), false);
+                    }
+                    Some(right_batch)
+                }
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => None,
+            }
+        } else {
+            ready!(chunked.probe.poll_next_batch(cx)).transpose()?
+        };
+
+        match right_batch {
+            Some(right_batch) => {
+                chunked.probe_offset = chunked.probe_rows;
+                chunked.probe_rows += right_batch.num_rows();
+                self.state = NestedLoopJoinStreamState::ProcessProbeBatch(right_batch);
+            }
+            None => self.next_build_chunk()?,
+        }
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Loads the next chunk of a spilled build side once the probe side has
+    /// been joined with the current one, and rewinds the spilled probe side.
+    ///
+    /// After the last chunk, updates state to `ProcessUnmatchedProbeBatch` if
+    /// the join type produces unmatched probe-side rows, and to
+    /// `ExhaustedProbeSide` otherwise.
+    fn next_build_chunk(&mut self) -> Result<()> {
+        let Some(left_data) = self.left_data.clone() else {
+            return internal_err!(
+                "Expected left_data to be Some in FetchProbeBatch state"
+            );
+        };
+        let (Some(spilled), Some(chunked)) = (&left_data.spilled, self.chunked.as_mut())
+        else {
+            return internal_err!(
+                "Expected a spilled build side in FetchProbeBatch state"
+            );
+        };
+
+        chunked.probe.rewind()?;
+        chunked.probe_rows = 0;
+        chunked.chunk_idx += 1;
+        // The cached indices are only valid for the row count of the chunk
+        self.indices_cache = (UInt64Array::new_null(0), UInt32Array::new_null(0));
+
+        if chunked.chunk_idx < spilled.num_chunks() {
+            chunked.loading =
+                Some(spilled.load_chunk(chunked.chunk_idx, &mut chunked.reservation)?);
+            chunked.chunk_offset = spilled.chunk_offset(chunked.chunk_idx);
+            self.state = NestedLoopJoinStreamState::FetchProbeBatch;
+        } else {
+            chunked.chunk = left_data.batch().clone();
+            chunked.reservation.free();
+            self.state = if chunked.visited_right_side.is_some() {
+                NestedLoopJoinStreamState::ProcessUnmatchedProbeBatch
+            } else {
+                NestedLoopJoinStreamState::ExhaustedProbeSide
+            };
+        }
+        Ok(())
+    }
+
     /// Joins current probe batch with build-side data and produces batch with
     /// matched output, updates state to `FetchProbeBatch`.
     fn process_probe_batch(
@@ -685,19 +919,30 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
 
         match self.batch_transformer.next() {
             None => {
-                // Setting up timer & updating input metrics
-                self.join_metrics.input_batches.add(1);
-                self.join_metrics.input_rows.add(batch.num_rows());
+                // Setting up timer & updating input metrics, the probe side
+                // being re-scanned for each chunk of a spilled build side
+                if !matches!(&self.chunked, Some(chunked) if chunked.chunk_idx > 0) {
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(batch.num_rows());
+                }
                 let timer = self.join_metrics.join_time.timer();
 
+                let (left_batch, left_offset, visited_right_side) =
+                    match self.chunked.as_mut() {
+                        Some(chunked) => chunked.join_input(),
+                        None => (left_data.batch(), 0, None),
+                    };
+
                 let result = join_left_and_right_batch(
-                    left_data.batch(),
+                    left_batch,
+                    left_offset,
                     batch,
                     self.join_type,
                     self.filter.as_ref(),
                     &self.column_indices,
                     &self.schema,
                     visited_left_side,
+                    visited_right_side,
                     &mut self.indices_cache,
                     self.right_side_ordered,
                 );
@@ -740,6 +985,11 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
                 return Ok(StatefulStreamResult::Ready(None));
             };
 
+            if left_data.spilled.is_some() {
+                self.state = NestedLoopJoinStreamState::ProcessUnmatchedBuildChunk(0);
+                return Ok(StatefulStreamResult::Continue);
+            }
+
             // Only setting up timer, input is exhausted
             let timer = self.join_metrics.join_time.timer();
             // use the global left bitmap to produce the left indices and right indices
@@ -770,17 +1020,143 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
             Ok(StatefulStreamResult::Ready(None))
         }
     }
+
+    /// Produces the unmatched rows of the next batch of the spilled probe side,
+    /// once it has been joined with all the chunks of a spilled build side,
+    /// updates state to `ExhaustedProbeSide` once the probe side is exhausted.
+    fn process_unmatched_probe_batch(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let Some(left_data) = self.left_data.clone() else {
+            return Poll::Ready(internal_err!(
+                "Expected left_data to be Some in ProcessUnmatchedProbeBatch state"
+            ));
+        };
+        let Some(chunked) = self.chunked.as_mut() else {
+            return Poll::Ready(internal_err!(
+                "Expected chunked join state in ProcessUnmatchedProbeBatch state"
+            ));
+        };
+        let Some(right_batch) = ready!(chunked.probe.poll_next_batch(cx)).transpose()?
+        else {
+            self.state = NestedLoopJoinStreamState::ExhaustedProbeSide;
+            return Poll::Ready(Ok(StatefulStreamResult::Continue));
+        };
+        let Some(visited_right_side) = &chunked.visited_right_side else {
+            return Poll::Ready(internal_err!(
+                "Expected visited right side bitmap in ProcessUnmatchedProbeBatch state"
+            ));
+        };
+
+        let timer = self.join_metrics.join_time.timer();
+        let probe_offset = chunked.probe_rows;
+        chunked.probe_rows += right_batch.num_rows();
+
+        // `RightSemi` produces the matched rows, other join types the unmatched ones
+        let matched = self.join_type == JoinType::RightSemi;
+        let right_side = UInt32Array::from_iter_values(
+            (0..right_batch.num_rows())
+                .filter(|idx| visited_right_side.get_bit(probe_offset + idx) == matched)
+                .map(|idx| idx as u32),
+        );
+        let left_side = UInt64Array::new_null(right_side.len());
+        let result = build_batch_from_indices(
+            &self.schema,
+            left_data.batch(),
+            &right_batch,
+            &left_side,
+            &right_side,
+            &self.column_indices,
+            JoinSide::Left,
+        )?;
+        timer.done();
+
+        self.join_metrics.output_batches.add(1);
+        self.join_metrics.output_rows.add(result.num_rows());
+        Poll::Ready(Ok(StatefulStreamResult::Ready(Some(result))))
+    }
+
+    /// Produces the unmatched rows of the chunk of a spilled build side with
+    /// index `chunk_idx`, once it is read back from disk, updates state to
+    /// `Completed` after the last chunk.
+    fn process_unmatched_build_chunk(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+        chunk_idx: usize,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let Some(left_data) = self.left_data.clone() else {
+            return Poll::Ready(internal_err!(
+                "Expected left_data to be Some in ProcessUnmatchedBuildChunk state"
+            ));
+        };
+        let (Some(spilled), Some(chunked)) = (&left_data.spilled, self.chunked.as_mut())
+        else {
+            return Poll::Ready(internal_err!(
+                "Expected a spilled build side in ProcessUnmatchedBuildChunk state"
+            ));
+        };
+        if chunk_idx == spilled.num_chunks() {
+            chunked.reservation.free();
+            self.state = NestedLoopJoinStreamState::Completed;
+            return Poll::Ready(Ok(StatefulStreamResult::Ready(None)));
+        }
+
+        let loading = match &mut chunked.loading {
+            Some(loading) => loading,
+            loading @ None => {
+                loading.insert(spilled.load_chunk(chunk_idx, &mut chunked.reservation)?)
+            }
+        };
+        let chunk = ready!(loading.poll_load(cx, &mut chunked.reservation))?;
+        chunked.loading = None;
+
+        // Only setting up timer, input is exhausted
+        let timer = self.join_metrics.join_time.timer();
+        let chunk_offset = spilled.chunk_offset(chunk_idx);
+        // the part of the global left bitmap covering the chunk
+        let mut visited_chunk = BooleanBufferBuilder::new(chunk.num_rows());
+        visited_chunk.append_packed_range(
+            chunk_offset..chunk_offset + chunk.num_rows(),
+            left_data.bitmap().lock().as_slice(),
+        );
+        let (left_side, right_side) =
+            get_final_indices_from_bit_map(&visited_chunk, self.join_type);
+        let empty_right_batch = RecordBatch::new_empty(self.outer_table.schema());
+        let result = build_batch_from_indices(
+            &self.schema,
+            &chunk,
+            &empty_right_batch,
+            &left_side,
+            &right_side,
+            &self.column_indices,
+            JoinSide::Left,
+        )?;
+        timer.done();
+
+        self.state = NestedLoopJoinStreamState::ProcessUnmatchedBuildChunk(chunk_idx + 1);
+        Poll::Ready(Ok(StatefulStreamResult::Ready(Some(result))))
+    }
 }
 
+/// Joins `left_batch`, starting at row `left_offset` of the build side, with
+/// `right_batch`.
+///
+/// If set, `visited_right_side` is updated with the matched rows of
+/// `right_batch`, starting at the given row of the probe side, and the
+/// unmatched rows of `right_batch` are not produced: they are only known once
+/// it has been joined with all the chunks of a spilled build side.
 #[allow(clippy::too_many_arguments)]
 fn join_left_and_right_batch(
     left_batch: &RecordBatch,
+    left_offset: usize,
     right_batch: &RecordBatch,
     join_type: JoinType,
     filter: Option<&JoinFilter>,
     column_indices: &[ColumnIndex],
     schema: &Schema,
     visited_left_side: &SharedBitmapBuilder,
+    visited_right_side: Option<(&mut BooleanBufferBuilder, usize)>,
     indices_cache: &mut (UInt64Array, UInt32Array),
     right_side_ordered: bool,
 ) -> Result<RecordBatch> {
@@ -798,9 +1174,23 @@ fn join_left_and_right_batch(
     if need_produce_result_in_final(join_type) {
         let mut bitmap = visited_left_side.lock();
         left_side.values().iter().for_each(|x| {
-            bitmap.set_bit(*x as usize, true);
+            bitmap.set_bit(left_offset + *x as usize, true);
         });
     }
+    let join_type = match visited_right_side {
+        Some((visited_right_side, right_offset)) => {
+            right_side.values().iter().for_each(|x| {
+                visited_right_side.set_bit(right_offset + *x as usize, true);
+            });
+            // only produce the matched rows, if any
+            match join_type {
+                JoinType::Right | JoinType::Full => JoinType::Inner,
+                JoinType::RightSemi | JoinType::RightAnti => JoinType::LeftSemi,
+                join_type => join_type,
+            }
+        }
+        None => join_type,
+    };
     // adjust the two side indices base on the join type
     let (left_side, right_side) = adjust_indices_by_join_type(
         left_side,
@@ -849,6 +1239,9 @@ impl<T: BatchTransformer + Unpin + Send> RecordBatchStream for NestedLoopJoinStr
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
+    use crate::joins::test_utils::{
+        collect_partitioned_sorted_lines, collect_sorted_lines, spilling_join_inputs,
+    };
     use crate::{
         common, expressions::Column, memory::MemoryExec, repartition::RepartitionExec,
         test::build_table_i32,
@@ -858,12 +1251,14 @@ pub(crate) mod tests {
     use arrow_array::Int32Array;
     use arrow_schema::SortOptions;
     use datafusion_common::{assert_batches_sorted_eq, assert_contains, ScalarValue};
+    use datafusion_execution::disk_manager::DiskManagerConfig;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
     use datafusion_physical_expr::{Partitioning, PhysicalExpr};
     use datafusion_physical_expr_common::sort_expr::{LexOrdering, PhysicalSortExpr};
 
+    use futures::TryStreamExt;
     use rstest::rstest;
 
     fn build_table(
@@ -1312,6 +1707,7 @@ pub(crate) mod tests {
         for join_type in join_types {
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager(DiskManagerConfig::Disabled)
                 .build_arc()?;
             let task_ctx = TaskContext::default().with_runtime(runtime);
             let task_ctx = Arc::new(task_ctx);
@@ -1335,6 +1731,81 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_spill_build_side() -> Result<()> {
+        let (left, right, left_batch_size) = spilling_join_inputs()?;
+        let filter = prepare_mod_join_filter();
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::LeftMark,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+        ];
+
+        for join_type in join_types {
+            let join = Arc::new(NestedLoopJoinExec::try_new(
+                Arc::clone(&left),
+                Arc::clone(&right),
+                Some(filter.clone()),
+                &join_type,
+            )?) as Arc<dyn ExecutionPlan>;
+            let expected =
+                collect_sorted_lines(&join, Arc::new(TaskContext::default())).await?;
+
+            // the left side only fits in memory in chunks
+            let runtime = RuntimeEnvBuilder::new()
+                .with_memory_limit(6 * left_batch_size + left_batch_size / 2, 1.0)
+                .with_disk_manager(DiskManagerConfig::NewOs)
+                .build_arc()?;
+            let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+            let join =
+                join.with_new_children(vec![Arc::clone(&left), Arc::clone(&right)])?;
+            let actual = collect_sorted_lines(&join, task_ctx).await?;
+
+            assert_eq!(actual, expected, "{join_type}");
+            assert!(join.metrics().unwrap().spill_count().unwrap() > 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_spill_build_side_concurrent_probe_partitions() -> Result<()> {
+        let (left, right, left_batch_size) = spilling_join_inputs()?;
+        let join = Arc::new(NestedLoopJoinExec::try_new(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            Some(prepare_mod_join_filter()),
+            &JoinType::Full,
+        )?) as Arc<dyn ExecutionPlan>;
+        let expected =
+            collect_sorted_lines(&join, Arc::new(TaskContext::default())).await?;
+
+        // the left side only fits in memory in chunks, which the partitions of
+        // the right side read back from disk at the same time
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(6 * left_batch_size + left_batch_size / 2, 1.0)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let join = join.with_new_children(vec![left, right])?;
+        let actual = collect_partitioned_sorted_lines(&join, task_ctx).await?;
+
+        assert_eq!(actual, expected);
+        // at least two chunks of the left side, and the right side of each of
+        // the 4 partitions
+        let spill_count = join.metrics().unwrap().spill_count().unwrap();
+        assert!(spill_count >= 2 + 4, "spill count {spill_count}");
+
+        Ok(())
+    }
+
     fn prepare_mod_join_filter() -> JoinFilter {
         let column_indices = vec![
             ColumnIndex {
@@ -42,6 +42,7 @@ use arrow::datatypes::{DataType, SchemaRef, TimeUnit};
 use arrow::error::ArrowError;
 use arrow::ipc::reader::FileReader;
 use arrow_array::types::UInt64Type;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{
     exec_err, internal_err, not_impl_err, plan_err, DataFusionError, HashSet, JoinSide,
     JoinType, Result,
@@ -115,9 +116,9 @@ impl SortMergeJoinExec {
         let left_schema = left.schema();
         let right_schema = right.schema();
 
-        if join_type == JoinType::RightSemi {
+        if matches!(join_type, JoinType::RightSemi | JoinType::AsOf) {
             return not_impl_err!(
-                "SortMergeJoinExec does not support JoinType::RightSemi"
+                "SortMergeJoinExec does not support JoinType::{join_type}"
             );
         }
 
@@ -180,7 +181,8 @@ impl SortMergeJoinExec {
             | JoinType::Full
             | JoinType::LeftAnti
             | JoinType::LeftSemi
-            | JoinType::LeftMark => JoinSide::Left,
+            | JoinType::LeftMark
+            | JoinType::AsOf => JoinSide::Left,
         }
     }
 
@@ -389,6 +391,7 @@ impl ExecutionPlan for SortMergeJoinExec {
             SortMergeJoinMetrics::new(partition, &self.metrics),
             reservation,
             context.runtime_env(),
+            context.session_config().spill_compression(),
         )?))
     }
 
@@ -430,6 +433,8 @@ struct SortMergeJoinMetrics {
     spill_count: Count,
     /// total spilled bytes during the execution of the operator
     spilled_bytes: Count,
+    /// total size of the spill files written by the operator
+    spill_file_bytes: Count,
     /// total spilled rows during the execution of the operator
     spilled_rows: Count,
 }
@@ -447,6 +452,7 @@ impl SortMergeJoinMetrics {
         let peak_mem_used = MetricBuilder::new(metrics).gauge("peak_mem_used", partition);
         let spill_count = MetricBuilder::new(metrics).spill_count(partition);
         let spilled_bytes = MetricBuilder::new(metrics).spilled_bytes(partition);
+        let spill_file_bytes = MetricBuilder::new(metrics).spill_file_bytes(partition);
         let spilled_rows = MetricBuilder::new(metrics).spilled_rows(partition);
 
         Self {
@@ -458,6 +464,7 @@ impl SortMergeJoinMetrics {
             peak_mem_used,
             spill_count,
             spilled_bytes,
+            spill_file_bytes,
             spilled_rows,
         }
     }
@@ -708,6 +715,8 @@ struct SMJStream {
     pub reservation: MemoryReservation,
     /// Runtime env
     pub runtime_env: Arc<RuntimeEnv>,
+    /// Compression codec of the spill files
+    pub spill_compression: SpillCompression,
     /// A unique number for each batch
     pub streamed_batch_counter: AtomicUsize,
 }
@@ -1028,6 +1037,7 @@ impl SMJStream {
         join_metrics: SortMergeJoinMetrics,
         reservation: MemoryReservation,
         runtime_env: Arc<RuntimeEnv>,
+        spill_compression: SpillCompression,
     ) -> Result<Self> {
         let streamed_schema = streamed.schema();
         let buffered_schema = buffered.schema();
@@ -1062,6 +1072,7 @@ impl SMJStream {
             join_metrics,
             reservation,
             runtime_env,
+            spill_compression,
             streamed_batch_counter: AtomicUsize::new(0),
         })
     }
@@ -1132,7 +1143,7 @@ impl SMJStream {
             }
             Err(_) if self.runtime_env.disk_manager.tmp_files_enabled() => {
                 // spill buffered batch to disk
-                let spill_file = self
+                let mut spill_file = self
                     .runtime_env
                     .disk_manager
                     .create_tmp_file("sort_merge_join_buffered_spill")?;
@@ -1140,9 +1151,13 @@ impl SMJStream {
                 if let Some(batch) = buffered_batch.batch {
                     spill_record_batches(
                         vec![batch],
-                        spill_file.path().into(),
+                        &mut spill_file,
                         Arc::clone(&self.buffered_schema),
+                        self.spill_compression,
                     )?;
+                    self.join_metrics
+                        .spill_file_bytes
+                        .add(spill_file.current_disk_usage() as usize);
                     buffered_batch.spill_file = Some(spill_file);
                     buffered_batch.batch = None;
 
@@ -64,7 +64,9 @@ use arrow::record_batch::RecordBatch;
 use arrow_buffer::ArrowNativeType;
 use datafusion_common::hash_utils::create_hashes;
 use datafusion_common::utils::bisect;
-use datafusion_common::{internal_err, plan_err, HashSet, JoinSide, JoinType, Result};
+use datafusion_common::{
+    internal_err, not_impl_err, plan_err, HashSet, JoinSide, JoinType, Result,
+};
 use datafusion_execution::memory_pool::MemoryConsumer;
 use datafusion_execution::TaskContext;
 use datafusion_expr::interval_arithmetic::Interval;
@@ -218,6 +220,12 @@ impl SymmetricHashJoinExec {
         let left_schema = left.schema();
         let right_schema = right.schema();
 
+        if join_type == &JoinType::AsOf {
+            return not_impl_err!(
+                "SymmetricHashJoinExec does not support JoinType::AsOf, use AsofJoinExec"
+            );
+        }
+
         // Error out if no "on" constraints are given:
         if on.is_empty() {
             return plan_err!(
@@ -25,7 +25,10 @@ use crate::joins::{
 };
 use crate::memory::MemoryExec;
 use crate::repartition::RepartitionExec;
-use crate::{common, ExecutionPlan, ExecutionPlanProperties, Partitioning};
+use crate::test::build_table_i32;
+use crate::{
+    collect_partitioned, common, ExecutionPlan, ExecutionPlanProperties, Partitioning,
+};
 
 use arrow::util::pretty::pretty_format_batches;
 use arrow_array::{
@@ -583,3 +586,71 @@ pub(crate) fn complicated_filter(
     )?;
     binary(left_expr, Operator::And, right_expr, filter_schema)
 }
+
+/// Collects all the partitions of `plan`, returning the sorted lines of the
+/// pretty-printed batches
+pub async fn collect_sorted_lines(
+    plan: &Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+) -> Result<Vec<String>> {
+    let mut batches = vec![];
+    for i in 0..plan.output_partitioning().partition_count() {
+        let stream = plan.execute(i, Arc::clone(&context))?;
+        batches.extend(common::collect(stream).await?);
+    }
+    sorted_lines(&batches)
+}
+
+/// Collects all the partitions of `plan` concurrently, each on its own task,
+/// returning the sorted lines of the pretty-printed batches
+pub async fn collect_partitioned_sorted_lines(
+    plan: &Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+) -> Result<Vec<String>> {
+    let batches = collect_partitioned(Arc::clone(plan), context).await?;
+    sorted_lines(&batches.concat())
+}
+
+fn sorted_lines(batches: &[RecordBatch]) -> Result<Vec<String>> {
+    let mut lines = pretty_format_batches(batches)?
+        .to_string()
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    lines.sort();
+    Ok(lines)
+}
+
+/// Returns the inputs of the tests spilling the left side of a join: ten
+/// 2-row batches on the left, each allocated separately so that it accounts
+/// for its own memory, and four 3-row partitions on the right, along with
+/// the memory size of a left batch
+pub fn spilling_join_inputs(
+) -> Result<(Arc<dyn ExecutionPlan>, Arc<dyn ExecutionPlan>, usize)> {
+    let left_batches = (0..10)
+        .map(|i| {
+            let values = vec![2 * i, 2 * i + 1];
+            build_table_i32(("a1", &values), ("b1", &values), ("c1", &values))
+        })
+        .collect::<Vec<_>>();
+    let left_batch_size = left_batches[0].get_array_memory_size();
+    let left_schema = left_batches[0].schema();
+    let left: Arc<dyn ExecutionPlan> =
+        Arc::new(MemoryExec::try_new(&[left_batches], left_schema, None)?);
+
+    let right_partitions = (0..4)
+        .map(|i| {
+            let values = (3 * i..3 * i + 3).collect::<Vec<_>>();
+            vec![build_table_i32(
+                ("a2", &values),
+                ("b2", &values),
+                ("c2", &values),
+            )]
+        })
+        .collect::<Vec<_>>();
+    let right_schema = right_partitions[0][0].schema();
+    let right: Arc<dyn ExecutionPlan> =
+        Arc::new(MemoryExec::try_new(&right_partitions, right_schema, None)?);
+
+    Ok((left, right, left_batch_size))
+}
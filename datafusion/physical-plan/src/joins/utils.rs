@@ -623,6 +623,7 @@ fn output_join_field(old_field: &Field, join_type: &JoinType, is_left: bool) ->
         JoinType::LeftAnti => false, // doesn't introduce nulls (or can it??)
         JoinType::RightAnti => false, // doesn't introduce nulls (or can it??)
         JoinType::LeftMark => false,
+        JoinType::AsOf => !is_left, // right input is padded with nulls
     };
 
     if force_nullable {
@@ -673,7 +674,11 @@ pub fn build_join_schema(
     };
 
     let (fields, column_indices): (SchemaBuilder, Vec<ColumnIndex>) = match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::Full | JoinType::Right => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::Full
+        | JoinType::Right
+        | JoinType::AsOf => {
             // left then right
             left_fields().chain(right_fields()).unzip()
         }
@@ -790,6 +795,29 @@ pub(crate) fn estimate_join_statistics(
     })
 }
 
+/// Estimate the statistics for the output of an inner equijoin of inputs
+/// with the given statistics, where `on` refers to columns of the respective
+/// inputs.
+///
+/// This is the same estimate [`HashJoinExec`] reports from its statistics,
+/// and returns `None` if the inputs don't carry enough information (row
+/// counts and min/max values of the join keys) to make it.
+///
+/// [`HashJoinExec`]: crate::joins::HashJoinExec
+pub fn estimate_inner_join_statistics(
+    left_stats: Statistics,
+    right_stats: Statistics,
+    on: &JoinOn,
+) -> Option<Statistics> {
+    estimate_join_cardinality(&JoinType::Inner, left_stats, right_stats, on).map(
+        |stats| Statistics {
+            num_rows: Precision::Inexact(stats.num_rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: stats.column_statistics,
+        },
+    )
+}
+
 // Estimate the cardinality for the given join with input statistics.
 fn estimate_join_cardinality(
     join_type: &JoinType,
@@ -902,6 +930,16 @@ fn estimate_join_cardinality(
                 column_statistics,
             })
         }
+
+        // Each left row is joined with at most one right row
+        JoinType::AsOf => Some(PartialJoinStatistics {
+            num_rows: *left_stats.num_rows.get_value()?,
+            column_statistics: left_stats
+                .column_statistics
+                .into_iter()
+                .chain(right_stats.column_statistics)
+                .collect(),
+        }),
     }
 }
 
@@ -918,6 +956,12 @@ fn estimate_inner_join_cardinality(
         return Some(estimation);
     };
 
+    if let Some(estimation) =
+        estimate_distribution_join_cardinality(&left_stats, &right_stats)
+    {
+        return Some(estimation);
+    }
+
     // The algorithm here is partly based on the non-histogram selectivity estimation
     // from Spark's Catalyst optimizer.
     let mut join_selectivity = Precision::Absent;
@@ -966,6 +1010,31 @@ fn estimate_inner_join_cardinality(
     }
 }
 
+/// Estimate the inner join cardinality from the histograms and most common
+/// values of the join columns. When several join columns have them, the most
+/// selective one is used. Returns None if no join column has them on both sides.
+fn estimate_distribution_join_cardinality(
+    left_stats: &Statistics,
+    right_stats: &Statistics,
+) -> Option<Precision<usize>> {
+    let left_num_rows = *left_stats.num_rows.get_value()?;
+    let right_num_rows = *right_stats.num_rows.get_value()?;
+    left_stats
+        .column_statistics
+        .iter()
+        .zip(right_stats.column_statistics.iter())
+        .filter_map(|(left_stat, right_stat)| {
+            let selectivity = left_stat.estimate_join_selectivity(right_stat)?;
+            let left_non_null = left_num_rows
+                .saturating_sub(*left_stat.null_count.get_value().unwrap_or(&0));
+            let right_non_null = right_num_rows
+                .saturating_sub(*right_stat.null_count.get_value().unwrap_or(&0));
+            Some(selectivity * left_non_null as f64 * right_non_null as f64)
+        })
+        .reduce(f64::min)
+        .map(|cardinality| Precision::Inexact(cardinality.round() as usize))
+}
+
 /// Estimates if inputs are non-overlapping, using input statistics.
 /// If inputs are disjoint, returns zero estimation, otherwise returns None
 fn estimate_disjoint_inputs(
@@ -1337,7 +1406,7 @@ pub(crate) fn adjust_indices_by_join_type(
             // the left_indices will not be used later for the `right anti` join
             Ok((left_indices, right_indices))
         }
-        JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
+        JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark | JoinType::AsOf => {
             // matched or unmatched left row will be produced in the end of loop
             // When visit the right batch, we can output the matched left row and don't need to wait the end of loop
             Ok((
@@ -1563,6 +1632,14 @@ pub(crate) struct BuildProbeJoinMetrics {
     pub(crate) output_batches: metrics::Count,
     /// Number of rows produced by this operator
     pub(crate) output_rows: metrics::Count,
+    /// Number of spill files written during execution of this operator
+    pub(crate) spill_count: metrics::Count,
+    /// Total bytes spilled to disk during execution of this operator
+    pub(crate) spilled_bytes: metrics::Count,
+    /// Total size of the spill files written by this operator
+    pub(crate) spill_file_bytes: metrics::Count,
+    /// Total rows spilled to disk during execution of this operator
+    pub(crate) spilled_rows: metrics::Count,
 }
 
 impl BuildProbeJoinMetrics {
@@ -1590,6 +1667,14 @@ impl BuildProbeJoinMetrics {
 
         let output_rows = MetricBuilder::new(metrics).output_rows(partition);
 
+        let spill_count = MetricBuilder::new(metrics).spill_count(partition);
+
+        let spilled_bytes = MetricBuilder::new(metrics).spilled_bytes(partition);
+
+        let spill_file_bytes = MetricBuilder::new(metrics).spill_file_bytes(partition);
+
+        let spilled_rows = MetricBuilder::new(metrics).spilled_rows(partition);
+
         Self {
             build_time,
             build_input_batches,
@@ -1600,6 +1685,10 @@ impl BuildProbeJoinMetrics {
             input_rows,
             output_batches,
             output_rows,
+            spill_count,
+            spilled_bytes,
+            spill_file_bytes,
+            spilled_rows,
         }
     }
 }
@@ -1660,9 +1749,11 @@ pub(crate) fn symmetric_join_output_partitioning(
     let left_partitioning = left.output_partitioning();
     let right_partitioning = right.output_partitioning();
     match join_type {
-        JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
-            left_partitioning.clone()
-        }
+        JoinType::Left
+        | JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::LeftMark
+        | JoinType::AsOf => left_partitioning.clone(),
         JoinType::RightSemi | JoinType::RightAnti => right_partitioning.clone(),
         JoinType::Inner | JoinType::Right => {
             adjust_right_output_partitioning(right_partitioning, left_columns_len)
@@ -1689,7 +1780,8 @@ pub(crate) fn asymmetric_join_output_partitioning(
         | JoinType::LeftSemi
         | JoinType::LeftAnti
         | JoinType::Full
-        | JoinType::LeftMark => Partitioning::UnknownPartitioning(
+        | JoinType::LeftMark
+        | JoinType::AsOf => Partitioning::UnknownPartitioning(
             right.output_partitioning().partition_count(),
         ),
     }
@@ -1989,6 +2081,8 @@ mod tests {
             min_value: min.map(ScalarValue::from),
             max_value: max.map(ScalarValue::from),
             null_count,
+            histogram: None,
+            most_common_values: None,
         }
     }
 
@@ -70,6 +70,7 @@ pub mod placeholder_row;
 pub mod projection;
 pub mod recursive_query;
 pub mod repartition;
+pub mod sample;
 pub mod sorts;
 pub mod spill;
 pub mod stream;
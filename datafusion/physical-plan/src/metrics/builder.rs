@@ -123,6 +123,15 @@ impl<'a> MetricBuilder<'a> {
         count
     }
 
+    /// Consume self and create a new counter for recording the total size of
+    /// the spill files written by an operator, after compression
+    pub fn spill_file_bytes(self, partition: usize) -> Count {
+        let count = Count::new();
+        self.with_partition(partition)
+            .build(MetricValue::SpillFileBytes(count.clone()));
+        count
+    }
+
     /// Consume self and create a new counter for recording the total spilled rows
     /// triggered by an operator
     pub fn spilled_rows(self, partition: usize) -> Count {
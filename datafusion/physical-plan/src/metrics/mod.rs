@@ -209,6 +209,13 @@ impl MetricsSet {
             .map(|v| v.as_usize())
     }
 
+    /// Convenience: return the total size of the spill files written to disk,
+    /// aggregated across partitions or `None` if no metric is present
+    pub fn spill_file_bytes(&self) -> Option<usize> {
+        self.sum(|metric| matches!(metric.value(), MetricValue::SpillFileBytes(_)))
+            .map(|v| v.as_usize())
+    }
+
     /// Convenience: return the total rows of spills, aggregated
     /// across partitions or `None` if no metric is present
     pub fn spilled_rows(&self) -> Option<usize> {
@@ -258,6 +265,7 @@ impl MetricsSet {
             MetricValue::ElapsedCompute(_) => false,
             MetricValue::SpillCount(_) => false,
             MetricValue::SpilledBytes(_) => false,
+            MetricValue::SpillFileBytes(_) => false,
             MetricValue::SpilledRows(_) => false,
             MetricValue::CurrentMemoryUsage(_) => false,
             MetricValue::Gauge { name, .. } => name == metric_name,
@@ -238,6 +238,16 @@ impl ExecutionPlan for ProjectionExec {
     fn cardinality_effect(&self) -> CardinalityEffect {
         CardinalityEffect::Equal
     }
+
+    fn with_block_sample(
+        &self,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Option<Arc<dyn ExecutionPlan>> {
+        // Projected rows stay in the blocks they were read from
+        let input = self.input.with_block_sample(fraction, seed)?;
+        Arc::new(self.clone()).with_new_children(vec![input]).ok()
+    }
 }
 
 /// If 'e' is a direct column reference, returns the field level
@@ -388,18 +398,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::Float32(Some(1.1))),
                     min_value: Precision::Exact(ScalarValue::Float32(Some(0.1))),
                     null_count: Precision::Absent,
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         }
@@ -432,12 +448,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(5),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -466,12 +486,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Float32(Some(1.1))),
                     min_value: Precision::Exact(ScalarValue::Float32(Some(0.1))),
                     null_count: Precision::Absent,
+                    histogram: None,
+                    most_common_values: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(5),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: None,
+                    most_common_values: None,
                 },
             ],
         };
@@ -0,0 +1,311 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the SAMPLE plan, which returns a random sample of the rows of its
+//! input
+
+use std::any::Any;
+use std::sync::Arc;
+
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::{DisplayAs, PlanProperties, Statistics};
+use crate::execution_plan::CardinalityEffect;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{DisplayFormatType, ExecutionPlan, SendableRecordBatchStream};
+
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::stats::Precision;
+use datafusion_common::{internal_err, plan_err, Result};
+use datafusion_execution::TaskContext;
+use datafusion_expr::SampleMethod;
+use futures::StreamExt;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Sample execution plan, returning every row (for
+/// [`SampleMethod::Bernoulli`]) or every batch (for [`SampleMethod::System`])
+/// of its input with probability `fraction`
+#[derive(Debug, Clone)]
+pub struct SampleExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// How the rows are chosen
+    method: SampleMethod,
+    /// The probability of a row or batch to be returned
+    fraction: f64,
+    /// Seed of the random number generator, if the same rows must be returned
+    /// every time
+    seed: Option<u64>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+impl SampleExec {
+    /// Create a new SampleExec, returning an error if `fraction` isn't
+    /// between 0 and 1
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!("Sample fraction must be between 0 and 1, got {fraction}");
+        }
+        // Sampling keeps the order and partitioning of the input
+        let cache = input.properties().clone();
+        Ok(Self {
+            input,
+            method,
+            fraction,
+            seed,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// How the rows are chosen
+    pub fn method(&self) -> SampleMethod {
+        self.method
+    }
+
+    /// The probability of a row or batch to be returned
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Seed of the random number generator
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl DisplayAs for SampleExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "SampleExec: method={}, fraction={}",
+                    self.method, self.fraction
+                )?;
+                if let Some(seed) = self.seed {
+                    write!(f, ", seed={seed}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SampleExec {
+    fn name(&self) -> &'static str {
+        "SampleExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [input] => Ok(Arc::new(SampleExec::try_new(
+                Arc::clone(input),
+                self.method,
+                self.fraction,
+                self.seed,
+            )?)),
+            _ => internal_err!("SampleExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, context)?;
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let mut rng = match self.seed {
+            // Every partition gets its own sequence of random numbers
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(partition as u64)),
+            None => StdRng::from_entropy(),
+        };
+        let method = self.method;
+        let fraction = self.fraction;
+        let stream = input
+            .map(move |batch| {
+                let _timer = baseline_metrics.elapsed_compute().timer();
+                let batch = sample_batch(batch?, method, fraction, &mut rng)?;
+                baseline_metrics.record_output(batch.num_rows());
+                Ok(batch)
+            })
+            .filter(|batch| {
+                futures::future::ready(
+                    batch.as_ref().map_or(true, |batch| batch.num_rows() > 0),
+                )
+            });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(sample_statistics(self.input.statistics()?, self.fraction))
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::LowerEqual
+    }
+}
+
+/// Returns the rows of `batch` chosen by `method`
+fn sample_batch(
+    batch: RecordBatch,
+    method: SampleMethod,
+    fraction: f64,
+    rng: &mut StdRng,
+) -> Result<RecordBatch> {
+    match method {
+        SampleMethod::Bernoulli => {
+            let mask = (0..batch.num_rows())
+                .map(|_| Some(rng.gen_bool(fraction)))
+                .collect::<BooleanArray>();
+            Ok(filter_record_batch(&batch, &mask)?)
+        }
+        SampleMethod::System if rng.gen_bool(fraction) => Ok(batch),
+        SampleMethod::System => Ok(batch.slice(0, 0)),
+    }
+}
+
+/// Returns the expected statistics of a sample of `fraction` of the rows
+/// described by `statistics`
+pub fn sample_statistics(statistics: Statistics, fraction: f64) -> Statistics {
+    let scale = |value: Precision<usize>| {
+        value.map(|value| (value as f64 * fraction).round() as usize)
+    };
+    let mut statistics = statistics.to_inexact();
+    statistics.num_rows = scale(statistics.num_rows);
+    statistics.total_byte_size = scale(statistics.total_byte_size);
+    for column in statistics.column_statistics.iter_mut() {
+        column.null_count = scale(column.null_count);
+        // A sample may not contain every distinct value
+        column.distinct_count = Precision::Absent;
+    }
+    statistics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::collect;
+    use crate::memory::MemoryExec;
+    use crate::test;
+
+    async fn sample_rows(
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Vec<RecordBatch>> {
+        let batches = (0..10)
+            .map(|_| test::make_partition(100))
+            .collect::<Vec<_>>();
+        let schema = batches[0].schema();
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema, None)?);
+        let sample = SampleExec::try_new(input, method, fraction, seed)?;
+        collect(sample.execute(0, Arc::new(TaskContext::default()))?).await
+    }
+
+    fn num_rows(batches: &[RecordBatch]) -> usize {
+        batches.iter().map(|batch| batch.num_rows()).sum()
+    }
+
+    #[tokio::test]
+    async fn bernoulli_sample() -> Result<()> {
+        let batches = sample_rows(SampleMethod::Bernoulli, 0.5, Some(42)).await?;
+        let rows = num_rows(&batches);
+        assert!(rows > 300 && rows < 700, "unexpected sample size {rows}");
+
+        // The same rows are returned with the same seed
+        let again = sample_rows(SampleMethod::Bernoulli, 0.5, Some(42)).await?;
+        assert_eq!(batches, again);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn system_sample_returns_whole_batches() -> Result<()> {
+        let batches = sample_rows(SampleMethod::System, 0.5, Some(7)).await?;
+        assert!(batches.iter().all(|batch| batch.num_rows() == 100));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sample_all_or_nothing() -> Result<()> {
+        for method in [SampleMethod::Bernoulli, SampleMethod::System] {
+            let all = sample_rows(method, 1.0, None).await?;
+            assert_eq!(num_rows(&all), 1000);
+            let nothing = sample_rows(method, 0.0, None).await?;
+            assert_eq!(num_rows(&nothing), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_fraction() -> Result<()> {
+        let input = Arc::new(MemoryExec::try_new(&[], test::aggr_test_schema(), None)?);
+        let err =
+            SampleExec::try_new(input, SampleMethod::Bernoulli, 1.5, None).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Sample fraction must be between 0 and 1, got 1.5"
+        );
+        Ok(())
+    }
+}
@@ -48,6 +48,7 @@ use arrow::record_batch::RecordBatch;
 use arrow::row::{RowConverter, SortField};
 use arrow_array::{Array, RecordBatchOptions, UInt32Array};
 use arrow_schema::DataType;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{internal_err, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
@@ -72,6 +73,9 @@ struct ExternalSorterMetrics {
     /// total spilled bytes during the execution of the operator
     spilled_bytes: Count,
 
+    /// total size of the spill files written by the operator
+    spill_file_bytes: Count,
+
     /// total spilled rows during the execution of the operator
     spilled_rows: Count,
 }
@@ -82,6 +86,7 @@ impl ExternalSorterMetrics {
             baseline: BaselineMetrics::new(metrics, partition),
             spill_count: MetricBuilder::new(metrics).spill_count(partition),
             spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spill_file_bytes: MetricBuilder::new(metrics).spill_file_bytes(partition),
             spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
         }
     }
@@ -238,6 +243,8 @@ struct ExternalSorter {
     /// the data will be concatenated and sorted in place rather than
     /// sort/merged.
     sort_in_place_threshold_bytes: usize,
+    /// Compression codec of the spill files
+    spill_compression: SpillCompression,
 }
 
 impl ExternalSorter {
@@ -252,6 +259,7 @@ impl ExternalSorter {
         fetch: Option<usize>,
         sort_spill_reservation_bytes: usize,
         sort_in_place_threshold_bytes: usize,
+        spill_compression: SpillCompression,
         metrics: &ExecutionPlanMetricsSet,
         runtime: Arc<RuntimeEnv>,
     ) -> Self {
@@ -278,6 +286,7 @@ impl ExternalSorter {
             batch_size,
             sort_spill_reservation_bytes,
             sort_in_place_threshold_bytes,
+            spill_compression,
         }
     }
 
@@ -396,16 +405,20 @@ impl ExternalSorter {
 
         self.in_mem_sort().await?;
 
-        let spill_file = self.runtime.disk_manager.create_tmp_file("Sorting")?;
+        let mut spill_file = self.runtime.disk_manager.create_tmp_file("Sorting")?;
         let batches = std::mem::take(&mut self.in_mem_batches);
         let spilled_rows = spill_record_batches(
             batches,
-            spill_file.path().into(),
+            &mut spill_file,
             Arc::clone(&self.schema),
+            self.spill_compression,
         )?;
         let used = self.reservation.free();
         self.metrics.spill_count.add(1);
         self.metrics.spilled_bytes.add(used);
+        self.metrics
+            .spill_file_bytes
+            .add(spill_file.current_disk_usage() as usize);
         self.metrics.spilled_rows.add(spilled_rows);
         self.spills.push(spill_file);
         Ok(used)
@@ -942,6 +955,7 @@ impl ExecutionPlan for SortExec {
                     self.fetch,
                     execution_options.sort_spill_reservation_bytes,
                     execution_options.sort_in_place_threshold_bytes,
+                    execution_options.spill_compression,
                     &self.metrics_set,
                     context.runtime_env(),
                 );
@@ -17,18 +17,22 @@
 
 //! Defines the spilling functions
 
+use std::borrow::Borrow;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::ptr::NonNull;
 
 use arrow::array::ArrayData;
 use arrow::datatypes::SchemaRef;
 use arrow::ipc::reader::FileReader;
 use arrow::record_batch::RecordBatch;
+use arrow_ipc::writer::IpcWriteOptions;
+use arrow_ipc::CompressionType;
 use log::debug;
 use tokio::sync::mpsc::Sender;
 
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{exec_datafusion_err, HashSet, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::human_readable_size;
@@ -39,39 +43,71 @@ use crate::stream::RecordBatchReceiverStream;
 
 /// Read spilled batches from the disk
 ///
-/// `path` - temp file
+/// `path` - temp file, or a shared temp file (e.g. `Arc<RefCountedTempFile>`)
+/// to read it several times
 /// `schema` - batches schema, should be the same across batches
 /// `buffer` - internal buffer of capacity batches
 pub(crate) fn read_spill_as_stream(
-    path: RefCountedTempFile,
+    path: impl Borrow<RefCountedTempFile> + Send + 'static,
     schema: SchemaRef,
     buffer: usize,
 ) -> Result<SendableRecordBatchStream> {
     let mut builder = RecordBatchReceiverStream::builder(schema, buffer);
     let sender = builder.tx();
 
-    builder.spawn_blocking(move || read_spill(sender, path.path()));
+    builder.spawn_blocking(move || read_spill(sender, path.borrow().path()));
 
     Ok(builder.build())
 }
 
-/// Spills in-memory `batches` to disk.
+/// Opens the spill `file` for reading its batches synchronously, e.g. to read
+/// them back while polling a stream
+pub(crate) fn spill_file_reader(
+    file: &RefCountedTempFile,
+) -> Result<FileReader<BufReader<File>>> {
+    let file = BufReader::new(File::open(file.path())?);
+    Ok(FileReader::try_new(file, None)?)
+}
+
+/// Creates a writer for the spill `file`, compressing its batches with
+/// `compression`
+pub(crate) fn spill_writer(
+    file: &RefCountedTempFile,
+    schema: &SchemaRef,
+    compression: SpillCompression,
+) -> Result<IPCWriter> {
+    let compression = match compression {
+        SpillCompression::Lz4Frame => Some(CompressionType::LZ4_FRAME),
+        SpillCompression::Zstd => Some(CompressionType::ZSTD),
+        SpillCompression::Uncompressed => None,
+    };
+    let write_options = IpcWriteOptions::default().try_with_compression(compression)?;
+    IPCWriter::new_with_options(file.path(), schema.as_ref(), write_options)
+}
+
+/// Spills in-memory `batches` to disk, compressed with `compression`.
 ///
-/// Returns total number of the rows spilled to disk.
+/// Returns total number of the rows spilled to disk. The size of the spill
+/// file is accounted in the disk usage of its `DiskManager`, see
+/// [`RefCountedTempFile::update_disk_usage`].
 pub(crate) fn spill_record_batches(
     batches: Vec<RecordBatch>,
-    path: PathBuf,
+    file: &mut RefCountedTempFile,
     schema: SchemaRef,
+    compression: SpillCompression,
 ) -> Result<usize> {
-    let mut writer = IPCWriter::new(path.as_ref(), schema.as_ref())?;
+    let mut writer = spill_writer(file, &schema, compression)?;
     for batch in batches {
         writer.write(&batch)?;
+        file.update_disk_usage()?;
     }
     writer.finish()?;
+    file.update_disk_usage()?;
     debug!(
-        "Spilled {} batches of total {} rows to disk, memory released {}",
+        "Spilled {} batches of total {} rows to disk ({} with {compression}), memory released {}",
         writer.num_batches,
         writer.num_rows,
+        human_readable_size(file.current_disk_usage() as usize),
         human_readable_size(writer.num_bytes),
     );
     Ok(writer.num_rows)
@@ -89,24 +125,27 @@ fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
 }
 
 /// Spill the `RecordBatch` to disk as smaller batches
-/// split by `batch_size_rows`
+/// split by `batch_size_rows`, compressed with `compression`
 pub fn spill_record_batch_by_size(
     batch: &RecordBatch,
-    path: PathBuf,
+    file: &mut RefCountedTempFile,
     schema: SchemaRef,
     batch_size_rows: usize,
+    compression: SpillCompression,
 ) -> Result<()> {
     let mut offset = 0;
     let total_rows = batch.num_rows();
-    let mut writer = IPCWriter::new(&path, schema.as_ref())?;
+    let mut writer = spill_writer(file, &schema, compression)?;
 
     while offset < total_rows {
         let length = std::cmp::min(total_rows - offset, batch_size_rows);
         let batch = batch.slice(offset, length);
         offset += batch.num_rows();
         writer.write(&batch)?;
+        file.update_disk_usage()?;
     }
     writer.finish()?;
+    file.update_disk_usage()?;
 
     Ok(())
 }
@@ -188,7 +227,7 @@ mod tests {
     use arrow::datatypes::{DataType, Field, Int32Type, Schema};
     use arrow::record_batch::RecordBatch;
     use arrow_array::ListArray;
-    use datafusion_common::Result;
+    use datafusion_common::{DataFusionError, Result};
     use datafusion_execution::disk_manager::DiskManagerConfig;
     use datafusion_execution::DiskManager;
     use std::fs::File;
@@ -211,13 +250,14 @@ mod tests {
 
         let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
 
-        let spill_file = disk_manager.create_tmp_file("Test Spill")?;
+        let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
         let schema = batch1.schema();
         let num_rows = batch1.num_rows() + batch2.num_rows();
         let cnt = spill_record_batches(
             vec![batch1, batch2],
-            spill_file.path().into(),
+            &mut spill_file,
             Arc::clone(&schema),
+            SpillCompression::Uncompressed,
         );
         assert_eq!(cnt.unwrap(), num_rows);
 
@@ -240,13 +280,14 @@ mod tests {
 
         let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
 
-        let spill_file = disk_manager.create_tmp_file("Test Spill")?;
+        let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
         let schema = batch1.schema();
         spill_record_batch_by_size(
             &batch1,
-            spill_file.path().into(),
+            &mut spill_file,
             Arc::clone(&schema),
             1,
+            SpillCompression::Uncompressed,
         )?;
 
         let file = BufReader::new(File::open(spill_file.path())?);
@@ -258,6 +299,71 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_compressed_spill_and_read() -> Result<()> {
+        // repetitive data, so that compression shrinks the spill files
+        let batch = build_table_i32(
+            ("a2", &vec![1; 1000]),
+            ("b2", &vec![2; 1000]),
+            ("c2", &vec![3; 1000]),
+        );
+        let schema = batch.schema();
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
+
+        let mut uncompressed = disk_manager.create_tmp_file("Test Spill")?;
+        spill_record_batches(
+            vec![batch.clone()],
+            &mut uncompressed,
+            Arc::clone(&schema),
+            SpillCompression::Uncompressed,
+        )?;
+
+        for compression in [SpillCompression::Lz4Frame, SpillCompression::Zstd] {
+            let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
+            let num_rows = spill_record_batches(
+                vec![batch.clone()],
+                &mut spill_file,
+                Arc::clone(&schema),
+                compression,
+            )?;
+            assert_eq!(num_rows, 1000);
+            assert!(spill_file.current_disk_usage() < uncompressed.current_disk_usage());
+
+            let file = BufReader::new(File::open(spill_file.path())?);
+            let batches =
+                FileReader::try_new(file, None)?.collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(batches, vec![batch.clone()]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spill_disk_quota() -> Result<()> {
+        let batch = build_table_i32(
+            ("a2", &vec![1; 1000]),
+            ("b2", &vec![2; 1000]),
+            ("c2", &vec![3; 1000]),
+        );
+        let disk_manager =
+            DiskManager::try_new(DiskManagerConfig::NewOs)?.with_disk_quota(1024);
+
+        let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
+        let err = spill_record_batches(
+            vec![batch.clone()],
+            &mut spill_file,
+            batch.schema(),
+            SpillCompression::Uncompressed,
+        )
+        .unwrap_err();
+        assert!(matches!(err, DataFusionError::ResourcesExhausted(_)));
+
+        drop(spill_file);
+        assert_eq!(disk_manager.used_disk_space(), 0);
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_record_batch_memory_size() {
         // Create a simple record batch with two columns
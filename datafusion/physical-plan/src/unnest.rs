@@ -402,10 +402,14 @@ pub struct ListUnnest {
 ///
 /// The returned array will has the same size as the input batch
 /// and only contains original columns that are not being unnested.
+///
+/// If [`UnnestOptions::ordinality`] is set, the element positions produced by the last
+/// level (depth = 1) are written to `temp_ordinality_arrs`.
 fn list_unnest_at_level(
     batch: &[ArrayRef],
     list_type_unnests: &[ListUnnest],
     temp_unnested_arrs: &mut HashMap<ListUnnest, ArrayRef>,
+    temp_ordinality_arrs: &mut HashMap<ListUnnest, ArrayRef>,
     level_to_unnest: usize,
     options: &UnnestOptions,
 ) -> Result<(Vec<ArrayRef>, usize)> {
@@ -451,6 +455,21 @@ fn list_unnest_at_level(
     let unnested_temp_arrays =
         unnest_list_arrays(arrs_to_unnest.as_ref(), unnested_length, total_length)?;
 
+    // Positions only matter for the innermost level, where the output elements come from
+    if options.ordinality && level_to_unnest == 1 {
+        let ordinality_arrays = unnest_ordinality_arrays(
+            arrs_to_unnest.as_ref(),
+            unnested_length,
+            total_length,
+        )?;
+        ordinality_arrays
+            .into_iter()
+            .zip(list_unnest_specs.iter())
+            .for_each(|(ordinality_arr, unnesting)| {
+                temp_ordinality_arrs.insert(*unnesting, ordinality_arr);
+            });
+    }
+
     // Create the take indices array for other columns
     let take_indices = create_take_indicies(unnested_length, total_length);
 
@@ -467,6 +486,7 @@ fn list_unnest_at_level(
 }
 struct UnnestingResult {
     arr: ArrayRef,
+    ordinality: Option<ArrayRef>,
     depth: usize,
 }
 
@@ -537,6 +557,7 @@ fn build_batch(
         0 => flatten_struct_cols(batch.columns(), schema, struct_column_indices),
         _ => {
             let mut temp_unnested_result = HashMap::new();
+            let mut temp_ordinality_result = HashMap::new();
             let max_recursion = list_type_columns
                 .iter()
                 .fold(0, |highest_depth, ListUnnest { depth, .. }| {
@@ -557,6 +578,7 @@ fn build_batch(
                     input,
                     list_type_columns,
                     &mut temp_unnested_result,
+                    &mut temp_ordinality_result,
                     depth,
                     options,
                 )?;
@@ -568,20 +590,14 @@ fn build_batch(
             let unnested_array_map: HashMap<usize, Vec<UnnestingResult>> =
                 temp_unnested_result.into_iter().fold(
                     HashMap::new(),
-                    |mut acc,
-                     (
-                        ListUnnest {
-                            index_in_input_schema,
-                            depth,
-                        },
-                        flattened_array,
-                    )| {
-                        acc.entry(index_in_input_schema).or_default().push(
-                            UnnestingResult {
+                    |mut acc, (unnesting, flattened_array)| {
+                        acc.entry(unnesting.index_in_input_schema)
+                            .or_default()
+                            .push(UnnestingResult {
                                 arr: flattened_array,
-                                depth,
-                            },
-                        );
+                                ordinality: temp_ordinality_result.remove(&unnesting),
+                                depth: unnesting.depth,
+                            });
                         acc
                     },
                 );
@@ -623,7 +639,10 @@ fn build_batch(
                             original_index,
                             unnested_columns
                                 .into_iter()
-                                .map(|result| result.arr)
+                                .flat_map(|result| {
+                                    // The element positions follow the unnested column
+                                    std::iter::once(result.arr).chain(result.ordinality)
+                                })
                                 .collect::<Vec<_>>(),
                         )
                     },
@@ -746,13 +765,9 @@ impl ListArrayType for FixedSizeListArray {
     }
 }
 
-/// Unnest multiple list arrays according to the length array.
-fn unnest_list_arrays(
-    list_arrays: &[ArrayRef],
-    length_array: &PrimitiveArray<Int64Type>,
-    capacity: usize,
-) -> Result<Vec<ArrayRef>> {
-    let typed_arrays = list_arrays
+/// Downcast each of the given arrays to a [`ListArrayType`].
+fn as_list_array_types(list_arrays: &[ArrayRef]) -> Result<Vec<&dyn ListArrayType>> {
+    list_arrays
         .iter()
         .map(|list_array| match list_array.data_type() {
             DataType::List(_) => Ok(list_array.as_list::<i32>() as &dyn ListArrayType),
@@ -764,14 +779,33 @@ fn unnest_list_arrays(
             }
             other => exec_err!("Invalid unnest datatype {other }"),
         })
-        .collect::<Result<Vec<_>>>()?;
+        .collect()
+}
 
-    typed_arrays
-        .iter()
-        .map(|list_array| unnest_list_array(*list_array, length_array, capacity))
+/// Unnest multiple list arrays according to the length array.
+fn unnest_list_arrays(
+    list_arrays: &[ArrayRef],
+    length_array: &PrimitiveArray<Int64Type>,
+    capacity: usize,
+) -> Result<Vec<ArrayRef>> {
+    as_list_array_types(list_arrays)?
+        .into_iter()
+        .map(|list_array| unnest_list_array(list_array, length_array, capacity))
         .collect::<Result<_>>()
 }
 
+/// Compute the element positions of multiple list arrays according to the length array.
+fn unnest_ordinality_arrays(
+    list_arrays: &[ArrayRef],
+    length_array: &PrimitiveArray<Int64Type>,
+    capacity: usize,
+) -> Result<Vec<ArrayRef>> {
+    Ok(as_list_array_types(list_arrays)?
+        .into_iter()
+        .map(|list_array| unnest_ordinality_array(list_array, length_array, capacity))
+        .collect())
+}
+
 /// Unnest a list array according the target length array.
 ///
 /// Consider a list array like this:
@@ -826,6 +860,49 @@ fn unnest_list_array(
     )?)
 }
 
+/// Compute the 1-based position of every element that [`unnest_list_array`]
+/// produces for the list array, according to the target length array.
+///
+/// Consider a list array like this:
+///
+/// ```ignore
+/// [1], [2, 3, 4], null, [5], [],
+/// ```
+///
+/// and the length array is:
+///
+/// ```ignore
+/// [2, 3, 2, 1, 2]
+/// ```
+///
+/// Padded elements have no position, so the ordinality array will look like this:
+///
+/// ```ignore
+/// [1, null, 1, 2, 3, null, null, 1, null, null]
+/// ```
+///
+fn unnest_ordinality_array(
+    list_array: &dyn ListArrayType,
+    length_array: &PrimitiveArray<Int64Type>,
+    capacity: usize,
+) -> ArrayRef {
+    let mut builder = PrimitiveArray::<Int64Type>::builder(capacity);
+    for row in 0..list_array.len() {
+        let mut value_length = 0;
+        if !list_array.is_null(row) {
+            let (start, end) = list_array.value_offsets(row);
+            value_length = end - start;
+            (1..=value_length).for_each(|position| builder.append_value(position));
+        }
+        let target_length = length_array.value(row);
+        // Pad with NULL values
+        for _ in value_length..target_length {
+            builder.append_null();
+        }
+    }
+    Arc::new(builder.finish())
+}
+
 /// Creates take indicies that will be used to expand all columns except for the list type
 /// [`columns`](UnnestExec::list_column_indices) that is being unnested.
 /// Every column value needs to be repeated multiple times according to the length array.
@@ -1089,6 +1166,7 @@ mod tests {
             &UnnestOptions {
                 preserve_nulls: true,
                 recursions: vec![],
+                ordinality: false,
             },
         )?;
 
@@ -1167,6 +1245,101 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_unnest_ordinality_array() -> Result<()> {
+        // [A, B, C], [], NULL, [D], NULL, [NULL, F]
+        let list_array = make_generic_array::<i32>();
+        let length_array = Int64Array::from(vec![3, 2, 1, 2, 0, 3]);
+        let ordinality_array = unnest_ordinality_array(&list_array, &length_array, 3 * 6);
+        let positions = ordinality_array
+            .as_primitive::<Int64Type>()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                Some(1),
+                Some(2),
+                Some(3),
+                None,
+                None,
+                None,
+                Some(1),
+                None,
+                Some(1),
+                Some(2),
+                None,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_batch_list_arr_ordinality() -> Result<()> {
+        // col1       | col2
+        // [1, 2, 3]  | ['a']
+        // null       | ['b', 'c']
+        // []         | null
+        let col1 = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2), Some(3)]),
+            None,
+            Some(vec![]),
+        ]);
+        let col2 = GenericListArray::<i32>::new(
+            Arc::new(Field::new_list_field(DataType::Utf8, true)),
+            OffsetBuffer::from_lengths([1, 2, 0]),
+            Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            Some(NullBuffer::from(vec![true, true, false])),
+        );
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("col1", col1.data_type().clone(), true),
+            Field::new("col2", col2.data_type().clone(), true),
+        ]));
+        let out_schema = Arc::new(Schema::new(vec![
+            Field::new("col1", DataType::Int32, true),
+            Field::new("col1_ordinality", DataType::Int64, true),
+            Field::new("col2", DataType::Utf8, true),
+            Field::new("col2_ordinality", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(col1) as ArrayRef, Arc::new(col2) as ArrayRef],
+        )?;
+        let list_type_columns = vec![
+            ListUnnest {
+                index_in_input_schema: 0,
+                depth: 1,
+            },
+            ListUnnest {
+                index_in_input_schema: 1,
+                depth: 1,
+            },
+        ];
+        let ret = build_batch(
+            &batch,
+            &out_schema,
+            list_type_columns.as_ref(),
+            &HashSet::default(),
+            &UnnestOptions::new()
+                .with_preserve_nulls(false)
+                .with_ordinality(true),
+        )?;
+
+        let expected = &[
+            "+------+-----------------+------+-----------------+",
+            "| col1 | col1_ordinality | col2 | col2_ordinality |",
+            "+------+-----------------+------+-----------------+",
+            "| 1    | 1               | a    | 1               |",
+            "| 2    | 2               |      |                 |",
+            "| 3    | 3               |      |                 |",
+            "|      |                 | b    | 1               |",
+            "|      |                 | c    | 2               |",
+            "+------+-----------------+------+-----------------+",
+        ];
+        assert_batches_eq!(expected, &[ret]);
+        Ok(())
+    }
+
     fn verify_longest_length(
         list_arrays: &[ArrayRef],
         preserve_nulls: bool,
@@ -1175,6 +1348,7 @@ mod tests {
         let options = UnnestOptions {
             preserve_nulls,
             recursions: vec![],
+            ordinality: false,
         };
         let longest_length = find_longest_length(list_arrays, &options)?;
         let expected_array = Int64Array::from(expected);
@@ -294,6 +294,8 @@ mod tests {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
+                    histogram: None,
+                    most_common_values: None,
                 },],
             }
         );
@@ -29,6 +29,7 @@ use std::task::{Context, Poll};
 
 use super::utils::create_schema;
 use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::spill::get_record_batch_memory_size;
 use crate::windows::{
     calc_requirements, get_ordered_partition_by_indices, get_partition_by_sort_exprs,
     window_equivalence_properties,
@@ -54,6 +55,7 @@ use datafusion_common::utils::{
 use datafusion_common::{
     arrow_datafusion_err, exec_err, DataFusionError, HashMap, Result,
 };
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use datafusion_execution::TaskContext;
 use datafusion_expr::window_state::{PartitionBatchState, WindowAggState};
 use datafusion_expr::ColumnarValue;
@@ -295,14 +297,19 @@ impl ExecutionPlan for BoundedWindowAggExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let input = self.input.execute(partition, context)?;
+        let input = self.input.execute(partition, Arc::clone(&context))?;
         let search_mode = self.get_search_algo()?;
+        let reservation =
+            MemoryConsumer::new(format!("BoundedWindowAggStream[{partition}]"))
+                .with_can_spill(false)
+                .register(context.memory_pool());
         let stream = Box::pin(BoundedWindowAggStream::new(
             Arc::clone(&self.schema),
             self.window_expr.clone(),
             input,
             BaselineMetrics::new(&self.metrics, partition),
             search_mode,
+            reservation,
         )?);
         Ok(stream)
     }
@@ -874,6 +881,9 @@ pub struct BoundedWindowAggStream {
     /// Search mode for partition columns. This determines the algorithm with
     /// which we group each partition.
     search_mode: Box<dyn PartitionSearcher>,
+    /// Memory reservation for the buffered rows and window expression results
+    /// that are kept after pruning
+    reservation: MemoryReservation,
 }
 
 impl BoundedWindowAggStream {
@@ -917,6 +927,7 @@ impl BoundedWindowAggStream {
         input: SendableRecordBatchStream,
         baseline_metrics: BaselineMetrics,
         search_mode: Box<dyn PartitionSearcher>,
+        reservation: MemoryReservation,
     ) -> Result<Self> {
         let state = window_expr.iter().map(|_| IndexMap::new()).collect();
         let empty_batch = RecordBatch::new_empty(Arc::clone(&schema));
@@ -930,6 +941,7 @@ impl BoundedWindowAggStream {
             window_expr,
             baseline_metrics,
             search_mode,
+            reservation,
         })
     }
 
@@ -960,12 +972,40 @@ impl BoundedWindowAggStream {
                 .collect::<Vec<_>>();
             let n_generated = columns_to_show[0].len();
             self.prune_state(n_generated)?;
+            self.update_reservation()?;
             Ok(RecordBatch::try_new(schema, columns_to_show)?)
         } else {
+            self.update_reservation()?;
             Ok(RecordBatch::new_empty(schema))
         }
     }
 
+    /// Resizes the memory reservation to the size of the rows and window
+    /// expression results kept for the next batches.
+    ///
+    /// These can not be spilled, as they may be accessed by the window frames
+    /// of the next rows, and are only kept until the frames move past them.
+    /// Fails with [`DataFusionError::ResourcesExhausted`] if they don't fit
+    /// in memory.
+    fn update_reservation(&mut self) -> Result<()> {
+        let partition_buffers_size = self
+            .partition_buffers
+            .values()
+            .map(|state| get_record_batch_memory_size(&state.record_batch))
+            .sum::<usize>();
+        let out_columns_size = self
+            .window_agg_states
+            .iter()
+            .flat_map(|window_agg_state| window_agg_state.values())
+            .map(|window_state| window_state.state.out_col.get_array_memory_size())
+            .sum::<usize>();
+        self.reservation.try_resize(
+            get_record_batch_memory_size(&self.input_buffer)
+                + partition_buffers_size
+                + out_columns_size,
+        )
+    }
+
     #[inline]
     fn poll_next_inner(
         &mut self,
@@ -1168,13 +1208,18 @@ mod tests {
     use crate::windows::{create_window_expr, BoundedWindowAggExec, InputOrderMode};
     use crate::{execute_stream, get_plan_string, ExecutionPlan};
 
+    use arrow::array::AsArray;
+    use arrow::datatypes::Int64Type;
     use arrow_array::builder::{Int64Builder, UInt64Builder};
     use arrow_array::RecordBatch;
     use arrow_schema::{DataType, Field, Schema, SchemaRef, SortOptions};
     use datafusion_common::{
-        assert_batches_eq, exec_datafusion_err, Result, ScalarValue,
+        assert_batches_eq, assert_contains, exec_datafusion_err, DataFusionError, Result,
+        ScalarValue,
     };
     use datafusion_execution::config::SessionConfig;
+    use datafusion_execution::memory_pool::{QueryMemoryPool, UnboundedMemoryPool};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_execution::{
         RecordBatchStream, SendableRecordBatchStream, TaskContext,
     };
@@ -1752,4 +1797,112 @@ mod tests {
 
         Ok(())
     }
+
+    /// Returns a count over the window frame starting at `start` and ending
+    /// at the current row, without PARTITION BY, on 4000 rows ordered by `sn`
+    fn count_over_rows(
+        start: WindowFrameBound,
+    ) -> Result<(Arc<dyn ExecutionPlan>, usize)> {
+        let schema = test_schema();
+        let batches = generate_batches(&schema, 4000, 100)?;
+        let input_size = batches
+            .iter()
+            .map(|batch| batch.get_array_memory_size())
+            .sum::<usize>();
+        let input = Arc::new(
+            MemoryExec::try_new(&[batches], Arc::clone(&schema), None)?
+                .try_with_sort_information(schema_orders(&schema)?)?,
+        );
+
+        let window_frame = WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            start,
+            WindowFrameBound::CurrentRow,
+        );
+        let order_by = schema_orders(&schema)?.swap_remove(0);
+        let window_expr = create_window_expr(
+            &WindowFunctionDefinition::AggregateUDF(count_udaf()),
+            "count".to_string(),
+            &[col("sn", &schema)?],
+            &[],
+            order_by.as_ref(),
+            Arc::new(window_frame),
+            schema.as_ref(),
+            false,
+        )?;
+        let plan = Arc::new(BoundedWindowAggExec::try_new(
+            vec![window_expr],
+            input,
+            vec![],
+            InputOrderMode::Sorted,
+        )?);
+        Ok((plan, input_size))
+    }
+
+    fn memory_limited_task_ctx(
+        memory_limit: usize,
+    ) -> Result<(Arc<QueryMemoryPool>, Arc<TaskContext>)> {
+        let pool = Arc::new(QueryMemoryPool::new(
+            Arc::new(UnboundedMemoryPool::default()),
+            Some(memory_limit),
+        ));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_pool(Arc::clone(&pool) as _)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        Ok((pool, task_ctx))
+    }
+
+    // Tests that a running count without PARTITION BY, evaluated like
+    // ROW_NUMBER() OVER (ORDER BY sn), only keeps the rows its window frames
+    // still need, and runs within a memory limit lower than the input size.
+    #[tokio::test]
+    async fn test_bounded_window_memory_limit() -> Result<()> {
+        let memory_limit = 16384;
+        let (plan, input_size) =
+            count_over_rows(WindowFrameBound::Preceding(ScalarValue::UInt64(None)))?;
+        assert!(input_size > memory_limit);
+        let (pool, task_ctx) = memory_limited_task_ctx(memory_limit)?;
+        let batches = collect(plan.execute(0, task_ctx)?).await?;
+
+        let mut n_rows = 0;
+        for batch in &batches {
+            let counts = batch.column(2).as_primitive::<Int64Type>();
+            for count in counts.values() {
+                n_rows += 1;
+                assert_eq!(*count, n_rows);
+            }
+        }
+        assert_eq!(n_rows, 4000);
+        assert!(
+            pool.peak_reserved() <= memory_limit,
+            "peak reserved {}",
+            pool.peak_reserved()
+        );
+        Ok(())
+    }
+
+    // Tests that a window whose frames need more rows than fit in memory
+    // fails instead of reserving more than the memory limit
+    #[tokio::test]
+    async fn test_bounded_window_memory_limit_exceeded() -> Result<()> {
+        let memory_limit = 4096;
+        let (plan, _) = count_over_rows(WindowFrameBound::Preceding(
+            ScalarValue::UInt64(Some(1000)),
+        ))?;
+        let (pool, task_ctx) = memory_limited_task_ctx(memory_limit)?;
+        let err = collect(plan.execute(0, task_ctx)?).await.unwrap_err();
+
+        assert!(
+            matches!(err, DataFusionError::ResourcesExhausted(_)),
+            "unexpected error {err}"
+        );
+        assert_contains!(err.to_string(), "BoundedWindowAggStream[0]");
+        assert!(
+            pool.peak_reserved() <= memory_limit,
+            "peak reserved {}",
+            pool.peak_reserved()
+        );
+        Ok(())
+    }
 }
@@ -18,12 +18,18 @@
 //! Stream and channel implementations for window function expressions.
 
 use std::any::Any;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use super::utils::create_schema;
-use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::metrics::{
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet, Time,
+};
+use crate::spill::{
+    get_record_batch_memory_size, read_spill_as_stream, spill_record_batches,
+};
 use crate::windows::{
     calc_requirements, get_ordered_partition_by_indices, get_partition_by_sort_exprs,
     window_equivalence_properties,
@@ -34,14 +40,26 @@ use crate::{
     RecordBatchStream, SendableRecordBatchStream, Statistics, WindowExpr,
 };
 use arrow::array::ArrayRef;
-use arrow::compute::{concat, concat_batches};
+use arrow::compute::{concat, concat_batches, SortColumn};
 use arrow::datatypes::SchemaRef;
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::stats::Precision;
 use datafusion_common::utils::{evaluate_partition_ranges, transpose};
-use datafusion_common::{internal_err, Result};
-use datafusion_execution::TaskContext;
+use datafusion_common::{
+    exec_err, internal_err, resources_datafusion_err, Result, ScalarValue,
+};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::{DiskManager, TaskContext};
+use datafusion_expr::window_state::{PartitionBatchState, WindowAggState};
+use datafusion_expr::{Accumulator, WindowFrameBound, WindowFrameUnits};
+use datafusion_physical_expr::aggregate::AggregateFunctionExpr;
+use datafusion_physical_expr::window::{
+    PartitionBatches, PartitionKey, PartitionWindowAggStates, PlainAggregateWindowExpr,
+    SlidingAggregateWindowExpr, WindowFn, WindowState,
+};
 use datafusion_physical_expr_common::sort_expr::{LexOrdering, LexRequirement};
 use futures::{ready, Stream, StreamExt};
 
@@ -228,14 +246,20 @@ impl ExecutionPlan for WindowAggExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let input = self.input.execute(partition, context)?;
+        let input = self.input.execute(partition, Arc::clone(&context))?;
+        let reservation = MemoryConsumer::new(format!("WindowAggStream[{partition}]"))
+            .with_can_spill(true)
+            .register(context.memory_pool());
         let stream = Box::pin(WindowAggStream::new(
             Arc::clone(&self.schema),
             self.window_expr.clone(),
             input,
-            BaselineMetrics::new(&self.metrics, partition),
+            WindowAggMetrics::new(&self.metrics, partition),
             self.partition_by_sort_keys()?,
             self.ordered_partition_by_indices.clone(),
+            reservation,
+            Arc::clone(&context.runtime_env().disk_manager),
+            context.session_config().spill_compression(),
         )?);
         Ok(stream)
     }
@@ -274,27 +298,359 @@ fn compute_window_aggregates(
         .collect()
 }
 
+/// Metrics of [`WindowAggStream`]
+struct WindowAggMetrics {
+    baseline: BaselineMetrics,
+    /// count of spills during the execution of the operator
+    spill_count: Count,
+    /// total spilled bytes during the execution of the operator
+    spilled_bytes: Count,
+    /// total size of the spill files written by the operator
+    spill_file_bytes: Count,
+    /// total spilled rows during the execution of the operator
+    spilled_rows: Count,
+}
+
+impl WindowAggMetrics {
+    fn new(metrics: &ExecutionPlanMetricsSet, partition: usize) -> Self {
+        Self {
+            baseline: BaselineMetrics::new(metrics, partition),
+            spill_count: MetricBuilder::new(metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spill_file_bytes: MetricBuilder::new(metrics).spill_file_bytes(partition),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+        }
+    }
+}
+
+/// Output of [`WindowAggStream`] waiting to be emitted
+enum WindowAggOutput {
+    /// Results computed in memory
+    Batch(RecordBatch),
+    /// Results of a spilled partition, computed while reading it back
+    Spilled(SpilledPartition),
+    /// A spilled partition being read back to memory, whose results are
+    /// computed once all of its rows are read
+    Reloaded(ReloadedPartition),
+}
+
+/// Reads the spill files of a partition back from disk, in order, on a
+/// blocking thread
+struct SpillReader {
+    files: Vec<Arc<RefCountedTempFile>>,
+    schema: SchemaRef,
+    /// The index in `files` of the next file to read
+    next_file: usize,
+    /// The batches of the file being read
+    stream: Option<SendableRecordBatchStream>,
+}
+
+impl SpillReader {
+    fn new(files: Vec<RefCountedTempFile>, schema: SchemaRef) -> Self {
+        Self {
+            files: files.into_iter().map(Arc::new).collect(),
+            schema,
+            next_file: 0,
+            stream: None,
+        }
+    }
+
+    fn poll_next_batch(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<RecordBatch>>> {
+        loop {
+            if let Some(stream) = &mut self.stream {
+                match ready!(stream.poll_next_unpin(cx)) {
+                    Some(Ok(batch)) if batch.num_rows() == 0 => continue,
+                    Some(result) => return Poll::Ready(result.map(Some)),
+                    None => self.stream = None,
+                }
+            }
+            let Some(file) = self.files.get(self.next_file) else {
+                return Poll::Ready(Ok(None));
+            };
+            self.next_file += 1;
+            self.stream = Some(read_spill_as_stream(
+                Arc::clone(file),
+                Arc::clone(&self.schema),
+                2,
+            )?);
+        }
+    }
+
+    /// Reads the spill files again, from the first one
+    fn rewind(&mut self) {
+        self.next_file = 0;
+        self.stream = None;
+    }
+}
+
+/// A spilled partition on which every window expression is an aggregate over
+/// a frame that starts or ends with the partition, see [`SpilledWindowFrame`].
+/// The window columns are computed while reading the partition back from
+/// disk, one batch at a time, and appended to the spilled batches.
+struct SpilledPartition {
+    reader: SpillReader,
+    /// The window expressions, in the order of the window columns
+    columns: Vec<SpilledWindowColumn>,
+    /// Whether the partition is being read to aggregate all of its rows,
+    /// before it is read again to compute the window columns
+    aggregating: bool,
+    elapsed_compute: Time,
+}
+
+impl SpilledPartition {
+    fn new(
+        reader: SpillReader,
+        columns: Vec<SpilledWindowColumn>,
+        elapsed_compute: Time,
+    ) -> Self {
+        let aggregating = columns
+            .iter()
+            .any(|column| column.frame != SpilledWindowFrame::Preceding);
+        Self {
+            reader,
+            columns,
+            aggregating,
+            elapsed_compute,
+        }
+    }
+
+    fn poll_next_batch(
+        &mut self,
+        cx: &mut Context<'_>,
+        schema: &SchemaRef,
+    ) -> Poll<Result<Option<RecordBatch>>> {
+        loop {
+            let Some(batch) = ready!(self.reader.poll_next_batch(cx))? else {
+                if !self.aggregating {
+                    return Poll::Ready(Ok(None));
+                }
+                // every row of the partition is aggregated, read them again
+                // to compute the window columns
+                self.aggregating = false;
+                self.reader.rewind();
+                continue;
+            };
+
+            // record compute time on drop
+            let _timer = self.elapsed_compute.timer();
+            if self.aggregating {
+                for column in &mut self.columns {
+                    column.update_partition(&batch)?;
+                }
+                continue;
+            }
+            let mut columns = batch.columns().to_vec();
+            for column in &mut self.columns {
+                columns.push(column.evaluate(&batch)?);
+            }
+            let batch = RecordBatch::try_new(Arc::clone(schema), columns)?;
+            return Poll::Ready(Ok(Some(batch)));
+        }
+    }
+}
+
+/// A spilled partition on which some window expression may access any row
+/// of the partition, so that it is read back to memory, which fails if it
+/// does not fit in the memory limit
+struct ReloadedPartition {
+    reader: SpillReader,
+    /// The rows read back so far
+    batches: Vec<RecordBatch>,
+    /// The memory reserved for `batches`
+    reserved: usize,
+}
+
+/// The frame of a window aggregate that can be evaluated on a spilled
+/// partition without reading it back to memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpilledWindowFrame {
+    /// The frame is the whole partition: every row of the partition is
+    /// aggregated before reading it back, and the window column holds the
+    /// same value for every row
+    WholePartition,
+    /// `ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`: the rows are
+    /// aggregated as they are read back
+    Preceding,
+    /// `ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING`: every row of the
+    /// partition is aggregated before reading it back, and the rows are
+    /// retracted from the aggregate as they are read back
+    Following,
+}
+
+/// A window expression of a [`SpilledPartition`]
+///
+/// The window columns of the [`SpilledWindowFrame::Preceding`] and
+/// [`SpilledWindowFrame::Following`] frames are computed by evaluating the
+/// window expression incrementally on each batch read back, as
+/// [`BoundedWindowAggExec`] does, keeping only the last row read back.
+///
+/// [`BoundedWindowAggExec`]: crate::windows::BoundedWindowAggExec
+struct SpilledWindowColumn {
+    window_expr: Arc<dyn WindowExpr>,
+    frame: SpilledWindowFrame,
+    /// The aggregate of every row of the partition, if the frame needs it
+    accumulator: Option<Box<dyn Accumulator>>,
+    /// The rows read back that the window expression is evaluated on
+    batches: PartitionBatches,
+    /// The state of the window expression evaluated on `batches`
+    states: PartitionWindowAggStates,
+}
+
+impl SpilledWindowColumn {
+    /// Returns the window expression evaluated on a spilled partition, if its
+    /// frame is one of [`SpilledWindowFrame`]
+    fn try_new(window_expr: &Arc<dyn WindowExpr>) -> Result<Option<Self>> {
+        let window_frame = window_expr.get_window_frame();
+        let Some(aggregate) = aggregate_expr(window_expr.as_ref()) else {
+            return Ok(None);
+        };
+        let rows = window_frame.units == WindowFrameUnits::Rows;
+        let (frame, accumulator) =
+            match (&window_frame.start_bound, &window_frame.end_bound) {
+                (start, end) if start.is_unbounded() && end.is_unbounded() => (
+                    SpilledWindowFrame::WholePartition,
+                    Some(aggregate.create_accumulator()?),
+                ),
+                (start, WindowFrameBound::CurrentRow) if rows && start.is_unbounded() => {
+                    (SpilledWindowFrame::Preceding, None)
+                }
+                (WindowFrameBound::CurrentRow, end) if rows && end.is_unbounded() => {
+                    let accumulator = aggregate.create_sliding_accumulator()?;
+                    if !accumulator.supports_retract_batch() {
+                        return Ok(None);
+                    }
+                    (SpilledWindowFrame::Following, Some(accumulator))
+                }
+                _ => return Ok(None),
+            };
+        Ok(Some(Self {
+            window_expr: Arc::clone(window_expr),
+            frame,
+            accumulator,
+            batches: PartitionBatches::new(),
+            states: PartitionWindowAggStates::new(),
+        }))
+    }
+
+    /// Aggregates `batch` before the partition is read back, if the frame
+    /// needs every row of the partition
+    fn update_partition(&mut self, batch: &RecordBatch) -> Result<()> {
+        match &mut self.accumulator {
+            Some(accumulator) => {
+                accumulator.update_batch(&self.window_expr.evaluate_args(batch)?)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Evaluates the window column of `batch`, the next rows of the
+    /// partition read back from disk
+    fn evaluate(&mut self, batch: &RecordBatch) -> Result<ArrayRef> {
+        if let Some(accumulator) = &mut self.accumulator {
+            if self.frame == SpilledWindowFrame::WholePartition {
+                return accumulator.evaluate()?.to_array_of_size(batch.num_rows());
+            }
+        }
+
+        let key = PartitionKey::new();
+        if let Some(accumulator) = self.accumulator.take() {
+            // the rows are retracted from the aggregate of the partition as
+            // the window expression is evaluated
+            let state = WindowAggState::new(self.window_expr.field()?.data_type())?;
+            let window_fn = WindowFn::Aggregate(accumulator);
+            self.states
+                .insert(key.clone(), WindowState { state, window_fn });
+        }
+
+        let partition = self
+            .batches
+            .entry(key.clone())
+            .or_insert_with(|| PartitionBatchState::new(batch.schema()));
+        partition.extend(batch)?;
+        // the frames of the rows read back do not depend on the next rows,
+        // which are either not in the frames or already aggregated
+        partition.is_end = true;
+        let num_rows = partition.record_batch.num_rows();
+        if let Some(window_state) = self.states.get_mut(&key) {
+            if self.frame == SpilledWindowFrame::Following {
+                window_state.state.window_frame_range.end = num_rows;
+            }
+        }
+        self.window_expr
+            .evaluate_stateful(&self.batches, &mut self.states)?;
+
+        // only keep the rows that the frames of the next rows start from
+        let (Some(partition), Some(window_state)) =
+            (self.batches.get_mut(&key), self.states.get_mut(&key))
+        else {
+            return internal_err!("Cannot find the state of a spilled partition");
+        };
+        let state = &mut window_state.state;
+        let n_prune = state.window_frame_range.start;
+        partition.record_batch =
+            partition.record_batch.slice(n_prune, num_rows - n_prune);
+        state.prune_state(n_prune);
+        let empty = state.out_col.slice(0, 0);
+        Ok(std::mem::replace(&mut state.out_col, empty))
+    }
+}
+
+/// Returns the aggregate of `window_expr`, if it is an aggregate window
+/// expression
+fn aggregate_expr(window_expr: &dyn WindowExpr) -> Option<&AggregateFunctionExpr> {
+    let window_expr = window_expr.as_any();
+    if let Some(window_expr) = window_expr.downcast_ref::<PlainAggregateWindowExpr>() {
+        return Some(window_expr.get_aggregate_expr());
+    }
+    window_expr
+        .downcast_ref::<SlidingAggregateWindowExpr>()
+        .map(|window_expr| window_expr.get_aggregate_expr())
+}
+
 /// stream for window aggregation plan
+///
+/// As the input is sorted on the partition keys, the window expressions are
+/// evaluated one partition at a time, as soon as all of its rows are received.
+/// The rows of the partition being received are spilled to disk when its
+/// memory reservation can not grow, and read back once the partition is
+/// complete.
 pub struct WindowAggStream {
     schema: SchemaRef,
     input: SendableRecordBatchStream,
+    /// The in-memory rows of the partition being received
     batches: Vec<RecordBatch>,
+    /// The spilled rows of the partition being received, preceding `batches`
+    spills: Vec<RefCountedTempFile>,
+    /// The values of the partition keys of the partition being received
+    partition_key: Option<Vec<ScalarValue>>,
+    /// Results waiting to be emitted, in order
+    output: VecDeque<WindowAggOutput>,
     finished: bool,
     window_expr: Vec<Arc<dyn WindowExpr>>,
     partition_by_sort_keys: LexOrdering,
-    baseline_metrics: BaselineMetrics,
+    metrics: WindowAggMetrics,
     ordered_partition_by_indices: Vec<usize>,
+    reservation: MemoryReservation,
+    disk_manager: Arc<DiskManager>,
+    spill_compression: SpillCompression,
 }
 
 impl WindowAggStream {
     /// Create a new WindowAggStream
-    pub fn new(
+    #[allow(clippy::too_many_arguments)]
+    fn new(
         schema: SchemaRef,
         window_expr: Vec<Arc<dyn WindowExpr>>,
         input: SendableRecordBatchStream,
-        baseline_metrics: BaselineMetrics,
+        metrics: WindowAggMetrics,
         partition_by_sort_keys: LexOrdering,
         ordered_partition_by_indices: Vec<usize>,
+        reservation: MemoryReservation,
+        disk_manager: Arc<DiskManager>,
+        spill_compression: SpillCompression,
     ) -> Result<Self> {
         // In WindowAggExec all partition by columns should be ordered.
         if window_expr[0].partition_by().len() != ordered_partition_by_indices.len() {
@@ -304,27 +660,28 @@ impl WindowAggStream {
             schema,
             input,
             batches: vec![],
+            spills: vec![],
+            partition_key: None,
+            output: VecDeque::new(),
             finished: false,
             window_expr,
-            baseline_metrics,
+            metrics,
             partition_by_sort_keys,
             ordered_partition_by_indices,
+            reservation,
+            disk_manager,
+            spill_compression,
         })
     }
 
-    fn compute_aggregates(&self) -> Result<RecordBatch> {
+    fn compute_aggregates(&self, batch: &RecordBatch) -> Result<RecordBatch> {
         // record compute time on drop
-        let _timer = self.baseline_metrics.elapsed_compute().timer();
-        let batch = concat_batches(&self.input.schema(), &self.batches)?;
+        let _timer = self.metrics.baseline.elapsed_compute().timer();
         if batch.num_rows() == 0 {
             return Ok(RecordBatch::new_empty(Arc::clone(&self.schema)));
         }
 
-        let partition_by_sort_keys = self
-            .ordered_partition_by_indices
-            .iter()
-            .map(|idx| self.partition_by_sort_keys[*idx].evaluate_to_sort_column(&batch))
-            .collect::<Result<Vec<_>>>()?;
+        let partition_by_sort_keys = self.partition_columns(batch)?;
         let partition_points =
             evaluate_partition_ranges(batch.num_rows(), &partition_by_sort_keys)?;
 
@@ -355,6 +712,200 @@ impl WindowAggStream {
             batch_columns,
         )?)
     }
+
+    /// Evaluates the partition by columns of `batch`
+    fn partition_columns(&self, batch: &RecordBatch) -> Result<Vec<SortColumn>> {
+        self.ordered_partition_by_indices
+            .iter()
+            .map(|idx| self.partition_by_sort_keys[*idx].evaluate_to_sort_column(batch))
+            .collect()
+    }
+
+    /// Evaluates the window expressions on the partitions of `batch` that are
+    /// complete, and buffers the rows of its last partition
+    fn process_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        let partition_columns = self.partition_columns(&batch)?;
+        let ranges = evaluate_partition_ranges(batch.num_rows(), &partition_columns)?;
+        let last = ranges[ranges.len() - 1].clone();
+
+        let mut complete_start = 0;
+        if let Some(partition_key) = self.partition_key.take() {
+            if partition_key != partition_key_at(&partition_columns, 0)? {
+                // the buffered partition ended with the previous batch
+                self.finish_partition()?;
+            } else if ranges.len() > 1 {
+                // the first rows of `batch` complete the buffered partition
+                self.buffer_batch(batch.slice(0, ranges[0].end))?;
+                self.finish_partition()?;
+                complete_start = ranges[0].end;
+            }
+        }
+
+        if complete_start < last.start {
+            let complete = batch.slice(complete_start, last.start - complete_start);
+            let output = self.compute_aggregates(&complete)?;
+            self.output.push_back(WindowAggOutput::Batch(output));
+        }
+
+        // the last partition may continue in the next batches
+        self.partition_key = Some(partition_key_at(&partition_columns, last.start)?);
+        self.buffer_batch(batch.slice(last.start, last.end - last.start))
+    }
+
+    /// Buffers `batch`, spilling the partition being received to disk when
+    /// the memory reservation can not grow
+    fn buffer_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        let size = get_record_batch_memory_size(&batch);
+        match self.reservation.try_grow(size) {
+            Ok(_) => {
+                self.batches.push(batch);
+                Ok(())
+            }
+            Err(_) if self.disk_manager.tmp_files_enabled() => {
+                self.batches.push(batch);
+                self.spill(size)
+            }
+            Err(e) => exec_err!("{}. Disk spilling disabled.", e.message()),
+        }
+    }
+
+    /// Spills the in-memory rows of the partition being received to disk,
+    /// `unreserved` bytes of which are not accounted in the reservation
+    fn spill(&mut self, unreserved: usize) -> Result<()> {
+        let batches = std::mem::take(&mut self.batches);
+        let mut spill_file = self.disk_manager.create_tmp_file("WindowAggSpill")?;
+        let spilled_rows = spill_record_batches(
+            batches,
+            &mut spill_file,
+            self.input.schema(),
+            self.spill_compression,
+        )?;
+
+        self.metrics.spill_count.add(1);
+        self.metrics
+            .spilled_bytes
+            .add(self.reservation.size() + unreserved);
+        self.metrics
+            .spill_file_bytes
+            .add(spill_file.current_disk_usage() as usize);
+        self.metrics.spilled_rows.add(spilled_rows);
+
+        self.spills.push(spill_file);
+        self.reservation.free();
+        Ok(())
+    }
+
+    /// Evaluates the window expressions on the buffered partition, which is
+    /// complete
+    fn finish_partition(&mut self) -> Result<()> {
+        if self.spills.is_empty() {
+            let batch = concat_batches(&self.input.schema(), &self.batches)?;
+            self.batches.clear();
+            self.reservation.free();
+            let output = self.compute_aggregates(&batch)?;
+            self.output.push_back(WindowAggOutput::Batch(output));
+            return Ok(());
+        }
+
+        // write the remaining rows to disk as well, so that the partition can
+        // be read back in order
+        if !self.batches.is_empty() {
+            self.spill(0)?;
+        }
+        let files = std::mem::take(&mut self.spills);
+        let reader = SpillReader::new(files, self.input.schema());
+
+        let output = match self.spilled_window_columns()? {
+            // the window columns are computed while reading the rows back,
+            // after aggregating them first if any frame needs the whole
+            // partition
+            Some(columns) => WindowAggOutput::Spilled(SpilledPartition::new(
+                reader,
+                columns,
+                self.metrics.baseline.elapsed_compute().clone(),
+            )),
+            // other window expressions may access any row of the partition,
+            // so that it has to be read back to memory, which fails if it
+            // does not fit in the memory limit
+            None => WindowAggOutput::Reloaded(ReloadedPartition {
+                reader,
+                batches: vec![],
+                reserved: 0,
+            }),
+        };
+        self.output.push_back(output);
+        Ok(())
+    }
+
+    /// Buffers `batch`, a row of a spilled partition read back to memory
+    fn reload_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        let Some(WindowAggOutput::Reloaded(partition)) = self.output.front_mut() else {
+            return internal_err!("No spilled partition is read back to memory");
+        };
+        let size = get_record_batch_memory_size(&batch);
+        self.reservation.try_grow(size).map_err(|e| {
+            resources_datafusion_err!(
+                "{}. The window expressions can not be evaluated on a spilled partition",
+                e.message()
+            )
+        })?;
+        partition.reserved += size;
+        partition.batches.push(batch);
+        Ok(())
+    }
+
+    /// Evaluates the window expressions on the spilled partition read back
+    /// to memory, once all of its rows are read
+    fn finish_reloaded_partition(&mut self) -> Result<RecordBatch> {
+        let Some(WindowAggOutput::Reloaded(partition)) = self.output.pop_front() else {
+            return internal_err!("No spilled partition is read back to memory");
+        };
+        let batch = concat_batches(&self.input.schema(), &partition.batches)?;
+        drop(partition.batches);
+        let output = self.compute_aggregates(&batch)?;
+        self.reservation.shrink(partition.reserved);
+        Ok(output)
+    }
+
+    /// Returns the window expressions as [`SpilledWindowColumn`]s if all of
+    /// them can be evaluated on a spilled partition
+    fn spilled_window_columns(&self) -> Result<Option<Vec<SpilledWindowColumn>>> {
+        let mut columns = Vec::with_capacity(self.window_expr.len());
+        for window_expr in &self.window_expr {
+            match SpilledWindowColumn::try_new(window_expr)? {
+                Some(column) => columns.push(column),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(columns))
+    }
+
+    /// Evaluates the window expressions on the last partition once the input
+    /// is exhausted
+    fn finish_input(&mut self) -> Result<()> {
+        if self.partition_key.take().is_some() {
+            self.finish_partition()
+        } else {
+            // the input is empty
+            let output = RecordBatch::new_empty(Arc::clone(&self.schema));
+            self.output.push_back(WindowAggOutput::Batch(output));
+            Ok(())
+        }
+    }
+}
+
+/// Returns the values of the partition keys at `row`
+fn partition_key_at(
+    partition_columns: &[SortColumn],
+    row: usize,
+) -> Result<Vec<ScalarValue>> {
+    partition_columns
+        .iter()
+        .map(|column| ScalarValue::try_from_array(&column.values, row))
+        .collect()
 }
 
 impl Stream for WindowAggStream {
@@ -365,7 +916,7 @@ impl Stream for WindowAggStream {
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
         let poll = self.poll_next_inner(cx);
-        self.baseline_metrics.record_poll(poll)
+        self.metrics.baseline.record_poll(poll)
     }
 }
 
@@ -375,22 +926,53 @@ impl WindowAggStream {
         &mut self,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<RecordBatch>>> {
-        if self.finished {
-            return Poll::Ready(None);
-        }
-
         loop {
-            let result = match ready!(self.input.poll_next_unpin(cx)) {
-                Some(Ok(batch)) => {
-                    self.batches.push(batch);
-                    continue;
+            let result = match self.output.front_mut() {
+                Some(WindowAggOutput::Batch(_)) => match self.output.pop_front() {
+                    Some(WindowAggOutput::Batch(batch)) => Ok(batch),
+                    _ => unreachable!(),
+                },
+                Some(WindowAggOutput::Spilled(partition)) => {
+                    match ready!(partition.poll_next_batch(cx, &self.schema)) {
+                        Ok(Some(batch)) => Ok(batch),
+                        Ok(None) => {
+                            self.output.pop_front();
+                            continue;
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                Some(WindowAggOutput::Reloaded(partition)) => {
+                    match ready!(partition.reader.poll_next_batch(cx)) {
+                        Ok(Some(batch)) => match self.reload_batch(batch) {
+                            Ok(()) => continue,
+                            Err(e) => Err(e),
+                        },
+                        Ok(None) => self.finish_reloaded_partition(),
+                        Err(e) => Err(e),
+                    }
+                }
+                None if self.finished => return Poll::Ready(None),
+                None => {
+                    let result = match ready!(self.input.poll_next_unpin(cx)) {
+                        Some(Ok(batch)) => self.process_batch(batch),
+                        Some(Err(e)) => Err(e),
+                        None => {
+                            self.finished = true;
+                            self.finish_input()
+                        }
+                    };
+                    match result {
+                        Ok(()) => continue,
+                        Err(e) => Err(e),
+                    }
                 }
-                Some(Err(e)) => Err(e),
-                None => self.compute_aggregates(),
             };
 
-            self.finished = true;
-
+            if result.is_err() {
+                self.finished = true;
+                self.output.clear();
+            }
             return Poll::Ready(Some(result));
         }
     }
@@ -402,3 +984,217 @@ impl RecordBatchStream for WindowAggStream {
         Arc::clone(&self.schema)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::expressions::{col, PhysicalSortExpr};
+    use crate::memory::MemoryExec;
+    use crate::windows::create_window_expr;
+
+    use arrow::array::{AsArray, Int64Array};
+    use arrow::datatypes::{DataType, Field, Int64Type, Schema};
+    use arrow_schema::SortOptions;
+    use datafusion_common::DataFusionError;
+    use datafusion_execution::disk_manager::DiskManagerConfig;
+    use datafusion_execution::memory_pool::{QueryMemoryPool, UnboundedMemoryPool};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_expr::{
+        WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition,
+    };
+    use datafusion_functions_aggregate::count::count_udaf;
+    use datafusion_physical_expr_common::sort_expr::LexOrderingRef;
+
+    /// Returns 3 partitions of 1500, 1500 and 1000 rows, in batches of 100
+    /// rows, sorted on the partition key `p`
+    fn partitioned_input() -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("p", DataType::Int64, false),
+            Field::new("v", DataType::Int64, false),
+        ]));
+        let batches = (0..40)
+            .map(|i| {
+                let rows = i * 100..(i + 1) * 100;
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![
+                        Arc::new(Int64Array::from_iter_values(
+                            rows.clone().map(|row| row / 1500),
+                        )),
+                        Arc::new(Int64Array::from_iter_values(rows)),
+                    ],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let sort_information = LexOrdering::new(vec![PhysicalSortExpr {
+            expr: col("p", &schema)?,
+            options: SortOptions::default(),
+        }]);
+        let exec = MemoryExec::try_new(&[batches], schema, None)?
+            .try_with_sort_information(vec![sort_information])?;
+        Ok(Arc::new(exec))
+    }
+
+    /// Counts the rows of the partition in each of `window_frames`
+    fn count_window_exec(
+        input: Arc<dyn ExecutionPlan>,
+        window_frames: Vec<WindowFrame>,
+    ) -> Result<Arc<WindowAggExec>> {
+        let schema = input.schema();
+        let partition_by = vec![col("p", &schema)?];
+        let window_expr = window_frames
+            .into_iter()
+            .enumerate()
+            .map(|(idx, window_frame)| {
+                create_window_expr(
+                    &WindowFunctionDefinition::AggregateUDF(count_udaf()),
+                    format!("count{idx}"),
+                    &[col("v", &schema)?],
+                    &partition_by,
+                    LexOrderingRef::default(),
+                    Arc::new(window_frame),
+                    schema.as_ref(),
+                    false,
+                )
+            })
+            .collect::<Result<_>>()?;
+        Ok(Arc::new(WindowAggExec::try_new(
+            window_expr,
+            input,
+            partition_by,
+        )?))
+    }
+
+    /// A memory limit lower than the size of a partition
+    const MEMORY_LIMIT: usize = 8192;
+
+    /// Runs `exec` with [`MEMORY_LIMIT`], returning the results and the peak
+    /// memory reserved
+    async fn collect_with_memory_limit(
+        exec: Arc<WindowAggExec>,
+    ) -> (Result<Vec<RecordBatch>>, usize) {
+        let pool = Arc::new(QueryMemoryPool::new(
+            Arc::new(UnboundedMemoryPool::default()),
+            Some(MEMORY_LIMIT),
+        ));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_pool(Arc::clone(&pool) as _)
+            .with_disk_manager(DiskManagerConfig::NewOs)
+            .build_arc()
+            .unwrap();
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let batches = collect(exec, task_ctx).await;
+        (batches, pool.peak_reserved())
+    }
+
+    /// Runs `exec` with [`MEMORY_LIMIT`], checking that it spills and does not
+    /// reserve more memory than the limit
+    async fn collect_spilled(exec: Arc<WindowAggExec>) -> Result<RecordBatch> {
+        let (batches, peak_reserved) = collect_with_memory_limit(Arc::clone(&exec)).await;
+        let batches = batches?;
+        assert!(
+            peak_reserved <= MEMORY_LIMIT,
+            "peak reserved {peak_reserved}"
+        );
+
+        let metrics = exec.metrics().unwrap();
+        assert!(metrics.spill_count().unwrap() > 0);
+        assert!(metrics.spilled_bytes().unwrap() > 0);
+        assert!(metrics.spilled_rows().unwrap() > 0);
+
+        let batch = concat_batches(&exec.schema(), &batches)?;
+        let rows = batch.column(1).as_primitive::<Int64Type>();
+        assert!(rows.values().iter().copied().eq(0..4000));
+        Ok(batch)
+    }
+
+    /// Returns the size of the partition of `row`, and its number of rows
+    /// from `row` to the end of the partition
+    fn partition_counts(row: i64) -> (i64, i64) {
+        let (start, end) = match row / 1500 {
+            0 => (0, 1500),
+            1 => (1500, 3000),
+            _ => (3000, 4000),
+        };
+        (end - start, end - row)
+    }
+
+    #[tokio::test]
+    async fn test_spill_whole_partition_aggregates() -> Result<()> {
+        let exec = count_window_exec(partitioned_input()?, vec![WindowFrame::new(None)])?;
+        let batch = collect_spilled(exec).await?;
+
+        let counts = batch.column(2).as_primitive::<Int64Type>();
+        for (row, count) in counts.values().iter().enumerate() {
+            assert_eq!(*count, partition_counts(row as i64).0);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spill_unbounded_following() -> Result<()> {
+        let following = WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::CurrentRow,
+            WindowFrameBound::Following(ScalarValue::UInt64(None)),
+        );
+        let exec = count_window_exec(
+            partitioned_input()?,
+            vec![WindowFrame::new(None), following],
+        )?;
+        let batch = collect_spilled(exec).await?;
+
+        let counts = batch.column(2).as_primitive::<Int64Type>();
+        let following_counts = batch.column(3).as_primitive::<Int64Type>();
+        for row in 0..batch.num_rows() {
+            assert_eq!(
+                (counts.value(row), following_counts.value(row)),
+                partition_counts(row as i64)
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spill_unbounded_preceding() -> Result<()> {
+        let preceding = WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
+            WindowFrameBound::CurrentRow,
+        );
+        let exec = count_window_exec(partitioned_input()?, vec![preceding])?;
+        let batch = collect_spilled(exec).await?;
+
+        let preceding_counts = batch.column(2).as_primitive::<Int64Type>();
+        for row in 0..batch.num_rows() {
+            let (size, following) = partition_counts(row as i64);
+            assert_eq!(preceding_counts.value(row), size - following + 1);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spill_bounded_frame_exceeds_memory_limit() -> Result<()> {
+        // any row of the partition may be in the frame of a sliding window, so
+        // that a spilled partition has to be read back to memory
+        let sliding = WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(ScalarValue::UInt64(Some(1))),
+            WindowFrameBound::Following(ScalarValue::UInt64(Some(1))),
+        );
+        let exec = count_window_exec(partitioned_input()?, vec![sliding])?;
+        let (batches, peak_reserved) = collect_with_memory_limit(exec).await;
+
+        let err = batches.unwrap_err();
+        assert!(
+            matches!(err.find_root(), DataFusionError::ResourcesExhausted(_)),
+            "{err}"
+        );
+        assert!(
+            peak_reserved <= MEMORY_LIMIT,
+            "peak reserved {peak_reserved}"
+        );
+        Ok(())
+    }
+}
@@ -699,6 +699,8 @@ impl From<&protobuf::ColumnStats> for ColumnStatistics {
             } else {
                 Precision::Absent
             },
+            histogram: None,
+            most_common_values: None,
         }
     }
 }
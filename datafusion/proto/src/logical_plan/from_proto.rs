@@ -23,15 +23,15 @@ use datafusion_common::{
     Result, ScalarValue, TableReference, UnnestOptions,
 };
 use datafusion_expr::expr::{Alias, Placeholder, Sort};
-use datafusion_expr::expr::{Unnest, WildcardOptions};
+use datafusion_expr::expr::{Lambda, Unnest, WildcardOptions};
 use datafusion_expr::ExprFunctionExt;
 use datafusion_expr::{
     expr::{self, InList, WindowFunction},
     logical_plan::{PlanType, StringifiedPlan},
     Between, BinaryExpr, BuiltInWindowFunction, Case, Cast, Expr, GroupingSet,
     GroupingSet::GroupingSets,
-    JoinConstraint, JoinType, Like, Operator, TryCast, WindowFrame, WindowFrameBound,
-    WindowFrameUnits,
+    JoinConstraint, JoinType, Like, Operator, SampleMethod, TryCast, WindowFrame,
+    WindowFrameBound, WindowFrameUnits,
 };
 use datafusion_proto_common::{from_proto::FromOptionalField, FromProtoError as Error};
 
@@ -65,6 +65,7 @@ impl From<&protobuf::UnnestOptions> for UnnestOptions {
                     depth: r.depth as usize,
                 })
                 .collect::<Vec<_>>(),
+            ordinality: opts.ordinality,
         }
     }
 }
@@ -214,6 +215,7 @@ impl From<protobuf::JoinType> for JoinType {
             protobuf::JoinType::Leftanti => JoinType::LeftAnti,
             protobuf::JoinType::Rightanti => JoinType::RightAnti,
             protobuf::JoinType::Leftmark => JoinType::LeftMark,
+            protobuf::JoinType::Asof => JoinType::AsOf,
         }
     }
 }
@@ -227,6 +229,15 @@ impl From<protobuf::JoinConstraint> for JoinConstraint {
     }
 }
 
+impl From<protobuf::SampleMethod> for SampleMethod {
+    fn from(method: protobuf::SampleMethod) -> Self {
+        match method {
+            protobuf::SampleMethod::Bernoulli => SampleMethod::Bernoulli,
+            protobuf::SampleMethod::System => SampleMethod::System,
+        }
+    }
+}
+
 pub fn parse_expr(
     proto: &protobuf::LogicalExprNode,
     registry: &dyn FunctionRegistry,
@@ -528,6 +539,10 @@ pub fn parse_expr(
             }
             Ok(Expr::Unnest(Unnest::new(exprs.swap_remove(0))))
         }
+        ExprType::Lambda(lambda) => Ok(Expr::Lambda(Lambda::new(
+            lambda.params.clone(),
+            parse_required_expr(lambda.body.as_deref(), registry, "body", codec)?,
+        ))),
         ExprType::InList(in_list) => Ok(Expr::InList(InList::new(
             Box::new(parse_required_expr(
                 in_list.expr.as_deref(),
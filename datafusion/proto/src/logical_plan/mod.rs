@@ -62,7 +62,7 @@ use datafusion_expr::{
     logical_plan::{
         builder::project, Aggregate, CreateCatalog, CreateCatalogSchema,
         CreateExternalTable, CreateView, DdlStatement, Distinct, EmptyRelation,
-        Extension, Join, JoinConstraint, Prepare, Projection, Repartition, Sort,
+        Extension, Join, JoinConstraint, Prepare, Projection, Repartition, Sample, Sort,
         SubqueryAlias, TableScan, Values, Window,
     },
     DistinctOn, DropView, Expr, LogicalPlan, LogicalPlanBuilder, ScalarUDF, SortExpr,
@@ -397,7 +397,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             }
                             Arc::new(json)
                         }
-                        FileFormatType::Avro(..) => Arc::new(AvroFormat),
+                        FileFormatType::Avro(..) => Arc::new(AvroFormat::default()),
                     };
 
                 let table_paths = &scan
@@ -905,6 +905,20 @@ impl AsLogicalPlan for LogicalPlanNode {
                     options: into_required!(unnest.options)?,
                 }))
             }
+            LogicalPlanType::Sample(sample) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(sample.input, ctx, extension_codec)?;
+                let method =
+                    protobuf::SampleMethod::try_from(sample.method).map_err(|_| {
+                        proto_error(format!(
+                            "Received a SampleNode message with unknown SampleMethod {}",
+                            sample.method
+                        ))
+                    })?;
+                LogicalPlanBuilder::from(input)
+                    .sample(method.into(), sample.fraction, sample.seed)?
+                    .build()
+            }
         }
     }
 
@@ -1597,6 +1611,19 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Ddl(DdlStatement::DropFunction(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropFunction",
             )),
+            LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(_)) => {
+                Err(proto_error(
+                    "LogicalPlan serde is not yet implemented for CreateMaterializedView",
+                ))
+            }
+            LogicalPlan::Ddl(DdlStatement::RefreshMaterializedView(_)) => {
+                Err(proto_error(
+                    "LogicalPlan serde is not yet implemented for RefreshMaterializedView",
+                ))
+            }
+            LogicalPlan::Ddl(DdlStatement::AnalyzeTable(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for AnalyzeTable",
+            )),
             LogicalPlan::Statement(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Statement",
             )),
@@ -1636,6 +1663,25 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Execute(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Execute",
             )),
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                input,
+            }) => {
+                let input =
+                    LogicalPlanNode::try_from_logical_plan(input, extension_codec)?;
+                Ok(LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::Sample(Box::new(
+                        protobuf::SampleNode {
+                            input: Some(Box::new(input)),
+                            method: protobuf::SampleMethod::from(*method).into(),
+                            fraction: *fraction,
+                            seed: *seed,
+                        },
+                    ))),
+                })
+            }
         }
     }
 }
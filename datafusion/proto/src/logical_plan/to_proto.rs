@@ -21,13 +21,13 @@
 
 use datafusion_common::{TableReference, UnnestOptions};
 use datafusion_expr::expr::{
-    self, Alias, Between, BinaryExpr, Cast, GroupingSet, InList, Like, Placeholder,
-    ScalarFunction, Unnest,
+    self, Alias, Between, BinaryExpr, Cast, GroupingSet, InList, Lambda, Like,
+    Placeholder, ScalarFunction, Unnest,
 };
 use datafusion_expr::{
     logical_plan::PlanType, logical_plan::StringifiedPlan, BuiltInWindowFunction, Expr,
-    JoinConstraint, JoinType, SortExpr, TryCast, WindowFrame, WindowFrameBound,
-    WindowFrameUnits, WindowFunctionDefinition,
+    JoinConstraint, JoinType, SampleMethod, SortExpr, TryCast, WindowFrame,
+    WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition,
 };
 
 use crate::protobuf::RecursionUnnestOption;
@@ -59,6 +59,7 @@ impl From<&UnnestOptions> for protobuf::UnnestOptions {
                     depth: r.depth as u32,
                 })
                 .collect(),
+            ordinality: opts.ordinality,
         }
     }
 }
@@ -550,6 +551,15 @@ pub fn serialize_expr(
                 expr_type: Some(ExprType::Unnest(expr)),
             }
         }
+        Expr::Lambda(Lambda { params, body }) => {
+            let expr = Box::new(protobuf::LambdaNode {
+                params: params.clone(),
+                body: Some(Box::new(serialize_expr(body.as_ref(), codec)?)),
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Lambda(expr)),
+            }
+        }
         Expr::InList(InList {
             expr,
             list,
@@ -686,6 +696,7 @@ impl From<JoinType> for protobuf::JoinType {
             JoinType::LeftAnti => protobuf::JoinType::Leftanti,
             JoinType::RightAnti => protobuf::JoinType::Rightanti,
             JoinType::LeftMark => protobuf::JoinType::Leftmark,
+            JoinType::AsOf => protobuf::JoinType::Asof,
         }
     }
 }
@@ -698,3 +709,12 @@ impl From<JoinConstraint> for protobuf::JoinConstraint {
         }
     }
 }
+
+impl From<SampleMethod> for protobuf::SampleMethod {
+    fn from(method: SampleMethod) -> Self {
+        match method {
+            SampleMethod::Bernoulli => protobuf::SampleMethod::Bernoulli,
+            SampleMethod::System => protobuf::SampleMethod::System,
+        }
+    }
+}
@@ -32,10 +32,14 @@ use datafusion::datasource::file_format::json::JsonSink;
 use datafusion::datasource::file_format::parquet::ParquetSink;
 use datafusion::datasource::listing::{FileRange, ListingTableUrl, PartitionedFile};
 use datafusion::datasource::object_store::ObjectStoreUrl;
-use datafusion::datasource::physical_plan::{FileScanConfig, FileSinkConfig};
+use datafusion::datasource::physical_plan::{
+    BlockSample, FileScanConfig, FileSinkConfig,
+};
 use datafusion::execution::FunctionRegistry;
 use datafusion::logical_expr::WindowFunctionDefinition;
-use datafusion::physical_expr::{LexOrdering, PhysicalSortExpr, ScalarFunctionExpr};
+use datafusion::physical_expr::{
+    LexOrdering, PhysicalLambda, PhysicalSortExpr, ScalarFunctionExpr,
+};
 use datafusion::physical_plan::expressions::{
     in_list, BinaryExpr, CaseExpr, CastExpr, Column, IsNotNullExpr, IsNullExpr, LikeExpr,
     Literal, NegativeExpr, NotExpr, TryCastExpr,
@@ -360,12 +364,30 @@ pub fn parse_physical_expr(
 
             let args = parse_physical_exprs(&e.args, registry, input_schema, codec)?;
 
-            Arc::new(ScalarFunctionExpr::new(
+            let expr = ScalarFunctionExpr::new(
                 e.name.as_str(),
                 scalar_fun_def,
                 args,
                 convert_required!(e.return_type)?,
-            ))
+            );
+            match &e.lambda {
+                Some(lambda) => {
+                    let schema: Schema = convert_required!(lambda.schema)?;
+                    let body = parse_required_physical_expr(
+                        lambda.body.as_deref(),
+                        registry,
+                        "body",
+                        &schema,
+                        codec,
+                    )?;
+                    Arc::new(expr.with_lambda(Arc::new(PhysicalLambda::new(
+                        lambda.params.clone(),
+                        Arc::new(schema),
+                        body,
+                    ))))
+                }
+                None => Arc::new(expr),
+            }
         }
         ExprType::LikeExpr(like_expr) => Arc::new(LikeExpr::new(
             like_expr.negated,
@@ -536,6 +558,10 @@ pub fn parse_protobuf_file_scan_config(
         limit: proto.limit.as_ref().map(|sl| sl.limit as usize),
         table_partition_cols,
         output_ordering,
+        sample: proto
+            .sample
+            .as_ref()
+            .map(|sample| BlockSample::new(sample.fraction, sample.seed)),
     })
 }
 
@@ -55,6 +55,7 @@ use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion::physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sample::SampleExec;
 use datafusion::physical_plan::sorts::sort::SortExec;
 use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use datafusion::physical_plan::union::{InterleaveExec, UnionExec};
@@ -1142,6 +1143,27 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                     into_required!(unnest.options)?,
                 )))
             }
+            PhysicalPlanType::Sample(sample) => {
+                let input = into_physical_plan(
+                    &sample.input,
+                    registry,
+                    runtime,
+                    extension_codec,
+                )?;
+                let method =
+                    protobuf::SampleMethod::try_from(sample.method).map_err(|_| {
+                        proto_error(format!(
+                            "Received a SampleExecNode message with unknown SampleMethod {}",
+                            sample.method
+                        ))
+                    })?;
+                Ok(Arc::new(SampleExec::try_new(
+                    input,
+                    method.into(),
+                    sample.fraction,
+                    sample.seed,
+                )?))
+            }
         }
     }
 
@@ -2025,6 +2047,24 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
             });
         }
 
+        if let Some(exec) = plan.downcast_ref::<SampleExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Sample(Box::new(
+                    protobuf::SampleExecNode {
+                        input: Some(Box::new(input)),
+                        method: protobuf::SampleMethod::from(exec.method()).into(),
+                        fraction: exec.fraction(),
+                        seed: exec.seed(),
+                    },
+                ))),
+            });
+        }
+
         let mut buf: Vec<u8> = vec![];
         match extension_codec.try_encode(Arc::clone(&plan_clone), &mut buf) {
             Ok(_) => {
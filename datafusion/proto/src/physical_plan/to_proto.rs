@@ -344,14 +344,29 @@ pub fn serialize_physical_expr(
         let mut buf = Vec::new();
         codec.try_encode_udf(expr.fun(), &mut buf)?;
         Ok(protobuf::PhysicalExprNode {
-            expr_type: Some(protobuf::physical_expr_node::ExprType::ScalarUdf(
+            expr_type: Some(protobuf::physical_expr_node::ExprType::ScalarUdf(Box::new(
                 protobuf::PhysicalScalarUdfNode {
                     name: expr.name().to_string(),
                     args: serialize_physical_exprs(expr.args(), codec)?,
                     fun_definition: (!buf.is_empty()).then_some(buf),
                     return_type: Some(expr.return_type().try_into()?),
+                    lambda: expr
+                        .lambda()
+                        .map(|lambda| {
+                            Ok::<_, DataFusionError>(Box::new(
+                                protobuf::PhysicalLambdaNode {
+                                    params: lambda.params().to_vec(),
+                                    schema: Some(lambda.schema().as_ref().try_into()?),
+                                    body: Some(Box::new(serialize_physical_expr(
+                                        lambda.body(),
+                                        codec,
+                                    )?)),
+                                },
+                            ))
+                        })
+                        .transpose()?,
                 },
-            )),
+            ))),
         })
     } else if let Some(expr) = expr.downcast_ref::<LikeExpr>() {
         Ok(protobuf::PhysicalExprNode {
@@ -530,6 +545,10 @@ pub fn serialize_file_scan_config(
                 physical_sort_expr_nodes: e,
             })
             .collect::<Vec<_>>(),
+        sample: conf.sample.map(|sample| protobuf::BlockSample {
+            fraction: sample.fraction,
+            seed: sample.seed,
+        }),
     })
 }
 
@@ -25,6 +25,7 @@ use std::vec;
 use arrow::array::RecordBatch;
 use arrow::csv::WriterBuilder;
 use arrow::datatypes::{Fields, TimeUnit};
+use datafusion::functions_nested::higher_order::array_transform_udf;
 use datafusion::physical_expr::aggregate::AggregateExprBuilder;
 use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
 use datafusion_expr::dml::InsertOp;
@@ -43,8 +44,8 @@ use datafusion::datasource::file_format::parquet::ParquetSink;
 use datafusion::datasource::listing::{ListingTableUrl, PartitionedFile};
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{
-    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileScanConfig,
-    FileSinkConfig, ParquetExec,
+    wrap_partition_type_in_dict, wrap_partition_value_in_dict, BlockSample,
+    FileScanConfig, FileSinkConfig, ParquetExec,
 };
 use datafusion::execution::FunctionRegistry;
 use datafusion::functions_aggregate::sum::sum_udaf;
@@ -52,7 +53,7 @@ use datafusion::logical_expr::{create_udf, JoinType, Operator, Volatility};
 use datafusion::physical_expr::expressions::Literal;
 use datafusion::physical_expr::window::SlidingAggregateWindowExpr;
 use datafusion::physical_expr::{
-    LexOrdering, LexOrderingRef, LexRequirement, PhysicalSortRequirement,
+    LexOrdering, LexOrderingRef, LexRequirement, PhysicalLambda, PhysicalSortRequirement,
     ScalarFunctionExpr,
 };
 use datafusion::physical_plan::aggregates::{
@@ -73,6 +74,7 @@ use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion::physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sample::SampleExec;
 use datafusion::physical_plan::sorts::sort::SortExec;
 use datafusion::physical_plan::union::{InterleaveExec, UnionExec};
 use datafusion::physical_plan::unnest::{ListUnnest, UnnestExec};
@@ -91,8 +93,8 @@ use datafusion_common::{
     internal_err, not_impl_err, DataFusionError, Result, UnnestOptions,
 };
 use datafusion_expr::{
-    Accumulator, AccumulatorFactoryFunction, AggregateUDF, ColumnarValue, ScalarUDF,
-    Signature, SimpleAggregateUDF, WindowFrame, WindowFrameBound,
+    Accumulator, AccumulatorFactoryFunction, AggregateUDF, ColumnarValue, SampleMethod,
+    ScalarUDF, Signature, SimpleAggregateUDF, WindowFrame, WindowFrameBound,
 };
 use datafusion_functions_aggregate::average::avg_udaf;
 use datafusion_functions_aggregate::nth_value::nth_value_udaf;
@@ -682,6 +684,7 @@ fn roundtrip_parquet_exec_with_pruning_predicate() -> Result<()> {
         limit: None,
         table_partition_cols: vec![],
         output_ordering: vec![],
+        sample: None,
     };
 
     let predicate = Arc::new(BinaryExpr::new(
@@ -717,11 +720,27 @@ async fn roundtrip_parquet_exec_with_table_partition_cols() -> Result<()> {
             false,
         )],
         output_ordering: vec![],
+        sample: None,
     };
 
     roundtrip_test(ParquetExec::builder(scan_config).build_arc())
 }
 
+#[test]
+fn roundtrip_parquet_exec_with_block_sample() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("col", DataType::Utf8, false)]));
+    let scan_config =
+        FileScanConfig::new(ObjectStoreUrl::local_filesystem(), Arc::clone(&schema))
+            .with_file(PartitionedFile::new(
+                "/path/to/file.parquet".to_string(),
+                1024,
+            ))
+            .with_statistics(Statistics::new_unknown(&schema))
+            .with_sample(Some(BlockSample::new(0.25, Some(42))));
+
+    roundtrip_test(ParquetExec::builder(scan_config).build_arc())
+}
+
 #[test]
 fn roundtrip_parquet_exec_with_custom_predicate_expr() -> Result<()> {
     let scan_config = FileScanConfig {
@@ -746,6 +765,7 @@ fn roundtrip_parquet_exec_with_custom_predicate_expr() -> Result<()> {
         limit: None,
         table_partition_cols: vec![],
         output_ordering: vec![],
+        sample: None,
     };
 
     #[derive(Debug, Hash, Clone)]
@@ -904,6 +924,39 @@ fn roundtrip_scalar_udf() -> Result<()> {
     roundtrip_test_with_context(Arc::new(project), &ctx)
 }
 
+#[test]
+fn roundtrip_scalar_udf_with_lambda() -> Result<()> {
+    let field_a =
+        Field::new_list("a", Field::new_list_field(DataType::Int64, true), true);
+    let schema = Arc::new(Schema::new(vec![field_a]));
+    let input = Arc::new(EmptyExec::new(schema.clone()));
+
+    let lambda_schema =
+        Arc::new(Schema::new(vec![Field::new("x", DataType::Int64, true)]));
+    let body = binary(
+        col("x", &lambda_schema)?,
+        Operator::Plus,
+        lit(1i64),
+        &lambda_schema,
+    )?;
+    let expr = ScalarFunctionExpr::new(
+        "array_transform",
+        array_transform_udf(),
+        vec![col("a", &schema)?],
+        DataType::new_list(DataType::Int64, true),
+    )
+    .with_lambda(Arc::new(PhysicalLambda::new(
+        vec!["x".to_string()],
+        lambda_schema,
+        body,
+    )));
+
+    let project =
+        ProjectionExec::try_new(vec![(Arc::new(expr), "a".to_string())], input)?;
+
+    roundtrip_test(Arc::new(project))
+}
+
 #[derive(Debug)]
 struct UDFExtensionCodec;
 
@@ -1407,3 +1460,11 @@ fn roundtrip_unnest() -> Result<()> {
     );
     roundtrip_test(Arc::new(unnest))
 }
+
+#[test]
+fn roundtrip_sample() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let input = Arc::new(EmptyExec::new(schema));
+    let sample = SampleExec::try_new(input, SampleMethod::System, 0.25, Some(42))?;
+    roundtrip_test(Arc::new(sample))
+}
@@ -20,22 +20,25 @@ use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use arrow_schema::DataType;
 use datafusion_common::{
     internal_datafusion_err, internal_err, not_impl_err, plan_datafusion_err, plan_err,
-    DFSchema, Dependency, Result,
+    DFSchema, Dependency, Result, ScalarValue,
 };
-use datafusion_expr::expr::WildcardOptions;
+use datafusion_expr::expr::{Lambda, WildcardOptions};
 use datafusion_expr::planner::PlannerResult;
 use datafusion_expr::{
-    expr, Expr, ExprFunctionExt, ExprSchemable, WindowFrame, WindowFunctionDefinition,
+    expr, Expr, ExprFunctionExt, ExprSchemable, ScalarUDF, WindowFrame,
+    WindowFunctionDefinition,
 };
 use datafusion_expr::{
     expr::{ScalarFunction, Unnest},
     BuiltInWindowFunction,
 };
 use sqlparser::ast::{
-    DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction, FunctionArg,
-    FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList, FunctionArguments,
-    NullTreatment, ObjectName, OrderByExpr, WindowType,
+    BinaryOperator, DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction,
+    FunctionArg, FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList,
+    FunctionArguments, Ident, LambdaFunction, NullTreatment, ObjectName,
+    OneOrManyWithParens, OrderByExpr, WindowType,
 };
+use std::collections::HashSet;
 use strum::IntoEnumIterator;
 
 /// Suggest a valid function based on an invalid input function name
@@ -89,6 +92,8 @@ struct FunctionArgs {
     args: Vec<FunctionArg>,
     /// ORDER BY clause, if any
     order_by: Vec<OrderByExpr>,
+    /// WITHIN GROUP clause, if any
+    within_group: Vec<OrderByExpr>,
     /// OVER clause, if any
     over: Option<WindowType>,
     /// FILTER clause, if any
@@ -117,6 +122,7 @@ impl FunctionArgs {
                 name,
                 args: vec![],
                 order_by: vec![],
+                within_group,
                 over,
                 filter,
                 null_treatment,
@@ -177,16 +183,18 @@ impl FunctionArgs {
             }
         }
 
-        if !within_group.is_empty() {
-            return not_impl_err!("WITHIN GROUP is not supported yet: {within_group:?}");
-        }
-
         let order_by = order_by.unwrap_or_default();
+        if !order_by.is_empty() && !within_group.is_empty() {
+            return plan_err!(
+                "Calling {name}: ORDER BY and WITHIN GROUP clauses can't be used together"
+            );
+        }
 
         Ok(Self {
             name,
             args,
             order_by,
+            within_group,
             over,
             filter,
             null_treatment,
@@ -207,6 +215,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             name,
             args,
             order_by,
+            within_group,
             over,
             filter,
             null_treatment,
@@ -226,6 +235,23 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             crate::utils::normalize_ident(name.0[0].clone())
         };
 
+        if !within_group.is_empty() {
+            if is_function_window {
+                return not_impl_err!(
+                    "WITHIN GROUP is not supported for window functions"
+                );
+            }
+            if !self
+                .context_provider
+                .get_aggregate_meta(&name)
+                .is_some_and(|fm| fm.is_ordered_set_aggregate())
+            {
+                return plan_err!(
+                    "WITHIN GROUP is only supported for ordered-set aggregate functions, got {name}"
+                );
+            }
+        }
+
         if name.eq("make_map") {
             let mut fn_args =
                 self.function_args_to_expr(args.clone(), schema, planner_context)?;
@@ -239,6 +265,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         // User-defined function (UDF) should have precedence
         if let Some(fm) = self.context_provider.get_function_meta(&name) {
+            if let Some(args) =
+                self.lambda_function_args_to_expr(&fm, &args, schema, planner_context)?
+            {
+                return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(fm, args)));
+            }
             let args = self.function_args_to_expr(args, schema, planner_context)?;
             return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(fm, args)));
         }
@@ -328,15 +359,26 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         } else {
             // User defined aggregate functions (UDAF) have precedence in case it has the same name as a scalar built-in function
             if let Some(fm) = self.context_provider.get_aggregate_meta(&name) {
+                let is_within_group = !within_group.is_empty();
                 let order_by = self.order_by_to_sort_expr(
-                    order_by,
+                    if is_within_group {
+                        within_group
+                    } else {
+                        order_by
+                    },
                     schema,
                     planner_context,
                     true,
                     None,
                 )?;
+                let mut args =
+                    self.function_args_to_expr(args, schema, planner_context)?;
+                // The expressions of the WITHIN GROUP clause are the first arguments
+                // of ordered-set aggregate functions
+                if is_within_group {
+                    args.splice(0..0, order_by.iter().map(|sort| sort.expr.clone()));
+                }
                 let order_by = (!order_by.is_empty()).then_some(order_by);
-                let args = self.function_args_to_expr(args, schema, planner_context)?;
                 let filter: Option<Box<Expr>> = filter
                     .map(|e| self.sql_expr_to_logical_expr(*e, schema, planner_context))
                     .transpose()?
@@ -459,6 +501,69 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .collect::<Result<Vec<Expr>>>()
     }
 
+    /// Plans the arguments of a higher-order function whose last argument is a
+    /// lambda such as `x -> x + 1`, returning `None` if it has no such argument.
+    ///
+    /// The types of the lambda parameters are given by the function from the
+    /// types of the arguments before it, and the body may only refer to them.
+    fn lambda_function_args_to_expr(
+        &self,
+        func: &ScalarUDF,
+        args: &[FunctionArg],
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Option<Vec<Expr>>> {
+        let Some((
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(last))
+            | FunctionArg::Named {
+                arg: FunctionArgExpr::Expr(last),
+                ..
+            },
+            other_args,
+        )) = args.split_last()
+        else {
+            return Ok(None);
+        };
+        let is_lambda = matches!(last, SQLExpr::Lambda(_));
+        let Some((params, body)) = sql_lambda(last.clone()) else {
+            return Ok(None);
+        };
+
+        let mut exprs =
+            self.function_args_to_expr(other_args.to_vec(), schema, planner_context)?;
+        let arg_types = exprs
+            .iter()
+            .map(|e| e.get_type(schema))
+            .collect::<Result<Vec<_>>>()?;
+        let param_types = match func.lambda_parameters(&arg_types) {
+            Ok(param_types) => param_types,
+            // In dialects without lambdas `x -> y` may also be the `->`
+            // operator, which is planned as such if the function accepts it
+            Err(e) if !is_lambda => {
+                return self
+                    .function_args_to_expr(args.to_vec(), schema, planner_context)
+                    .map(Some)
+                    .map_err(|_| e);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let params = params
+            .into_iter()
+            .map(|ident| self.ident_normalizer.normalize(ident))
+            .collect::<Vec<_>>();
+        if params.iter().collect::<HashSet<_>>().len() != params.len() {
+            return plan_err!("Lambda parameters must be unique, got {params:?}");
+        }
+        // The body is planned once the schema of the parameters is known
+        let mut lambda = Lambda::new(params, Expr::Literal(ScalarValue::Null));
+        let params_schema = lambda.params_schema(&param_types)?;
+        *lambda.body =
+            self.sql_expr_to_logical_expr(body, &params_schema, planner_context)?;
+        exprs.push(Expr::Lambda(lambda));
+        Ok(Some(exprs))
+    }
+
     pub(crate) fn check_unnest_arg(arg: &Expr, schema: &DFSchema) -> Result<()> {
         // Check argument type, array types are supported
         match arg.get_type(schema)? {
@@ -475,3 +580,55 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 }
+
+/// Splits `expr` into the parameters and body of a lambda, if it is one.
+///
+/// Dialects without lambdas parse `x -> x > 1 AND x < 5` as the `->` operator,
+/// which binds tighter than `AND` and `OR`, so these are moved into the body.
+fn sql_lambda(expr: SQLExpr) -> Option<(Vec<Ident>, SQLExpr)> {
+    match expr {
+        SQLExpr::Lambda(LambdaFunction { params, body }) => {
+            let params = match params {
+                OneOrManyWithParens::One(param) => vec![param],
+                OneOrManyWithParens::Many(params) => params,
+            };
+            Some((params, *body))
+        }
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::Arrow,
+            right,
+        } => {
+            let params = match *left {
+                SQLExpr::Identifier(param) => vec![param],
+                SQLExpr::Nested(param) => match *param {
+                    SQLExpr::Identifier(param) => vec![param],
+                    _ => return None,
+                },
+                SQLExpr::Tuple(params) => params
+                    .into_iter()
+                    .map(|param| match param {
+                        SQLExpr::Identifier(param) => Some(param),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+                _ => return None,
+            };
+            Some((params, *right))
+        }
+        SQLExpr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Xor),
+            right,
+        } => {
+            let (params, body) = sql_lambda(*left)?;
+            let body = SQLExpr::BinaryOp {
+                left: Box::new(body),
+                op,
+                right,
+            };
+            Some((params, body))
+        }
+        _ => None,
+    }
+}
@@ -216,6 +216,32 @@ impl fmt::Display for CreateExternalTable {
     }
 }
 
+/// DataFusion extension statement for `REFRESH MATERIALIZED VIEW`
+///
+/// # Syntax:
+///
+/// ```text
+/// REFRESH MATERIALIZED VIEW <view_name> [ FULL | INCREMENTAL ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshMaterializedViewStatement {
+    /// View name
+    pub name: ObjectName,
+    /// Whether to only add the rows computed from the rows appended to the
+    /// sources of the view since its last refresh
+    pub incremental: bool,
+}
+
+impl fmt::Display for RefreshMaterializedViewStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REFRESH MATERIALIZED VIEW {}", self.name)?;
+        if self.incremental {
+            write!(f, " INCREMENTAL")?;
+        }
+        Ok(())
+    }
+}
+
 /// DataFusion SQL Statement.
 ///
 /// This can either be a [`Statement`] from [`sqlparser`] from a
@@ -233,6 +259,8 @@ pub enum Statement {
     CopyTo(CopyToStatement),
     /// EXPLAIN for extensions
     Explain(ExplainStatement),
+    /// Extension: `REFRESH MATERIALIZED VIEW`
+    RefreshMaterializedView(RefreshMaterializedViewStatement),
 }
 
 impl fmt::Display for Statement {
@@ -242,6 +270,7 @@ impl fmt::Display for Statement {
             Statement::CreateExternalTable(stmt) => write!(f, "{stmt}"),
             Statement::CopyTo(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
+            Statement::RefreshMaterializedView(stmt) => write!(f, "{stmt}"),
         }
     }
 }
@@ -360,6 +389,21 @@ impl<'a> DFParser<'a> {
                         self.parser.next_token(); // EXPLAIN
                         self.parse_explain()
                     }
+                    Keyword::DROP
+                        if matches!(
+                            self.parser.peek_nth_token(1).token,
+                            Token::Word(w) if w.keyword == Keyword::MATERIALIZED
+                        ) =>
+                    {
+                        self.parser.next_token(); // DROP
+                        self.parser.next_token(); // MATERIALIZED
+                                                  // materialized views are dropped like any other view
+                        Ok(Statement::Statement(Box::from(self.parser.parse_drop()?)))
+                    }
+                    Keyword::NoKeyword if w.value.eq_ignore_ascii_case("REFRESH") => {
+                        self.parser.next_token(); // REFRESH
+                        self.parse_refresh()
+                    }
                     _ => {
                         // use sqlparser-rs parser
                         Ok(Statement::Statement(Box::from(
@@ -539,6 +583,29 @@ impl<'a> DFParser<'a> {
         }))
     }
 
+    /// Parse a SQL `REFRESH MATERIALIZED VIEW` statement
+    pub fn parse_refresh(&mut self) -> Result<Statement, ParserError> {
+        self.parser
+            .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+        let name = self.parser.parse_object_name(true)?;
+
+        let incremental = match self.parser.peek_token().token {
+            Token::Word(w) if w.keyword == Keyword::FULL => {
+                self.parser.next_token();
+                false
+            }
+            Token::Word(w) if w.value.eq_ignore_ascii_case("INCREMENTAL") => {
+                self.parser.next_token();
+                true
+            }
+            _ => false,
+        };
+
+        Ok(Statement::RefreshMaterializedView(
+            RefreshMaterializedViewStatement { name, incremental },
+        ))
+    }
+
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
@@ -1392,6 +1459,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn refresh_materialized_view() -> Result<(), ParserError> {
+        let expected = |incremental| {
+            Statement::RefreshMaterializedView(RefreshMaterializedViewStatement {
+                name: ObjectName(vec![Ident::new("s"), Ident::new("v")]),
+                incremental,
+            })
+        };
+        assert_eq!(
+            verified_stmt("REFRESH MATERIALIZED VIEW s.v"),
+            expected(false)
+        );
+        assert_eq!(
+            one_statement_parses_to(
+                "refresh materialized view s.v full",
+                "REFRESH MATERIALIZED VIEW s.v"
+            ),
+            expected(false)
+        );
+        assert_eq!(
+            verified_stmt("REFRESH MATERIALIZED VIEW s.v INCREMENTAL"),
+            expected(true)
+        );
+
+        expect_parse_error(
+            "REFRESH MATERIALIZED v",
+            "sql parser error: Expected: VIEW, found: v",
+        );
+        expect_parse_error(
+            "REFRESH MATERIALIZED VIEW v PARTIAL",
+            "sql parser error: Expected end of statement, found: PARTIAL",
+        );
+
+        // dropped like any other view
+        let sql = "DROP MATERIALIZED VIEW IF EXISTS v";
+        let mut statements = DFParser::parse_sql(sql)?;
+        assert_eq!(statements.len(), 1);
+        assert_eq!(
+            statements.pop_front().unwrap().to_string(),
+            "DROP VIEW IF EXISTS v"
+        );
+        Ok(())
+    }
+
     #[test]
     fn explain_copy_to_table_to_table() -> Result<(), ParserError> {
         let cases = vec![
@@ -16,9 +16,13 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{not_impl_err, Column, Result};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableFactor, TableWithJoins};
+use datafusion_common::{not_impl_err, plan_err, Column, Result};
+use datafusion_expr::expr::BinaryExpr;
+use datafusion_expr::utils::split_conjunction_owned;
+use datafusion_expr::{Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Operator};
+use sqlparser::ast::{
+    Expr as SQLExpr, Join, JoinConstraint, JoinOperator, TableFactor, TableWithJoins,
+};
 use std::collections::HashSet;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -94,6 +98,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.parse_join(left, right, constraint, JoinType::Full, planner_context)
             }
             JoinOperator::CrossJoin => self.parse_cross_join(left, right),
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => self.parse_asof_join(
+                left,
+                right,
+                match_condition,
+                constraint,
+                planner_context,
+            ),
             other => not_impl_err!("Unsupported JOIN operator {other:?}"),
         }
     }
@@ -106,6 +120,65 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         LogicalPlanBuilder::from(left).cross_join(right)?.build()
     }
 
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        match_condition: SQLExpr,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let join_schema = left.schema().join(right.schema())?;
+        let match_condition =
+            self.sql_to_expr(match_condition, &join_schema, planner_context)?;
+        let (left_keys, right_keys): (Vec<Expr>, Vec<Expr>) = match constraint {
+            JoinConstraint::On(sql_expr) => {
+                let expr = self.sql_to_expr(sql_expr, &join_schema, planner_context)?;
+                split_conjunction_owned(expr)
+                    .into_iter()
+                    .map(|expr| match expr {
+                        Expr::BinaryExpr(BinaryExpr {
+                            left,
+                            op: Operator::Eq,
+                            right,
+                        }) => Ok((*left, *right)),
+                        other => plan_err!(
+                            "ASOF JOIN ON condition only supports equalities, got {other}"
+                        ),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip()
+            }
+            JoinConstraint::Using(idents) => idents
+                .into_iter()
+                .map(|ident| {
+                    let name = self.ident_normalizer.normalize(ident);
+                    let left_key = Column::from_name(name.clone())
+                        .normalize_with_schemas_and_ambiguity_check(
+                            &[&[left.schema()]],
+                            &[],
+                        )?;
+                    let right_key = Column::from_name(name)
+                        .normalize_with_schemas_and_ambiguity_check(
+                            &[&[right.schema()]],
+                            &[],
+                        )?;
+                    Ok((Expr::Column(left_key), Expr::Column(right_key)))
+                })
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .unzip(),
+            JoinConstraint::None => (vec![], vec![]),
+            JoinConstraint::Natural => {
+                return not_impl_err!("NATURAL is not supported for ASOF JOIN")
+            }
+        };
+        LogicalPlanBuilder::from(left)
+            .join_asof(right, (left_keys, right_keys), match_condition)?
+            .build()
+    }
+
     fn parse_join(
         &self,
         left: LogicalPlan,
@@ -19,14 +19,22 @@ use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
+use arrow_schema::DataType;
 use datafusion_common::tree_node::{Transformed, TreeNode};
-use datafusion_common::{not_impl_err, plan_err, DFSchema, Result, TableReference};
-use datafusion_expr::builder::subquery_alias;
-use datafusion_expr::{expr::Unnest, Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion_common::{
+    internal_err, not_impl_err, plan_err, Column, DFSchema, Result, TableReference,
+    UnnestOptions,
+};
+use datafusion_expr::builder::{get_unnested_ordinality_column, subquery_alias};
+use datafusion_expr::conditional_expressions::CaseBuilder;
+use datafusion_expr::{
+    expr::Unnest, lit, Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder,
+};
 use datafusion_expr::{Subquery, SubqueryAlias};
 use sqlparser::ast::{FunctionArg, FunctionArgExpr, TableFactor};
 
 mod join;
+mod pivot;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Create a `LogicalPlan` that scans the named relation
@@ -41,33 +49,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             } => {
                 if let Some(func_args) = args {
                     let tbl_func_name = name.0.first().unwrap().value.to_string();
-                    let args = func_args
-                        .args
-                        .into_iter()
-                        .flat_map(|arg| {
-                            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg
-                            {
-                                self.sql_expr_to_logical_expr(
-                                    expr,
-                                    &DFSchema::empty(),
-                                    planner_context,
-                                )
-                            } else {
-                                plan_err!("Unsupported function argument type: {:?}", arg)
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                    let provider = self
-                        .context_provider
-                        .get_table_function_source(&tbl_func_name, args)?;
-                    let plan = LogicalPlanBuilder::scan(
-                        TableReference::Bare {
-                            table: "tmp_table".into(),
-                        },
-                        provider,
-                        None,
-                    )?
-                    .build()?;
+                    let plan = self.table_function_to_plan(
+                        &tbl_func_name,
+                        func_args.args,
+                        planner_context,
+                    )?;
                     (plan, alias)
                 } else {
                     // Normalize name and alias
@@ -90,6 +76,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     )
                 }
             }
+            TableFactor::Function {
+                name, args, alias, ..
+            } => {
+                let tbl_func_name = name.0.first().unwrap().value.to_string();
+                let plan =
+                    self.table_function_to_plan(&tbl_func_name, args, planner_context)?;
+                (plan, alias)
+            }
             TableFactor::Derived {
                 subquery, alias, ..
             } => {
@@ -106,14 +100,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             TableFactor::UNNEST {
                 alias,
                 array_exprs,
-                with_offset: false,
-                with_offset_alias: None,
+                with_offset,
+                with_offset_alias,
                 with_ordinality,
             } => {
-                if with_ordinality {
-                    return not_impl_err!("UNNEST with ordinality is not supported yet");
-                }
-
                 // Unnest table factor has empty input
                 let schema = DFSchema::empty();
                 let input = LogicalPlanBuilder::empty(true).build()?;
@@ -134,13 +124,51 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 if unnest_exprs.is_empty() {
                     return plan_err!("UNNEST must have at least one argument");
                 }
-                let logical_plan = self.try_process_unnest(input, unnest_exprs)?;
+                let logical_plan = if with_ordinality || with_offset {
+                    let offset_name = with_offset.then(|| {
+                        with_offset_alias
+                            .map(|ident| self.ident_normalizer.normalize(ident))
+                            .unwrap_or_else(|| "offset".to_string())
+                    });
+                    self.unnest_with_ordinality_to_plan(
+                        input,
+                        unnest_exprs,
+                        with_ordinality,
+                        offset_name,
+                    )?
+                } else {
+                    self.try_process_unnest(input, unnest_exprs)?
+                };
                 (logical_plan, alias)
             }
-            TableFactor::UNNEST { .. } => {
-                return not_impl_err!(
-                    "UNNEST table factor with offset is not supported yet"
-                );
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan = self.pivot_to_plan(
+                    input,
+                    aggregate_functions,
+                    value_column,
+                    value_source,
+                    default_on_null,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
+            TableFactor::Unpivot {
+                table,
+                value,
+                name,
+                columns,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                (self.unpivot_to_plan(input, value, name, columns)?, alias)
             }
             // @todo Support TableFactory::TableFunction?
             _ => {
@@ -158,6 +186,115 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
+    /// Create a `LogicalPlan` that scans the table returned by the table
+    /// function `name` for `args`
+    fn table_function_to_plan(
+        &self,
+        name: &str,
+        args: Vec<FunctionArg>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let args = args
+            .into_iter()
+            .flat_map(|arg| {
+                if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg {
+                    self.sql_expr_to_logical_expr(
+                        expr,
+                        &DFSchema::empty(),
+                        planner_context,
+                    )
+                } else {
+                    plan_err!("Unsupported function argument type: {:?}", arg)
+                }
+            })
+            .collect::<Vec<_>>();
+        // Table functions are called while planning, so the arguments of a
+        // lateral table function can't refer to the preceding relations
+        if args.iter().any(|arg| arg.contains_outer()) {
+            return not_impl_err!(
+                "Arguments of table function {name} referring to preceding FROM \
+                 items, consider a LATERAL subquery instead"
+            );
+        }
+        let provider = self
+            .context_provider
+            .get_table_function_source(name, args)?;
+        LogicalPlanBuilder::scan(
+            TableReference::Bare {
+                table: "tmp_table".into(),
+            },
+            provider,
+            None,
+        )?
+        .build()
+    }
+
+    /// Create a `LogicalPlan` for an `UNNEST` table factor `WITH ORDINALITY`
+    /// and/or `WITH OFFSET`, which appends the 1-based position (`ordinality`)
+    /// and/or the 0-based position (`offset_name`) of each output row to the
+    /// unnested columns
+    fn unnest_with_ordinality_to_plan(
+        &self,
+        input: LogicalPlan,
+        unnest_exprs: Vec<Expr>,
+        with_ordinality: bool,
+        offset_name: Option<String>,
+    ) -> Result<LogicalPlan> {
+        let mut inner_projection_exprs = vec![];
+        let mut outer_projection_exprs = vec![];
+        let mut columns_to_unnest = vec![];
+        let mut ordinality_columns = vec![];
+        for unnest_expr in unnest_exprs {
+            let Expr::Unnest(Unnest { expr: arg }) = &unnest_expr else {
+                return internal_err!("Expected an unnest expression, got {unnest_expr}");
+            };
+            if let DataType::Struct(_) = arg.get_type(input.schema())? {
+                return not_impl_err!(
+                    "UNNEST of a struct with ordinality or offset is not supported"
+                );
+            }
+            let placeholder_name = format!("unnest_placeholder({})", arg.schema_name());
+            inner_projection_exprs.push(arg.as_ref().clone().alias(&placeholder_name));
+            outer_projection_exprs.push(
+                Expr::Column(Column::from_name(&placeholder_name))
+                    .alias(unnest_expr.schema_name().to_string()),
+            );
+            ordinality_columns.push(Expr::Column(
+                get_unnested_ordinality_column(&placeholder_name).0,
+            ));
+            columns_to_unnest.push(Column::from_name(placeholder_name));
+        }
+
+        // Shorter lists are padded with nulls, which have no position, but
+        // the longest list always has an element at every output row
+        let ordinality = match ordinality_columns.as_slice() {
+            [column] => column.clone(),
+            columns => {
+                let when_exprs = columns
+                    .iter()
+                    .map(|column| column.clone().is_not_null())
+                    .collect();
+                CaseBuilder::new(None, when_exprs, columns.to_vec(), None).end()?
+            }
+        };
+        if with_ordinality {
+            outer_projection_exprs.push(ordinality.clone().alias("ordinality"));
+        }
+        if let Some(offset_name) = offset_name {
+            outer_projection_exprs.push((ordinality - lit(1_i64)).alias(offset_name));
+        }
+
+        // Set preserve_nulls to false to ensure compatibility with DuckDB and PostgreSQL
+        let options = UnnestOptions::new()
+            .with_preserve_nulls(false)
+            .with_ordinality(true);
+        LogicalPlanBuilder::from(input)
+            .project(inner_projection_exprs)?
+            .unnest_columns_with_options(columns_to_unnest, options)?
+            .project(outer_projection_exprs)?
+            .build()
+    }
+
     pub(crate) fn create_relation_subquery(
         &self,
         subquery: TableFactor,
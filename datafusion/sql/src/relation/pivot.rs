@@ -0,0 +1,106 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{not_impl_err, plan_err, Column, DFSchema, Result};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder};
+use sqlparser::ast::{Expr as SQLExpr, ExprWithAlias, Ident, PivotValueSource};
+
+impl<'a, S: ContextProvider> SqlToRel<'a, S> {
+    /// Plan `input PIVOT(aggregate_functions FOR value_column IN (value_source))`
+    pub(super) fn pivot_to_plan(
+        &self,
+        input: LogicalPlan,
+        aggregate_functions: Vec<ExprWithAlias>,
+        value_column: Vec<Ident>,
+        value_source: PivotValueSource,
+        default_on_null: Option<SQLExpr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let values = match value_source {
+            PivotValueSource::List(values) => values,
+            PivotValueSource::Any(_) | PivotValueSource::Subquery(_) => {
+                return not_impl_err!(
+                    "PIVOT only supports a list of values, got {value_source}"
+                );
+            }
+        };
+
+        let schema = Arc::clone(input.schema());
+        let value_column = match value_column.len() {
+            1 => SQLExpr::Identifier(value_column.into_iter().next().unwrap()),
+            _ => SQLExpr::CompoundIdentifier(value_column),
+        };
+        let value_column =
+            match self.sql_to_expr(value_column, &schema, planner_context)? {
+                Expr::Column(column) => column,
+                expr => return plan_err!("PIVOT expects a column, got {expr}"),
+            };
+        let aggr_expr =
+            self.exprs_with_alias(aggregate_functions, &schema, planner_context)?;
+        let values = self.exprs_with_alias(values, &schema, planner_context)?;
+        let default_on_null = default_on_null
+            .map(|expr| self.sql_to_expr(expr, &schema, planner_context))
+            .transpose()?;
+
+        LogicalPlanBuilder::from(input)
+            .pivot(aggr_expr, value_column, values, default_on_null)?
+            .build()
+    }
+
+    /// Plan `input UNPIVOT(value FOR name IN (columns))`
+    pub(super) fn unpivot_to_plan(
+        &self,
+        input: LogicalPlan,
+        value: Ident,
+        name: Ident,
+        columns: Vec<Ident>,
+    ) -> Result<LogicalPlan> {
+        let columns = columns
+            .into_iter()
+            .map(|ident| Column::from_name(self.ident_normalizer.normalize(ident)))
+            .collect::<Vec<_>>();
+        LogicalPlanBuilder::from(input)
+            .unpivot(
+                self.ident_normalizer.normalize(value),
+                self.ident_normalizer.normalize(name),
+                columns,
+                false,
+            )?
+            .build()
+    }
+
+    fn exprs_with_alias(
+        &self,
+        exprs: Vec<ExprWithAlias>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Vec<Expr>> {
+        exprs
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let expr = self.sql_to_expr(expr, schema, planner_context)?;
+                Ok(match alias {
+                    Some(alias) => expr.alias(self.ident_normalizer.normalize(alias)),
+                    None => expr,
+                })
+            })
+            .collect()
+    }
+}
@@ -60,9 +60,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         if !select.lateral_views.is_empty() {
             return not_impl_err!("LATERAL VIEWS");
         }
-        if select.qualify.is_some() {
-            return not_impl_err!("QUALIFY");
-        }
         if select.top.is_some() {
             return not_impl_err!("TOP");
         }
@@ -138,9 +135,28 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
             .transpose()?;
 
+        // Optionally the QUALIFY expression, which may refer to aliased
+        // columns of the projection like the HAVING expression.
+        let qualify_expr_opt = select
+            .qualify
+            .map::<Result<Expr>, _>(|qualify_expr| {
+                let qualify_expr = self.sql_expr_to_logical_expr(
+                    qualify_expr,
+                    &combined_schema,
+                    planner_context,
+                )?;
+                let qualify_expr = resolve_aliases_to_exprs(qualify_expr, &alias_map)?;
+                normalize_col(qualify_expr, &projected_plan)
+            })
+            .transpose()?;
+
         // The outer expressions we will search through for aggregates.
-        // Aggregates may be sourced from the SELECT list or from the HAVING expression.
-        let aggr_expr_haystack = select_exprs.iter().chain(having_expr_opt.iter());
+        // Aggregates may be sourced from the SELECT list, the HAVING expression
+        // or the QUALIFY expression.
+        let aggr_expr_haystack = select_exprs
+            .iter()
+            .chain(having_expr_opt.iter())
+            .chain(qualify_expr_opt.iter());
         // All of the aggregate expressions (deduplicated).
         let aggr_exprs = find_aggregate_exprs(aggr_expr_haystack);
 
@@ -189,21 +205,29 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         };
 
         // Process group by, aggregation or having
-        let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
-            .is_empty()
-            || !aggr_exprs.is_empty()
-        {
+        let AggregatePlanResult {
+            plan,
+            select_exprs: mut select_exprs_post_aggr,
+            having_expr: having_expr_post_aggr,
+            qualify_expr: qualify_expr_post_aggr,
+        } = if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
             self.aggregate(
                 &base_plan,
                 &select_exprs,
                 having_expr_opt.as_ref(),
+                qualify_expr_opt.as_ref(),
                 &group_by_exprs,
                 &aggr_exprs,
             )?
         } else {
             match having_expr_opt {
                 Some(having_expr) => return plan_err!("HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"),
-                None => (base_plan.clone(), select_exprs.clone(), having_expr_opt)
+                None => AggregatePlanResult {
+                    plan: base_plan.clone(),
+                    select_exprs: select_exprs.clone(),
+                    having_expr: having_expr_opt,
+                    qualify_expr: qualify_expr_opt,
+                },
             }
         };
 
@@ -215,8 +239,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
-        // Process window function
-        let window_func_exprs = find_window_exprs(&select_exprs_post_aggr);
+        // Process window function. The window functions of the QUALIFY
+        // expression are computed too, even if they are not projected.
+        let window_func_exprs = find_window_exprs(
+            &select_exprs_post_aggr
+                .iter()
+                .chain(qualify_expr_post_aggr.iter())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
 
         let plan = if window_func_exprs.is_empty() {
             plan
@@ -232,6 +263,21 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
+        // Process QUALIFY clause, which filters the results of the window functions
+        let plan = if let Some(qualify_expr) = qualify_expr_post_aggr {
+            if window_func_exprs.is_empty() {
+                return plan_err!(
+                    "QUALIFY clause requires a window function in the SELECT list or the QUALIFY clause"
+                );
+            }
+            let qualify_expr = rebase_expr(&qualify_expr, &window_func_exprs, &plan)?;
+            LogicalPlanBuilder::from(plan)
+                .filter(qualify_expr)?
+                .build()?
+        } else {
+            plan
+        };
+
         // Try processing unnest expression or do the final projection
         let plan = self.try_process_unnest(plan, select_exprs_post_aggr)?;
 
@@ -741,9 +787,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         input: &LogicalPlan,
         select_exprs: &[Expr],
         having_expr_opt: Option<&Expr>,
+        qualify_expr_opt: Option<&Expr>,
         group_by_exprs: &[Expr],
         aggr_exprs: &[Expr],
-    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>)> {
+    ) -> Result<AggregatePlanResult> {
         // create the aggregate plan
         let plan = LogicalPlanBuilder::from(input.clone())
             .aggregate(group_by_exprs.to_vec(), aggr_exprs.to_vec())?
@@ -760,7 +807,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         // the aggregate plan.
 
         // combine the original grouping and aggregate expressions into one list (note that
-        // we do not add the "having" expression since that is not part of the projection)
+        // we do not add the "having" and "qualify" expressions since they are not part of
+        // the projection)
         let mut aggr_projection_exprs = vec![];
         for expr in group_by_exprs {
             match expr {
@@ -824,10 +872,41 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             None
         };
 
-        Ok((plan, select_exprs_post_aggr, having_expr_post_aggr))
+        // Rewrite the QUALIFY expression to use the columns produced by the
+        // aggregation.
+        let qualify_expr_post_aggr = if let Some(qualify_expr) = qualify_expr_opt {
+            let qualify_expr_post_aggr =
+                rebase_expr(qualify_expr, &aggr_projection_exprs, input)?;
+
+            check_columns_satisfy_exprs(
+                &column_exprs_post_aggr,
+                &[qualify_expr_post_aggr.clone()],
+                "QUALIFY clause references non-aggregate values",
+            )?;
+
+            Some(qualify_expr_post_aggr)
+        } else {
+            None
+        };
+
+        Ok(AggregatePlanResult {
+            plan,
+            select_exprs: select_exprs_post_aggr,
+            having_expr: having_expr_post_aggr,
+            qualify_expr: qualify_expr_post_aggr,
+        })
     }
 }
 
+/// The plan of the GROUP BY clause with the expressions of the SELECT list, and
+/// the HAVING and QUALIFY clauses rewritten to refer to its output
+struct AggregatePlanResult {
+    plan: LogicalPlan,
+    select_exprs: Vec<Expr>,
+    having_expr: Option<Expr>,
+    qualify_expr: Option<Expr>,
+}
+
 // If there are any multiple-defined windows, we raise an error.
 fn check_conflicting_windows(window_defs: &[NamedWindowDefinition]) -> Result<()> {
     for (i, window_def_i) in window_defs.iter().enumerate() {
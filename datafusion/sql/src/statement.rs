@@ -22,12 +22,12 @@ use std::sync::Arc;
 
 use crate::parser::{
     CopyToSource, CopyToStatement, CreateExternalTable, DFParser, ExplainStatement,
-    LexOrdering, Statement as DFStatement,
+    LexOrdering, RefreshMaterializedViewStatement, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
 };
-use crate::utils::normalize_ident;
+use crate::utils::{normalize_ident, value_to_string};
 
 use arrow_schema::{DataType, Fields};
 use datafusion_common::error::_plan_err;
@@ -38,29 +38,31 @@ use datafusion_common::{
     DataFusionError, Result, ScalarValue, SchemaError, SchemaReference, TableReference,
     ToDFSchema,
 };
-use datafusion_expr::dml::{CopyTo, InsertOp};
+use datafusion_expr::dml::{
+    CopyTo, InsertOp, MergeAction, MergeClause, MergeClauseKind, MergeOp,
+};
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, Analyze, AnalyzeTable, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
-    CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, DescribeTable,
-    DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView, EmptyRelation,
-    Execute, Explain, Expr, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder,
-    OperateFunctionArg, PlanType, Prepare, SetVariable, SortExpr,
-    Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
-    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
-    Volatility, WriteOp,
+    CreateIndex as PlanCreateIndex, CreateMaterializedView, CreateMemoryTable,
+    CreateView, DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable,
+    DropView, EmptyRelation, Execute, Explain, Expr, ExprSchemable, Filter, LogicalPlan,
+    LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare, RefreshMaterializedView,
+    SetVariable, SortExpr, Statement as PlanStatement, ToStringifiedPlan,
+    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    TransactionIsolationLevel, TransactionStart, Volatility, WriteOp,
 };
 use sqlparser::ast::{self, SqliteOnConflict};
 use sqlparser::ast::{
     Assignment, AssignmentTarget, ColumnDef, CreateIndex, CreateTable,
     CreateTableOptions, Delete, DescribeAlias, Expr as SQLExpr, FromTable, Ident, Insert,
     ObjectName, ObjectType, OneOrManyWithParens, Query, SchemaName, SetExpr,
-    ShowCreateObject, ShowStatementFilter, Statement, TableConstraint, TableFactor,
-    TableWithJoins, TransactionMode, UnaryOperator, Value,
+    ShowCreateObject, ShowStatementFilter, SqlOption, Statement, TableConstraint,
+    TableFactor, TableWithJoins, TransactionMode, UnaryOperator, Value,
 };
 use sqlparser::parser::ParserError::ParserError;
 
@@ -171,6 +173,9 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 analyze,
                 statement,
             }) => self.explain_to_plan(verbose, analyze, *statement),
+            DFStatement::RefreshMaterializedView(s) => {
+                self.refresh_materialized_view_to_plan(s)
+            }
         }
     }
 
@@ -214,6 +219,32 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.explain_to_plan(verbose, analyze, DFStatement::Statement(statement))
             }
             Statement::Query(query) => self.query_to_plan(*query, planner_context),
+            Statement::Analyze {
+                table_name,
+                partitions,
+                for_columns: _,
+                columns,
+                cache_metadata,
+                noscan,
+                compute_statistics: _,
+            } => {
+                if partitions.is_some() {
+                    return not_impl_err!("ANALYZE TABLE with PARTITION not supported")?;
+                }
+                if cache_metadata || noscan {
+                    return not_impl_err!(
+                        "ANALYZE TABLE with CACHE METADATA or NOSCAN not supported"
+                    )?;
+                }
+                Ok(LogicalPlan::Ddl(DdlStatement::AnalyzeTable(AnalyzeTable {
+                    name: self.object_name_to_table_reference(table_name)?,
+                    columns: columns
+                        .into_iter()
+                        .map(|column| self.ident_normalizer.normalize(column))
+                        .collect(),
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                })))
+            }
             Statement::ShowVariable { variable } => self.show_variable_to_plan(&variable),
             Statement::SetVariable {
                 local,
@@ -267,9 +298,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 with_row_access_policy,
                 with_tags,
             }) if table_properties.is_empty() && with_options.is_empty() => {
-                if temporary {
-                    return not_impl_err!("Temporary tables not supported")?;
-                }
                 if external {
                     return not_impl_err!("External tables not supported")?;
                 }
@@ -483,7 +511,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 name,
                 columns,
                 query,
-                options: CreateTableOptions::None,
+                options,
                 cluster_by,
                 comment,
                 with_no_schema_binding,
@@ -491,8 +519,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 temporary,
                 to,
             } => {
-                if materialized {
-                    return not_impl_err!("Materialized views not supported")?;
+                if !materialized && !matches!(options, CreateTableOptions::None) {
+                    return not_impl_err!("Options not supported for views")?;
                 }
                 if !cluster_by.is_empty() {
                     return not_impl_err!("Cluster by not supported")?;
@@ -503,9 +531,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 if with_no_schema_binding {
                     return not_impl_err!("With no schema binding not supported")?;
                 }
-                if if_not_exists {
+                if if_not_exists && !materialized {
                     return not_impl_err!("If not exists not supported")?;
                 }
+                if temporary && materialized {
+                    return not_impl_err!("Temporary materialized views not supported")?;
+                }
                 if to.is_some() {
                     return not_impl_err!("To not supported")?;
                 }
@@ -526,6 +557,22 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 let mut plan = self.query_to_plan(*query, &mut PlannerContext::new())?;
                 plan = self.apply_expr_alias(plan, columns)?;
 
+                if materialized {
+                    let (location, file_type) =
+                        self.materialized_view_options(options)?;
+                    return Ok(LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                        CreateMaterializedView {
+                            name: self.object_name_to_table_reference(name)?,
+                            input: Arc::new(plan),
+                            or_replace,
+                            if_not_exists,
+                            definition: sql,
+                            location,
+                            file_type,
+                        },
+                    )));
+                }
+
                 Ok(LogicalPlan::Ddl(DdlStatement::CreateView(CreateView {
                     name: self.object_name_to_table_reference(name)?,
                     input: Arc::new(plan),
@@ -788,6 +835,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.delete_to_plan(table_name, selection)
             }
 
+            Statement::Merge {
+                // optional keyword doesn't change behavior
+                into: _,
+                table,
+                source,
+                on,
+                clauses,
+            } => self.merge_to_plan(table, source, *on, clauses),
+
             Statement::StartTransaction {
                 modes,
                 begin: false,
@@ -1088,6 +1144,68 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }))
     }
 
+    /// Returns the location and the file type of the results of a
+    /// materialized view from the options of `CREATE MATERIALIZED VIEW ...
+    /// WITH (location = '...', format = '...')`. The results are kept in
+    /// memory if no location is given.
+    fn materialized_view_options(
+        &self,
+        options: CreateTableOptions,
+    ) -> Result<(Option<String>, String)> {
+        let options = match options {
+            CreateTableOptions::None => vec![],
+            CreateTableOptions::With(options) => options,
+            CreateTableOptions::Options(_) => {
+                return not_impl_err!(
+                    "OPTIONS not supported for materialized views, use WITH"
+                );
+            }
+        };
+
+        let mut location = None;
+        let mut file_type = None;
+        for option in options {
+            let SqlOption::KeyValue { key, value } = option else {
+                return plan_err!("Unsupported materialized view option {option}");
+            };
+            let value_string = match &value {
+                SQLExpr::Value(v) => value_to_string(v),
+                _ => None,
+            };
+            let Some(value_string) = value_string else {
+                return plan_err!("Unsupported value {value} for option {key}");
+            };
+            match normalize_ident(key).as_str() {
+                "location" => location = Some(value_string),
+                "format" => file_type = Some(value_string.to_uppercase()),
+                key => {
+                    return plan_err!(
+                        "Unknown materialized view option '{key}', expected 'location' or 'format'"
+                    );
+                }
+            }
+        }
+
+        if file_type.is_some() && location.is_none() {
+            return plan_err!("The format of a materialized view requires a location");
+        }
+        Ok((location, file_type.unwrap_or_else(|| "PARQUET".to_string())))
+    }
+
+    fn refresh_materialized_view_to_plan(
+        &self,
+        statement: RefreshMaterializedViewStatement,
+    ) -> Result<LogicalPlan> {
+        let RefreshMaterializedViewStatement { name, incremental } = statement;
+        Ok(LogicalPlan::Ddl(DdlStatement::RefreshMaterializedView(
+            RefreshMaterializedView {
+                name: self.object_name_to_table_reference(name)?,
+                incremental,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            },
+        )))
+    }
+
     fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
         // Determine if source is table or query and handle accordingly
         let copy_source = statement.source;
@@ -1513,7 +1631,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         // Parse value string from Expr
         let value_string = match &value[0] {
             SQLExpr::Identifier(i) => ident_to_string(i),
-            SQLExpr::Value(v) => match crate::utils::value_to_string(v) {
+            SQLExpr::Value(v) => match value_to_string(v) {
                 None => {
                     return plan_err!("Unsupported Value {}", value[0]);
                 }
@@ -1698,6 +1816,185 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(plan)
     }
 
+    fn merge_to_plan(
+        &self,
+        table: TableFactor,
+        source: TableFactor,
+        on: SQLExpr,
+        clauses: Vec<ast::MergeClause>,
+    ) -> Result<LogicalPlan> {
+        let (table_name, table_alias) = match table {
+            TableFactor::Table { name, alias, .. } => (name, alias),
+            _ => plan_err!("Cannot merge into non-table relation!")?,
+        };
+        if let Some(alias) = &table_alias {
+            if !alias.columns.is_empty() {
+                plan_err!("Merge target alias with columns not supported: {alias}")?;
+            }
+        }
+
+        // Do a table lookup to verify the table exists
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        // If the target table has an alias, use it to qualify the column names
+        let qualifier = match table_alias {
+            Some(alias) => {
+                TableReference::bare(self.ident_normalizer.normalize(alias.name))
+            }
+            None => table_name.clone(),
+        };
+        let table_schema = Arc::new(DFSchema::try_from_qualified_schema(
+            qualifier,
+            &table_source.schema(),
+        )?);
+
+        let mut planner_context = PlannerContext::new();
+        let source = self.plan_table_with_joins(
+            TableWithJoins {
+                relation: source,
+                joins: vec![],
+            },
+            &mut planner_context,
+        )?;
+
+        // The expressions can refer to the columns of both the target and the source
+        let schema = table_schema.join(source.schema())?;
+        let mut plan_expr = |sql: SQLExpr| -> Result<Expr> {
+            let expr = self.sql_to_expr(sql, &schema, &mut planner_context)?;
+            let mut using_columns = HashSet::new();
+            expr_to_columns(&expr, &mut using_columns)?;
+            normalize_col_with_schemas_and_ambiguity_check(
+                expr,
+                &[&[&schema]],
+                &[using_columns],
+            )
+        };
+
+        let on = plan_expr(on)?;
+        let clauses = clauses
+            .into_iter()
+            .map(|clause| {
+                let kind = match clause.clause_kind {
+                    ast::MergeClauseKind::Matched => MergeClauseKind::Matched,
+                    ast::MergeClauseKind::NotMatched
+                    | ast::MergeClauseKind::NotMatchedByTarget => {
+                        MergeClauseKind::NotMatchedByTarget
+                    }
+                    ast::MergeClauseKind::NotMatchedBySource => {
+                        MergeClauseKind::NotMatchedBySource
+                    }
+                };
+                let predicate = clause.predicate.map(&mut plan_expr).transpose()?;
+                let action = match (kind, clause.action) {
+                    (MergeClauseKind::NotMatchedByTarget, ast::MergeAction::Insert(insert)) => {
+                        let values = match insert.kind {
+                            ast::MergeInsertKind::Values(ast::Values { mut rows, .. })
+                                if rows.len() == 1 =>
+                            {
+                                rows.remove(0)
+                            }
+                            ast::MergeInsertKind::Values(_) => {
+                                plan_err!("Merge insert must have exactly one row of values")?
+                            }
+                            ast::MergeInsertKind::Row => {
+                                not_impl_err!("Merge insert with ROW not supported")?
+                            }
+                        };
+                        let columns = if insert.columns.is_empty() {
+                            // Empty means we're inserting into all columns of the table
+                            table_schema
+                                .fields()
+                                .iter()
+                                .map(|field| field.name().clone())
+                                .collect()
+                        } else {
+                            insert
+                                .columns
+                                .into_iter()
+                                .map(|c| self.ident_normalizer.normalize(c))
+                                .collect::<Vec<_>>()
+                        };
+                        if columns.len() != values.len() {
+                            plan_err!("Column count doesn't match merge insert values!")?;
+                        }
+                        let mut value_map = HashMap::new();
+                        for (column, value) in columns.into_iter().zip(values) {
+                            // Validate that the insert target column exists
+                            table_schema.field_with_unqualified_name(&column)?;
+                            if value_map.insert(column.clone(), value).is_some() {
+                                return schema_err!(SchemaError::DuplicateUnqualifiedField {
+                                    name: column,
+                                });
+                            }
+                        }
+                        let values = table_schema
+                            .fields()
+                            .iter()
+                            .map(|field| {
+                                let expr = match value_map.remove(field.name()) {
+                                    Some(value) => plan_expr(value)?,
+                                    // The value is not specified. Fill in the default value for the column.
+                                    None => table_source
+                                        .get_column_default(field.name())
+                                        .cloned()
+                                        .unwrap_or(Expr::Literal(ScalarValue::Null)),
+                                };
+                                // Cast to target column type, if necessary
+                                expr.cast_to(field.data_type(), &schema)
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        MergeAction::Insert(values)
+                    }
+                    (MergeClauseKind::NotMatchedByTarget, action) => {
+                        plan_err!("Only INSERT is allowed when not matched by target, found {action}")?
+                    }
+                    (_, ast::MergeAction::Update { assignments }) => {
+                        let assignments = assignments
+                            .into_iter()
+                            .map(|assign| {
+                                let cols = match assign.target {
+                                    AssignmentTarget::ColumnName(cols) => cols,
+                                    _ => plan_err!("Tuples are not supported")?,
+                                };
+                                let col_name = cols
+                                    .0
+                                    .into_iter()
+                                    .last()
+                                    .ok_or_else(|| plan_datafusion_err!("Empty column id"))?;
+                                let col_name = self.ident_normalizer.normalize(col_name);
+                                // Validate that the assignment target column exists
+                                let field =
+                                    table_schema.field_with_unqualified_name(&col_name)?;
+                                // Cast to target column type, if necessary
+                                let value = plan_expr(assign.value)?
+                                    .cast_to(field.data_type(), &schema)?;
+                                Ok((col_name, value))
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        MergeAction::Update(assignments)
+                    }
+                    (_, ast::MergeAction::Delete) => MergeAction::Delete,
+                    (_, action @ ast::MergeAction::Insert(_)) => {
+                        plan_err!("INSERT is only allowed when not matched by target, found {action}")?
+                    }
+                };
+                Ok(MergeClause {
+                    kind,
+                    predicate,
+                    action,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let plan = LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            table_schema,
+            WriteOp::Merge(Box::new(MergeOp { on, clauses })),
+            Arc::new(source),
+        ));
+        Ok(plan)
+    }
+
     fn insert_to_plan(
         &self,
         table_name: ObjectName,
@@ -352,6 +352,8 @@ pub(super) struct RelationBuilder {
 enum TableFactorBuilder {
     Table(TableRelationBuilder),
     Derived(DerivedRelationBuilder),
+    Pivot(PivotRelationBuilder),
+    Unpivot(UnpivotRelationBuilder),
     Empty,
 }
 
@@ -368,6 +370,14 @@ impl RelationBuilder {
         self.relation = Some(TableFactorBuilder::Derived(value));
         self
     }
+    pub fn pivot(&mut self, value: PivotRelationBuilder) -> &mut Self {
+        self.relation = Some(TableFactorBuilder::Pivot(value));
+        self
+    }
+    pub fn unpivot(&mut self, value: UnpivotRelationBuilder) -> &mut Self {
+        self.relation = Some(TableFactorBuilder::Unpivot(value));
+        self
+    }
     pub fn empty(&mut self) -> &mut Self {
         self.relation = Some(TableFactorBuilder::Empty);
         self
@@ -381,6 +391,12 @@ impl RelationBuilder {
             Some(TableFactorBuilder::Derived(ref mut rel_builder)) => {
                 rel_builder.alias = value;
             }
+            Some(TableFactorBuilder::Pivot(ref mut rel_builder)) => {
+                rel_builder.alias = value;
+            }
+            Some(TableFactorBuilder::Unpivot(ref mut rel_builder)) => {
+                rel_builder.alias = value;
+            }
             Some(TableFactorBuilder::Empty) => (),
             None => (),
         }
@@ -390,6 +406,8 @@ impl RelationBuilder {
         Ok(match self.relation {
             Some(TableFactorBuilder::Table(ref value)) => Some(value.build()?),
             Some(TableFactorBuilder::Derived(ref value)) => Some(value.build()?),
+            Some(TableFactorBuilder::Pivot(ref value)) => Some(value.build()?),
+            Some(TableFactorBuilder::Unpivot(ref value)) => Some(value.build()?),
             Some(TableFactorBuilder::Empty) => None,
             None => return Err(Into::into(UninitializedFieldError::from("relation"))),
         })
@@ -525,6 +543,142 @@ impl Default for DerivedRelationBuilder {
     }
 }
 
+#[derive(Clone)]
+pub(super) struct PivotRelationBuilder {
+    table: Option<Box<ast::TableFactor>>,
+    aggregate_functions: Vec<ast::ExprWithAlias>,
+    value_column: Vec<ast::Ident>,
+    value_source: Option<ast::PivotValueSource>,
+    default_on_null: Option<ast::Expr>,
+    alias: Option<ast::TableAlias>,
+}
+
+#[allow(dead_code)]
+impl PivotRelationBuilder {
+    pub fn table(&mut self, value: ast::TableFactor) -> &mut Self {
+        self.table = Some(Box::new(value));
+        self
+    }
+    pub fn aggregate_functions(&mut self, value: Vec<ast::ExprWithAlias>) -> &mut Self {
+        self.aggregate_functions = value;
+        self
+    }
+    pub fn value_column(&mut self, value: Vec<ast::Ident>) -> &mut Self {
+        self.value_column = value;
+        self
+    }
+    pub fn value_source(&mut self, value: ast::PivotValueSource) -> &mut Self {
+        self.value_source = Some(value);
+        self
+    }
+    pub fn default_on_null(&mut self, value: Option<ast::Expr>) -> &mut Self {
+        self.default_on_null = value;
+        self
+    }
+    pub fn alias(&mut self, value: Option<ast::TableAlias>) -> &mut Self {
+        self.alias = value;
+        self
+    }
+    fn build(&self) -> Result<ast::TableFactor, BuilderError> {
+        Ok(ast::TableFactor::Pivot {
+            table: match self.table {
+                Some(ref value) => value.clone(),
+                None => return Err(Into::into(UninitializedFieldError::from("table"))),
+            },
+            aggregate_functions: self.aggregate_functions.clone(),
+            value_column: self.value_column.clone(),
+            value_source: match self.value_source {
+                Some(ref value) => value.clone(),
+                None => {
+                    return Err(Into::into(UninitializedFieldError::from("value_source")))
+                }
+            },
+            default_on_null: self.default_on_null.clone(),
+            alias: self.alias.clone(),
+        })
+    }
+    fn create_empty() -> Self {
+        Self {
+            table: Default::default(),
+            aggregate_functions: Default::default(),
+            value_column: Default::default(),
+            value_source: Default::default(),
+            default_on_null: Default::default(),
+            alias: Default::default(),
+        }
+    }
+}
+impl Default for PivotRelationBuilder {
+    fn default() -> Self {
+        Self::create_empty()
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct UnpivotRelationBuilder {
+    table: Option<Box<ast::TableFactor>>,
+    value: Option<ast::Ident>,
+    name: Option<ast::Ident>,
+    columns: Vec<ast::Ident>,
+    alias: Option<ast::TableAlias>,
+}
+
+#[allow(dead_code)]
+impl UnpivotRelationBuilder {
+    pub fn table(&mut self, value: ast::TableFactor) -> &mut Self {
+        self.table = Some(Box::new(value));
+        self
+    }
+    pub fn value(&mut self, value: ast::Ident) -> &mut Self {
+        self.value = Some(value);
+        self
+    }
+    pub fn name(&mut self, value: ast::Ident) -> &mut Self {
+        self.name = Some(value);
+        self
+    }
+    pub fn columns(&mut self, value: Vec<ast::Ident>) -> &mut Self {
+        self.columns = value;
+        self
+    }
+    pub fn alias(&mut self, value: Option<ast::TableAlias>) -> &mut Self {
+        self.alias = value;
+        self
+    }
+    fn build(&self) -> Result<ast::TableFactor, BuilderError> {
+        Ok(ast::TableFactor::Unpivot {
+            table: match self.table {
+                Some(ref value) => value.clone(),
+                None => return Err(Into::into(UninitializedFieldError::from("table"))),
+            },
+            value: match self.value {
+                Some(ref value) => value.clone(),
+                None => return Err(Into::into(UninitializedFieldError::from("value"))),
+            },
+            name: match self.name {
+                Some(ref value) => value.clone(),
+                None => return Err(Into::into(UninitializedFieldError::from("name"))),
+            },
+            columns: self.columns.clone(),
+            alias: self.alias.clone(),
+        })
+    }
+    fn create_empty() -> Self {
+        Self {
+            table: Default::default(),
+            value: Default::default(),
+            name: Default::default(),
+            columns: Default::default(),
+            alias: Default::default(),
+        }
+    }
+}
+impl Default for UnpivotRelationBuilder {
+    fn default() -> Self {
+        Self::create_empty()
+    }
+}
+
 /// Runtime error when a `build()` method is called and one or more required fields
 /// do not have a value.
 #[derive(Debug, Clone)]
@@ -126,6 +126,24 @@ pub trait Dialect: Send + Sync {
         false
     }
 
+    /// Whether the dialect supports the PIVOT and UNPIVOT table factors.
+    /// If not, plans built by [`LogicalPlanBuilder::pivot`] and
+    /// [`LogicalPlanBuilder::unpivot`] are unparsed as the aggregations and
+    /// unions they consist of.
+    ///
+    /// [`LogicalPlanBuilder::pivot`]: datafusion_expr::LogicalPlanBuilder::pivot
+    /// [`LogicalPlanBuilder::unpivot`]: datafusion_expr::LogicalPlanBuilder::unpivot
+    fn supports_pivot_unpivot(&self) -> bool {
+        false
+    }
+
+    /// Whether the dialect supports the QUALIFY clause for filtering on window
+    /// functions. If not, such filters are unparsed as a WHERE clause over a
+    /// derived table computing the window functions.
+    fn supports_qualify(&self) -> bool {
+        false
+    }
+
     /// Allows the dialect to override scalar function unparsing if the dialect has specific rules.
     /// Returns None if the default unparsing should be used, or Some(ast::Expr) if there is
     /// a custom implementation for the function.
@@ -373,6 +391,8 @@ pub struct CustomDialect {
     date32_cast_dtype: ast::DataType,
     supports_column_alias_in_table_alias: bool,
     requires_derived_table_alias: bool,
+    supports_pivot_unpivot: bool,
+    supports_qualify: bool,
 }
 
 impl Default for CustomDialect {
@@ -396,6 +416,8 @@ impl Default for CustomDialect {
             date32_cast_dtype: ast::DataType::Date,
             supports_column_alias_in_table_alias: true,
             requires_derived_table_alias: false,
+            supports_pivot_unpivot: false,
+            supports_qualify: false,
         }
     }
 }
@@ -488,6 +510,14 @@ impl Dialect for CustomDialect {
     fn requires_derived_table_alias(&self) -> bool {
         self.requires_derived_table_alias
     }
+
+    fn supports_pivot_unpivot(&self) -> bool {
+        self.supports_pivot_unpivot
+    }
+
+    fn supports_qualify(&self) -> bool {
+        self.supports_qualify
+    }
 }
 
 /// `CustomDialectBuilder` to build `CustomDialect` using builder pattern
@@ -520,6 +550,8 @@ pub struct CustomDialectBuilder {
     date32_cast_dtype: ast::DataType,
     supports_column_alias_in_table_alias: bool,
     requires_derived_table_alias: bool,
+    supports_pivot_unpivot: bool,
+    supports_qualify: bool,
 }
 
 impl Default for CustomDialectBuilder {
@@ -549,6 +581,8 @@ impl CustomDialectBuilder {
             date32_cast_dtype: ast::DataType::Date,
             supports_column_alias_in_table_alias: true,
             requires_derived_table_alias: false,
+            supports_pivot_unpivot: false,
+            supports_qualify: false,
         }
     }
 
@@ -570,6 +604,8 @@ impl CustomDialectBuilder {
             supports_column_alias_in_table_alias: self
                 .supports_column_alias_in_table_alias,
             requires_derived_table_alias: self.requires_derived_table_alias,
+            supports_pivot_unpivot: self.supports_pivot_unpivot,
+            supports_qualify: self.supports_qualify,
         }
     }
 
@@ -677,4 +713,16 @@ impl CustomDialectBuilder {
         self.requires_derived_table_alias = requires_derived_table_alias;
         self
     }
+
+    /// Customize the dialect to support the PIVOT and UNPIVOT table factors
+    pub fn with_supports_pivot_unpivot(mut self, supports_pivot_unpivot: bool) -> Self {
+        self.supports_pivot_unpivot = supports_pivot_unpivot;
+        self
+    }
+
+    /// Customize the dialect to support the QUALIFY clause
+    pub fn with_supports_qualify(mut self, supports_qualify: bool) -> Self {
+        self.supports_qualify = supports_qualify;
+        self
+    }
 }
@@ -40,7 +40,7 @@ use datafusion_common::{
     ScalarValue,
 };
 use datafusion_expr::{
-    expr::{Alias, Exists, InList, ScalarFunction, Sort, WindowFunction},
+    expr::{Alias, Exists, InList, Lambda, ScalarFunction, Sort, WindowFunction},
     Between, BinaryExpr, Case, Cast, Expr, GroupingSet, Like, Operator, TryCast,
 };
 
@@ -286,7 +286,19 @@ impl Unparser<'_> {
             Expr::AggregateFunction(agg) => {
                 let func_name = agg.func.name();
 
-                let args = self.function_args_to_sql(&agg.args)?;
+                // The first arguments of ordered-set aggregate functions are the
+                // expressions of their WITHIN GROUP clause
+                let (args, within_group) = match &agg.order_by {
+                    Some(order_by) if agg.func.is_ordered_set_aggregate() => {
+                        let within_group = order_by
+                            .iter()
+                            .map(|sort| self.sort_to_sql(sort))
+                            .collect::<Result<Vec<_>>>()?;
+                        let args = agg.args.get(order_by.len()..).unwrap_or_default();
+                        (self.function_args_to_sql(args)?, within_group)
+                    }
+                    _ => (self.function_args_to_sql(&agg.args)?, vec![]),
+                };
                 let filter = match &agg.filter {
                     Some(filter) => Some(Box::new(self.expr_to_sql_inner(filter)?)),
                     None => None,
@@ -306,7 +318,7 @@ impl Unparser<'_> {
                     filter,
                     null_treatment: None,
                     over: None,
-                    within_group: vec![],
+                    within_group,
                     parameters: ast::FunctionArguments::None,
                 }))
             }
@@ -468,6 +480,21 @@ impl Unparser<'_> {
             }
             Expr::OuterReferenceColumn(_, col) => self.col_to_sql(col),
             Expr::Unnest(unnest) => self.unnest_to_sql(unnest),
+            Expr::Lambda(Lambda { params, body }) => {
+                let mut params = params
+                    .iter()
+                    .map(|param| self.new_ident_quoted_if_needs(param.to_string()))
+                    .collect::<Vec<_>>();
+                let params = if params.len() == 1 {
+                    ast::OneOrManyWithParens::One(params.remove(0))
+                } else {
+                    ast::OneOrManyWithParens::Many(params)
+                };
+                Ok(ast::Expr::Lambda(ast::LambdaFunction {
+                    params,
+                    body: Box::new(self.expr_to_sql_inner(body)?),
+                }))
+            }
         }
     }
 
@@ -1473,9 +1500,9 @@ mod tests {
     use datafusion_expr::expr::WildcardOptions;
     use datafusion_expr::{
         case, col, cube, exists, grouping_set, interval_datetime_lit,
-        interval_year_month_lit, lit, not, not_exists, out_ref_col, placeholder, rollup,
-        table_scan, try_cast, when, wildcard, ColumnarValue, ScalarUDF, ScalarUDFImpl,
-        Signature, Volatility, WindowFrame, WindowFunctionDefinition,
+        interval_year_month_lit, lambda, lit, not, not_exists, out_ref_col, placeholder,
+        rollup, table_scan, try_cast, when, wildcard, ColumnarValue, ScalarUDF,
+        ScalarUDFImpl, Signature, Volatility, WindowFrame, WindowFunctionDefinition,
     };
     use datafusion_expr::{interval_month_day_nano_lit, ExprFunctionExt};
     use datafusion_functions_aggregate::count::count_udaf;
@@ -1884,6 +1911,11 @@ mod tests {
                 }),
                 r#"UNNEST("schema"."table".array_col)"#,
             ),
+            (lambda(["x"], col("x").gt(lit(1))), r#"x -> (x > 1)"#),
+            (
+                lambda(["acc", "x"], col("acc") + col("x")),
+                r#"(acc, x) -> (acc + x)"#,
+            ),
         ];
 
         for (expr, expected) in tests {
@@ -17,24 +17,26 @@
 
 use super::{
     ast::{
-        BuilderError, DerivedRelationBuilder, QueryBuilder, RelationBuilder,
-        SelectBuilder, TableRelationBuilder, TableWithJoinsBuilder,
+        BuilderError, DerivedRelationBuilder, PivotRelationBuilder, QueryBuilder,
+        RelationBuilder, SelectBuilder, TableRelationBuilder, TableWithJoinsBuilder,
+        UnpivotRelationBuilder,
     },
     rewrite::{
         inject_column_aliases_into_subquery, normalize_union_schema,
-        rewrite_plan_for_sort_on_non_projected_fields,
+        rewrite_plan_for_sort_on_non_projected_fields, rewrite_qualify_to_derived_table,
         subquery_alias_inner_query_and_columns, TableAliasRewriter,
     },
     utils::{
-        find_agg_node_within_select, find_unnest_node_within_select,
-        find_window_nodes_within_select, try_transform_to_simple_table_scan_with_filters,
-        unproject_sort_expr, unproject_unnest_expr, unproject_window_exprs,
+        find_agg_node_within_select, find_pivot, find_unnest_node_within_select,
+        find_unpivot, find_window_nodes_within_select,
+        try_transform_to_simple_table_scan_with_filters, unproject_sort_expr,
+        unproject_unnest_expr, unproject_window_exprs, Pivot, Unpivot,
     },
     Unparser,
 };
 use crate::unparser::utils::unproject_agg_exprs;
 use datafusion_common::{
-    internal_err, not_impl_err,
+    internal_datafusion_err, internal_err, not_impl_err,
     tree_node::{TransformedResult, TreeNode},
     Column, DataFusionError, Result, TableReference,
 };
@@ -117,6 +119,7 @@ impl Unparser<'_> {
             | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::RecursiveQuery(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Unnest(_) => not_impl_err!("Unsupported plan: {plan:?}"),
         }
     }
@@ -312,6 +315,12 @@ impl Unparser<'_> {
                         .select_to_sql_recursively(&new_plan, query, select, relation);
                 }
 
+                if self.dialect.supports_pivot_unpivot() {
+                    if let Some(pivot) = find_pivot(p) {
+                        return self.pivot_to_sql(pivot, select, relation);
+                    }
+                }
+
                 // Projection can be top-level plan for derived table
                 if select.already_projected() {
                     return self.derive_with_dialect_alias(
@@ -320,11 +329,53 @@ impl Unparser<'_> {
                         relation,
                     );
                 }
+
+                if !self.dialect.supports_qualify() {
+                    if let LogicalPlan::Filter(filter) = p.input.as_ref() {
+                        if find_window_nodes_within_select(&p.input, None, true).is_some()
+                        {
+                            let new_plan =
+                                rewrite_qualify_to_derived_table(Some(p), filter)?;
+                            return self.select_to_sql_recursively(
+                                &new_plan, query, select, relation,
+                            );
+                        }
+                    }
+                }
+
                 self.reconstruct_select_statement(plan, p, select)?;
                 self.select_to_sql_recursively(p.input.as_ref(), query, select, relation)
             }
             LogicalPlan::Filter(filter) => {
-                if let Some(agg) =
+                // A filter over window functions is a QUALIFY clause
+                if let Some(window) = find_window_nodes_within_select(
+                    plan,
+                    None,
+                    select.already_projected(),
+                ) {
+                    if !self.dialect.supports_qualify() {
+                        let new_plan = rewrite_qualify_to_derived_table(None, filter)?;
+                        return self.select_to_sql_recursively(
+                            &new_plan, query, select, relation,
+                        );
+                    }
+
+                    let unprojected = match find_agg_node_within_select(
+                        plan,
+                        select.already_projected(),
+                    ) {
+                        Some(agg) => unproject_agg_exprs(
+                            filter.predicate.clone(),
+                            agg,
+                            Some(&window),
+                        )?,
+                        None => {
+                            unproject_window_exprs(filter.predicate.clone(), &window)?
+                        }
+                    };
+                    let filter_expr = self.expr_to_sql(&unprojected)?;
+                    select.qualify(Some(filter_expr));
+                } else if let Some(agg) =
                     find_agg_node_within_select(plan, select.already_projected())
                 {
                     let unprojected =
@@ -505,8 +556,14 @@ impl Unparser<'_> {
                     &mut right_relation,
                 )?;
 
+                // The filter of an ASOF join is its match condition
+                let (join_filter, match_condition) = match join.join_type {
+                    JoinType::AsOf => (None, join.filter.as_ref()),
+                    _ => (join.filter.clone(), None),
+                };
+
                 let join_filters = if table_scan_filters.is_empty() {
-                    join.filter.clone()
+                    join_filter
                 } else {
                     // Combine `table_scan_filters` into a single filter using `AND`
                     let Some(combined_filters) =
@@ -521,10 +578,10 @@ impl Unparser<'_> {
                         return internal_err!("Failed to combine TableScan filters");
                     };
 
-                    // Combine `join.filter` with `combined_filters` using `AND`
-                    match &join.filter {
+                    // Combine `join_filter` with `combined_filters` using `AND`
+                    match join_filter {
                         Some(filter) => Some(Expr::BinaryExpr(BinaryExpr {
-                            left: Box::new(filter.clone()),
+                            left: Box::new(filter),
                             op: Operator::And,
                             right: Box::new(combined_filters),
                         })),
@@ -552,8 +609,11 @@ impl Unparser<'_> {
                 let ast_join = ast::Join {
                     relation,
                     global: false,
-                    join_operator: self
-                        .join_operator_to_sql(join.join_type, join_constraint)?,
+                    join_operator: self.join_operator_to_sql(
+                        join.join_type,
+                        join_constraint,
+                        match_condition,
+                    )?,
                 };
                 let mut from = select.pop_from().unwrap();
                 from.push_join(ast_join);
@@ -609,6 +669,12 @@ impl Unparser<'_> {
                 Ok(())
             }
             LogicalPlan::Union(union) => {
+                if self.dialect.supports_pivot_unpivot() {
+                    if let Some(unpivot) = find_unpivot(union) {
+                        return self.unpivot_to_sql(unpivot, select, relation);
+                    }
+                }
+
                 if union.inputs.len() != 2 {
                     return not_impl_err!(
                         "UNION ALL expected 2 inputs, but found {}",
@@ -668,6 +734,11 @@ impl Unparser<'_> {
                         unnest.struct_type_columns
                     );
                 }
+                if unnest.options.ordinality {
+                    return not_impl_err!(
+                        "UNNEST with ordinality is not currently supported: {unnest:?}"
+                    );
+                }
 
                 // In the case of UNNEST, the Unnest node is followed by a duplicate Projection node that we should skip.
                 // Otherwise, there will be a duplicate SELECT clause.
@@ -687,6 +758,119 @@ impl Unparser<'_> {
         }
     }
 
+    /// Unparses a PIVOT recognized by [`find_pivot`] as a table factor,
+    /// selecting all of its columns if the select is not projected yet
+    fn pivot_to_sql(
+        &self,
+        pivot: Pivot,
+        select: &mut SelectBuilder,
+        relation: &mut RelationBuilder,
+    ) -> Result<()> {
+        if !select.already_projected() {
+            select.projection(vec![ast::SelectItem::Wildcard(
+                ast::WildcardAdditionalOptions::default(),
+            )]);
+        }
+
+        let aggregate_functions = pivot
+            .aggregates
+            .iter()
+            .map(|(expr, alias)| self.expr_with_alias_to_sql(expr, alias.as_deref()))
+            .collect::<Result<Vec<_>>>()?;
+        let value_column =
+            match self.expr_to_sql(&Expr::Column(pivot.value_column.clone()))? {
+                ast::Expr::Identifier(ident) => vec![ident],
+                ast::Expr::CompoundIdentifier(idents) => idents,
+                expr => return internal_err!("Unexpected PIVOT column: {expr}"),
+            };
+        let values = pivot
+            .values
+            .iter()
+            .map(|(expr, alias)| self.expr_with_alias_to_sql(expr, alias.as_deref()))
+            .collect::<Result<Vec<_>>>()?;
+        let default_on_null = pivot
+            .default_on_null
+            .as_ref()
+            .map(|expr| self.expr_to_sql(expr))
+            .transpose()?;
+
+        let mut builder = PivotRelationBuilder::default();
+        builder
+            .table(self.relation_to_sql(pivot.input, "derived_pivot")?)
+            .aggregate_functions(aggregate_functions)
+            .value_column(value_column)
+            .value_source(ast::PivotValueSource::List(values))
+            .default_on_null(default_on_null);
+        relation.pivot(builder);
+        Ok(())
+    }
+
+    /// Unparses an UNPIVOT recognized by [`find_unpivot`] as a table factor,
+    /// selecting all of its columns if the select is not projected yet
+    fn unpivot_to_sql(
+        &self,
+        unpivot: Unpivot,
+        select: &mut SelectBuilder,
+        relation: &mut RelationBuilder,
+    ) -> Result<()> {
+        if !select.already_projected() {
+            select.projection(vec![ast::SelectItem::Wildcard(
+                ast::WildcardAdditionalOptions::default(),
+            )]);
+        }
+
+        let mut builder = UnpivotRelationBuilder::default();
+        builder
+            .table(self.relation_to_sql(unpivot.input, "derived_unpivot")?)
+            .value(self.new_ident_quoted_if_needs(unpivot.value.to_string()))
+            .name(self.new_ident_quoted_if_needs(unpivot.name.to_string()))
+            .columns(
+                unpivot
+                    .columns
+                    .iter()
+                    .map(|column| self.new_ident_quoted_if_needs(column.name.clone()))
+                    .collect(),
+            );
+        relation.unpivot(builder);
+        Ok(())
+    }
+
+    fn expr_with_alias_to_sql(
+        &self,
+        expr: &Expr,
+        alias: Option<&str>,
+    ) -> Result<ast::ExprWithAlias> {
+        Ok(ast::ExprWithAlias {
+            expr: self.expr_to_sql(expr)?,
+            alias: alias.map(|alias| self.new_ident_quoted_if_needs(alias.to_string())),
+        })
+    }
+
+    /// Unparses `plan` as the table factor of a PIVOT or UNPIVOT: a table, or
+    /// a derived table otherwise
+    fn relation_to_sql(
+        &self,
+        plan: &LogicalPlan,
+        alias: &str,
+    ) -> Result<ast::TableFactor> {
+        let mut relation = RelationBuilder::default();
+        match plan {
+            LogicalPlan::TableScan(scan) if !Self::is_scan_with_pushdown(scan) => {
+                let mut select = SelectBuilder::default();
+                self.select_to_sql_recursively(
+                    plan,
+                    &mut None,
+                    &mut select,
+                    &mut relation,
+                )?;
+            }
+            _ => self.derive_with_dialect_alias(alias, plan, &mut relation)?,
+        }
+        relation
+            .build()?
+            .ok_or_else(|| internal_datafusion_err!("Empty relation: {plan:?}"))
+    }
+
     fn is_scan_with_pushdown(scan: &TableScan) -> bool {
         scan.projection.is_some() || !scan.filters.is_empty() || scan.fetch.is_some()
     }
@@ -856,6 +1040,7 @@ impl Unparser<'_> {
         &self,
         join_type: JoinType,
         constraint: ast::JoinConstraint,
+        match_condition: Option<&Expr>,
     ) -> Result<ast::JoinOperator> {
         Ok(match join_type {
             JoinType::Inner => ast::JoinOperator::Inner(constraint),
@@ -867,6 +1052,15 @@ impl Unparser<'_> {
             JoinType::RightAnti => ast::JoinOperator::RightAnti(constraint),
             JoinType::RightSemi => ast::JoinOperator::RightSemi(constraint),
             JoinType::LeftMark => unimplemented!("Unparsing of Left Mark join type"),
+            JoinType::AsOf => {
+                let Some(match_condition) = match_condition else {
+                    return internal_err!("AsOf join without a match condition");
+                };
+                ast::JoinOperator::AsOf {
+                    match_condition: self.expr_to_sql(match_condition)?,
+                    constraint,
+                }
+            }
         })
     }
 
@@ -23,7 +23,9 @@ use datafusion_common::{
     Column, HashMap, Result, TableReference,
 };
 use datafusion_expr::{expr::Alias, tree_node::transform_sort_vec};
-use datafusion_expr::{Expr, LogicalPlan, Projection, Sort, SortExpr};
+use datafusion_expr::{
+    Expr, Filter, LogicalPlan, Projection, Sort, SortExpr, SubqueryAlias,
+};
 use sqlparser::ast::Ident;
 
 /// Normalize the schema of a union plan to remove qualifiers from the schema fields and sort expressions.
@@ -188,6 +190,90 @@ pub(super) fn rewrite_plan_for_sort_on_non_projected_fields(
     }
 }
 
+/// Rewrite a Filter over window functions, along with the Projection above it if
+/// any, into a Filter over a derived table computing the window functions. This
+/// is used for dialects that do not support the QUALIFY clause.
+///
+/// The following input SQL:
+/// ```sql
+/// SELECT j1_id FROM j1 QUALIFY rank() OVER (ORDER BY j1_id) = 1
+/// ```
+///
+/// Would be rewritten to:
+/// ```sql
+/// SELECT derived_qualify.j1_id FROM
+///   (SELECT j1.j1_id, rank() OVER (ORDER BY j1.j1_id) AS "rank() ..." FROM j1) AS derived_qualify
+/// WHERE (derived_qualify."rank() ..." = 1)
+/// ```
+pub(super) fn rewrite_qualify_to_derived_table(
+    projection: Option<&Projection>,
+    filter: &Filter,
+) -> Result<LogicalPlan> {
+    let alias = TableReference::bare("derived_qualify");
+    let schema = filter.input.schema();
+
+    let mut name_counts = HashMap::new();
+    for field in schema.fields() {
+        *name_counts.entry(field.name()).or_insert(0) += 1;
+    }
+
+    // Window and aggregate outputs are unqualified, so they are aliased to keep
+    // their names once unprojected. Ambiguous names use the qualified name.
+    let mut columns = HashMap::new();
+    let mut derived_columns = vec![];
+    let inner_exprs = schema
+        .iter()
+        .map(|(qualifier, field)| {
+            let column = Column::new(qualifier.cloned(), field.name());
+            let name = if name_counts[field.name()] > 1 {
+                column.flat_name()
+            } else {
+                field.name().clone()
+            };
+            let derived = Column::new(Some(alias.clone()), &name);
+            columns.insert(column.clone(), derived.clone());
+            derived_columns.push(Expr::Column(derived));
+
+            if qualifier.is_some() && &name == field.name() {
+                Expr::Column(column)
+            } else {
+                Expr::Column(column).alias(name)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let inner = Projection::try_new(inner_exprs, Arc::clone(&filter.input))?;
+    let derived =
+        SubqueryAlias::try_new(Arc::new(LogicalPlan::Projection(inner)), alias)?;
+
+    let replace_columns = |expr: Expr| {
+        expr.transform(|e| match e {
+            Expr::Column(c) => match columns.get(&c) {
+                Some(derived) => Ok(Transformed::yes(Expr::Column(derived.clone()))),
+                None => Ok(Transformed::no(Expr::Column(c))),
+            },
+            _ => Ok(Transformed::no(e)),
+        })
+        .data()
+    };
+
+    let predicate = replace_columns(filter.predicate.clone())?;
+    let filter =
+        Filter::try_new(predicate, Arc::new(LogicalPlan::SubqueryAlias(derived)))?;
+    let exprs = match projection {
+        Some(p) => p
+            .expr
+            .iter()
+            .cloned()
+            .map(replace_columns)
+            .collect::<Result<Vec<_>>>()?,
+        None => derived_columns,
+    };
+
+    Projection::try_new(exprs, Arc::new(LogicalPlan::Filter(filter)))
+        .map(LogicalPlan::Projection)
+}
+
 /// This logic is to work out the columns and inner query for SubqueryAlias plan for both types of
 /// subquery
 /// - `(SELECT column_a as a from table) AS A`
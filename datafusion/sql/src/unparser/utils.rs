@@ -15,7 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::{cmp::Ordering, sync::Arc, vec};
+use std::{cmp::Ordering, collections::HashSet, sync::Arc, vec};
 
 use datafusion_common::{
     internal_err,
@@ -23,8 +23,10 @@ use datafusion_common::{
     Column, DataFusionError, Result, ScalarValue,
 };
 use datafusion_expr::{
-    expr, utils::grouping_set_to_exprlist, Aggregate, Expr, LogicalPlan,
-    LogicalPlanBuilder, Projection, SortExpr, Unnest, Window,
+    expr::{self, AggregateFunction, Alias, Case},
+    utils::{expr_to_columns, grouping_set_to_exprlist},
+    Aggregate, BinaryExpr, Expr, LogicalPlan, LogicalPlanBuilder, Operator, Projection,
+    SortExpr, Union, Unnest, Window,
 };
 use sqlparser::ast;
 
@@ -126,6 +128,376 @@ pub(crate) fn find_window_nodes_within_select<'a>(
     }
 }
 
+/// The parts of a PIVOT table factor, see [`find_pivot`]
+pub(crate) struct Pivot<'a> {
+    /// The pivoted relation
+    pub(crate) input: &'a LogicalPlan,
+    /// The aggregate functions and their aliases
+    pub(crate) aggregates: Vec<(Expr, Option<String>)>,
+    pub(crate) value_column: Column,
+    /// The values of `value_column` and their aliases
+    pub(crate) values: Vec<(&'a Expr, Option<String>)>,
+    pub(crate) default_on_null: Option<Expr>,
+}
+
+/// Recognizes a PIVOT in `projection`, as planned by
+/// [`LogicalPlanBuilder::pivot`]:
+///
+/// ```text
+/// Projection: other columns, Q1, Q2
+///   Aggregate: groupBy=[[other columns]], aggr=[[
+///     sum(CASE WHEN quarter = 'Q1' THEN amount END) AS Q1,
+///     sum(CASE WHEN quarter = 'Q2' THEN amount END) AS Q2
+///   ]]
+/// ```
+///
+/// Returns None if `projection` is not a PIVOT.
+pub(crate) fn find_pivot(projection: &Projection) -> Option<Pivot<'_>> {
+    let LogicalPlan::Aggregate(agg) = projection.input.as_ref() else {
+        return None;
+    };
+
+    // Every aggregate is `aggr(CASE WHEN value_column = value THEN arg END)`,
+    // aliased by the name of the pivoted column
+    let mut value_column = None;
+    let mut pivoted = vec![];
+    for expr in &agg.aggr_expr {
+        let Expr::Alias(Alias {
+            expr,
+            relation: None,
+            name,
+        }) = expr
+        else {
+            return None;
+        };
+        let Expr::AggregateFunction(aggr) = expr.as_ref() else {
+            return None;
+        };
+        let mut value = None;
+        let mut args = vec![];
+        for arg in &aggr.args {
+            let (column, arg_value, arg) = pivot_case(arg)?;
+            if *value_column.get_or_insert(column) != column
+                || *value.get_or_insert(arg_value) != arg_value
+            {
+                return None;
+            }
+            args.push(arg.clone());
+        }
+        let aggregate = Expr::AggregateFunction(AggregateFunction {
+            args,
+            ..aggr.clone()
+        });
+        pivoted.push((name.as_str(), aggregate, value?));
+    }
+    let value_column = value_column?.clone();
+
+    // The aggregate functions are repeated for every value
+    let n_aggregates = pivoted
+        .iter()
+        .take_while(|(_, _, value)| *value == pivoted[0].2)
+        .count();
+    if pivoted.len() % n_aggregates != 0 {
+        return None;
+    }
+    let chunks = pivoted.chunks(n_aggregates).collect::<Vec<_>>();
+    for chunk in &chunks {
+        let value = chunk[0].2;
+        if chunk.iter().zip(chunks[0]).any(
+            |((_, aggregate, chunk_value), (_, first, _))| {
+                *chunk_value != value || aggregate != first
+            },
+        ) {
+            return None;
+        }
+    }
+
+    // The input is grouped by all of its other columns
+    let mut used_columns = HashSet::from([value_column.clone()]);
+    for (_, aggregate, _) in chunks[0] {
+        expr_to_columns(aggregate, &mut used_columns).ok()?;
+    }
+    let group_columns = agg
+        .input
+        .schema()
+        .columns()
+        .into_iter()
+        .filter(|column| !used_columns.contains(column))
+        .collect::<Vec<_>>();
+    if !exprs_are_columns(&agg.group_expr, &group_columns) {
+        return None;
+    }
+
+    // The projection keeps the group columns, followed by the pivoted columns
+    // with their nulls replaced if DEFAULT ON NULL is set
+    let names = pivoted.iter().map(|(name, _, _)| *name).collect::<Vec<_>>();
+    if projection.expr.len() != group_columns.len() + names.len() {
+        return None;
+    }
+    let (group_exprs, pivoted_exprs) = projection.expr.split_at(group_columns.len());
+    if !exprs_are_columns(group_exprs, &group_columns) {
+        return None;
+    }
+    let mut defaults = vec![];
+    for (expr, name) in pivoted_exprs.iter().zip(&names) {
+        defaults.push(match expr {
+            Expr::Column(column) if column.relation.is_none() && column.name == *name => {
+                None
+            }
+            Expr::Alias(Alias {
+                expr,
+                relation: None,
+                name: alias,
+            }) if alias.as_str() == *name => Some(pivot_default_on_null(expr, name)?),
+            _ => return None,
+        });
+    }
+    if defaults.iter().any(|default| *default != defaults[0]) {
+        return None;
+    }
+
+    let (aggregate_aliases, value_names) = split_pivot_names(&names, n_aggregates)?;
+    let aggregates = chunks[0]
+        .iter()
+        .map(|(_, aggregate, _)| aggregate.clone())
+        .zip(aggregate_aliases)
+        .collect();
+    let values = chunks
+        .iter()
+        .zip(value_names)
+        .map(|(chunk, name)| {
+            let value = chunk[0].2;
+            let alias = (name != pivot_value_name(value)).then_some(name);
+            (value, alias)
+        })
+        .collect();
+    Some(Pivot {
+        input: agg.input.as_ref(),
+        aggregates,
+        value_column,
+        values,
+        default_on_null: defaults.swap_remove(0),
+    })
+}
+
+/// Returns the parts of `CASE WHEN column = value THEN arg END`, the argument
+/// of a pivoted aggregate function
+fn pivot_case(expr: &Expr) -> Option<(&Column, &Expr, &Expr)> {
+    let Expr::Case(Case {
+        expr: None,
+        when_then_expr,
+        else_expr: None,
+    }) = expr
+    else {
+        return None;
+    };
+    let [(when, then)] = when_then_expr.as_slice() else {
+        return None;
+    };
+    let Expr::BinaryExpr(BinaryExpr {
+        left,
+        op: Operator::Eq,
+        right,
+    }) = when.as_ref()
+    else {
+        return None;
+    };
+    let Expr::Column(column) = left.as_ref() else {
+        return None;
+    };
+    Some((column, right.as_ref(), then.as_ref()))
+}
+
+/// Returns the default of `CASE WHEN name IS NULL THEN default ELSE name END`,
+/// the pivoted column `name` with DEFAULT ON NULL
+fn pivot_default_on_null(expr: &Expr, name: &str) -> Option<Expr> {
+    let column = Expr::Column(Column::from_name(name));
+    let Expr::Case(Case {
+        expr: None,
+        when_then_expr,
+        else_expr: Some(else_expr),
+    }) = expr
+    else {
+        return None;
+    };
+    let [(when, then)] = when_then_expr.as_slice() else {
+        return None;
+    };
+    (**when == column.clone().is_null() && **else_expr == column)
+        .then(|| then.as_ref().clone())
+}
+
+/// Returns the name of the pivoted column of `value`, if it has no alias
+fn pivot_value_name(value: &Expr) -> String {
+    match value {
+        Expr::Literal(scalar) => scalar.to_string(),
+        value => value.schema_name().to_string(),
+    }
+}
+
+/// Splits the names of the pivoted columns into the aliases of the aggregate
+/// functions and the names of the values.
+///
+/// The pivoted columns are named `{aggregate alias}_{value name}` for every
+/// value and aggregate function, or `{value name}` if there is a single
+/// aggregate function without alias.
+fn split_pivot_names(
+    names: &[&str],
+    n_aggregates: usize,
+) -> Option<(Vec<Option<String>>, Vec<String>)> {
+    if n_aggregates == 1 {
+        let value_names = names.iter().map(|name| name.to_string()).collect();
+        return Some((vec![None], value_names));
+    }
+
+    // try every split of the first name into an alias and a value name
+    let first = &names[..n_aggregates];
+    first[0].match_indices('_').find_map(|(idx, _)| {
+        let suffix = &first[0][idx..];
+        let aliases = first
+            .iter()
+            .map(|name| name.strip_suffix(suffix).filter(|alias| !alias.is_empty()))
+            .collect::<Option<Vec<_>>>()?;
+        let value_names = names
+            .chunks(n_aggregates)
+            .map(|chunk| {
+                let value_name = chunk[0].strip_prefix(aliases[0])?.strip_prefix('_')?;
+                chunk
+                    .iter()
+                    .zip(&aliases)
+                    .all(|(name, alias)| *name == format!("{alias}_{value_name}"))
+                    .then(|| value_name.to_string())
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let aliases = aliases
+            .into_iter()
+            .map(|alias| Some(alias.to_string()))
+            .collect();
+        Some((aliases, value_names))
+    })
+}
+
+/// The parts of an UNPIVOT table factor, see [`find_unpivot`]
+pub(crate) struct Unpivot<'a> {
+    /// The unpivoted relation
+    pub(crate) input: &'a LogicalPlan,
+    pub(crate) value: &'a str,
+    pub(crate) name: &'a str,
+    pub(crate) columns: Vec<&'a Column>,
+}
+
+/// Recognizes an UNPIVOT of at least two columns in `union`, as planned by
+/// [`LogicalPlanBuilder::unpivot`] without nulls:
+///
+/// ```text
+/// Union
+///   Projection: other columns, Utf8("a") AS name, a AS value
+///     Filter: a IS NOT NULL
+///   Projection: other columns, Utf8("b") AS name, b AS value
+///     Filter: b IS NOT NULL
+/// ```
+///
+/// An UNPIVOT of a single column is a plain projection, which is not
+/// recognized.
+///
+/// Returns None if `union` is not an UNPIVOT.
+pub(crate) fn find_unpivot(union: &Union) -> Option<Unpivot<'_>> {
+    if union.inputs.len() < 2 {
+        return None;
+    }
+
+    let mut unpivot: Option<(Unpivot, &[Expr])> = None;
+    for branch in &union.inputs {
+        let (input, kept, value, name, column) = unpivot_branch(branch)?;
+        match &mut unpivot {
+            Some((unpivot, unpivot_kept)) => {
+                if unpivot.input != input
+                    || unpivot.value != value
+                    || unpivot.name != name
+                    || *unpivot_kept != kept
+                {
+                    return None;
+                }
+                unpivot.columns.push(column);
+            }
+            None => {
+                let columns = vec![column];
+                unpivot = Some((
+                    Unpivot {
+                        input,
+                        value,
+                        name,
+                        columns,
+                    },
+                    kept,
+                ));
+            }
+        }
+    }
+
+    // The other columns of the input are kept
+    let (unpivot, kept) = unpivot?;
+    let kept_columns = unpivot
+        .input
+        .schema()
+        .columns()
+        .into_iter()
+        .filter(|column| !unpivot.columns.contains(&column))
+        .collect::<Vec<_>>();
+    exprs_are_columns(kept, &kept_columns).then_some(unpivot)
+}
+
+/// Returns the input, kept columns, value, name and unpivoted column of
+/// `Projection: kept, Utf8("column") AS name, column AS value` over
+/// `Filter: column IS NOT NULL`
+fn unpivot_branch(
+    plan: &LogicalPlan,
+) -> Option<(&LogicalPlan, &[Expr], &str, &str, &Column)> {
+    let LogicalPlan::Projection(projection) = plan else {
+        return None;
+    };
+    let [kept @ .., name_expr, value_expr] = projection.expr.as_slice() else {
+        return None;
+    };
+    let (
+        Expr::Alias(Alias {
+            expr: name_literal,
+            relation: None,
+            name,
+        }),
+        Expr::Alias(Alias {
+            expr: value_column,
+            relation: None,
+            name: value,
+        }),
+    ) = (name_expr, value_expr)
+    else {
+        return None;
+    };
+    let Expr::Column(column) = value_column.as_ref() else {
+        return None;
+    };
+    if **name_literal != Expr::Literal(ScalarValue::from(column.name.as_str())) {
+        return None;
+    }
+    let LogicalPlan::Filter(filter) = projection.input.as_ref() else {
+        return None;
+    };
+    if filter.predicate != Expr::Column(column.clone()).is_not_null() {
+        return None;
+    }
+    Some((filter.input.as_ref(), kept, value, name, column))
+}
+
+/// Returns true if `exprs` are the `columns`, in order
+fn exprs_are_columns(exprs: &[Expr], columns: &[Column]) -> bool {
+    exprs.len() == columns.len()
+        && exprs
+            .iter()
+            .zip(columns)
+            .all(|(expr, column)| matches!(expr, Expr::Column(expr) if expr == column))
+}
+
 /// Recursively identify Column expressions and transform them into the appropriate unnest expression
 ///
 /// For example, if expr contains the column expr "unnest_placeholder(make_array(Int64(1),Int64(2),Int64(2),Int64(5),NULL),depth=1)"
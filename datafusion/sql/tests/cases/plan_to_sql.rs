@@ -24,12 +24,14 @@ use datafusion_expr::test::function_stub::{count_udaf, max_udaf, min_udaf, sum_u
 use datafusion_expr::{col, lit, table_scan, wildcard, LogicalPlanBuilder};
 use datafusion_functions::unicode;
 use datafusion_functions_aggregate::grouping::grouping_udaf;
+use datafusion_functions_aggregate::mode::mode_udaf;
+use datafusion_functions_aggregate::percentile::percentile_cont_udaf;
 use datafusion_functions_nested::make_array::make_array_udf;
 use datafusion_functions_window::rank::rank_udwf;
 use datafusion_sql::planner::{ContextProvider, PlannerContext, SqlToRel};
 use datafusion_sql::unparser::dialect::{
-    DefaultDialect as UnparserDefaultDialect, Dialect as UnparserDialect,
-    MySqlDialect as UnparserMySqlDialect, SqliteDialect,
+    CustomDialect, CustomDialectBuilder, DefaultDialect as UnparserDefaultDialect,
+    Dialect as UnparserDialect, MySqlDialect as UnparserMySqlDialect, SqliteDialect,
 };
 use datafusion_sql::unparser::{expr_to_sql, plan_to_sql, Unparser};
 
@@ -100,6 +102,8 @@ fn roundtrip_statement() -> Result<()> {
             "select ta.j1_id from j1 ta where ta.j1_id > 1;",
             "select ta.j1_id, tb.j2_string from j1 ta join j2 tb on (ta.j1_id = tb.j2_id);",
             "select ta.j1_id, tb.j2_string, tc.j3_string from j1 ta join j2 tb on (ta.j1_id = tb.j2_id) join j3 tc on (ta.j1_id = tc.j3_id);",
+            "select ta.j1_id, tb.j2_string from j1 ta asof join j2 tb match_condition (ta.j1_id >= tb.j2_id) on (ta.j1_string = tb.j2_string);",
+            "select ta.j1_id, tb.j2_string from j1 ta asof join j2 tb match_condition (ta.j1_id < tb.j2_id);",
             "select * from (select id, first_name from person)",
             "select * from (select id, first_name from (select * from person))",
             "select id, count(*) as cnt from (select id from person) group by id",
@@ -217,6 +221,43 @@ fn roundtrip_statement() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn roundtrip_pivot_unpivot_statement() -> Result<()> {
+    let tests: Vec<&str> = vec![
+            "select * from j1 pivot(max(j1_id) for j1_string in ('a', 'b'))",
+            "select * from j1 pivot(max(j1_id) as m, count(*) as c for j1_string in ('a', 'b' as bb)) as p",
+            "select * from person pivot(sum(age) for first_name in ('x', 'y') default on null (0))",
+            "select * from person unpivot(n for first_or_last in (first_name, last_name)) as u",
+            "select * from (select j1_id as a, j1_id as b, j1_id as c, j1_string from j1) unpivot(v for n in (a, b, c))",
+    ];
+
+    let unparser_dialect = pivot_unparser_dialect();
+    let unparser = Unparser::new(&unparser_dialect);
+    for query in tests {
+        let dialect = GenericDialect {};
+        let statement = Parser::new(&dialect)
+            .try_with_sql(query)?
+            .parse_statement()?;
+        let state = MockSessionState::default()
+            .with_aggregate_function(sum_udaf())
+            .with_aggregate_function(count_udaf())
+            .with_aggregate_function(max_udaf())
+            .with_expr_planner(Arc::new(CoreFunctionPlanner::default()));
+        let context = MockContextProvider { state };
+        let sql_to_rel = SqlToRel::new(&context);
+        let plan = sql_to_rel.sql_statement_to_plan(statement).unwrap();
+
+        let roundtrip_statement = unparser.plan_to_sql(&plan)?;
+        let plan_roundtrip = sql_to_rel
+            .sql_statement_to_plan(roundtrip_statement.clone())
+            .unwrap();
+
+        assert_eq!(plan, plan_roundtrip);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn roundtrip_crossjoin() -> Result<()> {
     let query = "select j1.j1_id, j2.j2_string from j1, j2";
@@ -699,6 +740,17 @@ fn test_pretty_roundtrip() -> Result<()> {
 fn sql_round_trip<D>(dialect: D, query: &str, expect: &str)
 where
     D: Dialect,
+{
+    sql_round_trip_with_unparser(dialect, &Unparser::default(), query, expect)
+}
+
+fn sql_round_trip_with_unparser<D>(
+    dialect: D,
+    unparser: &Unparser,
+    query: &str,
+    expect: &str,
+) where
+    D: Dialect,
 {
     let statement = Parser::new(&dialect)
         .try_with_sql(query)
@@ -711,6 +763,8 @@ where
             .with_aggregate_function(sum_udaf())
             .with_aggregate_function(max_udaf())
             .with_aggregate_function(grouping_udaf())
+            .with_aggregate_function(percentile_cont_udaf())
+            .with_aggregate_function(mode_udaf())
             .with_window_function(rank_udwf())
             .with_scalar_function(Arc::new(unicode::substr().as_ref().clone()))
             .with_scalar_function(make_array_udf()),
@@ -718,10 +772,17 @@ where
     let sql_to_rel = SqlToRel::new(&context);
     let plan = sql_to_rel.sql_statement_to_plan(statement).unwrap();
 
-    let roundtrip_statement = plan_to_sql(&plan).unwrap();
+    let roundtrip_statement = unparser.plan_to_sql(&plan).unwrap();
     assert_eq!(roundtrip_statement.to_string(), expect);
 }
 
+/// An unparser dialect that supports the PIVOT and UNPIVOT table factors
+fn pivot_unparser_dialect() -> CustomDialect {
+    CustomDialectBuilder::new()
+        .with_supports_pivot_unpivot(true)
+        .build()
+}
+
 #[test]
 fn test_table_scan_alias() -> Result<()> {
     let schema = Schema::new(vec![
@@ -1188,3 +1249,134 @@ fn test_unnest_to_sql() {
         r#"SELECT UNNEST(make_array(1, 2, 2, 5, NULL)) AS u1"#,
     );
 }
+
+#[test]
+fn test_pivot_to_sql() {
+    let dialect = pivot_unparser_dialect();
+    let unparser = Unparser::new(&dialect);
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select * from j1 pivot(max(j1_id) for j1_string in ('a', 'b'))",
+        "SELECT * FROM j1 PIVOT(max(j1.j1_id) FOR j1.j1_string IN ('a', 'b'))",
+    );
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select * from j1 pivot(max(j1_id) for j1_string in ('a' as x) default on null (0)) as p",
+        "SELECT * FROM j1 PIVOT(max(j1.j1_id) FOR j1.j1_string IN ('a' AS x) DEFAULT ON NULL (0)) AS p",
+    );
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select * from j1 pivot(max(j1_id) as m, sum(j1_id) as s for j1_string in ('a', 'b' as bb))",
+        "SELECT * FROM j1 PIVOT(max(j1.j1_id) AS m, sum(j1.j1_id) AS s FOR j1.j1_string IN ('a', 'b' AS bb))",
+    );
+}
+
+#[test]
+fn test_unpivot_to_sql() {
+    let dialect = pivot_unparser_dialect();
+    let unparser = Unparser::new(&dialect);
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select * from (select j1_id as a, j1_id as b, j1_string from j1) unpivot(v for n in (a, b)) as u",
+        "SELECT * FROM (SELECT j1.j1_id AS a, j1.j1_id AS b, j1.j1_string FROM j1) UNPIVOT(v FOR n IN (a, b)) AS u",
+    );
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select * from (select j1_id as a, j1_id as b, j1_id as c, j1_string from j1) unpivot(v for n in (a, b, c))",
+        "SELECT * FROM (SELECT j1.j1_id AS a, j1.j1_id AS b, j1.j1_id AS c, j1.j1_string FROM j1) UNPIVOT(v FOR n IN (a, b, c))",
+    );
+}
+
+#[test]
+fn test_pivot_unpivot_shaped_queries_to_sql() -> Result<()> {
+    let dialect = pivot_unparser_dialect();
+    let pivot_unparser = Unparser::new(&dialect);
+    for unparser in [&Unparser::default(), &pivot_unparser] {
+        // a projection of one column, as an UNPIVOT of a single column is planned
+        sql_round_trip_with_unparser(
+            GenericDialect {},
+            unparser,
+            "SELECT j1_string, 'j1_id' AS n, j1_id AS v FROM j1 WHERE j1_id IS NOT NULL",
+            "SELECT j1.j1_string, 'j1_id' AS n, j1.j1_id AS v FROM j1 WHERE j1.j1_id IS NOT NULL",
+        );
+        sql_round_trip_with_unparser(
+            GenericDialect {},
+            unparser,
+            "SELECT j1_string, sum(CASE WHEN j1_string = 'a' THEN j1_id END) AS a FROM j1 GROUP BY j1_string",
+            "SELECT j1.j1_string, sum(CASE WHEN (j1.j1_string = 'a') THEN j1.j1_id END) AS a FROM j1 GROUP BY j1.j1_string",
+        );
+    }
+
+    // Dialects without PIVOT and UNPIVOT get the aggregation and union they
+    // are planned as
+    let queries = [
+        "select * from j1 pivot(max(j1_id) for j1_string in ('a', 'b'))",
+        "select * from (select j1_id as a, j1_id as b, j1_string from j1) unpivot(v for n in (a, b))",
+    ];
+    for query in queries {
+        let statement = Parser::new(&GenericDialect {})
+            .try_with_sql(query)?
+            .parse_statement()?;
+        let context = MockContextProvider {
+            state: MockSessionState::default().with_aggregate_function(max_udaf()),
+        };
+        let plan = SqlToRel::new(&context).sql_statement_to_plan(statement)?;
+        let sql = plan_to_sql(&plan)?.to_string();
+        assert!(!sql.contains("PIVOT"), "{sql}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_within_group_to_sql() {
+    sql_round_trip(
+        GenericDialect {},
+        "select percentile_cont(0.5) within group (order by age desc) from person group by first_name",
+        "SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY person.age DESC NULLS FIRST) FROM person GROUP BY person.first_name",
+    );
+    sql_round_trip(
+        GenericDialect {},
+        "select mode() within group (order by first_name) from person",
+        "SELECT mode() WITHIN GROUP (ORDER BY person.first_name ASC NULLS LAST) FROM person",
+    );
+}
+
+#[test]
+fn test_qualify_to_sql() {
+    let dialect = CustomDialectBuilder::new()
+        .with_supports_qualify(true)
+        .build();
+    let unparser = Unparser::new(&dialect);
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select j1_id, max(j1_id) over (partition by j1_string) as m from j1 qualify m > 1",
+        "SELECT j1.j1_id, max(j1.j1_id) OVER (PARTITION BY j1.j1_string ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS m FROM j1 QUALIFY (max(j1.j1_id) OVER (PARTITION BY j1.j1_string ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) > 1)",
+    );
+    sql_round_trip_with_unparser(
+        GenericDialect {},
+        &unparser,
+        "select j1_string from j1 group by j1_string qualify rank() over (order by sum(j1_id) desc) = 1",
+        "SELECT j1.j1_string FROM j1 GROUP BY j1.j1_string QUALIFY (rank() OVER (ORDER BY sum(j1.j1_id) DESC NULLS FIRST RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) = 1)",
+    );
+}
+
+#[test]
+fn test_qualify_to_sql_without_dialect_support() {
+    sql_round_trip(
+        GenericDialect {},
+        "select j1_id, max(j1_id) over (partition by j1_string) as m from j1 qualify m > 1",
+        r#"SELECT derived_qualify.j1_id, derived_qualify."max(j1.j1_id) PARTITION BY [j1.j1_string] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING" AS m FROM (SELECT j1.j1_id, j1.j1_string, max(j1.j1_id) OVER (PARTITION BY j1.j1_string ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS "max(j1.j1_id) PARTITION BY [j1.j1_string] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING" FROM j1) AS derived_qualify WHERE (derived_qualify."max(j1.j1_id) PARTITION BY [j1.j1_string] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING" > 1)"#,
+    );
+    sql_round_trip(
+        GenericDialect {},
+        "select j1_string from j1 group by j1_string qualify rank() over (order by sum(j1_id) desc) = 1",
+        r#"SELECT derived_qualify.j1_string FROM (SELECT j1.j1_string, sum(j1.j1_id) AS "sum(j1.j1_id)", rank() OVER (ORDER BY sum(j1.j1_id) DESC NULLS FIRST RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS "rank() ORDER BY [sum(j1.j1_id) DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW" FROM j1 GROUP BY j1.j1_string) AS derived_qualify WHERE (derived_qualify."rank() ORDER BY [sum(j1.j1_id) DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW" = 1)"#,
+    );
+}
@@ -32,8 +32,8 @@ use datafusion_expr::{
     dml::CopyTo,
     logical_plan::{LogicalPlan, Prepare},
     test::function_stub::sum_udaf,
-    ColumnarValue, CreateExternalTable, CreateIndex, DdlStatement, ScalarUDF,
-    ScalarUDFImpl, Signature, Volatility,
+    ColumnarValue, CreateExternalTable, CreateIndex, CreateMaterializedView,
+    DdlStatement, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
 };
 use datafusion_functions::{string, unicode};
 use datafusion_sql::{
@@ -621,6 +621,35 @@ Dml: op=[Delete] table=[person]
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_merge() {
+    let sql = "merge into j1 t using j2 on t.j1_id = j2.j2_id \
+        when matched and j2.j2_id > 10 then delete \
+        when matched then update set j1_string = j2.j2_string \
+        when not matched then insert values (j2.j2_id, 'new') \
+        when not matched by source then update set j1_id = t.j1_id + 1";
+    let plan = r#"
+Dml: op=[Merge] table=[j1] on=[t.j1_id = j2.j2_id] clauses=[WHEN MATCHED AND j2.j2_id > Int64(10) THEN DELETE, WHEN MATCHED THEN UPDATE SET j1_string = j2.j2_string, WHEN NOT MATCHED BY TARGET THEN INSERT VALUES (j2.j2_id, Utf8("new")), WHEN NOT MATCHED BY SOURCE THEN UPDATE SET j1_id = CAST(t.j1_id + Int64(1) AS Int32)]
+  TableScan: j2
+    "#
+    .trim();
+    quick_test(sql, plan);
+}
+
+#[rstest]
+#[case::missing_assignment_target(
+    "MERGE INTO j1 USING j2 ON j1_id = j2_id WHEN MATCHED THEN UPDATE SET doesnotexist = 1"
+)]
+#[case::missing_insert_target(
+    "MERGE INTO j1 USING j2 ON j1_id = j2_id WHEN NOT MATCHED THEN INSERT (doesnotexist) VALUES (1)"
+)]
+#[case::missing_on_expression("MERGE INTO j1 USING j2 ON doesnotexist = j2_id")]
+#[test]
+fn merge_column_does_not_exist(#[case] sql: &str) {
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_field_not_found(err, "doesnotexist");
+}
+
 #[test]
 fn select_column_does_not_exist() {
     let sql = "SELECT doesnotexist FROM person";
@@ -2509,6 +2538,50 @@ fn approx_median_window() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn qualify_window_not_in_projection() {
+    let sql = "SELECT order_id, qty FROM orders QUALIFY MAX(qty) OVER (PARTITION BY order_id) = qty";
+    let expected = "\
+        Projection: orders.order_id, orders.qty\
+        \n  Filter: max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = orders.qty\
+        \n    WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_window_alias() {
+    let sql = "SELECT order_id, MAX(qty) OVER (PARTITION BY order_id) AS max_qty FROM orders QUALIFY max_qty > 10";
+    let expected = "\
+        Projection: orders.order_id, max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS max_qty\
+        \n  Filter: max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING > Int64(10)\
+        \n    WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_with_aggregate() {
+    let sql = "SELECT order_id, SUM(qty) FROM orders GROUP BY order_id QUALIFY MAX(SUM(qty)) OVER () = SUM(qty)";
+    let expected = "\
+        Projection: orders.order_id, sum(orders.qty)\
+        \n  Filter: max(sum(orders.qty)) ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = sum(orders.qty)\
+        \n    WindowAggr: windowExpr=[[max(sum(orders.qty)) ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      Aggregate: groupBy=[[orders.order_id]], aggr=[[sum(orders.qty)]]\
+        \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_without_window() {
+    let sql = "SELECT order_id FROM orders QUALIFY qty > 1";
+    let err = logical_plan(sql).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: QUALIFY clause requires a window function in the SELECT list or the QUALIFY clause"
+    );
+}
+
 #[test]
 fn select_typed_date_string() {
     let sql = "SELECT date '2020-12-10' AS date";
@@ -3546,10 +3619,6 @@ fn test_select_distinct_order_by() {
     "SELECT id, number FROM person LATERAL VIEW explode(numbers) exploded_table AS number",
     "This feature is not implemented: LATERAL VIEWS"
 )]
-#[case::select_qualify_unsupported(
-    "SELECT i, p, o FROM person QUALIFY ROW_NUMBER() OVER (PARTITION BY p ORDER BY o) = 1",
-    "This feature is not implemented: QUALIFY"
-)]
 #[case::select_top_unsupported(
     "SELECT TOP (5) * FROM person",
     "This feature is not implemented: TOP"
@@ -4456,6 +4525,52 @@ fn plan_create_index() {
     }
 }
 
+#[test]
+fn plan_create_materialized_view() {
+    let sql = "CREATE MATERIALIZED VIEW IF NOT EXISTS v \
+        WITH (location = '/tmp/v/', format = 'csv') AS SELECT id FROM person";
+    let plan = logical_plan(sql).unwrap();
+    match plan {
+        LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+            CreateMaterializedView {
+                name,
+                if_not_exists,
+                location,
+                file_type,
+                ..
+            },
+        )) => {
+            assert_eq!(format!("{name}"), "v");
+            assert!(if_not_exists);
+            assert_eq!(location, Some("/tmp/v/".to_string()));
+            assert_eq!(file_type, "CSV");
+        }
+        _ => panic!("wrong plan type"),
+    }
+
+    let expected = "CreateMaterializedView: Bare { table: \"v\" }\
+        \n  Projection: person.id\
+        \n    TableScan: person";
+    quick_test(
+        "CREATE MATERIALIZED VIEW v AS SELECT id FROM person",
+        expected,
+    );
+
+    let sql = "CREATE MATERIALIZED VIEW v WITH (format = 'csv') AS SELECT 1";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "The format of a materialized view requires a location"
+    );
+
+    let sql = "CREATE MATERIALIZED VIEW v WITH (compression = 'gzip') AS SELECT 1";
+    let err = logical_plan(sql).unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Unknown materialized view option 'compression'"
+    );
+}
+
 fn assert_field_not_found(err: DataFusionError, name: &str) {
     match err {
         DataFusionError::SchemaError { .. } => {
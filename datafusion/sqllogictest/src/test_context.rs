@@ -98,6 +98,18 @@ impl TestContext {
                     return None;
                 }
             }
+            "orc.slt" => {
+                #[cfg(feature = "orc")]
+                {
+                    info!("Writing orc files");
+                    write_orc_files();
+                }
+                #[cfg(not(feature = "orc"))]
+                {
+                    info!("Skipping {file_name} because orc feature is not enabled");
+                    return None;
+                }
+            }
             "dynamic_file.slt" => {
                 test_ctx.ctx = test_ctx.ctx.enable_url_table();
             }
@@ -170,6 +182,38 @@ pub async fn register_avro_tables(ctx: &mut TestContext) {
         .unwrap();
 }
 
+/// Writes the ORC files read by `orc.slt` to `test_files/scratch/orc_files`.
+///
+/// There is no ORC writer in DataFusion to create them from the test itself,
+/// and they are written next to the scratch directory of `orc.slt`, which is
+/// cleared before the test runs.
+#[cfg(feature = "orc")]
+pub fn write_orc_files() {
+    use orc_rust::ArrowWriterBuilder;
+
+    let table_path = Path::new("test_files").join("scratch").join("orc_files");
+    if table_path.exists() {
+        std::fs::remove_dir_all(&table_path).expect("failed to clear orc table path");
+    }
+    std::fs::create_dir_all(&table_path).expect("failed to create orc table path");
+
+    for (file_idx, ids) in [vec![1, 2, 3], vec![4, 5]].into_iter().enumerate() {
+        let names = ids.iter().map(|id| format!("name{id}")).collect::<Vec<_>>();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(ids)) as ArrayRef),
+            ("name", Arc::new(StringArray::from(names)) as ArrayRef),
+        ])
+        .unwrap();
+
+        let file = File::create(table_path.join(format!("{file_idx}.orc"))).unwrap();
+        let mut writer = ArrowWriterBuilder::new(file, batch.schema())
+            .try_build()
+            .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+}
+
 /// Generate a partitioned CSV file and register it with an execution context
 pub async fn register_partition_table(test_ctx: &mut TestContext) {
     test_ctx.enable_testdir();
@@ -409,7 +409,7 @@ pub fn to_substrait_rel(
         LogicalPlan::Join(join) => {
             let left = to_substrait_rel(join.left.as_ref(), ctx, extensions)?;
             let right = to_substrait_rel(join.right.as_ref(), ctx, extensions)?;
-            let join_type = to_substrait_jointype(join.join_type);
+            let join_type = to_substrait_jointype(join.join_type)?;
             // we only support basic joins so return an error for anything not yet supported
             match join.join_constraint {
                 JoinConstraint::On => {}
@@ -717,8 +717,8 @@ fn to_substrait_join_expr(
     Ok(join_expr)
 }
 
-fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
-    match join_type {
+fn to_substrait_jointype(join_type: JoinType) -> Result<join_rel::JoinType> {
+    Ok(match join_type {
         JoinType::Inner => join_rel::JoinType::Inner,
         JoinType::Left => join_rel::JoinType::Left,
         JoinType::Right => join_rel::JoinType::Right,
@@ -729,7 +729,8 @@ fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
         JoinType::RightAnti | JoinType::RightSemi => {
             unimplemented!()
         }
-    }
+        JoinType::AsOf => return not_impl_err!("Unsupported join type: {join_type}"),
+    })
 }
 
 pub fn operator_to_name(op: Operator) -> &'static str {